@@ -0,0 +1,150 @@
+"""Tests pour Argon2 (RFC 9106)"""
+import random
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Chaînes produites par l'implémentation de référence (et argon2-cffi) pour
+# "password" et "somesalt", 2 passes sur 64 Mio en une voie
+REFERENCE = [
+    ("i", "c1628832147d9720c5bd1cfd61367078729f6dfb6f8fea9ff98158e0d7816ed0",
+     "$argon2i$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$wWKIMhR9lyDFvRz9YTZweHKfbftvj+qf+YFY4NeBbtA"),
+    ("id", "09316115d5cf24ed5a15a31a3ba326e5cf32edc24702987c02b6566f61913cf7",
+     "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMaO6Mm5c8y7cJHAph8ArZWb2GRPPc"),
+]
+
+# Hachage de la version 1.0 (sans champ v=), toujours accepté par argon2_verify
+ARGON2I_V10 = "$argon2i$m=65536,t=2,p=1$c29tZXNhbHQ$9sTbSlTio3Biev89thdrlKKiCaYsjjYVJxGAL3swxpQ"
+
+SMALL = dict(time_cost=1, memory_cost=64, parallelism=2)
+
+
+@pytest.mark.parametrize("kind,expected,phc", REFERENCE)
+def test_argon2_reference(kind, expected, phc):
+    """Test les vecteurs de l'implémentation de référence, bruts et en PHC"""
+    kwargs = dict(time_cost=2, memory_cost=65536, parallelism=1, type=kind)
+    assert RsHash.argon2_hash(b"password", b"somesalt", **kwargs).hex() == expected
+    assert RsHash.argon2_phc(b"password", b"somesalt", **kwargs) == phc
+    assert RsHash.argon2_verify(phc, b"password")
+    assert not RsHash.argon2_verify(phc, b"Password")
+
+
+def test_argon2_verify_version_10():
+    """Test la vérification d'un hachage de la version 1.0"""
+    assert RsHash.argon2_verify(ARGON2I_V10, b"password")
+    assert not RsHash.argon2_verify(ARGON2I_V10, b"passwore")
+
+
+def test_argon2_variants_differ():
+    """Test que les trois variantes et les noms longs sont acceptés"""
+    tags = {kind: RsHash.argon2_hash(b"pw", b"saltsalt", type=kind, **SMALL)
+            for kind in ("d", "i", "id")}
+    assert len(set(tags.values())) == 3
+    assert RsHash.argon2_hash(b"pw", b"saltsalt", type="argon2d", **SMALL) == tags["d"]
+
+
+def test_argon2_phc_defaults():
+    """Test les paramètres par défaut et le sel aléatoire de argon2_phc"""
+    first = RsHash.argon2_phc(b"secret", time_cost=1, memory_cost=256)
+    second = RsHash.argon2_phc(b"secret", time_cost=1, memory_cost=256)
+    assert first.startswith("$argon2id$v=19$m=256,t=1,p=4$")
+    assert first != second
+    assert RsHash.argon2_verify(first, b"secret")
+    assert RsHash.argon2_verify(second, bytearray(b"secret"))
+    assert not RsHash.argon2_verify(first, b"secret ")
+    assert len(RsHash.argon2_hash(b"secret", b"saltsalt", **SMALL)) == 32
+
+
+def test_argon2_hash_lengths():
+    """Test les longueurs de sortie courtes et longues (H' au-delà de 64 octets)"""
+    for hash_len in (4, 16, 64, 65, 100, 512):
+        tag = RsHash.argon2_hash(b"pw", b"saltsalt", hash_len=hash_len, **SMALL)
+        assert len(tag) == hash_len
+        phc = RsHash.argon2_phc(b"pw", b"saltsalt", hash_len=hash_len, **SMALL)
+        assert RsHash.argon2_verify(phc, b"pw")
+
+
+def test_argon2_parameters_change_output():
+    """Test que chaque paramètre change le hachage"""
+    base = dict(time_cost=2, memory_cost=64, parallelism=2, hash_len=32, type="id")
+    reference = RsHash.argon2_hash(b"pw", b"saltsalt", **base)
+    for name, value in [("time_cost", 3), ("memory_cost", 128), ("parallelism", 1),
+                        ("hash_len", 31), ("type", "i")]:
+        tag = RsHash.argon2_hash(b"pw", b"saltsalt", **dict(base, **{name: value}))
+        assert tag[:31] != reference[:31], name
+    assert RsHash.argon2_hash(b"pw", b"saltsalu", **base) != reference
+
+
+def test_argon2_threads():
+    """Test des appels concurrents, les voies étant calculées sans le GIL"""
+    expected = RsHash.argon2_hash(b"pw", b"saltsalt", memory_cost=4096, parallelism=4)
+    results = []
+    threads = [
+        threading.Thread(target=lambda: results.append(
+            RsHash.argon2_hash(b"pw", b"saltsalt", memory_cost=4096, parallelism=4)))
+        for _ in range(4)
+    ]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert results == [expected] * 4
+
+
+def test_argon2_matches_argon2_cffi():
+    """Test différentiel contre argon2-cffi, s'il est installé"""
+    low_level = pytest.importorskip("argon2.low_level")
+    types = {"d": low_level.Type.D, "i": low_level.Type.I, "id": low_level.Type.ID}
+    rng = random.Random(9106)
+    for _ in range(10):
+        password = rng.randbytes(rng.randrange(0, 40))
+        salt = rng.randbytes(rng.randrange(8, 32))
+        kind = rng.choice(list(types))
+        kwargs = dict(time_cost=rng.randrange(1, 4), memory_cost=rng.randrange(32, 512),
+                      parallelism=rng.randrange(1, 4), hash_len=rng.randrange(4, 80))
+        expected = low_level.hash_secret(password, salt, type=types[kind], **kwargs).decode()
+        assert RsHash.argon2_phc(password, salt, type=kind, **kwargs) == expected
+        assert RsHash.argon2_verify(expected, password)
+
+
+def test_argon2_matches_cryptography():
+    """Test différentiel d'Argon2id contre cryptography, s'il est installé"""
+    argon2 = pytest.importorskip("cryptography.hazmat.primitives.kdf.argon2")
+    rng = random.Random(9106)
+    for _ in range(10):
+        password = rng.randbytes(rng.randrange(0, 40))
+        salt = rng.randbytes(rng.randrange(8, 32))
+        time_cost, parallelism = rng.randrange(1, 4), rng.randrange(1, 5)
+        memory_cost, hash_len = rng.randrange(8 * parallelism, 512), rng.randrange(4, 80)
+        kdf = argon2.Argon2id(salt=salt, length=hash_len, iterations=time_cost,
+                              lanes=parallelism, memory_cost=memory_cost)
+        assert RsHash.argon2_hash(password, salt, time_cost=time_cost, memory_cost=memory_cost,
+                                  parallelism=parallelism, hash_len=hash_len,
+                                  type="id") == kdf.derive(password)
+
+
+def test_argon2_argument_errors():
+    """Test les paramètres hors limites et les chaînes PHC invalides"""
+    for kwargs in [dict(time_cost=0), dict(parallelism=0), dict(memory_cost=15, parallelism=2),
+                   dict(hash_len=3), dict(type="x")]:
+        with pytest.raises(ValueError):
+            RsHash.argon2_hash(b"pw", b"saltsalt", **dict(SMALL, **kwargs))
+    with pytest.raises(ValueError, match="salt"):
+        RsHash.argon2_hash(b"pw", b"short", **SMALL)
+    # Longueur de tag au-delà de 2^32 - 1 (RFC 9106), refusée avant toute allocation
+    for hash_len in (2**32, 2**62):
+        with pytest.raises(ValueError, match="hash_len"):
+            RsHash.argon2_hash(b"pw", b"saltsalt", **dict(SMALL, hash_len=hash_len))
+        with pytest.raises(ValueError, match="hash_len"):
+            RsHash.argon2_phc(b"pw", b"saltsalt", **dict(SMALL, hash_len=hash_len))
+    with pytest.raises(TypeError):
+        RsHash.argon2_hash("pw", b"saltsalt", **SMALL)
+    with pytest.raises(TypeError):
+        RsHash.argon2_hash(b"pw", b"saltsalt", 3)
+    phc = REFERENCE[1][2]
+    for bad in ["", phc.replace("argon2id", "argon2x"), phc.replace("v=19", "v=20"),
+                phc.replace("m=65536,t=2", "t=2,m=65536"), phc + "=", phc + "$"]:
+        with pytest.raises(ValueError):
+            RsHash.argon2_verify(bad, b"password")
@@ -0,0 +1,69 @@
+"""Tests pour Ascon-Hash256 et Ascon-XOF128 (NIST SP 800-232)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+HASH256_EMPTY = "0b3be5850f2f6b98caf29f8fdea89b64a1fa70aa249b8f839bd53baa304d92b2"
+XOF128_EMPTY = "473d5e6164f58b39dfd84aacdb8ae42ec2d91fed33388ee0d960d9b3993295c6"
+
+
+def test_ascon_hash256_kat():
+    """Test le vecteur KAT du message vide"""
+    assert RsHash.AsconHash256().hexdigest() == HASH256_EMPTY
+    assert RsHash.AsconHash256(b"").digest() == bytes.fromhex(HASH256_EMPTY)
+
+
+def test_ascon_xof128_kat():
+    """Test le vecteur KAT du message vide et les préfixes de sortie"""
+    h = RsHash.AsconXOF128()
+    assert h.hexdigest(32) == XOF128_EMPTY
+    assert h.hexdigest(5) == XOF128_EMPTY[:10]
+    assert h.digest(100)[:32] == bytes.fromhex(XOF128_EMPTY)
+    assert h.digest(0) == b""
+
+
+def test_ascon_streaming():
+    """Test l'absorption incrémentale autour du débit de 8 octets"""
+    data = bytes(range(256))
+    for step in (1, 3, 7, 8, 9, 64):
+        h = RsHash.AsconHash256()
+        x = RsHash.AsconXOF128()
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+            x.update(data[start:start + step])
+        assert h.digest() == RsHash.AsconHash256(data).digest()
+        assert x.digest(48) == RsHash.AsconXOF128(data).digest(48)
+
+
+def test_ascon_copy():
+    """Test que copy() capture l'état en cours d'absorption"""
+    h = RsHash.AsconHash256(b"abcde")
+    c = h.copy()
+    c.update(b"fgh")
+    assert c.digest() == RsHash.AsconHash256(b"abcdefgh").digest()
+    assert h.digest() == RsHash.AsconHash256(b"abcde").digest()
+
+
+def test_ascon_properties_and_new():
+    """Test les propriétés et new("ascon_hash256") / new("ascon_xof128")"""
+    h = RsHash.new("Ascon-Hash256", b"abc")
+    assert type(h) is RsHash.AsconHash256
+    assert h.name == "ascon_hash256"
+    assert h.digest_size == 32
+    assert h.block_size == 8
+    x = RsHash.new("ascon_xof128", b"abc")
+    assert type(x) is RsHash.AsconXOF128
+    assert x.name == "ascon_xof128"
+    assert x.digest_size == 0
+    assert x.digest(32) != h.digest()
+    assert {"ascon_hash256", "ascon_xof128"} <= RsHash.algorithms_available
+
+
+def test_ascon_fips_mode():
+    """Test qu'Ascon (SP 800-232) reste utilisable en mode FIPS"""
+    RsHash.set_fips_mode(True)
+    try:
+        assert RsHash.new("ascon_hash256").hexdigest() == HASH256_EMPTY
+        assert RsHash.AsconXOF128().hexdigest(32) == XOF128_EMPTY
+    finally:
+        RsHash.set_fips_mode(False)
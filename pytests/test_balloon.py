@@ -0,0 +1,148 @@
+"""Tests pour Balloon et Balloon-M"""
+import hashlib
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Vecteurs de l'implémentation d'exemple des auteurs (SHA-256, delta = 3)
+VECTORS = [
+    (b"hunter42", b"examplesalt", 1024, 3, 1,
+     "716043dff777b44aa7b88dcbab12c078abecfac9d289c5b5195967aa63440dfb"),
+    (b"", b"salt", 3, 3, 1,
+     "5f02f8206f9cd212485c6bdf85527b698956701ad0852106f94b94ee94577378"),
+    (b"password", b"", 3, 3, 1,
+     "20aa99d7fe3f4df4bd98c655c5480ec98b143107a331fd491deda885c4d6a6cc"),
+    (b"\0", b"\0", 3, 3, 1,
+     "4fc7e302ffa29ae0eac31166cee7a552d1d71135f4e0da66486fb68a749b73a4"),
+    (b"password", b"salt", 1, 1, 1,
+     "eefda4a8a75b461fa389c1dcfaf3e9dfacbc26f81f22e6f280d15cc18c417545"),
+    (b"hunter42", b"examplesalt", 1024, 3, 4,
+     "1832bd8e5cbeba1cb174a13838095e7e66508e9bf04c40178990adbc8ba9eb6f"),
+    (b"", b"salt", 3, 3, 2,
+     "f8767fe04059cef67b4427cda99bf8bcdd983959dbd399a5e63ea04523716c23"),
+    (b"password", b"", 3, 3, 3,
+     "bcad257eff3d1090b50276514857e60db5d0ec484129013ef3c88f7d36e438d6"),
+    (b"\0", b"\0", 3, 3, 4,
+     "8a665611e40710ba1fd78c181549c750f17c12e423c11930ce997f04c7153e0c"),
+    (b"password", b"salt", 1, 1, 16,
+     "a67b383bb88a282aef595d98697f90820adf64582a4b3627c76b7da3d8bae915"),
+]
+
+
+def reference_single(name, password, salt, space_cost, time_cost):
+    """Implémentation de référence d'une instance Balloon"""
+    cnt = 0
+
+    def h(*parts):
+        return hashlib.new(name, b"".join(parts)).digest()
+
+    def counted(*parts):
+        nonlocal cnt
+        out = h(cnt.to_bytes(8, "little"), *parts)
+        cnt += 1
+        return out
+
+    buf = [counted(password, salt)]
+    for m in range(1, space_cost):
+        buf.append(counted(buf[m - 1]))
+    for t in range(time_cost):
+        for m in range(space_cost):
+            buf[m] = counted(buf[m - 1], buf[m])
+            for i in range(3):
+                ints = b"".join(x.to_bytes(8, "little") for x in (t, m, i))
+                other = int.from_bytes(counted(salt, h(ints)), "little") % space_cost
+                buf[m] = counted(buf[m], buf[other])
+    return buf[-1]
+
+
+def reference(name, password, salt, space_cost, time_cost, parallelism):
+    """Implémentation de référence de Balloon et Balloon-M"""
+    if parallelism == 1:
+        return reference_single(name, password, salt, space_cost, time_cost)
+    combined = 0
+    for m in range(1, parallelism + 1):
+        out = reference_single(name, password, salt + m.to_bytes(8, "little"),
+                               space_cost, time_cost)
+        combined ^= int.from_bytes(out, "big")
+    size = hashlib.new(name).digest_size
+    return hashlib.new(name, password + salt + combined.to_bytes(size, "big")).digest()
+
+
+@pytest.mark.parametrize("password,salt,s_cost,t_cost,p,expected", VECTORS)
+def test_balloon_vectors(password, salt, s_cost, t_cost, p, expected):
+    """Test les vecteurs de l'implémentation d'exemple"""
+    out = RsHash.balloon(password, salt, space_cost=s_cost, time_cost=t_cost, parallelism=p)
+    assert out.hex() == expected
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512", "sha3_256", "blake2b"])
+@pytest.mark.parametrize("p", [1, 3])
+def test_balloon_matches_reference(name, p):
+    """Test différentiel contre l'implémentation de référence en Python"""
+    size = hashlib.new(name).digest_size
+    expected = reference(name, b"pw", b"salt", 16, 2, p)
+    out = RsHash.balloon(b"pw", b"salt", space_cost=16, time_cost=2, parallelism=p,
+                         hash=name, length=size)
+    assert out == expected
+
+
+def test_balloon_parameters_change_output():
+    """Test que modifier un paramètre change la sortie"""
+    base = dict(space_cost=8, time_cost=2, parallelism=1, hash="sha256")
+    reference_out = RsHash.balloon(b"password", b"salt", **base)
+    variants = [
+        RsHash.balloon(b"passwore", b"salt", **base),
+        RsHash.balloon(b"password", b"salu", **base),
+        RsHash.balloon(b"password", b"salt", **{**base, "space_cost": 9}),
+        RsHash.balloon(b"password", b"salt", **{**base, "time_cost": 3}),
+        RsHash.balloon(b"password", b"salt", **{**base, "parallelism": 2}),
+        RsHash.balloon(b"password", b"salt", **{**base, "hash": "sha3_256"}),
+    ]
+    assert all(len(out) == 32 for out in variants)
+    assert len({reference_out, *variants}) == len(variants) + 1
+
+
+def test_balloon_hash_and_length():
+    """Test le choix du hachage par nom ou par classe et la troncature"""
+    args = dict(space_cost=4, time_cost=1)
+    full = RsHash.balloon(b"pw", b"salt", hash="sha512", length=64, **args)
+    assert RsHash.balloon(b"pw", b"salt", hash=RsHash.SHA512, length=64, **args) == full
+    assert RsHash.balloon(b"pw", b"salt", hash="sha512", **args) == full[:32]
+    assert RsHash.balloon(b"pw", b"salt", hash="sha512", length=1, **args) == full[:1]
+    assert RsHash.balloon(bytearray(b"pw"), memoryview(b"salt"), **args) == \
+        RsHash.balloon(b"pw", b"salt", hash="sha256", **args)
+
+
+def test_balloon_threads():
+    """Test des appels concurrents, le calcul se faisant sans le GIL"""
+    expected = VECTORS[0][5]
+    results = []
+    threads = [threading.Thread(target=lambda: results.append(RsHash.balloon(
+        b"hunter42", b"examplesalt", space_cost=1024, time_cost=3).hex())) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert results == [expected] * 4
+
+
+def test_balloon_argument_errors():
+    """Test les coûts nuls, les longueurs invalides et les hachages refusés"""
+    for s_cost, t_cost, p in [(0, 1, 1), (1, 0, 1), (1, 1, 0)]:
+        with pytest.raises(ValueError):
+            RsHash.balloon(b"pw", b"salt", space_cost=s_cost, time_cost=t_cost, parallelism=p)
+    for length in (0, 33):
+        with pytest.raises(ValueError, match="length"):
+            RsHash.balloon(b"pw", b"salt", space_cost=1, time_cost=1, length=length)
+    with pytest.raises(ValueError):
+        RsHash.balloon(b"pw", b"salt", space_cost=1, time_cost=1, hash="shake_128")
+    with pytest.raises(OverflowError):
+        RsHash.balloon(b"pw", b"salt", space_cost=-1, time_cost=1)
+    with pytest.raises(TypeError):
+        RsHash.balloon(b"pw", b"salt")
+    with pytest.raises(TypeError):
+        RsHash.balloon(b"pw", b"salt", 1, 1)
+    with pytest.raises(TypeError):
+        RsHash.balloon("pw", b"salt", space_cost=1, time_cost=1)
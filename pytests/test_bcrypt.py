@@ -0,0 +1,114 @@
+"""Tests pour bcrypt"""
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Vecteurs du paquet bcrypt (PyPI) et de crypt_blowfish d'Openwall
+VECTORS = [
+    (b"Kk4DQuMMfZL9o", "$2b$04$cVWp4XaNU8a4v1uMRum2SO026BWLIoQMD/TXg5uZV.0P.uO8m3YEm"),
+    (b"xVQVbwa1S0M8r", "$2b$04$SQe9knOzepOVKoYXo9xTteNYr6MBwVz4tpriJVe3PNgYufGIsgKcW"),
+    (b"Zfgr26LWd22Za", "$2b$04$eH8zX.q5Q.j2hO1NkVYJQOM6KxntS/ow3.YzVmFrE4t//CoF4fvne"),
+    (b"Tg4daC27epFBE", "$2b$04$ahiTdwRXpUG2JLRcIznxc.s1.ydaPGD372bsGs8NqyYjLY1inG5n2"),
+    (b"U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"),
+    (b"U*U*", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK"),
+    (b"U*U*U", "$2a$05$XXXXXXXXXXXXXXXXXXXXXOAcXxm9kjPGEMsLznoKqmqw7tc8WCx4a"),
+    (b"", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.7uG0VCzI2bS7j6ymqJi9CdcdxiRTWNy"),
+    (b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+     b"chars after 72 are ignored",
+     "$2a$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui"),
+    (b"\xa3", "$2y$05$/OK.fbVrR/bpIqNJ5ianF.Sa7shbm4.OzKpvFnX1pQLmQW96oUlCq"),
+    (b"\xff\xff\xa3", "$2y$05$/OK.fbVrR/bpIqNJ5ianF.CE5elHaaO4EbggVDjb8P19RukzXSM3e"),
+]
+
+# bcrypt utilise son propre alphabet Base64
+ALPHABET = "./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+
+
+def decode_salt(hashed):
+    """Décode les 22 caractères de sel d'un hachage en 16 octets"""
+    bits = "".join(format(ALPHABET.index(c), "06b") for c in hashed[7:29])
+    return int(bits[:128], 2).to_bytes(16, "big")
+
+
+@pytest.mark.parametrize("password,hashed", VECTORS)
+def test_bcrypt_vectors(password, hashed):
+    """Test les vecteurs de référence, en vérification et en hachage"""
+    assert RsHash.bcrypt_verify(password, hashed)
+    assert RsHash.bcrypt_verify(password, hashed.encode())
+    assert not RsHash.bcrypt_verify(b"x" + password, hashed)
+    rounds = int(hashed[4:6])
+    rehashed = RsHash.bcrypt_hash(password, rounds=rounds, salt=decode_salt(hashed))
+    assert rehashed == "$2b$" + hashed[4:]
+
+
+def test_bcrypt_prefix_compatibility():
+    """Test que $2a$, $2b$ et $2y$ donnent le même hachage"""
+    hashed = RsHash.bcrypt_hash(b"password", rounds=4)
+    for prefix in ("$2a$", "$2b$", "$2y$"):
+        assert RsHash.bcrypt_verify(b"password", prefix + hashed[4:])
+    with pytest.raises(ValueError):
+        RsHash.bcrypt_verify(b"password", "$2x$" + hashed[4:])
+
+
+def test_bcrypt_random_salt():
+    """Test le format produit et le sel aléatoire"""
+    first = RsHash.bcrypt_hash(b"secret", rounds=4)
+    second = RsHash.bcrypt_hash(b"secret", rounds=4)
+    assert first.startswith("$2b$04$") and len(first) == 60
+    assert first != second
+    assert RsHash.bcrypt_verify(b"secret", first)
+    assert RsHash.bcrypt_verify(bytearray(b"secret"), second)
+    assert RsHash.bcrypt_hash(b"secret", salt=bytes(16)).startswith("$2b$12$" + "." * 21)
+
+
+def test_bcrypt_72_byte_truncation():
+    """Test que seuls les 72 premiers octets du mot de passe comptent"""
+    hashed = RsHash.bcrypt_hash(b"a" * 72, rounds=4)
+    assert RsHash.bcrypt_verify(b"a" * 100, hashed)
+    assert not RsHash.bcrypt_verify(b"a" * 71, hashed)
+
+
+def test_bcrypt_threads():
+    """Test des appels concurrents, le calcul se faisant sans le GIL"""
+    hashed = RsHash.bcrypt_hash(b"password", rounds=6)
+    results = []
+    threads = [threading.Thread(target=lambda: results.append(
+        RsHash.bcrypt_verify(b"password", hashed))) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert results == [True] * 4
+
+
+def test_bcrypt_matches_bcrypt_package():
+    """Test différentiel contre le paquet bcrypt, s'il est installé"""
+    bcrypt = pytest.importorskip("bcrypt")
+    for password in (b"", b"password", b"\xff" * 10, b"x" * 72):
+        hashed = bcrypt.hashpw(password, bcrypt.gensalt(rounds=4)).decode()
+        assert RsHash.bcrypt_verify(password, hashed)
+        assert RsHash.bcrypt_hash(password, rounds=4, salt=decode_salt(hashed)) == hashed
+        ours = RsHash.bcrypt_hash(password, rounds=4)
+        assert bcrypt.checkpw(password, ours.encode())
+
+
+def test_bcrypt_argument_errors():
+    """Test les coûts hors limites, les sels invalides et les hachages malformés"""
+    for rounds in (3, 32):
+        with pytest.raises(ValueError, match="rounds"):
+            RsHash.bcrypt_hash(b"pw", rounds=rounds)
+    with pytest.raises(ValueError, match="salt"):
+        RsHash.bcrypt_hash(b"pw", rounds=4, salt=bytes(15))
+    with pytest.raises(TypeError):
+        RsHash.bcrypt_hash("pw")
+    with pytest.raises(TypeError):
+        RsHash.bcrypt_hash(b"pw", 4)
+    hashed = VECTORS[0][1]
+    for bad in ["", hashed[:-1], hashed + "A", hashed.replace("$04$", "$4$"),
+                hashed.replace("$04$", "$03$"), "$argon2id$" + hashed[4:], hashed[1:]]:
+        with pytest.raises(ValueError):
+            RsHash.bcrypt_verify(b"pw", bad)
+    with pytest.raises(TypeError):
+        RsHash.bcrypt_verify(b"pw", 12)
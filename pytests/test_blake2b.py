@@ -0,0 +1,86 @@
+"""Tests pour BLAKE2b (RFC 7693), comparé à hashlib.blake2b"""
+import hashlib
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+def test_blake2b_rfc7693_abc():
+    """Test le vecteur de l'annexe A de RFC 7693"""
+    assert RsHash.blake2b(b"abc").hexdigest() == (
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+        "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    )
+
+
+def test_blake2b_keyed_reference_vector():
+    """Test un vecteur de blake2b-kat.txt (clé 00..3f, message 00..fe)"""
+    h = RsHash.blake2b(bytes(range(255)), key=bytes(range(64)))
+    assert h.hexdigest() == (
+        "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248"
+        "4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+    )
+
+
+def test_blake2b_matches_hashlib_random_params():
+    """Test différentiel contre hashlib sur des paramètres aléatoires"""
+    rng = random.Random(7693)
+
+    def random_bytes(n):
+        return bytes(rng.getrandbits(8) for _ in range(n))
+
+    for _ in range(200):
+        kwargs = {
+            "digest_size": rng.randint(1, 64),
+            "key": random_bytes(rng.choice([0, 1, 32, 64])),
+            "salt": random_bytes(rng.randint(0, 16)),
+            "person": random_bytes(rng.randint(0, 16)),
+        }
+        data = random_bytes(rng.choice([0, 1, 127, 128, 129, 1000]))
+        expected = hashlib.blake2b(data, **kwargs)
+        h = RsHash.blake2b(data, **kwargs)
+        assert h.hexdigest() == expected.hexdigest(), kwargs
+        assert h.digest_size == expected.digest_size
+
+
+def test_blake2b_digest_size_is_not_truncation():
+    """Test que digest_size entre dans le bloc de paramètres"""
+    short = RsHash.blake2b(b"abc", digest_size=32).digest()
+    assert len(short) == 32
+    assert short != RsHash.blake2b(b"abc").digest()[:32]
+
+
+def test_blake2b_incremental_and_copy():
+    """Test update() découpé et copy() d'un état avec clé"""
+    data = bytes(range(256)) * 5
+    h = RsHash.BLAKE2b(key=b"secret", digest_size=20)
+    h.update(data[:129])
+    c = h.copy()
+    h.update(data[129:])
+    assert h.digest() == hashlib.blake2b(data, key=b"secret", digest_size=20).digest()
+    c.update(b"tail")
+    assert c.digest() == hashlib.blake2b(data[:129] + b"tail", key=b"secret",
+                                         digest_size=20).digest()
+
+
+def test_blake2b_attributes_and_new():
+    """Test name, digest_size, block_size et le constructeur générique"""
+    h = RsHash.new("blake2b", b"abc", digest_size=48, person=b"app")
+    assert h.name == "blake2b"
+    assert h.digest_size == 48
+    assert h.block_size == 128
+    assert h.digest() == hashlib.blake2b(b"abc", digest_size=48, person=b"app").digest()
+
+
+@pytest.mark.parametrize("kwargs", [
+    {"digest_size": 0}, {"digest_size": 65}, {"key": b"k" * 65},
+    {"salt": b"s" * 17}, {"person": b"p" * 17},
+])
+def test_blake2b_rejects_like_hashlib(kwargs):
+    """Test que les paramètres trop longs lèvent ValueError, comme hashlib"""
+    with pytest.raises(ValueError):
+        hashlib.blake2b(**kwargs)
+    with pytest.raises(ValueError):
+        RsHash.blake2b(**kwargs)
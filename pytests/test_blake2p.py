@@ -0,0 +1,91 @@
+"""Tests pour BLAKE2bp et BLAKE2sp, les variantes parallèles de BLAKE2"""
+import copy
+import hashlib
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = bytes(range(256))
+
+# (nom, fonction séquentielle, nombre de feuilles, taille de bloc, taille max)
+VARIANTS = [
+    ("blake2bp", hashlib.blake2b, 4, 128, 64),
+    ("blake2sp", hashlib.blake2s, 8, 64, 32),
+]
+
+
+def reference(variant, data):
+    """BLAKE2bp/sp sans clé, reconstruit avec les paramètres d'arbre de hashlib"""
+    _, blake2, lanes, block, size = variant
+    leaves = [b""] * lanes
+    for i in range(0, len(data), block):
+        leaves[i // block % lanes] += data[i:i + block]
+    tree = {"digest_size": size, "fanout": lanes, "depth": 2, "inner_size": size}
+    inner = b"".join(
+        blake2(leaf, node_offset=i, last_node=i == lanes - 1, **tree).digest()
+        for i, leaf in enumerate(leaves)
+    )
+    return blake2(inner, node_depth=1, last_node=True, **tree).hexdigest()
+
+
+@pytest.mark.parametrize("name,key,length,expected", [
+    ("blake2bp", bytes(range(64)), 0,
+     "9d9461073e4eb640a255357b839f394b838c6ff57c9b686a3f76107c1066728f"
+     "3c9956bd785cbc3bf79dc2ab578c5a0c063b9d9c405848de1dbe821cd05c940a"),
+    ("blake2bp", bytes(range(64)), 255,
+     "96fbcbb60bd313b8845033e5bc058a38027438572d7e7957f3684f6268aadd3a"
+     "d08d21767ed6878685331ba98571487e12470aad669326716e46667f69f8d7e8"),
+    ("blake2sp", bytes(range(32)), 0,
+     "715cb13895aeb678f6124160bff21465b30f4f6874193fc851b4621043f09cc6"),
+    ("blake2sp", bytes(range(32)), 1,
+     "40578ffa52bf51ae1866f4284d3a157fc1bcd36ac13cbdcb0377e4d0cd0b6603"),
+])
+def test_blake2p_keyed_kat(name, key, length, expected):
+    """Test les vecteurs avec clé de blake2bp-kat.txt et blake2sp-kat.txt"""
+    assert getattr(RsHash, name)(DATA[:length], key=key).hexdigest() == expected
+
+
+@pytest.mark.parametrize("variant", VARIANTS, ids=[v[0] for v in VARIANTS])
+def test_blake2p_matches_tree_reference(variant):
+    """Test différentiel contre un arbre construit avec hashlib"""
+    rng = random.Random(variant[0])
+    for length in [0, 1, 63, 64, 65, 127, 128, 129, 511, 512, 513, 1024, 5000, 70000]:
+        data = bytes(rng.getrandbits(8) for _ in range(length))
+        assert getattr(RsHash, variant[0])(data).hexdigest() == reference(variant, data), length
+
+
+@pytest.mark.parametrize("name", ["blake2bp", "blake2sp"])
+def test_blake2p_chunking_does_not_matter(name):
+    """Test que le découpage des update() ne change pas le résultat"""
+    data = bytes(i * 7 % 251 for i in range(40000))
+    expected = getattr(RsHash, name)(data, key=b"k").digest()
+    for step in [1, 63, 64, 512, 513, 20000]:
+        h = getattr(RsHash, name)(key=b"k")
+        for i in range(0, len(data), step):
+            h.update(data[i:i + step])
+        assert h.digest() == expected, step
+
+
+def test_blake2p_copy_and_attributes():
+    """Test copy(), name, digest_size, block_size et le constructeur générique"""
+    h = RsHash.new("blake2bp", b"abc", digest_size=20, key=b"secret")
+    assert (h.name, h.digest_size, h.block_size) == ("blake2bp", 20, 128)
+    for c in (h.copy(), copy.copy(h), copy.deepcopy(h)):
+        c.update(b"def")
+        assert c.digest() == RsHash.blake2bp(b"abcdef", digest_size=20, key=b"secret").digest()
+    assert h.digest() == RsHash.BLAKE2bp(b"abc", digest_size=20, key=b"secret").digest()
+    s = RsHash.new("blake2sp256")
+    assert (s.name, s.digest_size, s.block_size) == ("blake2sp", 32, 64)
+
+
+@pytest.mark.parametrize("name,kwargs", [
+    ("blake2bp", {"digest_size": 0}), ("blake2bp", {"digest_size": 65}),
+    ("blake2bp", {"key": b"k" * 65}), ("blake2sp", {"digest_size": 33}),
+    ("blake2sp", {"key": b"k" * 33}),
+])
+def test_blake2p_rejects_invalid_parameters(name, kwargs):
+    """Test le rejet des tailles de sortie et de clé hors limites"""
+    with pytest.raises(ValueError):
+        getattr(RsHash, name)(**kwargs)
@@ -0,0 +1,99 @@
+"""Tests pour BLAKE2s (RFC 7693) et les paramètres d'arbre de BLAKE2"""
+import copy
+import hashlib
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+KEY = bytes(range(32))
+DATA = bytes(range(256))
+
+
+def test_blake2s_rfc7693_abc():
+    """Test le vecteur de l'annexe B de RFC 7693"""
+    assert RsHash.blake2s(b"abc").hexdigest() == \
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+
+
+@pytest.mark.parametrize("length,expected", [
+    (0, "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49"),
+    (1, "40d15fee7c328830166ac3f918650f807e7e01e177258cdc0a39b11f598066f1"),
+    (63, "c65382513f07460da39833cb666c5ed82e61b9e998f4b0c4287cee56c3cc9bcd"),
+    (64, "8975b0577fd35566d750b362b0897a26c399136df07bababbde6203ff2954ed4"),
+    (255, "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd"),
+])
+def test_blake2s_keyed_kat(length, expected):
+    """Test les vecteurs avec clé de blake2s-kat.txt"""
+    assert RsHash.blake2s(DATA[:length], key=KEY).hexdigest() == expected
+
+
+@pytest.mark.parametrize("name,limits", [
+    ("blake2b", (64, 16, 2 ** 64 - 1)), ("blake2s", (32, 8, 2 ** 48 - 1)),
+])
+def test_blake2_matches_hashlib_with_tree_params(name, limits):
+    """Test différentiel contre hashlib, paramètres d'arbre compris"""
+    max_size, salt_size, max_offset = limits
+    rng = random.Random(name)
+
+    def random_bytes(n):
+        return bytes(rng.getrandbits(8) for _ in range(n))
+
+    for _ in range(100):
+        kwargs = {
+            "digest_size": rng.randint(1, max_size),
+            "key": random_bytes(rng.choice([0, max_size])),
+            "salt": random_bytes(rng.randint(0, salt_size)),
+            "person": random_bytes(rng.randint(0, salt_size)),
+            "fanout": rng.randint(0, 255),
+            "depth": rng.randint(1, 255),
+            "leaf_size": rng.randint(0, 2 ** 32 - 1),
+            "node_offset": rng.randint(0, max_offset),
+            "node_depth": rng.randint(0, 255),
+            "inner_size": rng.randint(0, max_size),
+            "last_node": rng.random() < 0.5,
+        }
+        data = random_bytes(rng.choice([0, 1, 64, 65, 200]))
+        expected = getattr(hashlib, name)(data, **kwargs).hexdigest()
+        assert getattr(RsHash, name)(data, **kwargs).hexdigest() == expected, kwargs
+
+
+def test_blake2s_copy_preserves_keyed_state():
+    """Test que copy() conserve la clé et les données déjà hachées"""
+    h = RsHash.BLAKE2s(b"abc", key=b"secret", digest_size=16)
+    for c in (h.copy(), copy.copy(h), copy.deepcopy(h)):
+        c.update(b"def")
+        assert c.digest() == hashlib.blake2s(b"abcdef", key=b"secret", digest_size=16).digest()
+    assert h.digest() == hashlib.blake2s(b"abc", key=b"secret", digest_size=16).digest()
+
+
+def test_blake2s_attributes_and_new():
+    """Test name, digest_size, block_size et le constructeur générique"""
+    h = RsHash.new("blake2s", b"abc", digest_size=20)
+    assert (h.name, h.digest_size, h.block_size) == ("blake2s", 20, 64)
+    assert h.digest() == hashlib.blake2s(b"abc", digest_size=20).digest()
+    assert RsHash.blake2s().digest_size == 32
+
+
+@pytest.mark.parametrize("kwargs", [
+    {"digest_size": 0}, {"digest_size": 33}, {"key": b"k" * 33}, {"salt": b"s" * 9},
+    {"person": b"p" * 9}, {"fanout": 256}, {"depth": 0}, {"depth": 256},
+    {"node_depth": 256}, {"inner_size": 33},
+])
+def test_blake2s_rejects_like_hashlib(kwargs):
+    """Test que les paramètres hors limites lèvent ValueError, comme hashlib"""
+    with pytest.raises(ValueError):
+        hashlib.blake2s(**kwargs)
+    with pytest.raises(ValueError):
+        RsHash.blake2s(**kwargs)
+
+
+def test_blake2_oversized_integers_overflow():
+    """Test que leaf_size et node_offset trop grands sont refusés"""
+    with pytest.raises(OverflowError):
+        RsHash.blake2s(leaf_size=2 ** 32)
+    with pytest.raises(OverflowError):
+        RsHash.blake2b(node_offset=2 ** 64)
+    with pytest.raises(ValueError):
+        RsHash.blake2s(node_offset=2 ** 48)
@@ -0,0 +1,71 @@
+"""Tests pour BLAKE2Xb et BLAKE2Xs, les fonctions à sortie extensible de BLAKE2"""
+import copy
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = bytes(range(256))
+
+
+@pytest.mark.parametrize("name,length,expected", [
+    ("blake2xb", 1, "64"),
+    ("blake2xb", 33, "bad026c8b2bd3d294907f2280a7145253ec2117d76e3800357be6d431b16366e41"),
+    ("blake2xb", 65,
+     "78f0ed6e220b3da3cc9381563b2f72c8dc830cb0f39a48c6ae479a6a78dcfa94"
+     "002631dec467e9e9b47cc8f0887eb680e340aec3ec009d4a33d241533c76c8ca8c"),
+    ("blake2xs", 1, "0e"),
+    ("blake2xs", 33, "7dce710a20f42ab687ec6ea83b53faaa418229ce0d5a2ff2a5e66defb0b65c03c9"),
+])
+def test_blake2x_keyed_kat(name, length, expected):
+    """Test les vecteurs avec clé (clé 00..3f ou 00..1f, message 00..ff)"""
+    key = DATA[:64 if name == "blake2xb" else 32]
+    h = getattr(RsHash, name)(DATA, digest_size=length, key=key)
+    assert h.hexdigest() == expected
+    assert h.digest_size == length
+
+
+@pytest.mark.parametrize("name", ["blake2xb", "blake2xs"])
+def test_blake2x_known_and_unknown_lengths_differ(name):
+    """Test que la longueur déclarée change toute la sortie"""
+    unknown = getattr(RsHash, name)(b"abc")
+    stream = unknown.digest(300)
+    assert unknown.digest(100) == stream[:100]
+    known = getattr(RsHash, name)(b"abc", digest_size=100)
+    assert known.digest() != stream[:100]
+    assert known.digest()[:16] != stream[:16]
+    assert known.digest(40) == known.digest()[:40]
+    assert getattr(RsHash, name)(b"abc", digest_size=99).digest()[:16] != known.digest()[:16]
+
+
+def test_blake2x_digest_length_checks():
+    """Test les longueurs acceptées par digest() selon le mode"""
+    with pytest.raises(TypeError):
+        RsHash.blake2xb(b"abc").digest()
+    with pytest.raises(ValueError):
+        RsHash.blake2xs(b"abc", digest_size=10).hexdigest(11)
+    assert len(RsHash.blake2xs(digest_size=65534).digest()) == 65534
+    assert len(RsHash.blake2xs().digest(100000)) == 100000
+
+
+def test_blake2x_attributes_copy_and_new():
+    """Test name, digest_size, block_size, copy() et le constructeur générique"""
+    h = RsHash.new("blake2xs", b"abc", digest_size=50, key=b"secret")
+    assert (h.name, h.digest_size, h.block_size) == ("blake2xs", 50, 64)
+    for c in (h.copy(), copy.copy(h), copy.deepcopy(h)):
+        c.update(b"def")
+        assert c.digest() == RsHash.BLAKE2Xs(b"abcdef", digest_size=50, key=b"secret").digest()
+    assert h.digest() == RsHash.BLAKE2Xs(b"abc", digest_size=50, key=b"secret").digest()
+    x = RsHash.new("blake2xb")
+    assert (x.name, x.digest_size, x.block_size) == ("blake2xb", 0, 128)
+
+
+@pytest.mark.parametrize("name,kwargs", [
+    ("blake2xb", {"digest_size": 0}), ("blake2xb", {"digest_size": 2 ** 32 - 1}),
+    ("blake2xb", {"key": b"k" * 65}), ("blake2xs", {"digest_size": 65535}),
+    ("blake2xs", {"key": b"k" * 33}),
+])
+def test_blake2x_rejects_invalid_parameters(name, kwargs):
+    """Test le rejet des longueurs de sortie et de clé hors limites"""
+    with pytest.raises(ValueError):
+        getattr(RsHash, name)(**kwargs)
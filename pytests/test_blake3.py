@@ -0,0 +1,157 @@
+"""Tests pour BLAKE3"""
+import copy
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+KEY = b"whats the Elvish word for friend"
+CONTEXT = "BLAKE3 2019-12-27 16:29:52 test vectors context"
+
+
+def pattern(n):
+    """Motif 00 01 .. FA répété, utilisé par les vecteurs officiels"""
+    return bytes(i % 251 for i in range(n))
+
+
+@pytest.mark.parametrize("length,expected", [
+    (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+    (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+    (63, "e9bc37a594daad83be9470df7f7b3798297c3d834ce80ba85d6e207627b7db7b"),
+    (64, "4eed7141ea4a5cd4b788606bd23f46e212af9cacebacdc7d1f4c6dc7f2511b98"),
+    (65, "de1e5fa0be70df6d2be8fffd0e99ceaa8eb6e8c93a63f2d8d1c30ecb6b263dee"),
+    (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11"),
+    (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+    (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+    (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+    (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b"),
+    (16384, "f875d6646de28985646f34ee13be9a576fd515f76b5b0a26bb324735041ddde4"),
+    (31744, "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47"),
+    (102400, "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085"),
+])
+def test_blake3_official_vectors(length, expected):
+    """Test la colonne hash des vecteurs officiels (32 premiers octets)"""
+    assert RsHash.blake3(pattern(length)).hexdigest() == expected
+
+
+@pytest.mark.parametrize("length,expected", [
+    (0, ("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        "e00f03e7b69af26b7faaf09fcd333050338ddfe085b8cc869ca98b206c08243a"
+        "26f5487789e8f660afe6c99ef9e0c52b92e7393024a80459cf91f476f9ffdbda"
+        "7001c22e159b402631f277ca96f2defdf1078282314e763699a31c5363165421"
+        "cce14d")),
+    (1025, ("d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"
+           "f4c4a22b4b399155358a994e52bf255de60035742ec71bd08ac275a1b51cc6bf"
+           "e332b0ef84b409108cda080e6269ed4b3e2c3f7d722aa4cdc98d16deb554e562"
+           "7be8f955c98e1d5f9565a9194cad0c4285f93700062d9595adb992ae68ff1280"
+           "0ab67a")),
+])
+def test_blake3_official_extended_output(length, expected):
+    """Test la sortie étendue de 131 octets des vecteurs officiels"""
+    h = RsHash.blake3(pattern(length))
+    assert h.hexdigest(131) == expected
+    assert h.digest(131)[:32] == h.digest()
+
+
+def test_blake3_incremental_updates():
+    """Test que le découpage des update() ne change pas le résultat"""
+    data = pattern(10000)
+    expected = RsHash.blake3(data).digest()
+    for step in (1, 64, 1000, 1024, 1025, 4096):
+        h = RsHash.blake3()
+        for i in range(0, len(data), step):
+            h.update(data[i:i + step])
+        assert h.digest() == expected, step
+
+
+def test_blake3_attributes_copy_and_new():
+    """Test name, digest_size, block_size, copy() et le constructeur générique"""
+    h = RsHash.new("blake3", b"abc")
+    assert (h.name, h.digest_size, h.block_size) == ("blake3", 32, 64)
+    for c in (h.copy(), copy.copy(h), copy.deepcopy(h)):
+        c.update(b"def")
+        assert c.digest() == RsHash.BLAKE3(b"abcdef").digest()
+    assert h.digest() == RsHash.BLAKE3(b"abc").digest()
+    assert h.digest(0) == b""
+
+
+@pytest.mark.parametrize("length,expected", [
+    (0, "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26"),
+    (1, "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b"),
+    (64, "ba8ced36f327700d213f120b1a207a3b8c04330528586f414d09f2f7d9ccb7e6"),
+    (1024, "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4"),
+    (1025, "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69"),
+    (8193, "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5"),
+    (102400, "1c35d1a5811083fd7119f5d5d1ba027b4d01c0c6c49fb6ff2cf75393ea5db4a7"),
+])
+def test_blake3_official_keyed_vectors(length, expected):
+    """Test la colonne keyed_hash des vecteurs officiels"""
+    assert RsHash.blake3(pattern(length), key=KEY).hexdigest() == expected
+    assert RsHash.blake3_keyed(KEY, pattern(length)).hex() == expected
+
+
+@pytest.mark.parametrize("length,expected", [
+    (0, "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+    (1, "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c"),
+    (64, "a5c4a7053fa86b64746d4bb688d06ad1f02a18fce9afd3e818fefaa7126bf73e"),
+    (1024, "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706"),
+    (1025, "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb"),
+    (8193, "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1"),
+    (102400, "4652cff7a3f385a6103b5c260fc1593e13c778dbe608efb092fe7ee69df6e9c6"),
+])
+def test_blake3_official_derive_key_vectors(length, expected):
+    """Test la colonne derive_key des vecteurs officiels"""
+    h = RsHash.blake3(pattern(length), derive_key_context=CONTEXT)
+    assert h.hexdigest() == expected
+    assert RsHash.blake3_derive_key(CONTEXT, pattern(length)).hex() == expected
+    assert RsHash.blake3_derive_key(CONTEXT, pattern(length), length=131) == h.digest(131)
+
+
+def test_blake3_mode_errors():
+    """Test les messages d'erreur des modes avec clé et de dérivation"""
+    with pytest.raises(ValueError, match="mutually exclusive"):
+        RsHash.blake3(key=KEY, derive_key_context=CONTEXT)
+    for key in (b"", b"k" * 31, b"k" * 33):
+        with pytest.raises(ValueError, match="key must be 32 bytes"):
+            RsHash.blake3(key=key)
+        with pytest.raises(ValueError, match="key must be 32 bytes"):
+            RsHash.blake3_keyed(key, b"data")
+    with pytest.raises(TypeError):
+        RsHash.blake3(derive_key_context=b"bytes context")
+
+
+def test_blake3_modes_copy_and_strict_mode():
+    """Test que copy() garde le mode et que le mode strict refuse les one-shot"""
+    h = RsHash.blake3(b"abc", key=KEY)
+    c = h.copy()
+    c.update(b"def")
+    assert c.digest() == RsHash.blake3_keyed(KEY, b"abcdef")
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError):
+            RsHash.blake3_keyed(KEY, b"abc")
+        with pytest.raises(ValueError):
+            RsHash.blake3_derive_key(CONTEXT, b"abc")
+        assert RsHash.blake3(b"abc", key=KEY, usedforsecurity=False).digest() == h.digest()
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+@pytest.mark.parametrize("length,expected", [
+    (131073, "f837d4254d24ba3d50fe3743d46e4af6db5f5d6ab0469197d94e7ba1e906c4d8"),
+    (1048575, "f32b849d19684c18c138cf13e29dbadc776f4bc2a56b477680ef546b39ac3f71"),
+    (1048577, "2f053cd7472cf0cd2f9adaf45c1180255b91b9a865404a63671a0ee5f792ed33"),
+])
+def test_blake3_max_threads(length, expected):
+    """Test que le hachage multithread donne le même résultat que le séquentiel"""
+    data = pattern(length)
+    for max_threads in (None, 1, 2, 4):
+        assert RsHash.blake3(data, max_threads=max_threads).hexdigest() == expected
+    h = RsHash.blake3(pattern(5000), key=KEY, max_threads=3)
+    h.update(data)
+    assert h.digest(64) == RsHash.blake3(pattern(5000) + data, key=KEY, max_threads=1).digest(64)
+    # Les valeurs au-delà du nombre de cœurs sont ramenées à celui-ci
+    assert RsHash.blake3(data, max_threads=2**62).hexdigest() == expected
+    with pytest.raises(ValueError, match="max_threads must be at least 1"):
+        RsHash.blake3(max_threads=0)
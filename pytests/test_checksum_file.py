@@ -0,0 +1,357 @@
+"""Tests pour write_checksum_file et check_checksum_file (format de sha256sum)"""
+import hashlib
+import io
+import os
+import shutil
+import subprocess
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+FILES = {
+    "plain": b"a",
+    "back\\slash": b"b",
+    "new\nline": b"c",
+    "cr\rname": b"d",
+    " lead space": b"e",
+}
+
+# Sorties de sha256sum -b et sha256sum -t (GNU coreutils 9.1) pour FILES
+SHA256SUM_BINARY = (
+    b"ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb *plain\n"
+    b"\\3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d *back\\\\slash\n"
+    b"\\2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6 *new\\nline\n"
+    b"\\18ac3e7343f016890c510e93f935261169d9e3f565436429830faf0934f4f8e4 *cr\\rname\n"
+    b"3f79bb7b435b05321651daefd374cdc681dc06faa65e374e38337b88ca046dea * lead space\n"
+)
+SHA256SUM_TEXT = (
+    b"ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb  plain\n"
+    b"\\3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d  back\\\\slash\n"
+    b"\\2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6  new\\nline\n"
+    b"\\18ac3e7343f016890c510e93f935261169d9e3f565436429830faf0934f4f8e4  cr\\rname\n"
+    b"3f79bb7b435b05321651daefd374cdc681dc06faa65e374e38337b88ca046dea   lead space\n"
+)
+
+TAG_FILES = {**FILES, "pa(re)n = x": b"f"}
+
+# Sorties de shasum --tag (Perl Digest::SHA 6.02), qui n'échappe pas \r, de
+# sha256sum --tag (GNU coreutils 9.1) et d'openssl dgst (OpenSSL 3) pour TAG_FILES
+SHASUM_TAG_256 = (
+    b"SHA256 (plain) = ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb\n"
+    b"\\SHA256 (back\\\\slash) = "
+    b"3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d\n"
+    b"\\SHA256 (new\\nline) = 2e7d2c03a9507ae265ecf5b5356885a53393a2029d241394997265a1a25aefc6\n"
+    b"SHA256 (cr\rname) = 18ac3e7343f016890c510e93f935261169d9e3f565436429830faf0934f4f8e4\n"
+    b"SHA256 ( lead space) = 3f79bb7b435b05321651daefd374cdc681dc06faa65e374e38337b88ca046dea\n"
+    b"SHA256 (pa(re)n = x) = 252f10c83610ebca1a059c0bae8255eba2f95be4d1d7bcfa89d7248a82d9f111\n"
+)
+SHASUM_TAG_512256 = (
+    b"SHA512/256 (plain) = 455e518824bc0601f9fb858ff5c37d417d67c2f8e0df2babe4808858aea830f8\n"
+    b"\\SHA512/256 (back\\\\slash) = "
+    b"6edcf3ed1ef5632429a51f941d42ccfd1d3407671a2ac939eb5361a0f576ff8f\n"
+    b"\\SHA512/256 (new\\nline) = "
+    b"92e6842bbc2790993ffe844eddebf850b4da4f345cb9e99e1f0054a56fa41bfc\n"
+    b"SHA512/256 (cr\rname) = 9a895196448c0a9daa9769b48f29db5b41cfe2f6f65943a8ef2b8f446e388f7e\n"
+    b"SHA512/256 ( lead space) = "
+    b"a051935849c2d28415e06d56c4fbc4d7672d36a10f7b58396011998f34dcbf29\n"
+    b"SHA512/256 (pa(re)n = x) = "
+    b"ea3a2811a3f8cfb301bc088cdff8edf803a6b2d777c507d01c2d9cf6b5e5ba0b\n"
+)
+SHA256SUM_TAG = SHASUM_TAG_256.replace(b"SHA256 (cr\rname)", b"\\SHA256 (cr\\rname)")
+OPENSSL_SHA256 = (
+    b"SHA2-256(plain)= ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb\n"
+    b"SHA2-256(pa(re)n = x)= 252f10c83610ebca1a059c0bae8255eba2f95be4d1d7bcfa89d7248a82d9f111\n"
+    b"SHA2-256( lead space)= 3f79bb7b435b05321651daefd374cdc681dc06faa65e374e38337b88ca046dea\n"
+)
+
+
+def populate(tmp_path, files=FILES):
+    """Crée les fichiers de files dans tmp_path et renvoie leurs chemins"""
+    paths = []
+    for name, data in files.items():
+        path = tmp_path / name
+        path.write_bytes(data)
+        paths.append(path)
+    return paths
+
+
+def in_dir(path, fn, *args, **kwargs):
+    """Appelle fn depuis le répertoire path, comme sha256sum lancé dans ce répertoire"""
+    cwd = os.getcwd()
+    os.chdir(path)
+    try:
+        return fn(*args, **kwargs)
+    finally:
+        os.chdir(cwd)
+
+
+@pytest.mark.parametrize("binary,expected", [(True, SHA256SUM_BINARY), (False, SHA256SUM_TEXT)])
+def test_write_matches_sha256sum(tmp_path, binary, expected):
+    """Test l'écriture octet pour octet identique à la sortie de sha256sum"""
+    populate(tmp_path)
+    in_dir(tmp_path, RsHash.write_checksum_file, "SHA256SUMS", list(FILES), binary=binary)
+    assert (tmp_path / "SHA256SUMS").read_bytes() == expected
+
+
+@pytest.mark.parametrize("fixture", [SHA256SUM_BINARY, SHA256SUM_TEXT])
+def test_check_sha256sum_output(tmp_path, fixture):
+    """Test la vérification des fichiers produits par sha256sum"""
+    populate(tmp_path)
+    sums = tmp_path / "SHA256SUMS"
+    sums.write_bytes(fixture)
+    result = RsHash.check_checksum_file(sums, base_dir=tmp_path)
+    assert result
+    assert result.ok == list(FILES)
+    assert (result.failed, result.missing, result.malformed) == ([], [], [])
+    assert in_dir(tmp_path, RsHash.check_checksum_file, "SHA256SUMS", algorithm="sha256").ok == \
+        list(FILES)
+
+
+def test_roundtrip_file_objects(tmp_path):
+    """Test l'écriture dans des objets fichier binaires et texte"""
+    paths = populate(tmp_path)
+    binary = io.BytesIO()
+    RsHash.write_checksum_file(binary, paths[:1], "sha512")
+    assert binary.getvalue() == \
+        f"{hashlib.sha512(b'a').hexdigest()} *{paths[0]}\n".encode()
+    text = io.StringIO()
+    RsHash.write_checksum_file(text, [("x", hashlib.sha256(b"x").digest())], binary=False)
+    assert text.getvalue() == f"{hashlib.sha256(b'x').hexdigest()}  x\n"
+    sums = tmp_path / "SUMS"
+    with open(sums, "wb") as f:
+        RsHash.write_checksum_file(f, paths, "sha512")
+    result = RsHash.check_checksum_file(sums)
+    assert result.ok == [str(path) for path in paths]
+
+
+def test_write_pairs(tmp_path):
+    """Test les entrées (nom, condensé), en octets ou en hexadécimal"""
+    sums = tmp_path / "SUMS"
+    digest = hashlib.sha256(b"a")
+    RsHash.write_checksum_file(sums, [("plain", digest.digest()),
+                                      ("new\nline", digest.hexdigest().upper()),
+                                      (b"raw\xff", bytearray(digest.digest()))])
+    assert sums.read_bytes() == (
+        f"{digest.hexdigest()} *plain\n\\{digest.hexdigest()} *new\\nline\n".encode()
+        + digest.hexdigest().encode() + b" *raw\xff\n")
+    (tmp_path / "plain").write_bytes(b"a")
+    result = RsHash.check_checksum_file(sums, tmp_path)
+    assert result.ok == ["plain"]
+    assert result.missing == ["new\nline", os.fsdecode(b"raw\xff")]
+    assert not result
+
+
+def test_check_lines(tmp_path):
+    """Test les commentaires, fins de ligne CRLF, lignes vides et lignes invalides"""
+    populate(tmp_path)
+    a = hashlib.sha256(b"a").hexdigest()
+    sums = tmp_path / "SUMS"
+    sums.write_bytes(
+        b"# generated by hand\r\n"
+        + f"{a}  plain\r\n".encode()
+        + b"garbage\n"
+        + b"\n"
+        + f"{a}  missing\n".encode()
+        + f"{'0' * 64} *plain\n".encode()
+        + f"  {a.upper()}  plain\n".encode()
+        + f"{a} plain\n".encode()
+        + f"\\{a}  pl\\x\n".encode()
+        + f"{a[:-2]}  plain\n".encode()
+        + f"{hashlib.md5(b'e').hexdigest()}   lead space\n".encode()
+        + f"{hashlib.sha512(b'd').hexdigest()}  cr\rname".encode()
+    )
+    result = RsHash.check_checksum_file(sums, tmp_path)
+    assert result.ok == ["plain", "plain", " lead space", "cr\rname"]
+    assert result.failed == ["plain"]
+    assert result.missing == ["missing"]
+    assert result.malformed == [3, 8, 9, 10]
+    assert not result
+    assert repr(result).startswith("ChecksumResult(ok=['plain', 'plain', ")
+    # Avec un algorithme imposé, les condensés d'une autre taille sont invalides
+    result = RsHash.check_checksum_file(sums, tmp_path, "sha256")
+    assert result.malformed == [3, 8, 9, 10, 11, 12]
+
+
+def test_check_reversed(tmp_path):
+    """Test la forme inversée à une seule espace, comme md5 -r"""
+    populate(tmp_path)
+    a = hashlib.sha256(b"a").hexdigest()
+    sums = tmp_path / "SUMS"
+    sums.write_text(f"{a} plain\n{a}  plain\n")
+    result = RsHash.check_checksum_file(sums, tmp_path)
+    assert (result.ok, result.missing) == (["plain"], [" plain"])
+
+
+def test_check_errors(tmp_path):
+    """Test les fichiers de sommes inexploitables et les paramètres invalides"""
+    populate(tmp_path)
+    sums = tmp_path / "SUMS"
+    for text in (b"", b"# only a comment\n", b"garbage\n", b"abcd  plain\n"):
+        sums.write_bytes(text)
+        with pytest.raises(ValueError, match="no properly formatted checksum lines"):
+            RsHash.check_checksum_file(sums)
+    with pytest.raises(FileNotFoundError):
+        RsHash.check_checksum_file(tmp_path / "missing")
+    sums.write_bytes(SHA256SUM_BINARY)
+    with pytest.raises(ValueError, match="unsupported"):
+        RsHash.check_checksum_file(sums, algorithm="nope")
+    with pytest.raises(ValueError, match="unsupported"):
+        RsHash.write_checksum_file(tmp_path / "out", [], "nope")
+    with pytest.raises(FileNotFoundError):
+        RsHash.write_checksum_file(tmp_path / "out", [tmp_path / "missing"])
+    with pytest.raises(ValueError, match="bytes"):
+        RsHash.write_checksum_file(tmp_path / "out", [("x", bytes(20))])
+    with pytest.raises(ValueError, match="hexadecimal"):
+        RsHash.write_checksum_file(tmp_path / "out", [("x", "zz")])
+    with pytest.raises(TypeError):
+        RsHash.write_checksum_file(tmp_path / "out", 42)
+    RsHash.set_fips_mode(True)
+    try:
+        md5 = tmp_path / "MD5SUMS"
+        md5.write_text(f"{hashlib.md5(b'a').hexdigest()}  plain\n")
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.check_checksum_file(md5, tmp_path)
+        assert RsHash.check_checksum_file(md5, tmp_path, usedforsecurity=False)
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+@pytest.mark.parametrize("tool,algorithm", [("sha256sum", "sha256"), ("sha512sum", "sha512"),
+                                            ("md5sum", "md5"), ("sha1sum", "sha1")])
+def test_against_coreutils(tmp_path, tool, algorithm):
+    """Test différentiel contre les outils de coreutils quand ils sont installés"""
+    if shutil.which(tool) is None:
+        pytest.skip(f"{tool} not installed")
+    populate(tmp_path)
+    names = list(FILES)
+    for flag, binary in (("-b", True), ("-t", False)):
+        expected = subprocess.run([tool, flag, "--", *names], cwd=tmp_path, check=True,
+                                  capture_output=True).stdout
+        in_dir(tmp_path, RsHash.write_checksum_file, "SUMS", names, algorithm, binary)
+        assert (tmp_path / "SUMS").read_bytes() == expected
+        assert subprocess.run([tool, "-c", "SUMS"], cwd=tmp_path,
+                              capture_output=True).returncode == 0
+        assert RsHash.check_checksum_file(tmp_path / "SUMS", tmp_path).ok == names
+
+
+def test_write_bsd_matches_sha256sum_tag(tmp_path):
+    """Test l'écriture au format BSD identique à sha256sum --tag"""
+    populate(tmp_path, TAG_FILES)
+    in_dir(tmp_path, RsHash.write_checksum_file, "SUMS", list(TAG_FILES), format="bsd")
+    assert (tmp_path / "SUMS").read_bytes() == SHA256SUM_TAG
+
+
+@pytest.mark.parametrize("fixture,names", [
+    (SHASUM_TAG_256, list(TAG_FILES)),
+    (SHASUM_TAG_512256, list(TAG_FILES)),
+    (SHA256SUM_TAG, list(TAG_FILES)),
+    (OPENSSL_SHA256, ["plain", "pa(re)n = x", " lead space"]),
+])
+def test_check_bsd_fixtures(tmp_path, fixture, names):
+    """Test la vérification des sorties de shasum --tag, sha256sum --tag et openssl dgst"""
+    populate(tmp_path, TAG_FILES)
+    sums = tmp_path / "SUMS"
+    sums.write_bytes(fixture)
+    result = RsHash.check_checksum_file(sums, tmp_path)
+    assert result.ok == names
+    assert (result.failed, result.missing, result.malformed) == ([], [], [])
+    # Un chiffre modifié sur la première ligne la fait échouer
+    i = fixture.index(b"= ") + 2
+    sums.write_bytes(fixture[:i] + (b"1" if fixture[i:i + 1] == b"0" else b"0") + fixture[i + 1:])
+    assert RsHash.check_checksum_file(sums, tmp_path).failed == names[:1]
+
+
+def test_check_mixed_formats(tmp_path):
+    """Test la détection du format ligne par ligne"""
+    populate(tmp_path)
+    a = hashlib.sha256(b"a").hexdigest()
+    sums = tmp_path / "SUMS"
+    sums.write_text(
+        f"{a}  plain\n"
+        f"MD5 (plain) = {hashlib.md5(b'a').hexdigest()}\n"
+        f"SHA3-256(plain)= {hashlib.sha3_256(b'a').hexdigest()}\n"
+        f"BLAKE2b (plain) = {hashlib.blake2b(b'a').hexdigest()}\n"
+        f"BLAKE2b-256 (plain) = {hashlib.blake2b(b'a', digest_size=32).hexdigest()}\n"
+        f"SHA512/224 (plain) = {hashlib.new('sha512_224', b'a').hexdigest()}\n"
+        f"SHA384 (plain) = {hashlib.sha384(b'a').hexdigest()}\n"
+        f"SHAKE128 (plain) = {hashlib.shake_128(b'a').hexdigest(32)}\n"
+        f"SHA256 (plain) = {hashlib.sha512(b'a').hexdigest()}\n"
+        f"SHA256 (plain) = {'0' * 64}\n"
+    )
+    result = RsHash.check_checksum_file(sums, tmp_path)
+    assert result.ok == ["plain"] * 6
+    assert result.failed == ["plain"]
+    assert result.malformed == [7, 8, 9]
+
+
+def test_check_tag_validates_algorithm(tmp_path):
+    """Test que l'étiquette doit correspondre à l'algorithme demandé"""
+    populate(tmp_path, TAG_FILES)
+    sums = tmp_path / "SUMS"
+    sums.write_bytes(SHASUM_TAG_256 + OPENSSL_SHA256 + SHASUM_TAG_512256)
+    result = RsHash.check_checksum_file(sums, tmp_path, "SHA-256")
+    assert result.ok == list(TAG_FILES) + ["plain", "pa(re)n = x", " lead space"]
+    assert result.malformed == list(range(10, 16))
+    with pytest.raises(ValueError, match="no properly formatted"):
+        RsHash.check_checksum_file(sums, tmp_path, "md5")
+
+
+@pytest.mark.parametrize("algorithm,tag", [
+    ("md5", "MD5"), ("sha1", "SHA1"), ("sha224", "SHA224"), ("sha512", "SHA512"),
+    ("sha512_224", "SHA512/224"), ("blake2b", "BLAKE2b"), ("sm3", "SM3"),
+    ("sha3_256", "SHA3-256"), ("SHA2-256", "SHA256"), ("keccak_256", "KECCAK-256"),
+])
+def test_write_bsd_tags(algorithm, tag):
+    """Test les étiquettes écrites, celles de coreutils et shasum quand elles existent"""
+    out = io.BytesIO()
+    RsHash.write_checksum_file(out, [("x", RsHash.new(algorithm, b"x").digest())], algorithm,
+                               format="bsd")
+    assert out.getvalue() == f"{tag} (x) = {RsHash.new(algorithm, b'x').hexdigest()}\n".encode()
+
+
+def test_bsd_roundtrip_all_algorithms(tmp_path):
+    """Test l'aller-retour au format BSD pour chaque algorithme de taille fixe"""
+    populate(tmp_path, TAG_FILES)
+    sums = tmp_path / "SUMS"
+    checked = 0
+    for algorithm in sorted(RsHash.algorithms_available):
+        try:
+            in_dir(tmp_path, RsHash.write_checksum_file, "SUMS", list(TAG_FILES), algorithm,
+                   format="bsd")
+        except ValueError as err:
+            assert "digest size" in str(err)
+            continue
+        assert RsHash.check_checksum_file(sums, tmp_path).ok == list(TAG_FILES), algorithm
+        assert RsHash.check_checksum_file(sums, tmp_path, algorithm).ok == list(TAG_FILES)
+        checked += 1
+    assert checked > 30
+
+
+def test_write_bsd_errors(tmp_path):
+    """Test les formats refusés"""
+    with pytest.raises(ValueError, match="text mode"):
+        RsHash.write_checksum_file(tmp_path / "SUMS", [], binary=False, format="bsd")
+    with pytest.raises(ValueError, match="format"):
+        RsHash.write_checksum_file(tmp_path / "SUMS", [], format="openssl")
+
+
+def test_bsd_against_tools(tmp_path):
+    """Test différentiel contre sha256sum --tag et shasum --tag quand ils sont installés"""
+    if shutil.which("sha256sum") is None or shutil.which("shasum") is None:
+        pytest.skip("sha256sum or shasum not installed")
+    populate(tmp_path, TAG_FILES)
+    names = list(TAG_FILES)
+    expected = subprocess.run(["sha256sum", "--tag", "--", *names], cwd=tmp_path, check=True,
+                              capture_output=True).stdout
+    in_dir(tmp_path, RsHash.write_checksum_file, "SUMS", names, format="bsd")
+    assert (tmp_path / "SUMS").read_bytes() == expected
+    assert subprocess.run(["sha256sum", "-c", "SUMS"], cwd=tmp_path,
+                          capture_output=True).returncode == 0
+    # shasum n'échappe pas \r : on se limite aux autres noms
+    names.remove("cr\rname")
+    for bits in ("256", "512256"):
+        sums = subprocess.run(["shasum", "-a", bits, "--tag", "--", *names], cwd=tmp_path,
+                              check=True, capture_output=True).stdout
+        (tmp_path / "SUMS").write_bytes(sums)
+        assert RsHash.check_checksum_file(tmp_path / "SUMS", tmp_path).ok == names
@@ -0,0 +1,73 @@
+"""Tests pour compare_digest (comparaison en temps constant)"""
+import array
+import hashlib
+import hmac
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+TAG = bytes(range(32))
+
+
+def flip(data, index):
+    """Renvoie data avec un bit inversé à la position index"""
+    out = bytearray(data)
+    out[index] ^= 1
+    return bytes(out)
+
+
+@pytest.mark.parametrize("a,b,expected", [
+    (TAG, TAG, True),
+    (b"", b"", True),
+    (TAG, flip(TAG, 0), False),
+    (TAG, flip(TAG, 31), False),
+    (TAG, TAG[:31], False),
+    (TAG[:31], TAG, False),
+    (b"", TAG, False),
+    ("abcdef", "abcdef", True),
+    ("", "", True),
+    ("abcdef", "Abcdef", False),
+    ("abcdef", "abcdeF", False),
+    ("abcdef", "abcde", False),
+])
+def test_compare_digest(a, b, expected):
+    """Test l'égalité, les différences au premier et au dernier octet et les longueurs"""
+    assert RsHash.compare_digest(a, b) is expected
+    assert hmac.compare_digest(a, b) is expected
+
+
+def test_compare_digest_buffers():
+    """Test les objets compatibles avec le protocole buffer"""
+    assert RsHash.compare_digest(bytearray(TAG), memoryview(TAG))
+    assert RsHash.compare_digest(array.array("B", TAG), TAG)
+    assert RsHash.compare_digest(array.array("I", [1, 2]), array.array("I", [1, 2]))
+    assert not RsHash.compare_digest(bytearray(TAG), flip(TAG, 16))
+    assert RsHash.compare_digest(RsHash.SHA256(b"abc").digest(), hashlib.sha256(b"abc").digest())
+
+
+def test_compare_digest_matches_hmac():
+    """Test différentiel contre hmac.compare_digest"""
+    digest = RsHash.SHA256(b"abc").hexdigest()
+    for other in (digest, digest.upper(), digest[:-1], digest + "0", "0" * 64):
+        assert RsHash.compare_digest(digest, other) == hmac.compare_digest(digest, other)
+        assert RsHash.compare_digest(digest.encode(), other.encode()) == \
+            hmac.compare_digest(digest.encode(), other.encode())
+
+
+def test_compare_digest_errors():
+    """Test les combinaisons de types refusées, comme dans la bibliothèque standard"""
+    for a, b in [("abc", b"abc"), (b"abc", "abc"), (bytearray(b"abc"), "abc")]:
+        with pytest.raises(TypeError, match="combination of types"):
+            RsHash.compare_digest(a, b)
+        with pytest.raises(TypeError):
+            hmac.compare_digest(a, b)
+    with pytest.raises(TypeError, match="non-ASCII"):
+        RsHash.compare_digest("café", "café")
+    with pytest.raises(TypeError, match="non-ASCII"):
+        RsHash.compare_digest("abc", "é")
+    for a, b in [(1, 1), (None, b"abc"), (b"abc", [97, 98, 99])]:
+        with pytest.raises(TypeError):
+            RsHash.compare_digest(a, b)
+    with pytest.raises(BufferError):
+        RsHash.compare_digest(memoryview(TAG)[::2], TAG[::2])
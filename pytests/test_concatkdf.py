@@ -0,0 +1,103 @@
+"""Tests pour ConcatKDF (SP 800-56C) et le KDF ANSI X9.63"""
+import base64
+import hashlib
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Exemple ECDH-ES de l'annexe C de la RFC 7518
+JOSE_Z = bytes([158, 86, 217, 29, 129, 113, 53, 211, 114, 131, 66, 131, 191, 132, 38, 156,
+                251, 49, 110, 163, 218, 128, 106, 72, 246, 218, 167, 121, 140, 254, 144, 196])
+
+# Vecteurs CAVS du KDF X9.63 : hash, Z, SharedInfo, clé
+X963_VECTORS = [
+    ("sha1", "1c7d7b5f0597b03d06a018466ed1a93e30ed4b04dc64ccdd", "",
+     "bf71dffd8f4d99223936beb46fee8ccc"),
+    ("sha256", "96c05619d56c328ab95fe84b18264b08725b85e33fd34f08", "",
+     "443024c3dae66b95e6f5670601558f71"),
+    ("sha256", "22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d",
+     "75eef81aa3041e33b80971203d2c0c52",
+     "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e"
+     "52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485"
+     "500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269"
+     "142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21"),
+]
+
+
+def reference(name, z, info, length, concat):
+    """Implémentation de référence des deux constructions"""
+    out, counter = b"", 1
+    while len(out) < length:
+        c = counter.to_bytes(4, "big")
+        out += hashlib.new(name, c + z + info if concat else z + c + info).digest()
+        counter += 1
+    return out[:length]
+
+
+def test_concat_kdf_rfc7518():
+    """Test l'exemple ECDH-ES de la RFC 7518"""
+    other_info = RsHash.jose_other_info("A128GCM", keydatalen=128, apu=b"Alice", apv=b"Bob")
+    assert other_info.hex() == "000000074131323847434d00000005416c69636500000003426f6200000080"
+    key = RsHash.concat_kdf(JOSE_Z, length=16, other_info=other_info)
+    assert base64.urlsafe_b64encode(key).rstrip(b"=") == b"VqqN6vgjbSBcIijNcacQGg"
+    assert RsHash.concat_kdf(JOSE_Z, length=16, other_info=other_info, hash="sha256") == key
+
+
+@pytest.mark.parametrize("name,z,shared_info,expected", X963_VECTORS)
+def test_x963_kdf_vectors(name, z, shared_info, expected):
+    """Test les vecteurs CAVS du KDF X9.63"""
+    key = RsHash.x963_kdf(bytes.fromhex(z), length=len(expected) // 2,
+                          shared_info=bytes.fromhex(shared_info), hash=name)
+    assert key.hex() == expected
+
+
+@pytest.mark.parametrize("name", ["sha1", "sha256", "sha512", "sha3_256", "blake2b"])
+def test_kdfs_match_reference(name):
+    """Test différentiel contre l'implémentation de référence"""
+    for length in (0, 1, 31, 32, 33, 200):
+        for info in (b"", b"info" * 10):
+            assert RsHash.concat_kdf(b"z" * 20, length=length, other_info=info, hash=name) == \
+                reference(name, b"z" * 20, info, length, True)
+            assert RsHash.x963_kdf(b"z" * 20, length=length, shared_info=info, hash=name) == \
+                reference(name, b"z" * 20, info, length, False)
+
+
+def test_kdfs_match_cryptography():
+    """Test différentiel contre pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.kdf.concatkdf import ConcatKDFHash
+    from cryptography.hazmat.primitives.kdf.x963kdf import X963KDF
+    for name, algorithm in [("sha256", hashes.SHA256()), ("sha512", hashes.SHA512())]:
+        theirs = ConcatKDFHash(algorithm=algorithm, length=70, otherinfo=b"other").derive(b"z")
+        assert RsHash.concat_kdf(b"z", length=70, other_info=b"other", hash=name) == theirs
+        theirs = X963KDF(algorithm=algorithm, length=70, sharedinfo=b"shared").derive(b"z")
+        assert RsHash.x963_kdf(b"z", length=70, shared_info=b"shared", hash=name) == theirs
+
+
+def test_jose_other_info():
+    """Test l'encodage de OtherInfo pour JOSE"""
+    assert RsHash.jose_other_info("ECDH-ES+A256KW", keydatalen=256) == (
+        b"\x00\x00\x00\x0eECDH-ES+A256KW" + bytes(8) + b"\x00\x00\x01\x00")
+    other_info = RsHash.jose_other_info("A256GCM", keydatalen=256, apu=bytearray(b"u"),
+                                        apv=memoryview(b"vv"))
+    assert other_info.endswith(b"\x00\x00\x00\x01u\x00\x00\x00\x02vv\x00\x00\x01\x00")
+
+
+def test_kdf_argument_errors():
+    """Test les arguments manquants ou invalides"""
+    with pytest.raises(TypeError):
+        RsHash.concat_kdf(b"z", length=16)
+    with pytest.raises(TypeError):
+        RsHash.concat_kdf(b"z", 16, b"info")
+    with pytest.raises(TypeError):
+        RsHash.x963_kdf("z", length=16)
+    with pytest.raises(ValueError):
+        RsHash.x963_kdf(b"z", length=16, hash="shake_256")
+    with pytest.raises(OverflowError):
+        RsHash.concat_kdf(b"z", length=-1, other_info=b"")
+    with pytest.raises(OverflowError):
+        RsHash.jose_other_info("A128GCM", keydatalen=1 << 32)
+    with pytest.raises(TypeError):
+        RsHash.jose_other_info(b"A128GCM", keydatalen=128)
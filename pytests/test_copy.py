@@ -0,0 +1,46 @@
+"""Tests du protocole copy (copy.copy, copy.deepcopy)"""
+import copy
+import dataclasses
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ALGORITHMS = [("sha256", RsHash.SHA256), ("sha512", RsHash.SHA512)]
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+@pytest.mark.parametrize("copier", [copy.copy, copy.deepcopy])
+def test_copy_module_mid_block(name, cls, copier):
+    """Test qu'une copie en milieu de bloc continue vers le même digest"""
+    original = cls(b"x" * 70)
+    clone = copier(original)
+    assert type(clone) is cls
+    original.update(b"tail")
+    clone.update(b"tail")
+    expected = hashlib.new(name, b"x" * 70 + b"tail").hexdigest()
+    assert clone.hexdigest() == original.hexdigest() == expected
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_deepcopy_is_independent(name, cls):
+    """Test que la copie profonde est indépendante de l'original"""
+    original = cls(b"prefix")
+    clone = copy.deepcopy(original)
+    clone.update(b"only in clone")
+    assert original.hexdigest() == hashlib.new(name, b"prefix").hexdigest()
+
+
+@dataclasses.dataclass
+class Record:
+    label: str
+    hasher: object
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_deepcopy_inside_dataclass(name, cls):
+    """Test qu'un hasher dans une dataclass survit à copy.deepcopy"""
+    record = Record("r", cls(b"a" * 130))
+    copied = copy.deepcopy(record)
+    assert copied.hasher is not record.hasher
+    assert copied.hasher.hexdigest() == hashlib.new(name, b"a" * 130).hexdigest()
@@ -0,0 +1,269 @@
+"""Tests pour les CRC du sous-module noncrypto"""
+import binascii
+import random
+import shutil
+import subprocess
+import zlib
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+# PDU iSCSI « SCSI Read (10) » de la RFC 3720, annexe B.4
+ISCSI_READ_PDU = bytes(
+    [0x01, 0xC0] + [0] * 14 + [0x14, 0, 0, 0, 0, 0, 0x04, 0, 0, 0, 0, 0x14, 0, 0, 0, 0x18]
+    + [0x28, 0, 0, 0, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0, 0, 0]
+)
+
+
+@pytest.mark.parametrize("data", [
+    b"",
+    b"a",
+    b"123456789",
+    b"The quick brown fox jumps over the lazy dog",
+    bytes(range(256)) * 5,
+])
+def test_crc32_matches_binascii(data):
+    """Test crc32 contre binascii.crc32 et zlib.crc32"""
+    expected = binascii.crc32(data)
+    h = noncrypto.crc32(data)
+    assert h.intdigest() == expected == zlib.crc32(data)
+    assert h.digest() == expected.to_bytes(4, "big")
+    assert h.hexdigest() == f"{expected:08x}"
+    assert noncrypto.crc32_intdigest(data) == expected
+    assert noncrypto.crc32_digest(data) == expected.to_bytes(4, "big")
+    assert noncrypto.crc32_hexdigest(data) == f"{expected:08x}"
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", 0x00000000),
+    (b"123456789", 0xE3069283),
+    (b"\x00" * 32, 0x8A9136AA),
+    (b"\xff" * 32, 0x62A8AB43),
+    (bytes(range(32)), 0x46DD794E),
+    (bytes(range(31, -1, -1)), 0x113FDB5C),
+    (ISCSI_READ_PDU, 0xD9963A56),
+])
+def test_crc32c_known_answers(data, expected):
+    """Test les réponses connues de google-crc32c (RFC 3720)"""
+    h = noncrypto.crc32c(data)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(4, "big")
+    assert noncrypto.crc32c_intdigest(data) == expected
+    assert noncrypto.crc32c_hexdigest(data) == f"{expected:08x}"
+
+
+@pytest.mark.parametrize("name", ["crc32", "crc32c"])
+def test_crc_chunked_matches_one_shot(name):
+    """Test update() par morceaux aléatoires contre le calcul en une fois"""
+    cls = getattr(noncrypto, name)
+    intdigest = getattr(noncrypto, f"{name}_intdigest")
+    rng = random.Random(name)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 3000))
+        h = cls()
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 50)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.intdigest() == intdigest(data) == cls(data).intdigest()
+
+
+def test_crc_initial_value():
+    """Test initial comme le paramètre value de binascii.crc32 pour prolonger une somme"""
+    data = bytes(range(256)) * 3
+    head, tail = data[:100], data[100:]
+    assert noncrypto.crc32(tail, binascii.crc32(head)).intdigest() == binascii.crc32(data)
+    assert noncrypto.crc32_intdigest(tail, initial=zlib.crc32(head)) == zlib.crc32(data)
+    partial = noncrypto.crc32c_intdigest(head)
+    assert noncrypto.crc32c(tail, partial).intdigest() == noncrypto.crc32c_intdigest(data)
+    h = noncrypto.crc32c(b"more", initial=partial)
+    h.reset()
+    assert h.intdigest() == partial
+    with pytest.raises(OverflowError):
+        noncrypto.crc32(b"", 2**32)
+
+
+@pytest.mark.parametrize("name", ["crc32", "crc32c"])
+def test_crc_properties_and_copy(name):
+    """Test les propriétés, copy() et le mode FIPS"""
+    cls = getattr(noncrypto, name)
+    h = cls(b"1234")
+    c = h.copy()
+    c.update(b"56789")
+    assert c.intdigest() == cls(b"123456789").intdigest()
+    assert h.intdigest() == cls(b"1234").intdigest()
+    assert (h.name, h.digest_size, h.block_size) == (name, 4, 1)
+    RsHash.set_fips_mode(True)
+    try:
+        assert cls(b"123456789").intdigest() == c.intdigest()
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+def test_crc32c_matches_google_crc32c():
+    """Test différentiel contre le paquet google-crc32c"""
+    google_crc32c = pytest.importorskip("google_crc32c")
+    rng = random.Random(32)
+    for _ in range(50):
+        data = rng.randbytes(rng.randrange(0, 2000))
+        expected = google_crc32c.Checksum(data)
+        assert noncrypto.crc32c(data).digest() == expected.digest()
+        assert noncrypto.crc32c_intdigest(data) == google_crc32c.value(data)
+
+
+@pytest.mark.parametrize("variant,expected", [
+    ("xz", 0x995DC9BBDF1939FA),
+    ("XZ", 0x995DC9BBDF1939FA),
+    ("ecma-182", 0x6C40DF5F0B497347),
+    ("ecma182", 0x6C40DF5F0B497347),
+])
+def test_crc64_check_values(variant, expected):
+    """Test les valeurs de contrôle publiées pour « 123456789 »"""
+    h = noncrypto.crc64(b"123456789", variant)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(8, "big")
+    assert noncrypto.crc64_intdigest(b"123456789", variant=variant) == expected
+    assert noncrypto.crc64_hexdigest(b"123456789", variant) == f"{expected:016x}"
+    assert noncrypto.crc64(b"", variant).intdigest() == 0
+
+
+def test_crc64_sample_file():
+    """Test la valeur CheckVal affichée par xz --list -vv pour un fichier d'exemple"""
+    data = bytes(range(256)) * 40
+    assert noncrypto.crc64(data).hexdigest() == "9e61124bb0e88f95"
+
+
+def test_crc64_matches_xz_list(tmp_path):
+    """Test contre la sortie de xz --robot --list -vv sur des fichiers compressés"""
+    if shutil.which("xz") is None:
+        pytest.skip("xz not installed")
+    rng = random.Random(64)
+    for size in (0, 1, 1000, 100_000):
+        path = tmp_path / f"sample{size}.bin"
+        data = rng.randbytes(size)
+        path.write_bytes(data)
+        subprocess.run(["xz", "-k", "--check=crc64", str(path)], check=True)
+        listing = subprocess.run(
+            ["xz", "--robot", "--list", "-vv", f"{path}.xz"],
+            check=True, capture_output=True, text=True,
+        ).stdout
+        blocks = [line.split("\t") for line in listing.splitlines() if line.startswith("block")]
+        if size == 0:
+            assert not blocks
+            continue
+        assert blocks[0][9] == "CRC64"
+        assert noncrypto.crc64(data).hexdigest() == blocks[0][10]
+
+
+def test_crc64_resume_and_invalid_variant():
+    """Test la reprise avec initial pour les deux variantes et le rejet d'une variante inconnue"""
+    data = bytes(range(256)) * 10
+    for variant in ("xz", "ecma-182"):
+        partial = noncrypto.crc64_intdigest(data[:999], variant)
+        h = noncrypto.crc64(variant=variant, initial=partial)
+        for start in range(999, len(data), 7):
+            h.update(data[start:start + 7])
+        assert h.intdigest() == noncrypto.crc64_intdigest(data, variant)
+        h.reset()
+        assert h.intdigest() == partial
+    assert (h.name, h.digest_size, h.block_size) == ("crc64", 8, 1)
+    with pytest.raises(ValueError, match="unknown variant"):
+        noncrypto.crc64(b"", "iso")
+    with pytest.raises(ValueError, match="unknown variant"):
+        noncrypto.crc64_digest(b"", variant="jones")
+
+
+@pytest.mark.parametrize("variant,expected", [
+    ("ccitt-false", 0x29B1),
+    ("CCITT_FALSE", 0x29B1),
+    ("ibm-3740", 0x29B1),
+    ("modbus", 0x4B37),
+    ("xmodem", 0x31C3),
+    ("usb", 0xB4C8),
+])
+def test_crc16_check_values(variant, expected):
+    """Test la valeur de contrôle « 123456789 » de chaque préréglage"""
+    h = noncrypto.crc16(b"123456789", variant)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(2, "big")
+    assert noncrypto.crc16_intdigest(b"123456789", variant=variant) == expected
+    assert noncrypto.crc16_hexdigest(b"123456789", variant) == f"{expected:04x}"
+
+
+def test_crc16_modbus_default_and_frame():
+    """Test MODBUS par défaut sur une trame Read Holding Registers (CRC émis octet faible en premier)"""
+    frame = bytes([0x01, 0x03, 0x00, 0x00, 0x00, 0x0A])
+    h = noncrypto.crc16(frame)
+    assert h.intdigest().to_bytes(2, "little") == b"\xC5\xCD"
+    assert noncrypto.crc16_digest(frame) == b"\xCD\xC5"
+
+
+@pytest.mark.parametrize("variant", ["ccitt-false", "modbus", "xmodem", "usb"])
+def test_crc16_incremental(variant):
+    """Test update() par morceaux, copy() et reset()"""
+    data = bytes(range(256)) * 4
+    expected = noncrypto.crc16_intdigest(data, variant)
+    h = noncrypto.crc16(variant=variant)
+    for start in range(0, len(data), 5):
+        h.update(data[start:start + 5])
+    assert h.intdigest() == expected
+    c = h.copy()
+    h.reset()
+    assert h.intdigest() == noncrypto.crc16(b"", variant).intdigest()
+    assert c.intdigest() == expected
+    assert (c.name, c.digest_size, c.block_size) == ("crc16", 2, 1)
+
+
+def test_crc16_invalid_variant():
+    """Test le rejet d'un préréglage inconnu"""
+    with pytest.raises(ValueError, match="unknown variant"):
+        noncrypto.crc16(b"", "ccitt")
+
+
+def test_adler32_matches_zlib():
+    """Test adler32 contre zlib.adler32 pour des entrées aléatoires découpées"""
+    rng = random.Random(1950)
+    for _ in range(30):
+        data = rng.randbytes(rng.randrange(0, 20000))
+        expected = zlib.adler32(data)
+        h = noncrypto.adler32()
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 7000)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.intdigest() == expected
+        assert h.digest() == expected.to_bytes(4, "big")
+        assert noncrypto.adler32_intdigest(data) == expected
+    assert noncrypto.adler32(b"").intdigest() == 1
+    assert noncrypto.adler32(b"Wikipedia").hexdigest() == "11e60398"
+
+
+def test_adler32_initial_value():
+    """Test initial comme le paramètre value de zlib.adler32, y compris hors de l'intervalle réduit"""
+    data = bytes(range(256)) * 30
+    partial = zlib.adler32(data[:1000])
+    assert noncrypto.adler32(data[1000:], partial).intdigest() == zlib.adler32(data)
+    for value in (0, 0xFFFFFFFF, 0xFFF1FFF1):
+        for chunk in (b"", b"x", data):
+            assert noncrypto.adler32_intdigest(chunk, initial=value) == zlib.adler32(chunk, value)
+    h = noncrypto.adler32(b"more", initial=partial)
+    h.reset()
+    assert h.intdigest() == partial
+    assert (h.name, h.digest_size, h.block_size) == ("adler32", 4, 1)
+
+
+def test_adler32_combine():
+    """Test combine() contre le calcul sur la concaténation"""
+    rng = random.Random(32)
+    for _ in range(30):
+        first = rng.randbytes(rng.randrange(0, 5000))
+        second = rng.randbytes(rng.choice([0, 1, rng.randrange(0, 200_000)]))
+        combined = noncrypto.adler32.combine(
+            zlib.adler32(first), noncrypto.adler32(second).intdigest(), len(second))
+        assert combined == zlib.adler32(first + second)
+    with pytest.raises(OverflowError):
+        noncrypto.adler32.combine(1, 1, -1)
@@ -0,0 +1,92 @@
+"""Tests pour cSHAKE128 et cSHAKE256 (NIST SP 800-185)"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+SAMPLE_DATA = [bytes([0, 1, 2, 3]), bytes(range(200))]
+
+
+@pytest.mark.parametrize("cls,data,length,expected", [
+    (RsHash.cSHAKE128, SAMPLE_DATA[0], 32,
+     "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5"),
+    (RsHash.cSHAKE128, SAMPLE_DATA[1], 32,
+     "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b"),
+    (RsHash.cSHAKE256, SAMPLE_DATA[0], 64,
+     "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1"
+     "64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c"),
+    (RsHash.cSHAKE256, SAMPLE_DATA[1], 64,
+     "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917"
+     "27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb"),
+])
+def test_cshake_sp800_185_samples(cls, data, length, expected):
+    """Test les exemples de SP 800-185 (chaîne de personnalisation "Email Signature")"""
+    h = cls(data, custom=b"Email Signature")
+    assert h.hexdigest(length) == expected
+
+
+@pytest.mark.parametrize("cls,shake", [
+    (RsHash.cSHAKE128, hashlib.shake_128),
+    (RsHash.cSHAKE256, hashlib.shake_256),
+])
+def test_cshake_empty_strings_is_shake(cls, shake):
+    """Test que cSHAKE sans N ni S est exactement SHAKE"""
+    assert cls(b"abc").digest(100) == shake(b"abc").digest(100)
+    assert cls(b"abc", function_name=b"", custom=b"").digest(100) == shake(b"abc").digest(100)
+
+
+def test_cshake_customization_separates_domains():
+    """Test que N et S changent la sortie"""
+    plain = RsHash.cSHAKE128(b"abc").digest(32)
+    with_n = RsHash.cSHAKE128(b"abc", function_name=b"N").digest(32)
+    with_s = RsHash.cSHAKE128(b"abc", custom=b"N").digest(32)
+    assert len({plain, with_n, with_s}) == 3
+
+
+def test_cshake_incremental_update():
+    """Test update() incrémental et digest(length) cohérents"""
+    data = SAMPLE_DATA[1]
+    h = RsHash.cSHAKE256(custom=bytearray(b"Email Signature"))
+    for start in range(0, len(data), 7):
+        h.update(data[start:start + 7])
+    long = h.digest(300)
+    assert long[:64].hex() == (
+        "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917"
+        "27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb"
+    )
+    assert h.digest(17) == long[:17]
+
+
+def test_cshake_copy_keeps_customization():
+    """Test que copy() conserve N et S"""
+    h1 = RsHash.cSHAKE128(custom=b"Email Signature")
+    h2 = h1.copy()
+    h2.update(SAMPLE_DATA[0])
+    assert h2.hexdigest(32) == "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5"
+
+
+def test_cshake_arguments():
+    """Test que N et S sont nommés uniquement et refusent str"""
+    with pytest.raises(TypeError):
+        RsHash.cSHAKE128(b"abc", b"N")
+    with pytest.raises(TypeError):
+        RsHash.cSHAKE128(custom="Email Signature")
+    with pytest.raises(TypeError):
+        RsHash.cSHAKE128(b"abc").digest()
+
+
+@pytest.mark.parametrize("name,func,cls,block_size", [
+    ("cshake_128", RsHash.cshake128, RsHash.cSHAKE128, 168),
+    ("cshake_256", RsHash.cshake256, RsHash.cSHAKE256, 136),
+])
+def test_cshake_constructors(name, func, cls, block_size):
+    """Test les fonctions cshake128/cshake256, new() et les propriétés"""
+    expected = cls(b"abc", custom=b"S").digest(32)
+    assert func(b"abc", custom=b"S").digest(32) == expected
+    h = RsHash.new(name, b"abc", custom=b"S")
+    assert type(h) is cls
+    assert h.digest(32) == expected
+    assert h.name == name
+    assert h.digest_size == 0
+    assert h.block_size == block_size
+    assert name in RsHash.algorithms_available
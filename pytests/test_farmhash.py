@@ -0,0 +1,123 @@
+"""Tests pour FarmHash Fingerprint64 et CityHash64 du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+# Données pseudo-aléatoires : octet de poids fort d'un générateur congruentiel 64 bits
+def lcg_bytes(size):
+    state, out = 1, bytearray(size)
+    for i in range(size):
+        state = (state * 6364136223846793005 + 1442695040888963407) % 2**64
+        out[i] = state >> 56
+    return bytes(out)
+
+
+LCG_DATA = lcg_bytes(2100)
+
+# Fingerprint64 de LCG_DATA[n % 13:][:n], calculés avec farmhash.cc de référence
+FINGERPRINT64_REFERENCE = [
+    (0, 0x9AE16A3B2F90404F),
+    (1, 0xC17A3E9CA9CC9264),
+    (12, 0x2E8D2B5C8A3BDF70),
+    (64, 0xAD13B44C032349A1),
+    (128, 0x9E556259AD85AECD),
+    (161, 0x5B336ADF8EE3A1A0),
+    (192, 0x37969E8553819A63),
+    (256, 0x899031545A9295CF),
+    (257, 0xE63585C61CAAC5AB),
+    (362, 0x293BDD25C98311F5),
+    (461, 0xB3B0672E0449E5AD),
+    (532, 0x3C84E758762BBD0C),
+    (855, 0x40C0773D09D72DCC),
+    (894, 0x1FC6EB7894040AAC),
+    (975, 0x58F487A71814B964),
+    (996, 0x92E01EB773196A28),
+    (1154, 0x03CE8D4E2395204E),
+    (1183, 0xB0F481A52CCBB79B),
+    (1347, 0xC31392D32F344491),
+    (1463, 0x037D58BC51B05E9E),
+    (1564, 0x21ABB25370B49DB7),
+    (1744, 0x6FF09A02271298EE),
+    (2045, 0xE61EA1AB076E58B9),
+    (2048, 0x5CD4C457DFFF273C),
+]
+
+
+@pytest.mark.parametrize("text,expected", [
+    ("1footrue", -1541654101129638711),
+    ("2applefalse", 2794438866806483259),
+    ("3true", -4880158226897771312),
+])
+def test_fingerprint64_bigquery_examples(text, expected):
+    """Test les exemples de FARM_FINGERPRINT de la documentation BigQuery"""
+    data = text.encode()
+    assert noncrypto.farmhash_fingerprint64(data, signed=True) == expected
+    assert noncrypto.farmhash_fingerprint64(data) == expected % 2**64
+
+
+@pytest.mark.parametrize("length,expected", FINGERPRINT64_REFERENCE)
+def test_fingerprint64_reference(length, expected):
+    """Test contre farmhash.cc, y compris les longueurs multiples de 64 et au-delà de 256 octets"""
+    start = length % 13
+    data = LCG_DATA[start:start + length]
+    assert noncrypto.farmhash_fingerprint64(data) == expected
+    assert noncrypto.farmhash_fingerprint64(memoryview(bytearray(data))) == expected
+
+
+def test_cityhash64_reference():
+    """Test les lignes de city-test.cc (v1.1) pour CityHash64 et CityHash64WithSeed"""
+    k0 = 0xC3A5C85C97CB3127
+    a, b, data = 9, 777, bytearray(200 * 200 + 200)
+    for i in range(len(data)):
+        a = (a + b) % 2**64
+        b = (b + a) % 2**64
+        a = ((a ^ (a >> 41)) * k0) % 2**64
+        b = (((b ^ (b >> 41)) * k0) + i) % 2**64
+        data[i] = (b >> 37) & 0xFF
+    rows = [
+        (0, 0x9AE16A3B2F90404F, 0x75106DB890237A4A),
+        (5, 0x831F448BDC5600B3, 0x62A24BE3120A6919),
+        (17, 0x6ABBFDE37EE03B5B, 0x83FEBF188D2CC113),
+        (40, 0x4EC0B54CF1566AFF, 0x30D2C7269B206BF4),
+        (65, 0x105191E0EC8F7F60, 0x5918DBFCCA971E79),
+        (128, 0xB2E23E8116C2BA9F, 0x7E4D9C0060101151),
+        (200, 0x07FC98006E25CAC9, 0x77FEE0484CDA86A7),
+    ]
+    for i, plain, seeded in rows:
+        chunk = bytes(data[i * i:i * i + i])
+        assert noncrypto.cityhash64(chunk) == plain
+        assert noncrypto.cityhash64(chunk, 1234567) == seeded
+        assert noncrypto.cityhash64(chunk, seed=1234567, signed=True) == (
+            seeded - 2**64 if seeded >= 2**63 else seeded)
+
+
+def test_fingerprint64_short_inputs_match_cityhash64():
+    """Test que les deux fonctions coïncident jusqu'à 32 octets et divergent au-delà"""
+    rng = random.Random(32)
+    for length in range(33):
+        data = rng.randbytes(length)
+        assert noncrypto.farmhash_fingerprint64(data) == noncrypto.cityhash64(data)
+    data = rng.randbytes(33)
+    assert noncrypto.farmhash_fingerprint64(data) != noncrypto.cityhash64(data)
+
+
+def test_fingerprint64_matches_pyfarmhash():
+    """Test différentiel contre le paquet pyfarmhash pour des longueurs aléatoires de 0 à 2048"""
+    farmhash = pytest.importorskip("farmhash")
+    rng = random.Random(64)
+    for _ in range(200):
+        data = rng.randbytes(rng.randrange(0, 2049))
+        assert noncrypto.farmhash_fingerprint64(data) == farmhash.fingerprint64(data)
+
+
+def test_farmhash_argument_errors():
+    """Test le rejet de signed en positionnel et des graines hors limites"""
+    with pytest.raises(TypeError):
+        noncrypto.farmhash_fingerprint64(b"", True)
+    with pytest.raises(OverflowError):
+        noncrypto.cityhash64(b"", -1)
+    with pytest.raises(TypeError):
+        noncrypto.cityhash64("text")
@@ -0,0 +1,110 @@
+"""Tests pour file_digest (compatible avec hashlib.file_digest)"""
+import hashlib
+import io
+import os
+import socket
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = os.urandom(100000)
+
+
+def test_file_digest_bytesio():
+    """Test un io.BytesIO, haché en entier via getbuffer()"""
+    hasher = RsHash.file_digest(io.BytesIO(DATA), "sha256")
+    assert isinstance(hasher, RsHash.SHA256)
+    assert hasher.hexdigest() == hashlib.sha256(DATA).hexdigest()
+    stream = io.BytesIO(DATA)
+    stream.read(10)
+    # Comme hashlib, la position courante est ignorée
+    assert RsHash.file_digest(stream, "sha256").digest() == \
+        hashlib.file_digest(stream, "sha256").digest()
+
+
+@pytest.mark.parametrize("buffering", [-1, 0])
+def test_file_digest_real_file(tmp_path, buffering):
+    """Test un fichier réel ouvert en lecture binaire, avec et sans tampon"""
+    path = tmp_path / "data"
+    path.write_bytes(DATA)
+    for bufsize in (1, 4096, 2**18):
+        with open(path, "rb", buffering=buffering) as f:
+            hasher = RsHash.file_digest(f, "sha512", _bufsize=bufsize)
+        assert hasher.hexdigest() == hashlib.sha512(DATA).hexdigest()
+    with open(path, "rb", buffering=buffering) as f:
+        assert RsHash.file_digest(f, "md5").hexdigest() == hashlib.md5(DATA).hexdigest()
+
+
+def test_file_digest_empty(tmp_path):
+    """Test un fichier vide"""
+    path = tmp_path / "empty"
+    path.write_bytes(b"")
+    with open(path, "rb") as f:
+        assert RsHash.file_digest(f, "sha256").hexdigest() == hashlib.sha256().hexdigest()
+
+
+def test_file_digest_socket():
+    """Test l'objet renvoyé par socket.makefile("rb")"""
+    left, right = socket.socketpair()
+    sender = threading.Thread(target=lambda: (right.sendall(DATA), right.close()))
+    sender.start()
+    try:
+        with left.makefile("rb") as f:
+            hasher = RsHash.file_digest(f, "sha256", _bufsize=1000)
+    finally:
+        sender.join()
+        left.close()
+    assert hasher.hexdigest() == hashlib.sha256(DATA).hexdigest()
+
+
+def test_file_digest_callables():
+    """Test un constructeur ou une fonction renvoyant un objet de hachage"""
+    assert RsHash.file_digest(io.BytesIO(DATA), RsHash.SHA3_256).digest() == \
+        hashlib.sha3_256(DATA).digest()
+    hasher = RsHash.file_digest(io.BytesIO(DATA), lambda: RsHash.BLAKE2b(digest_size=32))
+    assert hasher.digest() == hashlib.blake2b(DATA, digest_size=32).digest()
+    # L'objet renvoyé n'est pas finalisé et reste utilisable
+    hasher.update(b"more")
+    assert hasher.digest() == hashlib.blake2b(DATA + b"more", digest_size=32).digest()
+    assert RsHash.file_digest(io.BytesIO(DATA), hashlib.sha1).digest() == \
+        hashlib.sha1(DATA).digest()
+
+
+def test_file_digest_errors(tmp_path):
+    """Test les fichiers refusés, comme par hashlib.file_digest"""
+    path = tmp_path / "data"
+    path.write_bytes(DATA)
+    with open(path, "r") as f:
+        with pytest.raises(ValueError, match="binary reading mode"):
+            RsHash.file_digest(f, "sha256")
+        with pytest.raises(ValueError):
+            hashlib.file_digest(f, "sha256")
+    with open(path, "ab") as f:
+        with pytest.raises(ValueError, match="binary reading mode"):
+            RsHash.file_digest(f, "sha256")
+    with pytest.raises(ValueError, match="binary reading mode"):
+        RsHash.file_digest(b"not a file", "sha256")
+    with pytest.raises(ValueError, match="unsupported hash type"):
+        RsHash.file_digest(io.BytesIO(DATA), "nope")
+    with pytest.raises(TypeError):
+        RsHash.file_digest(io.BytesIO(DATA), 42)
+    with open(path, "rb") as f:
+        with pytest.raises(ValueError, match="_bufsize"):
+            RsHash.file_digest(f, "sha256", _bufsize=0)
+        with pytest.raises(TypeError):
+            RsHash.file_digest(f, "sha256", 4096)
+
+
+def test_file_digest_non_blocking():
+    """Test un fichier non bloquant sans données disponibles"""
+    left, right = socket.socketpair()
+    try:
+        left.setblocking(False)
+        with left.makefile("rb", buffering=0) as f:
+            with pytest.raises(BlockingIOError):
+                RsHash.file_digest(f, "sha256")
+    finally:
+        left.close()
+        right.close()
@@ -0,0 +1,134 @@
+"""Tests pour les sommes de Fletcher du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+WORD_SIZES = {16: 1, 32: 2, 64: 4}
+
+
+def fletcher_reference(bits, data):
+    """Implémentation directe : mots little-endian complétés par des zéros, réduction à chaque mot"""
+    size = WORD_SIZES[bits]
+    modulus = (1 << (8 * size)) - 1
+    data = data + b"\x00" * (-len(data) % size)
+    s1 = s2 = 0
+    for i in range(0, len(data), size):
+        s1 = (s1 + int.from_bytes(data[i:i + size], "little")) % modulus
+        s2 = (s2 + s1) % modulus
+    return (s2 << (8 * size)) | s1
+
+
+@pytest.mark.parametrize("bits,data,expected", [
+    (16, b"abcde", 0xC8F0),
+    (16, b"abcdef", 0x2057),
+    (16, b"abcdefgh", 0x0627),
+    (32, b"abcde", 0xF04FC729),
+    (32, b"abcdef", 0x56502D2A),
+    (32, b"abcdefgh", 0xEBE19591),
+    (64, b"abcde", 0xC8C6C527646362C6),
+    (64, b"abcdef", 0xC8C72B276463C8C6),
+    (64, b"abcdefgh", 0x312E2B28CCCAC8C6),
+    (64, b"", 0),
+])
+def test_fletcher_known_vectors(bits, data, expected):
+    """Test les exemples de référence de Wikipédia"""
+    h = getattr(noncrypto, f"fletcher{bits}")(data)
+    assert h.intdigest() == expected == fletcher_reference(bits, data)
+    assert h.digest() == expected.to_bytes(bits // 8, "big")
+    assert h.hexdigest() == f"{expected:0{bits // 4}x}"
+    assert getattr(noncrypto, f"fletcher{bits}_intdigest")(data) == expected
+    assert getattr(noncrypto, f"fletcher{bits}_digest")(data) == expected.to_bytes(bits // 8, "big")
+
+
+@pytest.mark.parametrize("bits", [16, 32, 64])
+def test_fletcher_random_chunks_match_reference(bits):
+    """Test update() par morceaux de tailles impaires contre l'implémentation de référence"""
+    cls = getattr(noncrypto, f"fletcher{bits}")
+    rng = random.Random(bits)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 10000))
+        h = cls()
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 11)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.intdigest() == fletcher_reference(bits, data)
+
+
+@pytest.mark.parametrize("bits", [16, 32, 64])
+def test_fletcher_long_saturated_stream(bits):
+    """Test un long flux d'octets 0xFF, qui maximise les sommes avant réduction"""
+    data = b"\xff" * 300_000 + b"\x01\x02\x03"
+    h = getattr(noncrypto, f"fletcher{bits}")()
+    for start in range(0, len(data), 65537):
+        h.update(data[start:start + 65537])
+    assert h.intdigest() == fletcher_reference(bits, data)
+
+
+@pytest.mark.parametrize("bits", [16, 32, 64])
+def test_fletcher_properties_copy_reset(bits):
+    """Test les propriétés, copy() au milieu d'un mot et reset()"""
+    cls = getattr(noncrypto, f"fletcher{bits}")
+    h = cls(b"abc")
+    c = h.copy()
+    c.update(b"defgh")
+    assert c.intdigest() == fletcher_reference(bits, b"abcdefgh")
+    assert h.intdigest() == fletcher_reference(bits, b"abc")
+    h.reset()
+    assert h.intdigest() == 0
+    assert (h.name, h.digest_size, h.block_size) == (f"fletcher{bits}", bits // 8, bits // 16)
+
+
+def fletcher4_reference(data):
+    """Boucle fletcher_4_native de ZFS : quatre sommes de mots de 32 bits modulo 2^64"""
+    data = data + b"\x00" * (-len(data) % 4)
+    a = b = c = d = 0
+    for i in range(0, len(data), 4):
+        a = (a + int.from_bytes(data[i:i + 4], "little")) % 2**64
+        b = (b + a) % 2**64
+        c = (c + b) % 2**64
+        d = (d + c) % 2**64
+    return a, b, c, d
+
+
+@pytest.mark.parametrize("data,sums", [
+    (b"", (0, 0, 0, 0)),
+    (b"abcd", (0x64636261,) * 4),
+    (b"abcdefgh", (0xCCCAC8C6, 0x1312E2B27, 0x195918D88, 0x1F9F4EFE9)),
+    (b"\xff" * 131072,
+     (0x7FFFFFFF8000, 0x20003FFFDFFFC000, 0x75557AAA8AAA8000, 0xC7556D5537FFE000)),
+])
+def test_fletcher4_known_vectors(data, sums):
+    """Test fletcher4 : les sommes a, b, c, d en mots big-endian, comme zdb les affiche"""
+    assert fletcher4_reference(data) == sums
+    expected = b"".join(s.to_bytes(8, "big") for s in sums)
+    h = noncrypto.fletcher4(data)
+    assert h.digest() == expected == noncrypto.fletcher4_digest(data)
+    assert h.hexdigest() == "".join(f"{s:016x}" for s in sums)
+    assert h.intdigest() == int.from_bytes(expected, "big") == noncrypto.fletcher4_intdigest(data)
+
+
+def test_fletcher4_random_chunks_copy_reset():
+    """Test update() par morceaux, copy() au milieu d'un mot, reset() et les propriétés"""
+    rng = random.Random(4)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 10000))
+        h = noncrypto.fletcher4()
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 11)
+            h.update(data[pos:pos + step])
+            pos += step
+        expected = b"".join(s.to_bytes(8, "big") for s in fletcher4_reference(data))
+        assert h.digest() == expected
+    h = noncrypto.fletcher4(b"abc")
+    c = h.copy()
+    c.update(b"defgh")
+    assert c.digest() == noncrypto.fletcher4_digest(b"abcdefgh")
+    h.reset()
+    assert h.intdigest() == 0
+    assert (h.name, h.digest_size, h.block_size) == ("fletcher4", 32, 4)
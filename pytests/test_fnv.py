@@ -0,0 +1,68 @@
+"""Tests pour FNV-1 et FNV-1a du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+OFFSETS = {32: 0x811C9DC5, 64: 0xCBF29CE484222325, 128: 0x6C62272E07BB014262B821756295C58D}
+PRIMES = {32: 0x01000193, 64: 0x100000001B3, 128: 0x0000000001000000000000000000013B}
+
+VECTORS = [
+    ("fnv1_32", b"a", 0x050C5D7E),
+    ("fnv1_32", b"foobar", 0x31F0B262),
+    ("fnv1a_32", b"a", 0xE40C292C),
+    ("fnv1a_32", b"foobar", 0xBF9CF968),
+    ("fnv1_64", b"a", 0xAF63BD4C8601B7BE),
+    ("fnv1_64", b"foobar", 0x340D8765A4DDA9C2),
+    ("fnv1a_64", b"a", 0xAF63DC4C8601EC8C),
+    ("fnv1a_64", b"foobar", 0x85944171F73967E8),
+    ("fnv1_128", b"a", 0xD228CB69101A8CAF78912B704E4A141E),
+    ("fnv1_128", b"foobar", 0x7896BFEA9C3C64BF6DC58353D2C293AA),
+    ("fnv1a_128", b"a", 0xD228CB696F1A8CAF78912B704E4A8964),
+    ("fnv1a_128", b"foobar", 0x343E1662793C64BF6F0D3597BA446F18),
+] + [(f"fnv1{v}_{bits}", b"", OFFSETS[bits]) for v in ("", "a") for bits in (32, 64, 128)]
+
+
+def fnv_reference(name, data):
+    """Implémentation directe en Python"""
+    bits = int(name.split("_")[1])
+    mask = (1 << bits) - 1
+    h = OFFSETS[bits]
+    for byte in data:
+        if name.startswith("fnv1a"):
+            h = ((h ^ byte) * PRIMES[bits]) & mask
+        else:
+            h = ((h * PRIMES[bits]) & mask) ^ byte
+    return h
+
+
+@pytest.mark.parametrize("name,data,expected", VECTORS)
+def test_fnv_published_vectors(name, data, expected):
+    """Test les vecteurs publiés pour chaque largeur et variante"""
+    size = int(name.split("_")[1]) // 8
+    h = getattr(noncrypto, name)(data)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(size, "big")
+    assert getattr(noncrypto, f"{name}_intdigest")(data) == expected
+    assert getattr(noncrypto, f"{name}_digest")(data) == expected.to_bytes(size, "big")
+    assert getattr(noncrypto, f"{name}_hexdigest")(data) == f"{expected:0{size * 2}x}"
+
+
+@pytest.mark.parametrize("name", [f"fnv1{v}_{bits}" for v in ("", "a") for bits in (32, 64, 128)])
+def test_fnv_incremental_matches_reference(name):
+    """Test update() par morceaux contre l'implémentation de référence, copy() et reset()"""
+    cls = getattr(noncrypto, name)
+    rng = random.Random(name)
+    for _ in range(10):
+        data = rng.randbytes(rng.randrange(0, 300))
+        h = cls()
+        for start in range(0, len(data), 7):
+            h.update(data[start:start + 7])
+        assert h.intdigest() == fnv_reference(name, data)
+    c = h.copy()
+    h.reset()
+    assert h.intdigest() == OFFSETS[int(name.split("_")[1])]
+    assert c.intdigest() == fnv_reference(name, data)
+    assert (c.name, c.digest_size, c.block_size) == (name, int(name.split("_")[1]) // 8, 1)
@@ -0,0 +1,74 @@
+"""Tests pour GOST R 34.11-94 (paramètres de test et CryptoPro)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+M32 = b"This is message, length=32 bytes"
+M50 = b"Suppose the original message has length = 50 bytes"
+
+
+@pytest.mark.parametrize("data,expected", [
+    (M32, "b1c466d37519b82e8319819ff32595e047a28cb6f83eff1c6916a815a637fffa"),
+    (M50, "471aba57a60a770d3a76130635c1fbea4ef14de51f78b4ae57dd893b62f55208"),
+    (b"", "ce85b99cc46752fffee35cab9a7b0278abb4c2d2055cff685af4912c49490f8d"),
+    (b"abc", "f3134348c44fb1b2a277729e2285ebb5cb5e0f29c975bc753b70497c06a4d51d"),
+    (b"U" * 128, "53a3a3ed25180cef0c1d85a074273e551c25660a87062a52d926a9e8fe5733a4"),
+])
+def test_gost94_test_params(data, expected):
+    """Test les exemples de la RFC 5831 avec les S-boxes de test"""
+    assert RsHash.GOST94(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (M32, "2cefc2f7b7bdc514e18ea57fa74ff357e7fa17d652c75f69cb1be7893ede48eb"),
+    (M50, "c3730c5cbccacf915ac292676f21e8bd4ef75331d9405e5f1a61dc3130a65011"),
+    (b"", "981e5f3ca30c841487830f84fb433e13ac1101569b9c13584ac483234cd656c0"),
+    (b"abc", "b285056dbf18d7392d7677369524dd14747459ed8143997e163b2986f92fd42c"),
+])
+def test_gost94_cryptopro_params(data, expected):
+    """Test les mêmes messages avec les S-boxes CryptoPro"""
+    assert RsHash.GOST94_CryptoPro(data).hexdigest() == expected
+
+
+def test_gost94_streaming():
+    """Test GOST en plusieurs update() autour de la limite de bloc de 32 octets"""
+    data = bytes(range(256)) * 3
+    for cls in (RsHash.GOST94, RsHash.GOST94_CryptoPro):
+        for step in (1, 31, 32, 33, 100):
+            h = cls()
+            for start in range(0, len(data), step):
+                h.update(data[start:start + step])
+            assert h.digest() == cls(data).digest()
+        h = cls(data[:50])
+        c = h.copy()
+        c.update(data[50:])
+        assert c.digest() == cls(data).digest()
+
+
+@pytest.mark.parametrize("name,cls", [
+    ("gost94", "GOST94"),
+    ("gost94_cryptopro", "GOST94_CryptoPro"),
+])
+def test_gost94_properties_and_new(name, cls):
+    """Test les propriétés et new("gost94") / new("gost94_cryptopro")"""
+    h = RsHash.new(name.upper(), M32)
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == 32
+    assert h.block_size == 32
+    assert h.digest() == getattr(RsHash, cls)(M32).digest()
+    assert name in RsHash.algorithms_available
+
+
+def test_gost94_fips_mode():
+    """Test qu'en mode FIPS, GOST exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.GOST94(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("gost94_cryptopro")
+        h = RsHash.new("gost94", M32, usedforsecurity=False)
+        assert h.hexdigest().startswith("b1c466d3")
+    finally:
+        RsHash.set_fips_mode(False)
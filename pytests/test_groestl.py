@@ -0,0 +1,69 @@
+"""Tests pour Grøstl-256/512 (version finale de la soumission SHA-3)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+FOX = b"The quick brown fox jumps over the lazy dog"
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "1a52d11d550039be16107f9c58db9ebcc417f16f736adb2502567119f0083467"),
+    (FOX, "8c7ad62eb26a21297bc39c2d7293b4bd4d3399fa8afab29e970471739e28b301"),
+    (FOX + b".", "f48290b1bcacee406a0429b993adb8fb3d065f4b09cbcdb464a631d4a0080aaf"),
+])
+def test_groestl256_vectors(data, expected):
+    """Test Grøstl-256 sur le message vide et "The quick brown fox" """
+    assert RsHash.Groestl256(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"",
+     "6d3ad29d279110eef3adbd66de2a0345a77baede1557f5d099fce0c03d6dc2ba"
+     "8e6d4a6633dfbd66053c20faa87d1a11f39a7fbe4a6c2f009801370308fc4ad8"),
+    (FOX,
+     "badc1f70ccd69e0cf3760c3f93884289da84ec13c70b3d12a53a7a8a4a513f99"
+     "715d46288f55e1dbf926e6d084a0538e4eebfc91cf2b21452921ccde9131718d"),
+])
+def test_groestl512_vectors(data, expected):
+    """Test Grøstl-512 sur le message vide et "The quick brown fox" """
+    assert RsHash.Groestl512(data).hexdigest() == expected
+
+
+def test_groestl_streaming():
+    """Test Grøstl en plusieurs update() autour de la limite de padding"""
+    data = bytes(range(256)) * 3
+    for cls in (RsHash.Groestl256, RsHash.Groestl512):
+        for step in (1, 55, 56, 57, 119, 120, 128):
+            h = cls()
+            for start in range(0, len(data), step):
+                h.update(data[start:start + step])
+            assert h.digest() == cls(data).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", [
+    ("groestl256", "Groestl256", 32, 64),
+    ("groestl512", "Groestl512", 64, 128),
+])
+def test_groestl_properties_and_new(name, cls, digest_size, block_size):
+    """Test les propriétés et new("groestlN"), y compris l'orthographe Grøstl"""
+    h = RsHash.new(name.replace("groestl", "Grøstl-"), b"abc")
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert h.digest() == RsHash.new(name, b"abc").digest()
+    assert name in RsHash.algorithms_available
+
+
+def test_groestl_fips_mode():
+    """Test qu'en mode FIPS, Grøstl exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.Groestl256(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("groestl512")
+        h = RsHash.new("groestl256", FOX, usedforsecurity=False)
+        assert h.hexdigest().startswith("8c7ad62e")
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,62 @@
+"""Tests pour HAS-160 (TTAS.KO-12.0011/R2)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "307964ef34151d37c8047adec7ab50f4ff89762d"),
+    (b"a", "4872bcbc4cd0f0a9dc7c2f7045e5b43b6c830db8"),
+    (b"abc", "975e810488cf2a3d49838478124afce4b1c78804"),
+    (b"message digest", "2338dbc8638d31225f73086246ba529f96710bc6"),
+    (b"abcdefghijklmnopqrstuvwxyz", "596185c9ab6703d0d0dbb98702bc0f5729cd1d3c"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "cb5d7efbca2f02e0fb7167cabb123af5795764e5"),
+    (b"1234567890" * 8, "07f05c8c0773c55ca3a5a695ce6aca4c438911b5"),
+])
+def test_has160_vectors(data, expected):
+    """Test les vecteurs de la norme TTA"""
+    assert RsHash.HAS160(data).hexdigest() == expected
+
+
+def test_has160_million_a():
+    """Test HAS-160 sur un million de "a", en blocs de 1000"""
+    h = RsHash.HAS160()
+    for _ in range(1000):
+        h.update(b"a" * 1000)
+    assert h.hexdigest() == "d6ad6f0608b878da9b87999c2525cc84f4c9f18d"
+
+
+def test_has160_streaming():
+    """Test HAS-160 en plusieurs update() autour de la limite de padding"""
+    data = bytes(range(256)) * 3
+    for step in (1, 55, 56, 57, 63, 64, 65):
+        h = RsHash.HAS160()
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.digest() == RsHash.HAS160(data).digest()
+
+
+def test_has160_properties_and_new():
+    """Test les propriétés et new("has160")"""
+    h = RsHash.new("HAS-160", b"abc")
+    assert type(h) is RsHash.HAS160
+    assert h.name == "has160"
+    assert h.digest_size == 20
+    assert h.block_size == 64
+    assert h.hexdigest() == "975e810488cf2a3d49838478124afce4b1c78804"
+    assert "has160" in RsHash.algorithms_available
+
+
+def test_has160_fips_mode():
+    """Test qu'en mode FIPS, HAS-160 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.HAS160(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("has160")
+        h = RsHash.new("has160", b"abc", usedforsecurity=False)
+        assert h.hexdigest().startswith("975e8104")
+    finally:
+        RsHash.set_fips_mode(False)
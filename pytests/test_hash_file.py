@@ -0,0 +1,125 @@
+"""Tests pour hash_file (lecture de fichiers en Rust, GIL relâché)"""
+import hashlib
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+CHUNK = 4096
+
+
+def write(tmp_path, name, data):
+    """Écrit data dans un fichier temporaire et renvoie son chemin"""
+    path = tmp_path / name
+    path.write_bytes(data)
+    return path
+
+
+def test_hash_file_empty(tmp_path):
+    """Test le hachage d'un fichier vide"""
+    path = write(tmp_path, "empty", b"")
+    assert RsHash.hash_file(path) == hashlib.sha256(b"").hexdigest()
+    assert RsHash.hash_file(path, digest=True) == hashlib.sha256(b"").digest()
+    assert RsHash.hash_file(path, "md5", usedforsecurity=False) == hashlib.md5(b"").hexdigest()
+
+
+@pytest.mark.parametrize("size", [1, CHUNK - 1, CHUNK, CHUNK + 1, 2 * CHUNK, 3 * CHUNK + 7])
+def test_hash_file_chunk_boundaries(tmp_path, size):
+    """Test les tailles autour des limites de bloc de lecture"""
+    data = os.urandom(size)
+    path = write(tmp_path, "data", data)
+    for chunk_size in (1, 7, CHUNK, CHUNK + 1, 1 << 20):
+        assert RsHash.hash_file(path, chunk_size=chunk_size) == hashlib.sha256(data).hexdigest()
+    assert RsHash.hash_file(path, "sha512", CHUNK, digest=True) == hashlib.sha512(data).digest()
+
+
+@pytest.mark.parametrize("algorithm", ["sha1", "sha224", "sha512", "sha512_224", "sha3_256",
+                                       "blake2b", "blake2s", "SHA-256"])
+def test_hash_file_algorithms(tmp_path, algorithm):
+    """Test différentiel contre hashlib pour plusieurs algorithmes"""
+    data = os.urandom(100000)
+    path = write(tmp_path, "data", data)
+    assert RsHash.hash_file(path, algorithm) == hashlib.new(algorithm.replace("-", "").lower(),
+                                                            data).hexdigest()
+    assert RsHash.hash_file(path, algorithm) == RsHash.new(algorithm, data).hexdigest()
+
+
+def test_hash_file_path_types(tmp_path):
+    """Test les chemins str, bytes et os.PathLike"""
+    data = b"path types"
+    path = write(tmp_path, "café.bin", data)
+    expected = hashlib.sha256(data).hexdigest()
+    assert RsHash.hash_file(path) == expected
+    assert RsHash.hash_file(str(path)) == expected
+    assert RsHash.hash_file(os.fsencode(path)) == expected
+
+    class Custom:
+        def __fspath__(self):
+            return str(path)
+
+    assert RsHash.hash_file(Custom()) == expected
+    with pytest.raises(TypeError):
+        RsHash.hash_file(42)
+
+
+def test_hash_file_os_errors(tmp_path):
+    """Test que les erreurs du système sont levées comme par open()"""
+    missing = tmp_path / "missing"
+    with pytest.raises(FileNotFoundError) as info:
+        RsHash.hash_file(missing)
+    assert info.value.filename == missing
+    assert info.value.errno == 2
+    with pytest.raises((IsADirectoryError, PermissionError)):
+        RsHash.hash_file(tmp_path)
+    if hasattr(os, "geteuid") and os.geteuid() != 0:
+        locked = write(tmp_path, "locked", b"secret")
+        locked.chmod(0)
+        try:
+            with pytest.raises(PermissionError):
+                RsHash.hash_file(locked)
+        finally:
+            locked.chmod(0o600)
+
+
+def test_hash_file_errors(tmp_path):
+    """Test les paramètres invalides"""
+    path = write(tmp_path, "data", b"data")
+    with pytest.raises(ValueError, match="unsupported"):
+        RsHash.hash_file(path, "nope")
+    with pytest.raises(ValueError, match="digest size"):
+        RsHash.hash_file(path, "shake_128")
+    for chunk_size in (0, (1 << 30) + 1):
+        with pytest.raises(ValueError, match="chunk_size"):
+            RsHash.hash_file(path, chunk_size=chunk_size)
+    with pytest.raises(OverflowError):
+        RsHash.hash_file(path, chunk_size=-1)
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.hash_file(path, "md5")
+        assert RsHash.hash_file(path, "md5", usedforsecurity=False) == \
+            hashlib.md5(b"data").hexdigest()
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+def test_hash_file_sparse_over_4gib(tmp_path):
+    """Test un fichier creux de plus de 4 Gio, lent : lancer avec RSHASH_SLOW_TESTS=1"""
+    if not os.environ.get("RSHASH_SLOW_TESTS"):
+        pytest.skip("set RSHASH_SLOW_TESTS=1 to hash a sparse file over 4 GiB")
+    path = tmp_path / "sparse"
+    size = (1 << 32) + 3
+    try:
+        with open(path, "wb") as f:
+            f.seek(size - 3)
+            f.write(b"end")
+    except OSError as err:
+        pytest.skip(f"sparse files unavailable: {err}")
+    expected = hashlib.sha256()
+    zeros = bytes(1 << 24)
+    for _ in range((size - 3) // len(zeros)):
+        expected.update(zeros)
+    expected.update(b"end")
+    assert path.stat().st_size == size
+    assert RsHash.hash_file(path) == expected.hexdigest()
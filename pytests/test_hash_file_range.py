@@ -0,0 +1,71 @@
+"""Tests pour hash_file_range (plage d'octets d'un fichier)"""
+import hashlib
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = os.urandom(3 * 4096 + 7)
+
+
+def write(tmp_path):
+    """Écrit DATA dans un fichier temporaire et renvoie son chemin"""
+    path = tmp_path / "data"
+    path.write_bytes(DATA)
+    return path
+
+
+@pytest.mark.parametrize("offset,length", [
+    (0, 0), (0, 1), (0, len(DATA)), (1, 4095), (4095, 2), (4096, 4096), (100, 9000),
+    (len(DATA) - 1, 1), (len(DATA), 0),
+])
+def test_hash_file_range(tmp_path, offset, length):
+    """Test différentiel contre le découpage du fichier en Python"""
+    path = write(tmp_path)
+    expected = hashlib.sha256(path.read_bytes()[offset:offset + length])
+    assert RsHash.hash_file_range(path, offset, length) == expected.hexdigest()
+    for chunk_size in (1, 7, 4096):
+        assert RsHash.hash_file_range(path, offset, length, chunk_size=chunk_size,
+                                      digest=True) == expected.digest()
+    assert RsHash.hash_file_range(path, offset, length, "sha512") == \
+        hashlib.sha512(DATA[offset:offset + length]).hexdigest()
+
+
+@pytest.mark.parametrize("offset", [0, 1, 4096, len(DATA) - 1, len(DATA)])
+def test_hash_file_range_to_eof(tmp_path, offset):
+    """Test length=None, jusqu'à la fin du fichier"""
+    path = write(tmp_path)
+    assert RsHash.hash_file_range(path, offset, None) == hashlib.sha256(DATA[offset:]).hexdigest()
+    assert RsHash.hash_file_range(path, offset, None, "blake2b", 1000) == \
+        hashlib.blake2b(DATA[offset:]).hexdigest()
+    if offset == 0:
+        assert RsHash.hash_file_range(path, 0, None) == RsHash.hash_file(path)
+
+
+def test_hash_file_range_short_file(tmp_path):
+    """Test les plages dépassant la fin du fichier"""
+    path = write(tmp_path)
+    size = len(DATA)
+    for offset, length in [(0, size + 1), (size - 1, 2), (size, 1), (size + 10, 0),
+                           (size + 10, 5), (1, 2**64 - 1)]:
+        with pytest.raises(ValueError, match="shorter than offset"):
+            RsHash.hash_file_range(path, offset, length)
+    with pytest.raises(ValueError, match="shorter than offset"):
+        RsHash.hash_file_range(path, size + 1, None)
+
+
+def test_hash_file_range_errors(tmp_path):
+    """Test les paramètres invalides et les erreurs du système"""
+    path = write(tmp_path)
+    with pytest.raises(FileNotFoundError):
+        RsHash.hash_file_range(tmp_path / "missing", 0, 1)
+    for offset, length in [(-1, 1), (0, -1)]:
+        with pytest.raises(OverflowError):
+            RsHash.hash_file_range(path, offset, length)
+    with pytest.raises(ValueError, match="unsupported"):
+        RsHash.hash_file_range(path, 0, 1, "nope")
+    with pytest.raises(ValueError, match="chunk_size"):
+        RsHash.hash_file_range(path, 0, 1, chunk_size=0)
+    with pytest.raises(TypeError):
+        RsHash.hash_file_range(path, 0)
@@ -0,0 +1,92 @@
+"""Tests pour HighwayHash du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+KEY = bytes(range(32))
+
+# Valeurs de référence, clé 00 01 .. 1f et données 00 01 .. n - 1 :
+# (n, HHResult64, HHResult128, HHResult256), mots de poids faible en premier
+REFERENCE = [
+    (0, 0x907a56de22c26e53, (0x0fed268f9d8ffec7, 0x33565e767f093e6f),
+     (0xdd44482ac2c874f5, 0xd946017313c7351f, 0xb3aebeccb98714ff, 0x41da233145751df4)),
+    (1, 0x7eab43aac7cddd78, (0xd6b0a8893681e7a8, 0xdc291df9eb9cdcb4),
+     (0xedb941bce45f8254, 0xe20d44ef3dcac60f, 0x72651b9bcb324a47, 0x2073624cb275e484)),
+    (4, 0xf205a46893007eda, (0x26399eb46dace49e, 0x2e922ad039319208),
+     (0xc9cfc497212be4dc, 0xa85f9df6afd2929b, 0x1fda9f211df4109e, 0x07e4277a374d4f9b)),
+    (16, 0xcfab3489f97eb832, (0x414460ffd5a401ad, 0x029ea3d5019f18c8),
+     (0xf51ad989a1b6cd1f, 0xf7f075d62a627bd9, 0x7e01d5f579f28a06, 0x1ad415c16a174d9f)),
+    (17, 0x19fe67d2c8c5c0e2, (0xc52a4b96c51c9962, 0xecb878b1169b5ea0),
+     (0x19f4cfa82ca4068e, 0x3b9d4abd3a9275b9, 0x8000b0dde9c010c6, 0x8884d50949215613)),
+    (31, 0x9fc7007ccf035a68, (0x06e7b465e8a57c29, 0x52415e3a07f5d446),
+     (0x6880e276601a644d, 0xb3728b20b10fb7da, 0xd0bd12060610d16e, 0x8aef14ef33452ef2)),
+    (32, 0xa0c964d9ecd580fc, (0x1984df66c1434aaa, 0x16fc1958f9b3e4b9),
+     (0xbce38c9039a1c3fe, 0x42d56326a3c11289, 0xe35595f764fcaea9, 0xc9b03c6bc9475a99)),
+    (33, 0x2c90f73ca03181fc, (0x111678afe0c6c36c, 0xf958b59de5a2849d),
+     (0xf60115cbf034a6e5, 0x6c36ea75bfce46d0, 0x3b17c8d382725990, 0x7edaa2ed11007a35)),
+    (63, 0xab8eebe9bf2139a0, (0x74bd7018022f3ef0, 0x3aea94a8ad5f4bcb),
+     (0xf5b1f8266a3aeb67, 0x83b040be4dec1add, 0x7fe1c8635b26fbae, 0xf4a3a447defed79f)),
+    (64, 0x75542c5d4cd2a6ff, (0x98bb1f7198d4c4f2, 0xe0bc0571de918fc8),
+     (0x90d8e6ff6ac12475, 0x1a422a196edac1f2, 0x9e3765fe1f8eb002, 0xc1bdd7c4c351cfbe)),
+]
+
+
+def words_to_bytes(words):
+    return b"".join(w.to_bytes(8, "little") for w in words)
+
+
+@pytest.mark.parametrize("n,h64,h128,h256", REFERENCE)
+def test_highwayhash_reference(n, h64, h128, h256):
+    """Test les valeurs de l'implémentation de référence pour les trois tailles"""
+    data = bytes(range(n))
+    h = noncrypto.highwayhash64(KEY, data)
+    assert h.intdigest() == h64
+    assert h.digest() == h64.to_bytes(8, "little")
+    for name, words in [("highwayhash128", h128), ("highwayhash256", h256)]:
+        expected = words_to_bytes(words)
+        h = getattr(noncrypto, name)(KEY, data)
+        assert h.digest() == expected
+        assert h.intdigest() == int.from_bytes(expected, "little")
+        assert getattr(noncrypto, f"{name}_hexdigest")(KEY, data) == expected.hex()
+
+
+def test_highwayhash_second_key():
+    """Test le second vecteur de référence (clé 1, 2, 3, 4 et 33 octets)"""
+    key = words_to_bytes([1, 2, 3, 4])
+    data = bytes(range(128, 161))
+    assert noncrypto.highwayhash64_intdigest(key, data) == 0x53C516CCE478CAD7
+
+
+@pytest.mark.parametrize("name", ["highwayhash64", "highwayhash128", "highwayhash256"])
+def test_highwayhash_incremental(name):
+    """Test update() par morceaux aléatoires, copy() et reset()"""
+    cls = getattr(noncrypto, name)
+    rng = random.Random(name)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 2000))
+        key = rng.randbytes(32)
+        h = cls(key)
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 100)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.digest() == getattr(noncrypto, f"{name}_digest")(key, data)
+    c = h.copy()
+    h.reset()
+    assert h.digest() == cls(key, b"").digest()
+    assert c.digest() == cls(key, data).digest()
+    size = int(name[len("highwayhash"):]) // 8
+    assert (c.name, c.digest_size, c.block_size) == (name, size, 32)
+
+
+@pytest.mark.parametrize("length", [0, 16, 31, 33])
+def test_highwayhash_key_length(length):
+    """Test le rejet des clés qui ne font pas 32 octets"""
+    with pytest.raises(ValueError, match="32 bytes"):
+        noncrypto.highwayhash64(bytes(length))
+    with pytest.raises(ValueError, match="32 bytes"):
+        noncrypto.highwayhash256_digest(bytes(length), b"abc")
@@ -0,0 +1,110 @@
+"""Tests pour HKDF (RFC 5869)"""
+import hashlib
+import hmac
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+def octets(first, last):
+    return bytes(range(first, last + 1))
+
+
+# Vecteurs de l'annexe A de la RFC 5869 : hash, IKM, sel, info, L, PRK, OKM.
+# Le cas 7 n'a pas de sel, ce qui équivaut à un sel vide.
+RFC5869 = [
+    ("sha256", b"\x0b" * 22, octets(0x00, 0x0c), octets(0xf0, 0xf9), 42,
+     "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+     "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"),
+    ("sha256", octets(0x00, 0x4f), octets(0x60, 0xaf), octets(0xb0, 0xff), 82,
+     "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+     "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c"
+     "59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71"
+     "cc30c58179ec3e87c14c01d5c1f3434f1d87"),
+    ("sha256", b"\x0b" * 22, b"", b"", 42,
+     "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+     "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"),
+    ("sha1", b"\x0b" * 11, octets(0x00, 0x0c), octets(0xf0, 0xf9), 42,
+     "9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243",
+     "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896"),
+    ("sha1", octets(0x00, 0x4f), octets(0x60, 0xaf), octets(0xb0, 0xff), 82,
+     "8adae09a2a307059478d309b26c4115a224cfaf6",
+     "0bd770a74d1160f7c9f12cd5912a06ebff6adcae899d92191fe4305673ba2ffe"
+     "8fa3f1a4e5ad79f3f334b3b202b2173c486ea37ce3d397ed034c7f9dfeb15c5e"
+     "927336d0441f4c4300e2cff0d0900b52d3b4"),
+    ("sha1", b"\x0b" * 22, b"", b"", 42,
+     "da8c8a73c7fa77288ec6f5e7c297786aa0d32d01",
+     "0ac1af7002b3d761d1e55298da9d0506b9ae52057220a306e07b6b87e8df21d0ea00033de03984d34918"),
+    ("sha1", b"\x0c" * 22, b"", b"", 42,
+     "2adccada18779e7c2077ad2eb19d3f3e731385dd",
+     "2c91117204d745f3500d636a62f64f0ab3bae548aa53d423b0d1f27ebba6f5e5673a081d70cce7acfc48"),
+]
+
+
+def reference_hkdf(name, ikm, salt, info, length):
+    """HKDF écrit avec le module hmac, pour les tests différentiels"""
+    prk = hmac.digest(salt or bytes(hashlib.new(name).digest_size), ikm, name)
+    okm, block, counter = b"", b"", 1
+    while len(okm) < length:
+        block = hmac.digest(prk, block + info + bytes([counter]), name)
+        okm += block
+        counter += 1
+    return okm[:length]
+
+
+@pytest.mark.parametrize("name,ikm,salt,info,length,prk,okm", RFC5869)
+def test_hkdf_rfc5869(name, ikm, salt, info, length, prk, okm):
+    """Test tous les vecteurs de la RFC 5869, y compris sel et info vides"""
+    assert RsHash.hkdf_extract(salt, ikm, hash=name).hex() == prk
+    assert RsHash.hkdf_expand(bytes.fromhex(prk), info, length, hash=name).hex() == okm
+    assert RsHash.hkdf(ikm, salt=salt, info=info, length=length, hash=name).hex() == okm
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512", "sha3_256", "blake2b", "md5"])
+def test_hkdf_matches_reference(name):
+    """Test différentiel contre une implémentation en Python pur"""
+    rng = random.Random(name)
+    for _ in range(10):
+        ikm = rng.randbytes(rng.randrange(0, 100))
+        salt = rng.randbytes(rng.randrange(0, 200))
+        info = rng.randbytes(rng.randrange(0, 50))
+        length = rng.randrange(0, 300)
+        expected = reference_hkdf(name, ikm, salt, info, length)
+        assert RsHash.hkdf(ikm, salt=salt, info=info, length=length, hash=name) == expected
+
+
+def test_hkdf_defaults():
+    """Test SHA-256, le sel et l'info vides par défaut, et les classes comme hash"""
+    expected = reference_hkdf("sha256", b"secret", b"", b"", 32)
+    assert RsHash.hkdf(b"secret", length=32) == expected
+    assert RsHash.hkdf(bytearray(b"secret"), length=32, hash=RsHash.SHA256) == expected
+    prk = RsHash.hkdf_extract(b"", b"secret")
+    assert prk == RsHash.hkdf_extract(bytes(32), b"secret")
+    assert RsHash.hkdf_expand(prk, b"", 32) == expected
+
+
+def test_hkdf_length_limit():
+    """Test la limite de 255 * HashLen octets"""
+    assert len(RsHash.hkdf(b"k", length=255 * 32)) == 8160
+    assert len(RsHash.hkdf(b"k", length=255 * 64, hash="sha512")) == 16320
+    assert RsHash.hkdf(b"k", length=0) == b""
+    with pytest.raises(ValueError, match="255"):
+        RsHash.hkdf(b"k", length=255 * 32 + 1)
+    with pytest.raises(ValueError, match="255"):
+        RsHash.hkdf_expand(bytes(20), b"", 255 * 20 + 1, hash="sha1")
+
+
+def test_hkdf_argument_errors():
+    """Test les arguments obligatoires, les str et les hash non supportés"""
+    with pytest.raises(TypeError):
+        RsHash.hkdf(b"k")
+    with pytest.raises(TypeError):
+        RsHash.hkdf(b"k", b"salt", length=32)
+    with pytest.raises(TypeError):
+        RsHash.hkdf("k", length=32)
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.hkdf(b"k", length=32, hash="shake_128")
+    with pytest.raises(OverflowError):
+        RsHash.hkdf(b"k", length=-1)
@@ -0,0 +1,258 @@
+"""Tests pour la classe HMAC"""
+import copy
+import hmac
+import random
+import warnings
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+LONG_KEY = b"\xaa" * 131
+
+# Cas de test 1 à 7 de la RFC 4231 : clé, message, HMAC-SHA-256, HMAC-SHA-512.
+# Le cas 5 ne publie que les 128 premiers bits de chaque MAC.
+RFC4231 = [
+    (b"\x0b" * 20, b"Hi There",
+     "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+     "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde"
+     "daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"),
+    (b"Jefe", b"what do ya want for nothing?",
+     "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+     "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554"
+     "9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"),
+    (b"\xaa" * 20, b"\xdd" * 50,
+     "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+     "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39"
+     "bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb"),
+    (bytes(range(1, 26)), b"\xcd" * 50,
+     "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+     "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db"
+     "a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd"),
+    (b"\x0c" * 20, b"Test With Truncation",
+     "a3b6167473100ee06e0c796c2955552b",
+     "415fad6271580a531d4179bc891d87a6"),
+    (LONG_KEY, b"Test Using Larger Than Block-Size Key - Hash Key First",
+     "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+     "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352"
+     "6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"),
+    (LONG_KEY, b"This is a test using a larger than block-size key and a larger than "
+               b"block-size data. The key needs to be hashed before being used by "
+               b"the HMAC algorithm.",
+     "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+     "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944"
+     "b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"),
+]
+
+# Fonctions à sortie extensible du registre, sans taille de digest fixe pour HMAC
+XOFS = {
+    "shake_128", "shake_256", "cshake_128", "cshake_256", "tuplehash_128", "tuplehash_256",
+    "parallelhash_128", "parallelhash_256", "turboshake_128", "turboshake_256", "k12",
+    "blake2xb", "blake2xs", "blake3", "ascon_xof128",
+}
+SUPPORTED = sorted(RsHash.algorithms_available - XOFS)
+# Algorithmes que hashlib fournit aussi, pour une comparaison avec OpenSSL
+HASHLIB = ["md5", "sha1", "sha224", "sha256", "sha512", "sha512_224",
+           "sha3_224", "sha3_256", "sha3_384", "sha3_512", "blake2b", "blake2s"]
+
+
+def rshash_constructor(name):
+    """Constructeur au format attendu par hmac.new pour un algorithme de RsHash"""
+    return lambda data=b"": RsHash.new(name, data)
+
+
+@pytest.mark.parametrize("key,msg,sha256,sha512", RFC4231)
+def test_hmac_rfc4231(key, msg, sha256, sha512):
+    """Test les cas 1 à 7 de la RFC 4231, y compris la clé longue et la troncature"""
+    h = RsHash.HMAC(key, msg)
+    assert h.hexdigest()[:len(sha256)] == sha256
+    assert h.digest()[:len(sha256) // 2] == bytes.fromhex(sha256)
+    h = RsHash.HMAC(key, msg, "sha512")
+    assert h.hexdigest()[:len(sha512)] == sha512
+    h = RsHash.HMAC(key, digestmod=RsHash.SHA512)
+    h.update(msg)
+    assert h.hexdigest()[:len(sha512)] == sha512
+
+
+@pytest.mark.parametrize("name", HASHLIB)
+def test_hmac_matches_stdlib(name):
+    """Test différentiel contre le module hmac, autour de la taille de bloc"""
+    rng = random.Random(name)
+    block_size = hmac.new(b"", digestmod=name).block_size
+    for key_len in (0, 1, block_size - 1, block_size, block_size + 1, 200):
+        key = rng.randbytes(key_len)
+        msg = rng.randbytes(rng.randrange(0, 300))
+        expected = hmac.new(key, msg, name)
+        h = RsHash.HMAC(key, msg, digestmod=name)
+        assert h.digest() == expected.digest(), (name, key_len)
+        assert h.hexdigest() == expected.hexdigest()
+        assert h.digest_size == expected.digest_size
+        assert h.block_size == expected.block_size
+        assert h.name == expected.name
+
+
+@pytest.mark.parametrize("name", SUPPORTED)
+def test_hmac_every_registered_algorithm(name):
+    """Test chaque algorithme du registre contre hmac piloté par les classes de RsHash"""
+    cons = rshash_constructor(name)
+    rng = random.Random(name)
+    with warnings.catch_warnings():
+        # hmac remplace les blocs de moins de 16 octets (Ascon) par 64 octets, comme RsHash
+        warnings.simplefilter("ignore", RuntimeWarning)
+        block_size = hmac.new(b"", digestmod=cons).block_size
+        cases = []
+        for key_len in (0, 16, block_size, block_size + 1):
+            key = rng.randbytes(key_len)
+            msg = rng.randbytes(rng.randrange(0, 2 * block_size))
+            cases.append((key, msg, hmac.new(key, msg, digestmod=cons)))
+    for key, msg, expected in cases:
+        key_len = len(key)
+        h = RsHash.HMAC(key, msg, digestmod=name)
+        assert h.digest() == expected.digest(), (name, key_len)
+        assert h.name == expected.name == "hmac-" + name
+        assert h.block_size == block_size
+        assert h.digest_size == expected.digest_size
+        assert RsHash.hmac_digest(key, msg, name) == expected.digest()
+
+
+@pytest.mark.parametrize("name", sorted(XOFS))
+def test_hmac_rejects_xofs(name):
+    """Test que les XOF, sans taille de digest fixe, sont refusés"""
+    assert name in RsHash.algorithms_available
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.HMAC(b"key", digestmod=name)
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.hmac_digest(b"key", b"msg", name)
+
+
+def test_hmac_sizes_from_registry():
+    """Test que block_size et digest_size suivent l'algorithme choisi"""
+    for name, block_size, digest_size in [("sha224", 64, 28), ("sha512", 128, 64),
+                                          ("sha3_256", 136, 32), ("blake2b", 128, 64),
+                                          ("whirlpool", 64, 64), ("skein1024", 128, 128),
+                                          ("ascon_hash256", 64, 32)]:
+        h = RsHash.HMAC(b"key", digestmod=name)
+        assert (h.block_size, h.digest_size) == (block_size, digest_size), name
+
+
+def test_hmac_streaming_and_copy():
+    """Test update() par morceaux, copy() et digest() non destructif"""
+    msg = bytes(range(256)) * 3
+    h = RsHash.HMAC(b"key", digestmod="sha256")
+    for i in range(0, len(msg), 100):
+        h.update(msg[i:i + 100])
+    assert h.digest() == hmac.digest(b"key", msg, "sha256")
+    clone = h.copy()
+    clone.update(b"tail")
+    assert h.digest() == hmac.digest(b"key", msg, "sha256")
+    assert clone.digest() == hmac.digest(b"key", msg + b"tail", "sha256")
+    assert copy.copy(h).digest() == h.digest()
+    assert copy.deepcopy(clone).digest() == clone.digest()
+
+
+def test_hmac_attributes():
+    """Test name, digest_size et block_size pour plusieurs spellings de digestmod"""
+    h = RsHash.HMAC(b"k")
+    assert h.name == "hmac-sha256"
+    assert h.digest_size == 32
+    assert h.block_size == 64
+    h = RsHash.HMAC(b"k", digestmod="SHA-512")
+    assert (h.name, h.digest_size, h.block_size) == ("hmac-sha512", 64, 128)
+    h = RsHash.HMAC(b"k", digestmod=RsHash.SHA3_256)
+    assert (h.name, h.digest_size, h.block_size) == ("hmac-sha3_256", 32, 136)
+
+
+def test_hmac_buffer_inputs():
+    """Test que la clé et le message acceptent tout objet tampon"""
+    expected = hmac.digest(b"key", b"message", "sha256")
+    assert RsHash.HMAC(bytearray(b"key"), memoryview(b"message")).digest() == expected
+    h = RsHash.HMAC(memoryview(b"key"))
+    h.update(bytearray(b"message"))
+    assert h.digest() == expected
+
+
+def test_hmac_argument_errors():
+    """Test le rejet des clés str, des digestmod inconnus et des XOF"""
+    with pytest.raises(TypeError):
+        RsHash.HMAC("key")
+    with pytest.raises(TypeError):
+        RsHash.HMAC(b"key", "msg")
+    with pytest.raises(TypeError):
+        RsHash.HMAC(b"key", digestmod=42)
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.HMAC(b"key", digestmod="nope")
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.HMAC(b"key", digestmod=RsHash.SHAKE128)
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.HMAC(b"key", digestmod=int)
+
+
+def test_hmac_fips_mode():
+    """Test qu'HMAC-MD5 est refusé en mode FIPS, mais pas HMAC-SHA-256"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.HMAC(b"key", digestmod="md5")
+        assert RsHash.HMAC(b"key", b"abc").digest() == hmac.digest(b"key", b"abc", "sha256")
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+@pytest.mark.parametrize("key,msg,sha256,sha512", RFC4231)
+def test_hmac_digest_rfc4231(key, msg, sha256, sha512):
+    """Test les fonctions en une fois sur les vecteurs de la RFC 4231"""
+    assert RsHash.hmac_digest(key, msg).hex()[:len(sha256)] == sha256
+    assert RsHash.hmac_hexdigest(key, msg, "sha512")[:len(sha512)] == sha512
+    assert RsHash.hmac_digest(key, msg, digest=RsHash.SHA512).hex()[:len(sha512)] == sha512
+
+
+@pytest.mark.parametrize("name", HASHLIB)
+def test_hmac_digest_matches_stdlib(name):
+    """Test différentiel de hmac_digest contre hmac.digest"""
+    rng = random.Random(name)
+    for _ in range(10):
+        key = rng.randbytes(rng.randrange(0, 200))
+        msg = rng.randbytes(rng.randrange(0, 500))
+        expected = hmac.digest(key, msg, name)
+        assert RsHash.hmac_digest(key, msg, name) == expected
+        assert RsHash.hmac_hexdigest(key, msg, name) == expected.hex()
+        assert RsHash.hmac_verify(key, msg, expected, name) is True
+
+
+def test_hmac_digest_large_message():
+    """Test un message assez grand pour être traité sans le GIL"""
+    msg = bytes(range(256)) * 1024
+    assert RsHash.hmac_digest(b"key", memoryview(msg)) == hmac.digest(b"key", msg, "sha256")
+
+
+def test_hmac_verify_equal_length_tags():
+    """Test le rejet d'étiquettes de bonne longueur différant au premier ou au dernier octet"""
+    tag = hmac.digest(b"key", b"message", "sha256")
+    assert RsHash.hmac_verify(b"key", b"message", tag)
+    assert RsHash.hmac_verify(b"key", b"message", bytearray(tag))
+    for position in (0, 15, 31):
+        forged = bytearray(tag)
+        forged[position] ^= 1
+        assert RsHash.hmac_verify(b"key", b"message", forged) is False
+    assert RsHash.hmac_verify(b"key", b"other message", tag) is False
+    assert RsHash.hmac_verify(b"other key", b"message", tag) is False
+
+
+def test_hmac_verify_different_length_tags():
+    """Test le rejet des étiquettes tronquées, allongées ou vides"""
+    tag = hmac.digest(b"key", b"message", "sha512")
+    for forged in (tag[:32], tag[:-1], tag + b"\x00", b""):
+        assert RsHash.hmac_verify(b"key", b"message", forged, "sha512") is False
+    assert RsHash.hmac_verify(b"key", b"message", tag, "sha512") is True
+
+
+def test_hmac_oneshot_argument_errors():
+    """Test le rejet des str et des digest inconnus"""
+    with pytest.raises(TypeError):
+        RsHash.hmac_digest("key", b"msg")
+    with pytest.raises(TypeError):
+        RsHash.hmac_hexdigest(b"key", "msg")
+    with pytest.raises(TypeError):
+        RsHash.hmac_verify(b"key", b"msg", "00" * 32)
+    with pytest.raises(ValueError, match="unsupported digestmod"):
+        RsHash.hmac_digest(b"key", b"msg", "nope")
@@ -0,0 +1,197 @@
+"""Tests pour HMAC-DRBG (NIST SP 800-90A)"""
+import hmac
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Vecteurs CAVP sans reseed : hash, entropie, nonce, entrées additionnelles
+# des deux requêtes, sortie de la seconde requête
+CAVP = [
+    ("sha256", "ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488",
+     "659ba96c601dc69fc902940805ec0ca8", "", "",
+     "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89"
+     "d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1"
+     "07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668"
+     "961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8"),
+    ("sha256", "8e159f60060a7d6a7e6fe7c9f769c30b98acb1240b25e7ee33f1da834c0858e7",
+     "c39d35052201bdcce4e127a04f04d644", "", "",
+     "62910a77213967ea93d6457e255af51fc79d49629af2fccd81840cdfbb491099"
+     "1f50a477cbd29edd8a47c4fec9d141f50dfde7c4d8fcab473eff3cc2ee9e7cc9"
+     "0871f180777a97841597b0dd7e779eff9784b9cc33689fd7d48c0dcd341515ac"
+     "8fecf5c55a6327aea8d58f97220b7462373e84e3b7417a57e80ce946d6120db5"),
+    ("sha256", "d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd",
+     "0109b0e729f457328aa18569a9224921",
+     "3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6",
+     "fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4",
+     "9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f"
+     "1ffdbbb118f4e62fcfca3371b8fbfc5b0646b83e06bfbbab5fac30ea09ea2bc7"
+     "6f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab39018"
+     "3ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974"),
+    ("sha256", "53c56660c78481be9c63284e005fcc14fbc7fb27732c9bf1366d01a426765a31",
+     "dc7a14d0eb5b0b3534e717a0b3c64614",
+     "3aa848706ecb877f5bedf4ffc332d57c22e08747a47e75cff6f0fd1316861c95",
+     "9a401afa739b8f752fddacd291e0b854f5eff4a55b515e20cb319852189d3722",
+     "5c0eb420e0bf41ce9323e815310e4e8303cd677a8a8b023f31f0d79f0ca15aeb"
+     "636099a369fd074d69889865eac1b72ab3cbfebdb8cf460b00072802e2ec648b"
+     "1349a5303be4ccaadd729f1a9ea17482fd026aaeb93f1602bc1404b9853adde4"
+     "0d6c34b844cf148bc088941ecfc1642c8c0b9778e45f3b07e06e21ee2c9e0300"),
+    ("sha512", "35049f389a33c0ecb1293238fd951f8ffd517dfde06041d32945b3e26914ba15",
+     "f7328760be6168e6aa9fb54784989a11", "", "",
+     "e76491b0260aacfded01ad39fbf1a66a88284caa5123368a2ad9330ee48335e3"
+     "c9c9ba90e6cbc9429962d60c1a6661edcfaa31d972b8264b9d4562cf18494128"
+     "a092c17a8da6f3113e8a7edfcd4427082bd390675e9662408144971717303d8d"
+     "c352c9e8b95e7f35fa2ac9f549b292bc7c4bc7f01ee0a577859ef6e82d79ef23"
+     "892d167c140d22aac32b64ccdfeee2730528a38763b24227f91ac3ffe47fb115"
+     "38e435307e77481802b0f613f370ffb0dbeab774fe1efbb1a80d01154a9459e7"
+     "3ad361108bbc86b0914f095136cbe634555ce0bb263618dc5c367291ce082551"
+     "8987154fe9ecb052b3f0a256fcc30cc14572531c9628973639beda456f2bddf6"),
+]
+
+# Avec personnalisation, reseed et entrées additionnelles, comme les fichiers
+# CAVP sans résistance à la prédiction ; recoupé avec le HMAC-DRBG d'OpenSSL
+RESEED = {
+    "entropy": "7d72e7ed4f291a2e80318d0aaa8d5e25b0a115033b55efc0eec3bd4f298d820e",
+    "nonce": "0dacfa69fb6e61485f10ff0cc13af812",
+    "personalization": "8e1d97196406074f0670d62a054c268ebd6ae2e591facd1932e609f43c73bf65",
+    "reseed_entropy": "742931ab874342b3d60fd29a9fb518a3bc4cdbd8e8c47dc92d4249a4bce487a9",
+    "reseed_additional": "c4219b0ffcab1b7ab42f7258f5c295472b24b7ed57f32c6165c8981c61d01fb2",
+    "additional1": "fdc859aa664664287ce3b6b8da1f583f8724253d38b628a096e2eaf9bd9b9dee",
+    "additional2": "10337783e8068826701c0a594cc8f2d4ee18dd62061c4c7411cd74da4e1aa55a",
+}
+RESEED_OUTPUT = {
+    "sha256": "f42f26f200f2946891b3133db11a56492148cc2dd640b88e5452b79bf8e4c5bf"
+              "be68bb7793078ec79cb643b0d83e8ffb490211795e301d88cd6b35c2b8ff9033"
+              "359ade1e6a0bc059a5aaecc3ad1fe3cd12100baea359244b57976da54a84f0b0"
+              "c13770cbab2d7ac8e332975cb29d6e31e9eb7f1109dca571b753acebf56e0848",
+    "sha512": "499a4c527cfc892c2d765254713f48e99a89e92383cdd170ca45fd3e8cc260cf"
+              "d4dc692ed9081e003056bf548fef91d2d623a4d60db34932c5087d1c04d8c883"
+              "1760b247d0724bdb0163cfc61aec1c961ee72cda93a05110be80a625ca5ef7f7"
+              "49f134032f1c20a2e27692a1fe646a49cdb6663989a7e45d696fd29f3662dd39"
+              "fdff6ca65fc79b510ae22afac783e6bf8de575610b19e894156f25f83be3f7ed"
+              "93fef1e4604e29ab6e8cd3e1843344e5b975f358454d1979ae2499733ea77ca1"
+              "88cde37c3a8b02100b3f7c37ab56b271325a6b175e847528109e589c43533ff9"
+              "0f02542a76f1fa5169fbb4cac01fc9df40e381c62f8dd30243a7b1ec8f5bad0e",
+}
+
+
+class Reference:
+    """HMAC-DRBG de référence en Python"""
+
+    def __init__(self, name, entropy, nonce, personalization=b""):
+        self.name = name
+        size = hmac.new(b"", b"", name).digest_size
+        self.key, self.value = b"\0" * size, b"\1" * size
+        self.update(entropy + nonce + personalization)
+
+    def update(self, data):
+        for separator in (b"\0", b"\1"):
+            self.key = hmac.new(self.key, self.value + separator + data, self.name).digest()
+            self.value = hmac.new(self.key, self.value, self.name).digest()
+            if not data:
+                break
+
+    def reseed(self, entropy, additional=b""):
+        self.update(entropy + additional)
+
+    def generate(self, num_bytes, additional=b""):
+        if additional:
+            self.update(additional)
+        out = b""
+        while len(out) < num_bytes:
+            self.value = hmac.new(self.key, self.value, self.name).digest()
+            out += self.value
+        self.update(additional)
+        return out[:num_bytes]
+
+
+@pytest.mark.parametrize("name,entropy,nonce,add1,add2,expected", CAVP)
+def test_hmac_drbg_cavp(name, entropy, nonce, add1, add2, expected):
+    """Test les vecteurs CAVP sans reseed"""
+    drbg = RsHash.HmacDrbg(name)
+    drbg.instantiate(bytes.fromhex(entropy), bytes.fromhex(nonce))
+    drbg.generate(len(expected) // 2, bytes.fromhex(add1))
+    assert drbg.generate(len(expected) // 2, bytes.fromhex(add2)).hex() == expected
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512"])
+def test_hmac_drbg_reseed(name):
+    """Test un vecteur avec personnalisation, reseed et entrées additionnelles"""
+    v = {key: bytes.fromhex(value) for key, value in RESEED.items()}
+    expected = RESEED_OUTPUT[name]
+    drbg = RsHash.HmacDrbg(hash=name)
+    drbg.instantiate(v["entropy"], v["nonce"], personalization=v["personalization"])
+    drbg.reseed(v["reseed_entropy"], additional=v["reseed_additional"])
+    drbg.generate(len(expected) // 2, additional=v["additional1"])
+    assert drbg.generate(len(expected) // 2, v["additional2"]).hex() == expected
+
+
+@pytest.mark.parametrize("name", ["sha1", "sha256", "sha512", "sha3_256", "blake2b"])
+def test_hmac_drbg_matches_reference(name):
+    """Test différentiel contre l'implémentation de référence"""
+    drbg = RsHash.HmacDrbg(name)
+    drbg.instantiate(b"entropy" * 5, b"nonce", b"pers")
+    ref = Reference(name, b"entropy" * 5, b"nonce", b"pers")
+    for i, num_bytes in enumerate([0, 1, 20, 64, 65, 1000]):
+        additional = bytes([i]) * i
+        assert drbg.generate(num_bytes, additional) == ref.generate(num_bytes, additional)
+        if i % 2:
+            drbg.reseed(b"fresh" * i, additional)
+            ref.reseed(b"fresh" * i, additional)
+
+
+def test_hmac_drbg_state():
+    """Test le compteur de reseed, la réinstanciation et les attributs"""
+    drbg = RsHash.HmacDrbg(RsHash.SHA256)
+    assert drbg.name == "hmac-drbg-sha256"
+    assert drbg.reseed_counter is None
+    drbg.instantiate(bytearray(32), memoryview(bytes(16)))
+    assert drbg.reseed_counter == 1
+    first = drbg.generate(32)
+    drbg.generate(32)
+    assert drbg.reseed_counter == 3
+    drbg.reseed(b"x" * 32)
+    assert drbg.reseed_counter == 1
+    drbg.instantiate(bytes(32), bytes(16))
+    assert drbg.generate(32) == first
+    assert RsHash.HmacDrbg().name == "hmac-drbg-sha256"
+
+
+def test_hmac_drbg_reseed_interval():
+    """Test que le générateur exige un reseed après reseed_interval requêtes"""
+    drbg = RsHash.HmacDrbg("sha256", reseed_interval=2)
+    drbg.instantiate(bytes(32), bytes(16))
+    drbg.generate(16)
+    drbg.generate(16)
+    with pytest.raises(RuntimeError, match="reseed"):
+        drbg.generate(16)
+    drbg.reseed(bytes(32))
+    drbg.generate(16)
+    for interval in (0, 2 ** 48 + 1):
+        with pytest.raises(ValueError, match="reseed_interval"):
+            RsHash.HmacDrbg("sha256", reseed_interval=interval)
+
+
+def test_hmac_drbg_errors():
+    """Test les requêtes trop longues, l'usage avant instanciation et les hachages refusés"""
+    drbg = RsHash.HmacDrbg("sha256")
+    with pytest.raises(RuntimeError, match="instantiate"):
+        drbg.generate(16)
+    with pytest.raises(RuntimeError, match="instantiate"):
+        drbg.reseed(bytes(32))
+    drbg.instantiate(bytes(32), bytes(16))
+    assert len(drbg.generate(65536)) == 65536
+    with pytest.raises(ValueError, match="65536"):
+        drbg.generate(65537)
+    # Refusé avant d'allouer la sortie
+    with pytest.raises(ValueError, match="65536"):
+        drbg.generate(2**62)
+    assert drbg.reseed_counter == 2
+    with pytest.raises(OverflowError):
+        drbg.generate(-1)
+    with pytest.raises(TypeError):
+        drbg.generate(16, "additional")
+    with pytest.raises(ValueError):
+        RsHash.HmacDrbg("shake_128")
+    with pytest.raises(ValueError):
+        RsHash.HmacDrbg("nope")
@@ -0,0 +1,101 @@
+"""Tests pour HOTP (RFC 4226)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+SECRET = b"12345678901234567890"
+
+# Annexe D de la RFC 4226 : valeur tronquée et code à 6 chiffres, compteurs 0 à 9
+APPENDIX_D = [
+    (1284755224, "755224"),
+    (1094287082, "287082"),
+    (137359152, "359152"),
+    (1726969429, "969429"),
+    (1640338314, "338314"),
+    (868254676, "254676"),
+    (1918287922, "287922"),
+    (82162583, "162583"),
+    (673399871, "399871"),
+    (645520489, "520489"),
+]
+
+
+@pytest.mark.parametrize("counter", range(10))
+def test_hotp_rfc4226(counter):
+    """Test les valeurs de l'annexe D de la RFC 4226"""
+    truncated, code = APPENDIX_D[counter]
+    assert RsHash.hotp(SECRET, counter) == code
+    assert RsHash.hotp(SECRET, counter, digest="sha1") == code
+    for digits in (7, 8):
+        assert RsHash.hotp(SECRET, counter, digits=digits) == \
+            str(truncated % 10 ** digits).zfill(digits)
+
+
+def test_hotp_leading_zeros():
+    """Test que les codes gardent leurs zéros de tête"""
+    codes = [RsHash.hotp(SECRET, counter) for counter in range(2000)]
+    assert all(len(code) == 6 and code.isdigit() for code in codes)
+    assert any(code.startswith("0") for code in codes)
+
+
+@pytest.mark.parametrize("name,key,expected", [
+    ("sha1", SECRET, "94287082"),
+    ("sha256", b"12345678901234567890123456789012", "46119246"),
+    ("sha512", b"1234567890123456789012345678901234567890123456789012345678901234", "90693936"),
+])
+def test_hotp_digests(name, key, expected):
+    """Test SHA-256 et SHA-512 avec les valeurs de la RFC 6238 à T = 1"""
+    assert RsHash.hotp(key, 1, digits=8, digest=name) == expected
+    assert RsHash.hotp(key, 1, digits=8, digest=getattr(RsHash, name.upper())) == expected
+
+
+def test_hotp_matches_cryptography():
+    """Test différentiel contre pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.twofactor.hotp import HOTP
+    for name, algorithm in [("sha1", hashes.SHA1()), ("sha256", hashes.SHA256()),
+                            ("sha512", hashes.SHA512())]:
+        for digits in (6, 7, 8):
+            theirs = HOTP(b"k" * 20, digits, algorithm)
+            for counter in (0, 1, 255, 2 ** 32, 2 ** 64 - 1):
+                assert RsHash.hotp(b"k" * 20, counter, digits=digits, digest=name) == \
+                    theirs.generate(counter).decode()
+
+
+def test_hotp_verify():
+    """Test la fenêtre de resynchronisation"""
+    assert RsHash.hotp_verify(SECRET, 0, "755224") == 0
+    assert RsHash.hotp_verify(SECRET, 0, "969429") == 3
+    assert RsHash.hotp_verify(SECRET, 0, "338314") is None
+    assert RsHash.hotp_verify(SECRET, 0, "338314", look_ahead=4) == 4
+    assert RsHash.hotp_verify(SECRET, 4, "338314", look_ahead=0) == 4
+    assert RsHash.hotp_verify(SECRET, 5, "338314", look_ahead=100) is None
+    assert RsHash.hotp_verify(SECRET, 0, "84755224", digits=8) == 0
+    assert RsHash.hotp_verify(SECRET, 0, "84755224") is None
+    assert RsHash.hotp_verify(SECRET, 0, "75522") is None
+    assert RsHash.hotp_verify(SECRET, 0, " 755224") is None
+    assert RsHash.hotp_verify(SECRET, 2 ** 64 - 1, "000000", look_ahead=10) is None
+    key = b"12345678901234567890123456789012"
+    assert RsHash.hotp_verify(key, 0, "46119246", digits=8, digest="sha256") == 1
+
+
+def test_hotp_errors():
+    """Test les paramètres invalides"""
+    for digits in (0, 5, 9, 10):
+        with pytest.raises(ValueError, match="digits"):
+            RsHash.hotp(SECRET, 0, digits=digits)
+    with pytest.raises(ValueError, match="20 bytes"):
+        RsHash.hotp(SECRET, 0, digest="md5")
+    with pytest.raises(ValueError):
+        RsHash.hotp(SECRET, 0, digest="shake_128")
+    with pytest.raises(OverflowError):
+        RsHash.hotp(SECRET, -1)
+    with pytest.raises(OverflowError):
+        RsHash.hotp(SECRET, 2 ** 64)
+    with pytest.raises(TypeError):
+        RsHash.hotp("secret", 0)
+    with pytest.raises(TypeError):
+        RsHash.hotp(SECRET, 0, 6)
+    with pytest.raises(TypeError):
+        RsHash.hotp_verify(SECRET, 0, b"755224")
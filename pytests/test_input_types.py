@@ -0,0 +1,201 @@
+"""Tests des types d'entrée acceptés ou refusés, comparés à hashlib"""
+import array
+import hashlib
+import mmap
+import sys
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ALGORITHMS = [("sha256", RsHash.SHA256), ("sha512", RsHash.SHA512)]
+INVALID_INPUTS = ["abc", 42, [1, 2, 3]]
+
+
+def hashlib_error(func):
+    """Renvoie l'exception levée par hashlib pour la comparer à RsHash"""
+    try:
+        func()
+    except Exception as exc:
+        return exc
+    raise AssertionError("hashlib accepted the input")
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+@pytest.mark.parametrize("value", INVALID_INPUTS + [None])
+def test_update_invalid_input(name, cls, value):
+    """Test que update() lève la même erreur que hashlib"""
+    expected = hashlib_error(lambda: hashlib.new(name).update(value))
+    with pytest.raises(type(expected)) as excinfo:
+        cls().update(value)
+    assert str(excinfo.value) == str(expected)
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+@pytest.mark.parametrize("value", INVALID_INPUTS)
+def test_constructor_invalid_input(name, cls, value):
+    """Test que le constructeur et new() lèvent la même erreur que hashlib"""
+    expected = hashlib_error(lambda: hashlib.new(name, value))
+    with pytest.raises(type(expected)) as excinfo:
+        cls(value)
+    assert str(excinfo.value) == str(expected)
+    with pytest.raises(type(expected)) as excinfo:
+        RsHash.new(name, value)
+    assert str(excinfo.value) == str(expected)
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_constructor_none_means_no_data(name, cls):
+    """Test que None au constructeur équivaut à l'absence de données"""
+    assert cls(None).hexdigest() == hashlib.new(name).hexdigest()
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_accepted_buffer_types(name, cls):
+    """Test que bytes, bytearray et memoryview sont acceptés"""
+    expected = hashlib.new(name, b"hello world").hexdigest()
+    for value in [b"hello world", bytearray(b"hello world"), memoryview(b"hello world")]:
+        assert cls(value).hexdigest() == expected
+        h = cls()
+        h.update(value)
+        assert h.hexdigest() == expected
+
+
+def buffer_inputs(tmp_path):
+    """Construit des objets supportant le protocole buffer, avec leurs octets"""
+    payload = bytes(range(256)) * 40
+    path = tmp_path / "payload.bin"
+    path.write_bytes(payload)
+    with open(path, "rb") as f:
+        mapped = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_READ)
+    ints = array.array("i", range(100))
+    inputs = [
+        (payload, payload),
+        (bytearray(payload), payload),
+        (memoryview(payload), payload),
+        (memoryview(payload)[10:], payload[10:]),
+        (memoryview(bytearray(payload))[3:-3], payload[3:-3]),
+        (mapped, payload),
+        (ints, ints.tobytes()),
+        (memoryview(bytes(12)).cast("B", (3, 4)), bytes(12)),
+    ]
+    return inputs, mapped
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_buffer_protocol_inputs(name, cls, tmp_path):
+    """Test que tout objet buffer contigu est haché comme avec hashlib"""
+    inputs, mapped = buffer_inputs(tmp_path)
+    try:
+        for obj, raw in inputs:
+            expected = hashlib.new(name, raw).hexdigest()
+            assert hashlib.new(name, obj).hexdigest() == expected
+            assert cls(obj).hexdigest() == expected
+            h = cls()
+            h.update(obj)
+            assert h.hexdigest() == expected
+            assert RsHash.new(name, obj).hexdigest() == expected
+    finally:
+        del inputs
+        mapped.close()
+
+
+def test_oneshot_buffer_inputs(tmp_path):
+    """Test que les fonctions one-shot acceptent les objets buffer"""
+    inputs, mapped = buffer_inputs(tmp_path)
+    try:
+        for obj, raw in inputs:
+            assert RsHash.sha256_hexdigest(obj) == hashlib.sha256(raw).hexdigest()
+            assert RsHash.sha512_digest(obj) == hashlib.sha512(raw).digest()
+    finally:
+        del inputs
+        mapped.close()
+
+
+def test_numpy_array_input():
+    """Test avec un tableau NumPy contigu"""
+    np = pytest.importorskip("numpy")
+    arr = np.arange(1000, dtype=np.uint32)
+    assert RsHash.SHA256(arr).hexdigest() == hashlib.sha256(arr).hexdigest()
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_non_contiguous_buffer_error(name, cls):
+    """Test qu'un buffer non contigu lève BufferError sans copy=True"""
+    strided = memoryview(b"abcdefgh")[::2]
+    with pytest.raises(BufferError, match="C-contiguous"):
+        cls(strided)
+    with pytest.raises(BufferError, match="copy=True"):
+        cls().update(strided)
+    with pytest.raises(BufferError):
+        RsHash.new(name, strided)
+    with pytest.raises(BufferError):
+        RsHash.sha256_digest(strided)
+
+
+def strided_inputs():
+    """Buffers non contigus, avec leurs octets dans l'ordre logique"""
+    data = bytes(range(256)) * 300
+    ints = memoryview(array.array("i", range(5000)))
+    grid = memoryview(bytearray(range(240))).cast("B", (12, 20))
+    return [
+        memoryview(data)[::2],
+        memoryview(data)[::-1],
+        memoryview(data)[5:-7:3],
+        ints[::3],
+        ints[::-2],
+        grid[::2],
+        memoryview(b"")[::2],
+    ]
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_strided_buffer_with_copy(name, cls):
+    """Test que copy=True hache les buffers non contigus dans l'ordre logique"""
+    for view in strided_inputs():
+        expected = hashlib.new(name, view.tobytes()).hexdigest()
+        assert cls(view, copy=True).hexdigest() == expected
+        h = cls(b"")
+        h.update(view, copy=True)
+        assert h.hexdigest() == expected
+        assert RsHash.new(name, view, copy=True).hexdigest() == expected
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_contiguous_buffer_with_copy(name, cls):
+    """Test que copy=True ne change rien pour un buffer contigu"""
+    assert cls(b"abc", copy=True).hexdigest() == hashlib.new(name, b"abc").hexdigest()
+
+
+def test_numpy_strided_array():
+    """Test avec des tranches NumPy arr[::2] et 2-D"""
+    np = pytest.importorskip("numpy")
+    arr = np.arange(10000, dtype=np.uint16)
+    with pytest.raises(BufferError):
+        RsHash.SHA256(arr[::2])
+    sliced = arr[::2]
+    assert RsHash.SHA256(sliced, copy=True).hexdigest() == hashlib.sha256(sliced.tobytes()).hexdigest()
+    grid = arr.reshape(100, 100)[::3, 1::2]
+    assert RsHash.SHA512(grid, copy=True).hexdigest() == hashlib.sha512(grid.tobytes()).hexdigest()
+
+
+class ReentrantBuffer:
+    """Objet dont __buffer__ relit l'objet de hachage qui l'exporte"""
+
+    def __init__(self, h, data):
+        self.h, self.data, self.seen = h, data, None
+
+    def __buffer__(self, flags):
+        self.seen = self.h.hexdigest()
+        return memoryview(self.data)
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_update_reentrant_buffer(name, cls):
+    """Test que __buffer__ peut utiliser l'objet de hachage sans interblocage"""
+    if sys.version_info < (3, 12):
+        pytest.skip("__buffer__ requires Python 3.12")
+    h = cls(b"abc")
+    data = ReentrantBuffer(h, b"def")
+    h.update(data)
+    assert data.seen == hashlib.new(name, b"abc").hexdigest()
+    assert h.hexdigest() == hashlib.new(name, b"abcdef").hexdigest()
@@ -0,0 +1,69 @@
+"""Tests pour JH-256/512 (soumission SHA-3 du troisième tour, JH42)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+FOX = b"The quick brown fox jumps over the lazy dog"
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "46e64619c18bb0a92a5e87185a47eef83ca747b8fcc8e1412921357e326df434"),
+    (b"abc", "924bc82f24a76d519d4f69493da7fa70dc88bdb6016b6d1cc1dcf7def15e9cdd"),
+    (FOX, "6a049fed5fc6874acfdc4a08b568a4f8cbac27de933496f031015b38961608a0"),
+    (bytes(range(64)), "e8bab989ea39692776ef278a4752f8e1359d78a8332e030b82297453eef03d1d"),
+])
+def test_jh256_vectors(data, expected):
+    """Test JH-256 : message vide, messages courts et un bloc complet"""
+    assert RsHash.JH256(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"",
+     "90ecf2f76f9d2c8017d979ad5ab96b87d58fc8fc4b83060f3f900774faa2c8fa"
+     "be69c5f4ff1ec2b61d6b316941cedee117fb04b1f4c5bc1b919ae841c50eec4f"),
+    (FOX,
+     "043f14e7c0775e7b1ef5ad657b1e858250b21e2e61fd699783f8634cb86f3ff9"
+     "38451cabd0c8cdae91d4f659d3f9f6f654f1bfedca117ffba735c15fedda47a3"),
+])
+def test_jh512_vectors(data, expected):
+    """Test JH-512 sur le message vide et "The quick brown fox" """
+    assert RsHash.JH512(data).hexdigest() == expected
+
+
+def test_jh_streaming():
+    """Test JH en plusieurs update() autour de la limite de bloc"""
+    data = bytes(range(256)) * 3
+    for cls in (RsHash.JH256, RsHash.JH512):
+        for step in (1, 47, 48, 63, 64, 65):
+            h = cls()
+            for start in range(0, len(data), step):
+                h.update(data[start:start + step])
+            assert h.digest() == cls(data).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size", [
+    ("jh256", "JH256", 32),
+    ("jh512", "JH512", 64),
+])
+def test_jh_properties_and_new(name, cls, digest_size):
+    """Test les propriétés et new("jhN")"""
+    h = RsHash.new(name.upper().replace("JH", "JH-"), b"abc")
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == 64
+    assert name in RsHash.algorithms_available
+
+
+def test_jh_fips_mode():
+    """Test qu'en mode FIPS, JH exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.JH256(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("jh512")
+        h = RsHash.new("jh256", b"abc", usedforsecurity=False)
+        assert h.hexdigest().startswith("924bc82f")
+    finally:
+        RsHash.set_fips_mode(False)
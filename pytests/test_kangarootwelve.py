@@ -0,0 +1,69 @@
+"""Tests pour KangarooTwelve (RFC 9861)"""
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+def ptn(n):
+    """Motif 00 01 .. FA répété, utilisé par les vecteurs de RFC 9861"""
+    return bytes(i % 251 for i in range(n))
+
+
+@pytest.mark.parametrize("exponent,expected", [
+    (0, "2bda92450e8b147f8a7cb629e784a058efca7cf7d8218e02d345dfaa65244a1f"),
+    (1, "6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888"),
+    (2, "0c315ebcdedbf61426de7dcf8fb725d1e74675d7f5327a5067f367b108ecb67c"),
+    (3, "cb552e2ec77d9910701d578b457ddf772c12e322e4ee7fe417f92c758f0d59d0"),
+    (4, "8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe"),
+    (5, "844d610933b1b9963cbdeb5ae3b6b05cc7cbd67ceedf883eb678a0a8e0371682"),
+])
+def test_k12_pattern_messages(exponent, expected):
+    """Test les vecteurs officiels de longueur 17^i"""
+    assert RsHash.KangarooTwelve(ptn(17 ** exponent)).hexdigest(32) == expected
+
+
+@pytest.mark.parametrize("message,custom,expected", [
+    (b"", ptn(1), "fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583"),
+    (b"\xff", ptn(41), "d848c5068ced736f4462159b9867fd4c20b808acc3d5bc48e0b06ba0a3762ec4"),
+    (b"\xff" * 3, ptn(41 ** 2),
+     "c389e5009ae57120854c2e8c64670ac01358cf4c1baf89447a724234dc7ced74"),
+    (b"\xff" * 7, ptn(41 ** 3),
+     "75d2f86a2e644566726b4fbcfc5657b9dbcf070c7b0dca06450ab291d7443bcf"),
+])
+def test_k12_customization(message, custom, expected):
+    """Test les vecteurs officiels avec chaîne de personnalisation"""
+    assert RsHash.KangarooTwelve(message, custom=custom).hexdigest(32) == expected
+
+
+def test_k12_empty_and_long_output():
+    """Test le message vide et une sortie de 10032 octets"""
+    h = RsHash.KangarooTwelve()
+    assert h.hexdigest(32) == \
+        "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"
+    assert h.digest(10032)[10000:].hex() == \
+        "e8dc563642f7228c84684c898405d3a834799158c079b12880277a1d28e2ff6d"
+    assert h.digest(64)[:32] == h.digest(32)
+
+
+def test_k12_incremental():
+    """Test que le découpage des update() ne change pas le résultat"""
+    data = os.urandom(5 * 8192 + 17)
+    expected = RsHash.KangarooTwelve(data, custom=b"C").hexdigest(32)
+    for step in (1000, 8191, 8192, 8193, 30000):
+        h = RsHash.KangarooTwelve(custom=b"C")
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.hexdigest(32) == expected
+
+
+def test_k12_copy_and_name():
+    """Test copy(), name, block_size et le constructeur générique"""
+    h = RsHash.KangarooTwelve(b"abc")
+    c = h.copy()
+    h.update(b"def")
+    assert c.digest(32) == RsHash.KangarooTwelve(b"abc").digest(32)
+    assert h.name == "k12"
+    assert h.block_size == 168
+    assert RsHash.new("kangarootwelve", b"abcdef").digest(32) == h.digest(32)
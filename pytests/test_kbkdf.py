@@ -0,0 +1,110 @@
+"""Tests pour KBKDF en mode compteur (NIST SP 800-108)"""
+import hmac
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+KEY = b"key" * 8
+
+
+def reference(name, key, label, context, length, rlen=32, llen=32, byteorder="big"):
+    """Implémentation de référence en Python"""
+    fixed = label + b"\0" + context
+    if llen:
+        fixed += (length * 8).to_bytes(llen // 8, byteorder)
+    out = b""
+    counter = 1
+    while len(out) < length:
+        out += hmac.new(key, counter.to_bytes(rlen // 8, byteorder) + fixed, name).digest()
+        counter += 1
+    return out[:length]
+
+
+# Vecteurs croisés avec le KBKDFHMAC de pyca/cryptography
+VECTORS = [
+    (32, 32, "big",
+     "f7d39a79f71df1dd5440faddaa0510ced6cae64b17698777b38c38cb204b3833a2032b8bf40df6183947"),
+    (8, 16, "big",
+     "5c48bd33bfaf533ef39e5827b8c3f0aaf5b1ac27dbff995dbbe2f2c8cc4387959d36b93de31c7f4ba9bd"),
+    (32, 32, "little",
+     "e2b00e83f588878f3beff172062d635330c196896de323379e7c4587421ed6942124fb75755835d88ef0"),
+]
+
+
+@pytest.mark.parametrize("rlen,llen,byteorder,expected", VECTORS)
+def test_kbkdf_vectors(rlen, llen, byteorder, expected):
+    """Test les vecteurs HMAC-SHA256 pour plusieurs tailles de champs"""
+    out = RsHash.kbkdf_counter(KEY, label=b"label", context=b"context", length=42,
+                               rlen=rlen, llen=llen, byteorder=byteorder)
+    assert out.hex() == expected
+
+
+@pytest.mark.parametrize("prf", ["sha1", "sha256", "sha224", "sha512", "sha3_256"])
+@pytest.mark.parametrize("rlen,llen", [(8, 8), (16, 24), (24, 0), (32, 32)])
+@pytest.mark.parametrize("byteorder", ["big", "little"])
+def test_kbkdf_matches_reference(prf, rlen, llen, byteorder):
+    """Test différentiel contre l'implémentation de référence"""
+    for length in (1, 20, 31, 64, 100):
+        if llen and length * 8 >= 1 << llen:
+            continue
+        expected = reference(prf, KEY, b"label", b"ctx", length, rlen, llen, byteorder)
+        out = RsHash.kbkdf_counter(KEY, label=b"label", context=b"ctx", length=length,
+                                   prf="hmac-" + prf, rlen=rlen, llen=llen, byteorder=byteorder)
+        assert out == expected
+
+
+def test_kbkdf_matches_cryptography():
+    """Test différentiel contre pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.kdf import kbkdf
+    for name, algorithm in [("sha1", hashes.SHA1()), ("sha256", hashes.SHA256()),
+                            ("sha512", hashes.SHA512())]:
+        for rlen, llen in [(8, 16), (16, 32), (32, 16)]:
+            theirs = kbkdf.KBKDFHMAC(
+                algorithm=algorithm, mode=kbkdf.Mode.CounterMode, length=50,
+                rlen=rlen // 8, llen=llen // 8, location=kbkdf.CounterLocation.BeforeFixed,
+                label=b"label", context=b"context", fixed=None,
+            ).derive(KEY)
+            ours = RsHash.kbkdf_counter(KEY, label=b"label", context=b"context", length=50,
+                                        prf="hmac-" + name, rlen=rlen, llen=llen)
+            assert ours == theirs
+
+
+def test_kbkdf_prf_and_defaults():
+    """Test les valeurs par défaut et les façons de désigner la PRF"""
+    default = RsHash.kbkdf_counter(KEY, label=b"l", context=b"c", length=32)
+    assert default == reference("sha256", KEY, b"l", b"c", 32)
+    for prf in ("hmac-sha256", "HMAC-SHA256", "sha256", RsHash.SHA256):
+        assert RsHash.kbkdf_counter(KEY, label=b"l", context=b"c", length=32, prf=prf) == default
+    assert RsHash.kbkdf_counter(bytearray(KEY), label=memoryview(b"l"), context=b"c",
+                                length=32) == default
+    assert RsHash.kbkdf_counter(KEY, label=b"", context=b"", length=0) == b""
+
+
+def test_kbkdf_argument_errors():
+    """Test les tailles de champs, les longueurs et les PRF invalides"""
+    args = dict(label=b"l", context=b"c")
+    for rlen in (0, 12, 64):
+        with pytest.raises(ValueError, match="rlen"):
+            RsHash.kbkdf_counter(KEY, length=32, rlen=rlen, **args)
+    with pytest.raises(ValueError, match="llen"):
+        RsHash.kbkdf_counter(KEY, length=32, llen=4, **args)
+    with pytest.raises(ValueError, match="length"):
+        RsHash.kbkdf_counter(KEY, length=32, llen=8, **args)
+    with pytest.raises(ValueError, match="length"):
+        RsHash.kbkdf_counter(KEY, length=255 * 32 + 1, rlen=8, llen=32, **args)
+    assert len(RsHash.kbkdf_counter(KEY, length=255 * 32, rlen=8, llen=32, **args)) == 8160
+    with pytest.raises(ValueError, match="byteorder"):
+        RsHash.kbkdf_counter(KEY, length=32, byteorder="native", **args)
+    for prf in ("cmac-aes128", "hmac-shake_128"):
+        with pytest.raises(ValueError):
+            RsHash.kbkdf_counter(KEY, length=32, prf=prf, **args)
+    with pytest.raises(TypeError):
+        RsHash.kbkdf_counter(KEY, length=32, prf=3, **args)
+    with pytest.raises(TypeError):
+        RsHash.kbkdf_counter(KEY, b"l", b"c", 32)
+    with pytest.raises(TypeError):
+        RsHash.kbkdf_counter("key", length=32, **args)
+
@@ -0,0 +1,61 @@
+"""Tests pour Keccak-256 et Keccak-512 (padding d'origine, Ethereum)"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("cls,data,expected", [
+    (RsHash.Keccak256, b"",
+     "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+    (RsHash.Keccak256, b"abc",
+     "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+    (RsHash.Keccak512, b"",
+     "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304"
+     "c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e"),
+])
+def test_keccak_vectors(cls, data, expected):
+    """Test les vecteurs de la soumission Keccak d'origine"""
+    assert cls(data).hexdigest() == expected
+
+
+def test_keccak_differs_from_sha3():
+    """Test que le padding 0x01 donne un résultat différent de SHA-3"""
+    assert RsHash.Keccak256(b"abc").digest() != hashlib.sha3_256(b"abc").digest()
+    assert RsHash.Keccak512(b"abc").digest() != hashlib.sha3_512(b"abc").digest()
+
+
+def test_keccak_streaming():
+    """Test Keccak256 en plusieurs update() qui traversent le rate"""
+    data = bytes(range(256)) * 3
+    h = RsHash.Keccak256()
+    for start in range(0, len(data), 50):
+        h.update(data[start:start + 50])
+    assert h.digest() == RsHash.Keccak256(data).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", [
+    ("keccak_256", RsHash.Keccak256, 32, 136),
+    ("keccak_512", RsHash.Keccak512, 64, 72),
+])
+def test_keccak_properties_and_new(name, cls, digest_size, block_size):
+    """Test les propriétés et new()"""
+    h = RsHash.new(name, b"abc")
+    assert type(h) is cls
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert h.digest() == cls(b"abc").digest()
+    assert name in RsHash.algorithms_available
+
+
+def test_keccak_fips_mode():
+    """Test qu'en mode FIPS, Keccak exige usedforsecurity=False, contrairement à SHA-3"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.Keccak256()
+        assert RsHash.new("keccak_256", usedforsecurity=False).name == "keccak_256"
+        assert RsHash.SHA3_256(b"abc").hexdigest() == hashlib.sha3_256(b"abc").hexdigest()
+    finally:
+        RsHash.set_fips_mode(False)
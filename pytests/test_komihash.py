@@ -0,0 +1,97 @@
+"""Tests pour komihash du sous-module noncrypto"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+STRINGS = [
+    b"This is a 32-byte testing string",
+    b"The cat is out of the bag",
+    b"A 16-byte string",
+    b"The new string",
+    b"7 chars",
+]
+BULK = bytes(range(256))
+BULK_LENGTHS = [3, 6, 8, 12, 20, 31, 32, 40, 47, 48, 56, 64, 72, 80, 112, 132, 256]
+
+# Vecteurs du README de référence (komihash 5) : les chaînes, puis bulk(n) = octets 0..n-1
+REFERENCE = {
+    0: [
+        0x05AD960802903A9D, 0xD15723521D3C37B1, 0x467CAA28EA3DA7A6, 0xF18E67BC90C43233,
+        0x2C514F6E5DCB11CB, 0x7A9717E9EEA4BE8B, 0xA56469564C2EA0FF, 0x00B4313A24431306,
+        0x64C2AD96013F70FE, 0x7A3888BC95545364, 0xC77E02ED4B201B9A, 0x256D74350303A1BA,
+        0x59609C71697BB9DF, 0x36EB9E6A4C2C5E4B, 0x8DD56C332850BAA6, 0xCBB722192B353999,
+        0x90B07E2158F88CC0, 0x24C9621701603741, 0x1D4C1D97CA684334, 0xD1A425D530652287,
+        0x72623BE342C20AB5, 0x94C3DBDCA59DDF57,
+    ],
+    0x0123456789ABCDEF: [
+        0x6CE66A2E8D4979A5, 0x5B1DA0B43545D196, 0x26AF914213D0C915, 0x62D9CA1B73250CB5,
+        0x90AB7C9F831CD940, 0x84AE4EB65B96617E, 0xACEEBC32A3C0D9E4, 0xDAA1A90ECB95F6F8,
+        0xEC8EB3EF4AF380B4, 0x07045BD31ABBA34C, 0xD5F619FB2E62C4AE, 0x5A336FD2C4C39ABE,
+        0x0E870B4623EEA8EC, 0xE552EDD6BF419D1D, 0x37D170DDCB1223E6, 0x1CD89E708E5098B6,
+        0x765490569CCD77F2, 0x19E9D77B86D01EE8, 0x25F83EE520C1D241, 0xD6007417091CD4C0,
+        0x3E49C2D3727B9CC9, 0xB2B3405EE5D65F4C,
+    ],
+    0x100: [
+        0x5F197B30BCEC1E45, 0xA761280322BB7698, 0x11C31CCABAA524F1, 0x3A43B7F58281C229,
+        0xCFF90B0466B7E3A2, 0x8AB53F45CC9315E3, 0xEA606E43D1976CCF, 0x889B2F2CEECBEC73,
+        0xACBEC1886CD23275, 0x57C3AFFD1B71FCDB, 0x7EF6BA49A3B068C3, 0x49DBCA62ED5A1DDF,
+        0x192848484481E8C0, 0x420B43A5EDBA1BD7, 0xD6E8400A9DE24CE3, 0xBEA291B225FF384D,
+        0x0EC94062B2F06960, 0xFA613272ECD49985, 0x76F0BB380BC207BE, 0x4AFB4E08CA77C020,
+        0x410F9C129AD88AEA, 0x066C7B25F4F569AE,
+    ],
+}
+
+
+def reference_cases():
+    inputs = STRINGS + [BULK[:n] for n in BULK_LENGTHS]
+    for seed, expected in REFERENCE.items():
+        for data, value in zip(inputs, expected):
+            yield seed, data, value
+
+
+@pytest.mark.parametrize("seed,data,expected", list(reference_cases()))
+def test_komihash_reference(seed, data, expected):
+    """Test les vecteurs officiels pour les trois graines, en une fois et en flux"""
+    assert noncrypto.komihash(data, seed) == expected
+    assert noncrypto.komihash(memoryview(data), seed=seed) == expected
+    h = noncrypto.komihash_stream(data, seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(8, "big")
+    assert h.hexdigest() == f"{expected:016x}"
+
+
+def test_komihash_stream_chunks():
+    """Test que le découpage de l'entrée ne change pas le résultat"""
+    data = bytes(i * 73 % 256 for i in range(1000))
+    for length in (0, 1, 15, 16, 31, 32, 63, 64, 65, 128, 1000):
+        expected = noncrypto.komihash(data[:length], 5)
+        for step in (1, 7, 64, 100):
+            h = noncrypto.komihash_stream(seed=5)
+            for i in range(0, length, step):
+                h.update(data[i:min(i + step, length)])
+            assert h.intdigest() == expected, (length, step)
+
+
+def test_komihash_stream_copy_and_reset():
+    """Test copy(), reset() et les tailles annoncées"""
+    h = noncrypto.komihash_stream(b"abc", 7)
+    clone = h.copy()
+    clone.update(b"def")
+    assert h.intdigest() == noncrypto.komihash(b"abc", 7)
+    assert clone.intdigest() == noncrypto.komihash(b"abcdef", 7)
+    h.reset()
+    assert h.intdigest() == noncrypto.komihash(b"", 7)
+    assert h.digest_size == 8
+    assert h.block_size == 64
+    assert h.name == "komihash_stream"
+
+
+def test_komihash_argument_errors():
+    """Test le rejet des graines hors limites et des chaînes"""
+    with pytest.raises(OverflowError):
+        noncrypto.komihash(b"", -1)
+    with pytest.raises(OverflowError):
+        noncrypto.komihash_stream(b"", 2**64)
+    with pytest.raises(TypeError):
+        noncrypto.komihash("text")
@@ -0,0 +1,80 @@
+"""Tests pour Kupyna-256/512 (DSTU 7564:2014)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+def counting(length):
+    """Octets 0x00, 0x01, ... comme dans les exemples de la norme"""
+    return bytes(i % 256 for i in range(length))
+
+
+@pytest.mark.parametrize("data,expected", [
+    (counting(64), "08f4ee6f1be6903b324c4e27990cb24ef69dd58dbe84813ee0a52f6631239875"),
+    (counting(128), "0a9474e645a7d25e255e9e89fff42ec7eb31349007059284f0b182e452bda882"),
+    (counting(256), "d305a32b963d149dc765f68594505d4077024f836c1bf03806e1624ce176c08f"),
+    (b"", "cd5101d1ccdf0d1d1f4ada56e888cd724ca1a0838a3521e7131d4fb78d0f5eb6"),
+])
+def test_kupyna256_vectors(data, expected):
+    """Test Kupyna-256 sur les exemples de l'annexe de la DSTU 7564"""
+    assert RsHash.Kupyna256(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (counting(64),
+     "3813e2109118cdfb5a6d5e72f7208dccc80a2dfb3afdfb02f46992b5edbe536b"
+     "3560dd1d7e29c6f53978af58b444e37ba685c0dd910533ba5d78efffc13de62a"),
+    (counting(128),
+     "76ed1ac28b1d0143013ffa87213b4090b356441263c13e03fa060a8cada32b97"
+     "9635657f256b15d5fca4a174de029f0b1b4387c878fcc1c00e8705d783fd7ffe"),
+    (b"",
+     "656b2f4cd71462388b64a37043ea55dbe445d452aecd46c3298343314ef04019"
+     "bcfa3f04265a9857f91be91fce197096187ceda78c9c1c021c294a0689198538"),
+])
+def test_kupyna512_vectors(data, expected):
+    """Test Kupyna-512 sur les exemples de l'annexe de la DSTU 7564"""
+    assert RsHash.Kupyna512(data).hexdigest() == expected
+
+
+def test_kupyna_streaming():
+    """Test Kupyna en plusieurs update() autour de la limite de padding (12 octets)"""
+    data = bytes(range(256)) * 3
+    for cls in (RsHash.Kupyna256, RsHash.Kupyna512):
+        for step in (1, 51, 52, 53, 64, 115, 116, 128):
+            h = cls()
+            for start in range(0, len(data), step):
+                h.update(data[start:start + step])
+            assert h.digest() == cls(data).digest()
+        h = cls(data[:100])
+        c = h.copy()
+        c.update(data[100:])
+        assert c.digest() == cls(data).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", [
+    ("kupyna256", "Kupyna256", 32, 64),
+    ("kupyna512", "Kupyna512", 64, 128),
+])
+def test_kupyna_properties_and_new(name, cls, digest_size, block_size):
+    """Test les propriétés et new("kupynaN")"""
+    h = RsHash.new(name.replace("kupyna", "Kupyna-"), b"abc")
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert h.digest() == RsHash.new(name, b"abc").digest()
+    assert name in RsHash.algorithms_available
+
+
+def test_kupyna_fips_mode():
+    """Test qu'en mode FIPS, Kupyna exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.Kupyna256(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("kupyna512")
+        h = RsHash.new("kupyna256", counting(64), usedforsecurity=False)
+        assert h.hexdigest().startswith("08f4ee6f")
+    finally:
+        RsHash.set_fips_mode(False)
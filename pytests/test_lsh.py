@@ -0,0 +1,83 @@
+"""Tests pour LSH-256 et LSH-512 (norme coréenne KS X 3262)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("cls,digest_size,expected", [
+    ("LSH256", 28, "f7c53ba4034e708e74fba42e55997ca5126bb7623688f85342f73732"),
+    ("LSH256", 32, "5fbf365daea5446a7053c52b57404d77a07a5f48a1f7c1963a0898ba1b714741"),
+    ("LSH512", 28, "d1683234513ec5698394571ead128a8cd5373e97661ba20dcf89e489"),
+    ("LSH512", 32, "cd892310532602332b613f1ec11a6962fca61ea09ecffcd4bcf75858d802edec"),
+    ("LSH512", 48,
+     "5f344efaa0e43ccd2e5e194d6039794b4fb431f10fb4b65fd45e9da4ecde0f27"
+     "b66e8dbdfa47252e0d0b741bfd91f9fe"),
+    ("LSH512", 64,
+     "a3d93cfe60dc1aacdd3bd4bef0a6985381a396c7d49d9fd177795697c3535208"
+     "b5c57224bef21084d42083e95a4bd8eb33e869812b65031c428819a1e7ce596d"),
+])
+def test_lsh_vectors(cls, digest_size, expected):
+    """Test les vecteurs KISA pour "abc" dans les six tailles normalisées"""
+    h = getattr(RsHash, cls)(b"abc", digest_size=digest_size)
+    assert h.digest_size == digest_size
+    assert h.hexdigest() == expected
+
+
+def test_lsh_default_digest_size():
+    """Test que la taille par défaut est LSH-256-256 et LSH-512-512"""
+    assert RsHash.LSH256(b"abc").digest() == RsHash.LSH256(b"abc", digest_size=32).digest()
+    assert RsHash.LSH512(b"abc").digest() == RsHash.LSH512(b"abc", digest_size=64).digest()
+    with pytest.raises(ValueError, match="digest_size"):
+        RsHash.LSH256(digest_size=33)
+    with pytest.raises(ValueError, match="digest_size"):
+        RsHash.LSH512(digest_size=0)
+
+
+@pytest.mark.parametrize("cls,block_size", [("LSH256", 128), ("LSH512", 256)])
+def test_lsh_streaming_across_blocks(cls, block_size):
+    """Test LSH en plusieurs update() qui chevauchent les blocs de 128/256 octets"""
+    cls = getattr(RsHash, cls)
+    data = bytes(range(256)) * 5
+    expected = cls(data).digest()
+    for step in (1, block_size - 1, block_size, block_size + 1, 2 * block_size - 1):
+        h = cls()
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.digest() == expected
+    # Un message d'exactement un bloc reçoit un bloc de remplissage entier
+    assert cls(data[:block_size]).digest() != cls(data[:block_size - 1]).digest()
+    h = cls(data[:300])
+    c = h.copy()
+    c.update(data[300:])
+    assert c.digest() == expected
+    assert h.digest() == cls(data[:300]).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", [
+    ("lsh256", "LSH256", 32, 128),
+    ("lsh512", "LSH512", 64, 256),
+])
+def test_lsh_properties_and_new(name, cls, digest_size, block_size):
+    """Test les propriétés et new("lshN")"""
+    h = RsHash.new(name.replace("lsh", "LSH-"), b"abc")
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert h.digest() == getattr(RsHash, cls)(b"abc").digest()
+    assert RsHash.new(name, digest_size=28).digest_size == 28
+    assert name in RsHash.algorithms_available
+
+
+def test_lsh_fips_mode():
+    """Test qu'en mode FIPS, LSH exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.LSH256(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("lsh512")
+        h = RsHash.new("lsh256", b"abc", usedforsecurity=False)
+        assert h.digest() == RsHash.LSH256(b"abc", usedforsecurity=False).digest()
+    finally:
+        RsHash.set_fips_mode(False)
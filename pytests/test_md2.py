@@ -0,0 +1,51 @@
+"""Tests pour MD2 (RFC 1319), disponible uniquement via new("md2")"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "8350e5a3e24c153df2275c9f80692773"),
+    (b"a", "32ec01ec4a6dac72c0ab96fb34c0b5d1"),
+    (b"abc", "da853b0d3f88d99b30283a69e6ded6bb"),
+    (b"message digest", "ab4f496bfb2a530b219ff33031fe06b0"),
+    (b"abcdefghijklmnopqrstuvwxyz", "4e8ddff3650292ab5a4108c3aa47940b"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "da33def2a42df13975352846c30338cd"),
+    (b"1234567890" * 8, "d5976f79d83d3a0dc9806c3c66f3efd8"),
+])
+def test_md2_rfc1319_vectors(data, expected):
+    """Test la suite de tests de la RFC 1319"""
+    assert RsHash.new("md2", data).hexdigest() == expected
+
+
+def test_md2_streaming():
+    """Test MD2 en plusieurs update()"""
+    data = bytes(range(256)) * 3
+    h = RsHash.new("md2")
+    for start in range(0, len(data), 37):
+        h.update(data[start:start + 37])
+    assert h.digest() == RsHash.new("md2", data).digest()
+
+
+def test_md2_properties_and_new():
+    """Test les propriétés, et que MD2 n'a pas de classe dans le module"""
+    h = RsHash.new("MD-2", b"abc")
+    assert h.name == "md2"
+    assert h.digest_size == 16
+    assert h.block_size == 16
+    assert h.copy().hexdigest() == "da853b0d3f88d99b30283a69e6ded6bb"
+    assert "md2" in RsHash.algorithms_available
+    assert not hasattr(RsHash, "MD2")
+
+
+def test_md2_fips_mode():
+    """Test qu'en mode FIPS, MD2 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("md2")
+        h = RsHash.new("md2", b"abc", usedforsecurity=False)
+        assert h.hexdigest() == "da853b0d3f88d99b30283a69e6ded6bb"
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,62 @@
+"""Tests pour MD4 (RFC 1320)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+    (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+    (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+    (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+    (b"abcdefghijklmnopqrstuvwxyz", "d79e1c308aa5bbcdeea8ed63df412da9"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "043f8582f241db351ce627e153e7f0e4"),
+    (b"1234567890" * 8, "e33b4ddc9c38f2199c3e7b164fcc0536"),
+])
+def test_md4_rfc1320_vectors(data, expected):
+    """Test la suite de tests de la RFC 1320"""
+    assert RsHash.MD4(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("password,expected", [
+    ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+    ("password", "8846f7eaee8fb117ad06bdd830b7586c"),
+])
+def test_md4_ntlm_hash(password, expected):
+    """Test le hash NT : MD4 du mot de passe encodé en UTF-16LE"""
+    assert RsHash.MD4(password.encode("utf-16-le")).hexdigest() == expected
+
+
+def test_md4_streaming():
+    """Test MD4 en plusieurs update()"""
+    data = bytes(range(256)) * 3
+    h = RsHash.MD4()
+    for start in range(0, len(data), 37):
+        h.update(data[start:start + 37])
+    assert h.digest() == RsHash.MD4(data).digest()
+
+
+def test_md4_properties_and_new():
+    """Test les propriétés et new("md4")"""
+    h = RsHash.new("MD4", b"abc")
+    assert type(h) is RsHash.MD4
+    assert h.name == "md4"
+    assert h.digest_size == 16
+    assert h.block_size == 64
+    assert h.hexdigest() == "a448017aaf21d8525fc10ae87aa6729d"
+    assert "md4" in RsHash.algorithms_available
+
+
+def test_md4_fips_mode():
+    """Test qu'en mode FIPS, MD4 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.MD4(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("md4")
+        h = RsHash.new("md4", b"abc", usedforsecurity=False)
+        assert h.hexdigest() == "a448017aaf21d8525fc10ae87aa6729d"
+    finally:
+        RsHash.set_fips_mode(False)
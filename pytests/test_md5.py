@@ -0,0 +1,70 @@
+"""Tests pour MD5"""
+import hashlib
+import random
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "d41d8cd98f00b204e9800998ecf8427e"),
+    (b"a", "0cc175b9c0f1b6a831c399e269772661"),
+    (b"abc", "900150983cd24fb0d6963f7d28e17f72"),
+    (b"message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+    (b"abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "d174ab98d277d9f5a5611c2c9f419d9f"),
+    (b"1234567890" * 8, "57edf4a22be3c955ac49da2e2107b67a"),
+])
+def test_md5_rfc1321_vectors(data, expected):
+    """Test la suite de tests de la RFC 1321"""
+    assert RsHash.MD5(data).hexdigest() == expected
+
+
+def test_md5_differential():
+    """Test différentiel aléatoire contre hashlib"""
+    rng = random.Random(1321)
+    for _ in range(50):
+        data = bytes(rng.getrandbits(8) for _ in range(rng.randint(0, 700)))
+        h = RsHash.MD5()
+        expected = hashlib.md5()
+        pos = 0
+        while pos < len(data):
+            step = rng.randint(1, 150)
+            h.update(data[pos:pos + step])
+            expected.update(data[pos:pos + step])
+            pos += step
+            if rng.random() < 0.2:
+                assert h.hexdigest() == expected.hexdigest()
+        assert h.digest() == expected.digest()
+
+
+def test_md5_properties():
+    """Test les propriétés de MD5"""
+    h = RsHash.MD5()
+    assert h.digest_size == 16
+    assert h.block_size == 64
+    assert h.name == "md5"
+
+
+def test_md5_new():
+    """Test new("md5")"""
+    assert RsHash.new("md5", b"abc").hexdigest() == hashlib.md5(b"abc").hexdigest()
+    assert RsHash.new("MD5").name == "md5"
+    assert "md5" in RsHash.algorithms_available
+
+
+def test_md5_fips_mode():
+    """Test qu'en mode FIPS, MD5 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.MD5(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("md5")
+        h = RsHash.new("md5", b"abc", usedforsecurity=False)
+        assert h.hexdigest() == hashlib.md5(b"abc").hexdigest()
+        assert RsHash.MD5(usedforsecurity=False).copy().usedforsecurity is False
+    finally:
+        RsHash.set_fips_mode(False)
+    assert RsHash.MD5(b"abc").usedforsecurity is True
@@ -0,0 +1,120 @@
+"""Tests pour MD6 (soumission SHA-3, mode arbre)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,digest_size,expected", [
+    (b"", 32, "bca38b24a804aa37d821d31af00f5598230122c5bbfc4c4ad5ed40e4258f04ca"),
+    (b"abc", 32, "230637d4e6845cf0d092b558e87625f03881dd53a7439da34cf3b94ed0d8b2c5"),
+    (b"The quick brown fox jumps over the lazy dog", 32,
+     "977592608c45c9923340338450fdcccc21a68888e1e6350e133c5186cd9736ee"),
+    (b"abc", 20, "b5c2d6a7ce6be0c18c9a38b17a0db705c81ab6b5"),
+    (b"abc", 28, "510c30e4202a5cdd8a4f2ae9beebb6f5988128897937615d52e6d228"),
+    (b"abc", 48,
+     "e2c6d31dd8872cbd5a1207481cdac581054d13a4d4fe6854331cd8cf3e7cbafb"
+     "addd6e2517972b8ff57cdc4806d09190"),
+    (b"abc", 64,
+     "00918245271e377a7ffb202b90f3bda5477d8feab12d8a3a8994ebc55fe6e74c"
+     "a8341520032eeea3fdef892f2882378f636212af4b2683ccf80bf025b7d9b457"),
+])
+def test_md6_vectors(data, digest_size, expected):
+    """Test les vecteurs de l'implémentation de référence"""
+    h = RsHash.MD6(data, digest_size=digest_size)
+    assert h.digest_size == digest_size
+    assert h.hexdigest() == expected
+    assert RsHash.md6(data, digest_size=digest_size).hexdigest() == expected
+
+
+def test_md6_modes():
+    """Test le paramètre de mode L : arbre par défaut, chaîne séquentielle pour L=0"""
+    data = bytes(i * 7 % 251 for i in range(10000))
+    assert RsHash.md6(data).hexdigest() == (
+        "4c848995d37af9e91c3f5898d12c7e78cf528410c1ae1d83fcff6efc3f9767e8")
+    assert RsHash.md6(data, mode=64).hexdigest() == RsHash.md6(data).hexdigest()
+    assert RsHash.md6(data, mode=0).hexdigest() == (
+        "1b4a144707a6ab006a6e22524c02c206ac83fa8bc1f8731205c65f654fc86b38")
+    assert RsHash.md6(data, mode=1).hexdigest() == (
+        "a92b5c1b7156f415e0b16b374b7dcb3089e409c958e74d5752f16403b4df8b3f")
+    # L fait partie du mot de contrôle : même un message d'un bloc en dépend
+    assert RsHash.md6(b"abc", mode=1).digest() != RsHash.md6(b"abc").digest()
+
+
+def test_md6_invalid_parameters():
+    """Test le rejet des tailles hors de 1 à 64 octets et des modes au-delà de 64"""
+    with pytest.raises(ValueError, match="digest size"):
+        RsHash.MD6(digest_size=0)
+    with pytest.raises(ValueError, match="digest size"):
+        RsHash.MD6(digest_size=65)
+    with pytest.raises(ValueError, match="mode"):
+        RsHash.MD6(mode=65)
+
+
+@pytest.mark.parametrize("mode", [0, 64])
+def test_md6_streaming_across_blocks(mode):
+    """Test MD6 en plusieurs update() qui chevauchent les blocs de 512 octets"""
+    data = bytes(range(256)) * 40
+    expected = RsHash.md6(data, mode=mode).digest()
+    for step in (1, 383, 384, 511, 512, 513, 4096):
+        h = RsHash.md6(mode=mode)
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.digest() == expected
+    h = RsHash.md6(data[:3000], mode=mode)
+    c = h.copy()
+    c.update(data[3000:])
+    assert c.digest() == expected
+    assert h.digest() == RsHash.md6(data[:3000], mode=mode).digest()
+
+
+def test_md6_large_input():
+    """Test qu'une grande entrée (compressée en parallèle) égale le flux par petits morceaux"""
+    data = bytes(i * 13 % 256 for i in range(300_000))
+    h = RsHash.md6()
+    for start in range(0, len(data), 1000):
+        h.update(data[start:start + 1000])
+    assert RsHash.md6(data).digest() == h.digest()
+
+
+@pytest.mark.parametrize("name", ["md6", "md6_256", "MD6-256"])
+def test_md6_properties_and_new(name):
+    """Test les propriétés et new("md6_256")"""
+    h = RsHash.new(name, b"abc")
+    assert type(h) is RsHash.MD6
+    assert h.name == "md6_256"
+    assert h.digest_size == 32
+    assert h.block_size == 512
+    assert h.digest() == RsHash.md6(b"abc").digest()
+    assert RsHash.new(name, digest_size=64).digest_size == 64
+    assert "md6_256" in RsHash.algorithms_available
+
+
+def test_md6_name_round_trip():
+    """Test que name contient la taille en bits et est accepté par new()"""
+    for size in (1, 16, 20, 64):
+        h = RsHash.md6(b"abc", digest_size=size)
+        assert h.name == f"md6_{size * 8}"
+        other = RsHash.new(h.name, b"abc")
+        assert other.digest_size == size
+        assert other.digest() == h.digest()
+    assert RsHash.new("MD6-160", mode=0).digest() == RsHash.md6(digest_size=20, mode=0).digest()
+    with pytest.raises(ValueError, match="bits"):
+        RsHash.new("md6_100")
+    with pytest.raises(ValueError, match="digest size"):
+        RsHash.new("md6_1024")
+    with pytest.raises(TypeError, match="digest_size"):
+        RsHash.new("md6_128", digest_size=16)
+
+
+def test_md6_fips_mode():
+    """Test qu'en mode FIPS, MD6 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.md6(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("md6_256")
+        h = RsHash.new("md6_256", b"abc", usedforsecurity=False)
+        assert h.digest() == RsHash.MD6(b"abc", usedforsecurity=False).digest()
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,51 @@
+"""Tests pour MetroHash64 et MetroHash128 du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+# Chaîne de test de l'implémentation de référence
+TEST_STRING = b"012345678901234567890123456789012345678901234567890123456789012"
+
+
+@pytest.mark.parametrize("name,seed,expected", [
+    ("metrohash64", 0, "6b753dae06704bad"),
+    ("metrohash64", 1, "3b0d481cf4b9b8df"),
+    ("metrohash128", 0, "c77ce2bfa4ed9f9b0548b2ac5074a297"),
+    ("metrohash128", 1, "45a3cdb838199d7fbdd68d867a14ecef"),
+])
+def test_metrohash_reference_vectors(name, seed, expected):
+    """Test les vecteurs officiels (chaîne de 63 octets, graines 0 et 1)"""
+    h = getattr(noncrypto, name)(TEST_STRING, seed)
+    assert h.hexdigest() == expected
+    assert h.digest() == bytes.fromhex(expected)
+    assert h.intdigest() == int.from_bytes(bytes.fromhex(expected), "little")
+    assert getattr(noncrypto, f"{name}_hexdigest")(TEST_STRING, seed=seed) == expected
+    assert getattr(noncrypto, f"{name}_intdigest")(TEST_STRING, seed) == h.intdigest()
+
+
+@pytest.mark.parametrize("name", ["metrohash64", "metrohash128"])
+def test_metrohash_incremental(name):
+    """Test update() par morceaux aléatoires, copy() et reset()"""
+    cls = getattr(noncrypto, name)
+    rng = random.Random(name)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 300))
+        seed = rng.getrandbits(64)
+        h = cls(seed=seed)
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 70)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.digest() == getattr(noncrypto, f"{name}_digest")(data, seed)
+    c = h.copy()
+    h.reset()
+    assert h.digest() == cls(b"", seed).digest()
+    assert c.digest() == cls(data, seed).digest()
+    size = 8 if name == "metrohash64" else 16
+    assert (c.name, c.digest_size, c.block_size) == (name, size, 32)
+    with pytest.raises(OverflowError):
+        cls(b"", 2**64)
@@ -0,0 +1,94 @@
+"""Tests pour MurmurHash3 du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+
+def smhasher_verification(cls):
+    """VerificationTest de SMHasher : préfixes de 0..255 avec la graine 256 - longueur"""
+    key = bytes(range(256))
+    hashes = b"".join(cls(key[:n], 256 - n).digest() for n in range(256))
+    return int.from_bytes(cls(hashes, 0).digest()[:4], "little")
+
+
+@pytest.mark.parametrize("name,expected", [
+    ("murmur3_32", 0xB0F57EE3),
+    ("murmur3_128", 0x6384BA69),
+])
+def test_murmur3_smhasher_verification(name, expected):
+    """Test les valeurs de vérification canoniques de SMHasher"""
+    assert smhasher_verification(getattr(noncrypto, name)) == expected
+
+
+@pytest.mark.parametrize("data,seed,expected", [
+    (b"", 0, 0),
+    (b"", 1, 0x514E28B7),
+    (b"", 0xFFFFFFFF, 0x81F16F39),
+    (b"test", 0, 0xBA6BD213),
+    (b"Hello, world!", 0x9747B28C, 0x24884CBA),
+    (b"The quick brown fox jumps over the lazy dog", 0x9747B28C, 0x2FA826CD),
+])
+def test_murmur3_32_known_values(data, seed, expected):
+    """Test des valeurs connues de MurmurHash3_x86_32"""
+    h = noncrypto.murmur3_32(data, seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(4, "little")
+    assert noncrypto.murmur3_32_intdigest(data, seed=seed) == expected
+    assert noncrypto.murmur3_32_hexdigest(data, seed) == expected.to_bytes(4, "little").hex()
+
+
+def test_murmur3_signed_conventions():
+    """Test sintdigest() comme entier signé en complément à deux (JVM, mmh3.hash)"""
+    h = noncrypto.murmur3_32(b"test")
+    assert h.intdigest() == 0xBA6BD213
+    assert h.sintdigest() == 0xBA6BD213 - 2**32
+    assert noncrypto.murmur3_32(b"", 1).sintdigest() == 0x514E28B7
+    rng = random.Random(128)
+    for _ in range(20):
+        h = noncrypto.murmur3_128(rng.randbytes(rng.randrange(0, 40)), rng.getrandbits(32))
+        value = h.intdigest()
+        assert h.sintdigest() == (value - 2**128 if value >= 2**127 else value)
+        assert h.digest() == value.to_bytes(16, "little")
+
+
+@pytest.mark.parametrize("name", ["murmur3_32", "murmur3_128"])
+def test_murmur3_incremental(name):
+    """Test update() par morceaux aléatoires, copy() et reset()"""
+    cls = getattr(noncrypto, name)
+    rng = random.Random(name)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 1000))
+        seed = rng.getrandbits(32)
+        h = cls(seed=seed)
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 40)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.intdigest() == getattr(noncrypto, f"{name}_intdigest")(data, seed)
+    c = h.copy()
+    h.reset()
+    assert h.intdigest() == cls(b"", seed).intdigest()
+    assert c.intdigest() == cls(data, seed).intdigest()
+    size = 4 if name == "murmur3_32" else 16
+    assert (c.name, c.digest_size, c.block_size) == (name, size, size)
+    with pytest.raises(OverflowError):
+        cls(b"", 2**32)
+
+
+def test_murmur3_matches_mmh3():
+    """Test différentiel contre le paquet mmh3 pour des entrées et graines aléatoires"""
+    mmh3 = pytest.importorskip("mmh3")
+    rng = random.Random(3)
+    for _ in range(200):
+        data = rng.randbytes(rng.randrange(0, 300))
+        seed = rng.getrandbits(32)
+        assert noncrypto.murmur3_32(data, seed).sintdigest() == mmh3.hash(data, seed)
+        assert noncrypto.murmur3_32_intdigest(data, seed) == mmh3.hash(data, seed, signed=False)
+        h = noncrypto.murmur3_128(data, seed)
+        assert h.intdigest() == mmh3.hash128(data, seed)
+        assert h.sintdigest() == mmh3.hash128(data, seed, signed=True)
+        assert h.digest() == mmh3.hash_bytes(data, seed)
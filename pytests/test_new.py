@@ -0,0 +1,68 @@
+"""Tests pour la fonction fabrique new()"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512"])
+def test_new_positional(name):
+    """Test new() avec des arguments positionnels"""
+    assert RsHash.new(name, b"abc").hexdigest() == hashlib.new(name, b"abc").hexdigest()
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512"])
+def test_new_keywords(name):
+    """Test new() avec name et data passés par mot-clé"""
+    h = RsHash.new(name=name, data=b"abc")
+    assert h.hexdigest() == hashlib.new(name=name, data=b"abc").hexdigest()
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512"])
+def test_new_without_data(name):
+    """Test new() sans données initiales"""
+    assert RsHash.new(name).hexdigest() == hashlib.new(name).hexdigest()
+
+
+def test_new_unknown_keyword():
+    """Test qu'un mot-clé inconnu lève TypeError"""
+    with pytest.raises(TypeError):
+        RsHash.new("sha256", b"abc", bogus=1)
+
+
+@pytest.mark.parametrize("alias,canonical", [
+    ("sha256", "sha256"),
+    ("SHA256", "sha256"),
+    ("sha-256", "sha256"),
+    ("SHA2-256", "sha256"),
+    ("sha512", "sha512"),
+    ("SHA-512", "sha512"),
+    ("sha2_512", "sha512"),
+])
+def test_new_aliases(alias, canonical):
+    """Test que les alias courants sont acceptés et que name est normalisé"""
+    h = RsHash.new(alias, b"abc")
+    assert h.name == canonical
+    assert h.hexdigest() == hashlib.new(canonical, b"abc").hexdigest()
+
+
+def test_new_unknown_matches_hashlib():
+    """Test qu'un nom inconnu lève ValueError avec le message de hashlib"""
+    with pytest.raises(ValueError, match="^unsupported hash type sha-1024"):
+        RsHash.new("sha-1024")
+
+
+def test_new_unknown_lists_supported():
+    """Test que le message d'erreur liste les algorithmes supportés, triés"""
+    with pytest.raises(ValueError) as excinfo:
+        RsHash.new("not-a-hash")
+    message = str(excinfo.value)
+    names = sorted(RsHash.algorithms_available)
+    assert ", ".join(names) in message
+
+
+def test_algorithms_available():
+    """Test que algorithms_available est cohérent avec new()"""
+    assert {"sha256", "sha512"} <= RsHash.algorithms_available
+    for name in RsHash.algorithms_available:
+        assert RsHash.new(name).name == name
@@ -0,0 +1,55 @@
+"""Tests pour les fonctions one-shot (sha256_digest, sha256_hexdigest, ...)"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ONESHOT = [
+    ("sha256", RsHash.sha256_digest, RsHash.sha256_hexdigest, RsHash.SHA256),
+    ("sha512", RsHash.sha512_digest, RsHash.sha512_hexdigest, RsHash.SHA512),
+]
+
+SMALL_MESSAGE = b"hello world"
+
+
+@pytest.mark.parametrize("name,digest,hexdigest,cls", ONESHOT)
+def test_oneshot_matches_hashlib(name, digest, hexdigest, cls):
+    """Test que les fonctions one-shot correspondent à hashlib"""
+    # 4096 octets et plus : le GIL est relâché pendant le calcul
+    for size in [0, 1, 55, 64, 111, 128, 4095, 4096, 100_000]:
+        data = bytes(range(256)) * (size // 256) + bytes(range(size % 256))
+        expected = hashlib.new(name, data)
+        assert digest(data) == expected.digest()
+        assert hexdigest(data) == expected.hexdigest()
+
+
+@pytest.mark.parametrize("name,digest,hexdigest,cls", ONESHOT)
+def test_oneshot_return_types(name, digest, hexdigest, cls):
+    """Test que digest renvoie des bytes et hexdigest une str"""
+    assert isinstance(digest(b"abc"), bytes)
+    assert isinstance(hexdigest(b"abc"), str)
+    assert hexdigest(b"abc") == cls(b"abc").hexdigest()
+
+
+@pytest.mark.benchmark(group="sha256-small")
+def test_bench_sha256_oneshot(benchmark):
+    """Benchmark : chemin one-shot pour un petit message"""
+    benchmark(RsHash.sha256_hexdigest, SMALL_MESSAGE)
+
+
+@pytest.mark.benchmark(group="sha256-small")
+def test_bench_sha256_object(benchmark):
+    """Benchmark : création d'un objet puis hexdigest() pour un petit message"""
+    benchmark(lambda: RsHash.SHA256(SMALL_MESSAGE).hexdigest())
+
+
+@pytest.mark.benchmark(group="sha512-small")
+def test_bench_sha512_oneshot(benchmark):
+    """Benchmark : chemin one-shot pour un petit message"""
+    benchmark(RsHash.sha512_hexdigest, SMALL_MESSAGE)
+
+
+@pytest.mark.benchmark(group="sha512-small")
+def test_bench_sha512_object(benchmark):
+    """Benchmark : création d'un objet puis hexdigest() pour un petit message"""
+    benchmark(lambda: RsHash.SHA512(SMALL_MESSAGE).hexdigest())
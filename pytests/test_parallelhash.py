@@ -0,0 +1,110 @@
+"""Tests pour ParallelHash128 et ParallelHash256 (NIST SP 800-185)"""
+import hashlib
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+SAMPLE = bytes.fromhex("000102030405060710111213141516172021222324252627")
+
+
+def left_encode(x):
+    n = max(1, (x.bit_length() + 7) // 8)
+    return bytes([n]) + x.to_bytes(n, "big")
+
+
+def right_encode(x):
+    n = max(1, (x.bit_length() + 7) // 8)
+    return x.to_bytes(n, "big") + bytes([n])
+
+
+def reference(bits, data, block_size, length, custom=b""):
+    """ParallelHash écrit directement d'après SP 800-185, sur cSHAKE"""
+    shake = hashlib.shake_128 if bits == 128 else hashlib.shake_256
+    cshake = RsHash.cSHAKE128 if bits == 128 else RsHash.cSHAKE256
+    blocks = [data[i:i + block_size] for i in range(0, len(data), block_size)]
+    z = left_encode(block_size)
+    z += b"".join(shake(block).digest(bits // 4) for block in blocks)
+    z += right_encode(len(blocks)) + right_encode(length * 8)
+    return cshake(z, function_name=b"ParallelHash", custom=custom).hexdigest(length)
+
+
+@pytest.mark.parametrize("cls,custom,length,expected", [
+    (RsHash.ParallelHash128, b"", 32,
+     "ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5"),
+    (RsHash.ParallelHash128, b"Parallel Data", 32,
+     "fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206"),
+    (RsHash.ParallelHash256, b"", 64,
+     "bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c45110553"
+     "1b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429"),
+    (RsHash.ParallelHash256, b"Parallel Data", 64,
+     "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb"
+     "33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110"),
+])
+def test_parallelhash_sp800_185_samples(cls, custom, length, expected):
+    """Test les exemples de SP 800-185 (B = 8)"""
+    assert cls(SAMPLE, block_size=8, custom=custom).hexdigest(length) == expected
+
+
+@pytest.mark.parametrize("cls,bits", [
+    (RsHash.ParallelHash128, 128), (RsHash.ParallelHash256, 256),
+])
+@pytest.mark.parametrize("block_size", [1, 100, 8192])
+def test_parallelhash_matches_reference(cls, bits, block_size):
+    """Test contre l'implémentation de référence sur plusieurs blocs"""
+    data = os.urandom(3 * 8192 + 123)
+    assert cls(data, block_size=block_size, custom=b"S").hexdigest(40) == \
+        reference(bits, data, block_size, 40, b"S")
+
+
+def test_parallelhash_incremental():
+    """Test que le découpage des update() ne change pas le résultat"""
+    data = os.urandom(50_000)
+    expected = RsHash.ParallelHash128(data, block_size=1000).hexdigest(32)
+    h = RsHash.ParallelHash128(block_size=1000)
+    for start in range(0, len(data), 777):
+        h.update(data[start:start + 777])
+    assert h.hexdigest(32) == expected
+
+
+def test_parallelhash_default_block_size():
+    """Test que B vaut 8192 octets par défaut"""
+    data = os.urandom(20_000)
+    assert RsHash.ParallelHash256(data).digest(64) == \
+        RsHash.ParallelHash256(data, block_size=8192).digest(64)
+    assert RsHash.ParallelHash256(data).hexdigest(64) == reference(256, data, 8192, 64)
+
+
+def test_parallelhash_length_is_bound():
+    """Test que la longueur demandée entre dans le haché (pas de préfixe)"""
+    h = RsHash.ParallelHash128(SAMPLE, block_size=8)
+    assert h.digest(16) != h.digest(32)[:16]
+
+
+def test_parallelhash_copy_and_name():
+    """Test copy(), name et le constructeur générique"""
+    h = RsHash.ParallelHash128(b"abc", block_size=2)
+    c = h.copy()
+    h.update(b"def")
+    assert c.digest(32) == RsHash.ParallelHash128(b"abc", block_size=2).digest(32)
+    assert h.name == "parallelhash_128"
+    assert RsHash.new("parallelhash_256").name == "parallelhash_256"
+    assert h.block_size == 168
+
+
+@pytest.mark.parametrize("cls,rate", [(RsHash.ParallelHash128, 168), (RsHash.ParallelHash256, 136)])
+def test_parallelhash_chunk_size(cls, rate):
+    """Test que chunk_size est B et que block_size reste le débit de l'éponge"""
+    h = cls(block_size=8192)
+    assert h.chunk_size == 8192
+    assert h.block_size == rate
+    assert cls().chunk_size == 8192
+    assert cls(block_size=8).copy().chunk_size == 8
+
+
+@pytest.mark.parametrize("cls", [RsHash.ParallelHash128, RsHash.ParallelHash256])
+def test_parallelhash_rejects_empty_blocks(cls):
+    """Test que block_size=0 lève ValueError"""
+    with pytest.raises(ValueError):
+        cls(block_size=0)
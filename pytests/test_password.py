@@ -0,0 +1,173 @@
+"""Tests pour l'interface unifiée password_hash / password_verify / password_needs_rehash"""
+import base64
+import hashlib
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Réglages peu coûteux pour chaque schéma
+CHEAP = {
+    "argon2id": {"time_cost": 1, "memory_cost": 8, "parallelism": 1},
+    "argon2i": {"time_cost": 1, "memory_cost": 8, "parallelism": 1, "hash_len": 16},
+    "argon2d": {"time_cost": 1, "memory_cost": 16, "parallelism": 2},
+    "bcrypt": {"rounds": 4},
+    "scrypt": {"n": 16, "r": 1, "p": 1},
+    "sha256_crypt": {"rounds": 1000},
+    "sha512_crypt": {},
+}
+
+PREFIXES = {
+    "argon2id": "$argon2id$", "argon2i": "$argon2i$", "argon2d": "$argon2d$",
+    "bcrypt": "$2b$", "scrypt": "$scrypt$", "sha256_crypt": "$5$", "sha512_crypt": "$6$",
+}
+
+
+def unb64(text):
+    """Décode la base64 sans remplissage des chaînes PHC"""
+    return base64.b64decode(text + "=" * (-len(text) % 4))
+
+
+@pytest.mark.parametrize("scheme", list(CHEAP))
+def test_password_roundtrip(scheme):
+    """Test le hachage puis la vérification pour chaque schéma"""
+    stored = RsHash.password_hash(b"correct horse", scheme, **CHEAP[scheme])
+    assert stored.startswith(PREFIXES[scheme])
+    assert RsHash.password_verify(b"correct horse", stored)
+    assert RsHash.password_verify(bytearray(b"correct horse"), stored.encode())
+    assert not RsHash.password_verify(b"correct horse!", stored)
+    assert stored != RsHash.password_hash(b"correct horse", scheme, **CHEAP[scheme])
+
+
+def test_password_default_scheme():
+    """Test qu'Argon2id est le schéma par défaut"""
+    stored = RsHash.password_hash(b"pw", time_cost=1, memory_cost=8, parallelism=1)
+    assert stored.startswith("$argon2id$v=19$m=8,t=1,p=1$")
+    assert RsHash.password_verify(b"pw", stored)
+
+
+def test_password_verify_cross_scheme():
+    """Test la vérification de chaînes produites par les fonctions de chaque schéma"""
+    stored = [
+        RsHash.argon2_phc(b"pw", time_cost=1, memory_cost=8, parallelism=1),
+        RsHash.bcrypt_hash(b"pw", rounds=4),
+        RsHash.bcrypt_hash(b"pw", rounds=4).replace("$2b$", "$2y$"),
+        RsHash.sha256_crypt(b"pw"),
+        RsHash.sha512_crypt(b"pw", "saltstring", 1000),
+        "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdc"
+        "bYEdFCoEOfaS35inz1",
+    ]
+    for text in stored[:-1]:
+        assert RsHash.password_verify(b"pw", text)
+        assert not RsHash.password_verify(b"wrong", text)
+    assert RsHash.password_verify(b"Hello world!", stored[-1])
+    # Les chaînes de password_hash restent lisibles par les fonctions dédiées
+    argon = RsHash.password_hash(b"pw", "argon2i", **CHEAP["argon2i"])
+    assert RsHash.argon2_verify(argon, b"pw")
+    assert RsHash.bcrypt_verify(b"pw", RsHash.password_hash(b"pw", "bcrypt", rounds=4))
+    assert RsHash.crypt_verify(b"pw", RsHash.password_hash(b"pw", "sha512_crypt"))
+
+
+def test_password_scrypt_matches_hashlib():
+    """Test que la chaîne $scrypt$ contient la clé de hashlib.scrypt"""
+    stored = RsHash.password_hash(b"password", "scrypt", n=1024, r=8, p=2, hash_len=24)
+    _, _, costs, salt, key = stored.split("$")
+    assert costs == "ln=10,r=8,p=2"
+    assert unb64(key) == hashlib.scrypt(b"password", salt=unb64(salt), n=1024, r=8, p=2,
+                                        dklen=24)
+
+
+def test_password_needs_rehash():
+    """Test les décisions de re-hachage selon la politique"""
+    argon = RsHash.password_hash(b"pw", time_cost=1, memory_cost=64, parallelism=1)
+    assert not RsHash.password_needs_rehash(argon, {"time_cost": 1, "memory_cost": 64})
+    assert not RsHash.password_needs_rehash(
+        argon, {"scheme": "argon2id", "time_cost": 1, "memory_cost": 32, "parallelism": 4})
+    assert RsHash.password_needs_rehash(argon, {"time_cost": 2, "memory_cost": 64})
+    assert RsHash.password_needs_rehash(argon, {"time_cost": 1, "memory_cost": 128})
+    assert RsHash.password_needs_rehash(argon, {"time_cost": 1, "memory_cost": 64,
+                                                "hash_len": 64})
+    assert RsHash.password_needs_rehash(argon, {"scheme": "argon2i", "time_cost": 1,
+                                                "memory_cost": 64})
+    # Politique par défaut : 3 passes sur 64 Mio
+    assert RsHash.password_needs_rehash(argon, {})
+
+    bcrypt = RsHash.password_hash(b"pw", "bcrypt", rounds=5)
+    assert not RsHash.password_needs_rehash(bcrypt, {"scheme": "bcrypt", "rounds": 4})
+    assert not RsHash.password_needs_rehash(bcrypt.encode(), {"scheme": "bcrypt", "rounds": 5})
+    assert RsHash.password_needs_rehash(bcrypt, {"scheme": "bcrypt", "rounds": 6})
+    assert RsHash.password_needs_rehash(bcrypt, {"scheme": "bcrypt"})
+    assert RsHash.password_needs_rehash(bcrypt, {"time_cost": 1, "memory_cost": 8})
+
+    sha = RsHash.sha512_crypt(b"pw")
+    assert not RsHash.password_needs_rehash(sha, {"scheme": "sha512_crypt"})
+    assert RsHash.password_needs_rehash(sha, {"scheme": "sha512_crypt", "rounds": 10000})
+    assert RsHash.password_needs_rehash(sha, {"scheme": "sha256_crypt"})
+    assert not RsHash.password_needs_rehash(
+        RsHash.sha512_crypt(b"pw", rounds=20000), {"scheme": "sha512_crypt", "rounds": 10000})
+
+    scrypt = RsHash.password_hash(b"pw", "scrypt", n=16, r=2, p=1)
+    assert not RsHash.password_needs_rehash(scrypt, {"scheme": "scrypt", "n": 16, "r": 2})
+    for weaker in ({"n": 32}, {"r": 4}, {"p": 2}, {"hash_len": 64}):
+        assert RsHash.password_needs_rehash(scrypt, {"scheme": "scrypt", "n": 16, "r": 2,
+                                                     **weaker})
+
+
+def test_password_upgrade_on_login():
+    """Test le scénario de mise à niveau transparente à la connexion"""
+    policy = {"scheme": "argon2id", "time_cost": 1, "memory_cost": 8, "parallelism": 1}
+    stored = RsHash.sha512_crypt(b"hunter2")
+    assert RsHash.password_verify(b"hunter2", stored)
+    assert RsHash.password_needs_rehash(stored, policy)
+    params = {key: value for key, value in policy.items() if key != "scheme"}
+    stored = RsHash.password_hash(b"hunter2", policy["scheme"], **params)
+    assert RsHash.password_verify(b"hunter2", stored)
+    assert not RsHash.password_needs_rehash(stored, policy)
+
+
+def test_password_invalid_hash():
+    """Test que les chaînes invalides lèvent InvalidHashError"""
+    assert issubclass(RsHash.InvalidHashError, ValueError)
+    for bad in ("", "password", "$1$salt$hash", "$argon2id$v=19$m=8,t=1,p=1$c2FsdA",
+                "$argon2id$v=19$m=1,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+                "$2b$04$tooshort", "$5$saltstring$short",
+                "$scrypt$ln=4,r=1$c2FsdA$aGFzaA", "$scrypt$ln=4,r=1,p=1$c2FsdA$"):
+        with pytest.raises(RsHash.InvalidHashError):
+            RsHash.password_verify(b"pw", bad)
+        with pytest.raises(RsHash.InvalidHashError):
+            RsHash.password_needs_rehash(bad, {})
+    # Bien formée, mais n trop grand pour r = 1
+    with pytest.raises(RsHash.InvalidHashError):
+        RsHash.password_verify(b"pw", "$scrypt$ln=20,r=1,p=1$c2FsdA$aGFzaA")
+    with pytest.raises(TypeError):
+        RsHash.password_verify(b"pw", 5)
+
+
+def test_password_errors():
+    """Test les paramètres invalides"""
+    with pytest.raises(ValueError, match="scheme"):
+        RsHash.password_hash(b"pw", "md5")
+    with pytest.raises(TypeError, match="rounds"):
+        RsHash.password_hash(b"pw", "argon2id", rounds=4)
+    with pytest.raises(TypeError, match="'n'"):
+        RsHash.password_hash(b"pw", "bcrypt", n=16)
+    with pytest.raises(ValueError):
+        RsHash.password_hash(b"pw", "bcrypt", rounds=3)
+    with pytest.raises(ValueError):
+        RsHash.password_hash(b"pw", "scrypt", n=15)
+    for scheme in ("argon2id", "scrypt"):
+        for hash_len in (3, 1025, 2**62):
+            with pytest.raises(ValueError, match="hash_len"):
+                RsHash.password_hash(b"pw", scheme, hash_len=hash_len)
+        stored = RsHash.password_hash(b"pw", scheme, **CHEAP[scheme])
+        with pytest.raises(ValueError, match="hash_len"):
+            RsHash.password_needs_rehash(stored, {"scheme": scheme, "hash_len": 2**62})
+    with pytest.raises(TypeError):
+        RsHash.password_hash("pw")
+    stored = RsHash.sha256_crypt(b"pw")
+    with pytest.raises(ValueError, match="scheme"):
+        RsHash.password_needs_rehash(stored, {"scheme": "md5"})
+    with pytest.raises(TypeError):
+        RsHash.password_needs_rehash(stored, {"scheme": "bcrypt", "time_cost": 1})
+    with pytest.raises(TypeError):
+        RsHash.password_needs_rehash(stored, "bcrypt")
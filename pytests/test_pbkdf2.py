@@ -0,0 +1,102 @@
+"""Tests pour pbkdf2_hmac"""
+import hashlib
+import random
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Entrées de la RFC 6070 avec HMAC-SHA-256 (le cas à 16 777 216 itérations est omis)
+RFC6070_SHA256 = [
+    (b"password", b"salt", 1, 32,
+     "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"),
+    (b"password", b"salt", 2, 32,
+     "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"),
+    (b"password", b"salt", 4096, 32,
+     "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"),
+    (b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 40,
+     "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"),
+    (b"pass\0word", b"sa\0lt", 4096, 16, "89b69d0516f829893c696226650a8687"),
+]
+
+# Vecteurs originaux de la RFC 6070, avec SHA-1
+RFC6070_SHA1 = [
+    (b"password", b"salt", 1, 20, "0c60c80f961f0e71f3a9b524af6012062fe037a6"),
+    (b"password", b"salt", 2, 20, "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"),
+    (b"password", b"salt", 4096, 20, "4b007901b765489abead49d926f721d065a429c1"),
+    (b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 25,
+     "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"),
+    (b"pass\0word", b"sa\0lt", 4096, 16, "56fa6aa75548099dcc37d7f03425e0c3"),
+]
+
+
+@pytest.mark.parametrize("password,salt,iterations,dklen,expected", RFC6070_SHA256)
+def test_pbkdf2_sha256_rfc6070(password, salt, iterations, dklen, expected):
+    """Test les entrées de la RFC 6070 adaptées à SHA-256"""
+    assert RsHash.pbkdf2_hmac("sha256", password, salt, iterations, dklen).hex() == expected
+
+
+@pytest.mark.parametrize("password,salt,iterations,dklen,expected", RFC6070_SHA1)
+def test_pbkdf2_sha1_rfc6070(password, salt, iterations, dklen, expected):
+    """Test les vecteurs originaux de la RFC 6070"""
+    assert RsHash.pbkdf2_hmac("sha1", password, salt, iterations, dklen=dklen).hex() == expected
+
+
+@pytest.mark.parametrize("name", ["sha1", "sha224", "sha256", "sha512", "sha3_256", "md5"])
+def test_pbkdf2_matches_hashlib(name):
+    """Test différentiel contre hashlib, y compris plusieurs blocs de sortie"""
+    rng = random.Random(name)
+    for _ in range(8):
+        password = rng.randbytes(rng.randrange(0, 200))
+        salt = rng.randbytes(rng.randrange(0, 64))
+        iterations = rng.randrange(1, 300)
+        dklen = rng.choice([None, 1, 16, rng.randrange(1, 200)])
+        expected = hashlib.pbkdf2_hmac(name, password, salt, iterations, dklen)
+        assert RsHash.pbkdf2_hmac(name, password, salt, iterations, dklen) == expected
+
+
+def test_pbkdf2_defaults_and_inputs():
+    """Test dklen par défaut, les objets tampons et les classes comme hash_name"""
+    expected = hashlib.pbkdf2_hmac("sha512", b"pw", b"salt", 10)
+    assert len(RsHash.pbkdf2_hmac("sha512", b"pw", b"salt", 10)) == 64
+    assert RsHash.pbkdf2_hmac("SHA-512", bytearray(b"pw"), memoryview(b"salt"), 10) == expected
+    assert RsHash.pbkdf2_hmac(RsHash.SHA512, b"pw", b"salt", 10) == expected
+
+
+def test_pbkdf2_many_iterations():
+    """Test un grand nombre d'itérations, calculé sans le GIL depuis plusieurs threads"""
+    expected = hashlib.pbkdf2_hmac("sha256", b"password", b"salt", 20000)
+    results = []
+    threads = [
+        threading.Thread(
+            target=lambda: results.append(
+                RsHash.pbkdf2_hmac("sha256", b"password", b"salt", 20000)))
+        for _ in range(4)
+    ]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert results == [expected] * 4
+
+
+def test_pbkdf2_argument_errors():
+    """Test les mêmes erreurs que hashlib pour les itérations et dklen"""
+    with pytest.raises(ValueError):
+        RsHash.pbkdf2_hmac("sha256", b"pw", b"salt", 0)
+    with pytest.raises(ValueError):
+        RsHash.pbkdf2_hmac("sha256", b"pw", b"salt", -5)
+    with pytest.raises(ValueError):
+        RsHash.pbkdf2_hmac("sha256", b"pw", b"salt", 1, 0)
+    with pytest.raises(OverflowError):
+        RsHash.pbkdf2_hmac("sha256", b"pw", b"salt", 2**32)
+    for dklen in (2**31, 2**62):
+        with pytest.raises(OverflowError, match="key length is too great"):
+            RsHash.pbkdf2_hmac("sha256", b"pw", b"salt", 1, dklen)
+        with pytest.raises(OverflowError, match="key length is too great"):
+            hashlib.pbkdf2_hmac("sha256", b"pw", b"salt", 1, dklen)
+    with pytest.raises(TypeError):
+        RsHash.pbkdf2_hmac("sha256", "pw", b"salt", 1)
+    with pytest.raises(ValueError, match="unsupported"):
+        RsHash.pbkdf2_hmac("nope", b"pw", b"salt", 1)
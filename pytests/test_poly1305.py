@@ -0,0 +1,119 @@
+"""Tests pour Poly1305 (RFC 8439)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+IETF = (b"Any submission to the IETF intended by the Contributor for publication as all or "
+        b"part of an IETF Internet-Draft or RFC and any statement made within the context of "
+        b"an IETF activity is considered an \"IETF Contribution\". Such statements include oral "
+        b"statements in IETF sessions, as well as written and electronic communications made "
+        b"at any time or place, which are addressed to")
+JABBERWOCKY = (b"'Twas brillig, and the slithy toves\nDid gyre and gimble in the wabe:\n"
+               b"All mimsy were the borogoves,\nAnd the mome raths outgrabe.")
+ZERO = "00" * 16
+ONE = "01" + "00" * 15
+TWO = "02" + "00" * 15
+R10 = "01000000000000000400000000000000"
+DATA10 = bytes.fromhex("e33594d7505e43b900000000000000003394d7505e4379cd0100000000000000"
+                       "0000000000000000000000000000000001000000000000000000000000000000")
+
+# Section 2.5.2 et annexe A.3 de la RFC 8439 : r, s, message, tag
+VECTORS = [
+    ("85d6be7857556d337f4452fe42d506a8", "0103808afb0db2fd4abff6af4149f51b",
+     b"Cryptographic Forum Research Group", "a8061dc1305136c6c22b8baf0c0127a9"),
+    (ZERO, ZERO, bytes(64), ZERO),
+    (ZERO, "36e5f6b5c5e06070f0efca96227a863e", IETF, "36e5f6b5c5e06070f0efca96227a863e"),
+    ("36e5f6b5c5e06070f0efca96227a863e", ZERO, IETF, "f3477e7cd95417af89a6b8794c310cf0"),
+    ("1c9240a5eb55d38af333888604f6b5f0", "473917c1402b80099dca5cbc207075c0", JABBERWOCKY,
+     "4541669a7eaaee61e708dc7cbcc5eb62"),
+    # Cas limites de l'annexe A.3 (vecteurs 5 à 11)
+    (TWO, ZERO, b"\xff" * 16, "03" + "00" * 15),
+    (TWO, "ff" * 16, bytes.fromhex(TWO), "03" + "00" * 15),
+    (ONE, ZERO, b"\xff" * 16 + b"\xf0" + b"\xff" * 15 + b"\x11" + bytes(15), "05" + "00" * 15),
+    (ONE, ZERO, b"\xff" * 16 + b"\xfb" + b"\xfe" * 15 + b"\x01" * 16, ZERO),
+    (TWO, ZERO, b"\xfd" + b"\xff" * 15, "fa" + "ff" * 15),
+    (R10, ZERO, DATA10, "14000000000000005500000000000000"),
+    (R10, ZERO, DATA10[:48], "13000000000000000000000000000000"),
+]
+
+
+@pytest.mark.parametrize("r,s,data,expected", VECTORS)
+def test_poly1305_rfc8439(r, s, data, expected):
+    """Test les vecteurs de la RFC 8439"""
+    key = bytes.fromhex(r + s)
+    assert RsHash.poly1305(key, data).hex() == expected
+    assert RsHash.Poly1305(key, data).hexdigest() == expected
+    assert RsHash.Poly1305(key, data).verify(bytes.fromhex(expected))
+
+
+def test_poly1305_incremental():
+    """Test que le découpage des données ne change pas le tag"""
+    key = bytes(range(32))
+    expected = RsHash.poly1305(key, IETF)
+    for split in (0, 1, 15, 16, 17, 100, len(IETF)):
+        mac = RsHash.Poly1305(key)
+        mac.update(IETF[:split])
+        mac.update(memoryview(IETF)[split:])
+        assert mac.digest() == expected
+    mac = RsHash.Poly1305(key)
+    for byte in IETF:
+        mac.update(bytes([byte]))
+    assert mac.digest() == expected
+
+
+def test_poly1305_matches_cryptography():
+    """Test différentiel contre pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives.poly1305 import Poly1305
+    for size in (0, 1, 16, 33, 1000, 1 << 17):
+        key = bytes((i * 37 + size) % 256 for i in range(32))
+        data = bytes(i % 251 for i in range(size))
+        assert RsHash.poly1305(key, data) == Poly1305.generate_tag(key, data)
+
+
+def test_poly1305_verify():
+    """Test la vérification des tags"""
+    key = bytes(range(32))
+    tag = RsHash.poly1305(key, b"message")
+    assert RsHash.Poly1305(key, b"message").verify(tag)
+    assert not RsHash.Poly1305(key, b"messagE").verify(tag)
+    assert not RsHash.Poly1305(key, b"message").verify(tag[:15])
+    assert not RsHash.Poly1305(key, b"message").verify(tag + b"\0")
+    assert not RsHash.Poly1305(key, b"message").verify(bytes([tag[0] ^ 1]) + tag[1:])
+
+
+def test_poly1305_single_finalize():
+    """Test qu'un objet ne peut être finalisé qu'une fois"""
+    mac = RsHash.Poly1305(bytes(32), b"data")
+    assert (mac.digest_size, mac.block_size, mac.name) == (16, 16, "poly1305")
+    mac.digest()
+    for call in (mac.digest, mac.hexdigest, lambda: mac.update(b"more"),
+                 lambda: mac.verify(bytes(16))):
+        with pytest.raises(RuntimeError, match="finalized"):
+            call()
+    mac = RsHash.Poly1305(bytes(range(32)))
+    assert not mac.verify(bytes(16))
+    with pytest.raises(RuntimeError):
+        mac.verify(bytes(16))
+    assert not hasattr(mac, "copy")
+
+
+def test_poly1305_errors():
+    """Test les clés de mauvaise taille et le mode strict"""
+    for size in (0, 16, 31, 33, 64):
+        with pytest.raises(ValueError, match="32 bytes"):
+            RsHash.poly1305(bytes(size), b"data")
+        with pytest.raises(ValueError, match="32 bytes"):
+            RsHash.Poly1305(bytes(size))
+    with pytest.raises(TypeError):
+        RsHash.poly1305("k" * 32, b"data")
+    with pytest.raises(TypeError):
+        RsHash.Poly1305(bytes(32)).update("data")
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.poly1305(bytes(32), b"data")
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.Poly1305(bytes(32))
+    finally:
+        RsHash.set_fips_mode(False)
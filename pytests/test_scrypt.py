@@ -0,0 +1,92 @@
+"""Tests pour scrypt (RFC 7914)"""
+import hashlib
+import os
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Vecteurs de la section 12 de la RFC 7914 : mot de passe, sel, N, r, p, clé de 64 octets
+RFC7914 = [
+    (b"", b"", 16, 1, 1,
+     "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442"
+     "fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"),
+    (b"password", b"NaCl", 1024, 8, 16,
+     "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162"
+     "2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"),
+    (b"pleaseletmein", b"SodiumChloride", 16384, 8, 1,
+     "7023bdcb3afd7348461c06cd81fd38ebfda8fbba904f8e3ea9b543f6545da1f2"
+     "d5432955613f0fcf62d49705242a9af9e61e85dc0d651e40dfcf017b45575887"),
+]
+
+
+@pytest.mark.parametrize("password,salt,n,r,p,expected", RFC7914)
+def test_scrypt_rfc7914(password, salt, n, r, p, expected):
+    """Test les trois premiers vecteurs de la RFC 7914"""
+    assert RsHash.scrypt(password, salt=salt, n=n, r=r, p=p).hex() == expected
+
+
+def test_scrypt_rfc7914_large():
+    """Test le vecteur N=1048576 (1 Gio), lent : lancer avec RSHASH_SLOW_TESTS=1"""
+    if not os.environ.get("RSHASH_SLOW_TESTS"):
+        pytest.skip("set RSHASH_SLOW_TESTS=1 to run the 1 GiB scrypt vector")
+    key = RsHash.scrypt(b"pleaseletmein", salt=b"SodiumChloride", n=1048576, r=8, p=1,
+                        maxmem=2**31 - 1)
+    assert key.hex() == (
+        "2101cb9b6a511aaeaddbbe09cf70f881ec568d574a2ffd4dabe5ee9820adaa47"
+        "8e56fd8f4ba5d09ffa1c6d927c40f4c337304049e8a952fbcbf45c6fa77a41a4")
+
+
+def test_scrypt_matches_hashlib():
+    """Test différentiel contre hashlib.scrypt"""
+    rng = random.Random(7914)
+    for _ in range(20):
+        password = rng.randbytes(rng.randrange(0, 100))
+        salt = rng.randbytes(rng.randrange(0, 64))
+        kwargs = dict(n=2 ** rng.randrange(1, 9), r=rng.randrange(1, 5),
+                      p=rng.randrange(1, 4), dklen=rng.randrange(1, 150))
+        expected = hashlib.scrypt(password, salt=salt, **kwargs)
+        assert RsHash.scrypt(password, salt=salt, **kwargs) == expected
+
+
+def test_scrypt_inputs():
+    """Test dklen par défaut et les objets tampons"""
+    expected = hashlib.scrypt(b"pw", salt=b"salt", n=16, r=1, p=1)
+    assert len(expected) == 64
+    assert RsHash.scrypt(bytearray(b"pw"), salt=memoryview(b"salt"), n=16, r=1, p=1) == expected
+
+
+def test_scrypt_maxmem():
+    """Test la limite mémoire : 32 Mio par défaut, comme hashlib"""
+    # 128 * r * (N + 2 + p) octets
+    needed = 128 * 8 * (16384 + 2 + 1)
+    assert RsHash.scrypt(b"", salt=b"", n=16384, r=8, p=1, maxmem=needed, dklen=16)
+    with pytest.raises(ValueError, match="memory limit"):
+        RsHash.scrypt(b"", salt=b"", n=16384, r=8, p=1, maxmem=needed - 1)
+    with pytest.raises(ValueError, match="memory limit"):
+        RsHash.scrypt(b"", salt=b"", n=32768, r=8, p=1)
+    with pytest.raises(ValueError, match="memory limit"):
+        RsHash.scrypt(b"", salt=b"", n=1048576, r=8, p=1)
+    for maxmem in (-1, 2**31):
+        with pytest.raises(ValueError, match="maxmem"):
+            RsHash.scrypt(b"", salt=b"", n=16, r=1, p=1, maxmem=maxmem)
+
+
+def test_scrypt_argument_errors():
+    """Test les mêmes erreurs que hashlib pour n, r, p, dklen et les arguments"""
+    for n in (0, 1, 3, 24, 1000):
+        with pytest.raises(ValueError, match="power of 2"):
+            RsHash.scrypt(b"", salt=b"", n=n, r=1, p=1)
+    for r, p in ((0, 1), (1, 0)):
+        with pytest.raises(ValueError):
+            RsHash.scrypt(b"", salt=b"", n=16, r=r, p=p)
+    for dklen in (0, 2**31):
+        with pytest.raises(ValueError, match="dklen"):
+            RsHash.scrypt(b"", salt=b"", n=16, r=1, p=1, dklen=dklen)
+    with pytest.raises(TypeError):
+        RsHash.scrypt(b"", n=16, r=1, p=1)
+    with pytest.raises(TypeError):
+        RsHash.scrypt(b"", b"", 16, 1, 1)
+    with pytest.raises(TypeError):
+        RsHash.scrypt("pw", salt=b"", n=16, r=1, p=1)
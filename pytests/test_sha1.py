@@ -0,0 +1,53 @@
+"""Tests pour SHA1"""
+import hashlib
+import random
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+    (b"abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+    (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+     "84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+    (b"a" * 1000000, "34aa973cd4c4daa4f61eeb2bdbad27316534016f"),
+])
+def test_sha1_vectors(data, expected):
+    """Test les vecteurs de test de FIPS 180-4 (dont le million de 'a')"""
+    assert RsHash.SHA1(data).hexdigest() == expected
+
+
+def test_sha1_differential():
+    """Test différentiel contre hashlib avec des découpages aléatoires"""
+    rng = random.Random(1)
+    for _ in range(20):
+        data = bytes(rng.getrandbits(8) for _ in range(rng.randint(0, 500)))
+        h = RsHash.SHA1()
+        pos = 0
+        while pos < len(data):
+            step = rng.randint(1, 130)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.digest() == hashlib.sha1(data).digest()
+
+
+def test_sha1_properties():
+    """Test les propriétés de SHA1"""
+    h = RsHash.SHA1()
+    assert h.digest_size == 20
+    assert h.block_size == 64
+    assert h.name == "sha1"
+
+
+@pytest.mark.parametrize("name", ["sha1", "SHA1", "sha-1"])
+def test_sha1_new(name):
+    """Test new() avec sha1 et ses alias"""
+    assert RsHash.new(name, b"abc").hexdigest() == hashlib.sha1(b"abc").hexdigest()
+    assert "sha1" in RsHash.algorithms_available
+
+
+def test_sha1_usedforsecurity():
+    """Test que usedforsecurity est accepté et exposé"""
+    assert RsHash.SHA1(b"abc", usedforsecurity=False).usedforsecurity is False
+    assert RsHash.new("sha1", usedforsecurity=False).usedforsecurity is False
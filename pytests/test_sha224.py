@@ -0,0 +1,52 @@
+"""Tests pour SHA224"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"),
+    (b"abc", "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"),
+    (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+     "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"),
+])
+def test_sha224_fips_vectors(data, expected):
+    """Test les vecteurs de test de FIPS 180-4"""
+    assert RsHash.SHA224(data).hexdigest() == expected
+
+
+def test_sha224_streaming():
+    """Test SHA224 en plusieurs update() de tailles variées"""
+    data = bytes(range(256)) * 5
+    h = RsHash.SHA224()
+    for start in range(0, len(data), 37):
+        h.update(data[start:start + 37])
+    assert h.digest() == hashlib.sha224(data).digest()
+
+
+def test_sha224_properties():
+    """Test les propriétés de SHA224"""
+    h = RsHash.SHA224()
+    assert h.digest_size == 28
+    assert h.block_size == 64
+    assert h.name == "sha224"
+    assert len(h.digest()) == 28
+
+
+@pytest.mark.parametrize("name", ["sha224", "SHA-224", "sha2_224"])
+def test_sha224_new(name):
+    """Test new() avec sha224 et ses alias"""
+    h = RsHash.new(name, b"abc")
+    assert h.name == "sha224"
+    assert h.hexdigest() == hashlib.sha224(b"abc").hexdigest()
+    assert "sha224" in RsHash.algorithms_available
+
+
+def test_sha224_copy():
+    """Test que copy() reprend l'état courant du hasher"""
+    h1 = RsHash.SHA224(b"x" * 70)
+    h2 = h1.copy()
+    h2.update(b"tail")
+    assert h2.hexdigest() == hashlib.sha224(b"x" * 70 + b"tail").hexdigest()
+    assert h1.hexdigest() == hashlib.sha224(b"x" * 70).hexdigest()
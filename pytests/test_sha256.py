@@ -1,5 +1,6 @@
 """Tests pour SHA256"""
 import hashlib
+import random
 import pytest
 
 
@@ -17,7 +18,7 @@ def test_sha256_empty():
     try:
         import RsHash
         expected = hashlib.sha256(b"").hexdigest()
-        result = RsHash.SHA256(b"")
+        result = RsHash.SHA256(b"").hexdigest()
         assert result == expected
     except ImportError:
         pytest.skip("RsHash not installed")
@@ -28,7 +29,7 @@ def test_sha256_abc():
     try:
         import RsHash
         expected = hashlib.sha256(b"abc").hexdigest()
-        result = RsHash.SHA256(b"abc")
+        result = RsHash.SHA256(b"abc").hexdigest()
         assert result == expected
     except ImportError:
         pytest.skip("RsHash not installed")
@@ -40,7 +41,7 @@ def test_sha256_long():
         import RsHash
         data = b"a" * 1000
         expected = hashlib.sha256(data).hexdigest()
-        result = RsHash.SHA256(data)
+        result = RsHash.SHA256(data).hexdigest()
         assert result == expected
     except ImportError:
         pytest.skip("RsHash not installed")
@@ -78,3 +79,77 @@ def test_sha256_properties():
         assert hasher.name == "sha256"
     except ImportError:
         pytest.skip("RsHash not installed")
+
+
+def test_sha256_copy():
+    """Test que copy() reprend l'état courant du hasher"""
+    try:
+        import RsHash
+        data = bytes(range(256)) * 2
+        for split in [0, 1, 63, 64, 65, 127, 128, 129, 300, len(data)]:
+            h1 = RsHash.SHA256(data[:split])
+            h2 = h1.copy()
+            h2.update(data[split:])
+            assert h2.hexdigest() == hashlib.sha256(data).hexdigest()
+            # L'original n'est pas affecté par la copie
+            assert h1.hexdigest() == hashlib.sha256(data[:split]).hexdigest()
+    except ImportError:
+        pytest.skip("RsHash not installed")
+
+
+def test_sha256_digest_idempotent():
+    """Test que digest() et hexdigest() peuvent être appelés plusieurs fois"""
+    try:
+        import RsHash
+        for data in [b"", b"abc", b"a" * 200]:
+            expected = hashlib.sha256(data)
+            h = RsHash.SHA256(data)
+            assert h.digest() == expected.digest()
+            assert h.hexdigest() == expected.hexdigest()
+            assert h.digest() == expected.digest()
+            assert h.hexdigest() == expected.hexdigest()
+    except ImportError:
+        pytest.skip("RsHash not installed")
+
+
+def test_sha256_update_after_digest():
+    """Test différentiel : update() et digest() entrelacés comme avec hashlib"""
+    try:
+        import RsHash
+        rng = random.Random(256)
+        for _ in range(20):
+            expected = hashlib.sha256()
+            h = RsHash.SHA256()
+            for _ in range(rng.randint(1, 15)):
+                if rng.random() < 0.5:
+                    chunk = bytes(rng.getrandbits(8) for _ in range(rng.randint(0, 300)))
+                    expected.update(chunk)
+                    h.update(chunk)
+                else:
+                    assert h.hexdigest() == expected.hexdigest()
+            assert h.digest() == expected.digest()
+    except ImportError:
+        pytest.skip("RsHash not installed")
+
+
+def test_sha256_digest_cache_invalidation():
+    """Test que le digest mis en cache est invalidé par update()"""
+    try:
+        import RsHash
+        h = RsHash.SHA256(b"abc")
+        first = h.digest()
+        assert h.hexdigest() == first.hex()
+        h.update(b"def")
+        assert h.digest() == hashlib.sha256(b"abcdef").digest()
+        assert h.hexdigest() == h.digest().hex()
+        # update() vide : le digest reste le même
+        h.update(b"")
+        assert h.digest() == hashlib.sha256(b"abcdef").digest()
+        # Une copie faite après digest() ne partage pas le cache
+        clone = h.copy()
+        clone.update(b"g")
+        assert clone.hexdigest() == hashlib.sha256(b"abcdefg").hexdigest()
+        assert h.hexdigest() == hashlib.sha256(b"abcdef").hexdigest()
+    except ImportError:
+        pytest.skip("RsHash not installed")
+
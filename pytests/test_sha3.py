@@ -0,0 +1,89 @@
+"""Tests pour SHA3-224, SHA3-256, SHA3-384 et SHA3-512"""
+import hashlib
+import random
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# (nom, classe, taille du digest, taille de bloc = rate)
+VARIANTS = [
+    ("sha3_224", RsHash.SHA3_224, 28, 144),
+    ("sha3_256", RsHash.SHA3_256, 32, 136),
+    ("sha3_384", RsHash.SHA3_384, 48, 104),
+    ("sha3_512", RsHash.SHA3_512, 64, 72),
+]
+
+
+@pytest.mark.parametrize("cls,data,expected", [
+    (RsHash.SHA3_224, b"", "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"),
+    (RsHash.SHA3_224, b"abc", "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"),
+    (RsHash.SHA3_256, b"abc",
+     "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+    (RsHash.SHA3_384, b"",
+     "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2a"
+     "c3713831264adb47fb6bd1e058d5f004"),
+    (RsHash.SHA3_384, b"abc",
+     "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2"
+     "98d88cea927ac7f539f1edf228376d25"),
+    (RsHash.SHA3_512, b"abc",
+     "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e"
+     "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"),
+])
+def test_sha3_fips202_vectors(cls, data, expected):
+    """Test les vecteurs de test de FIPS 202"""
+    assert cls(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", VARIANTS)
+def test_sha3_rate_boundaries(name, cls, digest_size, block_size):
+    """Compare avec hashlib pour des longueurs autour de chaque frontière de rate"""
+    rng = random.Random(block_size)
+    for blocks in range(4):
+        for delta in (-2, -1, 0, 1, 2):
+            length = blocks * block_size + delta
+            if length < 0:
+                continue
+            data = bytes(rng.getrandbits(8) for _ in range(length))
+            assert cls(data).digest() == hashlib.new(name, data).digest(), length
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", VARIANTS)
+def test_sha3_random_streaming(name, cls, digest_size, block_size):
+    """Compare avec hashlib pour des update() de longueurs aléatoires"""
+    rng = random.Random(digest_size)
+    h = cls()
+    reference = hashlib.new(name)
+    for _ in range(50):
+        chunk = bytes(rng.getrandbits(8) for _ in range(rng.randrange(2 * block_size)))
+        h.update(chunk)
+        reference.update(chunk)
+        assert h.digest() == reference.digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", VARIANTS)
+def test_sha3_properties(name, cls, digest_size, block_size):
+    """Test les propriétés de chaque variante"""
+    h = cls()
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert len(h.digest()) == digest_size
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", VARIANTS)
+def test_sha3_new(name, cls, digest_size, block_size):
+    """Test new() avec le nom hashlib et l'alias à tiret"""
+    for alias in (name, name.replace("_", "-").upper()):
+        h = RsHash.new(alias, b"abc")
+        assert type(h) is cls
+        assert h.hexdigest() == hashlib.new(name, b"abc").hexdigest()
+    assert name in RsHash.algorithms_available
+
+
+def test_sha3_copy():
+    """Test que copy() reprend l'état courant du sponge"""
+    h1 = RsHash.SHA3_256(b"x" * 150)
+    h2 = h1.copy()
+    h2.update(b"tail")
+    assert h2.hexdigest() == hashlib.sha3_256(b"x" * 150 + b"tail").hexdigest()
+    assert h1.hexdigest() == hashlib.sha3_256(b"x" * 150).hexdigest()
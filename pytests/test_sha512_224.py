@@ -0,0 +1,44 @@
+"""Tests pour SHA512/224"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4"),
+    (b"abc", "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"),
+    (b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno"
+     b"ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+     "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9"),
+])
+def test_sha512_224_fips_vectors(data, expected):
+    """Test les vecteurs de test de FIPS 180-4"""
+    assert RsHash.SHA512_224(data).hexdigest() == expected
+
+
+def test_sha512_224_streaming():
+    """Test SHA512/224 en plusieurs update() de tailles variées"""
+    data = bytes(range(256)) * 5
+    h = RsHash.SHA512_224()
+    for start in range(0, len(data), 53):
+        h.update(data[start:start + 53])
+    assert h.digest() == hashlib.new("sha512_224", data).digest()
+
+
+def test_sha512_224_properties():
+    """Test les propriétés de SHA512/224"""
+    h = RsHash.SHA512_224()
+    assert h.digest_size == 28
+    assert h.block_size == 128
+    assert h.name == "sha512_224"
+    assert len(h.digest()) == 28
+
+
+@pytest.mark.parametrize("name", ["sha512_224", "SHA512/224", "sha-512/224"])
+def test_sha512_224_new(name):
+    """Test new() avec sha512_224 et ses alias"""
+    h = RsHash.new(name, b"abc")
+    assert h.name == "sha512_224"
+    assert h.hexdigest() == hashlib.new("sha512_224", b"abc").hexdigest()
+    assert "sha512_224" in RsHash.algorithms_available
@@ -0,0 +1,67 @@
+"""Tests pour SHA512/t"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+MESSAGES = [b"", b"abc", bytes(range(256)) * 3]
+
+
+@pytest.mark.parametrize("t,reference", [(224, "sha512_224"), (256, "sha512_256")])
+@pytest.mark.parametrize("data", MESSAGES)
+def test_sha512_t_standard_variants(t, reference, data):
+    """Test que /224 et /256 correspondent aux variantes standard"""
+    expected = hashlib.new(reference, data).hexdigest()
+    assert RsHash.sha512_t(t, data).hexdigest() == expected
+    assert RsHash.new("sha512/%d" % t, data).hexdigest() == expected
+    assert RsHash.new(reference, data).hexdigest() == expected
+
+
+def test_sha512_t_matches_fixed_class():
+    """Test que sha512_t(224) donne le même résultat que SHA512_224"""
+    for data in MESSAGES:
+        assert RsHash.sha512_t(224, data).digest() == RsHash.SHA512_224(data).digest()
+
+
+@pytest.mark.parametrize("t", [8, 128, 192, 248, 504])
+def test_sha512_t_properties(t):
+    """Test digest_size, block_size et name selon t"""
+    h = RsHash.sha512_t(t, b"abc")
+    assert h.digest_size == t // 8
+    assert h.block_size == 128
+    assert h.name == "sha512_%d" % t
+    assert len(h.digest()) == t // 8
+    assert isinstance(h, RsHash.SHA512_t)
+
+
+def test_sha512_t_192_constructors():
+    """Test que new(), sha512_t() et la classe SHA512_t concordent pour t=192"""
+    h = RsHash.new("SHA-512/192", b"abc")
+    assert h.hexdigest() == RsHash.SHA512_t(192, b"abc").hexdigest()
+    h.update(b"def")
+    assert h.hexdigest() == RsHash.sha512_t(192, b"abcdef").hexdigest()
+
+
+@pytest.mark.parametrize("t", [0, 100, 384, 512, 1024])
+def test_sha512_t_rejects_invalid(t):
+    """Test que les valeurs de t invalides lèvent ValueError"""
+    with pytest.raises(ValueError, match="SHA-512/t"):
+        RsHash.sha512_t(t)
+    with pytest.raises(ValueError, match="SHA-512/t"):
+        RsHash.new("sha512/%d" % t)
+
+
+def test_sha512_t_unknown_name_lists_family():
+    """Test que le message d'erreur mentionne la famille sha512/<t>"""
+    with pytest.raises(ValueError, match="sha512/<t>"):
+        RsHash.new("sha512/abc")
+
+
+def test_sha512_t_copy():
+    """Test que copy() conserve t et l'état"""
+    h = RsHash.sha512_t(128, b"x" * 200)
+    clone = h.copy()
+    clone.update(b"y")
+    assert clone.digest_size == 16
+    assert clone.digest() == RsHash.sha512_t(128, b"x" * 200 + b"y").digest()
+    assert h.digest() == RsHash.sha512_t(128, b"x" * 200).digest()
@@ -0,0 +1,125 @@
+"""Tests pour sha256-crypt et sha512-crypt ($5$ et $6$)"""
+import ctypes
+import ctypes.util
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Vecteurs de la spécification d'Ulrich Drepper : fonction, sel, rounds,
+# mot de passe, résultat
+VECTORS = [
+    ("sha256_crypt", "saltstring", 5000, b"Hello world!",
+     "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"),
+    ("sha256_crypt", "saltstringsaltstring", 10000, b"Hello world!",
+     "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA"),
+    ("sha256_crypt", "toolongsaltstring", 5000, b"This is just a test",
+     "$5$toolongsaltstrin$Un/5jzAHMgOGZ5.mWJpuVolil07guHPvOW8mGRcvxa5"),
+    ("sha256_crypt", "anotherlongsaltstring", 1400,
+     b"a very much longer text to encrypt.  This one even stretches over morethan one line.",
+     "$5$rounds=1400$anotherlongsalts$Rx.j8H.h8HjEDGomFU8bDkXm3XIUnzyxf12oP84Bnq1"),
+    ("sha256_crypt", "short", 77777, b"we have a short salt string but not a short password",
+     "$5$rounds=77777$short$JiO1O3ZpDAxGJeaDIuqCoEFysAe1mZNJRs3pw0KQRd/"),
+    ("sha256_crypt", "asaltof16chars..", 123456, b"a short string",
+     "$5$rounds=123456$asaltof16chars..$gP3VQ/6X7UUEW3HkBn2w1/Ptq2jxPyzV/cZKmF/wJvD"),
+    ("sha256_crypt", "roundstoolow", 10, b"the minimum number is still observed",
+     "$5$rounds=1000$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC"),
+    ("sha512_crypt", "saltstring", 5000, b"Hello world!",
+     "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdc"
+     "bYEdFCoEOfaS35inz1"),
+    ("sha512_crypt", "saltstringsaltstring", 10000, b"Hello world!",
+     "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/Urjm"
+     "M0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v."),
+    ("sha512_crypt", "toolongsaltstring", 5000, b"This is just a test",
+     "$6$toolongsaltstrin$lQ8jolhgVRVhY4b5pZKaysCLi0QBxGoNeKQzQ3glMhwllF7oGDZxUhx1yxdYcz/e"
+     "1JSbq3y6JMxxl8audkUEm0"),
+    ("sha512_crypt", "anotherlongsaltstring", 1400,
+     b"a very much longer text to encrypt.  This one even stretches over morethan one line.",
+     "$6$rounds=1400$anotherlongsalts$POfYwTEok97VWcjxIiSOjiykti.o/pQs.wPvMxQ6Fm7I6IoYN3Cm"
+     "Ls66x9t0oSwbtEW7o7UmJEiDwGqd8p4ur1"),
+    ("sha512_crypt", "short", 77777, b"we have a short salt string but not a short password",
+     "$6$rounds=77777$short$WuQyW2YR.hBNpjjRhpYD/ifIw05xdfeEyQoMxIXbkvr0gge1a1x3yRULJ5CCa"
+     "UeOxFmtlcGZelFl5CxtgfiAc0"),
+    ("sha512_crypt", "asaltof16chars..", 123456, b"a short string",
+     "$6$rounds=123456$asaltof16chars..$BtCwjqMJGx5hrJhZywWvt0RLE8uZ4oPwcelCjmw2kSYu.Ec6yc"
+     "ULevoBK25fs2xXgMNrCzIMVcgEJAstJeonj1"),
+    ("sha512_crypt", "roundstoolow", 10, b"the minimum number is still observed",
+     "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58j"
+     "aTfF4ZEQpyUNGc0dqbpBYYBaHHrsX."),
+]
+
+
+def libcrypt():
+    """Renvoie crypt() de la libcrypt du système, ou saute le test"""
+    name = ctypes.util.find_library("crypt")
+    if name is None:
+        pytest.skip("libcrypt indisponible")
+    crypt = ctypes.CDLL(name).crypt
+    crypt.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
+    crypt.restype = ctypes.c_char_p
+    return crypt
+
+
+@pytest.mark.parametrize("function,salt,rounds,password,expected", VECTORS)
+def test_sha_crypt_vectors(function, salt, rounds, password, expected):
+    """Test les vecteurs de la spécification"""
+    assert getattr(RsHash, function)(password, salt, rounds) == expected
+    assert RsHash.crypt_verify(password, expected)
+    assert RsHash.crypt_verify(password, expected.encode())
+    assert not RsHash.crypt_verify(password + b"!", expected)
+
+
+@pytest.mark.parametrize("function,prefix", [("sha256_crypt", "$5$"), ("sha512_crypt", "$6$")])
+def test_sha_crypt_random_salt(function, prefix):
+    """Test le sel aléatoire et le format de la chaîne"""
+    first = getattr(RsHash, function)(b"password")
+    second = getattr(RsHash, function)(b"password")
+    assert first != second
+    assert first.startswith(prefix) and second.startswith(prefix)
+    salt = first.split("$")[2]
+    assert len(salt) == 16
+    assert set(salt) <= set("./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz")
+    assert RsHash.crypt_verify(b"password", first)
+    assert RsHash.crypt_verify(bytearray(b"password"), second)
+    assert getattr(RsHash, function)(b"password", rounds=1000).split("$")[2] == "rounds=1000"
+
+
+@pytest.mark.parametrize("prefix", ["$5$", "$6$"])
+def test_sha_crypt_matches_libcrypt(prefix):
+    """Test différentiel contre crypt() de la libcrypt du système"""
+    crypt = libcrypt()
+    function = RsHash.sha256_crypt if prefix == "$5$" else RsHash.sha512_crypt
+    for rounds in (1000, 5000, 5001):
+        for _ in range(5):
+            password = os.urandom(1 + os.urandom(1)[0] % 100).replace(b"\x00", b"x")
+            ours = function(password, rounds=rounds)
+            salt_part = ours[:ours.rindex("$")]
+            assert crypt(password, salt_part.encode()).decode() == ours
+
+
+def test_crypt_verify_bcrypt():
+    """Test que crypt_verify accepte aussi les chaînes bcrypt"""
+    hashed = RsHash.bcrypt_hash(b"password", rounds=4)
+    assert RsHash.crypt_verify(b"password", hashed)
+    assert not RsHash.crypt_verify(b"wrong", hashed)
+    assert RsHash.crypt_verify(b"password", hashed.replace("$2b$", "$2y$"))
+
+
+def test_sha_crypt_errors():
+    """Test les paramètres et chaînes invalides"""
+    for salt in ("a$b", "a:b", "sél", "a b"):
+        with pytest.raises(ValueError, match="salt"):
+            RsHash.sha512_crypt(b"password", salt)
+    with pytest.raises(OverflowError):
+        RsHash.sha256_crypt(b"password", "salt", -1)
+    with pytest.raises(TypeError):
+        RsHash.sha256_crypt("password")
+    for bad in ("", "$1$salt$hash", "$argon2id$v=19$m=8,t=1,p=1$c2FsdA$aGFzaA",
+                "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc",
+                "$5$rounds=10$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC",
+                "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl"):
+        with pytest.raises(ValueError):
+            RsHash.crypt_verify(b"password", bad)
+    with pytest.raises(TypeError):
+        RsHash.crypt_verify(b"password", 5)
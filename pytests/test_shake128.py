@@ -0,0 +1,65 @@
+"""Tests pour SHAKE128"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,length,expected", [
+    (b"", 32, "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"),
+    (b"abc", 32, "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"),
+    (b"abc", 16, "5881092dd818bf5cf8a3ddb793fbcba7"),
+])
+def test_shake128_fips202_vectors(data, length, expected):
+    """Test les vecteurs de test de FIPS 202"""
+    assert RsHash.SHAKE128(data).hexdigest(length) == expected
+
+
+@pytest.mark.parametrize("length", [0, 1, 168, 1000])
+def test_shake128_lengths(length):
+    """Test les longueurs 0, 1, un rate complet et 1000 octets"""
+    data = bytes(range(256)) * 3
+    h = RsHash.SHAKE128(data)
+    out = h.digest(length)
+    assert len(out) == length
+    assert out == hashlib.shake_128(data).digest(length)
+    assert h.hexdigest(length) == out.hex()
+
+
+def test_shake128_prefix_consistency():
+    """Test que digest(16) est un préfixe de digest(32) sur le même état"""
+    h = RsHash.SHAKE128(b"prefix")
+    long = h.digest(500)
+    for length in (16, 32, 167, 168, 169, 336):
+        assert h.digest(length) == long[:length]
+
+
+def test_shake128_length_required():
+    """Test que digest() et hexdigest() exigent la longueur, comme hashlib"""
+    h = RsHash.SHAKE128(b"abc")
+    with pytest.raises(TypeError):
+        h.digest()
+    with pytest.raises(TypeError):
+        h.hexdigest()
+
+
+def test_shake128_large_output():
+    """Test une sortie plus grande que le seuil de libération du GIL"""
+    assert RsHash.SHAKE128(b"abc").digest(10000) == hashlib.shake_128(b"abc").digest(10000)
+
+
+def test_shake128_properties():
+    """Test les propriétés de SHAKE128"""
+    h = RsHash.SHAKE128()
+    assert h.name == "shake_128"
+    assert h.digest_size == 0
+    assert h.block_size == 168
+
+
+@pytest.mark.parametrize("name", ["shake_128", "SHAKE128", "shake-128"])
+def test_shake128_new(name):
+    """Test new() avec shake_128 et ses alias"""
+    h = RsHash.new(name, b"abc")
+    assert type(h) is RsHash.SHAKE128
+    assert h.digest(64) == hashlib.shake_128(b"abc").digest(64)
+    assert "shake_128" in RsHash.algorithms_available
@@ -0,0 +1,61 @@
+"""Tests pour SHAKE256"""
+import copy
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,length,expected", [
+    (b"", 64,
+     "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"
+     "d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"),
+    (b"abc", 32, "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739"),
+])
+def test_shake256_nist_vectors(data, length, expected):
+    """Test les vecteurs de test NIST"""
+    assert RsHash.SHAKE256(data).hexdigest(length) == expected
+
+
+@pytest.mark.parametrize("length", [0, 1, 135, 136, 137, 1000])
+def test_shake256_matches_hashlib(length):
+    """Compare avec hashlib autour de la frontière du rate (136 octets)"""
+    data = bytes(range(256)) * 2
+    assert RsHash.SHAKE256(data).digest(length) == hashlib.shake_256(data).digest(length)
+
+
+def test_shake256_prefix_consistency():
+    """Test que les sorties courtes sont des préfixes des sorties longues"""
+    h = RsHash.SHAKE256(b"prefix")
+    long = h.digest(400)
+    for length in (1, 16, 32, 136, 272):
+        assert h.digest(length) == long[:length]
+        assert h.hexdigest(length) == long[:length].hex()
+
+
+def test_shake256_properties():
+    """Test les propriétés de SHAKE256, digest_size vaut 0 comme dans hashlib"""
+    h = RsHash.SHAKE256()
+    assert h.name == "shake_256"
+    assert h.digest_size == hashlib.shake_256().digest_size == 0
+    assert h.block_size == 136
+    with pytest.raises(TypeError):
+        h.digest()
+
+
+def test_shake256_new():
+    """Test new("shake_256")"""
+    h = RsHash.new("shake_256", b"abc")
+    assert type(h) is RsHash.SHAKE256
+    assert h.digest(48) == hashlib.shake_256(b"abc").digest(48)
+
+
+def test_shake256_copy_mid_absorb():
+    """Test copy() au milieu de l'absorption, avant la fin d'un bloc"""
+    h1 = RsHash.SHAKE256(b"x" * 200)
+    h2 = h1.copy()
+    h3 = copy.deepcopy(h1)
+    h2.update(b"tail")
+    assert h2.digest(64) == hashlib.shake_256(b"x" * 200 + b"tail").digest(64)
+    assert h1.digest(64) == hashlib.shake_256(b"x" * 200).digest(64)
+    assert h3.digest(64) == h1.digest(64)
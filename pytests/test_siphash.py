@@ -0,0 +1,102 @@
+"""Tests pour SipHash-2-4 et SipHash-1-3 du sous-module noncrypto"""
+import os
+import random
+import subprocess
+import sys
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+KEY = bytes(range(16))
+MESSAGES = [bytes(range(n)) for n in range(64)]
+
+# Premières entrées de vectors_sip64 et vectors_sip128 de l'implémentation de référence
+VECTORS_SIP64 = [
+    "310e0edd47db6f72",
+    "fd67dc93c539f874",
+    "5a4fa9d909806c0d",
+    "2d7efbd796666785",
+]
+VECTORS_SIP128 = [
+    "a3817f04ba25a8e66df67214c7550293",
+    "da87c1d86b99af44347659119b22fc45",
+]
+
+
+@pytest.mark.parametrize("length", range(len(VECTORS_SIP64)))
+def test_siphash24_reference_vectors(length):
+    """Test les vecteurs de référence 64 bits (clé 00..0f, messages 00..n-1)"""
+    h = noncrypto.siphash24(KEY, MESSAGES[length])
+    expected = bytes.fromhex(VECTORS_SIP64[length])
+    assert h.digest() == expected
+    assert h.intdigest() == int.from_bytes(expected, "little")
+    assert noncrypto.siphash24_digest(KEY, MESSAGES[length]) == expected
+    assert noncrypto.siphash24_hexdigest(KEY, MESSAGES[length]) == VECTORS_SIP64[length]
+
+
+@pytest.mark.parametrize("length", range(len(VECTORS_SIP128)))
+def test_siphash24_reference_vectors_128(length):
+    """Test les vecteurs de référence 128 bits avec output_size=16"""
+    h = noncrypto.siphash24(KEY, MESSAGES[length], output_size=16)
+    expected = bytes.fromhex(VECTORS_SIP128[length])
+    assert h.digest() == expected
+    assert h.intdigest() == int.from_bytes(expected, "little")
+    assert (h.digest_size, h.block_size, h.name) == (16, 8, "siphash24")
+    assert noncrypto.siphash24_intdigest(KEY, MESSAGES[length], 16) == h.intdigest()
+
+
+def test_siphash13_matches_python_hash():
+    """Test SipHash-1-3 à clé nulle contre hash() de Python lancé avec PYTHONHASHSEED=0"""
+    if sys.hash_info.algorithm != "siphash13":
+        pytest.skip("Python does not hash with SipHash-1-3")
+    samples = [b"a", b"abc", b"12345678", bytes(range(100))]
+    code = f"print([hash(s) for s in {samples!r}])"
+    env = dict(os.environ, PYTHONHASHSEED="0")
+    output = subprocess.run([sys.executable, "-c", code], env=env, check=True,
+                            capture_output=True, text=True).stdout
+    for sample, expected in zip(samples, eval(output)):
+        value = noncrypto.siphash13_intdigest(bytes(16), sample)
+        signed = value - 2**64 if value >= 2**63 else value
+        assert signed == expected or (signed == -1 and expected == -2)
+
+
+@pytest.mark.parametrize("name", ["siphash24", "siphash13"])
+@pytest.mark.parametrize("output_size", [8, 16])
+def test_siphash_incremental(name, output_size):
+    """Test update() par morceaux aléatoires, copy() et reset()"""
+    cls = getattr(noncrypto, name)
+    rng = random.Random(name)
+    key = rng.randbytes(16)
+    for _ in range(20):
+        data = rng.randbytes(rng.randrange(0, 500))
+        h = cls(key, output_size=output_size)
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 20)
+            h.update(data[pos:pos + step])
+            pos += step
+        assert h.digest() == cls(key, data, output_size).digest()
+        assert len(h.digest()) == output_size
+    c = h.copy()
+    h.reset()
+    assert h.intdigest() == cls(key, output_size=output_size).intdigest()
+    assert c.digest() == cls(key, data, output_size).digest()
+
+
+@pytest.mark.parametrize("key", [b"", bytes(15), bytes(17), bytes(32)])
+def test_siphash_rejects_bad_keys(key):
+    """Test le rejet des clés qui ne font pas exactement 16 octets"""
+    with pytest.raises(ValueError, match="key must be 16 bytes"):
+        noncrypto.siphash24(key)
+    with pytest.raises(ValueError, match="key must be 16 bytes"):
+        noncrypto.siphash13_intdigest(key, b"data")
+
+
+def test_siphash_rejects_bad_output_size():
+    """Test le rejet des tailles de sortie autres que 8 et 16"""
+    with pytest.raises(ValueError, match="output size"):
+        noncrypto.siphash24(KEY, b"", 32)
+    with pytest.raises(TypeError):
+        noncrypto.siphash24()
@@ -0,0 +1,114 @@
+"""Tests pour Skein-256/512/1024 (Skein 1.3)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+FOX = b"The quick brown fox jumps over the lazy dog"
+
+
+def descending(length):
+    """Octets 0xff, 0xfe, ... comme dans les vecteurs de la soumission SHA-3"""
+    return bytes(0xff - i for i in range(length))
+
+
+@pytest.mark.parametrize("constructor,data,expected", [
+    ("skein256", b"",
+     "c8877087da56e072870daa843f176e9453115929094c3a40c463a196c29bf7ba"),
+    ("skein256", b"\xff",
+     "0b98dcd198ea0e50a7a244c444e25c23da30c10fc9a1f270a6637f1f34e67ed2"),
+    ("skein256", descending(32),
+     "8d0fa4ef777fd759dfd4044e6f6a5ac3c774aec943dcfc07927b723b5dbf408b"),
+    ("skein256", FOX,
+     "c0fbd7d779b20f0a4614a66697f9e41859eaf382f14bf857e8cdb210adb9b3fe"),
+    ("skein512", b"",
+     "bc5b4c50925519c290cc634277ae3d6257212395cba733bbad37a4af0fa06af4"
+     "1fca7903d06564fea7a2d3730dbdb80c1f85562dfcc070334ea4d1d9e72cba7a"),
+    ("skein512", b"\xff",
+     "71b7bce6fe6452227b9ced6014249e5bf9a9754c3ad618ccc4e0aae16b316cc8"
+     "ca698d864307ed3e80b6ef1570812ac5272dc409b5a012df2a579102f340617a"),
+    ("skein512", descending(64),
+     "45863ba3be0c4dfc27e75d358496f4ac9a736a505d9313b42b2f5eada79fc17f"
+     "63861e947afb1d056aa199575ad3f8c9a3cc1780b5e5fa4cae050e989876625b"),
+    ("skein512", FOX,
+     "94c2ae036dba8783d0b3f7d6cc111ff810702f5c77707999be7e1c9486ff238a"
+     "7044de734293147359b4ac7e1d09cd247c351d69826b78dcddd951f0ef912713"),
+    ("skein1024", descending(128),
+     "1f3e02c46fb80a3fcd2dfbbc7c173800b40c60c2354af551189ebf433c3d85f9"
+     "ff1803e6d920493179ed7ae7fce69c3581a5a2f82d3e0c7a295574d0cd7d217c"
+     "484d2f6313d59a7718ead07d0729c24851d7e7d2491b902d489194e6b7d369db"
+     "0ab7aa106f0ee0a39a42efc54f18d93776080985f907574f995ec6a37153a578"),
+])
+def test_skein_vectors(constructor, data, expected):
+    """Test les vecteurs de la soumission SHA-3 et l'exemple "The quick brown fox" """
+    assert getattr(RsHash, constructor)(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "39ccc4554a8b31853b9de7a1fe638a24cce6b35a55f2431009e18780335d2621"),
+    (FOX, "b3250457e05d3060b1a4bbc1428bc75a3f525ca389aeab96cfa34638d96e492a"),
+])
+def test_skein512_256(data, expected):
+    """Test Skein-512-256 : la taille de sortie change tout le haché"""
+    h = RsHash.skein512(data, digest_size=32)
+    assert h.digest_size == 32
+    assert h.hexdigest() == expected
+    assert not RsHash.skein512(data).hexdigest().startswith(expected)
+
+
+def test_skein_arbitrary_digest_size():
+    """Test des tailles de sortie plus longues que l'état et non alignées"""
+    for size in (1, 20, 129, 300):
+        h = RsHash.Skein1024(b"abc", digest_size=size)
+        assert len(h.digest()) == size
+    assert len(RsHash.skein256(digest_size=2**20).digest()) == 2**20
+    for size in (0, 2**20 + 1, 2**40):
+        with pytest.raises(ValueError, match="digest_size"):
+            RsHash.skein512(digest_size=size)
+
+
+def test_skein_streaming_and_copy():
+    """Test Skein en plusieurs update(), autour des limites de bloc, et copy()"""
+    data = bytes(range(256)) * 3
+    for cls in (RsHash.Skein256, RsHash.Skein512, RsHash.Skein1024):
+        expected = cls(data, digest_size=48).digest()
+        for step in (1, 31, 32, 33, 64, 128):
+            h = cls(digest_size=48)
+            for start in range(0, len(data), step):
+                h.update(data[start:start + step])
+            assert h.digest() == expected
+        h = cls(data[:100], digest_size=48)
+        c = h.copy()
+        c.update(data[100:])
+        assert c.digest() == expected
+        assert h.digest() == cls(data[:100], digest_size=48).digest()
+
+
+@pytest.mark.parametrize("name,cls,digest_size,block_size", [
+    ("skein256", "Skein256", 32, 32),
+    ("skein512", "Skein512", 64, 64),
+    ("skein1024", "Skein1024", 128, 128),
+])
+def test_skein_properties_and_new(name, cls, digest_size, block_size):
+    """Test les propriétés et new("skeinN")"""
+    h = RsHash.new(name.replace("skein", "Skein-"), b"abc")
+    assert type(h) is getattr(RsHash, cls)
+    assert h.name == name
+    assert h.digest_size == digest_size
+    assert h.block_size == block_size
+    assert h.digest() == getattr(RsHash, name)(b"abc").digest()
+    assert RsHash.new(name, digest_size=20).digest_size == 20
+    assert name in RsHash.algorithms_available
+
+
+def test_skein_fips_mode():
+    """Test qu'en mode FIPS, Skein exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.skein512(b"abc")
+        with pytest.raises(ValueError, match="not approved"):
+            RsHash.new("skein256")
+        h = RsHash.new("skein512", b"abc", usedforsecurity=False)
+        assert h.digest() == RsHash.Skein512(b"abc", usedforsecurity=False).digest()
+    finally:
+        RsHash.set_fips_mode(False)
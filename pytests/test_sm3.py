@@ -0,0 +1,58 @@
+"""Tests pour SM3 (GB/T 32905-2016)"""
+import hashlib
+import random
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b"),
+    (b"abc", "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"),
+    (b"abcd" * 16, "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"),
+])
+def test_sm3_standard_vectors(data, expected):
+    """Test les exemples de l'annexe A de la norme"""
+    assert RsHash.SM3(data).hexdigest() == expected
+
+
+def test_sm3_differential():
+    """Test différentiel aléatoire contre hashlib quand OpenSSL fournit SM3"""
+    try:
+        hashlib.new("sm3")
+    except ValueError:
+        pytest.skip("sm3 absent de cet OpenSSL")
+    rng = random.Random(32905)
+    for _ in range(50):
+        data = bytes(rng.getrandbits(8) for _ in range(rng.randint(0, 700)))
+        h = RsHash.SM3()
+        expected = hashlib.new("sm3")
+        pos = 0
+        while pos < len(data):
+            step = rng.randint(1, 150)
+            h.update(data[pos:pos + step])
+            expected.update(data[pos:pos + step])
+            pos += step
+        assert h.digest() == expected.digest()
+
+
+def test_sm3_properties_and_new():
+    """Test les propriétés et new("sm3")"""
+    h = RsHash.new("SM3", b"abc")
+    assert type(h) is RsHash.SM3
+    assert h.name == "sm3"
+    assert h.digest_size == 32
+    assert h.block_size == 64
+    assert h.digest() == RsHash.SM3(b"abc").digest()
+    assert "sm3" in RsHash.algorithms_available
+
+
+def test_sm3_fips_mode():
+    """Test qu'en mode FIPS, SM3 exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.SM3(b"abc")
+        assert RsHash.new("sm3", usedforsecurity=False).name == "sm3"
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,119 @@
+"""Tests pour SpookyHash V2 du sous-module noncrypto"""
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+# Données pseudo-aléatoires reproductibles
+_rng = random.Random(5)
+RANDOM_DATA = bytes(_rng.randrange(256) for _ in range(1200))
+
+# Hash32 des octets 128, 129, ... de longueur 0 à 15, graine 0 (TestResults de SpookyV2Test.cpp)
+HASH32_REFERENCE = [
+    0x6BF50919, 0x70DE1D26, 0xA2B37298, 0x35BC5FBF, 0x8223B279, 0x5BCB315E, 0x53FE88A1,
+    0xF9F1A233, 0xEE193982, 0x54F86F29, 0xC8772D36, 0x9ED60886, 0x5F23D1DA, 0x1ED9F474,
+    0xF2EF0C89, 0x83EC01F9,
+]
+
+# Hash128 de RANDOM_DATA[:n], graines 0x0123456789abcdef et 0xfedcba9876543210, calculés
+# avec SpookyV2.cpp de référence : chemins court (< 192 octets) et long
+HASH128_REFERENCE = [
+    (0, 0x9650BC819F542799, 0xF0D126948B81E07F),
+    (3, 0x47B2A89789CE0D59, 0x574EAA26BBB51B85),
+    (12, 0x20C0ABCA6FCE40A9, 0xF467DCF94E8457C8),
+    (16, 0x18E4C1A68055F29B, 0x8889B0D8AC0CE0FE),
+    (32, 0x1D49D8130EA2F301, 0x2CA84A4C7E6EFEB8),
+    (100, 0x45F7E08A65D9B854, 0x3106C8A273069A1E),
+    (191, 0xF8FC18B5B9559146, 0x3687E18C50997C97),
+    (192, 0xE04226A2106EE64E, 0xC4B094CF6DEDE3B1),
+    (193, 0x4627CE24B869150D, 0xC6D7776B4D85A0C1),
+    (288, 0xE2359F1E6F4729DB, 0xF44F9D92CF305354),
+    (500, 0x9C88837F1F8C1EB2, 0xB87BCE8EF5B286D8),
+    (1200, 0xDB8A10F24C394C9A, 0x3AE3F5CD3B000D2E),
+]
+
+# Premier mot de Hash128 avec les deux graines à 7, soit Hash64(seed=7)
+HASH64_REFERENCE = [
+    (0, 0xDB44A6E0E7F7F894),
+    (7, 0x1B6495270331D9A6),
+    (50, 0xD9AD57BAD07B3CEB),
+    (191, 0xF11712A73FCF4C1D),
+    (192, 0xB3004EFCB1E9D16B),
+    (300, 0x746694B56D006482),
+    (1000, 0x63DC026BC10038D6),
+]
+
+
+def test_spookyhash32_reference():
+    """Test les premières valeurs de TestResults de la référence V2"""
+    buf = bytes((i + 128) % 256 for i in range(16))
+    for length, expected in enumerate(HASH32_REFERENCE):
+        assert noncrypto.spookyhash32_intdigest(buf[:length]) == expected
+        assert noncrypto.spookyhash32(buf[:length]).digest() == expected.to_bytes(4, "little")
+
+
+@pytest.mark.parametrize("length,hash1,hash2", HASH128_REFERENCE)
+def test_spookyhash128_reference(length, hash1, hash2):
+    """Test Hash128 avec deux graines différentes, en une fois et par morceaux"""
+    data = RANDOM_DATA[:length]
+    seeds = {"seed1": 0x0123456789ABCDEF, "seed2": 0xFEDCBA9876543210}
+    expected = hash1.to_bytes(8, "little") + hash2.to_bytes(8, "little")
+    assert noncrypto.spookyhash128_digest(data, **seeds) == expected
+    assert noncrypto.spookyhash128_intdigest(data, **seeds) == (hash2 << 64) | hash1
+    h = noncrypto.spookyhash128(**seeds)
+    for i in range(0, length, 37):
+        h.update(data[i:min(i + 37, length)])
+    assert h.digest() == expected
+    assert h.hexdigest() == expected.hex()
+
+
+@pytest.mark.parametrize("length,expected", HASH64_REFERENCE)
+def test_spookyhash64_reference(length, expected):
+    """Test Hash64, qui tronque Hash128 avec la même graine deux fois"""
+    data = RANDOM_DATA[:length]
+    assert noncrypto.spookyhash64_intdigest(data, 7) == expected
+    assert noncrypto.spookyhash32_intdigest(data, seed=7) == expected & 0xFFFFFFFF
+    assert noncrypto.spookyhash128_intdigest(data, 7, 7) & (2**64 - 1) == expected
+
+
+def test_spooky_random_chunks():
+    """Test des découpages aléatoires autour des seuils de 96 et 192 octets"""
+    rng = random.Random(96)
+    for _ in range(100):
+        length = rng.randrange(0, len(RANDOM_DATA) + 1)
+        data = RANDOM_DATA[:length]
+        expected = noncrypto.spookyhash128_intdigest(data, 1, 2)
+        h = noncrypto.spookyhash128(seed1=1, seed2=2)
+        i = 0
+        while i < length:
+            step = rng.randrange(1, 250)
+            h.update(data[i:i + step])
+            i += step
+        assert h.intdigest() == expected, length
+
+
+def test_spooky_copy_reset_and_sizes():
+    """Test copy(), reset() et les tailles annoncées"""
+    h = noncrypto.spookyhash64(RANDOM_DATA[:150], 3)
+    clone = h.copy()
+    clone.update(RANDOM_DATA[150:300])
+    assert h.intdigest() == noncrypto.spookyhash64_intdigest(RANDOM_DATA[:150], 3)
+    assert clone.intdigest() == noncrypto.spookyhash64_intdigest(RANDOM_DATA[:300], 3)
+    h.reset()
+    assert h.intdigest() == noncrypto.spookyhash64_intdigest(b"", 3)
+    sizes = [(noncrypto.spookyhash32, 4), (noncrypto.spookyhash64, 8), (noncrypto.spookyhash128, 16)]
+    for cls, size in sizes:
+        assert cls().digest_size == size
+        assert cls().block_size == 96
+
+
+def test_spooky_argument_errors():
+    """Test le rejet des graines hors limites"""
+    with pytest.raises(OverflowError):
+        noncrypto.spookyhash32(b"", 2**32)
+    with pytest.raises(OverflowError):
+        noncrypto.spookyhash64_intdigest(b"", -1)
+    with pytest.raises(OverflowError):
+        noncrypto.spookyhash128(b"", 0, 2**64)
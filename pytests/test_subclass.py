@@ -0,0 +1,100 @@
+"""Tests de l'héritage des classes de hachage depuis Python"""
+import copy
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ALGORITHMS = [("sha256", RsHash.SHA256), ("sha512", RsHash.SHA512)]
+
+
+def make_subclass(base):
+    """Crée une sous-classe avec un attribut propre et une méthode verify()"""
+
+    class Tagged(base):
+        def __init__(self, data=None, *, tag="default", **kwargs):
+            super().__init__(data, **kwargs)
+            self.tag = tag
+
+        def verify(self, expected):
+            return self.hexdigest() == expected
+
+    return Tagged
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_subclass_isinstance(name, cls):
+    """Test les relations isinstance/issubclass"""
+    sub = make_subclass(cls)
+    h = sub(b"abc")
+    assert isinstance(h, sub)
+    assert isinstance(h, cls)
+    assert issubclass(sub, cls)
+    assert h.name == name
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_subclass_init_arguments(name, cls):
+    """Test que __init__ reçoit ses propres arguments et que data est haché"""
+    sub = make_subclass(cls)
+    h = sub(b"abc", tag="mine", usedforsecurity=False)
+    assert h.tag == "mine"
+    assert h.usedforsecurity is False
+    assert h.verify(hashlib.new(name, b"abc").hexdigest())
+
+    h.update(b"def")
+    assert h.verify(hashlib.new(name, b"abcdef").hexdigest())
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_subclass_override_hexdigest(name, cls):
+    """Test la surcharge de hexdigest() dans une sous-classe"""
+
+    class Upper(cls):
+        def hexdigest(self):
+            return super().hexdigest().upper()
+
+    h = Upper(b"abc")
+    assert h.hexdigest() == hashlib.new(name, b"abc").hexdigest().upper()
+    assert h.digest() == hashlib.new(name, b"abc").digest()
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+@pytest.mark.parametrize("copier", [
+    lambda h: h.copy(),
+    copy.copy,
+    copy.deepcopy,
+])
+def test_subclass_copy_keeps_type(name, cls, copier):
+    """Test que copy() renvoie une instance de la sous-classe avec ses attributs"""
+    sub = make_subclass(cls)
+    original = sub(b"abc", tag="kept")
+    original.extra = ["list"]
+    clone = copier(original)
+    assert type(clone) is sub
+    assert clone.tag == "kept"
+    assert clone.extra == ["list"]
+
+    clone.update(b"def")
+    assert original.hexdigest() == hashlib.new(name, b"abc").hexdigest()
+    assert clone.hexdigest() == hashlib.new(name, b"abcdef").hexdigest()
+
+
+@pytest.mark.parametrize("cls", [RsHash.SHA256, RsHash.SHA512])
+def test_deepcopy_copies_subclass_attributes(cls):
+    """Test que deepcopy copie aussi en profondeur les attributs"""
+    sub = make_subclass(cls)
+    original = sub(b"abc")
+    original.extra = ["list"]
+    clone = copy.deepcopy(original)
+    clone.extra.append("more")
+    assert original.extra == ["list"]
+
+
+@pytest.mark.parametrize("cls", [RsHash.SHA256, RsHash.SHA512])
+def test_base_class_rejects_extra_arguments(cls):
+    """Test que la classe de base refuse toujours les arguments inconnus"""
+    with pytest.raises(TypeError, match="unexpected keyword argument 'tag'"):
+        cls(b"abc", tag="x")
+    with pytest.raises(TypeError, match="positional argument"):
+        cls(b"abc", b"def")
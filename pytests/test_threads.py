@@ -0,0 +1,62 @@
+"""Tests d'accès concurrent à un même objet de hachage"""
+import hashlib
+import threading
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+THREADS = 8
+UPDATES = 200
+# Plus grand que le seuil de libération du GIL, et pas un multiple de la
+# taille de bloc, pour que les mises à jour se chevauchent dans le tampon
+CHUNK = bytes(range(256)) * 20 + b"tail"
+
+
+def hammer(h, chunk, barrier):
+    barrier.wait()
+    for _ in range(UPDATES):
+        h.update(chunk)
+
+
+@pytest.mark.parametrize("name,cls", [("sha256", RsHash.SHA256), ("sha512", RsHash.SHA512)])
+def test_concurrent_updates_serialize(name, cls):
+    """Test que des update() concurrents ne corrompent pas l'état
+
+    Chaque thread envoie les mêmes données : quel que soit l'ordre dans
+    lequel les appels sont sérialisés, le résultat doit être le digest
+    séquentiel de la concaténation.
+    """
+    h = cls()
+    barrier = threading.Barrier(THREADS)
+    threads = [threading.Thread(target=hammer, args=(h, CHUNK, barrier)) for _ in range(THREADS)]
+    for t in threads:
+        t.start()
+    for t in threads:
+        t.join()
+    expected = hashlib.new(name, CHUNK * (THREADS * UPDATES)).hexdigest()
+    assert h.hexdigest() == expected
+
+
+@pytest.mark.parametrize("cls", [RsHash.SHA256, RsHash.SHA512])
+def test_concurrent_digest_and_copy(cls):
+    """Test que digest() et copy() pendant des update() restent cohérents"""
+    h = cls()
+    barrier = threading.Barrier(THREADS + 1)
+    threads = [threading.Thread(target=hammer, args=(h, CHUNK, barrier)) for _ in range(THREADS)]
+    for t in threads:
+        t.start()
+    barrier.wait()
+    snapshots = []
+    while len(snapshots) < 100 and any(t.is_alive() for t in threads):
+        snapshots.append((h.copy(), h.digest()))
+    for t in threads:
+        t.join()
+    # Chaque copie doit correspondre à un nombre entier de mises à jour
+    reference = cls()
+    valid = {reference.digest()}
+    for _ in range(THREADS * UPDATES):
+        reference.update(CHUNK)
+        valid.add(reference.digest())
+    for clone, digest in snapshots:
+        assert clone.digest() in valid
+        assert digest in valid
@@ -0,0 +1,67 @@
+"""Tests pour Tiger et Tiger2 (192 bits)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3"),
+    (b"a", "77befbef2e7ef8ab2ec8f93bf587a7fc613e247f5f247809"),
+    (b"abc", "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93"),
+    (b"message digest", "d981f8cb78201a950dcf3048751e441c517fca1aa55a29f6"),
+    (b"abcdefghijklmnopqrstuvwxyz", "1714a472eee57d30040412bfcc55032a0b11602ff37beee9"),
+    (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+     "0f7bf9a19b9c58f2b7610df7e84f0ac3a71c631e7b53f78e"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "8dcea680a17583ee502ba38a3c368651890ffbccdc49a8cc"),
+    (b"1234567890" * 8, "1c14795529fd9f207a958f84c52f11e887fa0cabdfd91bfd"),
+    (b"Tiger", "dd00230799f5009fec6debc838bb6a27df2b9d6f110c7937"),
+])
+def test_tiger_reference_vectors(data, expected):
+    """Test les vecteurs de référence, octets de chaque mot en little-endian"""
+    assert RsHash.Tiger(data).hexdigest() == expected
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"", "4441be75f6018773c206c22745374b924aa8313fef919f41"),
+    (b"abc", "f68d7bc5af4b43a06e048d7829560d4a9415658bb0b1f3bf"),
+    (b"The quick brown fox jumps over the lazy dog",
+     "976abff8062a2e9dcea3a1ace966ed9c19cb85558b4976d8"),
+])
+def test_tiger2_reference_vectors(data, expected):
+    """Test Tiger2, qui ne diffère que par l'octet de padding 0x80"""
+    assert RsHash.Tiger2(data).hexdigest() == expected
+
+
+def test_tiger_byte_order():
+    """Test que chaque mot de 8 octets inversé donne la forme big-endian de certains outils"""
+    digest = RsHash.Tiger(b"").digest()
+    swapped = b"".join(digest[i:i + 8][::-1] for i in range(0, 24, 8))
+    assert swapped.hex() == "24f0130c63ac933216166e76b1bb925ff373de2d49584e7a"
+
+
+@pytest.mark.parametrize("name,cls", [("tiger", RsHash.Tiger), ("tiger2", RsHash.Tiger2)])
+def test_tiger_properties_new_and_streaming(name, cls):
+    """Test les propriétés, new() et update() en plusieurs morceaux"""
+    data = bytes(range(256)) * 3
+    h = RsHash.new(name)
+    for start in range(0, len(data), 37):
+        h.update(data[start:start + 37])
+    assert type(h) is cls
+    assert h.name == name
+    assert h.digest_size == 24
+    assert h.block_size == 64
+    assert h.digest() == cls(data).digest()
+    assert name in RsHash.algorithms_available
+    assert RsHash.new("tiger192,3", b"abc").digest() == RsHash.Tiger(b"abc").digest()
+
+
+def test_tiger_fips_mode():
+    """Test qu'en mode FIPS, Tiger exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.Tiger2(b"abc")
+        assert RsHash.new("tiger", usedforsecurity=False).name == "tiger"
+    finally:
+        RsHash.set_fips_mode(False)
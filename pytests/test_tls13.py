@@ -0,0 +1,100 @@
+"""Tests pour HKDF-Expand-Label et Derive-Secret (TLS 1.3, RFC 8446)"""
+import hashlib
+import hmac
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+EMPTY = hashlib.sha256(b"").digest()
+
+
+def expand_label(name, secret, label, context, length):
+    """HKDF-Expand-Label de référence en Python"""
+    full = b"tls13 " + label
+    info = length.to_bytes(2, "big") + bytes([len(full)]) + full + bytes([len(context)]) + context
+    out, block, counter = b"", b"", 1
+    while len(out) < length:
+        block = hmac.new(secret, block + info + bytes([counter]), name).digest()
+        out += block
+        counter += 1
+    return out[:length]
+
+
+def test_tls13_key_schedule_rfc8448():
+    """Test le calendrier de clés de la poignée de main simple de la RFC 8448"""
+    early = RsHash.hkdf_extract(b"", bytes(32))
+    assert early.hex() == "33ad0a1c607ec03b09e6cd9893680ce210adf300aa1f2660e1b22e10f170f92a"
+    derived = RsHash.tls13_derive_secret(early, b"derived", EMPTY)
+    assert derived.hex() == "6f2615a108c702c5678f54fc9dbab69716c076189c48250cebeac3576c3611ba"
+    shared = bytes.fromhex("8bd4054fb55b9d63fdfbacf9f04b9f0d35e6d63f537563efd46272900f89492d")
+    handshake = RsHash.hkdf_extract(derived, shared)
+    assert handshake.hex() == "1dc826e93606aa6fdc0aadc12f741b01046aa6b99f691ed221a9f0ca043fbeac"
+
+    hello = bytes.fromhex("860c06edc07858ee8e78f0e7428c58edd6b43f2ca3e6e95f02ed063cf0e1cad8")
+    client_hs = RsHash.tls13_derive_secret(handshake, b"c hs traffic", hello)
+    assert client_hs.hex() == "b3eddb126e067f35a780b3abf45e2d8f3b1a950738f52e9600746a0e27a55a21"
+    server_hs = RsHash.tls13_derive_secret(handshake, b"s hs traffic", hello, hash="sha256")
+    assert server_hs.hex() == "b67b7d690cc16c4e75e54213cb2d37b4e9c912bcded9105d42befd59d391ad38"
+    key = RsHash.hkdf_expand_label(server_hs, b"key", b"", 16)
+    assert key.hex() == "3fce516009c21727d0f2e4e86ee403bc"
+    iv = RsHash.hkdf_expand_label(server_hs, b"iv", b"", 12)
+    assert iv.hex() == "5d313eb2671276ee13000b30"
+
+    derived = RsHash.tls13_derive_secret(handshake, b"derived", EMPTY)
+    master = RsHash.hkdf_extract(derived, bytes(32))
+    assert master.hex() == "18df06843d13a08bf2a449844c5f8a478001bc4d4c627984d5a41da8d0402919"
+    finished = bytes.fromhex("9608102a0f1ccc6db6250b7b7e417b1a000eaada3daae4777a7686c9ff83df13")
+    expected = {
+        b"c ap traffic": "9e40646ce79a7f9dc05af8889bce6552875afa0b06df0087f792ebb7c17504a5",
+        b"s ap traffic": "a11af9f05531f856ad47116b45a950328204b4f44bfb6b3a4b4f1f3fcb631643",
+        b"exp master": "fe22f881176eda18eb8f44529e6792c50c9a3f89452f68d8ae311b4309d3cf50",
+    }
+    for label, secret in expected.items():
+        assert RsHash.tls13_derive_secret(master, label, finished).hex() == secret
+    client_finished = bytes.fromhex(
+        "209145a96ee8e2a122ff810047cc952684658d6049e86429426db87c54ad143d")
+    resumption = RsHash.tls13_derive_secret(master, b"res master", client_finished)
+    assert resumption.hex() == "7df235f2031d2a051287d02b0241b0bfdaf86cc856231f2d5aba46c434ec196c"
+
+
+@pytest.mark.parametrize("name", ["sha256", "sha512", "sha3_256", "blake2s"])
+def test_hkdf_expand_label_matches_reference(name):
+    """Test différentiel contre l'implémentation de référence"""
+    secret = hashlib.new(name, b"secret").digest()
+    for label, context, length in [(b"key", b"", 16), (b"iv", b"", 12), (b"", b"", 1),
+                                   (b"quic hp", b"ctx" * 20, 100), (b"x" * 249, b"y" * 255, 0)]:
+        expected = expand_label(name, secret, label, context, length)
+        assert RsHash.hkdf_expand_label(secret, label, context, length, hash=name) == expected
+    size = hashlib.new(name).digest_size
+    transcript = hashlib.new(name, b"ClientHello").digest()
+    assert RsHash.tls13_derive_secret(secret, b"c e traffic", transcript, hash=name) == \
+        expand_label(name, secret, b"c e traffic", transcript, size)
+
+
+def test_hkdf_expand_label_hash_class():
+    """Test le choix du hachage par classe et les entrées bytes-like"""
+    secret = bytes(range(32))
+    by_name = RsHash.hkdf_expand_label(secret, b"key", b"", 32, hash="sha512")
+    assert RsHash.hkdf_expand_label(secret, b"key", b"", 32, hash=RsHash.SHA512) == by_name
+    assert RsHash.hkdf_expand_label(bytearray(secret), memoryview(b"key"), b"", 32,
+                                    hash="sha512") == by_name
+
+
+def test_hkdf_expand_label_errors():
+    """Test les étiquettes, contextes et longueurs trop grands"""
+    secret = bytes(32)
+    with pytest.raises(ValueError, match="label"):
+        RsHash.hkdf_expand_label(secret, b"x" * 250, b"", 16)
+    with pytest.raises(ValueError, match="context"):
+        RsHash.hkdf_expand_label(secret, b"key", b"y" * 256, 16)
+    with pytest.raises(ValueError, match="length"):
+        RsHash.hkdf_expand_label(secret, b"key", b"", 255 * 32 + 1)
+    with pytest.raises(ValueError, match="length"):
+        RsHash.hkdf_expand_label(secret, b"key", b"", 65536, hash="sha512")
+    with pytest.raises(ValueError, match="context"):
+        RsHash.tls13_derive_secret(secret, b"derived", bytes(256))
+    with pytest.raises(ValueError):
+        RsHash.tls13_derive_secret(secret, b"derived", EMPTY, hash="shake_128")
+    with pytest.raises(TypeError):
+        RsHash.hkdf_expand_label(secret, "key", b"", 16)
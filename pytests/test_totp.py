@@ -0,0 +1,152 @@
+"""Tests pour TOTP (RFC 6238) et les URI otpauth://"""
+import base64
+import time as _time
+from urllib.parse import parse_qs, unquote, urlsplit
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+# Clés de l'annexe B de la RFC 6238, une par hash
+KEYS = {
+    "sha1": b"12345678901234567890",
+    "sha256": b"12345678901234567890123456789012",
+    "sha512": b"1234567890123456789012345678901234567890123456789012345678901234",
+}
+
+# Annexe B de la RFC 6238 : temps, codes SHA-1, SHA-256 et SHA-512 à 8 chiffres
+APPENDIX_B = [
+    (59, "94287082", "46119246", "90693936"),
+    (1111111109, "07081804", "68084774", "25091201"),
+    (1111111111, "14050471", "67062674", "99943326"),
+    (1234567890, "89005924", "91819424", "93441116"),
+    (2000000000, "69279037", "90698825", "38618901"),
+    (20000000000, "65353130", "77737706", "47863826"),
+]
+
+
+@pytest.mark.parametrize("time,sha1,sha256,sha512", APPENDIX_B)
+def test_totp_rfc6238(time, sha1, sha256, sha512):
+    """Test les vecteurs de l'annexe B de la RFC 6238"""
+    for name, expected in [("sha1", sha1), ("sha256", sha256), ("sha512", sha512)]:
+        assert RsHash.totp(KEYS[name], time=time, digits=8, digest=name) == expected
+        assert RsHash.totp(KEYS[name], time=float(time) + 0.5, digits=8, digest=name) == expected
+        assert RsHash.totp_verify(KEYS[name], expected, time=time, digits=8,
+                                  digest=name) == (time // 30)
+
+
+def test_totp_step_and_t0():
+    """Test que le pas et T0 donnent le compteur HOTP attendu"""
+    key = KEYS["sha1"]
+    assert RsHash.totp(key, time=59) == RsHash.hotp(key, 1)
+    assert RsHash.totp(key, time=119, step=60) == RsHash.hotp(key, 1)
+    assert RsHash.totp(key, time=1000, t0=1000) == RsHash.hotp(key, 0)
+    assert RsHash.totp(key, time=1089, t0=1000) == RsHash.hotp(key, 2)
+    assert RsHash.totp(key, time=59, digits=7) == "4287082"
+
+
+def test_totp_current_time():
+    """Test que le temps courant est utilisé par défaut"""
+    key = KEYS["sha1"]
+    before = int(_time.time())
+    code = RsHash.totp(key)
+    after = int(_time.time())
+    assert code in {RsHash.totp(key, time=before), RsHash.totp(key, time=after)}
+    assert RsHash.totp_verify(key, code, window=1) in {before // 30, after // 30}
+
+
+def test_totp_verify_window():
+    """Test la tolérance sur les pas de temps voisins"""
+    key = KEYS["sha1"]
+    code = RsHash.totp(key, time=1000 * 30)
+    assert RsHash.totp_verify(key, code, time=1000 * 30) == 1000
+    assert RsHash.totp_verify(key, code, time=1001 * 30) == 1000
+    assert RsHash.totp_verify(key, code, time=999 * 30 + 29) == 1000
+    assert RsHash.totp_verify(key, code, time=1002 * 30) is None
+    assert RsHash.totp_verify(key, code, time=1002 * 30, window=2) == 1000
+    assert RsHash.totp_verify(key, code, time=1001 * 30, window=0) is None
+    assert RsHash.totp_verify(key, "000000" if code != "000000" else "111111",
+                              time=1000 * 30) is None
+    first = RsHash.totp(key, time=0)
+    assert RsHash.totp_verify(key, first, time=0, window=5) == 0
+
+
+def test_totp_matches_cryptography():
+    """Test différentiel contre pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.twofactor.totp import TOTP
+    for name, algorithm in [("sha1", hashes.SHA1()), ("sha256", hashes.SHA256()),
+                            ("sha512", hashes.SHA512())]:
+        for step in (30, 60):
+            theirs = TOTP(KEYS[name], 6, algorithm, step)
+            for time in (0, 59, 1700000000, 4102444800):
+                assert RsHash.totp(KEYS[name], time=time, step=step, digest=name) == \
+                    theirs.generate(time).decode()
+
+
+def test_totp_uri():
+    """Test l'URI de provisionnement otpauth://"""
+    key = KEYS["sha1"]
+    uri = RsHash.totp_uri(key, "ACME Co", "john.doe@example.com")
+    assert uri == ("otpauth://totp/ACME%20Co:john.doe%40example.com"
+                   "?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=ACME%20Co"
+                   "&algorithm=SHA1&digits=6&period=30")
+    parts = urlsplit(RsHash.totp_uri(b"\x00" * 10, "Ex", "a", digits=8, digest="sha512",
+                                     step=60))
+    assert (parts.scheme, parts.netloc, unquote(parts.path)) == ("otpauth", "totp", "/Ex:a")
+    query = parse_qs(parts.query)
+    assert query == {"secret": ["AAAAAAAAAAAAAAAA"], "issuer": ["Ex"], "algorithm": ["SHA512"],
+                     "digits": ["8"], "period": ["60"]}
+    secret = parse_qs(urlsplit(RsHash.totp_uri(b"hello", "", "bob")).query)["secret"][0]
+    assert base64.b32decode(secret + "=" * (-len(secret) % 8)) == b"hello"
+    assert RsHash.totp_uri(b"hello", "", "bob").startswith("otpauth://totp/bob?secret=")
+    assert RsHash.totp_uri(key, "Ex", "a", digest=RsHash.SHA256).endswith(
+        "&algorithm=SHA256&digits=6&period=30")
+
+
+def test_totp_uri_matches_cryptography():
+    """Test l'URI contre celle de pyca/cryptography, s'il est installé"""
+    pytest.importorskip("cryptography")
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.twofactor.totp import TOTP
+    key = KEYS["sha256"]
+    theirs = urlsplit(TOTP(key, 8, hashes.SHA256(), 60).get_provisioning_uri(
+        "alice@example.com", "Big Corp"))
+    ours = urlsplit(RsHash.totp_uri(key, "Big Corp", "alice@example.com", digits=8,
+                                    digest="sha256", step=60))
+    theirs_query = parse_qs(theirs.query)
+    # cryptography garde le remplissage « = » de la base32, que le format omet
+    theirs_query["secret"] = [theirs_query["secret"][0].rstrip("=")]
+    assert unquote(ours.path) == unquote(theirs.path)
+    assert parse_qs(ours.query) == theirs_query
+
+
+def test_totp_errors():
+    """Test les paramètres invalides"""
+    key = KEYS["sha1"]
+    with pytest.raises(ValueError, match="step"):
+        RsHash.totp(key, time=59, step=0)
+    with pytest.raises(ValueError, match="t0"):
+        RsHash.totp(key, time=59, t0=60)
+    for time in (-1, float("nan"), float("inf")):
+        with pytest.raises(ValueError, match="time"):
+            RsHash.totp(key, time=time)
+    with pytest.raises(ValueError, match="digits"):
+        RsHash.totp(key, time=59, digits=9)
+    with pytest.raises(ValueError, match="20 bytes"):
+        RsHash.totp_verify(key, "123456", time=59, digest="md5")
+    with pytest.raises(TypeError):
+        RsHash.totp(key, 59)
+    with pytest.raises(TypeError):
+        RsHash.totp_verify(key, 123456, time=59)
+    with pytest.raises(ValueError, match=":"):
+        RsHash.totp_uri(key, "Big:Corp", "alice")
+    with pytest.raises(ValueError, match=":"):
+        RsHash.totp_uri(key, "Big Corp", "a:lice")
+    with pytest.raises(ValueError, match="account"):
+        RsHash.totp_uri(key, "Big Corp", "")
+    with pytest.raises(ValueError, match="SHA1"):
+        RsHash.totp_uri(key, "Big Corp", "alice", digest="sha3_256")
+    with pytest.raises(ValueError, match="step"):
+        RsHash.totp_uri(key, "Big Corp", "alice", step=0)
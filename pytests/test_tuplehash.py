@@ -0,0 +1,80 @@
+"""Tests pour TupleHash128 et TupleHash256 (NIST SP 800-185)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ITEMS = [bytes.fromhex("000102"), bytes.fromhex("101112131415"),
+         bytes.fromhex("202122232425262728")]
+
+
+def tuple_hash(cls, items, length, custom=b""):
+    h = cls(custom=custom)
+    for item in items:
+        h.update(item)
+    return h.hexdigest(length)
+
+
+@pytest.mark.parametrize("cls,count,custom,length,expected", [
+    (RsHash.TupleHash128, 2, b"", 32,
+     "c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1"),
+    (RsHash.TupleHash128, 2, b"My Tuple App", 32,
+     "75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb"),
+    (RsHash.TupleHash128, 3, b"My Tuple App", 32,
+     "e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84"),
+    (RsHash.TupleHash256, 2, b"", 64,
+     "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073"
+     "11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194"),
+    (RsHash.TupleHash256, 3, b"My Tuple App", 64,
+     "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7"
+     "d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce"),
+])
+def test_tuplehash_sp800_185_samples(cls, count, custom, length, expected):
+    """Test les exemples de SP 800-185"""
+    assert tuple_hash(cls, ITEMS[:count], length, custom) == expected
+
+
+@pytest.mark.parametrize("cls", [RsHash.TupleHash128, RsHash.TupleHash256])
+def test_tuplehash_unambiguous(cls):
+    """Test que ("abc", "d") et ("ab", "cd") donnent des hachés différents"""
+    assert tuple_hash(cls, [b"abc", b"d"], 32) != tuple_hash(cls, [b"ab", b"cd"], 32)
+    assert tuple_hash(cls, [b"abcd"], 32) != tuple_hash(cls, [b"ab", b"cd"], 32)
+    assert tuple_hash(cls, [], 32) != tuple_hash(cls, [b""], 32)
+
+
+def test_tuplehash_constructor_data_is_first_item():
+    """Test que data passé au constructeur est le premier élément"""
+    h = RsHash.TupleHash128(ITEMS[0], custom=b"My Tuple App")
+    h.update(ITEMS[1])
+    assert h.hexdigest(32) == tuple_hash(RsHash.TupleHash128, ITEMS[:2], 32, b"My Tuple App")
+
+
+def test_tuplehash_strided_item_is_one_element():
+    """Test qu'un buffer non contigu avec copy=True reste un seul élément"""
+    item = bytes(range(256)) * 600
+    h = RsHash.TupleHash128()
+    h.update(memoryview(item + item)[::2], copy=True)
+    expected = tuple_hash(RsHash.TupleHash128, [(item + item)[::2]], 32)
+    assert h.hexdigest(32) == expected
+
+
+def test_tuplehash_length_is_bound():
+    """Test que la longueur demandée fait partie du haché"""
+    h = RsHash.TupleHash128(b"abc")
+    assert len(h.digest(16)) == 16
+    assert h.digest(16) != h.digest(32)[:16]
+    assert h.digest(32) == h.digest(32)
+
+
+def test_tuplehash_copy_and_properties():
+    """Test copy(), new() et les propriétés"""
+    h1 = RsHash.new("tuplehash_128", ITEMS[0], custom=b"My Tuple App")
+    assert type(h1) is RsHash.TupleHash128
+    h2 = h1.copy()
+    h2.update(ITEMS[1])
+    assert h2.hexdigest(32) == tuple_hash(RsHash.TupleHash128, ITEMS[:2], 32, b"My Tuple App")
+    assert h1.hexdigest(32) == tuple_hash(RsHash.TupleHash128, ITEMS[:1], 32, b"My Tuple App")
+    assert h1.name == "tuplehash_128"
+    assert h1.digest_size == 0
+    assert h1.block_size == 168
+    assert RsHash.TupleHash256().block_size == 136
+    assert "tuplehash_256" in RsHash.algorithms_available
@@ -0,0 +1,92 @@
+"""Tests pour TurboSHAKE128 et TurboSHAKE256 (RFC 9861)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+def ptn(n):
+    """Motif 00 01 .. FA répété, utilisé par les vecteurs de RFC 9861"""
+    return bytes(i % 251 for i in range(n))
+
+
+@pytest.mark.parametrize("message,domain,expected", [
+    (b"", 0x1f, "1e415f1c5983aff2169217277d17bb538cd945a397ddec541f1ce41af2c1b74c"),
+    (ptn(1), 0x1f, "55cedd6f60af7bb29a4042ae832ef3f58db7299f893ebb9247247d856958daa9"),
+    (ptn(17), 0x1f, "9c97d036a3bac819db70ede0ca554ec6e4c2a1a4ffbfd9ec269ca6a111161233"),
+    (ptn(17 ** 2), 0x1f, "96c77c279e0126f7fc07c9b07f5cdae1e0be60bdbe10620040e75d7223a624d2"),
+    (ptn(17 ** 3), 0x1f, "d4976eb56bcf118520582b709f73e1d6853e001fdaf80e1b13e0d0599d5fb372"),
+    (b"\xff" * 3, 0x01, "bf323f940494e88ee1c540fe660be8a0c93f43d15ec006998462fa994eed5dab"),
+    (b"\xff", 0x06, "8ec9c66465ed0d4a6c35d13506718d687a25cb05c74cca1e42501abd83874a67"),
+    (b"\xff" * 7, 0x0b, "8deeaa1aec47ccee569f659c21dfa8e112db3cee37b18178b2acd805b799cc37"),
+    (b"\xff" * 3, 0x7f, "16274cc656d44cefd422395d0f9053bda6d28e122aba15c765e5ad0e6eaf26f9"),
+])
+def test_turboshake128_rfc9861(message, domain, expected):
+    """Test les vecteurs de RFC 9861 pour TurboSHAKE128"""
+    assert RsHash.TurboSHAKE128(message, domain=domain).hexdigest(32) == expected
+
+
+@pytest.mark.parametrize("message,domain,expected", [
+    (b"", 0x1f,
+     "367a329dafea871c7802ec67f905ae13c57695dc2c6663c61035f59a18f8e7db"
+     "11edc0e12e91ea60eb6b32df06dd7f002fbafabb6e13ec1cc20d995547600db0"),
+    (ptn(17), 0x1f,
+     "b3bab0300e6a191fbe6137939835923578794ea54843f5011090fa2f3780a9e5"
+     "cb22c59d78b40a0fbff9e672c0fbe0970bd2c845091c6044d687054da5d8e9c7"),
+    (b"\xff" * 7, 0x0b,
+     "bb36764951ec97e9d85f7ee9a67a7718fc005cf42556be79ce12c0bde50e5736"
+     "d6632b0d0dfb202d1bbb8ffe3dd74cb00834fa756cb03471bab13a1e2c16b3c0"),
+])
+def test_turboshake256_rfc9861(message, domain, expected):
+    """Test les vecteurs de RFC 9861 pour TurboSHAKE256"""
+    assert RsHash.TurboSHAKE256(message, domain=domain).hexdigest(64) == expected
+
+
+def test_turboshake_long_output():
+    """Test les 32 derniers octets d'une sortie de 10032 octets"""
+    assert RsHash.TurboSHAKE128().digest(10032)[10000:].hex() == \
+        "a3b9b0385900ce761f22aed548e754da10a5242d62e8c658e3f3a923a7555607"
+    assert RsHash.TurboSHAKE256().digest(10032)[10000:].hex() == \
+        "abefa11630c661269249742685ec082f207265dccf2f43534e9c61ba0c9d1d75"
+
+
+@pytest.mark.parametrize("cls", [RsHash.TurboSHAKE128, RsHash.TurboSHAKE256])
+def test_turboshake_outputs_are_prefixes(cls):
+    """Test que les sorties courtes sont des préfixes des sorties longues"""
+    h = cls(b"abc", domain=0x42)
+    long = h.digest(1000)
+    for length in (0, 1, 32, 135, 136, 137, 168, 169, 999):
+        assert h.digest(length) == long[:length]
+        assert h.hexdigest(length) == long[:length].hex()
+
+
+@pytest.mark.parametrize("cls,name", [
+    (RsHash.TurboSHAKE128, "turboshake_128"), (RsHash.TurboSHAKE256, "turboshake_256"),
+])
+def test_turboshake_copy_and_new(cls, name):
+    """Test copy(), update() et le constructeur générique"""
+    h = cls(b"abc", domain=0x06)
+    c = h.copy()
+    h.update(b"def")
+    assert c.digest(32) == cls(b"abc", domain=0x06).digest(32)
+    assert h.digest(32) == cls(b"abcdef", domain=0x06).digest(32)
+    assert h.name == name
+    assert RsHash.new(name, b"abc").digest(32) == cls(b"abc").digest(32)
+
+
+def test_turboshake_domain_changes_output():
+    """Test que l'octet de domaine sépare les sorties"""
+    assert RsHash.TurboSHAKE128(b"x", domain=0x1f).digest(32) != \
+        RsHash.TurboSHAKE128(b"x", domain=0x1e).digest(32)
+
+
+@pytest.mark.parametrize("domain", [0x00, 0x80, 0xff])
+def test_turboshake_rejects_invalid_domain(domain):
+    """Test qu'un octet de domaine hors de 0x01..0x7f lève ValueError"""
+    with pytest.raises(ValueError):
+        RsHash.TurboSHAKE128(domain=domain)
+
+
+def test_turboshake_domain_must_be_a_byte():
+    """Test qu'un domaine qui ne tient pas sur un octet est refusé"""
+    with pytest.raises(OverflowError):
+        RsHash.TurboSHAKE256(domain=0x100)
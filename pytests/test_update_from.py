@@ -0,0 +1,191 @@
+"""Tests pour la méthode update_from des objets de hachage"""
+import hashlib
+import io
+import os
+import socket
+import threading
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = os.urandom(100000)
+
+
+class ReadOnly:
+    """Objet fichier minimal n'exposant que read()"""
+
+    def __init__(self, data):
+        self.stream = io.BytesIO(data)
+        self.calls = 0
+
+    def read(self, size):
+        self.calls += 1
+        return self.stream.read(size)
+
+
+@pytest.mark.parametrize("chunk_size", [1, 7, 4096, 1 << 20])
+def test_update_from_bytesio(chunk_size):
+    """Test un io.BytesIO, lu avec readinto() par blocs"""
+    hasher = RsHash.SHA256(b"prefix")
+    assert hasher.update_from(io.BytesIO(DATA[:20000]), chunk_size) == 20000
+    assert hasher.hexdigest() == hashlib.sha256(b"prefix" + DATA[:20000]).hexdigest()
+
+
+def test_update_from_real_file(tmp_path):
+    """Test un fichier réel, avec et sans tampon"""
+    path = tmp_path / "data"
+    path.write_bytes(DATA)
+    for buffering in (-1, 0):
+        with open(path, "rb", buffering=buffering) as f:
+            hasher = RsHash.SHA512()
+            assert hasher.update_from(f, chunk_size=4096) == len(DATA)
+        assert hasher.digest() == hashlib.sha512(DATA).digest()
+    # La lecture commence à la position courante
+    with open(path, "rb") as f:
+        f.seek(10)
+        hasher = RsHash.SHA256()
+        assert hasher.update_from(f) == len(DATA) - 10
+    assert hasher.digest() == hashlib.sha256(DATA[10:]).digest()
+
+
+def test_update_from_read_only():
+    """Test un objet sans readinto(), lu avec read(n)"""
+    stream = ReadOnly(DATA)
+    hasher = RsHash.SHA256()
+    assert hasher.update_from(stream, chunk_size=30000) == len(DATA)
+    assert hasher.digest() == hashlib.sha256(DATA).digest()
+    assert stream.calls == 5
+
+
+class Strided(ReadOnly):
+    """Objet fichier dont read() renvoie une memoryview non contiguë"""
+
+    def read(self, size):
+        self.calls += 1
+        return memoryview(self.stream.read(2 * size))[::2]
+
+
+def test_update_from_strided_read():
+    """Test que les blocs non contigus renvoyés par read() sont hachés dans l'ordre"""
+    hasher = RsHash.SHA256()
+    assert hasher.update_from(Strided(DATA), chunk_size=3000) == len(DATA) // 2
+    assert hasher.digest() == hashlib.sha256(DATA[::2]).digest()
+    hasher = RsHash.SHA256()
+    assert hasher.update_from(Strided(DATA), 3000, 4001) == 4001
+    assert hasher.digest() == hashlib.sha256(DATA[:8002:2]).digest()
+    tuple_hash = RsHash.TupleHash128()
+    tuple_hash.update_from(Strided(DATA), 1000)
+    assert tuple_hash.digest(32) == RsHash.TupleHash128(DATA[::2]).digest(32)
+
+
+@pytest.mark.parametrize("max_bytes", [0, 1, 4095, 4096, 4097, 99999, 100000, 10**9])
+def test_update_from_max_bytes(max_bytes):
+    """Test l'arrêt après max_bytes octets, le reste du fichier n'étant pas lu"""
+    for stream in (io.BytesIO(DATA), ReadOnly(DATA)):
+        hasher = RsHash.SHA256()
+        consumed = hasher.update_from(stream, 4096, max_bytes)
+        assert consumed == min(max_bytes, len(DATA))
+        assert hasher.digest() == hashlib.sha256(DATA[:consumed]).digest()
+        stream = stream.stream if isinstance(stream, ReadOnly) else stream
+        assert stream.tell() == consumed
+
+
+def test_update_from_empty():
+    """Test un fichier vide"""
+    hasher = RsHash.SHA256()
+    assert hasher.update_from(io.BytesIO()) == 0
+    assert hasher.digest() == hashlib.sha256().digest()
+
+
+def test_update_from_socket():
+    """Test l'objet renvoyé par socket.makefile("rb")"""
+    left, right = socket.socketpair()
+    sender = threading.Thread(target=lambda: (right.sendall(DATA), right.close()))
+    sender.start()
+    try:
+        with left.makefile("rb") as f:
+            hasher = RsHash.SHA256()
+            assert hasher.update_from(f, chunk_size=1000) == len(DATA)
+    finally:
+        sender.join()
+        left.close()
+    assert hasher.hexdigest() == hashlib.sha256(DATA).hexdigest()
+
+
+def test_update_from_classes():
+    """Test les autres classes, XOF et à éléments compris"""
+    for name, expected in [("SHA3_256", hashlib.sha3_256(DATA).digest()),
+                           ("BLAKE2b", hashlib.blake2b(DATA).digest())]:
+        hasher = getattr(RsHash, name)()
+        hasher.update_from(io.BytesIO(DATA), 1000)
+        assert hasher.digest() == expected
+    shake = RsHash.SHAKE128()
+    shake.update_from(ReadOnly(DATA), 1000)
+    assert shake.digest(64) == hashlib.shake_128(DATA).digest(64)
+    blake3 = RsHash.BLAKE3()
+    blake3.update_from(io.BytesIO(DATA), 1000)
+    assert blake3.digest() == RsHash.BLAKE3(DATA).digest()
+    # TupleHash : le fichier forme un seul élément, comme update(f.read())
+    tuple_hash = RsHash.TupleHash128()
+    tuple_hash.update_from(io.BytesIO(DATA), 1000)
+    assert tuple_hash.digest(32) == RsHash.TupleHash128(DATA).digest(32)
+
+
+def test_update_from_digest_cache():
+    """Test que le condensé mis en cache est invalidé"""
+    hasher = RsHash.SHA256(b"abc")
+    hasher.digest()
+    hasher.update_from(io.BytesIO(b"def"))
+    assert hasher.digest() == hashlib.sha256(b"abcdef").digest()
+
+
+def test_update_from_reentrant():
+    """Test un read() qui utilise lui-même l'objet de hachage"""
+    hasher = RsHash.SHA256()
+
+    class Tapped(ReadOnly):
+        def read(self, size):
+            hasher.update(b"!")
+            return super().read(size)
+
+    hasher.update_from(Tapped(b"abcdef"), chunk_size=4)
+    assert hasher.digest() == hashlib.sha256(b"!abcd!ef!").digest()
+
+
+def test_update_from_exceptions():
+    """Test que les exceptions de read() sont propagées telles quelles"""
+
+    class Failing(ReadOnly):
+        def read(self, size):
+            if self.calls:
+                raise ConnectionResetError("peer went away")
+            return super().read(size)
+
+    hasher = RsHash.SHA256()
+    with pytest.raises(ConnectionResetError, match="peer went away"):
+        hasher.update_from(Failing(DATA), chunk_size=10)
+    # Les octets déjà lus restent hachés
+    assert hasher.digest() == hashlib.sha256(DATA[:10]).digest()
+    with pytest.raises(AttributeError):
+        hasher.update_from(b"not a file")
+    with pytest.raises(ValueError, match="chunk_size"):
+        hasher.update_from(io.BytesIO(DATA), 0)
+    with pytest.raises(OverflowError):
+        hasher.update_from(io.BytesIO(DATA), max_bytes=-1)
+    with open(__file__, "r") as f:
+        with pytest.raises(TypeError):
+            hasher.update_from(f)
+
+
+def test_update_from_non_blocking():
+    """Test un fichier non bloquant sans données disponibles"""
+    left, right = socket.socketpair()
+    try:
+        left.setblocking(False)
+        with left.makefile("rb", buffering=0) as f:
+            with pytest.raises(BlockingIOError):
+                RsHash.SHA256().update_from(f)
+    finally:
+        left.close()
+        right.close()
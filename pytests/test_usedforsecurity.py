@@ -0,0 +1,61 @@
+"""Tests du mot-clé usedforsecurity et du mode FIPS"""
+import hashlib
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+ALGORITHMS = [
+    ("md5", RsHash.MD5),
+    ("sha1", RsHash.SHA1),
+    ("sha256", RsHash.SHA256),
+    ("sha512", RsHash.SHA512),
+]
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_usedforsecurity_default(name, cls):
+    """Test que usedforsecurity vaut True par défaut"""
+    assert cls().usedforsecurity is True
+    assert RsHash.new(name).usedforsecurity is True
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_usedforsecurity_false(name, cls):
+    """Test que usedforsecurity=False est accepté comme avec hashlib"""
+    expected = hashlib.new(name, b"abc", usedforsecurity=False).hexdigest()
+    h = cls(b"abc", usedforsecurity=False)
+    assert h.usedforsecurity is False
+    assert h.hexdigest() == expected
+    h = RsHash.new(name, b"abc", usedforsecurity=False)
+    assert h.usedforsecurity is False
+    assert h.hexdigest() == expected
+    assert h.copy().usedforsecurity is False
+
+
+@pytest.mark.parametrize("name,cls", ALGORITHMS)
+def test_usedforsecurity_read_only(name, cls):
+    """Test que la propriété est en lecture seule"""
+    with pytest.raises(AttributeError):
+        cls().usedforsecurity = False
+
+
+def test_fips_mode_toggle():
+    """Test du mode FIPS : SHA-2 reste utilisable dans tous les cas"""
+    assert RsHash.get_fips_mode() is False
+    RsHash.set_fips_mode(True)
+    try:
+        assert RsHash.get_fips_mode() is True
+        assert RsHash.SHA256(b"abc").hexdigest() == hashlib.sha256(b"abc").hexdigest()
+        assert RsHash.new("sha512", usedforsecurity=True).usedforsecurity is True
+    finally:
+        RsHash.set_fips_mode(False)
+    assert RsHash.get_fips_mode() is False
+
+
+def test_fips_mode_sha1_still_allowed():
+    """Test que SHA-1, fonction de FIPS 180-4, reste utilisable en mode FIPS"""
+    RsHash.set_fips_mode(True)
+    try:
+        assert RsHash.SHA1(b"abc").hexdigest() == hashlib.sha1(b"abc").hexdigest()
+    finally:
+        RsHash.set_fips_mode(False)
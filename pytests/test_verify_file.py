@@ -0,0 +1,105 @@
+"""Tests pour verify_file et verify_bytes (comparaison à un condensé attendu)"""
+import hashlib
+import os
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+DATA = os.urandom(10000)
+SHA256 = hashlib.sha256(DATA)
+
+
+def write(tmp_path, data=DATA):
+    """Écrit data dans un fichier temporaire et renvoie son chemin"""
+    path = tmp_path / "data"
+    path.write_bytes(data)
+    return path
+
+
+def flip(hexdigest):
+    """Renvoie hexdigest avec son dernier chiffre modifié"""
+    return hexdigest[:-1] + ("0" if hexdigest[-1] != "0" else "1")
+
+
+@pytest.mark.parametrize("expected,algorithm", [
+    (SHA256.hexdigest(), "sha256"),
+    (SHA256.hexdigest().upper(), "sha256"),
+    (SHA256.hexdigest(), "SHA-256"),
+    (SHA256.digest(), "sha256"),
+    (bytearray(SHA256.digest()), "sha256"),
+    (memoryview(SHA256.digest()), "sha256"),
+    ("sha256:" + SHA256.hexdigest(), None),
+    ("SHA256:" + SHA256.hexdigest().upper(), None),
+    ("sha-256:" + SHA256.hexdigest(), "sha256"),
+    ("sha256:" + SHA256.hexdigest() + "\n", None),
+    ("sha512:" + hashlib.sha512(DATA).hexdigest(), None),
+    (hashlib.blake2b(DATA).digest(), "blake2b"),
+    ("sha3_256:" + hashlib.sha3_256(DATA).hexdigest(), None),
+])
+def test_verify_match(tmp_path, expected, algorithm):
+    """Test toutes les formes acceptées pour le condensé attendu"""
+    path = write(tmp_path)
+    assert RsHash.verify_file(path, expected, algorithm) is True
+    assert RsHash.verify_bytes(DATA, expected, algorithm) is True
+    assert RsHash.verify_file(str(path), expected, algorithm, chunk_size=7) is True
+
+
+@pytest.mark.parametrize("expected,algorithm", [
+    (flip(SHA256.hexdigest()), "sha256"),
+    ("0" + SHA256.hexdigest()[1:], "sha256"),
+    (bytes(32), "sha256"),
+    ("sha256:" + flip(SHA256.hexdigest()), None),
+    ("sha256:" + hashlib.sha256(DATA[:-1]).hexdigest(), None),
+    ("sha3_256:" + SHA256.hexdigest(), None),
+])
+def test_verify_mismatch(tmp_path, expected, algorithm):
+    """Test les condensés différents, y compris au premier et au dernier chiffre"""
+    path = write(tmp_path)
+    assert RsHash.verify_file(path, expected, algorithm) is False
+    assert RsHash.verify_bytes(DATA, expected, algorithm) is False
+
+
+def test_verify_other_content(tmp_path):
+    """Test un fichier modifié et un fichier vide"""
+    assert not RsHash.verify_file(write(tmp_path, DATA + b"\0"), SHA256.hexdigest(), "sha256")
+    empty = hashlib.sha256(b"").hexdigest()
+    assert RsHash.verify_file(write(tmp_path, b""), "sha256:" + empty)
+    assert RsHash.verify_bytes(b"", "sha256:" + empty)
+    assert RsHash.verify_bytes(bytearray(DATA), SHA256.digest(), "sha256")
+
+
+def test_verify_errors(tmp_path):
+    """Test les valeurs attendues refusées"""
+    path = write(tmp_path)
+    for expected, algorithm, match in [
+        (SHA256.hexdigest(), None, "cannot determine"),
+        (SHA256.digest(), None, "cannot determine"),
+        ("sha256:" + SHA256.hexdigest(), "sha512", "not sha512"),
+        ("sha256:xyz", None, "hexadecimal"),
+        (SHA256.hexdigest()[:-1], "sha256", "hexadecimal"),
+        ("sha256:" + SHA256.hexdigest()[:-2], None, "31 bytes"),
+        (SHA256.digest(), "sha512", "32 bytes"),
+        ("nope:" + SHA256.hexdigest(), None, "unsupported"),
+        (SHA256.hexdigest(), "shake_128", "digest size"),
+    ]:
+        with pytest.raises(ValueError, match=match):
+            RsHash.verify_file(path, expected, algorithm)
+        with pytest.raises(ValueError, match=match):
+            RsHash.verify_bytes(DATA, expected, algorithm)
+    with pytest.raises(TypeError):
+        RsHash.verify_bytes(DATA, 42, "sha256")
+    with pytest.raises(TypeError):
+        RsHash.verify_bytes("text", SHA256.digest(), "sha256")
+    with pytest.raises(FileNotFoundError):
+        RsHash.verify_file(tmp_path / "missing", "sha256:" + SHA256.hexdigest())
+    with pytest.raises(BufferError):
+        RsHash.verify_bytes(memoryview(DATA)[::2], SHA256.digest(), "sha256")
+    RsHash.set_fips_mode(True)
+    try:
+        md5 = "md5:" + hashlib.md5(DATA).hexdigest()
+        with pytest.raises(ValueError, match="FIPS"):
+            RsHash.verify_file(path, md5)
+        assert RsHash.verify_file(path, md5, usedforsecurity=False)
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,67 @@
+"""Tests pour Whirlpool (ISO/IEC 10118-3)"""
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+
+
+@pytest.mark.parametrize("data,expected", [
+    (b"",
+     "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7"
+     "3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3"),
+    (b"a",
+     "8aca2602792aec6f11a67206531fb7d7f0dff59413145e6973c45001d0087b42"
+     "d11bc645413aeff63a42391a39145a591a92200d560195e53b478584fdae231a"),
+    (b"abc",
+     "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c"
+     "7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5"),
+    (b"message digest",
+     "378c84a4126e2dc6e56dcc7458377aac838d00032230f53ce1f5700c0ffb4d3b"
+     "8421557659ef55c106b4b52ac5a4aaa692ed920052838f3362e86dbd37a8903e"),
+    (b"abcdefghijklmnopqrstuvwxyz",
+     "f1d754662636ffe92c82ebb9212a484a8d38631ead4238f5442ee13b8054e41b"
+     "08bf2a9251c30b6a0b8aae86177ab4a6f68f673e7207865d5d9819a3dba4eb3b"),
+    (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+     "dc37e008cf9ee69bf11f00ed9aba26901dd7c28cdec066cc6af42e40f82f3a1e"
+     "08eba26629129d8fb7cb57211b9281a65517cc879d7b962142c65f5a7af01467"),
+    (b"1234567890" * 8,
+     "466ef18babb0154d25b9d38a6414f5c08784372bccb204d6549c4afadb601429"
+     "4d5bd8df2a6c44e538cd047b2681a51a2c60481e88c5a20b2c2a80cf3a9a083b"),
+    (b"The quick brown fox jumps over the lazy dog",
+     "b97de512e91e3828b40d2b0fdce9ceb3c4a71f9bea8d88e75c4fa854df36725f"
+     "d2b52eb6544edcacd6f8beddfea403cb55ae31f03ad62a5ef54e42ee82c3fb35"),
+])
+def test_whirlpool_iso_vectors(data, expected):
+    """Test les vecteurs ISO et l'exemple "The quick brown fox" """
+    assert RsHash.Whirlpool(data).hexdigest() == expected
+
+
+def test_whirlpool_streaming():
+    """Test Whirlpool en plusieurs update() autour de la limite de padding (32 octets)"""
+    data = bytes(range(256)) * 2
+    for step in (1, 31, 32, 33, 64):
+        h = RsHash.Whirlpool()
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.digest() == RsHash.Whirlpool(data).digest()
+
+
+def test_whirlpool_properties_and_new():
+    """Test les propriétés et new("whirlpool")"""
+    h = RsHash.new("Whirlpool", b"abc")
+    assert type(h) is RsHash.Whirlpool
+    assert h.name == "whirlpool"
+    assert h.digest_size == 64
+    assert h.block_size == 64
+    assert h.digest() == RsHash.Whirlpool(b"abc").digest()
+    assert "whirlpool" in RsHash.algorithms_available
+
+
+def test_whirlpool_fips_mode():
+    """Test qu'en mode FIPS, Whirlpool exige usedforsecurity=False"""
+    RsHash.set_fips_mode(True)
+    try:
+        with pytest.raises(ValueError, match="usedforsecurity=False"):
+            RsHash.Whirlpool(b"abc")
+        assert RsHash.new("whirlpool", usedforsecurity=False).name == "whirlpool"
+    finally:
+        RsHash.set_fips_mode(False)
@@ -0,0 +1,73 @@
+"""Tests pour wyhash et rapidhash du sous-module noncrypto"""
+import hashlib
+import struct
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+# Secret par défaut de wyhash (_wyp), en quatre mots little-endian
+DEFAULT_SECRET = struct.pack(
+    "<4Q", 0x2D358DCCAA6C78A5, 0x8BB84B93962EACC9, 0x4B33A62ED433D4A3, 0x4D5A2DA51DE1AA47)
+
+
+@pytest.mark.parametrize("seed,data,expected", [
+    (0, b"", 0x93228A4DE0EEC5A2),
+    (1, b"a", 0xC5BAC3DB178713C4),
+    (2, b"abc", 0xA97F2F7B1D9B3314),
+    (3, b"message digest", 0x786D1F1DF3801DF4),
+    (4, b"abcdefghijklmnopqrstuvwxyz", 0xDCA5A8138AD37C87),
+    (5, b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", 0xB9E734F117CFAF70),
+    (6, b"1234567890" * 8, 0x6CC5EAB49A92D617),
+])
+def test_wyhash_reference(seed, data, expected):
+    """Test les vecteurs de test_vector.cpp de wyhash (version finale 4)"""
+    assert noncrypto.wyhash(data, seed) == expected
+    assert noncrypto.wyhash(memoryview(bytearray(data)), seed=seed) == expected
+    assert noncrypto.wyhash(data, seed, DEFAULT_SECRET) == expected
+
+
+def test_rapidhash_reference():
+    """Test rapidhash v3 pour les longueurs 0 à 112 et trois graines, contre l'implémentation de référence"""
+    data = bytes(range(113))
+    out = b"".join(
+        struct.pack("<Q", noncrypto.rapidhash(data[:length], seed))
+        for seed in (0, 7, 2**64 - 1)
+        for length in range(113)
+    )
+    assert hashlib.sha256(out).hexdigest() == (
+        "5608fa48528608ea8bbb3498dad928b3137fc25efba57eedc1d4fff58710cfba")
+
+
+def test_rapidhash_long_input():
+    """Test que les blocs de 112 octets dépendent de chaque octet et de la graine"""
+    data = bytearray(range(256)) * 4
+    value = noncrypto.rapidhash(bytes(data))
+    assert value != noncrypto.rapidhash(bytes(data), 1)
+    data[500] ^= 1
+    assert value != noncrypto.rapidhash(bytes(data))
+    assert noncrypto.rapidhash(b"abc") != noncrypto.wyhash(b"abc")
+
+
+def test_wyhash_secret():
+    """Test qu'un secret personnalisé change le résultat"""
+    secret = bytes(range(32))
+    data = b"x" * 100
+    assert noncrypto.wyhash(data, secret=secret) != noncrypto.wyhash(data)
+    assert noncrypto.wyhash(data, 0, secret) == noncrypto.wyhash(data, secret=bytearray(secret))
+
+
+def test_wyhash_argument_errors():
+    """Test le rejet des secrets de mauvaise taille et des graines hors limites"""
+    with pytest.raises(ValueError, match="32 bytes"):
+        noncrypto.wyhash(b"", secret=bytes(31))
+    with pytest.raises(ValueError, match="32 bytes"):
+        noncrypto.wyhash(b"", secret=bytes(33))
+    with pytest.raises(OverflowError):
+        noncrypto.wyhash(b"", -1)
+    with pytest.raises(OverflowError):
+        noncrypto.rapidhash(b"", 2**64)
+    with pytest.raises(TypeError):
+        noncrypto.rapidhash("text")
+    assert noncrypto.rapidhash(b"", 2**64 - 1) >= 0
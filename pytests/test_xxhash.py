@@ -0,0 +1,239 @@
+"""Tests pour les fonctions xxHash du sous-module noncrypto"""
+import copy
+import random
+
+import pytest
+
+RsHash = pytest.importorskip("RsHash")
+noncrypto = RsHash.noncrypto
+
+PRIME32 = 2654435761
+PRIME64 = 11400714785074694797
+
+
+def sanity_buffer(length):
+    """Tampon des tests de référence de xxhsum (octet de poids fort des puissances de PRIME64)"""
+    generator = PRIME32
+    out = bytearray()
+    for _ in range(length):
+        out.append(generator >> 56)
+        generator = (generator * 11400714785074694797) % 2**64
+    return bytes(out)
+
+
+@pytest.mark.parametrize("length,seed,expected", [
+    (0, 0, 0x02CC5D05),
+    (0, PRIME32, 0x36B78AE7),
+    (1, 0, 0xCF65B03E),
+    (1, PRIME32, 0xB4545AA4),
+    (14, 0, 0x1208E7E2),
+    (14, PRIME32, 0x6AF1D1FE),
+    (222, 0, 0x5BD11DBD),
+    (222, PRIME32, 0x58803C5F),
+    # Graines qui font déborder les accumulateurs, autour d'une bande de 16 octets
+    (3, 0xFFFFFFFF, 0x37919432),
+    (15, 0xFFFFFFFF, 0x83C33FAB),
+    (16, 0xFFFFFFFF, 0x6D7A6DE2),
+    (17, 0x7FFFFFFF, 0x32BCD374),
+])
+def test_xxh32_sanity_vectors(length, seed, expected):
+    """Test les vecteurs officiels de xxhsum pour XXH32"""
+    h = noncrypto.xxh32(sanity_buffer(length), seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(4, "big")
+    assert h.hexdigest() == f"{expected:08x}"
+
+
+def test_xxh32_small_updates():
+    """Test des update() plus petits qu'une bande de 16 octets, copy() et reset()"""
+    data = sanity_buffer(500)
+    expected = noncrypto.xxh32(data, 0xFFFFFFFF).intdigest()
+    for step in (1, 2, 3, 5, 15, 16, 17):
+        h = noncrypto.xxh32(seed=0xFFFFFFFF)
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.intdigest() == expected
+    h = noncrypto.xxh32(data[:7], 0xFFFFFFFF)
+    c = h.copy()
+    c.update(data[7:])
+    assert c.intdigest() == expected
+    h.reset()
+    assert h.intdigest() == noncrypto.xxh32(b"", 0xFFFFFFFF).intdigest()
+    assert (h.name, h.digest_size, h.block_size) == ("xxh32", 4, 16)
+    assert noncrypto.xxh32(b"a").hexdigest() == "550d7456"
+    with pytest.raises(OverflowError):
+        noncrypto.xxh32(b"", 2**32)
+
+
+@pytest.mark.parametrize("length,seed,expected", [
+    (0, 0, 0xEF46DB3751D8E999),
+    (0, PRIME32, 0xAC75FDA2929B17EF),
+    (1, 0, 0xE934A84ADB052768),
+    (1, PRIME32, 0x5014607643A9B4C3),
+    (4, 0, 0x9136A0DCA57457EE),
+    (14, 0, 0x8282DCC4994E35C8),
+    (14, PRIME32, 0xC3BD6BF63DEB6DF0),
+    (222, 0, 0xB641AE8CB691C174),
+    (222, PRIME32, 0x20CB8AB7AE10C14A),
+])
+def test_xxh64_sanity_vectors(length, seed, expected):
+    """Test les vecteurs officiels de xxhsum pour plusieurs graines"""
+    h = noncrypto.xxh64(sanity_buffer(length), seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(8, "big")
+    assert h.hexdigest() == f"{expected:016x}"
+    assert noncrypto.xxh64(sanity_buffer(length), seed=seed).intdigest() == expected
+
+
+def test_xxh64_streaming_copy_and_reset():
+    """Test update() par morceaux, copy() et reset() qui conserve la graine"""
+    data = sanity_buffer(1000)
+    expected = noncrypto.xxh64(data, PRIME32).intdigest()
+    for step in (1, 7, 31, 32, 33, 100):
+        h = noncrypto.xxh64(seed=PRIME32)
+        for start in range(0, len(data), step):
+            h.update(data[start:start + step])
+        assert h.intdigest() == expected
+    h = noncrypto.xxh64(data[:500], PRIME32)
+    c = h.copy()
+    c.update(data[500:])
+    assert c.intdigest() == expected
+    assert copy.copy(h).intdigest() == h.intdigest()
+    h.update(b"more")
+    h.reset()
+    assert h.intdigest() == noncrypto.xxh64(b"", PRIME32).intdigest()
+
+
+def test_xxh64_properties():
+    """Test les propriétés et l'import du sous-module"""
+    from RsHash.noncrypto import xxh64
+
+    h = xxh64(b"a")
+    assert h.name == "xxh64"
+    assert h.digest_size == 8
+    assert h.block_size == 32
+    assert h.hexdigest() == "d24ec4f1a98c6e5b"
+    assert isinstance(h, xxh64)
+    with pytest.raises(OverflowError):
+        xxh64(b"", -1)
+    with pytest.raises(TypeError):
+        xxh64("text")
+
+
+def test_xxh64_fips_mode():
+    """Test que xxh64 reste disponible en mode FIPS (somme de contrôle non cryptographique)"""
+    RsHash.set_fips_mode(True)
+    try:
+        assert noncrypto.xxh64(b"a").hexdigest() == "d24ec4f1a98c6e5b"
+    finally:
+        RsHash.set_fips_mode(False)
+
+
+@pytest.mark.parametrize("length,seed,expected", [
+    # 0 à 16 octets
+    (0, 0, 0x2D06800538D394C2),
+    (1, 0, 0xC44BDFF4074EECDB),
+    (1, PRIME64, 0x032BE332DD766EF8),
+    (6, 0, 0x27B56A84CD2D7325),
+    (12, PRIME64, 0xE7303E1B2336DE0E),
+    # 17 à 128 octets
+    (24, 0, 0xA3FE70BF9D3510EB),
+    (80, PRIME64, 0xC6DD0CB699532E73),
+    # 129 à 240 octets
+    (195, 0, 0xCD94217EE362EC3A),
+    (195, PRIME64, 0xBA68003D370CB3D9),
+    # Entrées longues
+    (403, 0, 0xCDEB804D65C6DEA4),
+    (2048, PRIME64, 0x66F81670669ABABC),
+    (2367, 0, 0xCB37AEB9E5D361ED),
+])
+def test_xxh3_64_sanity_vectors(length, seed, expected):
+    """Test les vecteurs officiels de xxhsum pour XXH3-64 dans tous les régimes de longueur"""
+    h = noncrypto.xxh3_64(sanity_buffer(length), seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(8, "big")
+
+
+@pytest.mark.parametrize("length,seed,expected", [
+    (0, 0, 0x99AA06D3014798D86001C324468D497F),
+    (1, PRIME64, 0x20E49ABCC53B3842032BE332DD766EF8),
+    (6, 0, 0x082AFE0B8162D12A3E7039BDDA43CFC6),
+    (12, 0, 0x6E3EFD8FC7802B18061A192713F69AD9),
+    (48, PRIME64, 0xBC689F4C0152FB443A94D91333ED395A),
+    (195, 0, 0x7729543A26B207EE3FB593C086A66075),
+    (512, PRIME64, 0x925D06B8EC5B80403CE457DE14C27708),
+    (2240, 0, 0xCCB134FBFA7CE49D6E73A90539CF2948),
+])
+def test_xxh3_128_sanity_vectors(length, seed, expected):
+    """Test les vecteurs officiels de xxhsum pour XXH3-128"""
+    h = noncrypto.xxh3_128(sanity_buffer(length), seed=seed)
+    assert h.intdigest() == expected
+    assert h.digest() == expected.to_bytes(16, "big")
+    assert h.hexdigest() == f"{expected:032x}"
+
+
+@pytest.mark.parametrize("length,expected64,expected128", [
+    (0, 0x3559D64878C5C66C, 0x5F70F4EA232F1D38005923CCEECBE8AE),
+    (6, 0x82C90AB0519369AD, 0x376BD91B6432F36D0B61C8ACA7D4778F),
+    (80, 0xE687BA1684965297, 0x5DE1C8EB7BD056B812568D8D732F8544),
+    (195, 0xA057273F5EECFB20, 0x18783075F43015BE002CDB4476B9A36F),
+    (2367, 0x293FA8E5173BB5E7, 0x343654A35ACF0DAE293FA8E5173BB5E7),
+])
+def test_xxh3_custom_secret(length, expected64, expected128):
+    """Test les vecteurs officiels avec un secret de 147 octets tiré du tampon"""
+    buffer = sanity_buffer(2367)
+    secret = buffer[7:7 + 147]
+    assert noncrypto.xxh3_64(buffer[:length], secret=secret).intdigest() == expected64
+    assert noncrypto.xxh3_128(buffer[:length], secret=secret).intdigest() == expected128
+
+
+def test_xxh3_invalid_secret():
+    """Test le rejet d'un secret trop court et d'une graine combinée à un secret"""
+    with pytest.raises(ValueError, match="at least 136 bytes"):
+        noncrypto.xxh3_64(secret=bytes(135))
+    with pytest.raises(ValueError, match="cannot be combined"):
+        noncrypto.xxh3_128(b"", 1, bytes(136))
+
+
+@pytest.mark.parametrize("name", ["xxh3_64", "xxh3_128"])
+def test_xxh3_streaming_copy_and_reset(name):
+    """Test update() par morceaux autour du tampon de 256 octets et des blocs de 1024 octets"""
+    cls = getattr(noncrypto, name)
+    data = sanity_buffer(3000)
+    for length in (240, 241, 256, 257, 1024, 1025, 3000):
+        expected = cls(data[:length], PRIME64).intdigest()
+        for step in (1, 63, 64, 65, 256, 1000):
+            h = cls(seed=PRIME64)
+            for start in range(0, length, step):
+                h.update(data[start:min(start + step, length)])
+            assert h.intdigest() == expected
+    h = cls(data[:100], PRIME64)
+    c = h.copy()
+    c.update(data[100:])
+    assert c.intdigest() == cls(data, PRIME64).intdigest()
+    h.reset()
+    assert h.intdigest() == cls(b"", PRIME64).intdigest()
+    assert (h.name, h.block_size) == (name, 64)
+    assert h.digest_size == (8 if name == "xxh3_64" else 16)
+
+
+@pytest.mark.parametrize("name,bits", [
+    ("xxh32", 32), ("xxh64", 64), ("xxh3_64", 64), ("xxh3_128", 64),
+])
+def test_xxhash_matches_xxhash_package(name, bits):
+    """Test différentiel contre le paquet xxhash pour des entrées aléatoires découpées"""
+    xxhash = pytest.importorskip("xxhash")
+    rng = random.Random(bits)
+    for _ in range(50):
+        data = rng.randbytes(rng.randrange(0, 2000))
+        seed = rng.getrandbits(bits)
+        ours = getattr(noncrypto, name)(seed=seed)
+        theirs = getattr(xxhash, name)(seed=seed)
+        pos = 0
+        while pos < len(data):
+            step = rng.randrange(1, 100)
+            ours.update(data[pos:pos + step])
+            theirs.update(data[pos:pos + step])
+            pos += step
+        assert ours.intdigest() == theirs.intdigest()
+        assert ours.digest() == theirs.digest()
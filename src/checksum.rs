@@ -0,0 +1,376 @@
+//! GNU coreutils checksum files, as written by `sha256sum` and read by
+//! `sha256sum -c`.
+//!
+//! Each line is `<hex digest> <type><file name>`, the type being `*` for
+//! binary mode and a space for text mode, or the BSD tagged form
+//! `<TAG> (<file name>) = <hex digest>` of `sha256sum --tag`, `shasum
+//! --tag` and the BSD tools, whose `<TAG>(<file name>)= <hex digest>`
+//! variant is written by `openssl dgst`. Names holding a backslash, a
+//! newline or a carriage return are escaped as `\\`, `\n` and `\r`, and
+//! their line starts with a backslash.
+//!
+//! Reading follows coreutils 9: lines starting with `#` are comments, a
+//! trailing carriage return is dropped, blank lines are skipped, and a
+//! digest followed by a single space and the name (the reversed form of
+//! `md5 -r`) is accepted only if the first entry of the file has that form.
+//! Each line is read as tagged when it starts with a tag and `(`, the
+//! name running to the last `)` so that it may hold parentheses itself.
+
+use crate::utils::{from_hex, to_hex};
+
+/// One entry of a checksum file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The digest, decoded from hex.
+    pub digest: Vec<u8>,
+    /// Whether the name was marked `*` for binary mode.
+    pub binary: bool,
+    /// The file name, unescaped.
+    pub name: Vec<u8>,
+    /// The algorithm tag of a BSD line, such as `SHA256`, or `None` for a
+    /// GNU line.
+    pub tag: Option<String>,
+}
+
+/// Writes the line for `name` with `digest`, newline included, as
+/// `sha256sum` does.
+pub fn format_line(digest: &[u8], name: &[u8], binary: bool) -> Vec<u8> {
+    let mut line = Vec::with_capacity(digest.len() * 2 + name.len() + 4);
+    if needs_escape(name) {
+        line.push(b'\\');
+    }
+    line.extend_from_slice(to_hex(digest).as_bytes());
+    line.extend_from_slice(if binary { b" *" } else { b"  " });
+    escape_into(&mut line, name);
+    line.push(b'\n');
+    line
+}
+
+/// Writes the BSD tagged line for `name` with `digest`, newline included,
+/// as `sha256sum --tag` does.
+pub fn format_tagged_line(tag: &str, digest: &[u8], name: &[u8]) -> Vec<u8> {
+    let mut line = Vec::with_capacity(tag.len() + digest.len() * 2 + name.len() + 8);
+    if needs_escape(name) {
+        line.push(b'\\');
+    }
+    line.extend_from_slice(tag.as_bytes());
+    line.extend_from_slice(b" (");
+    escape_into(&mut line, name);
+    line.extend_from_slice(b") = ");
+    line.extend_from_slice(to_hex(digest).as_bytes());
+    line.push(b'\n');
+    line
+}
+
+/// Returns whether `name` must be escaped, which also marks its line with
+/// a leading backslash.
+fn needs_escape(name: &[u8]) -> bool {
+    name.iter().any(|b| matches!(b, b'\\' | b'\n' | b'\r'))
+}
+
+/// Appends `name` to `line`, escaped if [`needs_escape`] says so.
+fn escape_into(line: &mut Vec<u8>, name: &[u8]) {
+    for &b in name {
+        match b {
+            b'\\' => line.extend_from_slice(b"\\\\"),
+            b'\n' => line.extend_from_slice(b"\\n"),
+            b'\r' => line.extend_from_slice(b"\\r"),
+            _ => line.push(b),
+        }
+    }
+}
+
+/// Parses a checksum file, returning each line that is neither a comment
+/// nor blank with its number, counted from 1, and its entry, or `None` if
+/// the line is improperly formatted.
+pub fn parse(text: &[u8]) -> Vec<(usize, Option<Entry>)> {
+    let mut reversed = None;
+    let mut lines: Vec<&[u8]> = text.split(|&b| b == b'\n').collect();
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            if line.starts_with(b"#") {
+                return None;
+            }
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                return None;
+            }
+            Some((i + 1, parse_line(line, &mut reversed)))
+        })
+        .collect()
+}
+
+/// Parses one line, as `split_3()` of coreutils.
+///
+/// `reversed` records whether the file uses the reversed form, once its
+/// first entry has settled it.
+fn parse_line(line: &[u8], reversed: &mut Option<bool>) -> Option<Entry> {
+    let is_white = |b: u8| b == b' ' || b == b'\t';
+    let mut rest = &line[line.iter().position(|&b| !is_white(b))?..];
+    let escaped = rest[0] == b'\\';
+    if escaped {
+        rest = &rest[1..];
+    }
+    if let Some(tag) = tag_of(rest) {
+        return parse_tagged(tag, &rest[tag.len()..], escaped);
+    }
+    let end = rest.iter().position(|&b| is_white(b))?;
+    let digest = from_hex(std::str::from_utf8(&rest[..end]).ok()?)?;
+    if digest.is_empty() {
+        return None;
+    }
+    rest = &rest[end + 1..];
+    let mut binary = false;
+    if rest.len() == 1 || !matches!(rest.first(), Some(b' ' | b'*')) {
+        if *reversed == Some(false) {
+            return None;
+        }
+        *reversed = Some(true);
+    } else if *reversed != Some(true) {
+        *reversed = Some(false);
+        binary = rest[0] == b'*';
+        rest = &rest[1..];
+    }
+    let name = if escaped { unescape(rest)? } else { rest.to_vec() };
+    if name.is_empty() {
+        return None;
+    }
+    Some(Entry { digest, binary, name, tag: None })
+}
+
+/// Returns the algorithm tag at the start of `line` if it is a BSD line,
+/// that is a tag followed by `(`, with or without a space.
+///
+/// Tags are made of letters, digits, `-`, `/` and `_`, and must not be
+/// all hex digits, so that a GNU line is never taken for a BSD one.
+fn tag_of(line: &[u8]) -> Option<&str> {
+    let len = line
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || matches!(b, b'-' | b'/' | b'_')))?;
+    let tag = &line[..len];
+    let after = &line[len..];
+    if tag.is_empty() || tag.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    if !(after.starts_with(b"(") || after.starts_with(b" (")) {
+        return None;
+    }
+    std::str::from_utf8(tag).ok()
+}
+
+/// Parses the rest of a BSD line after its tag, as `bsd_split_3()` of
+/// coreutils: the name runs from the `(` to the last `)`, which is
+/// followed by `=` and the digest, with optional spaces around the `=`.
+fn parse_tagged(tag: &str, rest: &[u8], escaped: bool) -> Option<Entry> {
+    let rest = rest.strip_prefix(b" ").unwrap_or(rest).strip_prefix(b"(")?;
+    let close = rest.iter().rposition(|&b| b == b')')?;
+    let name = if escaped { unescape(&rest[..close])? } else { rest[..close].to_vec() };
+    let is_white = |b: &u8| *b == b' ' || *b == b'\t';
+    let after = &rest[close + 1..];
+    let after = &after[after.iter().position(|b| !is_white(b))?..];
+    let after = after.strip_prefix(b"=")?;
+    let hex = &after[after.iter().position(|b| !is_white(b))?..];
+    let digest = from_hex(std::str::from_utf8(hex).ok()?)?;
+    if digest.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(Entry { digest, binary: true, name, tag: Some(tag.to_owned()) })
+}
+
+/// Undoes the escaping of [`format_line`], returning `None` for any other
+/// backslash sequence.
+fn unescape(name: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(name.len());
+    let mut bytes = name.iter();
+    while let Some(&b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        out.push(match bytes.next()? {
+            b'\\' => b'\\',
+            b'n' => b'\n',
+            b'r' => b'\r',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    fn entry(name: &[u8], binary: bool) -> Option<Entry> {
+        Some(Entry { digest: from_hex(ABC).unwrap(), binary, name: name.to_vec(), tag: None })
+    }
+
+    fn tagged(tag: &str, name: &[u8]) -> Option<Entry> {
+        let tag = Some(tag.to_owned());
+        Some(Entry { digest: from_hex(ABC).unwrap(), binary: true, name: name.to_vec(), tag })
+    }
+
+    #[test]
+    fn test_format_line() {
+        let digest = from_hex(ABC).unwrap();
+        assert_eq!(format_line(&digest, b"abc", false), format!("{}  abc\n", ABC).into_bytes());
+        assert_eq!(format_line(&digest, b" *x", true), format!("{} * *x\n", ABC).into_bytes());
+        assert_eq!(
+            format_line(&digest, b"a\\b\nc\rd", true),
+            format!("\\{} *a\\\\b\\nc\\rd\n", ABC).into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_parse_coreutils_output() {
+        // The names and escapes sha256sum (GNU coreutils) 9.1 writes
+        let text = format!(
+            "{0}  plain\n\\{0} *back\\\\slash\n\\{0}  new\\nline\n\\{0} *cr\\rname\n{0} * lead\n",
+            ABC
+        );
+        assert_eq!(
+            parse(text.as_bytes()),
+            [
+                (1, entry(b"plain", false)),
+                (2, entry(b"back\\slash", true)),
+                (3, entry(b"new\nline", false)),
+                (4, entry(b"cr\rname", true)),
+                (5, entry(b" lead", true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let digest = from_hex(ABC).unwrap();
+        for name in [&b"x"[..], b"\\", b"\n", b"a\\n", b"\\\\r\r\n", b" x ", b"*", b"\xff"] {
+            for binary in [false, true] {
+                let line = format_line(&digest, name, binary);
+                assert_eq!(parse(&line), [(1, entry(name, binary))]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let text = format!(
+            "# comment\n{0}  a\r\n\n\r\n  {0}  b\n{1}  c\n\t{0}\t*d\n#{0}  e\n{0}  f ",
+            ABC,
+            ABC.to_uppercase()
+        );
+        assert_eq!(
+            parse(text.as_bytes()),
+            [
+                (2, entry(b"a", false)),
+                (5, entry(b"b", false)),
+                (6, entry(b"c", false)),
+                (7, entry(b"d", true)),
+                (9, entry(b"f ", false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_reversed() {
+        let text = format!("{0} a\n{0}  b\n{0} *c\n", ABC);
+        assert_eq!(
+            parse(text.as_bytes()),
+            [(1, entry(b"a", false)), (2, entry(b" b", false)), (3, entry(b"*c", false))]
+        );
+        let text = format!("{0}  a\n{0} b\n{0}\tc\n{0}\t c\n", ABC);
+        assert_eq!(
+            parse(text.as_bytes()),
+            [(1, entry(b"a", false)), (2, None), (3, None), (4, entry(b"c", false))]
+        );
+        assert_eq!(parse(format!("{}  \n", ABC).as_bytes()), [(1, entry(b" ", false))]);
+    }
+
+    #[test]
+    fn test_format_tagged_line() {
+        let digest = from_hex(ABC).unwrap();
+        assert_eq!(
+            format_tagged_line("SHA256", &digest, b"abc"),
+            format!("SHA256 (abc) = {}\n", ABC).into_bytes()
+        );
+        assert_eq!(
+            format_tagged_line("SHA256", &digest, b"a\\b\nc"),
+            format!("\\SHA256 (a\\\\b\\nc) = {}\n", ABC).into_bytes()
+        );
+        for name in [&b"x"[..], b"(x)", b"a) = b", b"\\)\n", b" x ", b"\xff"] {
+            let line = format_tagged_line("SHA512/256", &digest, name);
+            assert_eq!(parse(&line), [(1, tagged("SHA512/256", name))]);
+        }
+    }
+
+    #[test]
+    fn test_parse_tagged() {
+        // The forms of sha256sum --tag, shasum --tag and openssl dgst
+        let text = format!(
+            "SHA256 (plain) = {0}\n\\SHA256 (new\\nline) = {0}\r\nSHA2-256(pa(re)n = x)= {0}\n\
+             \tBLAKE2b-256 ( lead) = {1}\n{0}  gnu\nMD5 (x)={0}\n",
+            ABC,
+            ABC.to_uppercase()
+        );
+        assert_eq!(
+            parse(text.as_bytes()),
+            [
+                (1, tagged("SHA256", b"plain")),
+                (2, tagged("SHA256", b"new\nline")),
+                (3, tagged("SHA2-256", b"pa(re)n = x")),
+                (4, tagged("BLAKE2b-256", b" lead")),
+                (5, entry(b"gnu", false)),
+                (6, tagged("MD5", b"x")),
+            ]
+        );
+        // Tagged lines leave the choice of the reversed form to GNU lines
+        let text = format!("SHA256 (a) = {0}\n{0} b\n{0}  c\n", ABC);
+        assert_eq!(
+            parse(text.as_bytes()),
+            [(1, tagged("SHA256", b"a")), (2, entry(b"b", false)), (3, entry(b" c", false))]
+        );
+        // All-hex tags are GNU digests
+        assert_eq!(parse(format!("{} (x)", ABC).as_bytes()), [(1, entry(b"(x)", false))]);
+    }
+
+    #[test]
+    fn test_parse_tagged_malformed() {
+        for line in [
+            format!("SHA256 (x) {}", ABC),
+            format!("SHA256 (x = {}", ABC),
+            "SHA256 (x) = ".to_owned(),
+            format!("SHA256 () = {}", ABC),
+            format!("SHA256 (x) = {} ", ABC),
+            format!("SHA256 (x) = {}z", ABC),
+            format!("SHA256  (x) = {}", ABC),
+            format!("\\SHA256 (x\\y) = {}", ABC),
+        ] {
+            assert_eq!(parse(line.as_bytes()), [(1, None)], "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        for line in [
+            " ".to_owned(),
+            "garbage".to_owned(),
+            ABC.to_owned(),
+            format!("{} ", ABC),
+            format!("{}x  a", ABC),
+            format!("{}  a", &ABC[1..]),
+            format!("\\{}  a\\x", ABC),
+            format!("\\{}  a\\", ABC),
+            format!("\\\\{}  a", ABC),
+        ] {
+            assert_eq!(parse(line.as_bytes()), [(1, None)], "{:?}", line);
+        }
+        assert!(parse(b"").is_empty());
+        assert!(parse(b"\n\r\n# only comments\n").is_empty());
+    }
+}
@@ -0,0 +1,653 @@
+//! Argon2 memory-hard password hashing.
+//!
+//! Pure Rust implementation of Argon2d, Argon2i and Argon2id following
+//! RFC 9106, built on the BLAKE2b core. The memory is split into
+//! `parallelism` lanes of 1 KiB blocks, each lane into four segments, and
+//! lanes fill their segment of a slice concurrently on the rayon thread
+//! pool before synchronizing.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 1024 bytes, mixed by a BLAKE2b-round permutation with
+//!   the BlaMka multiplication
+//! - **Addressing**: data-dependent (Argon2d), data-independent (Argon2i),
+//!   or independent for the first half of the first pass only (Argon2id)
+//! - **Versions**: 0x13 (current, the default) and 0x10 (no XOR on later passes)
+//! - **Encoding**: PHC strings such as
+//!   `$argon2id$v=19$m=65536,t=3,p=4$<salt>$<hash>` ([`phc_encode`])
+
+use super::{Blake2Params, Blake2b, ParameterError};
+use crate::utils::{b64_decode, b64_encode, parse_decimal};
+use rayon::prelude::*;
+
+/// Number of 64-bit words in a 1 KiB block.
+const BLOCK_WORDS: usize = 128;
+
+/// Number of segments per lane, and of synchronization points per pass.
+const SYNC_POINTS: usize = 4;
+
+/// Current version of the algorithm (1.3).
+pub const VERSION: u32 = 0x13;
+
+/// Original version of the algorithm (1.0), still found in old hashes.
+pub const VERSION_10: u32 = 0x10;
+
+type Block = [u64; BLOCK_WORDS];
+
+/// Argon2 variant, selecting how reference blocks are chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argon2Variant {
+    /// Data-dependent addressing: fastest, but open to side channels.
+    Argon2d = 0,
+    /// Data-independent addressing: side-channel resistant.
+    Argon2i = 1,
+    /// Argon2i for the first half pass, then Argon2d (recommended).
+    Argon2id = 2,
+}
+
+impl Argon2Variant {
+    /// Name used in PHC strings, such as `"argon2id"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Argon2Variant::Argon2d => "argon2d",
+            Argon2Variant::Argon2i => "argon2i",
+            Argon2Variant::Argon2id => "argon2id",
+        }
+    }
+
+    /// Looks a variant up by its PHC name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "argon2d" => Some(Argon2Variant::Argon2d),
+            "argon2i" => Some(Argon2Variant::Argon2i),
+            "argon2id" => Some(Argon2Variant::Argon2id),
+            _ => None,
+        }
+    }
+}
+
+/// Argon2 cost parameters and optional inputs.
+///
+/// Build them from [`Argon2Params::new`] and override fields with struct
+/// update syntax:
+///
+/// ```ignore
+/// let params = Argon2Params { t_cost: 1, m_cost: 2 * 1024 * 1024, ..Argon2Params::new(variant) };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Variant of the algorithm.
+    pub variant: Argon2Variant,
+    /// Version number, [`VERSION`] or [`VERSION_10`].
+    pub version: u32,
+    /// Number of passes over the memory.
+    pub t_cost: u32,
+    /// Memory size in KiB, at least 8 per lane.
+    pub m_cost: u32,
+    /// Number of lanes, computed in parallel.
+    pub parallelism: u32,
+    /// Optional secret value (pepper), empty when unused.
+    pub secret: Vec<u8>,
+    /// Optional associated data, empty when unused.
+    pub associated_data: Vec<u8>,
+}
+
+impl Argon2Params {
+    /// Creates parameters for `variant` with 3 passes over 64 MiB in 4
+    /// lanes, the defaults of argon2-cffi, and no secret or associated data.
+    pub fn new(variant: Argon2Variant) -> Self {
+        Argon2Params {
+            variant,
+            version: VERSION,
+            t_cost: 3,
+            m_cost: 65536,
+            parallelism: 4,
+            secret: Vec::new(),
+            associated_data: Vec::new(),
+        }
+    }
+
+    /// Checks the parameters and the salt and tag lengths against the
+    /// limits of RFC 9106.
+    fn check(&self, salt_len: usize, tag_len: usize) -> Result<(), ParameterError> {
+        let message = if self.version != VERSION && self.version != VERSION_10 {
+            format!("unsupported version {:#x}", self.version)
+        } else if self.t_cost == 0 {
+            "time_cost must be at least 1".to_owned()
+        } else if self.parallelism == 0 || self.parallelism >= 1 << 24 {
+            "parallelism must be between 1 and 2^24 - 1".to_owned()
+        } else if (self.m_cost as u64) < 8 * self.parallelism as u64 {
+            format!("memory_cost must be at least 8 * parallelism = {} KiB", 8 * self.parallelism)
+        } else if salt_len < 8 {
+            "salt must be at least 8 bytes".to_owned()
+        } else if tag_len < 4 {
+            "hash length must be at least 4 bytes".to_owned()
+        } else if [salt_len, tag_len, self.secret.len(), self.associated_data.len()]
+            .iter()
+            .any(|&len| len as u64 > u32::MAX as u64)
+        {
+            "inputs must be shorter than 2^32 bytes".to_owned()
+        } else {
+            return Ok(());
+        };
+        Err(ParameterError { algorithm: "Argon2", message })
+    }
+}
+
+/// Hashes `password` with `salt`, filling `out` with the tag.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the parameters are out of range, the salt
+/// is shorter than 8 bytes, `out` is shorter than 4 bytes, or the memory
+/// cannot be allocated.
+pub fn argon2(
+    params: &Argon2Params,
+    password: &[u8],
+    salt: &[u8],
+    out: &mut [u8],
+) -> Result<(), ParameterError> {
+    params.check(salt.len(), out.len())?;
+    if password.len() as u64 > u32::MAX as u64 {
+        return Err(ParameterError {
+            algorithm: "Argon2",
+            message: "inputs must be shorter than 2^32 bytes".to_owned(),
+        });
+    }
+    let lanes = params.parallelism as usize;
+    // Memory is rounded down to a multiple of 4 * lanes blocks
+    let segment_len = params.m_cost as usize / (SYNC_POINTS * lanes);
+    let lane_len = segment_len * SYNC_POINTS;
+    let mut memory: Vec<Block> = Vec::new();
+    memory.try_reserve_exact(lane_len * lanes).map_err(|_| ParameterError {
+        algorithm: "Argon2",
+        message: format!("not enough memory for {} KiB", lane_len * lanes),
+    })?;
+    memory.resize(lane_len * lanes, [0u64; BLOCK_WORDS]);
+
+    // H0 covers the requested memory size, not the rounded-down one
+    let h0 = initial_hash(params, password, salt, out.len());
+    memory.par_chunks_mut(lane_len).enumerate().for_each(|(lane, blocks)| {
+        for (index, block) in blocks[..2].iter_mut().enumerate() {
+            let mut input = h0.to_vec();
+            input.extend_from_slice(&(index as u32).to_le_bytes());
+            input.extend_from_slice(&(lane as u32).to_le_bytes());
+            let mut bytes = [0u8; 1024];
+            hash_long(&input, &mut bytes);
+            for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(8)) {
+                *word = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+        }
+    });
+
+    let filler = Filler { params, lanes, lane_len, segment_len };
+    for pass in 0..params.t_cost as usize {
+        for slice in 0..SYNC_POINTS {
+            // Each lane writes its own segment and only reads blocks outside
+            // the segments being filled, except for the ones of its own lane
+            let mut segments = Vec::with_capacity(lanes);
+            let mut others = Vec::with_capacity(lanes);
+            for blocks in memory.chunks_mut(lane_len) {
+                let (before, rest) = blocks.split_at_mut(slice * segment_len);
+                let (segment, after) = rest.split_at_mut(segment_len);
+                segments.push(segment);
+                others.push((&*before, &*after));
+            }
+            segments.into_par_iter().enumerate().for_each(|(lane, segment)| {
+                filler.fill_segment(&others, pass, slice, lane, segment)
+            });
+        }
+    }
+
+    let mut last = memory[lane_len - 1];
+    for lane in 1..lanes {
+        for (word, other) in last.iter_mut().zip(&memory[(lane + 1) * lane_len - 1]) {
+            *word ^= other;
+        }
+    }
+    let bytes: Vec<u8> = last.iter().flat_map(|word| word.to_le_bytes()).collect();
+    hash_long(&bytes, out);
+    Ok(())
+}
+
+/// Computes H0, the 64-byte digest of every parameter and input.
+fn initial_hash(
+    params: &Argon2Params,
+    password: &[u8],
+    salt: &[u8],
+    tag_len: usize,
+) -> Vec<u8> {
+    let mut h = Blake2b::new();
+    for value in [
+        params.parallelism,
+        tag_len as u32,
+        params.m_cost,
+        params.t_cost,
+        params.version,
+        params.variant as u32,
+    ] {
+        h.update(&value.to_le_bytes());
+    }
+    for input in [password, salt, &params.secret, &params.associated_data] {
+        h.update(&(input.len() as u32).to_le_bytes());
+        h.update(input);
+    }
+    h.digest()
+}
+
+/// H', the variable-length hash: BLAKE2b chained 32 bytes at a time.
+fn hash_long(input: &[u8], out: &mut [u8]) {
+    let blake2b = |len: usize, parts: &[&[u8]]| {
+        let mut h = Blake2b::with_params(&Blake2Params::new(len)).expect("length is 1 to 64");
+        for part in parts {
+            h.update(part);
+        }
+        h.digest()
+    };
+    let prefix = (out.len() as u32).to_le_bytes();
+    if out.len() <= 64 {
+        out.copy_from_slice(&blake2b(out.len(), &[&prefix, input]));
+        return;
+    }
+    let mut v = blake2b(64, &[&prefix, input]);
+    let mut written = 0;
+    while out.len() - written > 64 {
+        out[written..written + 32].copy_from_slice(&v[..32]);
+        written += 32;
+        v = blake2b((out.len() - written).min(64), &[&v]);
+    }
+    out[written..].copy_from_slice(&v);
+}
+
+/// Fills the segments of one slice, with the geometry of the memory.
+struct Filler<'a> {
+    params: &'a Argon2Params,
+    lanes: usize,
+    lane_len: usize,
+    segment_len: usize,
+}
+
+impl Filler<'_> {
+    /// Fills `segment`, the `slice`-th segment of `lane`, during `pass`.
+    ///
+    /// `others` holds, for every lane, the blocks before and after the
+    /// segments of this slice.
+    fn fill_segment(
+        &self,
+        others: &[(&[Block], &[Block])],
+        pass: usize,
+        slice: usize,
+        lane: usize,
+        segment: &mut [Block],
+    ) {
+        let independent = match self.params.variant {
+            Argon2Variant::Argon2d => false,
+            Argon2Variant::Argon2i => true,
+            Argon2Variant::Argon2id => pass == 0 && slice < SYNC_POINTS / 2,
+        };
+        let mut input = [0u64; BLOCK_WORDS];
+        let mut addresses = [0u64; BLOCK_WORDS];
+        if independent {
+            input[..6].copy_from_slice(&[
+                pass as u64,
+                lane as u64,
+                slice as u64,
+                (self.lane_len * self.lanes) as u64,
+                self.params.t_cost as u64,
+                self.params.variant as u64,
+            ]);
+        }
+        let start = if pass == 0 && slice == 0 { 2 } else { 0 };
+        let segment_start = slice * self.segment_len;
+        let block_at = |segment: &[Block], ref_lane: usize, index: usize| -> Block {
+            let (before, after) = others[ref_lane];
+            if index < segment_start {
+                before[index]
+            } else if index >= segment_start + self.segment_len {
+                after[index - segment_start - self.segment_len]
+            } else {
+                debug_assert_eq!(ref_lane, lane);
+                segment[index - segment_start]
+            }
+        };
+
+        for i in start..self.segment_len {
+            let index = segment_start + i;
+            let prev_index = if index == 0 { self.lane_len - 1 } else { index - 1 };
+            let prev = block_at(segment, lane, prev_index);
+            let pseudo_rand = if independent {
+                if i % BLOCK_WORDS == 0 || i == start {
+                    input[6] += 1;
+                    addresses = compress(&compress(&input));
+                }
+                addresses[i % BLOCK_WORDS]
+            } else {
+                prev[0]
+            };
+
+            let ref_lane = if pass == 0 && slice == 0 {
+                lane
+            } else {
+                (pseudo_rand >> 32) as usize % self.lanes
+            };
+            let ref_index = self.reference_index(pass, slice, i, ref_lane == lane, pseudo_rand);
+            let reference = block_at(segment, ref_lane, ref_index);
+
+            let mut mixed = prev;
+            for (word, r) in mixed.iter_mut().zip(&reference) {
+                *word ^= r;
+            }
+            let mixed = compress(&mixed);
+            let current = &mut segment[i];
+            if pass > 0 && self.params.version == VERSION {
+                for (word, m) in current.iter_mut().zip(&mixed) {
+                    *word ^= m;
+                }
+            } else {
+                *current = mixed;
+            }
+        }
+    }
+
+    /// Maps the low 32 bits of `pseudo_rand` to a block of the reference
+    /// lane, skewed towards recent blocks (RFC 9106, section 3.4.2).
+    fn reference_index(
+        &self,
+        pass: usize,
+        slice: usize,
+        i: usize,
+        same_lane: bool,
+        pseudo_rand: u64,
+    ) -> usize {
+        // Blocks of the current segment are only available in our own lane,
+        // and the block right before the current one is never a reference
+        let area = if pass == 0 {
+            if same_lane {
+                slice * self.segment_len + i - 1
+            } else {
+                slice * self.segment_len - (i == 0) as usize
+            }
+        } else if same_lane {
+            self.lane_len - self.segment_len + i - 1
+        } else {
+            self.lane_len - self.segment_len - (i == 0) as usize
+        } as u64;
+        let x = (pseudo_rand & 0xffff_ffff).pow(2) >> 32;
+        let relative = area - 1 - ((area * x) >> 32);
+        let start = if pass == 0 || slice == SYNC_POINTS - 1 {
+            0
+        } else {
+            (slice + 1) * self.segment_len
+        };
+        (start + relative as usize) % self.lane_len
+    }
+}
+
+/// The compression function G applied to `r = X ^ Y`: the permutation P
+/// on the rows then the columns of `r`, XORed back with `r`.
+fn compress(r: &Block) -> Block {
+    let mut q = *r;
+    for row in q.chunks_exact_mut(16) {
+        permute(row.try_into().unwrap());
+    }
+    for column in 0..8 {
+        let mut v = [0u64; 16];
+        for (k, pair) in v.chunks_exact_mut(2).enumerate() {
+            pair.copy_from_slice(&q[16 * k + 2 * column..16 * k + 2 * column + 2]);
+        }
+        permute(&mut v);
+        for (k, pair) in v.chunks_exact(2).enumerate() {
+            q[16 * k + 2 * column..16 * k + 2 * column + 2].copy_from_slice(pair);
+        }
+    }
+    for (word, x) in q.iter_mut().zip(r) {
+        *word ^= x;
+    }
+    q
+}
+
+/// The permutation P: one BLAKE2b round without message words, using the
+/// BlaMka addition `a + b + 2 * lo(a) * lo(b)`.
+fn permute(v: &mut [u64; 16]) {
+    fn blamka(a: u64, b: u64) -> u64 {
+        let product = (a & 0xffff_ffff).wrapping_mul(b & 0xffff_ffff);
+        a.wrapping_add(b).wrapping_add(product.wrapping_mul(2))
+    }
+    fn gb(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+        v[a] = blamka(v[a], v[b]);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = blamka(v[c], v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = blamka(v[a], v[b]);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = blamka(v[c], v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+    gb(v, 0, 4, 8, 12);
+    gb(v, 1, 5, 9, 13);
+    gb(v, 2, 6, 10, 14);
+    gb(v, 3, 7, 11, 15);
+    gb(v, 0, 5, 10, 15);
+    gb(v, 1, 6, 11, 12);
+    gb(v, 2, 7, 8, 13);
+    gb(v, 3, 4, 9, 14);
+}
+
+/// Encodes a hash as a PHC string:
+/// `$argon2id$v=19$m=<m_cost>,t=<t_cost>,p=<parallelism>$<salt>$<hash>`.
+///
+/// The secret and associated data are not part of the string.
+pub fn phc_encode(params: &Argon2Params, salt: &[u8], hash: &[u8]) -> String {
+    format!(
+        "${}$v={}$m={},t={},p={}${}${}",
+        params.variant.name(),
+        params.version,
+        params.m_cost,
+        params.t_cost,
+        params.parallelism,
+        b64_encode(salt),
+        b64_encode(hash)
+    )
+}
+
+/// Parses a PHC string into its parameters, salt and hash.
+///
+/// A missing `v=` field stands for version 0x10, as in the reference
+/// implementation.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is not a well-formed Argon2
+/// PHC string.
+pub fn phc_decode(text: &str) -> Result<(Argon2Params, Vec<u8>, Vec<u8>), ParameterError> {
+    let invalid = || ParameterError {
+        algorithm: "Argon2",
+        message: "invalid Argon2 PHC string".to_owned(),
+    };
+    let mut fields = text.strip_prefix('$').ok_or_else(invalid)?.split('$');
+    let variant = fields.next().and_then(Argon2Variant::from_name).ok_or_else(invalid)?;
+    let mut params = Argon2Params::new(variant);
+    let mut field = fields.next().ok_or_else(invalid)?;
+    params.version = match field.strip_prefix("v=") {
+        Some(version) => {
+            field = fields.next().ok_or_else(invalid)?;
+            parse_decimal(version).ok_or_else(invalid)?
+        }
+        None => VERSION_10,
+    };
+    let mut costs = field.split(',');
+    let targets = [
+        ("m=", &mut params.m_cost),
+        ("t=", &mut params.t_cost),
+        ("p=", &mut params.parallelism),
+    ];
+    for (name, value) in targets {
+        let cost = costs.next().and_then(|c| c.strip_prefix(name)).ok_or_else(invalid)?;
+        *value = parse_decimal(cost).ok_or_else(invalid)?;
+    }
+    let salt = fields.next().and_then(b64_decode).ok_or_else(invalid)?;
+    let hash = fields.next().and_then(b64_decode).ok_or_else(invalid)?;
+    if costs.next().is_some() || fields.next().is_some() {
+        return Err(invalid());
+    }
+    params.check(salt.len(), hash.len())?;
+    Ok((params, salt, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_hex;
+
+    /// RFC 9106, section 5: 32 bytes of 0x01 as password, 16 bytes of 0x02
+    /// as salt, 8 bytes of 0x03 as secret and 12 bytes of 0x04 as data.
+    fn rfc9106(variant: Argon2Variant) -> String {
+        let params = Argon2Params {
+            t_cost: 3,
+            m_cost: 32,
+            parallelism: 4,
+            secret: vec![3; 8],
+            associated_data: vec![4; 12],
+            ..Argon2Params::new(variant)
+        };
+        let mut tag = [0u8; 32];
+        argon2(&params, &[1; 32], &[2; 16], &mut tag).unwrap();
+        to_hex(&tag)
+    }
+
+    #[test]
+    fn test_argon2_rfc9106() {
+        assert_eq!(
+            rfc9106(Argon2Variant::Argon2d),
+            "512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb"
+        );
+        assert_eq!(
+            rfc9106(Argon2Variant::Argon2i),
+            "c814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8"
+        );
+        assert_eq!(
+            rfc9106(Argon2Variant::Argon2id),
+            "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+        );
+    }
+
+    #[test]
+    fn test_argon2_reference_vectors() {
+        // From the test suite of the reference implementation: "password"
+        // and "somesalt" with 2 passes over 64 MiB in one lane
+        let cases = [
+            (
+                Argon2Variant::Argon2i,
+                VERSION,
+                "c1628832147d9720c5bd1cfd61367078729f6dfb6f8fea9ff98158e0d7816ed0",
+            ),
+            (
+                Argon2Variant::Argon2id,
+                VERSION,
+                "09316115d5cf24ed5a15a31a3ba326e5cf32edc24702987c02b6566f61913cf7",
+            ),
+            (
+                Argon2Variant::Argon2i,
+                VERSION_10,
+                "f6c4db4a54e2a370627aff3db6176b94a2a209a62c8e36152711802f7b30c694",
+            ),
+        ];
+        for (variant, version, expected) in cases {
+            let params = Argon2Params {
+                version,
+                t_cost: 2,
+                m_cost: 65536,
+                parallelism: 1,
+                ..Argon2Params::new(variant)
+            };
+            let mut tag = [0u8; 32];
+            argon2(&params, b"password", b"somesalt", &mut tag).unwrap();
+            assert_eq!(to_hex(&tag), expected);
+        }
+    }
+
+    #[test]
+    fn test_argon2_unaligned_memory() {
+        // Memory sizes that are not a multiple of 4 * lanes blocks, checked
+        // against libargon2, OpenSSL and cryptography: "password" and
+        // "somesalt" with 3 passes
+        let cases = [
+            (33, 1, [
+                "d99454e152f6b8bc3eb793c759f6bb9e37e5cd00cea2bfb69d75a1840925546a",
+                "503cb843106f8f114bb6454fe0d0ae2a3ceaa55005188cf5bd606dfc4a1cc283",
+                "2adfab4679a66b5b23f86e15c7072fcd824bdbeecb1ee149f72dd57c6c285de2",
+            ]),
+            (17, 2, [
+                "d08be9f9f8662a02d0a4488805f6250319f75147afc89315baa9e54f668de179",
+                "c7d2e83c906503967e3e93bad1c16c0ccea464675fcbe24b9f74fb07798dfbe9",
+                "c2b5457039fa998b1ca2a62b8592961af5c80aac5a16e0fdc9f13c0f2618d884",
+            ]),
+            (100, 4, [
+                "b29f98009a3f679442a1ed67290248c4b785ec9151d2bd342aa89d96758f11e6",
+                "8ffa9056a38c0d1c4e8dce06f7d43d35473b21eb19a72ee94eed5196e8e5477a",
+                "5fabef4c4352c818c316aff38899dd61cce601406cbcbe2ff9da5eb35527432a",
+            ]),
+        ];
+        let variants = [Argon2Variant::Argon2d, Argon2Variant::Argon2i, Argon2Variant::Argon2id];
+        for (m_cost, parallelism, expected) in cases {
+            for (variant, expected) in variants.into_iter().zip(expected) {
+                let params = Argon2Params {
+                    t_cost: 3,
+                    m_cost,
+                    parallelism,
+                    ..Argon2Params::new(variant)
+                };
+                let mut tag = [0u8; 32];
+                argon2(&params, b"password", b"somesalt", &mut tag).unwrap();
+                assert_eq!(to_hex(&tag), expected, "m={} p={}", m_cost, parallelism);
+            }
+        }
+    }
+
+    #[test]
+    fn test_argon2_phc_round_trip() {
+        let params =
+            Argon2Params { t_cost: 2, m_cost: 256, ..Argon2Params::new(Argon2Variant::Argon2id) };
+        let mut hash = [0u8; 32];
+        argon2(&params, b"password", b"somesalt", &mut hash).unwrap();
+        let encoded = phc_encode(&params, b"somesalt", &hash);
+        assert!(encoded.starts_with("$argon2id$v=19$m=256,t=2,p=4$c29tZXNhbHQ$"));
+        assert_eq!(phc_decode(&encoded).unwrap(), (params, b"somesalt".to_vec(), hash.to_vec()));
+    }
+
+    #[test]
+    fn test_argon2_phc_rejects_malformed() {
+        for text in [
+            "",
+            "argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2x$v=19$m=256,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2id$v=19$t=2,m=256,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2id$v=19$m=256,t=02,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ=$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2id$v=19$m=256,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA$",
+            "$argon2id$v=18$m=256,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+            "$argon2id$v=19$m=4,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA",
+        ] {
+            assert!(phc_decode(text).is_err(), "{}", text);
+        }
+        let old = phc_decode("$argon2i$m=256,t=2,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA").unwrap();
+        assert_eq!(old.0.version, VERSION_10);
+    }
+
+    #[test]
+    fn test_argon2_rejects_invalid_parameters() {
+        let mut tag = [0u8; 32];
+        let params = Argon2Params::new(Argon2Variant::Argon2id);
+        for bad in [
+            Argon2Params { t_cost: 0, ..params.clone() },
+            Argon2Params { parallelism: 0, ..params.clone() },
+            Argon2Params { m_cost: 31, ..params.clone() },
+            Argon2Params { version: 0x12, ..params.clone() },
+        ] {
+            let err = argon2(&bad, b"password", b"somesalt", &mut tag).unwrap_err();
+            assert_eq!(err.algorithm, "Argon2");
+        }
+        assert!(argon2(&params, b"password", b"short", &mut tag).is_err());
+        assert!(argon2(&params, b"password", b"somesalt", &mut tag[..3]).is_err());
+    }
+}
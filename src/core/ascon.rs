@@ -0,0 +1,336 @@
+//! Ascon-Hash256 and Ascon-XOF128.
+//!
+//! Pure Rust implementation following NIST SP 800-232. Both functions are
+//! sponges over the 320-bit Ascon permutation with a rate of 64 bits: each
+//! 8-byte block is XORed into the first state word, which is also read out
+//! 8 bytes at a time, and the full 12-round permutation runs between
+//! blocks. They differ only in their initial value and output length.
+//!
+//! # Algorithm Details
+//!
+//! - **Permutation**: Ascon-p[12], five 64-bit words
+//! - **Rate**: 64 bits (8 bytes)
+//! - **Digest size**: 32 bytes for Ascon-Hash256, any length for Ascon-XOF128
+//! - **Byte order**: little-endian words, as in SP 800-232 (the earlier
+//!   Ascon v1.2 submission was big-endian, so its digests differ)
+//! - **Padding**: a 0x01 byte after the message, then zeros
+
+use super::{Digest, LengthOverflowError, Xof};
+use crate::utils::to_hex;
+
+/// Initial value of Ascon-Hash256 (SP 800-232, section 5.1).
+const HASH256_IV: u64 = 0x0000_0801_00cc_0002;
+
+/// Initial value of Ascon-XOF128 (SP 800-232, section 5.2).
+const XOF128_IV: u64 = 0x0000_0800_00cc_0003;
+
+/// Constants added to the third word, one per round of Ascon-p[12].
+const ROUND_CONSTANTS: [u64; 12] = [
+    0xf0, 0xe1, 0xd2, 0xc3, 0xb4, 0xa5, 0x96, 0x87, 0x78, 0x69, 0x5a, 0x4b,
+];
+
+/// Ascon-p[12]: constant addition, the bit-sliced 5-bit S-box and the
+/// linear diffusion layer, twelve times.
+fn permute(x: &mut [u64; 5]) {
+    for constant in ROUND_CONSTANTS {
+        x[2] ^= constant;
+
+        // Substitution layer
+        x[0] ^= x[4];
+        x[4] ^= x[3];
+        x[2] ^= x[1];
+        let t = [
+            !x[0] & x[1],
+            !x[1] & x[2],
+            !x[2] & x[3],
+            !x[3] & x[4],
+            !x[4] & x[0],
+        ];
+        for i in 0..5 {
+            x[i] ^= t[(i + 1) % 5];
+        }
+        x[1] ^= x[0];
+        x[0] ^= x[4];
+        x[3] ^= x[2];
+        x[2] = !x[2];
+
+        // Linear diffusion layer
+        x[0] ^= x[0].rotate_right(19) ^ x[0].rotate_right(28);
+        x[1] ^= x[1].rotate_right(61) ^ x[1].rotate_right(39);
+        x[2] ^= x[2].rotate_right(1) ^ x[2].rotate_right(6);
+        x[3] ^= x[3].rotate_right(10) ^ x[3].rotate_right(17);
+        x[4] ^= x[4].rotate_right(7) ^ x[4].rotate_right(41);
+    }
+}
+
+/// Ascon sponge with a 64-bit rate, shared by both functions.
+#[derive(Clone)]
+struct Sponge {
+    state: [u64; 5],
+    buffer: [u8; 8],       // Fixed 8-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+}
+
+impl Sponge {
+    /// Creates a sponge whose state is the permuted initial value.
+    fn new(iv: u64) -> Self {
+        let mut state = [iv, 0, 0, 0, 0];
+        permute(&mut state);
+        Sponge { state, buffer: [0u8; 8], buffer_len: 0 }
+    }
+
+    /// Absorbs data, permuting after each complete 8-byte block.
+    fn absorb(&mut self, data: &[u8]) {
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(8 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 8 {
+                self.state[0] ^= u64::from_le_bytes(self.buffer);
+                permute(&mut self.state);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 8-byte blocks directly from input
+        while offset + 8 <= data.len() {
+            self.state[0] ^= u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+            permute(&mut self.state);
+            offset += 8;
+        }
+
+        // Buffer any remaining bytes (< 8)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Pads a copy of the state and fills `out` with output blocks.
+    fn squeeze(&self, out: &mut [u8]) {
+        let mut state = self.state;
+        let mut block = [0u8; 8];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x01;
+        state[0] ^= u64::from_le_bytes(block);
+
+        for chunk in out.chunks_mut(8) {
+            permute(&mut state);
+            chunk.copy_from_slice(&state[0].to_le_bytes()[..chunk.len()]);
+        }
+    }
+}
+
+/// Ascon-Hash256 hasher, with a 32-byte digest.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct AsconHash256 {
+    sponge: Sponge,
+}
+
+impl AsconHash256 {
+    /// Creates a new Ascon-Hash256 hasher.
+    pub fn new() -> Self {
+        AsconHash256 { sponge: Sponge::new(HASH256_IV) }
+    }
+
+    /// Feeds data into the hasher. Ascon-Hash256 has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        self.sponge.squeeze(&mut result);
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 32] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (32 for Ascon-Hash256).
+    pub fn digest_size() -> usize {
+        32
+    }
+
+    /// Returns the block size (rate) in bytes (8 for Ascon).
+    pub fn block_size() -> usize {
+        8
+    }
+}
+
+impl Default for AsconHash256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for AsconHash256 {
+    type Output = [u8; 32];
+
+    /// Never fails: Ascon-Hash256 has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        AsconHash256::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        AsconHash256::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        AsconHash256::block_size()
+    }
+}
+
+/// Ascon-XOF128 extendable-output hasher.
+///
+/// Shorter outputs are prefixes of longer ones. Cloning captures the full
+/// streaming state, so a clone can be finalized or extended independently
+/// of the original.
+#[derive(Clone)]
+pub struct AsconXof128 {
+    sponge: Sponge,
+}
+
+impl AsconXof128 {
+    /// Creates a new Ascon-XOF128 hasher.
+    pub fn new() -> Self {
+        AsconXof128 { sponge: Sponge::new(XOF128_IV) }
+    }
+
+    /// Feeds data into the hasher. Ascon-XOF128 has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        let mut result = vec![0u8; length];
+        self.sponge.squeeze(&mut result);
+        result
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the block size (rate) in bytes (8 for Ascon).
+    pub fn block_size() -> usize {
+        8
+    }
+}
+
+impl Default for AsconXof128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Xof for AsconXof128 {
+    /// Never fails: Ascon-XOF128 has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.sponge.squeeze(out);
+    }
+
+    fn block_size(&self) -> usize {
+        AsconXof128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_hex(data: &[u8]) -> String {
+        let mut hasher = AsconHash256::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    fn xof_hex(data: &[u8], length: usize) -> String {
+        let mut hasher = AsconXof128::new();
+        hasher.update(data);
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_ascon_hash256_kat() {
+        // LWC_HASH_KAT_128_256, Count = 1 (empty message)
+        assert_eq!(
+            hash_hex(b""),
+            "0b3be5850f2f6b98caf29f8fdea89b64a1fa70aa249b8f839bd53baa304d92b2"
+        );
+    }
+
+    #[test]
+    fn test_ascon_xof128_kat() {
+        // LWC_XOF_KAT_128_512, Count = 1 (empty message, 32 output bytes)
+        assert_eq!(
+            xof_hex(b"", 32),
+            "473d5e6164f58b39dfd84aacdb8ae42ec2d91fed33388ee0d960d9b3993295c6"
+        );
+        // Shorter outputs are prefixes of longer ones
+        assert!(xof_hex(b"abc", 100).starts_with(&xof_hex(b"abc", 13)));
+    }
+
+    #[test]
+    fn test_ascon_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..100u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 3, 7, 8, 9, 64] {
+            let mut hash = AsconHash256::new();
+            let mut xof = AsconXof128::new();
+            for chunk in data.chunks(step) {
+                hash.update(chunk);
+                xof.update(chunk);
+            }
+            assert_eq!(hash.hexdigest(), hash_hex(&data), "step {}", step);
+            assert_eq!(xof.hexdigest(40), xof_hex(&data, 40), "step {}", step);
+        }
+        // The two functions are separated by their initial values
+        assert_ne!(hash_hex(&data), xof_hex(&data, 32));
+    }
+}
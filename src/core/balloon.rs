@@ -0,0 +1,230 @@
+//! Balloon memory-hard password hashing.
+//!
+//! Pure Rust implementation of Balloon hashing (Boneh, Corrigan-Gibbs and
+//! Schechter, 2016) over any [`Digest`], with the encodings of the
+//! authors' sample implementation: integers are 8-byte little-endian and
+//! every hash call but the index derivation is prefixed by a counter.
+//!
+//! ```text
+//! buf[0] = H(cnt++, password, salt),  buf[m] = H(cnt++, buf[m - 1])
+//! repeat time_cost times, for each m:
+//!     buf[m] = H(cnt++, buf[m - 1], buf[m])
+//!     repeat 3 times (i):
+//!         other = H(cnt++, salt, H(t, m, i)) mod space_cost
+//!         buf[m] = H(cnt++, buf[m], buf[other])
+//! output = buf[space_cost - 1]
+//! ```
+//!
+//! Balloon-M runs `parallelism` such instances with the salts
+//! `salt || 1`, `salt || 2`, ... on the rayon thread pool, XORs their
+//! outputs and hashes them as `H(password, salt, xor)`.
+//!
+//! # Algorithm Details
+//!
+//! - **Space cost**: number of digest-sized blocks in the buffer
+//! - **Time cost**: number of mixing rounds over the buffer
+//! - **Delta**: 3 pseudorandom dependencies per block
+//! - **Output size**: the digest size of the hash
+
+use super::{Digest, ParameterError};
+use rayon::prelude::*;
+
+/// Number of pseudorandom blocks mixed into each block per round.
+const DELTA: u64 = 3;
+
+/// Hashes `password` with `salt` using Balloon, or Balloon-M when
+/// `parallelism` is above 1.
+///
+/// `hasher` must be in its initial state. Balloon-M with one instance is
+/// not the same as Balloon, which is why `parallelism` 1 selects the
+/// single-buffer algorithm.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if a cost or `parallelism` is 0, or if the
+/// buffer cannot be allocated.
+pub fn balloon<D: Digest>(
+    hasher: D,
+    password: &[u8],
+    salt: &[u8],
+    space_cost: u64,
+    time_cost: u64,
+    parallelism: u32,
+) -> Result<Vec<u8>, ParameterError> {
+    let error = |message: String| ParameterError { algorithm: "Balloon", message };
+    if space_cost == 0 || time_cost == 0 {
+        return Err(error("space_cost and time_cost must be at least 1".to_owned()));
+    }
+    if parallelism == 0 {
+        return Err(error("parallelism must be at least 1".to_owned()));
+    }
+    let buffer_len = usize::try_from(space_cost)
+        .ok()
+        .and_then(|blocks| blocks.checked_mul(hasher.digest_size()))
+        .ok_or_else(|| error("space_cost is too large".to_owned()))?;
+    if parallelism == 1 {
+        return single(&hasher, password, salt, space_cost, time_cost, buffer_len);
+    }
+
+    // Digest is Send but not Sync, so every instance gets its own hasher
+    let instances: Vec<(u64, D)> =
+        (1..=parallelism as u64).map(|m| (m, hasher.clone())).collect();
+    let outputs = instances
+        .into_par_iter()
+        .map(|(m, hasher)| {
+            let salt = [salt, &m.to_le_bytes()].concat();
+            single(&hasher, password, &salt, space_cost, time_cost, buffer_len)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut combined = vec![0u8; hasher.digest_size()];
+    for output in outputs {
+        for (byte, other) in combined.iter_mut().zip(output) {
+            *byte ^= other;
+        }
+    }
+    Ok(hash(&hasher, &[password, salt, &combined]).as_ref().to_vec())
+}
+
+/// Runs one Balloon instance over a buffer of `buffer_len` bytes.
+fn single<D: Digest>(
+    hasher: &D,
+    password: &[u8],
+    salt: &[u8],
+    space_cost: u64,
+    time_cost: u64,
+    buffer_len: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    let block_len = hasher.digest_size();
+    let mut buf = Vec::new();
+    buf.try_reserve_exact(buffer_len).map_err(|_| ParameterError {
+        algorithm: "Balloon",
+        message: format!("not enough memory for {} blocks", space_cost),
+    })?;
+    buf.resize(buffer_len, 0u8);
+    let block = |index: u64| index as usize * block_len..(index as usize + 1) * block_len;
+
+    // Step 1: expand the input into the buffer
+    let mut cnt = 0u64;
+    let mut counted = |parts: &[&[u8]]| {
+        let counter = cnt.to_le_bytes();
+        let mut input = vec![&counter[..]];
+        input.extend_from_slice(parts);
+        let output = hash(hasher, &input);
+        cnt += 1;
+        output
+    };
+    buf[block(0)].copy_from_slice(counted(&[password, salt]).as_ref());
+    for m in 1..space_cost {
+        let output = counted(&[&buf[block(m - 1)]]);
+        buf[block(m)].copy_from_slice(output.as_ref());
+    }
+
+    // Step 2: mix the buffer
+    for t in 0..time_cost {
+        for m in 0..space_cost {
+            let prev = if m == 0 { space_cost - 1 } else { m - 1 };
+            let output = counted(&[&buf[block(prev)], &buf[block(m)]]);
+            buf[block(m)].copy_from_slice(output.as_ref());
+            for i in 0..DELTA {
+                let ints = [t.to_le_bytes(), m.to_le_bytes(), i.to_le_bytes()].concat();
+                let index = hash(hasher, &[&ints]);
+                let other = reduce(counted(&[salt, index.as_ref()]).as_ref(), space_cost);
+                let output = counted(&[&buf[block(m)], &buf[block(other)]]);
+                buf[block(m)].copy_from_slice(output.as_ref());
+            }
+        }
+    }
+
+    // Step 3: extract the last block
+    Ok(buf[block(space_cost - 1)].to_vec())
+}
+
+/// Hashes the concatenation of `parts` with a copy of `hasher`.
+fn hash<D: Digest>(hasher: &D, parts: &[&[u8]]) -> D::Output {
+    let mut h = hasher.clone();
+    for part in parts {
+        h.try_update(part).expect("inputs fit in memory, so below the length limit");
+    }
+    h.digest()
+}
+
+/// Reads `bytes` as a little-endian integer and reduces it modulo `modulus`.
+fn reduce(bytes: &[u8], modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    bytes.iter().rev().fold(0u128, |acc, &b| (acc << 8 | b as u128) % modulus) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Sha256;
+    use crate::utils::to_hex;
+
+    fn balloon_hex(password: &[u8], salt: &[u8], s: u64, t: u64, p: u32) -> String {
+        to_hex(&balloon(Sha256::new(), password, salt, s, t, p).unwrap())
+    }
+
+    #[test]
+    fn test_balloon_sample_vectors() {
+        assert_eq!(
+            balloon_hex(b"hunter42", b"examplesalt", 1024, 3, 1),
+            "716043dff777b44aa7b88dcbab12c078abecfac9d289c5b5195967aa63440dfb"
+        );
+        assert_eq!(
+            balloon_hex(b"", b"salt", 3, 3, 1),
+            "5f02f8206f9cd212485c6bdf85527b698956701ad0852106f94b94ee94577378"
+        );
+        assert_eq!(
+            balloon_hex(b"password", b"", 3, 3, 1),
+            "20aa99d7fe3f4df4bd98c655c5480ec98b143107a331fd491deda885c4d6a6cc"
+        );
+        assert_eq!(
+            balloon_hex(b"\0", b"\0", 3, 3, 1),
+            "4fc7e302ffa29ae0eac31166cee7a552d1d71135f4e0da66486fb68a749b73a4"
+        );
+        assert_eq!(
+            balloon_hex(b"password", b"salt", 1, 1, 1),
+            "eefda4a8a75b461fa389c1dcfaf3e9dfacbc26f81f22e6f280d15cc18c417545"
+        );
+    }
+
+    #[test]
+    fn test_balloon_m_sample_vectors() {
+        assert_eq!(
+            balloon_hex(b"hunter42", b"examplesalt", 1024, 3, 4),
+            "1832bd8e5cbeba1cb174a13838095e7e66508e9bf04c40178990adbc8ba9eb6f"
+        );
+        assert_eq!(
+            balloon_hex(b"", b"salt", 3, 3, 2),
+            "f8767fe04059cef67b4427cda99bf8bcdd983959dbd399a5e63ea04523716c23"
+        );
+        assert_eq!(
+            balloon_hex(b"password", b"", 3, 3, 3),
+            "bcad257eff3d1090b50276514857e60db5d0ec484129013ef3c88f7d36e438d6"
+        );
+        assert_eq!(
+            balloon_hex(b"\0", b"\0", 3, 3, 4),
+            "8a665611e40710ba1fd78c181549c750f17c12e423c11930ce997f04c7153e0c"
+        );
+        assert_eq!(
+            balloon_hex(b"password", b"salt", 1, 1, 16),
+            "a67b383bb88a282aef595d98697f90820adf64582a4b3627c76b7da3d8bae915"
+        );
+    }
+
+    #[test]
+    fn test_balloon_reduce() {
+        assert_eq!(reduce(&[0x01, 0x02], 500), 0x0201 - 500);
+        assert_eq!(reduce(&[0xff; 32], 1), 0);
+        // 2^256 - 1 = 3 * 5 * 17 * 257 * ... so it is divisible by 65537
+        assert_eq!(reduce(&[0xff; 32], 65537), 0);
+    }
+
+    #[test]
+    fn test_balloon_rejects_invalid_parameters() {
+        for (s, t, p) in [(0, 1, 1), (1, 0, 1), (1, 1, 0)] {
+            let err = balloon(Sha256::new(), b"pw", b"salt", s, t, p).unwrap_err();
+            assert_eq!(err.algorithm, "Balloon");
+        }
+    }
+}
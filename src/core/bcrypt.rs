@@ -0,0 +1,194 @@
+//! bcrypt password hashing.
+//!
+//! Pure Rust implementation of the OpenBSD bcrypt scheme (Provos and
+//! Mazières, 1999): the expensive [`Blowfish::eks_setup`] key schedule
+//! keyed by the password and a 16-byte salt, then 64 encryptions of the
+//! text `"OrpheanBeholderScryDoubt"`.
+//!
+//! # Algorithm Details
+//!
+//! - **Cost**: 4 to 31, for 2^cost key schedule rounds
+//! - **Password**: NUL-terminated, only the first 72 bytes are used
+//! - **Output**: 23 of the 24 ciphertext bytes
+//! - **Encoding**: `$2b$<cost>$<22-char salt><31-char hash>` in bcrypt's
+//!   own Base64 alphabet ([`encode`], [`decode`])
+//!
+//! The `$2a$`, `$2b$` and `$2y$` prefixes are computed the same way, as in
+//! current OpenBSD and the `bcrypt` package: they only differ by bugs of
+//! older implementations with long or non-ASCII passwords.
+
+use super::ParameterError;
+use super::blowfish::Blowfish;
+use crate::utils::{b64_decode_with, b64_encode_with};
+
+/// Lowest accepted cost.
+pub const MIN_COST: u32 = 4;
+
+/// Highest accepted cost.
+pub const MAX_COST: u32 = 31;
+
+/// Salt size in bytes.
+pub const SALT_LEN: usize = 16;
+
+/// Size of the hash in bytes.
+pub const HASH_LEN: usize = 23;
+
+/// Number of password bytes taken into account, NUL terminator included.
+pub const MAX_PASSWORD_LEN: usize = 72;
+
+/// bcrypt's Base64 alphabet, which sorts like the encoded bytes.
+const ALPHABET: &[u8; 64] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Versions accepted in hash strings.
+const VERSIONS: [&str; 3] = ["2a", "2b", "2y"];
+
+/// Hashes `password` with a 16-byte `salt` and 2^`cost` rounds.
+///
+/// Password bytes beyond the 72nd are ignored.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `cost` is not between 4 and 31.
+pub fn bcrypt(
+    password: &[u8],
+    salt: &[u8; SALT_LEN],
+    cost: u32,
+) -> Result<[u8; HASH_LEN], ParameterError> {
+    if !(MIN_COST..=MAX_COST).contains(&cost) {
+        return Err(ParameterError {
+            algorithm: "bcrypt",
+            message: format!("rounds must be between {} and {}", MIN_COST, MAX_COST),
+        });
+    }
+    let mut key = password[..password.len().min(MAX_PASSWORD_LEN)].to_vec();
+    if key.len() < MAX_PASSWORD_LEN {
+        key.push(0);
+    }
+    let cipher = Blowfish::eks_setup(cost, salt, &key);
+
+    let mut ctext = [0u32; 6];
+    for (word, chunk) in ctext.iter_mut().zip(b"OrpheanBeholderScryDoubt".chunks_exact(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for _ in 0..64 {
+        for pair in ctext.chunks_exact_mut(2) {
+            (pair[0], pair[1]) = cipher.encrypt_block(pair[0], pair[1]);
+        }
+    }
+    let mut hash = [0u8; HASH_LEN];
+    for (chunk, word) in hash.chunks_mut(4).zip(ctext) {
+        chunk.copy_from_slice(&word.to_be_bytes()[..chunk.len()]);
+    }
+    Ok(hash)
+}
+
+/// Encodes a hash as a modular crypt string such as
+/// `$2b$12$<salt><hash>`; `version` is `"2a"`, `"2b"` or `"2y"`.
+pub fn encode(version: &str, cost: u32, salt: &[u8; SALT_LEN], hash: &[u8; HASH_LEN]) -> String {
+    format!(
+        "${}${:02}${}{}",
+        version,
+        cost,
+        b64_encode_with(salt, ALPHABET),
+        b64_encode_with(hash, ALPHABET)
+    )
+}
+
+/// A parsed bcrypt hash string.
+pub type Decoded = (&'static str, u32, [u8; SALT_LEN], [u8; HASH_LEN]);
+
+/// Parses a `$2a$`, `$2b$` or `$2y$` hash string into its version, cost,
+/// salt and hash.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is not a well-formed bcrypt
+/// hash with a cost between 4 and 31.
+pub fn decode(text: &str) -> Result<Decoded, ParameterError> {
+    let invalid = || ParameterError {
+        algorithm: "bcrypt",
+        message: "invalid bcrypt hash".to_owned(),
+    };
+    let mut fields = text.strip_prefix('$').ok_or_else(invalid)?.split('$');
+    let version = fields.next().ok_or_else(invalid)?;
+    let version = VERSIONS.into_iter().find(|&v| v == version).ok_or_else(invalid)?;
+    let cost = fields.next().filter(|c| c.len() == 2).ok_or_else(invalid)?;
+    if !cost.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let cost: u32 = cost.parse().map_err(|_| invalid())?;
+    let rest = fields.next().filter(|r| r.len() == 53 && r.is_ascii()).ok_or_else(invalid)?;
+    if fields.next().is_some() || !(MIN_COST..=MAX_COST).contains(&cost) {
+        return Err(invalid());
+    }
+    let salt = b64_decode_with(&rest[..22], ALPHABET).ok_or_else(invalid)?;
+    let hash = b64_decode_with(&rest[22..], ALPHABET).ok_or_else(invalid)?;
+    Ok((version, cost, salt.try_into().unwrap(), hash.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recomputes a hash string from its own salt and cost.
+    fn rehash(password: &[u8], stored: &str) -> String {
+        let (version, cost, salt, _) = decode(stored).unwrap();
+        encode(version, cost, &salt, &bcrypt(password, &salt, cost).unwrap())
+    }
+
+    #[test]
+    fn test_bcrypt_openwall_vectors() {
+        // From the test suite of Openwall's crypt_blowfish
+        let cases: [(&[u8], &str); 5] = [
+            (b"U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"),
+            (b"U*U*", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK"),
+            (b"U*U*U", "$2a$05$XXXXXXXXXXXXXXXXXXXXXOAcXxm9kjPGEMsLznoKqmqw7tc8WCx4a"),
+            (b"", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.7uG0VCzI2bS7j6ymqJi9CdcdxiRTWNy"),
+            (
+                b"0123456789abcdefghijklmnopqrstuvwxyz\
+                  ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789chars after 72 are ignored",
+                "$2a$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui",
+            ),
+        ];
+        for (password, expected) in cases {
+            assert_eq!(rehash(password, expected), expected);
+        }
+    }
+
+    #[test]
+    fn test_bcrypt_2y_non_ascii() {
+        assert_eq!(
+            rehash(b"\xff\xff\xa3", "$2y$05$/OK.fbVrR/bpIqNJ5ianF.CE5elHaaO4EbggVDjb8P19RukzXSM3e"),
+            "$2y$05$/OK.fbVrR/bpIqNJ5ianF.CE5elHaaO4EbggVDjb8P19RukzXSM3e"
+        );
+    }
+
+    #[test]
+    fn test_bcrypt_truncates_at_72_bytes() {
+        let salt = [7u8; SALT_LEN];
+        let long = [b'a'; 100];
+        let at_limit = bcrypt(&long[..72], &salt, 4).unwrap();
+        assert_eq!(bcrypt(&long, &salt, 4).unwrap(), at_limit);
+        assert_ne!(bcrypt(&long[..71], &salt, 4).unwrap(), at_limit);
+    }
+
+    #[test]
+    fn test_bcrypt_rejects_invalid_input() {
+        assert!(bcrypt(b"", &[0; SALT_LEN], 3).is_err());
+        assert!(bcrypt(b"", &[0; SALT_LEN], 32).is_err());
+        let valid = "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+        assert!(decode(valid).is_ok());
+        for text in [
+            "",
+            &valid[1..],
+            &valid.replace("$2b$", "$2x$"),
+            &valid.replace("$05$", "$5$"),
+            &valid.replace("$05$", "$32$"),
+            &valid[..59],
+            &format!("{}W", valid),
+            &valid.replace("C.E5", "CCE5"),
+        ] {
+            assert!(decode(text).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! bcrypt_pbkdf key derivation.
+//!
+//! Pure Rust implementation of OpenBSD's `bcrypt_pbkdf`, the KDF that
+//! encrypts OpenSSH private keys (`openssh-key-v1`). It follows PBKDF2
+//! with SHA-512 pre-hashing and a bcrypt-like PRF, `bcrypt_hash`: the
+//! [`Blowfish`] key schedule salted by `SHA512(salt || counter)` and keyed
+//! by `SHA512(password)` (64 rounds alternating salt and key), which then
+//! encrypts `"OxychromaticBlowfishSwatDynamite"` 64 times.
+//!
+//! # Algorithm Details
+//!
+//! - **PRF output**: 32 bytes, Blowfish words written little-endian
+//! - **Rounds**: each block is the XOR of `rounds` chained PRF outputs,
+//!   the salt of each round being the SHA-512 of the previous output
+//! - **Output size**: 1 to 1024 bytes, interleaved: byte `i` of block `k`
+//!   lands at `i * stride + k`, so every block feeds the whole key
+
+use super::blowfish::Blowfish;
+use super::{ParameterError, Sha512};
+
+/// Size of one `bcrypt_hash` output in bytes.
+pub const HASH_LEN: usize = 32;
+
+/// Largest output, in bytes.
+pub const MAX_KEY_LEN: usize = HASH_LEN * HASH_LEN;
+
+/// Largest salt, in bytes.
+pub const MAX_SALT_LEN: usize = 1 << 20;
+
+/// Text encrypted by `bcrypt_hash`.
+const MAGIC: &[u8; HASH_LEN] = b"OxychromaticBlowfishSwatDynamite";
+
+/// Returns the SHA-512 digest of the concatenation of `parts`.
+fn sha512(parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.try_update(part).expect("inputs fit in memory, so below the length limit");
+    }
+    hasher.digest()
+}
+
+/// The bcrypt_pbkdf PRF over pre-hashed password and salt.
+fn bcrypt_hash(sha2pass: &[u8; 64], sha2salt: &[u8; 64]) -> [u8; HASH_LEN] {
+    let mut cipher = Blowfish::initial();
+    cipher.expand_key_salted(sha2salt, sha2pass);
+    for _ in 0..64 {
+        cipher.expand_key(sha2salt);
+        cipher.expand_key(sha2pass);
+    }
+
+    let mut cdata = [0u32; 8];
+    for (word, chunk) in cdata.iter_mut().zip(MAGIC.chunks_exact(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for _ in 0..64 {
+        for pair in cdata.chunks_exact_mut(2) {
+            (pair[0], pair[1]) = cipher.encrypt_block(pair[0], pair[1]);
+        }
+    }
+    let mut out = [0u8; HASH_LEN];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(cdata) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+/// Derives `key_len` bytes from `password` and `salt` with `rounds`
+/// iterations, as OpenBSD's `bcrypt_pbkdf`.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `password` or `salt` is empty, `salt` is
+/// longer than 2^20 bytes, `key_len` is not between 1 and 1024, or
+/// `rounds` is 0.
+pub fn bcrypt_pbkdf(
+    password: &[u8],
+    salt: &[u8],
+    key_len: usize,
+    rounds: u32,
+) -> Result<Vec<u8>, ParameterError> {
+    let error = |message: &str| ParameterError {
+        algorithm: "bcrypt_pbkdf",
+        message: message.to_owned(),
+    };
+    if password.is_empty() {
+        return Err(error("password must not be empty"));
+    }
+    if salt.is_empty() || salt.len() > MAX_SALT_LEN {
+        return Err(error("salt must be between 1 and 2^20 bytes long"));
+    }
+    if !(1..=MAX_KEY_LEN).contains(&key_len) {
+        return Err(error("key_len must be between 1 and 1024"));
+    }
+    if rounds == 0 {
+        return Err(error("rounds must be at least 1"));
+    }
+
+    let stride = key_len.div_ceil(HASH_LEN);
+    let amt = key_len.div_ceil(stride);
+    let sha2pass = sha512(&[password]);
+    let mut key = vec![0u8; key_len];
+    for block in 0..stride {
+        let counter = (block as u32 + 1).to_be_bytes();
+        let mut tmp = bcrypt_hash(&sha2pass, &sha512(&[salt, &counter]));
+        let mut out = tmp;
+        for _ in 1..rounds {
+            tmp = bcrypt_hash(&sha2pass, &sha512(&[&tmp]));
+            out.iter_mut().zip(tmp).for_each(|(o, t)| *o ^= t);
+        }
+        for (i, byte) in out.iter().take(amt).enumerate() {
+            match key.get_mut(i * stride + block) {
+                Some(dest) => *dest = *byte,
+                None => break,
+            }
+        }
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_hex;
+
+    #[test]
+    fn test_bcrypt_pbkdf_vectors() {
+        // Known answers shared by the OpenSSH-compatible implementations
+        let cases: [(&[u8], &[u8], u32, &str); 4] = [
+            (
+                b"password",
+                b"salt",
+                4,
+                "5bbf0cc293587f1c3635555c27796598d47e579071bf427e9d8fbe842aba34d9",
+            ),
+            (b"password", b"\x00", 4, "c12b566235eee04c212598970a579a67"),
+            (b"\x00", b"salt", 4, "6051be18c2f4f82cbf0efee5471b4bb9"),
+            (b"pass\x00wor", b"sa\x00l", 4, "c2bffd9db38f6569efef4372f4de83c0"),
+        ];
+        for (password, salt, rounds, expected) in cases {
+            let key = bcrypt_pbkdf(password, salt, expected.len() / 2, rounds).unwrap();
+            assert_eq!(to_hex(&key), expected);
+        }
+    }
+
+    #[test]
+    fn test_bcrypt_pbkdf_interleaving() {
+        // The first block alone makes a 32-byte key; longer keys spread it
+        // over every stride-th byte rather than using it as a prefix
+        let first = bcrypt_pbkdf(b"password", b"salt", 32, 2).unwrap();
+        let two = bcrypt_pbkdf(b"password", b"salt", 64, 2).unwrap();
+        assert_ne!(two[..32], first[..]);
+        assert_eq!(two.iter().step_by(2).copied().collect::<Vec<u8>>(), first);
+        let three = bcrypt_pbkdf(b"password", b"salt", 65, 2).unwrap();
+        assert_eq!(three.iter().step_by(3).copied().collect::<Vec<u8>>(), first[..22]);
+        let second: Vec<u8> = two.iter().skip(1).step_by(2).take(22).copied().collect();
+        assert_eq!(three.iter().skip(1).step_by(3).copied().collect::<Vec<u8>>(), second);
+    }
+
+    #[test]
+    fn test_bcrypt_pbkdf_rejects_invalid_input() {
+        assert!(bcrypt_pbkdf(b"", b"salt", 32, 4).is_err());
+        assert!(bcrypt_pbkdf(b"password", b"", 32, 4).is_err());
+        assert!(bcrypt_pbkdf(b"password", b"salt", 0, 4).is_err());
+        assert!(bcrypt_pbkdf(b"password", b"salt", MAX_KEY_LEN + 1, 4).is_err());
+        assert!(bcrypt_pbkdf(b"password", b"salt", 32, 0).is_err());
+        assert_eq!(bcrypt_pbkdf(b"password", b"salt", MAX_KEY_LEN, 1).unwrap().len(), 1024);
+    }
+}
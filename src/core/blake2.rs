@@ -0,0 +1,161 @@
+//! Parameters shared by the BLAKE2 hash functions.
+//!
+//! BLAKE2 (RFC 7693) folds a parameter block into its initial state, so the
+//! digest size, key, salt and personalization string all change the whole
+//! output rather than just truncating or prefixing it. [`Blake2Params`]
+//! describes that block independently of the variant; each variant checks
+//! the limits that apply to it. [`Blake2Node`] lets the tree modes and
+//! BLAKE2X drive either variant.
+
+use super::ParameterError;
+
+/// Message word permutations, one per round (rounds past 10 wrap around).
+pub(crate) const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Parameters of a BLAKE2 instance.
+///
+/// Start from [`Blake2Params::new`] and set the other fields with struct
+/// update syntax:
+///
+/// ```ignore
+/// let params = Blake2Params { key: b"secret".to_vec(), ..Blake2Params::new(32) };
+/// ```
+///
+/// The tree fields only matter when hashing in a tree mode; their defaults
+/// describe sequential hashing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blake2Params {
+    /// Output size in bytes.
+    pub digest_size: usize,
+    /// Secret key for keyed hashing (MAC mode), empty for plain hashing.
+    pub key: Vec<u8>,
+    /// Salt, zero-padded to the variant's salt size.
+    pub salt: Vec<u8>,
+    /// Personalization string, zero-padded like the salt.
+    pub person: Vec<u8>,
+    /// Maximum number of children per node, 0 for unlimited.
+    pub fanout: u8,
+    /// Maximum tree depth, 255 for unlimited.
+    pub depth: u8,
+    /// Maximum leaf size in bytes, 0 for unlimited.
+    pub leaf_size: u32,
+    /// Offset of the node within its level.
+    pub node_offset: u64,
+    /// Level of the node, 0 for leaves.
+    pub node_depth: u8,
+    /// Size of the inner hashes in bytes.
+    pub inner_size: u8,
+    /// Whether this node is the last one of its level.
+    pub last_node: bool,
+}
+
+impl Blake2Params {
+    /// Creates parameters for an unkeyed sequential hash of `digest_size`
+    /// bytes, with no salt and no personalization.
+    pub fn new(digest_size: usize) -> Self {
+        Blake2Params {
+            digest_size,
+            key: Vec::new(),
+            salt: Vec::new(),
+            person: Vec::new(),
+            fanout: 1,
+            depth: 1,
+            leaf_size: 0,
+            node_offset: 0,
+            node_depth: 0,
+            inner_size: 0,
+            last_node: false,
+        }
+    }
+
+    /// Checks the parameters against the limits of a variant whose digest
+    /// and key are at most `max_size` bytes, whose salt and personalization
+    /// are `salt_size` bytes and whose node offset is `offset_bits` wide.
+    ///
+    /// Error messages follow hashlib's. Unlike hashlib, a depth of 0 is
+    /// accepted along with a fanout of 0, as in BLAKE2X output nodes.
+    pub(crate) fn check(
+        &self,
+        algorithm: &'static str,
+        max_size: usize,
+        salt_size: usize,
+        offset_bits: u32,
+    ) -> Result<(), ParameterError> {
+        let message = if self.digest_size == 0 || self.digest_size > max_size {
+            format!("digest_size must be between 1 and {} bytes", max_size)
+        } else if self.key.len() > max_size {
+            format!("maximum key length is {} bytes", max_size)
+        } else if self.salt.len() > salt_size {
+            format!("maximum salt length is {} bytes", salt_size)
+        } else if self.person.len() > salt_size {
+            format!("maximum person length is {} bytes", salt_size)
+        } else if self.depth == 0 && self.fanout != 0 {
+            "depth must be between 1 and 255".to_owned()
+        } else if offset_bits < 64 && self.node_offset >> offset_bits != 0 {
+            "node_offset is too large".to_owned()
+        } else if self.inner_size as usize > max_size {
+            format!("inner_size must be between 0 and {}", max_size)
+        } else {
+            return Ok(());
+        };
+        Err(ParameterError { algorithm, message })
+    }
+}
+
+/// A sequential BLAKE2 function usable as a node of the BLAKE2 tree modes.
+///
+/// Implemented by [`super::Blake2b`] and [`super::Blake2s`].
+pub trait Blake2Node: Clone + Send + Sync {
+    /// Block size in bytes.
+    const BLOCK_SIZE: usize;
+    /// Maximum digest, key and inner hash size in bytes.
+    const MAX_DIGEST_SIZE: usize;
+    /// Number of leaves of the parallel variant (BLAKE2bp or BLAKE2sp).
+    const PARALLEL_LANES: usize;
+    /// Name of the parallel variant, used in error messages.
+    const PARALLEL_NAME: &'static str;
+    /// Value of the BLAKE2X `xof_length` field for an unknown output
+    /// length; known lengths must be below it.
+    const XOF_UNKNOWN_LENGTH: u64;
+    /// Name of the BLAKE2X variant (BLAKE2Xb or BLAKE2Xs), used in error
+    /// messages.
+    const XOF_NAME: &'static str;
+
+    /// Creates a node from `params` that outputs `output_size` bytes.
+    ///
+    /// The key length is always part of the parameter block, but the key
+    /// block is only hashed when `absorb_key` is true: in the tree modes,
+    /// leaves hash it and the root does not. `output_size` can differ from
+    /// `params.digest_size`, as for leaves, which output full-size inner
+    /// hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if `params` is out of range for the
+    /// variant.
+    fn node(
+        params: &Blake2Params,
+        absorb_key: bool,
+        output_size: usize,
+    ) -> Result<Self, ParameterError>;
+
+    /// Feeds data into the node.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the output of the node for the data fed so far.
+    fn digest(&self) -> Vec<u8>;
+
+    /// Resets the node to its initial state, keeping its parameters.
+    fn reset_node(&mut self);
+}
@@ -0,0 +1,407 @@
+//! BLAKE2b cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following RFC 7693. BLAKE2b is optimized for
+//! 64-bit platforms and supports keyed hashing, salts and personalization
+//! strings through its parameter block (see [`Blake2Params`]).
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 1024 bits (128 bytes), little-endian 64-bit words
+//! - **Digest size**: 1 to 64 bytes, part of the parameter block
+//! - **Key**: up to 64 bytes; **salt** and **personalization**: up to 16
+//! - **Rounds**: 12
+
+use super::blake2::{Blake2Node, Blake2Params, SIGMA};
+use super::{Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// BLAKE2b hasher state.
+///
+/// The last block is kept buffered until `digest()`, since it has to be
+/// compressed with the finalization flag. Cloning captures the full
+/// streaming state, keyed or not, so a clone can be finalized or extended
+/// independently of the original.
+#[derive(Clone)]
+pub struct Blake2b {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffer_len: usize,
+    /// Number of bytes compressed so far, the key block included.
+    counter: u128,
+    digest_size: usize,
+    /// State right after the parameter block, restored by `reset()`.
+    initial: [u64; 8],
+    /// Key padded to a full block, or `None` for plain hashing.
+    key_block: Option<[u8; 128]>,
+    /// Whether the final block also gets the last-node flag.
+    last_node: bool,
+}
+
+impl Blake2b {
+    /// Maximum digest size in bytes.
+    pub const MAX_DIGEST_SIZE: usize = 64;
+
+    /// Maximum key length in bytes.
+    pub const MAX_KEY_SIZE: usize = 64;
+
+    /// Salt length in bytes.
+    pub const SALT_SIZE: usize = 16;
+
+    /// Personalization string length in bytes.
+    pub const PERSON_SIZE: usize = 16;
+
+    /// BLAKE2b initialization vector (same as the SHA-512 initial hash value).
+    const IV: [u64; 8] = [
+        0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+    ];
+
+    /// Creates a new unkeyed BLAKE2b hasher with a 64-byte digest.
+    pub fn new() -> Self {
+        Self::with_params(&Blake2Params::new(Self::MAX_DIGEST_SIZE))
+            .expect("default parameters are valid")
+    }
+
+    /// Creates a new BLAKE2b hasher from `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if the digest size is not between 1 and 64
+    /// bytes, if the key, salt or personalization string is too long, or if
+    /// a tree parameter is out of range.
+    pub fn with_params(params: &Blake2Params) -> Result<Self, ParameterError> {
+        Self::node(params, true, params.digest_size)
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Complete blocks are compressed right away, except the last one, which
+    /// stays buffered until more data arrives or the digest is computed.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        if self.buffer_len > 0 {
+            let take = data.len().min(128 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if data.is_empty() {
+                return;
+            }
+            self.counter += 128;
+            Self::compress(&mut self.state, &self.buffer, self.counter, None);
+            self.buffer_len = 0;
+        }
+
+        // Keep at least one byte for the final, flagged block.
+        while data.len() > 128 {
+            self.counter += 128;
+            let block = data[..128].try_into().unwrap();
+            Self::compress(&mut self.state, block, self.counter, None);
+            data = &data[128..];
+        }
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    /// BLAKE2b compression function F.
+    ///
+    /// `counter` is the number of bytes hashed including this block, and
+    /// `last` marks the final block: `Some(last_node)` sets the finalization
+    /// flag, plus the last-node flag when `last_node` is true.
+    fn compress(state: &mut [u64; 8], block: &[u8; 128], counter: u128, last: Option<bool>) {
+        let mut m = [0u64; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(state);
+        v[8..].copy_from_slice(&Self::IV);
+        v[12] ^= counter as u64;
+        v[13] ^= (counter >> 64) as u64;
+        if let Some(last_node) = last {
+            v[14] = !v[14];
+            if last_node {
+                v[15] = !v[15];
+            }
+        }
+
+        for sigma in SIGMA.iter().cycle().take(12) {
+            Self::g(&mut v, 0, 4, 8, 12, m[sigma[0]], m[sigma[1]]);
+            Self::g(&mut v, 1, 5, 9, 13, m[sigma[2]], m[sigma[3]]);
+            Self::g(&mut v, 2, 6, 10, 14, m[sigma[4]], m[sigma[5]]);
+            Self::g(&mut v, 3, 7, 11, 15, m[sigma[6]], m[sigma[7]]);
+            Self::g(&mut v, 0, 5, 10, 15, m[sigma[8]], m[sigma[9]]);
+            Self::g(&mut v, 1, 6, 11, 12, m[sigma[10]], m[sigma[11]]);
+            Self::g(&mut v, 2, 7, 8, 13, m[sigma[12]], m[sigma[13]]);
+            Self::g(&mut v, 3, 4, 9, 14, m[sigma[14]], m[sigma[15]]);
+        }
+
+        for i in 0..8 {
+            state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+
+    /// Mixing function G.
+    #[inline(always)]
+    fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+
+    /// Returns the digest of the data fed so far, `digest_size` bytes long.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut state = self.state;
+        let mut block = self.buffer;
+        block[self.buffer_len..].fill(0);
+        let counter = self.counter + self.buffer_len as u128;
+        Self::compress(&mut state, &block, counter, Some(self.last_node));
+
+        let mut out = [0u8; 64];
+        for (bytes, word) in out.chunks_exact_mut(8).zip(state.iter()) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out[..self.digest_size].to_vec()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, keeping its parameters and
+    /// key.
+    pub fn reset(&mut self) {
+        self.state = self.initial;
+        self.counter = 0;
+        match self.key_block {
+            Some(key_block) => {
+                self.buffer = key_block;
+                self.buffer_len = 128;
+            }
+            None => self.buffer_len = 0,
+        }
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    /// Returns the block size in bytes (128 for BLAKE2b).
+    pub fn block_size() -> usize {
+        128
+    }
+}
+
+impl Default for Blake2b {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blake2Node for Blake2b {
+    const BLOCK_SIZE: usize = 128;
+    const MAX_DIGEST_SIZE: usize = 64;
+    const PARALLEL_LANES: usize = 4;
+    const PARALLEL_NAME: &'static str = "BLAKE2bp";
+    const XOF_UNKNOWN_LENGTH: u64 = 0xffff_ffff;
+    const XOF_NAME: &'static str = "BLAKE2Xb";
+
+    fn node(
+        params: &Blake2Params,
+        absorb_key: bool,
+        output_size: usize,
+    ) -> Result<Self, ParameterError> {
+        params.check("BLAKE2b", Self::MAX_DIGEST_SIZE, Self::SALT_SIZE, 64)?;
+
+        let mut block = [0u8; 64];
+        block[0] = params.digest_size as u8;
+        block[1] = params.key.len() as u8;
+        block[2] = params.fanout;
+        block[3] = params.depth;
+        block[4..8].copy_from_slice(&params.leaf_size.to_le_bytes());
+        block[8..16].copy_from_slice(&params.node_offset.to_le_bytes());
+        block[16] = params.node_depth;
+        block[17] = params.inner_size;
+        block[32..32 + params.salt.len()].copy_from_slice(&params.salt);
+        block[48..48 + params.person.len()].copy_from_slice(&params.person);
+
+        let mut initial = Self::IV;
+        for (word, bytes) in initial.iter_mut().zip(block.chunks_exact(8)) {
+            *word ^= u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let key_block = (absorb_key && !params.key.is_empty()).then(|| {
+            let mut key_block = [0u8; 128];
+            key_block[..params.key.len()].copy_from_slice(&params.key);
+            key_block
+        });
+        let mut hasher = Blake2b {
+            state: initial,
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            counter: 0,
+            digest_size: output_size,
+            initial,
+            key_block,
+            last_node: params.last_node,
+        };
+        hasher.reset();
+        Ok(hasher)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Blake2b::update(self, data);
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Blake2b::digest(self)
+    }
+
+    fn reset_node(&mut self) {
+        self.reset();
+    }
+}
+
+impl Digest for Blake2b {
+    type Output = Vec<u8>;
+
+    /// Never fails: the 128-bit byte counter cannot overflow in practice.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Blake2b::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    fn block_size(&self) -> usize {
+        128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blake2b_hex(params: &Blake2Params, data: &[u8]) -> String {
+        let mut hasher = Blake2b::with_params(params).unwrap();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_blake2b_rfc7693_abc() {
+        let mut hasher = Blake2b::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn test_blake2b_empty() {
+        assert_eq!(
+            Blake2b::new().hexdigest(),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+             d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+    }
+
+    #[test]
+    fn test_blake2b_keyed_reference_vectors() {
+        // blake2b-kat.txt: key = 00..3f, message = 00..(n-1).
+        let key: Vec<u8> = (0..64).collect();
+        let params = Blake2Params { key, ..Blake2Params::new(64) };
+        assert_eq!(
+            blake2b_hex(&params, b""),
+            "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786\
+             b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"
+        );
+        assert_eq!(
+            blake2b_hex(&params, &[0x00]),
+            "961f6dd1e4dd30f63901690c512e78e4b45e4742ed197c3c5e45c549fd25f2e4\
+             187b0bc9fe30492b16b0d0bc4ef9b0f34c7003fac09a5ef1532e69430234cebd"
+        );
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(
+            blake2b_hex(&params, &data[..255]),
+            "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248\
+             4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+        );
+    }
+
+    #[test]
+    fn test_blake2b_digest_size_is_not_truncation() {
+        let params = Blake2Params::new(32);
+        let short = blake2b_hex(&params, b"abc");
+        let mut long = Blake2b::new();
+        long.update(b"abc");
+        assert_eq!(short.len(), 64);
+        assert_ne!(short, long.hexdigest()[..64]);
+    }
+
+    #[test]
+    fn test_blake2b_split_updates() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let params = Blake2Params { key: b"key".to_vec(), ..Blake2Params::new(48) };
+        let expected = blake2b_hex(&params, &data);
+        for split in [0, 1, 127, 128, 129, 256, 999, 1000] {
+            let mut hasher = Blake2b::with_params(&params).unwrap();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.hexdigest(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_blake2b_reset_keeps_key() {
+        let params = Blake2Params { key: b"key".to_vec(), ..Blake2Params::new(64) };
+        let mut hasher = Blake2b::with_params(&params).unwrap();
+        hasher.update(b"discarded");
+        hasher.reset();
+        hasher.update(b"abc");
+        assert_eq!(hasher.hexdigest(), blake2b_hex(&params, b"abc"));
+    }
+
+    #[test]
+    fn test_blake2b_rejects_invalid_params() {
+        let too_long = vec![0u8; 65];
+        let cases = [
+            Blake2Params::new(0),
+            Blake2Params::new(65),
+            Blake2Params { key: too_long.clone(), ..Blake2Params::new(64) },
+            Blake2Params { salt: too_long[..17].to_vec(), ..Blake2Params::new(64) },
+            Blake2Params { person: too_long[..17].to_vec(), ..Blake2Params::new(64) },
+        ];
+        for params in &cases {
+            let err = Blake2b::with_params(params).err().expect("should be rejected");
+            assert_eq!(err.algorithm, "BLAKE2b");
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! BLAKE2bp and BLAKE2sp parallel hash functions.
+//!
+//! Pure Rust implementation following the BLAKE2 specification (section
+//! 2.10) and its reference code. The message is dealt out block by block,
+//! round-robin, to a fixed number of leaves; the root hashes the leaves'
+//! inner hashes. Leaves are independent, so large updates feed them
+//! concurrently on the rayon thread pool.
+//!
+//! # Algorithm Details
+//!
+//! - **BLAKE2bp**: 4 BLAKE2b leaves, 128-byte blocks, up to 64-byte output
+//! - **BLAKE2sp**: 8 BLAKE2s leaves, 64-byte blocks, up to 32-byte output
+//! - **Tree**: fanout = lanes, depth 2, full-size inner hashes; the key (if
+//!   any) is hashed by every leaf, its length is recorded in every node
+
+use rayon::prelude::*;
+
+use super::blake2::{Blake2Node, Blake2Params};
+use super::{Blake2b, Blake2s, Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// Minimum update size, in bytes, worth spreading across threads.
+const PARALLEL_THRESHOLD: usize = 16 * 1024;
+
+/// Parallel BLAKE2 hasher with [`Blake2Node::PARALLEL_LANES`] leaves of
+/// type `H`.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// independently of the original.
+#[derive(Clone)]
+pub struct Blake2Parallel<H: Blake2Node> {
+    /// One hasher per lane; leaf `i` gets blocks `i`, `i + lanes`, ...
+    leaves: Vec<H>,
+    /// Root node, fed the leaves' inner hashes when finalizing.
+    root: H,
+    /// Start of an incomplete stripe (one block per lane).
+    buffer: Vec<u8>,
+    digest_size: usize,
+    /// Whether large updates may feed the leaves on the rayon thread pool.
+    parallel: bool,
+}
+
+/// BLAKE2bp hasher.
+pub type Blake2bp = Blake2Parallel<Blake2b>;
+
+/// BLAKE2sp hasher.
+pub type Blake2sp = Blake2Parallel<Blake2s>;
+
+impl<H: Blake2Node> Blake2Parallel<H> {
+    /// Bytes in one stripe: a block for each lane.
+    const STRIPE: usize = H::PARALLEL_LANES * H::BLOCK_SIZE;
+
+    /// Creates a new unkeyed hasher with the maximum digest size.
+    pub fn new() -> Self {
+        Self::with_key(H::MAX_DIGEST_SIZE, &[]).expect("default parameters are valid")
+    }
+
+    /// Creates a new hasher with `digest_size` bytes of output, keyed with
+    /// `key` unless it is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if the digest size is zero or above the
+    /// maximum, or if the key is longer than the maximum digest size.
+    pub fn with_key(digest_size: usize, key: &[u8]) -> Result<Self, ParameterError> {
+        let lanes = H::PARALLEL_LANES;
+        let params = |node_offset, node_depth, last_node| Blake2Params {
+            key: key.to_vec(),
+            fanout: lanes as u8,
+            depth: 2,
+            node_offset,
+            node_depth,
+            inner_size: H::MAX_DIGEST_SIZE as u8,
+            last_node,
+            ..Blake2Params::new(digest_size)
+        };
+        let rename = |err: ParameterError| ParameterError { algorithm: H::PARALLEL_NAME, ..err };
+
+        let root = H::node(&params(0, 1, true), false, digest_size).map_err(rename)?;
+        let leaves = (0..lanes)
+            .map(|i| H::node(&params(i as u64, 0, i == lanes - 1), true, H::MAX_DIGEST_SIZE))
+            .collect::<Result<_, _>>()
+            .map_err(rename)?;
+        Ok(Blake2Parallel {
+            leaves,
+            root,
+            buffer: Vec::with_capacity(Self::STRIPE),
+            digest_size,
+            parallel: true,
+        })
+    }
+
+    /// Enables or disables feeding the leaves on the rayon thread pool.
+    ///
+    /// The digest is the same either way; this only trades threads for
+    /// latency on large updates.
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Feeds lane `lane`'s block of every stripe in `data` to `leaf`.
+    fn feed_lane(leaf: &mut H, lane: usize, data: &[u8]) {
+        for stripe in data.chunks_exact(Self::STRIPE) {
+            leaf.update(&stripe[lane * H::BLOCK_SIZE..(lane + 1) * H::BLOCK_SIZE]);
+        }
+    }
+
+    /// Feeds complete stripes to the leaves.
+    fn absorb_stripes(&mut self, data: &[u8]) {
+        debug_assert!(data.len().is_multiple_of(Self::STRIPE));
+        if self.parallel && data.len() >= PARALLEL_THRESHOLD {
+            self.leaves
+                .par_iter_mut()
+                .enumerate()
+                .for_each(|(lane, leaf)| Self::feed_lane(leaf, lane, data));
+        } else {
+            for (lane, leaf) in self.leaves.iter_mut().enumerate() {
+                Self::feed_lane(leaf, lane, data);
+            }
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Complete stripes go to the leaves right away, which hash them in
+    /// parallel when the update is large. The result does not depend on how
+    /// the message is split across calls.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if !self.buffer.is_empty() {
+            let take = data.len().min(Self::STRIPE - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < Self::STRIPE {
+                return;
+            }
+            let stripe = std::mem::take(&mut self.buffer);
+            self.absorb_stripes(&stripe);
+            self.buffer = stripe;
+            self.buffer.clear();
+        }
+        let whole = data.len() / Self::STRIPE * Self::STRIPE;
+        self.absorb_stripes(&data[..whole]);
+        self.buffer.extend_from_slice(&data[whole..]);
+    }
+
+    /// Returns the digest of the data fed so far, `digest_size` bytes long.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut root = self.root.clone();
+        for (lane, leaf) in self.leaves.iter().enumerate() {
+            let start = (lane * H::BLOCK_SIZE).min(self.buffer.len());
+            let end = ((lane + 1) * H::BLOCK_SIZE).min(self.buffer.len());
+            let inner = if start < end {
+                let mut leaf = leaf.clone();
+                leaf.update(&self.buffer[start..end]);
+                leaf.digest()
+            } else {
+                leaf.digest()
+            };
+            root.update(&inner);
+        }
+        root.digest()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, keeping its digest size and
+    /// key.
+    pub fn reset(&mut self) {
+        for leaf in &mut self.leaves {
+            leaf.reset_node();
+        }
+        self.buffer.clear();
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    /// Returns the block size in bytes (that of the leaves).
+    pub fn block_size() -> usize {
+        H::BLOCK_SIZE
+    }
+}
+
+impl<H: Blake2Node> Default for Blake2Parallel<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Blake2Node> Digest for Blake2Parallel<H> {
+    type Output = Vec<u8>;
+
+    /// Never fails: each leaf sees only part of the message, and the leaf
+    /// counters cannot overflow in practice.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Blake2Parallel::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    fn block_size(&self) -> usize {
+        H::BLOCK_SIZE
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message and key of the reference `blake2*p-kat.txt` files.
+    fn kat_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_blake2bp_keyed_kat() {
+        let cases = [
+            (0, "9d9461073e4eb640a255357b839f394b838c6ff57c9b686a3f76107c1066728f\
+                 3c9956bd785cbc3bf79dc2ab578c5a0c063b9d9c405848de1dbe821cd05c940a"),
+            (1, "ff8e90a37b94623932c59f7559f26035029c376732cb14d41602001cbb73adb7\
+                 9293a2dbda5f60703025144d158e2735529596251c73c0345ca6fccb1fb1e97e"),
+            (255, "96fbcbb60bd313b8845033e5bc058a38027438572d7e7957f3684f6268aadd3a\
+                   d08d21767ed6878685331ba98571487e12470aad669326716e46667f69f8d7e8"),
+        ];
+        for (len, expected) in cases {
+            let mut hasher = Blake2bp::with_key(64, &kat_bytes(64)).unwrap();
+            hasher.update(&kat_bytes(len));
+            assert_eq!(hasher.hexdigest(), expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_blake2sp_keyed_kat() {
+        let cases = [
+            (0, "715cb13895aeb678f6124160bff21465b30f4f6874193fc851b4621043f09cc6"),
+            (1, "40578ffa52bf51ae1866f4284d3a157fc1bcd36ac13cbdcb0377e4d0cd0b6603"),
+            (255, "0c8a36597d7461c63a94732821c941856c668376606c86a52de0ee4104c615db"),
+        ];
+        for (len, expected) in cases {
+            let mut hasher = Blake2sp::with_key(32, &kat_bytes(32)).unwrap();
+            hasher.update(&kat_bytes(len));
+            assert_eq!(hasher.hexdigest(), expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_unkeyed() {
+        assert_eq!(
+            Blake2bp::new().hexdigest(),
+            "b5ef811a8038f70b628fa8b294daae7492b1ebe343a80eaabbf1f6ae664dd67b\
+             9d90b0120791eab81dc96985f28849f6a305186a85501b405114bfa678df9380"
+        );
+        let mut hasher = Blake2sp::new();
+        hasher.update(&pattern(2000));
+        assert_eq!(
+            hasher.hexdigest(),
+            "3fa2bedfa6ef75bbfd40286fcc1af761d706cb05debfe756d9cd3da6268ac5ac"
+        );
+    }
+
+    #[test]
+    fn test_split_updates_match_single_update() {
+        let data = pattern(3000);
+        let mut whole = Blake2bp::new();
+        whole.update(&data);
+        for step in [1, 7, 128, 511, 512, 513] {
+            let mut split = Blake2bp::new();
+            for chunk in data.chunks(step) {
+                split.update(chunk);
+            }
+            assert_eq!(split.digest(), whole.digest(), "step {}", step);
+        }
+    }
+
+    #[test]
+    fn test_parallel_and_serial_paths_match() {
+        let data = pattern(100_000);
+        let mut parallel = Blake2sp::with_key(20, b"key").unwrap();
+        let mut serial = parallel.clone();
+        serial.set_parallel(false);
+        parallel.update(&data);
+        serial.update(&data);
+        assert_eq!(parallel.digest(), serial.digest());
+    }
+
+    #[test]
+    fn test_digest_size_is_not_truncation() {
+        let short = Blake2bp::with_key(32, b"").unwrap().digest();
+        assert_eq!(short.len(), 32);
+        assert_ne!(short, Blake2bp::new().digest()[..32]);
+    }
+
+    #[test]
+    fn test_reset_keeps_key() {
+        let mut hasher = Blake2sp::with_key(32, &kat_bytes(32)).unwrap();
+        hasher.update(b"discarded");
+        hasher.reset();
+        hasher.update(&kat_bytes(1));
+        assert_eq!(
+            hasher.hexdigest(),
+            "40578ffa52bf51ae1866f4284d3a157fc1bcd36ac13cbdcb0377e4d0cd0b6603"
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        let err = Blake2bp::with_key(65, b"").err().expect("digest size should be rejected");
+        assert_eq!(err.to_string(), "BLAKE2bp: digest_size must be between 1 and 64 bytes");
+        assert!(Blake2sp::with_key(0, b"").is_err());
+        let err = Blake2sp::with_key(32, &[0u8; 33]).err().expect("key should be rejected");
+        assert_eq!(err.to_string(), "BLAKE2sp: maximum key length is 32 bytes");
+    }
+}
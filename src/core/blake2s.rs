@@ -0,0 +1,393 @@
+//! BLAKE2s cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following RFC 7693. BLAKE2s is the 32-bit
+//! counterpart of BLAKE2b, for small and embedded platforms; it takes the
+//! same parameters (see [`Blake2Params`]) with smaller limits.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes), little-endian 32-bit words
+//! - **Digest size**: 1 to 32 bytes, part of the parameter block
+//! - **Key**: up to 32 bytes; **salt** and **personalization**: up to 8
+//! - **Rounds**: 10
+
+use super::blake2::{Blake2Node, Blake2Params, SIGMA};
+use super::{Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// BLAKE2s hasher state.
+///
+/// The last block is kept buffered until `digest()`, since it has to be
+/// compressed with the finalization flag. Cloning captures the full
+/// streaming state, keyed or not, so a clone can be finalized or extended
+/// independently of the original.
+#[derive(Clone)]
+pub struct Blake2s {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    /// Number of bytes compressed so far, the key block included.
+    counter: u64,
+    digest_size: usize,
+    /// State right after the parameter block, restored by `reset()`.
+    initial: [u32; 8],
+    /// Key padded to a full block, or `None` for plain hashing.
+    key_block: Option<[u8; 64]>,
+    /// Whether the final block also gets the last-node flag.
+    last_node: bool,
+}
+
+impl Blake2s {
+    /// Maximum digest size in bytes.
+    pub const MAX_DIGEST_SIZE: usize = 32;
+
+    /// Maximum key length in bytes.
+    pub const MAX_KEY_SIZE: usize = 32;
+
+    /// Salt length in bytes.
+    pub const SALT_SIZE: usize = 8;
+
+    /// Personalization string length in bytes.
+    pub const PERSON_SIZE: usize = 8;
+
+    /// BLAKE2s initialization vector (same as the SHA-256 initial hash value).
+    const IV: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    /// Creates a new unkeyed BLAKE2s hasher with a 32-byte digest.
+    pub fn new() -> Self {
+        Self::with_params(&Blake2Params::new(Self::MAX_DIGEST_SIZE))
+            .expect("default parameters are valid")
+    }
+
+    /// Creates a new BLAKE2s hasher from `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if the digest size is not between 1 and 32
+    /// bytes, if the key, salt or personalization string is too long, or if
+    /// a tree parameter is out of range (the node offset has 48 bits).
+    pub fn with_params(params: &Blake2Params) -> Result<Self, ParameterError> {
+        Self::node(params, true, params.digest_size)
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Complete blocks are compressed right away, except the last one, which
+    /// stays buffered until more data arrives or the digest is computed.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        if self.buffer_len > 0 {
+            let take = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if data.is_empty() {
+                return;
+            }
+            self.counter += 64;
+            Self::compress(&mut self.state, &self.buffer, self.counter, None);
+            self.buffer_len = 0;
+        }
+
+        // Keep at least one byte for the final, flagged block.
+        while data.len() > 64 {
+            self.counter += 64;
+            let block = data[..64].try_into().unwrap();
+            Self::compress(&mut self.state, block, self.counter, None);
+            data = &data[64..];
+        }
+        self.buffer[..data.len()].copy_from_slice(data);
+        self.buffer_len = data.len();
+    }
+
+    /// BLAKE2s compression function F.
+    ///
+    /// `counter` is the number of bytes hashed including this block, and
+    /// `last` marks the final block: `Some(last_node)` sets the finalization
+    /// flag, plus the last-node flag when `last_node` is true.
+    fn compress(state: &mut [u32; 8], block: &[u8; 64], counter: u64, last: Option<bool>) {
+        let mut m = [0u32; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let mut v = [0u32; 16];
+        v[..8].copy_from_slice(state);
+        v[8..].copy_from_slice(&Self::IV);
+        v[12] ^= counter as u32;
+        v[13] ^= (counter >> 32) as u32;
+        if let Some(last_node) = last {
+            v[14] = !v[14];
+            if last_node {
+                v[15] = !v[15];
+            }
+        }
+
+        for sigma in &SIGMA {
+            Self::g(&mut v, 0, 4, 8, 12, m[sigma[0]], m[sigma[1]]);
+            Self::g(&mut v, 1, 5, 9, 13, m[sigma[2]], m[sigma[3]]);
+            Self::g(&mut v, 2, 6, 10, 14, m[sigma[4]], m[sigma[5]]);
+            Self::g(&mut v, 3, 7, 11, 15, m[sigma[6]], m[sigma[7]]);
+            Self::g(&mut v, 0, 5, 10, 15, m[sigma[8]], m[sigma[9]]);
+            Self::g(&mut v, 1, 6, 11, 12, m[sigma[10]], m[sigma[11]]);
+            Self::g(&mut v, 2, 7, 8, 13, m[sigma[12]], m[sigma[13]]);
+            Self::g(&mut v, 3, 4, 9, 14, m[sigma[14]], m[sigma[15]]);
+        }
+
+        for i in 0..8 {
+            state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+
+    /// Mixing function G.
+    #[inline(always)]
+    fn g(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    }
+
+    /// Returns the digest of the data fed so far, `digest_size` bytes long.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut state = self.state;
+        let mut block = self.buffer;
+        block[self.buffer_len..].fill(0);
+        let counter = self.counter + self.buffer_len as u64;
+        Self::compress(&mut state, &block, counter, Some(self.last_node));
+
+        let mut out = [0u8; 32];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(state.iter()) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out[..self.digest_size].to_vec()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, keeping its parameters and
+    /// key.
+    pub fn reset(&mut self) {
+        self.state = self.initial;
+        self.counter = 0;
+        match self.key_block {
+            Some(key_block) => {
+                self.buffer = key_block;
+                self.buffer_len = 64;
+            }
+            None => self.buffer_len = 0,
+        }
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    /// Returns the block size in bytes (64 for BLAKE2s).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Blake2s {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blake2Node for Blake2s {
+    const BLOCK_SIZE: usize = 64;
+    const MAX_DIGEST_SIZE: usize = 32;
+    const PARALLEL_LANES: usize = 8;
+    const PARALLEL_NAME: &'static str = "BLAKE2sp";
+    const XOF_UNKNOWN_LENGTH: u64 = 0xffff;
+    const XOF_NAME: &'static str = "BLAKE2Xs";
+
+    fn node(
+        params: &Blake2Params,
+        absorb_key: bool,
+        output_size: usize,
+    ) -> Result<Self, ParameterError> {
+        params.check("BLAKE2s", Self::MAX_DIGEST_SIZE, Self::SALT_SIZE, 48)?;
+
+        let mut block = [0u8; 32];
+        block[0] = params.digest_size as u8;
+        block[1] = params.key.len() as u8;
+        block[2] = params.fanout;
+        block[3] = params.depth;
+        block[4..8].copy_from_slice(&params.leaf_size.to_le_bytes());
+        block[8..14].copy_from_slice(&params.node_offset.to_le_bytes()[..6]);
+        block[14] = params.node_depth;
+        block[15] = params.inner_size;
+        block[16..16 + params.salt.len()].copy_from_slice(&params.salt);
+        block[24..24 + params.person.len()].copy_from_slice(&params.person);
+
+        let mut initial = Self::IV;
+        for (word, bytes) in initial.iter_mut().zip(block.chunks_exact(4)) {
+            *word ^= u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let key_block = (absorb_key && !params.key.is_empty()).then(|| {
+            let mut key_block = [0u8; 64];
+            key_block[..params.key.len()].copy_from_slice(&params.key);
+            key_block
+        });
+        let mut hasher = Blake2s {
+            state: initial,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            counter: 0,
+            digest_size: output_size,
+            initial,
+            key_block,
+            last_node: params.last_node,
+        };
+        hasher.reset();
+        Ok(hasher)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Blake2s::update(self, data);
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Blake2s::digest(self)
+    }
+
+    fn reset_node(&mut self) {
+        self.reset();
+    }
+}
+
+impl Digest for Blake2s {
+    type Output = Vec<u8>;
+
+    /// Never fails: the 64-bit byte counter cannot overflow in practice.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Blake2s::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    fn block_size(&self) -> usize {
+        64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blake2s_hex(params: &Blake2Params, data: &[u8]) -> String {
+        let mut hasher = Blake2s::with_params(params).unwrap();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_blake2s_rfc7693_abc() {
+        let mut hasher = Blake2s::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+    }
+
+    #[test]
+    fn test_blake2s_empty() {
+        assert_eq!(
+            Blake2s::new().hexdigest(),
+            "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+        );
+    }
+
+    #[test]
+    fn test_blake2s_keyed_reference_vectors() {
+        // blake2s-kat.txt: key = 00..1f, message = 00..(n-1).
+        let key: Vec<u8> = (0..32).collect();
+        let params = Blake2Params { key, ..Blake2Params::new(32) };
+        let data: Vec<u8> = (0..=255).collect();
+        let cases = [
+            (0, "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49"),
+            (1, "40d15fee7c328830166ac3f918650f807e7e01e177258cdc0a39b11f598066f1"),
+            (63, "c65382513f07460da39833cb666c5ed82e61b9e998f4b0c4287cee56c3cc9bcd"),
+            (64, "8975b0577fd35566d750b362b0897a26c399136df07bababbde6203ff2954ed4"),
+            (255, "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd"),
+        ];
+        for (len, expected) in cases {
+            assert_eq!(blake2s_hex(&params, &data[..len]), expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_blake2s_split_updates() {
+        let data: Vec<u8> = (0..=255).cycle().take(500).collect();
+        let params = Blake2Params { key: b"key".to_vec(), ..Blake2Params::new(20) };
+        let expected = blake2s_hex(&params, &data);
+        for split in [0, 1, 63, 64, 65, 128, 499, 500] {
+            let mut hasher = Blake2s::with_params(&params).unwrap();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.hexdigest(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_blake2s_copy_keeps_key() {
+        let params = Blake2Params { key: b"key".to_vec(), ..Blake2Params::new(32) };
+        let mut hasher = Blake2s::with_params(&params).unwrap();
+        let copy = hasher.clone();
+        hasher.update(b"abc");
+        assert_eq!(copy.hexdigest(), blake2s_hex(&params, b""));
+        assert_eq!(hasher.hexdigest(), blake2s_hex(&params, b"abc"));
+    }
+
+    #[test]
+    fn test_blake2s_rejects_invalid_params() {
+        let too_long = vec![0u8; 33];
+        let cases = [
+            Blake2Params::new(0),
+            Blake2Params::new(33),
+            Blake2Params { key: too_long.clone(), ..Blake2Params::new(32) },
+            Blake2Params { salt: too_long[..9].to_vec(), ..Blake2Params::new(32) },
+            Blake2Params { person: too_long[..9].to_vec(), ..Blake2Params::new(32) },
+            Blake2Params { depth: 0, ..Blake2Params::new(32) },
+            Blake2Params { node_offset: 1 << 48, ..Blake2Params::new(32) },
+            Blake2Params { inner_size: 33, ..Blake2Params::new(32) },
+        ];
+        for params in &cases {
+            let err = Blake2s::with_params(params).err().expect("should be rejected");
+            assert_eq!(err.algorithm, "BLAKE2s");
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! BLAKE2Xb and BLAKE2Xs extendable-output functions.
+//!
+//! Pure Rust implementation following the BLAKE2X specification and its
+//! reference code. The message is first hashed into a full-size root hash
+//! `H0`; output block `i` is then the BLAKE2 hash of `H0` with node offset
+//! `i`, so any part of the output can be computed independently.
+//!
+//! # Algorithm Details
+//!
+//! - **Output length**: declared up front (1 to 2^32 - 2 bytes for BLAKE2Xb,
+//!   1 to 65534 bytes for BLAKE2Xs) or unknown
+//! - **Parameter block**: `xof_length` holds the declared length, or all
+//!   ones when unknown, in every node; the 32-bit node offset leaves room
+//!   for it
+//! - **Output nodes**: unkeyed, fanout 0, depth 0, leaf and inner size equal
+//!   to the maximum digest size; a declared length shortens the last block
+//!
+//! Because `xof_length` is hashed into every node, an output of declared
+//! length `L` is not a prefix of the unknown-length output, and outputs of
+//! different declared lengths are unrelated.
+
+use std::marker::PhantomData;
+
+use super::blake2::{Blake2Node, Blake2Params};
+use super::{Blake2b, Blake2s, LengthOverflowError, ParameterError, Xof};
+use crate::utils::to_hex;
+
+/// BLAKE2X hasher built on the BLAKE2 variant `H`.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// independently of the original.
+#[derive(Clone)]
+pub struct Blake2X<H: Blake2Node> {
+    /// Hasher for the root hash `H0`.
+    root: H,
+    /// Parameters shared by the output nodes.
+    params: Blake2Params,
+    /// Declared output length in bytes, `None` when unknown.
+    length: Option<usize>,
+}
+
+/// BLAKE2Xb hasher.
+pub type Blake2Xb = Blake2X<Blake2b>;
+
+/// BLAKE2Xs hasher.
+pub type Blake2Xs = Blake2X<Blake2s>;
+
+impl<H: Blake2Node> Blake2X<H> {
+    /// Creates a new hasher producing `length` bytes (`None` for an unknown
+    /// length), keyed with `key` unless it is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if `length` is 0 or too large for the
+    /// `xof_length` field, or if the key is longer than the maximum digest
+    /// size.
+    pub fn new(length: Option<usize>, key: &[u8]) -> Result<Self, ParameterError> {
+        let xof_length = match length {
+            None => H::XOF_UNKNOWN_LENGTH,
+            Some(length) if length > 0 && (length as u64) < H::XOF_UNKNOWN_LENGTH => {
+                length as u64
+            }
+            Some(_) => {
+                return Err(ParameterError {
+                    algorithm: H::XOF_NAME,
+                    message: format!(
+                        "output length must be between 1 and {} bytes",
+                        H::XOF_UNKNOWN_LENGTH - 1
+                    ),
+                });
+            }
+        };
+        let size = H::MAX_DIGEST_SIZE;
+        let root_params = Blake2Params {
+            key: key.to_vec(),
+            node_offset: xof_length << 32,
+            ..Blake2Params::new(size)
+        };
+        let root = H::node(&root_params, true, size)
+            .map_err(|err| ParameterError { algorithm: H::XOF_NAME, ..err })?;
+        let params = Blake2Params {
+            fanout: 0,
+            depth: 0,
+            leaf_size: size as u32,
+            node_offset: xof_length << 32,
+            inner_size: size as u8,
+            ..Blake2Params::new(size)
+        };
+        Ok(Blake2X { root, params, length })
+    }
+
+    /// Returns the declared output length, `None` when unknown.
+    pub fn output_length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.root.update(data);
+    }
+
+    /// Returns a reader over the output for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> Blake2XReader<H> {
+        Blake2XReader {
+            root_hash: self.root.digest(),
+            params: self.params.clone(),
+            length: self.length,
+            position: 0,
+            block: Vec::new(),
+            variant: PhantomData,
+        }
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the declared output length.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        let mut out = vec![0u8; length];
+        self.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` exceeds the declared output length.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, keeping its output length
+    /// and key.
+    pub fn reset(&mut self) {
+        self.root.reset_node();
+    }
+
+    /// Returns the block size in bytes (that of the underlying BLAKE2).
+    pub fn block_size() -> usize {
+        H::BLOCK_SIZE
+    }
+}
+
+impl<H: Blake2Node> Xof for Blake2X<H> {
+    /// Never fails: the BLAKE2 byte counters cannot overflow in practice.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    /// Panics if `out` is longer than the declared output length.
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        H::BLOCK_SIZE
+    }
+}
+
+/// Output half of BLAKE2X: the expansion of a root hash into output blocks.
+///
+/// Successive reads continue the output, so reading 16 then 16 bytes gives
+/// the same bytes as reading 32 at once.
+#[derive(Clone)]
+pub struct Blake2XReader<H: Blake2Node> {
+    /// Root hash `H0`, the input of every output node.
+    root_hash: Vec<u8>,
+    params: Blake2Params,
+    length: Option<usize>,
+    /// Number of output bytes already read.
+    position: u64,
+    /// Current output block, of which `position % size` bytes were read.
+    block: Vec<u8>,
+    variant: PhantomData<H>,
+}
+
+impl<H: Blake2Node> Blake2XReader<H> {
+    /// Returns the number of bytes left, `None` when the length is unknown.
+    pub fn remaining(&self) -> Option<usize> {
+        self.length.map(|length| length - self.position as usize)
+    }
+
+    /// Computes output block `index`.
+    fn output_block(&self, index: u64) -> Vec<u8> {
+        let size = H::MAX_DIGEST_SIZE;
+        let block_len = match self.length {
+            Some(length) => size.min(length - index as usize * size),
+            None => size,
+        };
+        let params = Blake2Params {
+            digest_size: block_len,
+            node_offset: self.params.node_offset | index,
+            ..self.params.clone()
+        };
+        let mut node =
+            H::node(&params, false, block_len).expect("output node parameters are valid");
+        node.update(&self.root_hash);
+        node.digest()
+    }
+
+    /// Fills `out` with the next output bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when reading past the declared output length, or past the
+    /// 2^32 output blocks the node offset can number.
+    pub fn read(&mut self, out: &mut [u8]) {
+        if let Some(remaining) = self.remaining() {
+            assert!(out.len() <= remaining, "read past the declared BLAKE2X output length");
+        }
+        let size = H::MAX_DIGEST_SIZE as u64;
+        let mut out = out;
+        while !out.is_empty() {
+            let offset = (self.position % size) as usize;
+            if offset == 0 {
+                let index = self.position / size;
+                assert!(index >> 32 == 0, "BLAKE2X output exhausted");
+                self.block = self.output_block(index);
+            }
+            let take = out.len().min(self.block.len() - offset);
+            out[..take].copy_from_slice(&self.block[offset..offset + take]);
+            out = &mut out[take..];
+            self.position += take as u64;
+        }
+    }
+
+    /// Returns the next `len` output bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`Blake2XReader::read`].
+    pub fn read_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; len];
+        self.read(&mut out);
+        out
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message and key layout of the reference BLAKE2X known-answer tests.
+    fn kat_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    fn kat<H: Blake2Node>(length: usize) -> String {
+        let mut hasher = Blake2X::<H>::new(Some(length), &kat_bytes(H::MAX_DIGEST_SIZE)).unwrap();
+        hasher.update(&kat_bytes(256));
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_blake2xb_keyed_kat() {
+        assert_eq!(kat::<Blake2b>(1), "64");
+        assert_eq!(
+            kat::<Blake2b>(33),
+            "bad026c8b2bd3d294907f2280a7145253ec2117d76e3800357be6d431b16366e41"
+        );
+        assert_eq!(
+            kat::<Blake2b>(65),
+            "78f0ed6e220b3da3cc9381563b2f72c8dc830cb0f39a48c6ae479a6a78dcfa94\
+             002631dec467e9e9b47cc8f0887eb680e340aec3ec009d4a33d241533c76c8ca8c"
+        );
+    }
+
+    #[test]
+    fn test_blake2xs_keyed_kat() {
+        assert_eq!(kat::<Blake2s>(1), "0e");
+        assert_eq!(
+            kat::<Blake2s>(33),
+            "7dce710a20f42ab687ec6ea83b53faaa418229ce0d5a2ff2a5e66defb0b65c03c9"
+        );
+        assert_eq!(
+            kat::<Blake2s>(65),
+            "cf601753ffa09fe48a8a84c37769991e96290e200bbaf1910c57760f989bd0c7\
+             2e6128e294528ee861ad7eee70d589de3cf4a0c35f7197e1925a64d0133628d87d"
+        );
+    }
+
+    #[test]
+    fn test_unknown_length_outputs_are_prefixes() {
+        let mut hasher = Blake2Xb::new(None, b"key").unwrap();
+        hasher.update(b"abc");
+        let long = hasher.digest(1000);
+        for length in [1, 63, 64, 65, 999] {
+            assert_eq!(hasher.digest(length), &long[..length]);
+        }
+        let mut reader = hasher.finalize_xof();
+        let mut pieces = Vec::new();
+        for step in [1, 70, 129, 300] {
+            pieces.extend(reader.read_vec(step));
+        }
+        assert_eq!(pieces, &long[..500]);
+    }
+
+    #[test]
+    fn test_known_length_differs_from_unknown_length() {
+        let mut unknown = Blake2Xs::new(None, b"").unwrap();
+        unknown.update(b"abc");
+        let stream = unknown.digest(100);
+        let mut known = Blake2Xs::new(Some(100), b"").unwrap();
+        known.update(b"abc");
+        let full = known.digest(100);
+        assert_ne!(full, stream);
+        assert_ne!(full[..32], stream[..32]);
+
+        // Every node hashes the declared length, so even the first blocks of
+        // two declared lengths differ.
+        let mut shorter = Blake2Xs::new(Some(99), b"").unwrap();
+        shorter.update(b"abc");
+        assert_ne!(shorter.digest(32), full[..32]);
+        // Within one declared length, shorter reads are still prefixes.
+        assert_eq!(known.digest(40), &full[..40]);
+    }
+
+    #[test]
+    #[should_panic(expected = "declared BLAKE2X output length")]
+    fn test_reading_past_declared_length_panics() {
+        let hasher = Blake2Xb::new(Some(10), b"").unwrap();
+        let mut reader = hasher.finalize_xof();
+        reader.read_vec(6);
+        assert_eq!(reader.remaining(), Some(4));
+        reader.read_vec(5);
+    }
+
+    #[test]
+    fn test_reset_keeps_key_and_length() {
+        let mut hasher = Blake2Xs::new(Some(33), &kat_bytes(32)).unwrap();
+        hasher.update(b"discarded");
+        hasher.reset();
+        hasher.update(&kat_bytes(256));
+        assert_eq!(hasher.hexdigest(33), kat::<Blake2s>(33));
+    }
+
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        let err = Blake2Xs::new(Some(65535), b"").err().expect("length should be rejected");
+        assert_eq!(err.to_string(), "BLAKE2Xs: output length must be between 1 and 65534 bytes");
+        assert!(Blake2Xs::new(Some(65534), b"").is_ok());
+        assert!(Blake2Xb::new(Some(0), b"").is_err());
+        assert!(Blake2Xb::new(Some(0xffff_ffff), b"").is_err());
+        let err = Blake2Xb::new(None, &[0u8; 65]).err().expect("key should be rejected");
+        assert_eq!(err.to_string(), "BLAKE2Xb: maximum key length is 64 bytes");
+    }
+}
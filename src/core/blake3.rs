@@ -0,0 +1,846 @@
+//! BLAKE3 hash function.
+//!
+//! Pure Rust implementation following the BLAKE3 specification. The input
+//! is split into 1 KiB chunks, each hashed with a 7-round compression
+//! function into a chaining value; chaining values are merged pairwise by
+//! parent nodes up to a single root, whose output can be extended to any
+//! length.
+//!
+//! # Algorithm Details
+//!
+//! - **Compression**: 7 rounds over a 16-word state, 64-byte blocks
+//! - **Chunks**: 1024 bytes, numbered by a 64-bit counter
+//! - **Tree**: left subtrees are complete, holding the largest power of two
+//!   number of chunks that leaves at least one chunk on the right
+//! - **Output**: 32 bytes by default, any length in extended mode; shorter
+//!   outputs are prefixes of longer ones
+//! - **Modes**: plain hashing, keyed hashing with a 32-byte key, and key
+//!   derivation, whose key is the hash of a context string
+//!
+//! The streaming state keeps one chaining value per level of the tree (a
+//! stack of at most 54 entries), so memory use does not grow with the
+//! input. Complete subtrees are independent, so large updates hash them
+//! concurrently on a rayon thread pool and merge the resulting chaining
+//! values, with the same output as a sequential pass.
+
+use std::sync::Arc;
+use std::thread;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use super::{LengthOverflowError, ParameterError, Xof};
+use crate::utils::to_hex;
+
+/// Size of a block in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// Size of a chunk in bytes.
+const CHUNK_LEN: usize = 1024;
+
+/// Minimum update size, in bytes, worth spreading across threads.
+const PARALLEL_THRESHOLD: usize = 128 * 1024;
+
+/// Subtrees smaller than this are hashed on a single thread.
+const MIN_SPLIT_LEN: usize = 16 * CHUNK_LEN;
+
+/// Default output size in bytes.
+pub const OUT_LEN: usize = 32;
+
+/// Size of a key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Initial chaining value of the unkeyed mode (the SHA-256 IV).
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Message word permutation applied between rounds.
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// Domain separation flags.
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+/// Mixes two message words into four state words.
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(x);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(y);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+/// The BLAKE3 compression function, returning the full 16-word state.
+///
+/// The first 8 words are the new chaining value; all 16 are output words
+/// of a root node.
+fn compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        cv[0], cv[1], cv[2], cv[3], cv[4], cv[5], cv[6], cv[7],
+        IV[0], IV[1], IV[2], IV[3],
+        counter as u32, (counter >> 32) as u32, block_len, flags,
+    ];
+    let mut m = *block;
+    for round in 0..7 {
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+        if round < 6 {
+            m = MSG_PERMUTATION.map(|i| m[i]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+/// Reads a block as little-endian words, zero-padding a partial block.
+fn block_words(bytes: &[u8]) -> [u32; 16] {
+    let mut block = [0u8; BLOCK_LEN];
+    block[..bytes.len()].copy_from_slice(bytes);
+    let mut words = [0u32; 16];
+    for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+/// Reads a key as little-endian words.
+fn key_words(key: &[u8; KEY_LEN]) -> [u32; 8] {
+    first_8(block_words(key))
+}
+
+/// Returns the first 8 words of a compression output.
+fn first_8(words: [u32; 16]) -> [u32; 8] {
+    words[..8].try_into().unwrap()
+}
+
+/// The last compression of a node, kept unevaluated: it yields a chaining
+/// value for a child, or any number of output bytes for the root.
+#[derive(Clone)]
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    /// Returns the chaining value of a non-root node.
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8(compress(&self.cv, &self.block, self.counter, self.block_len, self.flags))
+    }
+
+    /// Returns output block `index` (64 bytes) of a root node.
+    fn root_block(&self, index: u64) -> [u8; BLOCK_LEN] {
+        let words = compress(&self.cv, &self.block, index, self.block_len, self.flags | ROOT);
+        let mut out = [0u8; BLOCK_LEN];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(words) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+/// Returns the output of the parent node of two chaining values.
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u32) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    Output { cv: *key, block, counter: 0, block_len: BLOCK_LEN as u32, flags: flags | PARENT }
+}
+
+/// Hashing state of the current chunk.
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    counter: u64,
+    /// Last block, kept until more input shows it is not the chunk's last.
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], counter: u64, flags: u32) -> Self {
+        ChunkState {
+            cv: *key,
+            counter,
+            block: [0u8; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    /// Number of input bytes in the chunk so far.
+    fn len(&self) -> usize {
+        self.blocks_compressed * BLOCK_LEN + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 { CHUNK_START } else { 0 }
+    }
+
+    /// Feeds at most the rest of the chunk.
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.block_len == BLOCK_LEN {
+                let flags = self.flags | self.start_flag();
+                let words = block_words(&self.block);
+                let block_len = BLOCK_LEN as u32;
+                self.cv = first_8(compress(&self.cv, &words, self.counter, block_len, flags));
+                self.blocks_compressed += 1;
+                self.block_len = 0;
+            }
+            let take = data.len().min(BLOCK_LEN - self.block_len);
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&data[..take]);
+            self.block_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            cv: self.cv,
+            block: block_words(&self.block[..self.block_len]),
+            counter: self.counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// BLAKE3 hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Blake3 {
+    /// Key words: the IV in the plain mode, the key in the keyed mode and
+    /// the context key in the key derivation mode.
+    key: [u32; 8],
+    chunk: ChunkState,
+    /// Chaining values of the complete subtrees to the left of `chunk`,
+    /// one per set bit of the number of complete chunks.
+    cv_stack: Vec<[u32; 8]>,
+    /// Mode flags applied to every compression.
+    flags: u32,
+    /// Whether large updates may hash subtrees on several threads.
+    parallel: bool,
+    /// Dedicated pool capping the number of threads, or `None` for the
+    /// global rayon pool.
+    pool: Option<Arc<ThreadPool>>,
+}
+
+impl Blake3 {
+    /// Creates a new hasher in the default (unkeyed) mode.
+    pub fn new() -> Self {
+        Self::with_key_words(IV, 0)
+    }
+
+    /// Creates a new hasher in the keyed mode, for use as a MAC.
+    pub fn new_keyed(key: &[u8; KEY_LEN]) -> Self {
+        Self::with_key_words(key_words(key), KEYED_HASH)
+    }
+
+    /// Creates a new hasher in the key derivation mode: the data fed to it
+    /// is key material, from which it derives keys bound to `context`.
+    ///
+    /// The context should be a hardcoded, globally unique string describing
+    /// the purpose of the keys, such as
+    /// `"example.com 2019-12-25 16:18:03 session tokens v1"`.
+    pub fn new_derive_key(context: &str) -> Self {
+        let mut context_hasher = Self::with_key_words(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+        let mut context_key = [0u8; KEY_LEN];
+        context_hasher.finalize_xof().read(&mut context_key);
+        Self::with_key_words(key_words(&context_key), DERIVE_KEY_MATERIAL)
+    }
+
+    fn with_key_words(key: [u32; 8], flags: u32) -> Self {
+        Blake3 {
+            key,
+            chunk: ChunkState::new(&key, 0, flags),
+            cv_stack: Vec::new(),
+            flags,
+            parallel: true,
+            pool: None,
+        }
+    }
+
+    /// Limits the number of threads used to hash large updates.
+    ///
+    /// `None` uses the global rayon pool, `Some(1)` hashes on the calling
+    /// thread only. Larger limits are clamped to the available parallelism,
+    /// since extra threads only add overhead. The output does not depend on
+    /// this setting.
+    pub fn set_max_threads(&mut self, max_threads: Option<usize>) -> Result<(), ParameterError> {
+        let invalid = |message: String| ParameterError { algorithm: "BLAKE3", message };
+        let cores = thread::available_parallelism().map_or(1, usize::from);
+        match max_threads.map(|threads| threads.min(cores)) {
+            None => {
+                self.parallel = true;
+                self.pool = None;
+            }
+            Some(0) => return Err(invalid("max_threads must be at least 1".to_owned())),
+            Some(1) => {
+                self.parallel = false;
+                self.pool = None;
+            }
+            Some(threads) => {
+                let pool = ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .map_err(|err| invalid(format!("cannot start {} threads: {}", threads, err)))?;
+                self.parallel = true;
+                self.pool = Some(Arc::new(pool));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the chaining value of a complete subtree of `chunks` chunks
+    /// (a power of two) starting at chunk `first_chunk`, merging every
+    /// larger subtree it completes.
+    fn push_subtree_cv(&mut self, mut cv: [u32; 8], first_chunk: u64, chunks: u64) {
+        let mut total_subtrees = (first_chunk + chunks) / chunks;
+        while total_subtrees & 1 == 0 {
+            let left = self.cv_stack.pop().expect("a complete subtree is on the stack");
+            cv = parent_output(&left, &cv, &self.key, self.flags).chaining_value();
+            total_subtrees >>= 1;
+        }
+        self.cv_stack.push(cv);
+    }
+
+    /// Returns the chaining value of the complete subtree covering `data`,
+    /// a power of two number of chunks starting at chunk `counter`.
+    fn subtree_cv(&self, data: &[u8], counter: u64) -> [u32; 8] {
+        if data.len() == CHUNK_LEN {
+            let mut chunk = ChunkState::new(&self.key, counter, self.flags);
+            chunk.update(data);
+            return chunk.output().chaining_value();
+        }
+        let (left, right) = data.split_at(data.len() / 2);
+        let right_counter = counter + (left.len() / CHUNK_LEN) as u64;
+        let (left_cv, right_cv) = if data.len() > MIN_SPLIT_LEN {
+            rayon::join(|| self.subtree_cv(left, counter), || self.subtree_cv(right, right_counter))
+        } else {
+            (self.subtree_cv(left, counter), self.subtree_cv(right, right_counter))
+        };
+        parent_output(&left_cv, &right_cv, &self.key, self.flags).chaining_value()
+    }
+
+    /// Hashes the largest aligned complete subtrees of `data`, starting on
+    /// a chunk boundary, and returns the rest: at most one chunk, which
+    /// may be the last one.
+    fn absorb_subtrees<'a>(&mut self, mut data: &'a [u8]) -> &'a [u8] {
+        debug_assert_eq!(self.chunk.len(), 0);
+        while data.len() > CHUNK_LEN {
+            let counter = self.chunk.counter;
+            // The subtree must leave at least one byte for the right-hand
+            // side and be aligned on its own size in the tree.
+            let mut chunks = 1u64 << ((data.len() - 1) / CHUNK_LEN).ilog2();
+            if counter != 0 {
+                chunks = chunks.min(1 << counter.trailing_zeros());
+            }
+            let len = chunks as usize * CHUNK_LEN;
+            let cv = self.subtree_cv(&data[..len], counter);
+            self.push_subtree_cv(cv, counter, chunks);
+            self.chunk = ChunkState::new(&self.key, counter + chunks, self.flags);
+            data = &data[len..];
+        }
+        data
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// A full chunk is only merged into the tree once more data arrives,
+    /// since the last chunk is the root when it is the only one. Updates of
+    /// at least 128 KiB hash their complete subtrees in parallel; the result
+    /// does not depend on how the message is split across calls.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.parallel && data.len() >= PARALLEL_THRESHOLD {
+            let take = (CHUNK_LEN - self.chunk.len()) % CHUNK_LEN;
+            self.chunk.update(&data[..take]);
+            data = &data[take..];
+            if self.chunk.len() == CHUNK_LEN {
+                let cv = self.chunk.output().chaining_value();
+                let counter = self.chunk.counter;
+                self.push_subtree_cv(cv, counter, 1);
+                self.chunk = ChunkState::new(&self.key, counter + 1, self.flags);
+            }
+            data = match self.pool.clone() {
+                Some(pool) => pool.install(|| self.absorb_subtrees(data)),
+                None => self.absorb_subtrees(data),
+            };
+        }
+        while !data.is_empty() {
+            if self.chunk.len() == CHUNK_LEN {
+                let cv = self.chunk.output().chaining_value();
+                let counter = self.chunk.counter;
+                self.push_subtree_cv(cv, counter, 1);
+                self.chunk = ChunkState::new(&self.key, counter + 1, self.flags);
+            }
+            let take = data.len().min(CHUNK_LEN - self.chunk.len());
+            self.chunk.update(&data[..take]);
+            data = &data[take..];
+        }
+    }
+
+    /// Returns a reader over the output stream for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> Blake3Reader {
+        let mut output = self.chunk.output();
+        for left in self.cv_stack.iter().rev() {
+            output = parent_output(left, &output.chaining_value(), &self.key, self.flags);
+        }
+        Blake3Reader { output, position: 0 }
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        self.finalize_xof().read_vec(length)
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, keeping its mode.
+    pub fn reset(&mut self) {
+        self.chunk = ChunkState::new(&self.key, 0, self.flags);
+        self.cv_stack.clear();
+    }
+
+    /// Returns the block size in bytes (64 for BLAKE3).
+    pub fn block_size() -> usize {
+        BLOCK_LEN
+    }
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Xof for Blake3 {
+    /// Never fails: the 64-bit chunk counter allows 2^74 bytes.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        BLOCK_LEN
+    }
+}
+
+/// Output stream of a BLAKE3 root node.
+///
+/// Successive reads continue the stream, so reading 16 then 16 bytes gives
+/// the same output as reading 32 bytes at once.
+#[derive(Clone)]
+pub struct Blake3Reader {
+    output: Output,
+    /// Number of output bytes already read.
+    position: u64,
+}
+
+impl Blake3Reader {
+    /// Fills `out` with the next output bytes.
+    pub fn read(&mut self, out: &mut [u8]) {
+        let mut out = out;
+        while !out.is_empty() {
+            let block = self.output.root_block(self.position / BLOCK_LEN as u64);
+            let offset = (self.position % BLOCK_LEN as u64) as usize;
+            let take = out.len().min(BLOCK_LEN - offset);
+            out[..take].copy_from_slice(&block[offset..offset + take]);
+            out = &mut out[take..];
+            self.position += take as u64;
+        }
+    }
+
+    /// Returns the next `len` output bytes.
+    pub fn read_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; len];
+        self.read(&mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Input of the official test vectors: the repeating pattern 0..=250.
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Input lengths of the official test vectors and the first 32 bytes of
+    /// the `hash` column.
+    const HASHES: [(usize, &str); 35] = [
+        (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+        (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+        (2, "7b7015bb92cf0b318037702a6cdd81dee41224f734684c2c122cd6359cb1ee63"),
+        (3, "e1be4d7a8ab5560aa4199eea339849ba8e293d55ca0a81006726d184519e647f"),
+        (4, "f30f5ab28fe047904037f77b6da4fea1e27241c5d132638d8bedce9d40494f32"),
+        (5, "b40b44dfd97e7a84a996a91af8b85188c66c126940ba7aad2e7ae6b385402aa2"),
+        (6, "06c4e8ffb6872fad96f9aaca5eee1553eb62aed0ad7198cef42e87f6a616c844"),
+        (7, "3f8770f387faad08faa9d8414e9f449ac68e6ff0417f673f602a646a891419fe"),
+        (8, "2351207d04fc16ade43ccab08600939c7c1fa70a5c0aaca76063d04c3228eaeb"),
+        (63, "e9bc37a594daad83be9470df7f7b3798297c3d834ce80ba85d6e207627b7db7b"),
+        (64, "4eed7141ea4a5cd4b788606bd23f46e212af9cacebacdc7d1f4c6dc7f2511b98"),
+        (65, "de1e5fa0be70df6d2be8fffd0e99ceaa8eb6e8c93a63f2d8d1c30ecb6b263dee"),
+        (127, "d81293fda863f008c09e92fc382a81f5a0b4a1251cba1634016a0f86a6bd640d"),
+        (128, "f17e570564b26578c33bb7f44643f539624b05df1a76c81f30acd548c44b45ef"),
+        (129, "683aaae9f3c5ba37eaaf072aed0f9e30bac0865137bae68b1fde4ca2aebdcb12"),
+        (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11"),
+        (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+        (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+        (2048, "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a"),
+        (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+        (3072, "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2"),
+        (3073, "7124b49501012f81cc7f11ca069ec9226cecb8a2c850cfe644e327d22d3e1cd3"),
+        (4096, "015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969"),
+        (4097, "9b4052b38f1c5fc8b1f9ff7ac7b27cd242487b3d890d15c96a1c25b8aa0fb995"),
+        (5120, "9cadc15fed8b5d854562b26a9536d9707cadeda9b143978f319ab34230535833"),
+        (5121, "628bd2cb2004694adaab7bbd778a25df25c47b9d4155a55f8fbd79f2fe154cff"),
+        (6144, "3e2e5b74e048f3add6d21faab3f83aa44d3b2278afb83b80b3c35164ebeca205"),
+        (6145, "f1323a8631446cc50536a9f705ee5cb619424d46887f3c376c695b70e0f0507f"),
+        (7168, "61da957ec2499a95d6b8023e2b0e604ec7f6b50e80a9678b89d2628e99ada77a"),
+        (7169, "a003fc7a51754a9b3c7fae0367ab3d782dccf28855a03d435f8cfe74605e7817"),
+        (8192, "aae792484c8efe4f19e2ca7d371d8c467ffb10748d8a5a1ae579948f718a2a63"),
+        (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b"),
+        (16384, "f875d6646de28985646f34ee13be9a576fd515f76b5b0a26bb324735041ddde4"),
+        (31744, "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47"),
+        (102400, "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085"),
+    ];
+
+    /// Key and context string of the official test vectors.
+    const KEY: &[u8; KEY_LEN] = b"whats the Elvish word for friend";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    /// First 32 bytes of the `keyed_hash` column.
+    const KEYED_HASHES: [(usize, &str); 35] = [
+        (0, "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26"),
+        (1, "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b"),
+        (2, "5392ddae0e0a69d5f40160462cbd9bd889375082ff224ac9c758802b7a6fd20a"),
+        (3, "39e67b76b5a007d4921969779fe666da67b5213b096084ab674742f0d5ec62b9"),
+        (4, "7671dde590c95d5ac9616651ff5aa0a27bee5913a348e053b8aa9108917fe070"),
+        (5, "73ac69eecf286894d8102018a6fc729f4b1f4247d3703f69bdc6a5fe3e0c8461"),
+        (6, "82d3199d0013035682cc7f2a399d4c212544376a839aa863a0f4c91220ca7a6d"),
+        (7, "af0a7ec382aedc0cfd626e49e7628bc7a353a4cb108855541a5651bf64fbb28a"),
+        (8, "be2f5495c61cba1bb348a34948c004045e3bd4dae8f0fe82bf44d0da245a0600"),
+        (63, "bb1eb5d4afa793c1ebdd9fb08def6c36d10096986ae0cfe148cd101170ce37ae"),
+        (64, "ba8ced36f327700d213f120b1a207a3b8c04330528586f414d09f2f7d9ccb7e6"),
+        (65, "c0a4edefa2d2accb9277c371ac12fcdbb52988a86edc54f0716e1591b4326e72"),
+        (127, "c64200ae7dfaf35577ac5a9521c47863fb71514a3bcad18819218b818de85818"),
+        (128, "b04fe15577457267ff3b6f3c947d93be581e7e3a4b018679125eaf86f6a628ec"),
+        (129, "d4a64dae6cdccbac1e5287f54f17c5f985105457c1a2ec1878ebd4b57e20d38f"),
+        (1023, "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e"),
+        (1024, "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4"),
+        (1025, "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69"),
+        (2048, "879cf1fa2ea0e79126cb1063617a05b6ad9d0b696d0d757cf053439f60a99dd1"),
+        (2049, "9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5"),
+        (3072, "044a0e7b172a312dc02a4c9a818c036ffa2776368d7f528268d2e6b5df191770"),
+        (3073, "68dede9bef00ba89e43f31a6825f4cf433389fedae75c04ee9f0cf16a427c95a"),
+        (4096, "befc660aea2f1718884cd8deb9902811d332f4fc4a38cf7c7300d597a081bfc0"),
+        (4097, "00df940cd36bb9fa7cbbc3556744e0dbc8191401afe70520ba292ee3ca80abbc"),
+        (5120, "2c493e48e9b9bf31e0553a22b23503c0a3388f035cece68eb438d22fa1943e20"),
+        (5121, "6ccf1c34753e7a044db80798ecd0782a8f76f33563accaddbfbb2e0ea4b2d024"),
+        (6144, "3d6b6d21281d0ade5b2b016ae4034c5dec10ca7e475f90f76eac7138e9bc8f1d"),
+        (6145, "9ac301e9e39e45e3250a7e3b3df701aa0fb6889fbd80eeecf28dbc6300fbc539"),
+        (7168, "b42835e40e9d4a7f42ad8cc04f85a963a76e18198377ed84adddeaecacc6f3fc"),
+        (7169, "ed9b1a922c046fdb3d423ae34e143b05ca1bf28b710432857bf738bcedbfa511"),
+        (8192, "dc9637c8845a770b4cbf76b8daec0eebf7dc2eac11498517f08d44c8fc00d58a"),
+        (8193, "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5"),
+        (16384, "9e9fc4eb7cf081ea7c47d1807790ed211bfec56aa25bb7037784c13c4b707b0d"),
+        (31744, "efa53b389ab67c593dba624d898d0f7353ab99e4ac9d42302ee64cbf9939a419"),
+        (102400, "1c35d1a5811083fd7119f5d5d1ba027b4d01c0c6c49fb6ff2cf75393ea5db4a7"),
+    ];
+
+    /// First 32 bytes of the `derive_key` column.
+    const DERIVED_KEYS: [(usize, &str); 35] = [
+        (0, "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+        (1, "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c"),
+        (2, "1f166565a7df0098ee65922d7fea425fb18b9943f19d6161e2d17939356168e6"),
+        (3, "440aba35cb006b61fc17c0529255de438efc06a8c9ebf3f2ddac3b5a86705797"),
+        (4, "f46085c8190d69022369ce1a18880e9b369c135eb93f3c63550d3e7630e91060"),
+        (5, "1f24eda69dbcb752847ec3ebb5dd42836d86e58500c7c98d906ecd82ed9ae47f"),
+        (6, "be96b30b37919fe4379dfbe752ae77b4f7e2ab92f7ff27435f76f2f065f6a5f4"),
+        (7, "dc3b6485f9d94935329442916b0d059685ba815a1fa2a14107217453a7fc9f0e"),
+        (8, "2b166978cef14d9d438046c720519d8b1cad707e199746f1562d0c87fbd32940"),
+        (63, "b6451e30b953c206e34644c6803724e9d2725e0893039cfc49584f991f451af3"),
+        (64, "a5c4a7053fa86b64746d4bb688d06ad1f02a18fce9afd3e818fefaa7126bf73e"),
+        (65, "51fd05c3c1cfbc8ed67d139ad76f5cf8236cd2acd26627a30c104dfd9d3ff8a8"),
+        (127, "c91c090ceee3a3ac81902da31838012625bbcd73fcb92e7d7e56f78deba4f0c3"),
+        (128, "81720f34452f58a0120a58b6b4608384b5c51d11f39ce97161a0c0e442ca0225"),
+        (129, "938d2d4435be30eafdbb2b7031f7857c98b04881227391dc40db3c7b21f41fc1"),
+        (1023, "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5"),
+        (1024, "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706"),
+        (1025, "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb"),
+        (2048, "7b2945cb4fef70885cc5d78a87bf6f6207dd901ff239201351ffac04e1088a23"),
+        (2049, "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273"),
+        (3072, "050df97f8c2ead654d9bb3ab8c9178edcd902a32f8495949feadcc1e0480c46b"),
+        (3073, "72613c9ec9ff7e40f8f5c173784c532ad852e827dba2bf85b2ab4b76f7079081"),
+        (4096, "1e0d7f3db8c414c97c6307cbda6cd27ac3b030949da8e23be1a1a924ad2f25b9"),
+        (4097, "aca51029626b55fda7117b42a7c211f8c6e9ba4fe5b7a8ca922f34299500ead8"),
+        (5120, "7a7acac8a02adcf3038d74cdd1d34527de8a0fcc0ee3399d1262397ce5817f60"),
+        (5121, "b07f01e518e702f7ccb44a267e9e112d403a7b3f4883a47ffbed4b48339b3c34"),
+        (6144, "2a95beae63ddce523762355cf4b9c1d8f131465780a391286a5d01abb5683a15"),
+        (6145, "379bcc61d0051dd489f686c13de00d5b14c505245103dc040d9e4dd1facab8e5"),
+        (7168, "11c37a112765370c94a51415d0d651190c288566e295d505defdad895dae2237"),
+        (7169, "554b0a5efea9ef183f2f9b931b7497995d9eb26f5c5c6dad2b97d62fc5ac31d9"),
+        (8192, "ad01d7ae4ad059b0d33baa3c01319dcf8088094d0359e5fd45d6aeaa8b2d0c3d"),
+        (8193, "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1"),
+        (16384, "160e18b5878cd0df1c3af85eb25a0db5344d43a6fbd7a8ef4ed98d0714c3f7e1"),
+        (31744, "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e"),
+        (102400, "4652cff7a3f385a6103b5c260fc1593e13c778dbe608efb092fe7ee69df6e9c6"),
+    ];
+
+    #[test]
+    fn test_official_vectors() {
+        for (len, expected) in HASHES {
+            let mut hasher = Blake3::new();
+            hasher.update(&input(len));
+            assert_eq!(hasher.hexdigest(32), expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_official_extended_output_0() {
+        let mut hasher = Blake3::new();
+        hasher.update(&input(0));
+        assert_eq!(
+            hasher.hexdigest(131),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262\
+             e00f03e7b69af26b7faaf09fcd333050338ddfe085b8cc869ca98b206c08243a\
+             26f5487789e8f660afe6c99ef9e0c52b92e7393024a80459cf91f476f9ffdbda\
+             7001c22e159b402631f277ca96f2defdf1078282314e763699a31c5363165421\
+             cce14d"
+        );
+    }
+
+    #[test]
+    fn test_official_extended_output_102400() {
+        let mut hasher = Blake3::new();
+        hasher.update(&input(102400));
+        assert_eq!(
+            hasher.hexdigest(131),
+            "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085\
+             e01c59dab908c04c3342b816941a26d69c2605ebee5ec5291cc55e15b76146e6\
+             745f0601156c3596cb75065a9c57f35585a52e1ac70f69131c23d611ce11ee4a\
+             b1ec2c009012d236648e77be9295dd0426f29b764d65de58eb7d01dd42248204\
+             f45f8e"
+        );
+    }
+
+    #[test]
+    fn test_official_keyed_vectors() {
+        for (len, expected) in KEYED_HASHES {
+            let mut hasher = Blake3::new_keyed(KEY);
+            hasher.update(&input(len));
+            assert_eq!(hasher.hexdigest(32), expected, "length {}", len);
+        }
+        let mut hasher = Blake3::new_keyed(KEY);
+        hasher.update(&input(1025));
+        assert_eq!(
+            hasher.hexdigest(131),
+            "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69\
+             362396b77fdc0d2634a552970843722066c3c15902ae5097e00ff53f1e116f1c\
+             d5352720113a837ab2452cafbde4d54085d9cf5d21ca613071551b25d52e69d6\
+             c81123872b6f19cd3bc1333edf0c52b94de23ba772cf82636cff4542540a7738\
+             d5b930"
+        );
+    }
+
+    #[test]
+    fn test_official_derive_key_vectors() {
+        for (len, expected) in DERIVED_KEYS {
+            let mut hasher = Blake3::new_derive_key(CONTEXT);
+            hasher.update(&input(len));
+            assert_eq!(hasher.hexdigest(32), expected, "length {}", len);
+        }
+        let mut hasher = Blake3::new_derive_key(CONTEXT);
+        hasher.update(&input(1025));
+        assert_eq!(
+            hasher.hexdigest(131),
+            "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb\
+             5d31013a167509e9066273ab6e2123bc835b408b067d88f96addb550d96b6852\
+             dad38e320b9d940f86db74d398c770f462118b35d2724efa13da97194491d96d\
+             d37c3c09cbef665953f2ee85ec83d88b88d11547a6f911c8217cca46defa2751\
+             e7f3ad"
+        );
+    }
+
+    #[test]
+    fn test_split_updates_across_chunk_boundaries() {
+        let data = input(8193);
+        let mut whole = Blake3::new();
+        whole.update(&data);
+        for step in [1, 63, 64, 65, 1023, 1024, 1025, 4096] {
+            let mut split = Blake3::new();
+            for piece in data.chunks(step) {
+                split.update(piece);
+            }
+            assert_eq!(split.digest(32), whole.digest(32), "step {}", step);
+        }
+    }
+
+    #[test]
+    fn test_outputs_are_prefixes_and_reads_continue() {
+        let mut hasher = Blake3::new();
+        hasher.update(b"abc");
+        let long = hasher.digest(300);
+        for length in [0, 1, 32, 64, 65, 299] {
+            assert_eq!(hasher.digest(length), &long[..length]);
+        }
+        let mut reader = hasher.finalize_xof();
+        let mut pieces = reader.read_vec(10);
+        pieces.extend(reader.read_vec(100));
+        pieces.extend(reader.read_vec(190));
+        assert_eq!(pieces, long);
+    }
+
+    #[test]
+    fn test_reset_keeps_mode() {
+        let mut hasher = Blake3::new();
+        hasher.update(&input(5000));
+        hasher.reset();
+        assert_eq!(hasher.hexdigest(32), HASHES[0].1);
+        let mut keyed = Blake3::new_keyed(KEY);
+        keyed.update(&input(5000));
+        keyed.reset();
+        assert_eq!(keyed.hexdigest(32), KEYED_HASHES[0].1);
+    }
+
+    /// Plain hashes of the test vector pattern for sizes large enough to
+    /// take the parallel path.
+    const LARGE_HASHES: [(usize, &str); 6] = [
+        (131072, "306baba93b1a393cbd35172837c98b0f59a41f64e1b2682ae102d8b2534b9e1c"),
+        (131073, "f837d4254d24ba3d50fe3743d46e4af6db5f5d6ab0469197d94e7ba1e906c4d8"),
+        (1048575, "f32b849d19684c18c138cf13e29dbadc776f4bc2a56b477680ef546b39ac3f71"),
+        (1048576, "74cb441fd087764ca9c3694da742ebe30cbeb3060a17009ca81825c7a8d10343"),
+        (1048577, "2f053cd7472cf0cd2f9adaf45c1180255b91b9a865404a63671a0ee5f792ed33"),
+        (5000000, "1ab09ef1af116159d8d394fc5a13c25842753b617048329dc8874051d1b9e171"),
+    ];
+
+    #[test]
+    fn test_large_inputs_parallel_and_sequential() {
+        for (len, expected) in LARGE_HASHES {
+            let data = input(len);
+            for max_threads in [None, Some(1), Some(3)] {
+                let mut hasher = Blake3::new();
+                hasher.set_max_threads(max_threads).unwrap();
+                hasher.update(&data);
+                assert_eq!(hasher.hexdigest(32), expected, "len {} {:?}", len, max_threads);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential_for_unaligned_updates() {
+        let data = input(600_000);
+        let mut sizes = Vec::new();
+        for shift in [17, 18, 19] {
+            sizes.extend([(1 << shift) - 1, 1 << shift, (1 << shift) + 1]);
+        }
+        sizes.extend([PARALLEL_THRESHOLD + CHUNK_LEN - 1, 3 * PARALLEL_THRESHOLD + 5]);
+        for prefix in [0, 1, 1023, 1024, 1025, 5000, 65536] {
+            for &size in &sizes {
+                let mut sequential = Blake3::new_derive_key(CONTEXT);
+                sequential.set_max_threads(Some(1)).unwrap();
+                let mut parallel = Blake3::new_derive_key(CONTEXT);
+                for hasher in [&mut sequential, &mut parallel] {
+                    hasher.update(&data[..prefix]);
+                    hasher.update(&data[prefix..prefix + size]);
+                    hasher.update(b"tail");
+                }
+                assert_eq!(
+                    parallel.digest(64),
+                    sequential.digest(64),
+                    "prefix {} size {}",
+                    prefix,
+                    size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_max_threads_rejects_zero() {
+        let err = Blake3::new().set_max_threads(Some(0)).unwrap_err();
+        assert_eq!(err.message, "max_threads must be at least 1");
+    }
+
+    #[test]
+    fn test_set_max_threads_clamps_to_cores() {
+        let data = vec![0x5au8; 1 << 20];
+        let mut hasher = Blake3::new();
+        hasher.set_max_threads(Some(usize::MAX)).unwrap();
+        hasher.update(&data);
+        let mut sequential = Blake3::new();
+        sequential.set_max_threads(Some(1)).unwrap();
+        sequential.update(&data);
+        assert_eq!(hasher.digest(32), sequential.digest(32));
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn bench_blake3_thread_scaling() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let data = vec![0xa5u8; 256 << 20];
+        let mut baseline = None;
+        for threads in [1, 2, 4] {
+            let mut hasher = Blake3::new();
+            hasher.set_max_threads(Some(threads)).unwrap();
+            let start = Instant::now();
+            hasher.update(black_box(&data));
+            black_box(hasher.digest(32));
+            let time = start.elapsed();
+            let single = *baseline.get_or_insert(time);
+            println!(
+                "{} thread(s): {:?} ({:.2} GiB/s), speedup: {:.1}x",
+                threads,
+                time,
+                0.25 / time.as_secs_f64(),
+                single.as_secs_f64() / time.as_secs_f64()
+            );
+        }
+    }
+}
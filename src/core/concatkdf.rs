@@ -0,0 +1,196 @@
+//! One-step counter-hash key derivation: ConcatKDF and the ANSI X9.63 KDF.
+//!
+//! Pure Rust implementations over any [`Digest`] of the hash-based one-step
+//! KDF of NIST SP 800-56C (the "Concat KDF" of JOSE, RFC 7518) and of the
+//! ANSI X9.63 KDF (SEC 1, section 3.6.1). Both hash a 32-bit big-endian
+//! counter starting at 1 with the shared secret, and differ in where the
+//! counter goes:
+//!
+//! ```text
+//! ConcatKDF: K(i) = H(i || Z || OtherInfo)
+//! X9.63:     K(i) = H(Z || i || SharedInfo)
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **Counter**: 32 bits, big-endian, starting at 1
+//! - **Output size**: up to (2^32 - 1) * `HashLen` bytes
+//! - **JOSE OtherInfo**: length-prefixed `alg`, `apu` and `apv`, then the
+//!   key size in bits ([`jose_other_info`])
+
+use super::{Digest, ParameterError};
+
+/// Concatenates `H(counter || ...)` blocks built by `block` until `length`
+/// bytes are produced.
+fn derive<D: Digest>(
+    hasher: D,
+    algorithm: &'static str,
+    length: usize,
+    block: impl Fn(&mut D, &[u8; 4]),
+) -> Result<Vec<u8>, ParameterError> {
+    let hash_len = hasher.digest_size();
+    if length.div_ceil(hash_len) > u32::MAX as usize {
+        return Err(ParameterError {
+            algorithm,
+            message: format!("length must be at most (2^32 - 1) * {} bytes", hash_len),
+        });
+    }
+    let mut out = Vec::with_capacity(length);
+    for counter in 1..=length.div_ceil(hash_len) as u32 {
+        let mut h = hasher.clone();
+        block(&mut h, &counter.to_be_bytes());
+        let digest = h.digest();
+        let take = (length - out.len()).min(hash_len);
+        out.extend_from_slice(&digest.as_ref()[..take]);
+    }
+    Ok(out)
+}
+
+/// Feeds `data` into `hasher`.
+fn absorb<D: Digest>(hasher: &mut D, data: &[u8]) {
+    hasher.try_update(data).expect("inputs fit in memory, so below the length limit");
+}
+
+/// ConcatKDF (SP 800-56C one-step KDF with a hash): derives `length`
+/// bytes from the shared secret `z` and `other_info`.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `length` exceeds (2^32 - 1) * `HashLen`.
+pub fn concat_kdf<D: Digest>(
+    hasher: D,
+    z: &[u8],
+    other_info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    derive(hasher, "ConcatKDF", length, |h, counter| {
+        absorb(h, counter);
+        absorb(h, z);
+        absorb(h, other_info);
+    })
+}
+
+/// ANSI X9.63 KDF: derives `length` bytes from the shared secret `z` and
+/// `shared_info`.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `length` exceeds (2^32 - 1) * `HashLen`.
+pub fn x963_kdf<D: Digest>(
+    hasher: D,
+    z: &[u8],
+    shared_info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    derive(hasher, "X9.63 KDF", length, |h, counter| {
+        absorb(h, z);
+        absorb(h, counter);
+        absorb(h, shared_info);
+    })
+}
+
+/// Builds the ConcatKDF OtherInfo of JOSE ECDH-ES (RFC 7518, section
+/// 4.6.2): `AlgorithmID || PartyUInfo || PartyVInfo || SuppPubInfo`.
+///
+/// The first three are the 32-bit big-endian length followed by the bytes
+/// of `algorithm_id`, `apu` and `apv`; SuppPubInfo is `keydatalen`, the
+/// size of the derived key in bits.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if a field is longer than 2^32 - 1 bytes.
+pub fn jose_other_info(
+    algorithm_id: &[u8],
+    apu: &[u8],
+    apv: &[u8],
+    keydatalen: u32,
+) -> Result<Vec<u8>, ParameterError> {
+    let mut other_info = Vec::with_capacity(16 + algorithm_id.len() + apu.len() + apv.len());
+    for field in [algorithm_id, apu, apv] {
+        let len = u32::try_from(field.len()).map_err(|_| ParameterError {
+            algorithm: "ConcatKDF",
+            message: "OtherInfo fields must be shorter than 2^32 bytes".to_owned(),
+        })?;
+        other_info.extend_from_slice(&len.to_be_bytes());
+        other_info.extend_from_slice(field);
+    }
+    other_info.extend_from_slice(&keydatalen.to_be_bytes());
+    Ok(other_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Sha1, Sha256, Sha512};
+    use crate::utils::to_hex;
+
+    fn unhex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_concat_kdf_rfc7518() {
+        // ECDH-ES example of RFC 7518, appendix C
+        let z = [
+            158, 86, 217, 29, 129, 113, 53, 211, 114, 131, 66, 131, 191, 132, 38, 156, 251, 49,
+            110, 163, 218, 128, 106, 72, 246, 218, 167, 121, 140, 254, 144, 196,
+        ];
+        let other_info = jose_other_info(b"A128GCM", b"Alice", b"Bob", 128).unwrap();
+        assert_eq!(
+            to_hex(&other_info),
+            "000000074131323847434d00000005416c69636500000003426f6200000080"
+        );
+        let key = concat_kdf(Sha256::new(), &z, &other_info, 16).unwrap();
+        assert_eq!(key, [86, 170, 141, 234, 248, 35, 109, 32, 92, 34, 40, 205, 113, 167, 16, 26]);
+    }
+
+    #[test]
+    fn test_x963_kdf_cavs() {
+        // NIST CAVS vectors for the ANSI X9.63 KDF
+        let key = x963_kdf(
+            Sha1::new(),
+            &unhex("1c7d7b5f0597b03d06a018466ed1a93e30ed4b04dc64ccdd"),
+            b"",
+            16,
+        );
+        assert_eq!(to_hex(&key.unwrap()), "bf71dffd8f4d99223936beb46fee8ccc");
+        let key = x963_kdf(
+            Sha256::new(),
+            &unhex("96c05619d56c328ab95fe84b18264b08725b85e33fd34f08"),
+            b"",
+            16,
+        );
+        assert_eq!(to_hex(&key.unwrap()), "443024c3dae66b95e6f5670601558f71");
+        let key = x963_kdf(
+            Sha256::new(),
+            &unhex("22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d"),
+            &unhex("75eef81aa3041e33b80971203d2c0c52"),
+            128,
+        );
+        assert_eq!(
+            to_hex(&key.unwrap()),
+            "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e\
+             52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485\
+             500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269\
+             142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21"
+        );
+    }
+
+    #[test]
+    fn test_counter_placement() {
+        // The same inputs give different keys, and truncation keeps prefixes
+        let concat = concat_kdf(Sha512::new(), b"z", b"info", 100).unwrap();
+        let x963 = x963_kdf(Sha512::new(), b"z", b"info", 100).unwrap();
+        assert_ne!(concat, x963);
+        assert_eq!(concat_kdf(Sha512::new(), b"z", b"info", 10).unwrap(), concat[..10]);
+        assert_eq!(x963_kdf(Sha512::new(), b"z", b"info", 70).unwrap(), x963[..70]);
+        assert!(concat_kdf(Sha256::new(), b"z", b"", 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! cSHAKE customizable extendable-output functions.
+//!
+//! Pure Rust implementation following NIST SP 800-185. cSHAKE is SHAKE with
+//! a function-name string `N` and a customization string `S` absorbed
+//! first, so that differently customized instances give unrelated outputs.
+//!
+//! # Algorithm Details
+//!
+//! - **Prefix**: `bytepad(encode_string(N) || encode_string(S), rate)`
+//! - **Domain suffix**: `00` (byte `0x04`), or SHAKE's `1111` when both `N`
+//!   and `S` are empty, in which case cSHAKE is exactly SHAKE
+//! - **Rates**: 168 bytes (cSHAKE128) and 136 bytes (cSHAKE256)
+//!
+//! The SP 800-185 encodings defined here are shared with the other
+//! SP 800-185 functions.
+
+use super::keccak::{Sponge, XofReader};
+use super::{LengthOverflowError, Xof};
+use crate::utils::to_hex;
+
+/// Domain separation byte of cSHAKE with a non-empty `N` or `S`.
+const CSHAKE_DOMAIN: u8 = 0x04;
+
+/// Domain separation byte of SHAKE, used when `N` and `S` are both empty.
+const SHAKE_DOMAIN: u8 = 0x1f;
+
+/// `left_encode(x)`: the minimal big-endian encoding of `x`, preceded by
+/// its length in bytes (SP 800-185, 2.3.1).
+pub(crate) fn left_encode(x: u64) -> ([u8; 9], usize) {
+    let mut out = [0u8; 9];
+    let n = (8 - x.leading_zeros() as usize / 8).max(1);
+    out[0] = n as u8;
+    out[1..=n].copy_from_slice(&x.to_be_bytes()[8 - n..]);
+    (out, n + 1)
+}
+
+/// `right_encode(x)`: the minimal big-endian encoding of `x`, followed by
+/// its length in bytes (SP 800-185, 2.3.1).
+pub(crate) fn right_encode(x: u64) -> ([u8; 9], usize) {
+    let mut out = [0u8; 9];
+    let n = (8 - x.leading_zeros() as usize / 8).max(1);
+    out[..n].copy_from_slice(&x.to_be_bytes()[8 - n..]);
+    out[n] = n as u8;
+    (out, n + 1)
+}
+
+/// Absorbs `encode_string(s)`: `left_encode` of the bit length, then `s`.
+///
+/// Returns the number of bytes absorbed.
+pub(crate) fn absorb_encoded_string(sponge: &mut Sponge, s: &[u8]) -> usize {
+    let (prefix, len) = left_encode(s.len() as u64 * 8);
+    sponge.absorb(&prefix[..len]);
+    sponge.absorb(s);
+    len + s.len()
+}
+
+/// Creates the sponge of a cSHAKE instance with `N` and `S` absorbed:
+/// `bytepad(encode_string(N) || encode_string(S), rate)`.
+pub(crate) fn customized_sponge(
+    rate: usize,
+    function_name: &[u8],
+    customization: &[u8],
+) -> Sponge {
+    let mut sponge = Sponge::new(rate, 24);
+    let (prefix, len) = left_encode(rate as u64);
+    sponge.absorb(&prefix[..len]);
+    let mut absorbed = len;
+    absorbed += absorb_encoded_string(&mut sponge, function_name);
+    absorbed += absorb_encoded_string(&mut sponge, customization);
+    // Zero padding to a multiple of the rate leaves the state unchanged,
+    // but the block still has to be permuted.
+    let padding = (rate - absorbed % rate) % rate;
+    sponge.absorb(&vec![0u8; padding]);
+    sponge
+}
+
+/// cSHAKE hasher with a rate of `RATE` bytes.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct CShake<const RATE: usize> {
+    sponge: Sponge,
+    /// Sponge right after the customization prefix, restored by `reset()`.
+    initial: Sponge,
+    domain: u8,
+}
+
+/// cSHAKE128 hasher.
+pub type CShake128 = CShake<168>;
+
+/// cSHAKE256 hasher.
+pub type CShake256 = CShake<136>;
+
+impl<const RATE: usize> CShake<RATE> {
+    /// Creates a new hasher with function-name string `function_name` (`N`)
+    /// and customization string `customization` (`S`).
+    ///
+    /// `N` is reserved for functions defined by NIST; applications should
+    /// leave it empty and use `S`. With both empty this is plain SHAKE.
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        let (sponge, domain) = if function_name.is_empty() && customization.is_empty() {
+            (Sponge::new(RATE, 24), SHAKE_DOMAIN)
+        } else {
+            (customized_sponge(RATE, function_name, customization), CSHAKE_DOMAIN)
+        };
+        CShake { initial: sponge.clone(), sponge, domain }
+    }
+
+    /// Feeds data into the hasher. cSHAKE has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns a reader over the output stream for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> XofReader {
+        self.sponge.finalize(self.domain)
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        self.finalize_xof().read_vec(length)
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its customized initial state, discarding any
+    /// data fed so far.
+    pub fn reset(&mut self) {
+        self.sponge = self.initial.clone();
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize> Xof for CShake<RATE> {
+    /// Never fails: cSHAKE has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Shake128, Shake256};
+
+    #[test]
+    fn test_encodings() {
+        let (out, len) = left_encode(0);
+        assert_eq!(&out[..len], &[1, 0]);
+        let (out, len) = left_encode(168);
+        assert_eq!(&out[..len], &[1, 168]);
+        let (out, len) = left_encode(256);
+        assert_eq!(&out[..len], &[2, 1, 0]);
+        let (out, len) = right_encode(0);
+        assert_eq!(&out[..len], &[0, 1]);
+        let (out, len) = right_encode(u64::MAX);
+        assert_eq!(&out[..len], &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 8]);
+    }
+
+    #[test]
+    fn test_cshake128_sp800_185_samples() {
+        let mut hasher = CShake128::new(b"", b"Email Signature");
+        hasher.update(&[0x00, 0x01, 0x02, 0x03]);
+        assert_eq!(
+            hasher.hexdigest(32),
+            "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5"
+        );
+
+        let data: Vec<u8> = (0..200u8).collect();
+        let mut hasher = CShake128::new(b"", b"Email Signature");
+        hasher.update(&data);
+        assert_eq!(
+            hasher.hexdigest(32),
+            "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b"
+        );
+    }
+
+    #[test]
+    fn test_cshake256_sp800_185_samples() {
+        let mut hasher = CShake256::new(b"", b"Email Signature");
+        hasher.update(&[0x00, 0x01, 0x02, 0x03]);
+        assert_eq!(
+            hasher.hexdigest(64),
+            "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1\
+             64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c"
+        );
+
+        let data: Vec<u8> = (0..200u8).collect();
+        let mut hasher = CShake256::new(b"", b"Email Signature");
+        hasher.update(&data);
+        assert_eq!(
+            hasher.hexdigest(64),
+            "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917\
+             27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb"
+        );
+    }
+
+    #[test]
+    fn test_cshake_without_strings_is_shake() {
+        let mut cshake = CShake128::new(b"", b"");
+        cshake.update(b"abc");
+        let mut shake = Shake128::new();
+        shake.update(b"abc");
+        assert_eq!(cshake.digest(64), shake.digest(64));
+
+        let mut cshake = CShake256::new(b"", b"");
+        cshake.update(b"abc");
+        let mut shake = Shake256::new();
+        shake.update(b"abc");
+        assert_eq!(to_hex(&cshake.digest(64)), shake.hexdigest(64));
+    }
+
+    #[test]
+    fn test_cshake_reset_keeps_customization() {
+        let mut hasher = CShake128::new(b"", b"Email Signature");
+        hasher.update(b"discarded");
+        hasher.reset();
+        hasher.update(&[0x00, 0x01, 0x02, 0x03]);
+        assert_eq!(
+            hasher.hexdigest(32),
+            "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! GOST R 34.11-94 hash function implementation.
+//!
+//! Pure Rust implementation of the pre-2012 Russian hash standard
+//! (GOST R 34.11-94, RFC 5831), replaced by Streebog but still needed to
+//! check signatures on archived documents. Each 256-bit block `m` updates
+//! the chaining value `h` in three stages: four keys are derived from `h`
+//! and `m`, each quarter of `h` is encrypted with the GOST 28147-89 block
+//! cipher under one key, and the result is mixed with `m` and `h` through
+//! the linear shift register ψ. A 256-bit sum of all blocks and the bit
+//! length are compressed last.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 256 bits (32 bytes)
+//! - **Digest size**: 256 bits (32 bytes)
+//! - **Cipher**: GOST 28147-89, 32 rounds, with the S-boxes of the chosen
+//!   parameter set ([`Gost94SBox`])
+//! - **Byte order**: little-endian, as in RFC 5831
+//! - **Padding**: a partial last block is completed with zeros; the length
+//!   block then records the true bit length
+//!
+//! # Security
+//!
+//! GOST R 34.11-94 has a theoretical collision attack (2^105) and is
+//! withdrawn in Russia since 2013. Use it only to verify legacy data.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// GOST 28147-89 S-boxes of the "test" parameter set (RFC 5831, section
+/// 11), lowest nibble first.
+const SBOX_TEST: [[u8; 16]; 8] = [
+    [4, 10, 9, 2, 13, 8, 0, 14, 6, 11, 1, 12, 7, 15, 5, 3],
+    [14, 11, 4, 12, 6, 13, 15, 10, 2, 3, 8, 1, 0, 7, 5, 9],
+    [5, 8, 1, 13, 10, 3, 4, 2, 14, 15, 12, 7, 6, 0, 9, 11],
+    [7, 13, 10, 1, 0, 8, 9, 15, 14, 4, 6, 12, 11, 2, 5, 3],
+    [6, 12, 7, 1, 5, 15, 13, 8, 4, 10, 9, 14, 0, 3, 11, 2],
+    [4, 11, 10, 0, 7, 2, 1, 13, 3, 6, 8, 5, 9, 12, 15, 14],
+    [13, 11, 4, 1, 3, 15, 5, 9, 0, 10, 14, 7, 6, 8, 2, 12],
+    [1, 15, 13, 0, 5, 7, 10, 4, 9, 2, 3, 14, 6, 11, 8, 12],
+];
+
+/// GOST 28147-89 S-boxes of id-GostR3411-94-CryptoProParamSet (RFC 4357,
+/// section 11.2), lowest nibble first.
+const SBOX_CRYPTOPRO: [[u8; 16]; 8] = [
+    [10, 4, 5, 6, 8, 1, 3, 7, 13, 12, 14, 0, 9, 2, 11, 15],
+    [5, 15, 4, 0, 2, 13, 11, 9, 1, 7, 6, 3, 12, 14, 10, 8],
+    [7, 15, 12, 14, 9, 4, 1, 0, 3, 11, 5, 2, 6, 10, 8, 13],
+    [4, 10, 7, 12, 0, 15, 2, 8, 14, 1, 6, 5, 13, 11, 9, 3],
+    [7, 6, 4, 11, 9, 12, 2, 10, 1, 8, 0, 14, 15, 13, 3, 5],
+    [7, 6, 2, 4, 13, 9, 15, 0, 10, 1, 5, 11, 8, 14, 12, 3],
+    [13, 14, 4, 1, 7, 0, 5, 10, 3, 12, 8, 15, 6, 2, 9, 11],
+    [1, 3, 10, 9, 5, 11, 4, 15, 8, 6, 7, 14, 13, 0, 2, 12],
+];
+
+/// Constant XORed into the third key (C3 of RFC 5831, section 6.1), as
+/// little-endian bytes.
+const C3: [u8; 32] = [
+    0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00,
+    0x00, 0xff, 0xff, 0x00, 0xff, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0x00, 0xff, 0xff, 0x00, 0xff,
+];
+
+/// Builds the cipher tables: `tables[i][x]` substitutes byte `i` of the
+/// round input, two S-boxes at once, and applies the 11-bit rotation.
+const fn build_tables(sbox: &[[u8; 16]; 8]) -> [[u32; 256]; 4] {
+    let mut tables = [[0u32; 256]; 4];
+    let mut i = 0;
+    while i < 4 {
+        let mut x = 0;
+        while x < 256 {
+            let low = sbox[2 * i][x & 15] as u32;
+            let high = sbox[2 * i + 1][x >> 4] as u32;
+            tables[i][x] = ((high << 4 | low) << (8 * i)).rotate_left(11);
+            x += 1;
+        }
+        i += 1;
+    }
+    tables
+}
+
+/// Cipher tables of the "test" parameter set.
+static TABLES_TEST: [[u32; 256]; 4] = build_tables(&SBOX_TEST);
+
+/// Cipher tables of the CryptoPro parameter set.
+static TABLES_CRYPTOPRO: [[u32; 256]; 4] = build_tables(&SBOX_CRYPTOPRO);
+
+/// S-box parameter set of the GOST 28147-89 cipher inside the hash.
+///
+/// The two sets give unrelated digests; documents name the one they use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gost94SBox {
+    /// The "test" parameters of the standard, used by the RFC 5831 examples.
+    Test,
+    /// The CryptoPro parameters (RFC 4357), used by most deployed software.
+    CryptoPro,
+}
+
+/// GOST R 34.11-94 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Gost94 {
+    state: [u8; 32],
+    sum: [u8; 32],         // Sum of all blocks modulo 2^256 (little-endian)
+    buffer: [u8; 32],      // Fixed 32-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u128,       // Total bytes processed (for final length)
+    sbox: Gost94SBox,
+}
+
+impl Gost94 {
+    /// Creates a new hasher with the "test" parameter set.
+    pub fn new() -> Self {
+        Self::with_sbox(Gost94SBox::Test)
+    }
+
+    /// Creates a new hasher with the given S-box parameter set.
+    pub fn with_sbox(sbox: Gost94SBox) -> Self {
+        Gost94 {
+            state: [0u8; 32],
+            sum: [0u8; 32],
+            buffer: [0u8; 32],
+            buffer_len: 0,
+            total_len: 0,
+            sbox,
+        }
+    }
+
+    /// Returns the S-box parameter set of this hasher.
+    pub fn sbox(&self) -> Gost94SBox {
+        self.sbox
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 256-bit blocks immediately with zero-copy
+    /// streaming. Only incomplete blocks (< 32 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        self.total_len += data.len() as u128;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(32 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 32 {
+                let block = self.buffer;
+                self.process_block(&block);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 32-byte blocks directly from input
+        while offset + 32 <= data.len() {
+            let block: [u8; 32] = data[offset..offset + 32].try_into().unwrap();
+            self.process_block(&block);
+            offset += 32;
+        }
+
+        // Buffer any remaining bytes (< 32)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Finalization runs on a copy of the state, so the hasher can keep
+    /// absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = self.clone();
+
+        // Zero-padded last block, then the bit length, then the block sum
+        if self.buffer_len > 0 {
+            let mut block = [0u8; 32];
+            block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+            hasher.process_block(&block);
+        }
+        let mut length = [0u8; 32];
+        length[..16].copy_from_slice(&self.total_len.wrapping_mul(8).to_le_bytes());
+        hasher.compress(&length);
+        let sum = hasher.sum;
+        hasher.compress(&sum);
+        hasher.state
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 32] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so
+    /// far. The parameter set is kept.
+    pub fn reset(&mut self) {
+        *self = Self::with_sbox(self.sbox);
+    }
+
+    /// Compresses a message block and adds it to the block sum.
+    fn process_block(&mut self, block: &[u8; 32]) {
+        self.compress(block);
+        let mut carry = 0u16;
+        for (sum, &byte) in self.sum.iter_mut().zip(block) {
+            carry += *sum as u16 + byte as u16;
+            *sum = carry as u8;
+            carry >>= 8;
+        }
+    }
+
+    /// Step function: `h = ψ^61(h ^ ψ(m ^ ψ^12(s)))`, where `s` is `h`
+    /// encrypted quarter by quarter under keys derived from `h` and `m`.
+    fn compress(&mut self, block: &[u8; 32]) {
+        let tables = match self.sbox {
+            Gost94SBox::Test => &TABLES_TEST,
+            Gost94SBox::CryptoPro => &TABLES_CRYPTOPRO,
+        };
+
+        // Key generation: K1 from h ^ m, then U = A(U) ^ C, V = A(A(V))
+        let (mut u, mut v) = (self.state, *block);
+        let mut encrypted = [0u8; 32];
+        for i in 0..4 {
+            if i > 0 {
+                u = Self::a(&u);
+                if i == 2 {
+                    u = xor(&u, &C3);
+                }
+                v = Self::a(&Self::a(&v));
+            }
+            let key = Self::p(&xor(&u, &v));
+            let quarter = &self.state[8 * i..8 * i + 8];
+            encrypted[8 * i..8 * i + 8]
+                .copy_from_slice(&Self::encrypt(tables, &key, quarter.try_into().unwrap()));
+        }
+
+        // Mixing through the ψ shift register
+        let mut words = to_words(&encrypted);
+        for _ in 0..12 {
+            psi(&mut words);
+        }
+        let mut words = to_words(&xor(block, &from_words(&words)));
+        psi(&mut words);
+        let mut words = to_words(&xor(&self.state, &from_words(&words)));
+        for _ in 0..61 {
+            psi(&mut words);
+        }
+        self.state = from_words(&words);
+    }
+
+    /// Transformation A: with `y = y4 || y3 || y2 || y1` in 64-bit words,
+    /// returns `(y1 ^ y2) || y4 || y3 || y2`.
+    fn a(y: &[u8; 32]) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        result[..24].copy_from_slice(&y[8..]);
+        for i in 0..8 {
+            result[24 + i] = y[i] ^ y[8 + i];
+        }
+        result
+    }
+
+    /// Transformation P: the byte transposition turning `u ^ v` into a key.
+    fn p(w: &[u8; 32]) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        for i in 0..4 {
+            for k in 0..8 {
+                key[i + 4 * k] = w[8 * i + k];
+            }
+        }
+        key
+    }
+
+    /// Encrypts one 64-bit block with GOST 28147-89 in ECB mode.
+    fn encrypt(tables: &[[u32; 256]; 4], key: &[u8; 32], block: &[u8; 8]) -> [u8; 8] {
+        let mut keys = [0u32; 8];
+        for (k, chunk) in keys.iter_mut().zip(key.chunks_exact(4)) {
+            *k = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        let mut n1 = u32::from_le_bytes(block[..4].try_into().unwrap());
+        let mut n2 = u32::from_le_bytes(block[4..].try_into().unwrap());
+
+        // Keys K1..K8 three times, then K8..K1
+        for round in 0..32 {
+            let k = if round < 24 { keys[round % 8] } else { keys[31 - round] };
+            let x = n1.wrapping_add(k).to_le_bytes();
+            let f = tables[0][x[0] as usize]
+                ^ tables[1][x[1] as usize]
+                ^ tables[2][x[2] as usize]
+                ^ tables[3][x[3] as usize];
+            (n1, n2) = (n2 ^ f, n1);
+        }
+
+        let mut result = [0u8; 8];
+        result[..4].copy_from_slice(&n2.to_le_bytes());
+        result[4..].copy_from_slice(&n1.to_le_bytes());
+        result
+    }
+
+    /// Returns the output size in bytes (32 for GOST R 34.11-94).
+    pub fn digest_size() -> usize {
+        32
+    }
+
+    /// Returns the block size in bytes (32 for GOST R 34.11-94).
+    pub fn block_size() -> usize {
+        32
+    }
+}
+
+/// XORs two 256-bit values.
+fn xor(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    std::array::from_fn(|i| a[i] ^ b[i])
+}
+
+/// Splits a 256-bit value into little-endian 16-bit words, lowest first.
+fn to_words(bytes: &[u8; 32]) -> [u16; 16] {
+    std::array::from_fn(|i| u16::from_le_bytes([bytes[2 * i], bytes[2 * i + 1]]))
+}
+
+/// Joins little-endian 16-bit words back into a 256-bit value.
+fn from_words(words: &[u16; 16]) -> [u8; 32] {
+    std::array::from_fn(|i| words[i / 2].to_le_bytes()[i % 2])
+}
+
+/// Transformation ψ: with `y = y16 || ... || y1` in 16-bit words, returns
+/// `(y1 ^ y2 ^ y3 ^ y4 ^ y13 ^ y16) || y16 || ... || y2`.
+fn psi(y: &mut [u16; 16]) {
+    let feedback = y[0] ^ y[1] ^ y[2] ^ y[3] ^ y[12] ^ y[15];
+    y.rotate_left(1);
+    y[15] = feedback;
+}
+
+impl Default for Gost94 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Gost94 {
+    type Output = [u8; 32];
+
+    /// Never fails: the 256-bit length block covers any real message.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        Gost94::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Gost94::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Gost94::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gost_hex(sbox: Gost94SBox, data: &[u8]) -> String {
+        let mut hasher = Gost94::with_sbox(sbox);
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_gost94_rfc5831_examples() {
+        // RFC 5831, section 10: a 32-byte and a 50-byte message
+        assert_eq!(
+            gost_hex(Gost94SBox::Test, b"This is message, length=32 bytes"),
+            "b1c466d37519b82e8319819ff32595e047a28cb6f83eff1c6916a815a637fffa"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::Test, b"Suppose the original message has length = 50 bytes"),
+            "471aba57a60a770d3a76130635c1fbea4ef14de51f78b4ae57dd893b62f55208"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::Test, b""),
+            "ce85b99cc46752fffee35cab9a7b0278abb4c2d2055cff685af4912c49490f8d"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::Test, b"abc"),
+            "f3134348c44fb1b2a277729e2285ebb5cb5e0f29c975bc753b70497c06a4d51d"
+        );
+    }
+
+    #[test]
+    fn test_gost94_cryptopro() {
+        assert_eq!(
+            gost_hex(Gost94SBox::CryptoPro, b"This is message, length=32 bytes"),
+            "2cefc2f7b7bdc514e18ea57fa74ff357e7fa17d652c75f69cb1be7893ede48eb"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::CryptoPro, b"Suppose the original message has length = 50 bytes"),
+            "c3730c5cbccacf915ac292676f21e8bd4ef75331d9405e5f1a61dc3130a65011"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::CryptoPro, b""),
+            "981e5f3ca30c841487830f84fb433e13ac1101569b9c13584ac483234cd656c0"
+        );
+        assert_eq!(
+            gost_hex(Gost94SBox::CryptoPro, b"abc"),
+            "b285056dbf18d7392d7677369524dd14747459ed8143997e163b2986f92fd42c"
+        );
+    }
+
+    #[test]
+    fn test_gost94_streaming_matches_one_shot() {
+        // 128 bytes of 'U' carry through the block sum
+        let data = [b'U'; 128];
+        assert_eq!(
+            gost_hex(Gost94SBox::Test, &data),
+            "53a3a3ed25180cef0c1d85a074273e551c25660a87062a52d926a9e8fe5733a4"
+        );
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 31, 32, 33, 100] {
+            let mut hasher = Gost94::with_sbox(Gost94SBox::CryptoPro);
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            let expected = gost_hex(Gost94SBox::CryptoPro, &data);
+            assert_eq!(hasher.hexdigest(), expected, "step {}", step);
+        }
+        let mut hasher = Gost94::with_sbox(Gost94SBox::CryptoPro);
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.sbox(), Gost94SBox::CryptoPro);
+        assert_eq!(hasher.digest(), Gost94::with_sbox(Gost94SBox::CryptoPro).digest());
+    }
+}
@@ -0,0 +1,375 @@
+//! Grøstl hash function implementation.
+//!
+//! Pure Rust implementation of the final-round (tweaked) version of Grøstl,
+//! the SHA-3 finalist built from two AES-like permutations P and Q. The
+//! chaining value is twice as wide as the digest (wide-pipe): each block
+//! `m` updates it as `P(h ^ m) ^ Q(m) ^ h`, and the output transform
+//! truncates `P(h) ^ h` to the digest size.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits for [`Groestl256`], 1024 bits for [`Groestl512`]
+//! - **Digest size**: 256 or 512 bits
+//! - **Rounds**: 10 (Grøstl-256) or 14 (Grøstl-512) per permutation
+//! - **Padding**: 0x80, zeros, then the big-endian 64-bit block count
+//!
+//! The state is a matrix of 8 rows and 8 or 16 columns, stored as one
+//! big-endian 64-bit word per column. SubBytes and MixBytes are merged into
+//! eight precomputed 256-entry tables, computed at compile time, and
+//! ShiftBytes is folded into the column each lookup reads from.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// AES S-box (FIPS 197), used by SubBytes.
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// First row of the circulant MixBytes matrix; row `i` is this row rotated
+/// right by `i` positions.
+const MATRIX_ROW: [u8; 8] = [2, 2, 3, 4, 5, 3, 5, 7];
+
+/// Multiplies two elements of GF(2^8) modulo x^8 + x^4 + x^3 + x + 1.
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Builds the round tables: `TABLES[r][x]` is the column produced by MixBytes
+/// from `SBOX[x]` in row `r`, packed big-endian.
+const fn build_tables() -> [[u64; 256]; 8] {
+    let mut tables = [[0u64; 256]; 8];
+    let mut x = 0;
+    while x < 256 {
+        // Output row i takes coefficient MATRIX_ROW[(r - i) mod 8]
+        let mut column = 0u64;
+        let mut i = 0;
+        while i < 8 {
+            column = (column << 8) | gf_mul(SBOX[x], MATRIX_ROW[(8 - i) % 8]) as u64;
+            i += 1;
+        }
+        let mut r = 0;
+        while r < 8 {
+            tables[r][x] = column.rotate_right(8 * r as u32);
+            r += 1;
+        }
+        x += 1;
+    }
+    tables
+}
+
+/// Round tables merging SubBytes and MixBytes.
+static TABLES: [[u64; 256]; 8] = build_tables();
+
+/// Grøstl hasher state, generic over the number of state columns (8 or 16)
+/// and the digest size in bytes.
+///
+/// Use the [`Groestl256`] and [`Groestl512`] aliases. Cloning captures the
+/// full streaming state, so a clone can be finalized or extended
+/// independently of the original.
+#[derive(Clone)]
+pub struct Groestl<const COLUMNS: usize, const OUT: usize> {
+    /// Chaining value as big-endian columns.
+    state: [u64; COLUMNS],
+    buffer: [u8; 128],     // Pending block; only the first COLUMNS * 8 bytes are used
+    buffer_len: usize,     // Number of bytes currently in buffer
+    blocks: u64,           // Message blocks processed (for the final count)
+}
+
+/// Grøstl-256: 512-bit state, 32-byte digest.
+pub type Groestl256 = Groestl<8, 32>;
+
+/// Grøstl-512: 1024-bit state, 64-byte digest.
+pub type Groestl512 = Groestl<16, 64>;
+
+impl<const COLUMNS: usize, const OUT: usize> Groestl<COLUMNS, OUT> {
+    /// Block size in bytes, equal to the state size.
+    const BLOCK_SIZE: usize = COLUMNS * 8;
+
+    /// Rounds of each permutation.
+    const ROUNDS: usize = if COLUMNS == 8 { 10 } else { 14 };
+
+    /// Left rotation of each row in ShiftBytes, for P and for Q.
+    const SHIFTS_P: [usize; 8] = if COLUMNS == 8 {
+        [0, 1, 2, 3, 4, 5, 6, 7]
+    } else {
+        [0, 1, 2, 3, 4, 5, 6, 11]
+    };
+    const SHIFTS_Q: [usize; 8] = if COLUMNS == 8 {
+        [1, 3, 5, 7, 0, 2, 4, 6]
+    } else {
+        [1, 3, 5, 11, 0, 2, 4, 6]
+    };
+
+    /// Creates a new hasher; the initial value encodes the digest size.
+    pub fn new() -> Self {
+        let mut state = [0u64; COLUMNS];
+        state[COLUMNS - 1] = (OUT * 8) as u64;
+        Groestl {
+            state,
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            blocks: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        let block_size = Self::BLOCK_SIZE;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(block_size - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == block_size {
+                let block = self.buffer;
+                self.compress(&block[..block_size]);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete blocks directly from input
+        while offset + block_size <= data.len() {
+            self.compress(&data[offset..offset + block_size]);
+            offset += block_size;
+        }
+
+        // Buffer any remaining bytes
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; OUT] {
+        let block_size = Self::BLOCK_SIZE;
+        let mut hasher = self.clone();
+        let mut block = [0u8; 128];
+
+        // 0x80 byte, zeros, then the total block count on the last 8 bytes
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        let final_blocks = if self.buffer_len < block_size - 8 { 1 } else { 2 };
+        let count = self.blocks.wrapping_add(final_blocks);
+        if final_blocks == 2 {
+            hasher.compress(&block[..block_size]);
+            block = [0u8; 128];
+        }
+        block[block_size - 8..block_size].copy_from_slice(&count.to_be_bytes());
+        hasher.compress(&block[..block_size]);
+
+        // Output transform: the last OUT bytes of P(h) ^ h
+        let mut output = hasher.state;
+        Self::permute(&mut output, false);
+        let bytes: Vec<u8> = output
+            .iter()
+            .zip(hasher.state)
+            .flat_map(|(p, h)| (p ^ h).to_be_bytes())
+            .collect();
+        bytes[block_size - OUT..].try_into().unwrap()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; OUT] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Applies the permutation P, or Q when `q` is set, to `state`.
+    fn permute(state: &mut [u64; COLUMNS], q: bool) {
+        let shifts = if q { Self::SHIFTS_Q } else { Self::SHIFTS_P };
+        for round in 0..Self::ROUNDS {
+            // AddRoundConstant: P alters row 0, Q inverts every byte and
+            // alters row 7
+            for (j, column) in state.iter_mut().enumerate() {
+                let constant = ((j << 4) ^ round) as u64;
+                *column ^= if q { !constant } else { constant << 56 };
+            }
+
+            // SubBytes, ShiftBytes and MixBytes through the tables
+            let input = *state;
+            for (j, column) in state.iter_mut().enumerate() {
+                *column = 0;
+                for (r, table) in TABLES.iter().enumerate() {
+                    let byte = (input[(j + shifts[r]) % COLUMNS] >> (56 - 8 * r)) as u8;
+                    *column ^= table[byte as usize];
+                }
+            }
+        }
+    }
+
+    /// Processes a single block: `h = P(h ^ m) ^ Q(m) ^ h`.
+    fn compress(&mut self, block: &[u8]) {
+        let mut message = [0u64; COLUMNS];
+        for (column, chunk) in message.iter_mut().zip(block.chunks_exact(8)) {
+            *column = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut p = [0u64; COLUMNS];
+        for j in 0..COLUMNS {
+            p[j] = self.state[j] ^ message[j];
+        }
+        Self::permute(&mut p, false);
+        Self::permute(&mut message, true);
+        for j in 0..COLUMNS {
+            self.state[j] ^= p[j] ^ message[j];
+        }
+        self.blocks = self.blocks.wrapping_add(1);
+    }
+
+    /// Returns the output size in bytes (32 or 64).
+    pub fn digest_size() -> usize {
+        OUT
+    }
+
+    /// Returns the block size in bytes (64 or 128).
+    pub fn block_size() -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+impl<const COLUMNS: usize, const OUT: usize> Default for Groestl<COLUMNS, OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const COLUMNS: usize, const OUT: usize> Digest for Groestl<COLUMNS, OUT> {
+    type Output = [u8; OUT];
+
+    /// Never fails: the 64-bit block count covers messages of 2^70 bytes.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; OUT] {
+        Groestl::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        OUT
+    }
+
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groestl256_hex(data: &[u8]) -> String {
+        let mut hasher = Groestl256::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    fn groestl512_hex(data: &[u8]) -> String {
+        let mut hasher = Groestl512::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_groestl256_vectors() {
+        assert_eq!(
+            groestl256_hex(b""),
+            "1a52d11d550039be16107f9c58db9ebcc417f16f736adb2502567119f0083467"
+        );
+        assert_eq!(
+            groestl256_hex(b"The quick brown fox jumps over the lazy dog"),
+            "8c7ad62eb26a21297bc39c2d7293b4bd4d3399fa8afab29e970471739e28b301"
+        );
+        assert_eq!(
+            groestl256_hex(b"The quick brown fox jumps over the lazy dog."),
+            "f48290b1bcacee406a0429b993adb8fb3d065f4b09cbcdb464a631d4a0080aaf"
+        );
+    }
+
+    #[test]
+    fn test_groestl512_vectors() {
+        assert_eq!(
+            groestl512_hex(b""),
+            "6d3ad29d279110eef3adbd66de2a0345a77baede1557f5d099fce0c03d6dc2ba\
+             8e6d4a6633dfbd66053c20faa87d1a11f39a7fbe4a6c2f009801370308fc4ad8"
+        );
+        assert_eq!(
+            groestl512_hex(b"The quick brown fox jumps over the lazy dog"),
+            "badc1f70ccd69e0cf3760c3f93884289da84ec13c70b3d12a53a7a8a4a513f99\
+             715d46288f55e1dbf926e6d084a0538e4eebfc91cf2b21452921ccde9131718d"
+        );
+    }
+
+    #[test]
+    fn test_groestl_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 55, 56, 57, 64, 119, 120, 128] {
+            let mut h256 = Groestl256::new();
+            let mut h512 = Groestl512::new();
+            for chunk in data.chunks(step) {
+                h256.update(chunk);
+                h512.update(chunk);
+            }
+            assert_eq!(h256.hexdigest(), groestl256_hex(&data), "step {}", step);
+            assert_eq!(h512.hexdigest(), groestl512_hex(&data), "step {}", step);
+        }
+        let mut hasher = Groestl256::new();
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Groestl256::new().digest());
+    }
+}
@@ -0,0 +1,312 @@
+//! HAS-160 cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following TTAS.KO-12.0011/R2, the Korean hash
+//! standard paired with the KCDSA signature scheme. HAS-160 keeps the
+//! SHA-1 state, initial value and boolean functions, but uses MD5's
+//! little-endian byte order, a per-step rotation schedule, and a message
+//! expansion that adds four XOR-combined words to each round of 20 steps.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 160 bits (20 bytes)
+//! - **Rounds**: 4 rounds of 20 steps, each over the 16 message words and
+//!   4 extra words
+//! - **Byte order**: little-endian words and length, as in MD5
+//!
+//! # Security
+//!
+//! HAS-160 has known attacks on reduced-step versions and is retired from
+//! Korean PKI. It is provided to verify legacy KCDSA artifacts.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Message words combined into the four extra words of each round: extra
+/// word `i` of round `r` is the XOR of the words `EXTRA_WORDS[r][i]`.
+const EXTRA_WORDS: [[[usize; 4]; 4]; 4] = [
+    [[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11], [12, 13, 14, 15]],
+    [[3, 6, 9, 12], [15, 2, 5, 8], [11, 14, 1, 4], [7, 10, 13, 0]],
+    [[12, 5, 14, 7], [0, 9, 2, 11], [4, 13, 6, 15], [8, 1, 10, 3]],
+    [[7, 2, 13, 8], [3, 14, 9, 4], [15, 10, 5, 0], [11, 6, 1, 12]],
+];
+
+/// Word read by each step of each round; 16 to 19 are the extra words.
+const WORD_ORDER: [[usize; 20]; 4] = [
+    [18, 0, 1, 2, 3, 19, 4, 5, 6, 7, 16, 8, 9, 10, 11, 17, 12, 13, 14, 15],
+    [18, 3, 6, 9, 12, 19, 15, 2, 5, 8, 16, 11, 14, 1, 4, 17, 7, 10, 13, 0],
+    [18, 12, 5, 14, 7, 19, 0, 9, 2, 11, 16, 4, 13, 6, 15, 17, 8, 1, 10, 3],
+    [18, 7, 2, 13, 8, 19, 3, 14, 9, 4, 16, 15, 10, 5, 0, 17, 11, 6, 1, 12],
+];
+
+/// Left rotation of A in each step, the same for every round.
+const STEP_ROTATIONS: [u32; 20] = [
+    5, 11, 7, 15, 6, 13, 8, 14, 7, 12, 9, 11, 8, 15, 6, 12, 9, 14, 5, 13,
+];
+
+/// Left rotation of B in each round (SHA-1 always uses 30).
+const ROUND_ROTATIONS: [u32; 4] = [10, 17, 25, 30];
+
+/// Additive constant of each round.
+const ROUND_CONSTANTS: [u32; 4] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc];
+
+/// HAS-160 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Has160 {
+    state: [u32; 5],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u64,        // Total bytes processed (for final length)
+}
+
+impl Has160 {
+    /// Maximum message length in bytes.
+    ///
+    /// The padding encodes the length in bits as a 64-bit integer, so
+    /// messages are limited to 2^61 - 1 bytes.
+    pub const MAX_MESSAGE_LEN: u64 = (1 << 61) - 1;
+
+    /// HAS-160 initial value, the same as SHA-1's.
+    const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    /// Creates a new HAS-160 hasher with initial state.
+    pub fn new() -> Self {
+        Has160 {
+            state: Self::IV,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The length check happens before any data is absorbed, so the hasher
+    /// is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.total_len = self
+            .total_len
+            .checked_add(data.len() as u64)
+            .filter(|&len| len <= Self::MAX_MESSAGE_LEN)
+            .ok_or(LengthOverflowError { algorithm: "HAS-160" })?;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 20] {
+        let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Same padding as MD5: 0x80, zeros, then the little-endian bit length
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+        block[56..64].copy_from_slice(&bit_len.to_le_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 20];
+        for (i, &word) in state.iter().enumerate() {
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 20] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a single 512-bit block through the HAS-160 compression function.
+    fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+        let mut x = [0u32; 20];
+        for (word, chunk) in x.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = *state;
+        for round in 0..4 {
+            for (i, words) in EXTRA_WORDS[round].iter().enumerate() {
+                x[16 + i] = words.iter().fold(0, |acc, &w| acc ^ x[w]);
+            }
+
+            for (step, &w) in WORD_ORDER[round].iter().enumerate() {
+                let f = match round {
+                    0 => (b & c) | (!b & d),
+                    2 => c ^ (b | !d),
+                    _ => b ^ c ^ d,
+                };
+                let temp = a
+                    .rotate_left(STEP_ROTATIONS[step])
+                    .wrapping_add(f)
+                    .wrapping_add(e)
+                    .wrapping_add(x[w])
+                    .wrapping_add(ROUND_CONSTANTS[round]);
+                e = d;
+                d = c;
+                c = b.rotate_left(ROUND_ROTATIONS[round]);
+                b = a;
+                a = temp;
+            }
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+    }
+
+    /// Returns the output size in bytes (20 for HAS-160).
+    pub fn digest_size() -> usize {
+        20
+    }
+
+    /// Returns the block size in bytes (64 for HAS-160).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Has160 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Has160 {
+    type Output = [u8; 20];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Has160::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 20] {
+        Has160::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Has160::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Has160::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has160_hex(data: &[u8]) -> String {
+        let mut hasher = Has160::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_has160_standard_vectors() {
+        // TTAS.KO-12.0011/R2, appendix
+        assert_eq!(has160_hex(b""), "307964ef34151d37c8047adec7ab50f4ff89762d");
+        assert_eq!(has160_hex(b"a"), "4872bcbc4cd0f0a9dc7c2f7045e5b43b6c830db8");
+        assert_eq!(has160_hex(b"abc"), "975e810488cf2a3d49838478124afce4b1c78804");
+        assert_eq!(has160_hex(b"message digest"), "2338dbc8638d31225f73086246ba529f96710bc6");
+        assert_eq!(
+            has160_hex(b"abcdefghijklmnopqrstuvwxyz"),
+            "596185c9ab6703d0d0dbb98702bc0f5729cd1d3c"
+        );
+        assert_eq!(
+            has160_hex(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"),
+            "cb5d7efbca2f02e0fb7167cabb123af5795764e5"
+        );
+        assert_eq!(
+            has160_hex(&b"1234567890".repeat(8)),
+            "07f05c8c0773c55ca3a5a695ce6aca4c438911b5"
+        );
+    }
+
+    #[test]
+    fn test_has160_million_a() {
+        let mut hasher = Has160::new();
+        let chunk = [b'a'; 1000];
+        for _ in 0..1000 {
+            hasher.update(&chunk);
+        }
+        assert_eq!(hasher.hexdigest(), "d6ad6f0608b878da9b87999c2525cc84f4c9f18d");
+    }
+
+    #[test]
+    fn test_has160_length_limit() {
+        let mut hasher = Has160::new();
+        hasher.total_len = Has160::MAX_MESSAGE_LEN - 3;
+        assert!(hasher.try_update(b"abc").is_ok());
+        let err = hasher.try_update(b"d").unwrap_err();
+        assert_eq!(err.algorithm, "HAS-160");
+    }
+}
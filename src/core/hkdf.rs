@@ -0,0 +1,324 @@
+//! HKDF extract-and-expand key derivation.
+//!
+//! Pure Rust implementation following RFC 5869, built on [`Hmac`] so it
+//! works with any [`Digest`]:
+//!
+//! ```text
+//! PRK = HMAC(salt, IKM)
+//! T(i) = HMAC(PRK, T(i - 1) || info || i),  OKM = T(1) || T(2) || ...
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **PRK size**: the digest size of the hash (`HashLen`)
+//! - **Output size**: 0 to 255 * `HashLen` bytes
+//!
+//! The TLS 1.3 key schedule (RFC 8446, section 7.1) frames HKDF-Expand
+//! with [`hkdf_expand_label`] and [`tls13_derive_secret`].
+
+use super::{Digest, Hmac, ParameterError};
+
+/// Maximum number of `HashLen` blocks HKDF-Expand can produce.
+pub const MAX_BLOCKS: usize = 255;
+
+/// HKDF-Extract: derives a pseudorandom key from `ikm` and `salt`.
+///
+/// `hasher` must be in its initial state. An empty salt stands for
+/// `HashLen` zero bytes, which HMAC pads to the same key.
+pub fn hkdf_extract<D: Digest>(hasher: D, salt: &[u8], ikm: &[u8]) -> D::Output {
+    let mut mac = Hmac::new(hasher, salt);
+    mac.update(ikm);
+    mac.digest()
+}
+
+/// HKDF-Expand: derives `length` bytes of output keying material from the
+/// pseudorandom key `prk` and the context `info`.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `length` exceeds 255 * `HashLen`.
+pub fn hkdf_expand<D: Digest>(
+    hasher: D,
+    prk: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    let hash_len = hasher.digest_size();
+    let max_len = MAX_BLOCKS * hash_len;
+    if length > max_len {
+        return Err(ParameterError {
+            algorithm: "HKDF",
+            message: format!(
+                "length must be at most 255 * {} = {} bytes, got {}",
+                hash_len, max_len, length
+            ),
+        });
+    }
+
+    let keyed = Hmac::new(hasher, prk);
+    let mut okm = Vec::with_capacity(length);
+    let mut block: Option<D::Output> = None;
+    for counter in 1..=length.div_ceil(hash_len) as u8 {
+        let mut mac = keyed.clone();
+        if let Some(previous) = &block {
+            mac.update(previous.as_ref());
+        }
+        mac.update(info);
+        mac.update(&[counter]);
+        let current = mac.digest();
+        let take = (length - okm.len()).min(hash_len);
+        okm.extend_from_slice(&current.as_ref()[..take]);
+        block = Some(current);
+    }
+    Ok(okm)
+}
+
+/// HKDF: extracts a pseudorandom key from `ikm` and `salt`, then expands it
+/// into `length` bytes bound to `info`.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `length` exceeds 255 * `HashLen`.
+pub fn hkdf<D: Digest>(
+    hasher: D,
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    let prk = hkdf_extract(hasher.clone(), salt, ikm);
+    hkdf_expand(hasher, prk.as_ref(), info, length)
+}
+
+/// Prefix TLS 1.3 adds to every HKDF-Expand-Label label.
+const TLS13_LABEL_PREFIX: &[u8] = b"tls13 ";
+
+/// HKDF-Expand-Label from TLS 1.3 (RFC 8446, section 7.1): HKDF-Expand of
+/// `secret` with the `HkdfLabel` structure as info.
+///
+/// ```text
+/// HkdfLabel = length (2 bytes) || len || "tls13 " || label || len || context
+/// ```
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `"tls13 " || label` or `context` is longer
+/// than 255 bytes, or if `length` exceeds 255 * `HashLen` or 65535.
+pub fn hkdf_expand_label<D: Digest>(
+    hasher: D,
+    secret: &[u8],
+    label: &[u8],
+    context: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, ParameterError> {
+    let error = |message: String| ParameterError { algorithm: "HKDF-Expand-Label", message };
+    let length_field = u16::try_from(length)
+        .map_err(|_| error(format!("length must be at most 65535 bytes, got {}", length)))?;
+    let full_len = TLS13_LABEL_PREFIX.len() + label.len();
+    if full_len > 255 {
+        return Err(error(format!("label must be at most 249 bytes, got {}", label.len())));
+    }
+    if context.len() > 255 {
+        return Err(error(format!("context must be at most 255 bytes, got {}", context.len())));
+    }
+    let mut info = Vec::with_capacity(4 + full_len + context.len());
+    info.extend_from_slice(&length_field.to_be_bytes());
+    info.push(full_len as u8);
+    info.extend_from_slice(TLS13_LABEL_PREFIX);
+    info.extend_from_slice(label);
+    info.push(context.len() as u8);
+    info.extend_from_slice(context);
+    hkdf_expand(hasher, secret, &info, length)
+}
+
+/// Derive-Secret from TLS 1.3: HKDF-Expand-Label of `secret` with the
+/// transcript hash as context, for one digest size of output.
+///
+/// `transcript_hash` is the hash of the handshake messages, computed by the
+/// caller with the same hash.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `label` or `transcript_hash` is too long
+/// for [`hkdf_expand_label`].
+pub fn tls13_derive_secret<D: Digest>(
+    hasher: D,
+    secret: &[u8],
+    label: &[u8],
+    transcript_hash: &[u8],
+) -> Result<Vec<u8>, ParameterError> {
+    let hash_len = hasher.digest_size();
+    hkdf_expand_label(hasher, secret, label, transcript_hash, hash_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Sha1, Sha256};
+    use crate::utils::to_hex;
+
+    fn range(first: u8, last: u8) -> Vec<u8> {
+        (first..=last).collect()
+    }
+
+    fn unhex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_hkdf_sha256_rfc5869() {
+        // Test cases 1 to 3 of RFC 5869, appendix A
+        let cases = [
+            (
+                vec![0x0b; 22],
+                range(0x00, 0x0c),
+                range(0xf0, 0xf9),
+                42,
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+                 34007208d5b887185865",
+            ),
+            (
+                range(0x00, 0x4f),
+                range(0x60, 0xaf),
+                range(0xb0, 0xff),
+                82,
+                "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+                 59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+                 cc30c58179ec3e87c14c01d5c1f3434f1d87",
+            ),
+            (
+                vec![0x0b; 22],
+                vec![],
+                vec![],
+                42,
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+                 9d201395faa4b61a96c8",
+            ),
+        ];
+        for (ikm, salt, info, length, prk, okm) in cases {
+            let extracted = hkdf_extract(Sha256::new(), &salt, &ikm);
+            assert_eq!(to_hex(&extracted), prk);
+            let expanded = hkdf_expand(Sha256::new(), &extracted, &info, length).unwrap();
+            assert_eq!(to_hex(&expanded), okm);
+            assert_eq!(hkdf(Sha256::new(), &salt, &ikm, &info, length).unwrap(), expanded);
+        }
+    }
+
+    #[test]
+    fn test_hkdf_sha1_rfc5869() {
+        // Test cases 4 and 7 of RFC 5869: short inputs and an absent salt
+        let okm = hkdf(Sha1::new(), &range(0x00, 0x0c), &[0x0b; 11], &range(0xf0, 0xf9), 42);
+        assert_eq!(
+            to_hex(&okm.unwrap()),
+            "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2\
+             c22e422478d305f3f896"
+        );
+        let okm = hkdf(Sha1::new(), &[], &[0x0c; 22], &[], 42);
+        assert_eq!(
+            to_hex(&okm.unwrap()),
+            "2c91117204d745f3500d636a62f64f0ab3bae548aa53d423b0d1f27ebba6f5e5\
+             673a081d70cce7acfc48"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_expand_length_limit() {
+        let prk = [0x42u8; 32];
+        let longest = hkdf_expand(Sha256::new(), &prk, b"info", 255 * 32).unwrap();
+        assert_eq!(longest.len(), 8160);
+        let shorter = hkdf_expand(Sha256::new(), &prk, b"info", 100).unwrap();
+        assert_eq!(shorter[..], longest[..100]);
+        assert!(hkdf_expand(Sha256::new(), &prk, b"", 0).unwrap().is_empty());
+        let err = hkdf_expand(Sha256::new(), &prk, b"info", 255 * 32 + 1).unwrap_err();
+        assert_eq!(err.algorithm, "HKDF");
+    }
+
+    #[test]
+    fn test_tls13_key_schedule_rfc8448() {
+        // Simple 1-RTT handshake of RFC 8448, section 3
+        let derive = |secret: &[u8], label: &[u8], transcript: &str| {
+            tls13_derive_secret(Sha256::new(), secret, label, &unhex(transcript)).unwrap()
+        };
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let early = hkdf_extract(Sha256::new(), &[], &[0; 32]);
+        assert_eq!(
+            to_hex(&early),
+            "33ad0a1c607ec03b09e6cd9893680ce210adf300aa1f2660e1b22e10f170f92a"
+        );
+        let derived = derive(&early, b"derived", empty);
+        assert_eq!(
+            to_hex(&derived),
+            "6f2615a108c702c5678f54fc9dbab69716c076189c48250cebeac3576c3611ba"
+        );
+        let shared = unhex("8bd4054fb55b9d63fdfbacf9f04b9f0d35e6d63f537563efd46272900f89492d");
+        let handshake = hkdf_extract(Sha256::new(), &derived, &shared);
+        assert_eq!(
+            to_hex(&handshake),
+            "1dc826e93606aa6fdc0aadc12f741b01046aa6b99f691ed221a9f0ca043fbeac"
+        );
+        let hello = "860c06edc07858ee8e78f0e7428c58edd6b43f2ca3e6e95f02ed063cf0e1cad8";
+        assert_eq!(
+            to_hex(&derive(&handshake, b"c hs traffic", hello)),
+            "b3eddb126e067f35a780b3abf45e2d8f3b1a950738f52e9600746a0e27a55a21"
+        );
+        let server_hs = derive(&handshake, b"s hs traffic", hello);
+        assert_eq!(
+            to_hex(&server_hs),
+            "b67b7d690cc16c4e75e54213cb2d37b4e9c912bcded9105d42befd59d391ad38"
+        );
+        let key = hkdf_expand_label(Sha256::new(), &server_hs, b"key", b"", 16).unwrap();
+        assert_eq!(to_hex(&key), "3fce516009c21727d0f2e4e86ee403bc");
+        let iv = hkdf_expand_label(Sha256::new(), &server_hs, b"iv", b"", 12).unwrap();
+        assert_eq!(to_hex(&iv), "5d313eb2671276ee13000b30");
+
+        let derived = derive(&handshake, b"derived", empty);
+        assert_eq!(
+            to_hex(&derived),
+            "43de77e0c77713859a944db9db2590b53190a65b3ee2e4f12dd7a0bb7ce254b4"
+        );
+        let master = hkdf_extract(Sha256::new(), &derived, &[0; 32]);
+        assert_eq!(
+            to_hex(&master),
+            "18df06843d13a08bf2a449844c5f8a478001bc4d4c627984d5a41da8d0402919"
+        );
+        let finished = "9608102a0f1ccc6db6250b7b7e417b1a000eaada3daae4777a7686c9ff83df13";
+        assert_eq!(
+            to_hex(&derive(&master, b"c ap traffic", finished)),
+            "9e40646ce79a7f9dc05af8889bce6552875afa0b06df0087f792ebb7c17504a5"
+        );
+        assert_eq!(
+            to_hex(&derive(&master, b"s ap traffic", finished)),
+            "a11af9f05531f856ad47116b45a950328204b4f44bfb6b3a4b4f1f3fcb631643"
+        );
+        assert_eq!(
+            to_hex(&derive(&master, b"exp master", finished)),
+            "fe22f881176eda18eb8f44529e6792c50c9a3f89452f68d8ae311b4309d3cf50"
+        );
+        let client_finished = "209145a96ee8e2a122ff810047cc952684658d6049e86429426db87c54ad143d";
+        assert_eq!(
+            to_hex(&derive(&master, b"res master", client_finished)),
+            "7df235f2031d2a051287d02b0241b0bfdaf86cc856231f2d5aba46c434ec196c"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_expand_label_limits() {
+        let secret = [0x42u8; 32];
+        assert!(hkdf_expand_label(Sha256::new(), &secret, &[b'a'; 249], b"", 32).is_ok());
+        let err = hkdf_expand_label(Sha256::new(), &secret, &[b'a'; 250], b"", 32).unwrap_err();
+        assert_eq!(err.algorithm, "HKDF-Expand-Label");
+        assert!(hkdf_expand_label(Sha256::new(), &secret, b"l", &[0; 256], 32).is_err());
+        assert!(hkdf_expand_label(Sha256::new(), &secret, b"l", b"", 255 * 32 + 1).is_err());
+        assert!(hkdf_expand_label(Sha1::new(), &secret, b"l", b"", 65536).is_err());
+    }
+}
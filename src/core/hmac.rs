@@ -0,0 +1,300 @@
+//! HMAC keyed-hash message authentication code.
+//!
+//! Pure Rust implementation following RFC 2104 and FIPS 198-1, generic over
+//! any [`Digest`]:
+//!
+//! ```text
+//! HMAC(K, m) = H((K' ^ opad) || H((K' ^ ipad) || m))
+//! ```
+//!
+//! where `K'` is the key zero-padded to the hash's block size, or the hash
+//! of the key when it is longer than a block.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: that of the underlying hash, or [`MIN_BLOCK_SIZE`]
+//!   bytes when the hash's own block is smaller (Ascon-Hash256)
+//! - **Digest size**: that of the underlying hash (may be truncated by the caller)
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Smallest key block size; hashes with shorter blocks use
+/// [`DEFAULT_BLOCK_SIZE`] instead, as Python's hmac module does.
+pub const MIN_BLOCK_SIZE: usize = 16;
+
+/// Key block size used for hashes whose block is below [`MIN_BLOCK_SIZE`].
+pub const DEFAULT_BLOCK_SIZE: usize = 64;
+
+/// Byte XORed into the padded key for the inner hash.
+const IPAD: u8 = 0x36;
+
+/// Byte XORed into the padded key for the outer hash.
+const OPAD: u8 = 0x5c;
+
+/// HMAC state over the hash `D`.
+///
+/// Holds the inner hash, already keyed and fed the message so far, and the
+/// keyed outer hash, so finalizing never needs the key again. Cloning
+/// captures the full streaming state.
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer: D,
+    /// Keyed inner hash before any message, restored by `reset()`.
+    inner_init: D,
+    /// Length the key is padded to.
+    block_size: usize,
+}
+
+impl<D: Digest> Hmac<D> {
+    /// Creates an HMAC keyed with `key`.
+    ///
+    /// `hasher` must be in its initial state; it is cloned for the inner
+    /// and outer hashes, so parameterized hashers keep their parameters.
+    pub fn new(hasher: D, key: &[u8]) -> Self {
+        let block_size = match hasher.block_size() {
+            size if size < MIN_BLOCK_SIZE => DEFAULT_BLOCK_SIZE,
+            size => size,
+        };
+        let mut padded = if key.len() > block_size {
+            let mut key_hasher = hasher.clone();
+            key_hasher.try_update(key).expect("key fits in memory, so below the length limit");
+            key_hasher.digest().as_ref().to_vec()
+        } else {
+            key.to_vec()
+        };
+        // Digests longer than a block (e.g. Skein-1024 with a large output)
+        // are used whole, as Python's hmac module does
+        padded.resize(padded.len().max(block_size), 0);
+
+        let keyed = |pad: u8| {
+            let block: Vec<u8> = padded.iter().map(|byte| byte ^ pad).collect();
+            let mut keyed = hasher.clone();
+            keyed.try_update(&block).expect("a single block is below the length limit");
+            keyed
+        };
+        let inner = keyed(IPAD);
+        Hmac { outer: keyed(OPAD), inner_init: inner.clone(), inner, block_size }
+    }
+
+    /// Feeds message data into the MAC.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message would exceed the underlying hash's length
+    /// limit. Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds message data into the MAC, failing if the message becomes too
+    /// long for the underlying hash.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.inner.try_update(data)
+    }
+
+    /// Returns the MAC of the message fed so far.
+    ///
+    /// The MAC can keep absorbing data afterwards.
+    pub fn digest(&self) -> D::Output {
+        let mut outer = self.outer.clone();
+        outer
+            .try_update(self.inner.digest().as_ref())
+            .expect("a single digest is below the length limit");
+        outer.digest()
+    }
+
+    /// Returns the MAC of `data` alone, ignoring the message fed so far.
+    ///
+    /// Starts from the keyed state, so MACing many messages under one key,
+    /// as PBKDF2 does, never repeats the key setup.
+    pub fn mac(&self, data: &[u8]) -> D::Output {
+        let mut inner = self.inner_init.clone();
+        inner.try_update(data).expect("message length limit exceeded");
+        let mut outer = self.outer.clone();
+        outer
+            .try_update(inner.digest().as_ref())
+            .expect("a single digest is below the length limit");
+        outer.digest()
+    }
+
+    /// Returns the MAC of the message fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(self.digest().as_ref())
+    }
+
+    /// Resets the MAC to its keyed initial state, discarding the message.
+    pub fn reset(&mut self) {
+        self.inner = self.inner_init.clone();
+    }
+
+    /// Returns the output size in bytes, that of the underlying hash.
+    pub fn digest_size(&self) -> usize {
+        self.inner.digest_size()
+    }
+
+    /// Returns the key block size in bytes, normally that of the
+    /// underlying hash (see [`MIN_BLOCK_SIZE`]).
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+}
+
+impl<D: Digest> Digest for Hmac<D> {
+    type Output = D::Output;
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Hmac::try_update(self, data)
+    }
+
+    fn digest(&self) -> D::Output {
+        Hmac::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Hmac::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Hmac::block_size(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{AsconHash256, DynDigest, Sha256, Sha512};
+
+    /// RFC 4231 test cases 1 to 7: key, data, HMAC-SHA-256, HMAC-SHA-512.
+    /// Test case 5 only publishes the first 128 bits of each MAC.
+    fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>, &'static str, &'static str)> {
+        vec![
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39\
+                 bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+            ),
+            (
+                (1..=25).collect(),
+                vec![0xcd; 50],
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db\
+                 a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+            ),
+            (
+                vec![0x0c; 20],
+                b"Test With Truncation".to_vec(),
+                "a3b6167473100ee06e0c796c2955552b",
+                "415fad6271580a531d4179bc891d87a6",
+            ),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than \
+                  block-size data. The key needs to be hashed before being used by \
+                  the HMAC algorithm."
+                    .to_vec(),
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944\
+                 b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        for (key, data, expected, _) in rfc4231_cases() {
+            let mut mac = Hmac::new(Sha256::new(), &key);
+            mac.update(&data);
+            assert!(mac.hexdigest().starts_with(expected), "{}", expected);
+        }
+    }
+
+    #[test]
+    fn test_hmac_sha512_rfc4231() {
+        for (key, data, _, expected) in rfc4231_cases() {
+            let mut mac = Hmac::new(Sha512::new(), &key);
+            mac.update(&data);
+            assert!(mac.hexdigest().starts_with(expected), "{}", expected);
+        }
+    }
+
+    #[test]
+    fn test_hmac_key_at_block_size_is_not_hashed() {
+        // A key of exactly one block is used as is, one byte more is hashed
+        let key = [0x42u8; 64];
+        let mut hashed_key = Sha256::new();
+        hashed_key.update(&[0x42u8; 65]);
+        let long = Hmac::new(Sha256::new(), &[0x42u8; 65]);
+        let equivalent = Hmac::new(Sha256::new(), &hashed_key.digest());
+        assert_eq!(long.digest(), equivalent.digest());
+        assert_ne!(Hmac::new(Sha256::new(), &key).digest(), long.digest());
+    }
+
+    #[test]
+    fn test_hmac_small_block_uses_default() {
+        // Ascon-Hash256 absorbs 8-byte blocks; keys are padded to 64 bytes
+        let mac = Hmac::new(AsconHash256::new(), &[0x11; 40]);
+        assert_eq!(mac.block_size(), DEFAULT_BLOCK_SIZE);
+        let mut hashed_key = AsconHash256::new();
+        hashed_key.update(&[0x11; 65]);
+        assert_eq!(
+            Hmac::new(AsconHash256::new(), &[0x11; 65]).digest(),
+            Hmac::new(AsconHash256::new(), &hashed_key.digest()).digest()
+        );
+    }
+
+    #[test]
+    fn test_hmac_streaming_and_reset() {
+        let data = [0x5au8; 300];
+        let mut oneshot = Hmac::new(Sha512::new(), b"key");
+        oneshot.update(&data);
+        let mut streamed = Hmac::new(Sha512::new(), b"key");
+        for chunk in data.chunks(7) {
+            streamed.update(chunk);
+        }
+        assert_eq!(streamed.digest(), oneshot.digest());
+
+        streamed.reset();
+        streamed.update(b"abc");
+        let mut fresh = Hmac::new(Sha512::new(), b"key");
+        fresh.update(b"abc");
+        assert_eq!(streamed.digest(), fresh.digest());
+        assert_eq!(oneshot.mac(b"abc"), fresh.digest());
+    }
+
+    #[test]
+    fn test_hmac_over_boxed_hasher() {
+        let boxed: Box<dyn DynDigest> = Box::new(Sha256::new());
+        let mut dynamic = Hmac::new(boxed, b"Jefe");
+        dynamic.update(b"what do ya want for nothing?");
+        let mut fixed = Hmac::new(Sha256::new(), b"Jefe");
+        fixed.update(b"what do ya want for nothing?");
+        assert_eq!(dynamic.digest(), fixed.digest().to_vec());
+        assert_eq!(dynamic.block_size(), 64);
+        assert_eq!(dynamic.digest_size(), 32);
+    }
+}
@@ -0,0 +1,300 @@
+//! HMAC-DRBG deterministic random bit generator.
+//!
+//! Pure Rust implementation of HMAC_DRBG from NIST SP 800-90A Rev. 1,
+//! section 10.1.2, over any [`Digest`]. The state is a key `K` and a value
+//! `V` of one digest each, mixed with the provided data by:
+//!
+//! ```text
+//! K = HMAC(K, V || 0x00 || data),  V = HMAC(K, V)
+//! K = HMAC(K, V || 0x01 || data),  V = HMAC(K, V)   (only if data is not empty)
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **Instantiate**: `K = 0x00...`, `V = 0x01...`, then update with
+//!   `entropy || nonce || personalization`
+//! - **Generate**: at most 2^19 bits (65536 bytes) per request, and at most
+//!   2^48 requests between reseeds
+//! - **Inputs**: entropy, personalization and additional input of at most
+//!   2^35 bits each
+//!
+//! The generator is deterministic: the caller supplies the entropy, which
+//! is what RFC 6979 nonces and known-answer tests need. Its length is not
+//! checked against the security strength of the hash.
+
+use super::{Digest, Hmac, ParameterError};
+
+/// Largest request [`HmacDrbg::generate`] accepts, in bytes (2^19 bits).
+pub const MAX_REQUEST_LEN: usize = 1 << 16;
+
+/// Number of requests allowed between reseeds.
+pub const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// Largest entropy, personalization string or additional input, in bytes
+/// (2^35 bits).
+pub const MAX_INPUT_LEN: u64 = 1 << 32;
+
+/// HMAC-DRBG state.
+#[derive(Clone)]
+pub struct HmacDrbg<D: Digest> {
+    hasher: D,
+    key: Vec<u8>,
+    value: Vec<u8>,
+    reseed_counter: u64,
+    reseed_interval: u64,
+}
+
+/// Checks that each of `inputs` is at most [`MAX_INPUT_LEN`] bytes long.
+fn check_inputs(inputs: &[(&str, &[u8])]) -> Result<(), ParameterError> {
+    for (name, input) in inputs {
+        if input.len() as u64 > MAX_INPUT_LEN {
+            return Err(ParameterError {
+                algorithm: "HMAC-DRBG",
+                message: format!("{} must be at most 2^35 bits long", name),
+            });
+        }
+    }
+    Ok(())
+}
+
+impl<D: Digest> HmacDrbg<D> {
+    /// Instantiates the generator from `entropy`, `nonce` and an optional
+    /// `personalization` string.
+    ///
+    /// `hasher` must be in its initial state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if an input is longer than 2^35 bits.
+    pub fn new(
+        hasher: D,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Result<Self, ParameterError> {
+        check_inputs(&[("entropy", entropy), ("personalization", personalization)])?;
+        let len = hasher.digest_size();
+        let mut drbg = HmacDrbg {
+            hasher,
+            key: vec![0x00; len],
+            value: vec![0x01; len],
+            reseed_counter: 1,
+            reseed_interval: RESEED_INTERVAL,
+        };
+        drbg.update(&[entropy, nonce, personalization]);
+        Ok(drbg)
+    }
+
+    /// Lowers the number of requests allowed between reseeds, for tests of
+    /// reseeding; `interval` is clamped to 1..=2^48.
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        self.reseed_interval = interval.clamp(1, RESEED_INTERVAL);
+    }
+
+    /// Number of the next request, which restarts at 1 after a reseed.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    /// Returns whether [`generate`](Self::generate) needs a reseed first.
+    pub fn needs_reseed(&self) -> bool {
+        self.reseed_counter > self.reseed_interval
+    }
+
+    /// Mixes fresh `entropy` and optional `additional` input into the state
+    /// and resets the reseed counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if an input is longer than 2^35 bits.
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), ParameterError> {
+        check_inputs(&[("entropy", entropy), ("additional input", additional)])?;
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// Fills `out` with pseudorandom bytes, mixing in optional `additional`
+    /// input before and after.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if `out` is longer than 65536 bytes,
+    /// `additional` is longer than 2^35 bits, or a reseed is required.
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), ParameterError> {
+        let error = |message: String| ParameterError { algorithm: "HMAC-DRBG", message };
+        if out.len() > MAX_REQUEST_LEN {
+            return Err(error(format!(
+                "at most {} bytes can be requested at once, got {}",
+                MAX_REQUEST_LEN,
+                out.len()
+            )));
+        }
+        check_inputs(&[("additional input", additional)])?;
+        if self.needs_reseed() {
+            return Err(error("reseed required".to_owned()));
+        }
+        if !additional.is_empty() {
+            self.update(&[additional]);
+        }
+        for chunk in out.chunks_mut(self.value.len()) {
+            self.value = self.mac(&[&self.value]);
+            chunk.copy_from_slice(&self.value[..chunk.len()]);
+        }
+        self.update(&[additional]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// HMAC keyed with `K` of the concatenation of `parts`.
+    fn mac(&self, parts: &[&[u8]]) -> Vec<u8> {
+        let mut mac = Hmac::new(self.hasher.clone(), &self.key);
+        for part in parts {
+            mac.update(part);
+        }
+        mac.digest().as_ref().to_vec()
+    }
+
+    /// HMAC_DRBG_Update with the concatenation of `provided`.
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|part| part.is_empty());
+        for separator in [0x00u8, 0x01] {
+            self.key = self.mac(&[&self.value, &[separator], &provided.concat()]);
+            self.value = self.mac(&[&self.value]);
+            if empty {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Sha256, Sha512};
+    use crate::utils::to_hex;
+
+    fn unhex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Instantiates, optionally reseeds, and returns the output of the
+    /// second of two requests, as in the CAVP files.
+    fn run<D: Digest>(
+        hasher: D,
+        seed: [&str; 3],
+        reseed: Option<[&str; 2]>,
+        additional: [&str; 2],
+        len: usize,
+    ) -> String {
+        let [entropy, nonce, personalization] = seed.map(unhex);
+        let mut drbg = HmacDrbg::new(hasher, &entropy, &nonce, &personalization).unwrap();
+        if let Some([entropy, additional]) = reseed {
+            drbg.reseed(&unhex(entropy), &unhex(additional)).unwrap();
+        }
+        let mut out = vec![0u8; len];
+        drbg.generate(&mut out, &unhex(additional[0])).unwrap();
+        drbg.generate(&mut out, &unhex(additional[1])).unwrap();
+        to_hex(&out)
+    }
+
+    #[test]
+    fn test_hmac_drbg_sha256_cavp_no_reseed() {
+        let seed = [
+            "ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488",
+            "659ba96c601dc69fc902940805ec0ca8",
+            "",
+        ];
+        assert_eq!(
+            run(Sha256::new(), seed, None, ["", ""], 128),
+            "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89\
+             d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1\
+             07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668\
+             961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8"
+        );
+        let seed = [
+            "d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd",
+            "0109b0e729f457328aa18569a9224921",
+            "",
+        ];
+        let additional = [
+            "3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6",
+            "fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4",
+        ];
+        assert_eq!(
+            run(Sha256::new(), seed, None, additional, 128),
+            "9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f\
+             1ffdbbb118f4e62fcfca3371b8fbfc5b0646b83e06bfbbab5fac30ea09ea2bc7\
+             6f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab39018\
+             3ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974"
+        );
+    }
+
+    #[test]
+    fn test_hmac_drbg_sha512_cavp_no_reseed() {
+        let seed = [
+            "35049f389a33c0ecb1293238fd951f8ffd517dfde06041d32945b3e26914ba15",
+            "f7328760be6168e6aa9fb54784989a11",
+            "",
+        ];
+        assert_eq!(
+            run(Sha512::new(), seed, None, ["", ""], 256),
+            "e76491b0260aacfded01ad39fbf1a66a88284caa5123368a2ad9330ee48335e3\
+             c9c9ba90e6cbc9429962d60c1a6661edcfaa31d972b8264b9d4562cf18494128\
+             a092c17a8da6f3113e8a7edfcd4427082bd390675e9662408144971717303d8d\
+             c352c9e8b95e7f35fa2ac9f549b292bc7c4bc7f01ee0a577859ef6e82d79ef23\
+             892d167c140d22aac32b64ccdfeee2730528a38763b24227f91ac3ffe47fb115\
+             38e435307e77481802b0f613f370ffb0dbeab774fe1efbb1a80d01154a9459e7\
+             3ad361108bbc86b0914f095136cbe634555ce0bb263618dc5c367291ce082551\
+             8987154fe9ecb052b3f0a256fcc30cc14572531c9628973639beda456f2bddf6"
+        );
+    }
+
+    #[test]
+    fn test_hmac_drbg_reseed() {
+        // Personalization, reseed and additional inputs everywhere, as in
+        // the CAVP prediction-resistance-false files; cross-checked against
+        // the HMAC-DRBG of OpenSSL
+        let seed = [
+            "7d72e7ed4f291a2e80318d0aaa8d5e25b0a115033b55efc0eec3bd4f298d820e",
+            "0dacfa69fb6e61485f10ff0cc13af812",
+            "8e1d97196406074f0670d62a054c268ebd6ae2e591facd1932e609f43c73bf65",
+        ];
+        let reseed = [
+            "742931ab874342b3d60fd29a9fb518a3bc4cdbd8e8c47dc92d4249a4bce487a9",
+            "c4219b0ffcab1b7ab42f7258f5c295472b24b7ed57f32c6165c8981c61d01fb2",
+        ];
+        let additional = [
+            "fdc859aa664664287ce3b6b8da1f583f8724253d38b628a096e2eaf9bd9b9dee",
+            "10337783e8068826701c0a594cc8f2d4ee18dd62061c4c7411cd74da4e1aa55a",
+        ];
+        assert_eq!(
+            run(Sha256::new(), seed, Some(reseed), additional, 128),
+            "f42f26f200f2946891b3133db11a56492148cc2dd640b88e5452b79bf8e4c5bf\
+             be68bb7793078ec79cb643b0d83e8ffb490211795e301d88cd6b35c2b8ff9033\
+             359ade1e6a0bc059a5aaecc3ad1fe3cd12100baea359244b57976da54a84f0b0\
+             c13770cbab2d7ac8e332975cb29d6e31e9eb7f1109dca571b753acebf56e0848"
+        );
+    }
+
+    #[test]
+    fn test_hmac_drbg_limits() {
+        let mut drbg = HmacDrbg::new(Sha256::new(), &[1; 32], &[2; 16], b"").unwrap();
+        let mut out = vec![0u8; MAX_REQUEST_LEN + 1];
+        let err = drbg.generate(&mut out, b"").unwrap_err();
+        assert_eq!(err.algorithm, "HMAC-DRBG");
+        assert_eq!(drbg.reseed_counter(), 1);
+        drbg.generate(&mut out[..MAX_REQUEST_LEN], b"").unwrap();
+
+        drbg.set_reseed_interval(2);
+        drbg.generate(&mut out[..16], b"").unwrap();
+        assert!(drbg.needs_reseed());
+        assert!(drbg.generate(&mut out[..16], b"").is_err());
+        drbg.reseed(&[3; 32], b"").unwrap();
+        assert_eq!(drbg.reseed_counter(), 1);
+        drbg.generate(&mut out[..16], b"").unwrap();
+    }
+}
@@ -0,0 +1,400 @@
+//! JH hash function implementation.
+//!
+//! Pure Rust implementation of JH as submitted to the third round of the
+//! SHA-3 competition (JH42). The 1024-bit state absorbs each 512-bit block
+//! `m` with the compression function F8: `m` is XORed into the first half
+//! of the state, the 42-round permutation E8 is applied, and `m` is XORed
+//! into the second half. The digest is the end of the final state.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **State size**: 1024 bits
+//! - **Digest size**: 256 bits ([`Jh256`]) or 512 bits ([`Jh512`])
+//! - **Rounds**: 42
+//! - **Padding**: a 1 bit, zeros, then the 128-bit big-endian message
+//!   length in bits, always adding at least one block
+//!
+//! E8 is computed in the bit-sliced form of the reference optimized
+//! implementation: the state is eight 128-bit words, each round applies
+//! the two 4-bit S-boxes and the linear layer as boolean operations on
+//! whole words, and the permutation layer becomes a bit swap in the odd
+//! words whose distance cycles through 1, 2, 4, ..., 64 every seven rounds.
+//!
+//! Only byte-aligned messages are supported; the bit-length messages of
+//! the submission's test files cannot be expressed through this API.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// E8 round constants in bit-sliced form: the words selecting the S-box of
+/// the even and the odd 128-bit words, low half first.
+const ROUND_CONSTANTS: [[u64; 4]; 42] = [
+    [0x67f815dfa2ded572, 0x571523b70a15847b, 0xf6875a4d90d6ab81, 0x402bd1c3c54f9f4e],
+    [0x9cfa455ce03a98ea, 0x9a99b26699d2c503, 0x8a53bbf2b4960266, 0x31a2db881a1456b5],
+    [0xdb0e199a5c5aa303, 0x1044c1870ab23f40, 0x1d959e848019051c, 0xdccde75eadeb336f],
+    [0x416bbf029213ba10, 0xd027bbf7156578dc, 0x5078aa3739812c0a, 0xd3910041d2bf1a3f],
+    [0x907eccf60d5a2d42, 0xce97c0929c9f62dd, 0xac442bc70ba75c18, 0x23fcc663d665dfd1],
+    [0x1ab8e09e036c6e97, 0xa8ec6c447e450521, 0xfa618e5dbb03f1ee, 0x97818394b29796fd],
+    [0x2f3003db37858e4a, 0x956a9ffb2d8d672a, 0x6c69b8f88173fe8a, 0x14427fc04672c78a],
+    [0xc45ec7bd8f15f4c5, 0x80bb118fa76f4475, 0xbc88e4aeb775de52, 0xf4a3a6981e00b882],
+    [0x1563a3a9338ff48e, 0x89f9b7d524565faa, 0xfde05a7c20edf1b6, 0x362c42065ae9ca36],
+    [0x3d98fe4e433529ce, 0xa74b9a7374f93a53, 0x86814e6f591ff5d0, 0x9f5ad8af81ad9d0e],
+    [0x6a6234ee670605a7, 0x2717b96ebe280b8b, 0x3f1080c626077447, 0x7b487ec66f7ea0e0],
+    [0xc0a4f84aa50a550d, 0x9ef18e979fe7e391, 0xd48d605081727686, 0x62b0e5f3415a9e7e],
+    [0x7a205440ec1f9ffc, 0x84c9f4ce001ae4e3, 0xd895fa9df594d74f, 0xa554c324117e2e55],
+    [0x286efebd2872df5b, 0xb2c4a50fe27ff578, 0x2ed349eeef7c8905, 0x7f5928eb85937e44],
+    [0x4a3124b337695f70, 0x65e4d61df128865e, 0xe720b95104771bc7, 0x8a87d423e843fe74],
+    [0xf2947692a3e8297d, 0xc1d9309b097acbdd, 0xe01bdc5bfb301b1d, 0xbf829cf24f4924da],
+    [0xffbf70b431bae7a4, 0x48bcf8de0544320d, 0x39d3bb5332fcae3b, 0xa08b29e0c1c39f45],
+    [0x0f09aef7fd05c9e5, 0x34f1904212347094, 0x95ed44e301b771a2, 0x4a982f4f368e3be9],
+    [0x15f66ca0631d4088, 0xffaf52874b44c147, 0x30c60ae2f14abb7e, 0xe68c6eccc5b67046],
+    [0x00ca4fbd56a4d5a4, 0xae183ec84b849dda, 0xadd1643045ce5773, 0x67255c1468cea6e8],
+    [0x16e10ecbf28cdaa3, 0x9a99949a5806e933, 0x7b846fc220b2601f, 0x1885d1a07facced1],
+    [0xd319dd8da15b5932, 0x46b4a5aac01c9a50, 0xba6b04e467633d9f, 0x7eee560bab19caf6],
+    [0x742128a9ea79b11f, 0xee51363b35f7bde9, 0x76d350755aac571d, 0x01707da3fec2463a],
+    [0x42d8a498afc135f7, 0x79676b9e20eced78, 0xa8db3aea15638341, 0x832c83324d3bc3fa],
+    [0xf347271c1f3b40a7, 0x9a762db734f04059, 0xfd4f21d26c4e3ee7, 0xef5957dc398dfdb8],
+    [0xdaeb492b490c9b8d, 0x0d70f36849d7a25b, 0x84558d7ad0ae3b7d, 0x658ef8e4f0e9a5f5],
+    [0x533b1036f4a2b8a0, 0x5aec3e759e07a80c, 0x4f88e85692946891, 0x4cbcbaf8555cb05b],
+    [0x7b9487f3993bbbe3, 0x5d1c6b72d6f4da75, 0x6db334dc28acae64, 0x71db28b850a5346c],
+    [0x2a518d10f2e261f8, 0xfc75dd593364dbe3, 0xa23fce43f1bcac1c, 0xb043e8023cd1bb67],
+    [0x75a12988ca5b0a33, 0x5c5316b44d19347f, 0x1e4d790ec3943b92, 0x3fafeeb6d7757479],
+    [0x21391abef7d4a8ea, 0x5127234c097ef45c, 0xd23c32ba5324a326, 0xadd5a66d4a17a344],
+    [0x08c9f2afa63e1db5, 0x563c6b91983d5983, 0x4d608672a17cf84c, 0xf6c76e08cc3ee246],
+    [0x5e76bcb1b333982f, 0x2ae6c4efa566d62b, 0x36d4c1bee8b6f406, 0x6321efbc1582ee74],
+    [0x69c953f40d4ec1fd, 0x26585806c45a7da7, 0x16fae0061614c17e, 0x3f9d63283daf907e],
+    [0x0cd29b00e3f2c9d2, 0x300cd4b730ceaa5f, 0x9832e0f216512a74, 0x9af8cee3d830eb0d],
+    [0x9279f1b57b9ec54b, 0xd36886046ee651ff, 0x316796e6574d239b, 0x05750a17f3a6e6cc],
+    [0xce6c3213d98176b1, 0x62a205f88452173c, 0x47154778b3cb2bf4, 0x486a9323825446ff],
+    [0x65655e4e0758df38, 0x8e5086fc897cfcf2, 0x86ca0bd0442e7031, 0x4e477830a20940f0],
+    [0x8338f7d139eea065, 0xbd3a2ce437e95ef7, 0x6ff8130126b29721, 0xe7de9fefd1ed44a3],
+    [0xd992257615dfa08b, 0xbe42dc12f6f7853c, 0x7eb027ab7ceca7d8, 0xdea83eaada7d8d53],
+    [0xd86902bd93ce25aa, 0xf908731afd43f65a, 0xa5194a17daef5fc0, 0x6a21fd4c33664d97],
+    [0x701541db3198b435, 0x9b54cdedbb0f1eea, 0x72409751a163d09a, 0xe26f4791bf9d75f6],
+];
+
+/// Masks of the bits that move up in the in-word swaps of rounds 0 to 5.
+const SWAP_MASKS: [u64; 6] = [
+    0x5555_5555_5555_5555,
+    0x3333_3333_3333_3333,
+    0x0f0f_0f0f_0f0f_0f0f,
+    0x00ff_00ff_00ff_00ff,
+    0x0000_ffff_0000_ffff,
+    0x0000_0000_ffff_ffff,
+];
+
+/// JH hasher state, generic over the digest size in bytes.
+///
+/// Use the [`Jh256`] and [`Jh512`] aliases. Cloning captures the full
+/// streaming state, so a clone can be finalized or extended independently
+/// of the original.
+#[derive(Clone)]
+pub struct Jh<const OUT: usize> {
+    /// State as eight 128-bit words, each split into little-endian halves.
+    state: [[u64; 2]; 8],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u128,       // Total bytes processed (for final length)
+}
+
+/// JH-256: 32-byte digest.
+pub type Jh256 = Jh<32>;
+
+/// JH-512: 64-byte digest.
+pub type Jh512 = Jh<64>;
+
+impl<const OUT: usize> Jh<OUT> {
+    /// Creates a new hasher.
+    ///
+    /// The initial state encodes the digest size in bits in its first two
+    /// bytes and is then compressed with an all-zero block.
+    pub fn new() -> Self {
+        let bits = (OUT * 8) as u64;
+        let mut state = [[0u64; 2]; 8];
+        state[0][0] = (bits >> 8) | ((bits & 0xff) << 8);
+        Self::compress(&mut state, &[0u8; 64]);
+        Jh {
+            state,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        // The padding holds a 128-bit length in bits, out of reach of any message
+        self.total_len += data.len() as u128;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; OUT] {
+        let bit_len = self.total_len.wrapping_mul(8);
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // 0x80 byte and zeros up to a full block after the message, then
+        // a block ending with the length; an aligned message needs only one
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len > 0 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+        block[48..64].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
+
+        let bytes: Vec<u8> = state.iter().flatten().flat_map(|word| word.to_le_bytes()).collect();
+        bytes[128 - OUT..].try_into().unwrap()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; OUT] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Compression function F8: XORs the block into the first half of the
+    /// state, applies E8, then XORs it into the second half.
+    fn compress(state: &mut [[u64; 2]; 8], block: &[u8; 64]) {
+        let mut message = [0u64; 8];
+        for (word, chunk) in message.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        for (i, &word) in message.iter().enumerate() {
+            state[i / 2][i % 2] ^= word;
+        }
+        Self::e8(state);
+        for (i, &word) in message.iter().enumerate() {
+            state[4 + i / 2][i % 2] ^= word;
+        }
+    }
+
+    /// The 42-round bit-sliced permutation E8.
+    fn e8(x: &mut [[u64; 2]; 8]) {
+        for (round, constant) in ROUND_CONSTANTS.iter().enumerate() {
+            for half in 0..2 {
+                let mut m = [
+                    x[0][half], x[2][half], x[4][half], x[6][half],
+                    x[1][half], x[3][half], x[5][half], x[7][half],
+                ];
+                Self::sbox(&mut m, constant[half], constant[2 + half]);
+                Self::linear(&mut m);
+                [x[0][half], x[2][half], x[4][half], x[6][half]] = [m[0], m[1], m[2], m[3]];
+                [x[1][half], x[3][half], x[5][half], x[7][half]] = [m[4], m[5], m[6], m[7]];
+            }
+
+            // Permutation layer: swap bits of the odd words at distance
+            // 2^(round mod 7); distance 64 exchanges the two halves
+            let shift = round % 7;
+            for word in x.iter_mut().skip(1).step_by(2) {
+                if shift == 6 {
+                    word.swap(0, 1);
+                } else {
+                    let mask = SWAP_MASKS[shift];
+                    for half in word.iter_mut() {
+                        *half = ((*half & mask) << (1 << shift)) | ((*half >> (1 << shift)) & mask);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies S-box S0 or S1, selected by each bit of `c0` and `c1`, to the
+    /// 4-bit elements held in `m[0..4]` and `m[4..8]`.
+    fn sbox(m: &mut [u64; 8], c0: u64, c1: u64) {
+        m[3] = !m[3];
+        m[7] = !m[7];
+        m[0] ^= !m[2] & c0;
+        m[4] ^= !m[6] & c1;
+        let t0 = c0 ^ (m[0] & m[1]);
+        let t1 = c1 ^ (m[4] & m[5]);
+        m[0] ^= m[2] & m[3];
+        m[4] ^= m[6] & m[7];
+        m[3] ^= !m[1] & m[2];
+        m[7] ^= !m[5] & m[6];
+        m[1] ^= m[0] & m[2];
+        m[5] ^= m[4] & m[6];
+        m[2] ^= m[0] & !m[3];
+        m[6] ^= m[4] & !m[7];
+        m[0] ^= m[1] | m[3];
+        m[4] ^= m[5] | m[7];
+        m[3] ^= m[1] & m[2];
+        m[7] ^= m[5] & m[6];
+        m[1] ^= t0 & m[0];
+        m[5] ^= t1 & m[4];
+        m[2] ^= t0;
+        m[6] ^= t1;
+    }
+
+    /// Linear layer: the MDS transform over GF(2^4) of each pair of
+    /// elements in `m[0..4]` and `m[4..8]`.
+    fn linear(m: &mut [u64; 8]) {
+        m[4] ^= m[1];
+        m[5] ^= m[2];
+        m[6] ^= m[0] ^ m[3];
+        m[7] ^= m[0];
+        m[0] ^= m[5];
+        m[1] ^= m[6];
+        m[2] ^= m[4] ^ m[7];
+        m[3] ^= m[4];
+    }
+
+    /// Returns the output size in bytes (32 or 64).
+    pub fn digest_size() -> usize {
+        OUT
+    }
+
+    /// Returns the block size in bytes (64 for JH).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl<const OUT: usize> Default for Jh<OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const OUT: usize> Digest for Jh<OUT> {
+    type Output = [u8; OUT];
+
+    /// Never fails: the 128-bit length field cannot overflow.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; OUT] {
+        Jh::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        OUT
+    }
+
+    fn block_size(&self) -> usize {
+        64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jh256_hex(data: &[u8]) -> String {
+        let mut hasher = Jh256::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    fn jh512_hex(data: &[u8]) -> String {
+        let mut hasher = Jh512::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_jh_empty_message() {
+        // Round-3 submission, ShortMsgKAT Len = 0
+        assert_eq!(
+            jh256_hex(b""),
+            "46e64619c18bb0a92a5e87185a47eef83ca747b8fcc8e1412921357e326df434"
+        );
+        assert_eq!(
+            jh512_hex(b""),
+            "90ecf2f76f9d2c8017d979ad5ab96b87d58fc8fc4b83060f3f900774faa2c8fa\
+             be69c5f4ff1ec2b61d6b316941cedee117fb04b1f4c5bc1b919ae841c50eec4f"
+        );
+    }
+
+    #[test]
+    fn test_jh_vectors() {
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            jh256_hex(fox),
+            "6a049fed5fc6874acfdc4a08b568a4f8cbac27de933496f031015b38961608a0"
+        );
+        assert_eq!(
+            jh256_hex(b"abc"),
+            "924bc82f24a76d519d4f69493da7fa70dc88bdb6016b6d1cc1dcf7def15e9cdd"
+        );
+        assert_eq!(
+            jh512_hex(fox),
+            "043f14e7c0775e7b1ef5ad657b1e858250b21e2e61fd699783f8634cb86f3ff9\
+             38451cabd0c8cdae91d4f659d3f9f6f654f1bfedca117ffba735c15fedda47a3"
+        );
+        // One full block: the padding block holds only the 0x80 byte and
+        // the length
+        let block: Vec<u8> = (0..64).collect();
+        assert_eq!(
+            jh256_hex(&block),
+            "e8bab989ea39692776ef278a4752f8e1359d78a8332e030b82297453eef03d1d"
+        );
+    }
+
+    #[test]
+    fn test_jh_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 47, 48, 63, 64, 65] {
+            let mut hasher = Jh512::new();
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.hexdigest(), jh512_hex(&data), "step {}", step);
+        }
+        let mut hasher = Jh256::new();
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Jh256::new().digest());
+    }
+}
@@ -0,0 +1,400 @@
+//! KangarooTwelve extendable-output function.
+//!
+//! Pure Rust implementation following RFC 9861. KangarooTwelve (K12) runs
+//! the Keccak sponge with the permutation reduced to 12 rounds, in a tree
+//! mode whose 8 KiB chunks are hashed independently; chunks after the first
+//! are therefore processed concurrently on the rayon thread pool.
+//!
+//! # Algorithm Details
+//!
+//! - **Input string**: `S = M || C || length_encode(|C|)`, where `C` is the
+//!   optional customization string
+//! - **Short inputs** (`|S| <= 8192`): TurboSHAKE128 of `S` with domain
+//!   byte `0x07`
+//! - **Long inputs**: each chunk after the first is hashed to a 32-byte
+//!   chaining value (domain byte `0x0B`); the final node is the first chunk,
+//!   `0x03 || 0^7`, the chaining values, `length_encode(n - 1) || FF FF`,
+//!   hashed with domain byte `0x06`
+//! - **Output**: any length; shorter outputs are prefixes of longer ones
+
+use rayon::prelude::*;
+
+use super::keccak::{Sponge, XofReader};
+use super::{LengthOverflowError, Xof};
+use crate::utils::to_hex;
+
+/// Rate of TurboSHAKE128 in bytes.
+const RATE: usize = 168;
+
+/// Number of Keccak-p[1600] rounds.
+const ROUNDS: usize = 12;
+
+/// Chunk size of the tree mode in bytes.
+const CHUNK_LEN: usize = 8192;
+
+/// Size of a chaining value in bytes.
+const CV_LEN: usize = 32;
+
+/// Domain separation byte of a message that fits in a single chunk.
+const SINGLE_NODE_DOMAIN: u8 = 0x07;
+
+/// Domain separation byte of the final node of the tree.
+const FINAL_NODE_DOMAIN: u8 = 0x06;
+
+/// Domain separation byte of the leaves (chunks after the first).
+const LEAF_DOMAIN: u8 = 0x0b;
+
+/// Appended to the first chunk when the tree has more than one node.
+const FIRST_CHUNK_SUFFIX: [u8; 8] = [0x03, 0, 0, 0, 0, 0, 0, 0];
+
+/// Appended to the final node after the number of chaining values.
+const FINAL_NODE_SUFFIX: [u8; 2] = [0xff, 0xff];
+
+/// `length_encode(x)`: the minimal big-endian encoding of `x` (no bytes for
+/// 0), followed by its length in bytes.
+fn length_encode(x: u64) -> ([u8; 9], usize) {
+    let mut out = [0u8; 9];
+    let n = 8 - x.leading_zeros() as usize / 8;
+    out[..n].copy_from_slice(&x.to_be_bytes()[8 - n..]);
+    out[n] = n as u8;
+    (out, n + 1)
+}
+
+/// KangarooTwelve hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct KangarooTwelve {
+    /// Final-node sponge; also the whole hash while `chunks` is 0.
+    sponge: Sponge,
+    /// Bytes of the current chunk, at most [`CHUNK_LEN`]. A full chunk stays
+    /// buffered until more data shows that it is not the last one.
+    buffer: Vec<u8>,
+    /// Number of chunks absorbed into `sponge`, the first one included.
+    chunks: u64,
+    customization: Vec<u8>,
+    /// Whether chunks may be hashed on the rayon thread pool.
+    parallel: bool,
+}
+
+impl KangarooTwelve {
+    /// Creates a new hasher with customization string `customization`
+    /// (`C`, may be empty).
+    pub fn new(customization: &[u8]) -> Self {
+        KangarooTwelve {
+            sponge: Sponge::new(RATE, ROUNDS),
+            buffer: Vec::with_capacity(CHUNK_LEN),
+            chunks: 0,
+            customization: customization.to_vec(),
+            parallel: true,
+        }
+    }
+
+    /// Enables or disables hashing chunks on the rayon thread pool.
+    ///
+    /// Enabled by default. The output is the same either way.
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Hashes one chunk into a chaining value.
+    fn chaining_value(chunk: &[u8]) -> [u8; CV_LEN] {
+        let mut sponge = Sponge::new(RATE, ROUNDS);
+        sponge.absorb(chunk);
+        let mut cv = [0u8; CV_LEN];
+        sponge.finalize(LEAF_DOMAIN).read(&mut cv);
+        cv
+    }
+
+    /// Absorbs complete chunks, each known to be followed by more input.
+    fn absorb_chunks(&mut self, mut data: &[u8]) {
+        debug_assert!(data.len().is_multiple_of(CHUNK_LEN));
+        if data.is_empty() {
+            return;
+        }
+        if self.chunks == 0 {
+            self.sponge.absorb(&data[..CHUNK_LEN]);
+            self.sponge.absorb(&FIRST_CHUNK_SUFFIX);
+            self.chunks = 1;
+            data = &data[CHUNK_LEN..];
+        }
+        let count = data.len() / CHUNK_LEN;
+        if self.parallel && count > 1 {
+            let cvs: Vec<[u8; CV_LEN]> =
+                data.par_chunks(CHUNK_LEN).map(Self::chaining_value).collect();
+            for cv in &cvs {
+                self.sponge.absorb(cv);
+            }
+        } else {
+            for chunk in data.chunks_exact(CHUNK_LEN) {
+                self.sponge.absorb(&Self::chaining_value(chunk));
+            }
+        }
+        self.chunks += count as u64;
+    }
+
+    /// Feeds bytes of `S` into the tree.
+    fn absorb(&mut self, mut data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        if !self.buffer.is_empty() {
+            if self.buffer.len() < CHUNK_LEN {
+                let take = data.len().min(CHUNK_LEN - self.buffer.len());
+                self.buffer.extend_from_slice(&data[..take]);
+                data = &data[take..];
+                if data.is_empty() {
+                    return;
+                }
+            }
+            let chunk = std::mem::take(&mut self.buffer);
+            self.absorb_chunks(&chunk);
+            self.buffer = chunk;
+            self.buffer.clear();
+        }
+
+        // Keep the last (possibly full) chunk buffered: only later input
+        // tells whether it is the final one.
+        let whole = (data.len() - 1) / CHUNK_LEN * CHUNK_LEN;
+        self.absorb_chunks(&data[..whole]);
+        self.buffer.extend_from_slice(&data[whole..]);
+    }
+
+    /// Feeds data into the hasher. KangarooTwelve has no message length
+    /// limit.
+    ///
+    /// Complete chunks are hashed right away, in parallel when there are
+    /// several.
+    pub fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+
+    /// Returns a reader over the output stream for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> XofReader {
+        let mut tree = self.clone();
+        let customization = std::mem::take(&mut tree.customization);
+        tree.absorb(&customization);
+        let (suffix, len) = length_encode(customization.len() as u64);
+        tree.absorb(&suffix[..len]);
+
+        if tree.chunks == 0 {
+            tree.sponge.absorb(&tree.buffer);
+            return tree.sponge.finalize(SINGLE_NODE_DOMAIN);
+        }
+        tree.sponge.absorb(&Self::chaining_value(&tree.buffer));
+        let (suffix, len) = length_encode(tree.chunks);
+        tree.sponge.absorb(&suffix[..len]);
+        tree.sponge.absorb(&FINAL_NODE_SUFFIX);
+        tree.sponge.finalize(FINAL_NODE_DOMAIN)
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        self.finalize_xof().read_vec(length)
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, keeping the customization
+    /// string.
+    pub fn reset(&mut self) {
+        self.sponge = Sponge::new(RATE, ROUNDS);
+        self.buffer.clear();
+        self.chunks = 0;
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl Xof for KangarooTwelve {
+    /// Never fails: KangarooTwelve has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `ptn(n)` of RFC 9861: the repeating pattern `00 01 .. FA`.
+    fn ptn(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn k12_hex(message: &[u8], customization: &[u8], length: usize) -> String {
+        let mut hasher = KangarooTwelve::new(customization);
+        hasher.update(message);
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_length_encode() {
+        let (out, len) = length_encode(0);
+        assert_eq!(&out[..len], &[0]);
+        let (out, len) = length_encode(12);
+        assert_eq!(&out[..len], &[12, 1]);
+        let (out, len) = length_encode(65538);
+        assert_eq!(&out[..len], &[1, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_k12_empty() {
+        assert_eq!(
+            k12_hex(b"", b"", 32),
+            "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"
+        );
+        assert_eq!(
+            k12_hex(b"", b"", 64),
+            "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5\
+             4269c056b8c82e48276038b6d292966cc07a3d4645272e31ff38508139eb0a71"
+        );
+        let long = KangarooTwelve::new(b"").digest(10032);
+        assert_eq!(
+            to_hex(&long[10000..]),
+            "e8dc563642f7228c84684c898405d3a834799158c079b12880277a1d28e2ff6d"
+        );
+    }
+
+    #[test]
+    fn test_k12_pattern_messages() {
+        let expected = [
+            "2bda92450e8b147f8a7cb629e784a058efca7cf7d8218e02d345dfaa65244a1f",
+            "6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888",
+            "0c315ebcdedbf61426de7dcf8fb725d1e74675d7f5327a5067f367b108ecb67c",
+            "cb552e2ec77d9910701d578b457ddf772c12e322e4ee7fe417f92c758f0d59d0",
+            "8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe",
+        ];
+        for (i, expected) in expected.iter().enumerate() {
+            assert_eq!(k12_hex(&ptn(17usize.pow(i as u32)), b"", 32), *expected, "17^{}", i);
+        }
+    }
+
+    #[test]
+    #[ignore = "large inputs, run with `cargo test --release -- --ignored`"]
+    fn test_k12_long_pattern_messages() {
+        assert_eq!(
+            k12_hex(&ptn(17usize.pow(5)), b"", 32),
+            "844d610933b1b9963cbdeb5ae3b6b05cc7cbd67ceedf883eb678a0a8e0371682"
+        );
+        assert_eq!(
+            k12_hex(&ptn(17usize.pow(6)), b"", 32),
+            "3c390782a8a4e89fa6367f72feaaf13255c8d95878481d3cd8ce85f58e880af8"
+        );
+    }
+
+    #[test]
+    fn test_k12_customization() {
+        assert_eq!(
+            k12_hex(b"", &ptn(1), 32),
+            "fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583"
+        );
+        assert_eq!(
+            k12_hex(&[0xff], &ptn(41), 32),
+            "d848c5068ced736f4462159b9867fd4c20b808acc3d5bc48e0b06ba0a3762ec4"
+        );
+        assert_eq!(
+            k12_hex(&[0xff; 3], &ptn(41 * 41), 32),
+            "c389e5009ae57120854c2e8c64670ac01358cf4c1baf89447a724234dc7ced74"
+        );
+        assert_eq!(
+            k12_hex(&[0xff; 7], &ptn(41 * 41 * 41), 32),
+            "75d2f86a2e644566726b4fbcfc5657b9dbcf070c7b0dca06450ab291d7443bcf"
+        );
+    }
+
+    #[test]
+    fn test_k12_chunk_boundaries() {
+        assert_eq!(
+            k12_hex(&ptn(8191), b"", 32),
+            "1b577636f723643e990cc7d6a659837436fd6a103626600eb8301cd1dbe553d6"
+        );
+        assert_eq!(
+            k12_hex(&ptn(8192), b"", 32),
+            "48f256f6772f9edfb6a8b661ec92dc93b95ebd05a08a17b39ae3490870c926c3"
+        );
+        assert_eq!(
+            k12_hex(&ptn(8192), &ptn(8189), 32),
+            "3ed12f70fb05ddb58689510ab3e4d23c6c6033849aa01e1d8c220a297fedcd0b"
+        );
+        assert_eq!(
+            k12_hex(&ptn(8192), &ptn(8190), 32),
+            "6a7c1b6a5cd0d8c9ca943a4a216cc64604559a2ea45f78570a15253d67ba00ae"
+        );
+    }
+
+    #[test]
+    fn test_parallel_and_serial_paths_match() {
+        let data = ptn(5 * CHUNK_LEN + 100);
+        let mut parallel = KangarooTwelve::new(b"C");
+        let mut serial = KangarooTwelve::new(b"C");
+        serial.set_parallel(false);
+        for chunk in data.chunks(3 * CHUNK_LEN - 1) {
+            parallel.update(chunk);
+            serial.update(chunk);
+        }
+        assert_eq!(parallel.digest(32), serial.digest(32));
+    }
+
+    #[test]
+    fn test_update_split_matches_whole() {
+        let data = ptn(2 * CHUNK_LEN + 1);
+        let expected = k12_hex(&data, b"", 32);
+        for split in [0, 1, CHUNK_LEN - 1, CHUNK_LEN, CHUNK_LEN + 1, 2 * CHUNK_LEN, data.len()] {
+            let mut hasher = KangarooTwelve::new(b"");
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.hexdigest(32), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn bench_k12_vs_sha256_1gb() {
+        use crate::core::Sha256;
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let data = vec![0xa5u8; 1 << 30];
+
+        let start = Instant::now();
+        let mut hasher = Sha256::new();
+        hasher.update(black_box(&data));
+        black_box(hasher.digest());
+        let sha256_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut hasher = KangarooTwelve::new(b"");
+        hasher.update(black_box(&data));
+        black_box(hasher.digest(32));
+        let k12_time = start.elapsed();
+
+        let gib = |time: std::time::Duration| 1.0 / time.as_secs_f64();
+        println!(
+            "SHA-256: {:?} ({:.2} GiB/s), K12: {:?} ({:.2} GiB/s), speedup: {:.1}x",
+            sha256_time,
+            gib(sha256_time),
+            k12_time,
+            gib(k12_time),
+            sha256_time.as_secs_f64() / k12_time.as_secs_f64()
+        );
+    }
+}
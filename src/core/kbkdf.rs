@@ -0,0 +1,234 @@
+//! KBKDF key derivation in counter mode with HMAC.
+//!
+//! Pure Rust implementation of the counter mode of NIST SP 800-108, with
+//! HMAC over any [`Digest`] as the pseudorandom function and the counter
+//! placed before the fixed input data:
+//!
+//! ```text
+//! K(i) = HMAC(KI, [i]_r || FixedInput),  KO = K(1) || K(2) || ...
+//! FixedInput = Label || 0x00 || Context || [L]_llen   (see fixed_input)
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **Counter**: `rlen` of 8, 16, 24 or 32 bits, starting at 1
+//! - **Length field**: `L` in bits, on `llen` of 8, 16, 24 or 32 bits, or
+//!   left out when `llen` is 0
+//! - **Byte order**: big-endian per the specification; some vendors write
+//!   both fields little-endian
+
+use super::{Digest, Hmac, ParameterError};
+
+/// Byte order of the counter and length fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Most significant byte first, as in SP 800-108.
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+impl ByteOrder {
+    /// Parses `"big"` or `"little"`, as in `int.to_bytes()`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "big" => Some(Self::Big),
+            "little" => Some(Self::Little),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the low `bits` bits of `value` in the given byte order.
+fn encode_int(value: u32, bits: u32, order: ByteOrder) -> Vec<u8> {
+    let len = (bits / 8) as usize;
+    match order {
+        ByteOrder::Big => value.to_be_bytes()[4 - len..].to_vec(),
+        ByteOrder::Little => value.to_le_bytes()[..len].to_vec(),
+    }
+}
+
+/// Builds the usual fixed input data `Label || 0x00 || Context || [L]`,
+/// where `L` is `length` bytes expressed in bits on `llen` bits.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `llen` is not 0, 8, 16, 24 or 32, or if
+/// `L` does not fit in `llen` bits.
+pub fn fixed_input(
+    label: &[u8],
+    context: &[u8],
+    length: usize,
+    llen: u32,
+    order: ByteOrder,
+) -> Result<Vec<u8>, ParameterError> {
+    let error = |message: String| ParameterError { algorithm: "KBKDF", message };
+    if !matches!(llen, 0 | 8 | 16 | 24 | 32) {
+        return Err(error(format!("llen must be 0, 8, 16, 24 or 32 bits, got {}", llen)));
+    }
+    let mut fixed = [label, &[0], context].concat();
+    if llen > 0 {
+        let bits = (length as u64)
+            .checked_mul(8)
+            .filter(|&bits| bits >> llen == 0)
+            .ok_or_else(|| error(format!("length does not fit in {} bits", llen)))?;
+        fixed.extend(encode_int(bits as u32, llen, order));
+    }
+    Ok(fixed)
+}
+
+/// Derives `length` bytes from the key `key` and the fixed input data
+/// `fixed`, with an `rlen`-bit counter in front of each HMAC input.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `rlen` is not 8, 16, 24 or 32, or if
+/// `length` needs more blocks than the counter can number.
+pub fn kbkdf_counter<D: Digest>(
+    hasher: D,
+    key: &[u8],
+    fixed: &[u8],
+    length: usize,
+    rlen: u32,
+    order: ByteOrder,
+) -> Result<Vec<u8>, ParameterError> {
+    let error = |message: String| ParameterError { algorithm: "KBKDF", message };
+    if !matches!(rlen, 8 | 16 | 24 | 32) {
+        return Err(error(format!("rlen must be 8, 16, 24 or 32 bits, got {}", rlen)));
+    }
+    let hash_len = hasher.digest_size();
+    let max_blocks = (1u64 << rlen) - 1;
+    if length.div_ceil(hash_len) as u64 > max_blocks {
+        return Err(error(format!(
+            "length must be at most (2^{} - 1) * {} bytes",
+            rlen, hash_len
+        )));
+    }
+
+    let keyed = Hmac::new(hasher, key);
+    let mut out = Vec::with_capacity(length);
+    for counter in 1..=length.div_ceil(hash_len) as u32 {
+        let mut mac = keyed.clone();
+        mac.update(&encode_int(counter, rlen, order));
+        mac.update(fixed);
+        let block = mac.digest();
+        let take = (length - out.len()).min(hash_len);
+        out.extend_from_slice(&block.as_ref()[..take]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Sha1, Sha256, Sha512};
+    use crate::utils::to_hex;
+
+    fn unhex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_kbkdf_counter_raw_fixed_input() {
+        // Counter before raw fixed input data, as in the CAVP files;
+        // cross-checked against the KBKDFHMAC of pyca/cryptography
+        let out = kbkdf_counter(
+            Sha1::new(),
+            &unhex("b02c780e9917d6feecf631e840857515d8302d23"),
+            &unhex(
+                "a9e53f3ba4e1ae739d25002ea09c3dbe4ef674763d023d3315e6fba7bdef3863\
+                 4030e8397fd8520de57d902226c2b41b02cd5d7946c97f51d65b45f1",
+            ),
+            16,
+            8,
+            ByteOrder::Big,
+        );
+        assert_eq!(to_hex(&out.unwrap()), "6ea039347c2a769141d297acd5ed4054");
+
+        let out = kbkdf_counter(
+            Sha256::new(),
+            &unhex("f5e616982aef683d364236c5e5ffeffa7819a155a59944da32e6016fe02f83f2"),
+            &unhex(
+                "40614551130b4cfe28e860e75f504d80461374143bbc86f853f57085b896c7ee\
+                 1a65d91009172cd4bfe3e1d23a61d67b5439a54194aa181e51c922cc",
+            ),
+            40,
+            32,
+            ByteOrder::Big,
+        );
+        assert_eq!(
+            to_hex(&out.unwrap()),
+            "090e52dcd3c3354ee8c45dd28801c844fa2a2e84ed63e9f9e1f3128f4ff10a3b2c46511314a573b0"
+        );
+
+        let out = kbkdf_counter(
+            Sha512::new(),
+            &unhex(
+                "ea0b7e8e874291f6a84ceb2b02f1813d726457806a54acc5ce659c83d654dd92\
+                 f5560e28d229eacc97846e61185a7cf0d6cede85d093648106a088774ae46f54",
+            ),
+            &unhex(
+                "e198cff478729067ec9b35e090aa05ad86f65a3bfcbad4c8486de18a0f8b0dad\
+                 a69e16bd38c70e4c3c9a1be870fbd11ea4331b2476ef41cda9946873aed9da94",
+            ),
+            80,
+            16,
+            ByteOrder::Big,
+        );
+        assert_eq!(
+            to_hex(&out.unwrap()),
+            "131e3395f52901d406a586c0ce56cc9a771e1d28828531d75bc70ab31a0604ef\
+             d96373cfbf212d5ecfe1a7b55db7ca2bec41fc60081ec81c71da9aac5cf90b0a\
+             414773908f3ac6114fc3dadb5fcdd0c0"
+        );
+    }
+
+    #[test]
+    fn test_kbkdf_counter_label_and_context() {
+        let derive = |rlen, llen, order| {
+            let fixed = fixed_input(b"label", b"context", 42, llen, order).unwrap();
+            let key = b"key".repeat(8);
+            to_hex(&kbkdf_counter(Sha256::new(), &key, &fixed, 42, rlen, order).unwrap())
+        };
+        assert_eq!(
+            derive(32, 32, ByteOrder::Big),
+            "f7d39a79f71df1dd5440faddaa0510ced6cae64b17698777b38c38cb204b3833a2032b8bf40df6183947"
+        );
+        assert_eq!(
+            derive(8, 16, ByteOrder::Big),
+            "5c48bd33bfaf533ef39e5827b8c3f0aaf5b1ac27dbff995dbbe2f2c8cc4387959d36b93de31c7f4ba9bd"
+        );
+        assert_eq!(
+            derive(32, 32, ByteOrder::Little),
+            "e2b00e83f588878f3beff172062d635330c196896de323379e7c4587421ed6942124fb75755835d88ef0"
+        );
+    }
+
+    #[test]
+    fn test_kbkdf_fixed_input() {
+        let fixed = fixed_input(b"ab", b"cd", 32, 16, ByteOrder::Big).unwrap();
+        assert_eq!(fixed, b"ab\0cd\x01\x00");
+        let fixed = fixed_input(b"ab", b"cd", 32, 24, ByteOrder::Little).unwrap();
+        assert_eq!(fixed, b"ab\0cd\x00\x01\x00");
+        assert_eq!(fixed_input(b"ab", b"cd", 32, 0, ByteOrder::Big).unwrap(), b"ab\0cd");
+        assert!(fixed_input(b"", b"", 32, 8, ByteOrder::Big).is_err());
+        assert!(fixed_input(b"", b"", 31, 8, ByteOrder::Big).is_ok());
+        assert!(fixed_input(b"", b"", 1, 12, ByteOrder::Big).is_err());
+    }
+
+    #[test]
+    fn test_kbkdf_counter_limits() {
+        let longest = kbkdf_counter(Sha256::new(), b"key", b"", 255 * 32, 8, ByteOrder::Big);
+        assert_eq!(longest.unwrap().len(), 8160);
+        let err = kbkdf_counter(Sha256::new(), b"key", b"", 255 * 32 + 1, 8, ByteOrder::Big);
+        assert_eq!(err.unwrap_err().algorithm, "KBKDF");
+        assert!(kbkdf_counter(Sha256::new(), b"key", b"", 16, 12, ByteOrder::Big).is_err());
+        let empty = kbkdf_counter(Sha256::new(), b"key", b"", 0, 8, ByteOrder::Big);
+        assert!(empty.unwrap().is_empty());
+    }
+}
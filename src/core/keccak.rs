@@ -0,0 +1,419 @@
+//! Keccak permutation and sponge construction.
+//!
+//! Pure Rust implementation of Keccak-p[1600] following FIPS 202. Shared by
+//! SHA-3, SHAKE and the other Keccak-based functions, which only differ in
+//! their rate, domain separation byte, output length and round count.
+//!
+//! # Algorithm Details
+//!
+//! - **State**: 1600 bits (25 lanes of 64 bits, little-endian)
+//! - **Rounds**: 24 for Keccak-f[1600], fewer for reduced-round variants
+//! - **Padding**: domain byte, then `pad10*1` up to the rate
+//!
+//! [`Keccak256`] and [`Keccak512`] are the original Keccak submission, as
+//! used by Ethereum: same sponge as SHA-3 but with the legacy `0x01`
+//! padding, so their digests differ from SHA3-256 and SHA3-512.
+
+use super::{Digest, LengthOverflowError, Xof};
+use crate::utils::to_hex;
+
+/// Round constants of Keccak-f[1600], for rounds 0 to 23.
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// Rotation offsets of the rho step, in the lane order visited by pi.
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// Lane visiting order of the pi step, starting from lane 1.
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Applies the last `rounds` rounds of Keccak-f[1600] to `state`.
+///
+/// `rounds = 24` is the full permutation; 12 gives the Keccak-p[1600, 12]
+/// permutation used by TurboSHAKE and KangarooTwelve.
+pub(crate) fn keccak_p1600(state: &mut [u64; 25], rounds: usize) {
+    for &rc in &RC[24 - rounds..] {
+        // Theta
+        let mut c = [0u64; 5];
+        for (x, column) in c.iter_mut().enumerate() {
+            *column = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[5 * y + x] ^= d;
+            }
+        }
+
+        // Rho and pi
+        let mut last = state[1];
+        for (&lane, &rotation) in PI.iter().zip(RHO.iter()) {
+            let next = state[lane];
+            state[lane] = last.rotate_left(rotation);
+            last = next;
+        }
+
+        // Chi
+        for y in 0..5 {
+            let row: [u64; 5] = state[5 * y..5 * y + 5].try_into().unwrap();
+            for x in 0..5 {
+                state[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        state[0] ^= rc;
+    }
+}
+
+/// Absorbing half of a Keccak sponge.
+///
+/// Input is XORed straight into the state, so no block buffer is needed.
+/// Cloning captures the full absorbing state.
+#[derive(Clone)]
+pub(crate) struct Sponge {
+    state: [u64; 25],
+    /// Rate in bytes.
+    rate: usize,
+    /// Number of bytes absorbed into the current block.
+    pos: usize,
+    rounds: usize,
+}
+
+impl Sponge {
+    /// Creates an empty sponge with a rate of `rate` bytes.
+    pub(crate) fn new(rate: usize, rounds: usize) -> Self {
+        debug_assert!(rate > 0 && rate < 200 && rate.is_multiple_of(8));
+        Sponge { state: [0u64; 25], rate, pos: 0, rounds }
+    }
+
+    fn xor_byte(state: &mut [u64; 25], index: usize, byte: u8) {
+        state[index / 8] ^= (byte as u64) << (8 * (index % 8));
+    }
+
+    /// Absorbs `data` into the sponge.
+    pub(crate) fn absorb(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.pos == 0 && data.len() >= self.rate {
+                // Whole block: XOR lane by lane
+                let (block, rest) = data.split_at(self.rate);
+                for (lane, bytes) in self.state.iter_mut().zip(block.chunks_exact(8)) {
+                    *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+                }
+                keccak_p1600(&mut self.state, self.rounds);
+                data = rest;
+                continue;
+            }
+
+            let take = data.len().min(self.rate - self.pos);
+            for (i, &byte) in data[..take].iter().enumerate() {
+                Self::xor_byte(&mut self.state, self.pos + i, byte);
+            }
+            self.pos += take;
+            data = &data[take..];
+            if self.pos == self.rate {
+                keccak_p1600(&mut self.state, self.rounds);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Pads a copy of the sponge with `domain` and `pad10*1`, returning a
+    /// reader over the output stream.
+    ///
+    /// `domain` holds the domain separation bits followed by the first
+    /// padding bit, e.g. `0x06` for SHA-3, `0x1f` for SHAKE and `0x01` for
+    /// the original Keccak submission.
+    pub(crate) fn finalize(&self, domain: u8) -> XofReader {
+        let mut state = self.state;
+        Self::xor_byte(&mut state, self.pos, domain);
+        Self::xor_byte(&mut state, self.rate - 1, 0x80);
+        keccak_p1600(&mut state, self.rounds);
+        XofReader { state, rate: self.rate, pos: 0, rounds: self.rounds }
+    }
+}
+
+/// Squeezing half of a Keccak sponge: an endless stream of output bytes.
+///
+/// Successive reads continue the stream, so reading 16 then 16 bytes gives
+/// the same output as reading 32 bytes at once.
+#[derive(Clone)]
+pub struct XofReader {
+    state: [u64; 25],
+    rate: usize,
+    /// Number of output bytes already read from the current block.
+    pos: usize,
+    rounds: usize,
+}
+
+impl XofReader {
+    /// Fills `out` with the next output bytes.
+    pub fn read(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            if self.pos == self.rate {
+                keccak_p1600(&mut self.state, self.rounds);
+                self.pos = 0;
+            }
+            *byte = (self.state[self.pos / 8] >> (8 * (self.pos % 8))) as u8;
+            self.pos += 1;
+        }
+    }
+
+    /// Returns the next `len` output bytes.
+    pub fn read_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; len];
+        self.read(&mut out);
+        out
+    }
+}
+
+/// Fixed-output Keccak hash: a 24-round sponge with `RATE` bytes of rate,
+/// `OUT` bytes of output and `DOMAIN` as domain separation byte.
+///
+/// Instantiated as the SHA-3 functions (see [`super::sha3`]) and the
+/// original Keccak submission (`DOMAIN = 0x01`).
+#[derive(Clone)]
+pub struct KeccakDigest<const RATE: usize, const OUT: usize, const DOMAIN: u8> {
+    sponge: Sponge,
+}
+
+impl<const RATE: usize, const OUT: usize, const DOMAIN: u8> KeccakDigest<RATE, OUT, DOMAIN> {
+    /// Creates a new hasher with an empty state.
+    pub fn new() -> Self {
+        KeccakDigest { sponge: Sponge::new(RATE, 24) }
+    }
+
+    /// Feeds data into the hasher. Keccak has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the state, so the hasher can keep
+    /// absorbing data afterwards.
+    pub fn digest(&self) -> [u8; OUT] {
+        let mut result = [0u8; OUT];
+        self.sponge.finalize(DOMAIN).read(&mut result);
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; OUT] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size() -> usize {
+        OUT
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize, const OUT: usize, const DOMAIN: u8> Default
+    for KeccakDigest<RATE, OUT, DOMAIN>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const RATE: usize, const OUT: usize, const DOMAIN: u8> Digest
+    for KeccakDigest<RATE, OUT, DOMAIN>
+{
+    type Output = [u8; OUT];
+
+    /// Never fails: Keccak has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; OUT] {
+        KeccakDigest::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        OUT
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+/// Domain byte of the original Keccak submission: no suffix, only `pad10*1`.
+const LEGACY_DOMAIN: u8 = 0x01;
+
+/// Keccak-256 hasher with the original (pre-FIPS 202) padding.
+pub type Keccak256 = KeccakDigest<136, 32, LEGACY_DOMAIN>;
+
+/// Keccak-512 hasher with the original (pre-FIPS 202) padding.
+pub type Keccak512 = KeccakDigest<72, 64, LEGACY_DOMAIN>;
+
+/// Keccak extendable-output function: a 24-round sponge with `RATE` bytes
+/// of rate and `DOMAIN` as domain separation byte.
+///
+/// Instantiated as the SHAKE functions (see [`super::shake`]).
+#[derive(Clone)]
+pub struct KeccakXof<const RATE: usize, const DOMAIN: u8> {
+    sponge: Sponge,
+}
+
+impl<const RATE: usize, const DOMAIN: u8> KeccakXof<RATE, DOMAIN> {
+    /// Creates a new hasher with an empty state.
+    pub fn new() -> Self {
+        KeccakXof { sponge: Sponge::new(RATE, 24) }
+    }
+
+    /// Feeds data into the hasher. Keccak has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns a reader over the output stream for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> XofReader {
+        self.sponge.finalize(DOMAIN)
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        self.finalize_xof().read_vec(length)
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize, const DOMAIN: u8> Default for KeccakXof<RATE, DOMAIN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const RATE: usize, const DOMAIN: u8> Xof for KeccakXof<RATE, DOMAIN> {
+    /// Never fails: Keccak has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak_f1600_zero_state() {
+        // First permutation of the all-zero state (Keccak team reference)
+        let mut state = [0u64; 25];
+        keccak_p1600(&mut state, 24);
+        assert_eq!(state[0], 0xf1258f7940e1dde7);
+        assert_eq!(state[1], 0x84d5ccf933c0478a);
+        assert_eq!(state[24], 0xeaf1ff7b5ceca249);
+    }
+
+    #[test]
+    fn test_keccak256_legacy_padding() {
+        assert_eq!(
+            Keccak256::new().hexdigest(),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        let mut hasher = Keccak256::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn test_keccak512_legacy_padding() {
+        assert_eq!(
+            Keccak512::new().hexdigest(),
+            "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304\
+             c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e"
+        );
+        let mut hasher = Keccak512::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5\
+             d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96"
+        );
+    }
+
+    #[test]
+    fn test_reader_streams_consistently() {
+        let mut sponge = Sponge::new(168, 24);
+        sponge.absorb(b"abc");
+        let whole = sponge.finalize(0x1f).read_vec(500);
+        let mut reader = sponge.finalize(0x1f);
+        let mut pieces = Vec::new();
+        for len in [1, 167, 1, 168, 163] {
+            pieces.extend(reader.read_vec(len));
+        }
+        assert_eq!(pieces, whole);
+    }
+
+    #[test]
+    fn test_absorb_split_matches_whole() {
+        let data: Vec<u8> = (0..600u32).map(|i| (i * 13) as u8).collect();
+        let mut whole = Sponge::new(136, 24);
+        whole.absorb(&data);
+        for split in [0, 1, 135, 136, 137, 272, 599] {
+            let mut parts = Sponge::new(136, 24);
+            parts.absorb(&data[..split]);
+            parts.absorb(&data[split..]);
+            assert_eq!(parts.finalize(0x06).read_vec(32), whole.finalize(0x06).read_vec(32));
+        }
+    }
+}
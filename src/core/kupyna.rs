@@ -0,0 +1,457 @@
+//! Kupyna hash function implementation.
+//!
+//! Pure Rust implementation of Kupyna, the Ukrainian hash standard
+//! (DSTU 7564:2014). Like Grøstl, it is a wide-pipe design built from two
+//! AES-like permutations: each block `m` updates the chaining value as
+//! `T⊕(h ^ m) ^ T+(m) ^ h`, and the output transform truncates
+//! `T⊕(h) ^ h`. The permutations use the four S-boxes of the Kalyna block
+//! cipher (DSTU 7624:2014), one per row modulo 4, and differ in their round
+//! constants: T⊕ XORs them into the first row, T+ adds them to each column
+//! modulo 2^64.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits for [`Kupyna256`], 1024 bits for [`Kupyna512`]
+//! - **Digest size**: 256 or 512 bits, the last bytes of the state
+//! - **Rounds**: 10 (Kupyna-256) or 14 (Kupyna-512) per permutation
+//! - **Padding**: a 1 bit, zeros, then the 96-bit little-endian bit length
+//!
+//! The state is a matrix of 8 rows and 8 or 16 columns, stored as one
+//! little-endian 64-bit word per column. The S-boxes and the MDS matrix
+//! are merged into eight precomputed 256-entry tables, computed at compile
+//! time, and the row shifts are folded into the column each lookup reads
+//! from.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Kalyna S-box π0 (DSTU 7624:2014).
+const SBOX_0: [u8; 256] = [
+    0xa8, 0x43, 0x5f, 0x06, 0x6b, 0x75, 0x6c, 0x59, 0x71, 0xdf, 0x87, 0x95, 0x17, 0xf0, 0xd8, 0x09,
+    0x6d, 0xf3, 0x1d, 0xcb, 0xc9, 0x4d, 0x2c, 0xaf, 0x79, 0xe0, 0x97, 0xfd, 0x6f, 0x4b, 0x45, 0x39,
+    0x3e, 0xdd, 0xa3, 0x4f, 0xb4, 0xb6, 0x9a, 0x0e, 0x1f, 0xbf, 0x15, 0xe1, 0x49, 0xd2, 0x93, 0xc6,
+    0x92, 0x72, 0x9e, 0x61, 0xd1, 0x63, 0xfa, 0xee, 0xf4, 0x19, 0xd5, 0xad, 0x58, 0xa4, 0xbb, 0xa1,
+    0xdc, 0xf2, 0x83, 0x37, 0x42, 0xe4, 0x7a, 0x32, 0x9c, 0xcc, 0xab, 0x4a, 0x8f, 0x6e, 0x04, 0x27,
+    0x2e, 0xe7, 0xe2, 0x5a, 0x96, 0x16, 0x23, 0x2b, 0xc2, 0x65, 0x66, 0x0f, 0xbc, 0xa9, 0x47, 0x41,
+    0x34, 0x48, 0xfc, 0xb7, 0x6a, 0x88, 0xa5, 0x53, 0x86, 0xf9, 0x5b, 0xdb, 0x38, 0x7b, 0xc3, 0x1e,
+    0x22, 0x33, 0x24, 0x28, 0x36, 0xc7, 0xb2, 0x3b, 0x8e, 0x77, 0xba, 0xf5, 0x14, 0x9f, 0x08, 0x55,
+    0x9b, 0x4c, 0xfe, 0x60, 0x5c, 0xda, 0x18, 0x46, 0xcd, 0x7d, 0x21, 0xb0, 0x3f, 0x1b, 0x89, 0xff,
+    0xeb, 0x84, 0x69, 0x3a, 0x9d, 0xd7, 0xd3, 0x70, 0x67, 0x40, 0xb5, 0xde, 0x5d, 0x30, 0x91, 0xb1,
+    0x78, 0x11, 0x01, 0xe5, 0x00, 0x68, 0x98, 0xa0, 0xc5, 0x02, 0xa6, 0x74, 0x2d, 0x0b, 0xa2, 0x76,
+    0xb3, 0xbe, 0xce, 0xbd, 0xae, 0xe9, 0x8a, 0x31, 0x1c, 0xec, 0xf1, 0x99, 0x94, 0xaa, 0xf6, 0x26,
+    0x2f, 0xef, 0xe8, 0x8c, 0x35, 0x03, 0xd4, 0x7f, 0xfb, 0x05, 0xc1, 0x5e, 0x90, 0x20, 0x3d, 0x82,
+    0xf7, 0xea, 0x0a, 0x0d, 0x7e, 0xf8, 0x50, 0x1a, 0xc4, 0x07, 0x57, 0xb8, 0x3c, 0x62, 0xe3, 0xc8,
+    0xac, 0x52, 0x64, 0x10, 0xd0, 0xd9, 0x13, 0x0c, 0x12, 0x29, 0x51, 0xb9, 0xcf, 0xd6, 0x73, 0x8d,
+    0x81, 0x54, 0xc0, 0xed, 0x4e, 0x44, 0xa7, 0x2a, 0x85, 0x25, 0xe6, 0xca, 0x7c, 0x8b, 0x56, 0x80,
+];
+
+/// Kalyna S-box π1 (DSTU 7624:2014).
+const SBOX_1: [u8; 256] = [
+    0xce, 0xbb, 0xeb, 0x92, 0xea, 0xcb, 0x13, 0xc1, 0xe9, 0x3a, 0xd6, 0xb2, 0xd2, 0x90, 0x17, 0xf8,
+    0x42, 0x15, 0x56, 0xb4, 0x65, 0x1c, 0x88, 0x43, 0xc5, 0x5c, 0x36, 0xba, 0xf5, 0x57, 0x67, 0x8d,
+    0x31, 0xf6, 0x64, 0x58, 0x9e, 0xf4, 0x22, 0xaa, 0x75, 0x0f, 0x02, 0xb1, 0xdf, 0x6d, 0x73, 0x4d,
+    0x7c, 0x26, 0x2e, 0xf7, 0x08, 0x5d, 0x44, 0x3e, 0x9f, 0x14, 0xc8, 0xae, 0x54, 0x10, 0xd8, 0xbc,
+    0x1a, 0x6b, 0x69, 0xf3, 0xbd, 0x33, 0xab, 0xfa, 0xd1, 0x9b, 0x68, 0x4e, 0x16, 0x95, 0x91, 0xee,
+    0x4c, 0x63, 0x8e, 0x5b, 0xcc, 0x3c, 0x19, 0xa1, 0x81, 0x49, 0x7b, 0xd9, 0x6f, 0x37, 0x60, 0xca,
+    0xe7, 0x2b, 0x48, 0xfd, 0x96, 0x45, 0xfc, 0x41, 0x12, 0x0d, 0x79, 0xe5, 0x89, 0x8c, 0xe3, 0x20,
+    0x30, 0xdc, 0xb7, 0x6c, 0x4a, 0xb5, 0x3f, 0x97, 0xd4, 0x62, 0x2d, 0x06, 0xa4, 0xa5, 0x83, 0x5f,
+    0x2a, 0xda, 0xc9, 0x00, 0x7e, 0xa2, 0x55, 0xbf, 0x11, 0xd5, 0x9c, 0xcf, 0x0e, 0x0a, 0x3d, 0x51,
+    0x7d, 0x93, 0x1b, 0xfe, 0xc4, 0x47, 0x09, 0x86, 0x0b, 0x8f, 0x9d, 0x6a, 0x07, 0xb9, 0xb0, 0x98,
+    0x18, 0x32, 0x71, 0x4b, 0xef, 0x3b, 0x70, 0xa0, 0xe4, 0x40, 0xff, 0xc3, 0xa9, 0xe6, 0x78, 0xf9,
+    0x8b, 0x46, 0x80, 0x1e, 0x38, 0xe1, 0xb8, 0xa8, 0xe0, 0x0c, 0x23, 0x76, 0x1d, 0x25, 0x24, 0x05,
+    0xf1, 0x6e, 0x94, 0x28, 0x9a, 0x84, 0xe8, 0xa3, 0x4f, 0x77, 0xd3, 0x85, 0xe2, 0x52, 0xf2, 0x82,
+    0x50, 0x7a, 0x2f, 0x74, 0x53, 0xb3, 0x61, 0xaf, 0x39, 0x35, 0xde, 0xcd, 0x1f, 0x99, 0xac, 0xad,
+    0x72, 0x2c, 0xdd, 0xd0, 0x87, 0xbe, 0x5e, 0xa6, 0xec, 0x04, 0xc6, 0x03, 0x34, 0xfb, 0xdb, 0x59,
+    0xb6, 0xc2, 0x01, 0xf0, 0x5a, 0xed, 0xa7, 0x66, 0x21, 0x7f, 0x8a, 0x27, 0xc7, 0xc0, 0x29, 0xd7,
+];
+
+/// Kalyna S-box π2 (DSTU 7624:2014).
+const SBOX_2: [u8; 256] = [
+    0x93, 0xd9, 0x9a, 0xb5, 0x98, 0x22, 0x45, 0xfc, 0xba, 0x6a, 0xdf, 0x02, 0x9f, 0xdc, 0x51, 0x59,
+    0x4a, 0x17, 0x2b, 0xc2, 0x94, 0xf4, 0xbb, 0xa3, 0x62, 0xe4, 0x71, 0xd4, 0xcd, 0x70, 0x16, 0xe1,
+    0x49, 0x3c, 0xc0, 0xd8, 0x5c, 0x9b, 0xad, 0x85, 0x53, 0xa1, 0x7a, 0xc8, 0x2d, 0xe0, 0xd1, 0x72,
+    0xa6, 0x2c, 0xc4, 0xe3, 0x76, 0x78, 0xb7, 0xb4, 0x09, 0x3b, 0x0e, 0x41, 0x4c, 0xde, 0xb2, 0x90,
+    0x25, 0xa5, 0xd7, 0x03, 0x11, 0x00, 0xc3, 0x2e, 0x92, 0xef, 0x4e, 0x12, 0x9d, 0x7d, 0xcb, 0x35,
+    0x10, 0xd5, 0x4f, 0x9e, 0x4d, 0xa9, 0x55, 0xc6, 0xd0, 0x7b, 0x18, 0x97, 0xd3, 0x36, 0xe6, 0x48,
+    0x56, 0x81, 0x8f, 0x77, 0xcc, 0x9c, 0xb9, 0xe2, 0xac, 0xb8, 0x2f, 0x15, 0xa4, 0x7c, 0xda, 0x38,
+    0x1e, 0x0b, 0x05, 0xd6, 0x14, 0x6e, 0x6c, 0x7e, 0x66, 0xfd, 0xb1, 0xe5, 0x60, 0xaf, 0x5e, 0x33,
+    0x87, 0xc9, 0xf0, 0x5d, 0x6d, 0x3f, 0x88, 0x8d, 0xc7, 0xf7, 0x1d, 0xe9, 0xec, 0xed, 0x80, 0x29,
+    0x27, 0xcf, 0x99, 0xa8, 0x50, 0x0f, 0x37, 0x24, 0x28, 0x30, 0x95, 0xd2, 0x3e, 0x5b, 0x40, 0x83,
+    0xb3, 0x69, 0x57, 0x1f, 0x07, 0x1c, 0x8a, 0xbc, 0x20, 0xeb, 0xce, 0x8e, 0xab, 0xee, 0x31, 0xa2,
+    0x73, 0xf9, 0xca, 0x3a, 0x1a, 0xfb, 0x0d, 0xc1, 0xfe, 0xfa, 0xf2, 0x6f, 0xbd, 0x96, 0xdd, 0x43,
+    0x52, 0xb6, 0x08, 0xf3, 0xae, 0xbe, 0x19, 0x89, 0x32, 0x26, 0xb0, 0xea, 0x4b, 0x64, 0x84, 0x82,
+    0x6b, 0xf5, 0x79, 0xbf, 0x01, 0x5f, 0x75, 0x63, 0x1b, 0x23, 0x3d, 0x68, 0x2a, 0x65, 0xe8, 0x91,
+    0xf6, 0xff, 0x13, 0x58, 0xf1, 0x47, 0x0a, 0x7f, 0xc5, 0xa7, 0xe7, 0x61, 0x5a, 0x06, 0x46, 0x44,
+    0x42, 0x04, 0xa0, 0xdb, 0x39, 0x86, 0x54, 0xaa, 0x8c, 0x34, 0x21, 0x8b, 0xf8, 0x0c, 0x74, 0x67,
+];
+
+/// Kalyna S-box π3 (DSTU 7624:2014).
+const SBOX_3: [u8; 256] = [
+    0x68, 0x8d, 0xca, 0x4d, 0x73, 0x4b, 0x4e, 0x2a, 0xd4, 0x52, 0x26, 0xb3, 0x54, 0x1e, 0x19, 0x1f,
+    0x22, 0x03, 0x46, 0x3d, 0x2d, 0x4a, 0x53, 0x83, 0x13, 0x8a, 0xb7, 0xd5, 0x25, 0x79, 0xf5, 0xbd,
+    0x58, 0x2f, 0x0d, 0x02, 0xed, 0x51, 0x9e, 0x11, 0xf2, 0x3e, 0x55, 0x5e, 0xd1, 0x16, 0x3c, 0x66,
+    0x70, 0x5d, 0xf3, 0x45, 0x40, 0xcc, 0xe8, 0x94, 0x56, 0x08, 0xce, 0x1a, 0x3a, 0xd2, 0xe1, 0xdf,
+    0xb5, 0x38, 0x6e, 0x0e, 0xe5, 0xf4, 0xf9, 0x86, 0xe9, 0x4f, 0xd6, 0x85, 0x23, 0xcf, 0x32, 0x99,
+    0x31, 0x14, 0xae, 0xee, 0xc8, 0x48, 0xd3, 0x30, 0xa1, 0x92, 0x41, 0xb1, 0x18, 0xc4, 0x2c, 0x71,
+    0x72, 0x44, 0x15, 0xfd, 0x37, 0xbe, 0x5f, 0xaa, 0x9b, 0x88, 0xd8, 0xab, 0x89, 0x9c, 0xfa, 0x60,
+    0xea, 0xbc, 0x62, 0x0c, 0x24, 0xa6, 0xa8, 0xec, 0x67, 0x20, 0xdb, 0x7c, 0x28, 0xdd, 0xac, 0x5b,
+    0x34, 0x7e, 0x10, 0xf1, 0x7b, 0x8f, 0x63, 0xa0, 0x05, 0x9a, 0x43, 0x77, 0x21, 0xbf, 0x27, 0x09,
+    0xc3, 0x9f, 0xb6, 0xd7, 0x29, 0xc2, 0xeb, 0xc0, 0xa4, 0x8b, 0x8c, 0x1d, 0xfb, 0xff, 0xc1, 0xb2,
+    0x97, 0x2e, 0xf8, 0x65, 0xf6, 0x75, 0x07, 0x04, 0x49, 0x33, 0xe4, 0xd9, 0xb9, 0xd0, 0x42, 0xc7,
+    0x6c, 0x90, 0x00, 0x8e, 0x6f, 0x50, 0x01, 0xc5, 0xda, 0x47, 0x3f, 0xcd, 0x69, 0xa2, 0xe2, 0x7a,
+    0xa7, 0xc6, 0x93, 0x0f, 0x0a, 0x06, 0xe6, 0x2b, 0x96, 0xa3, 0x1c, 0xaf, 0x6a, 0x12, 0x84, 0x39,
+    0xe7, 0xb0, 0x82, 0xf7, 0xfe, 0x9d, 0x87, 0x5c, 0x81, 0x35, 0xde, 0xb4, 0xa5, 0xfc, 0x80, 0xef,
+    0xcb, 0xbb, 0x6b, 0x76, 0xba, 0x5a, 0x7d, 0x78, 0x0b, 0x95, 0xe3, 0xad, 0x74, 0x98, 0x3b, 0x36,
+    0x64, 0x6d, 0xdc, 0xf0, 0x59, 0xa9, 0x4c, 0x17, 0x7f, 0x91, 0xb8, 0xc9, 0x57, 0x1b, 0xe0, 0x61,
+];
+
+/// S-boxes by row: row `r` uses `SBOXES[r % 4]`.
+const SBOXES: [[u8; 256]; 4] = [SBOX_0, SBOX_1, SBOX_2, SBOX_3];
+
+/// MDS vector: row `i` of the circulant matrix is this vector rotated right
+/// by `i` positions.
+const MDS_ROW: [u8; 8] = [0x01, 0x01, 0x05, 0x01, 0x08, 0x06, 0x07, 0x04];
+
+/// Multiplies two elements of GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1d;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Builds the round tables: `TABLES[r][x]` is the column produced by the
+/// MDS matrix from the S-box output of `x` in row `r`, packed
+/// little-endian.
+const fn build_tables() -> [[u64; 256]; 8] {
+    let mut tables = [[0u64; 256]; 8];
+    let mut r = 0;
+    while r < 8 {
+        let mut x = 0;
+        while x < 256 {
+            // Output row i takes coefficient MDS_ROW[(r - i) mod 8]
+            let s = SBOXES[r % 4][x];
+            let mut column = 0u64;
+            let mut i = 0;
+            while i < 8 {
+                column |= (gf_mul(s, MDS_ROW[(8 + r - i) % 8]) as u64) << (8 * i);
+                i += 1;
+            }
+            tables[r][x] = column;
+            x += 1;
+        }
+        r += 1;
+    }
+    tables
+}
+
+/// Round tables merging the S-boxes and the MDS matrix.
+static TABLES: [[u64; 256]; 8] = build_tables();
+
+/// Kupyna hasher state, generic over the number of state columns (8 or 16)
+/// and the digest size in bytes.
+///
+/// Use the [`Kupyna256`] and [`Kupyna512`] aliases. Cloning captures the
+/// full streaming state, so a clone can be finalized or extended
+/// independently of the original.
+#[derive(Clone)]
+pub struct Kupyna<const COLUMNS: usize, const OUT: usize> {
+    /// Chaining value as little-endian columns.
+    state: [u64; COLUMNS],
+    buffer: [u8; 128],     // Pending block; only the first COLUMNS * 8 bytes are used
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u128,       // Total bytes processed (for final length)
+}
+
+/// Kupyna-256: 512-bit state, 32-byte digest.
+pub type Kupyna256 = Kupyna<8, 32>;
+
+/// Kupyna-512: 1024-bit state, 64-byte digest.
+pub type Kupyna512 = Kupyna<16, 64>;
+
+impl<const COLUMNS: usize, const OUT: usize> Kupyna<COLUMNS, OUT> {
+    /// Block size in bytes, equal to the state size.
+    const BLOCK_SIZE: usize = COLUMNS * 8;
+
+    /// Rounds of each permutation.
+    const ROUNDS: usize = if COLUMNS == 8 { 10 } else { 14 };
+
+    /// Right rotation of each row in the row-shift step.
+    const SHIFTS: [usize; 8] = if COLUMNS == 8 {
+        [0, 1, 2, 3, 4, 5, 6, 7]
+    } else {
+        [0, 1, 2, 3, 4, 5, 6, 11]
+    };
+
+    /// Creates a new hasher; the initial value encodes the state size.
+    pub fn new() -> Self {
+        let mut state = [0u64; COLUMNS];
+        state[0] = if COLUMNS == 8 { 0x40 } else { 0x80 };
+        Kupyna {
+            state,
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        let block_size = Self::BLOCK_SIZE;
+        self.total_len += data.len() as u128;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(block_size - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == block_size {
+                let block = self.buffer;
+                self.compress(&block[..block_size]);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete blocks directly from input
+        while offset + block_size <= data.len() {
+            self.compress(&data[offset..offset + block_size]);
+            offset += block_size;
+        }
+
+        // Buffer any remaining bytes
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; OUT] {
+        let block_size = Self::BLOCK_SIZE;
+        let mut hasher = self.clone();
+        let mut block = [0u8; 128];
+
+        // 0x80 byte, zeros, then the bit length on the last 12 bytes
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len >= block_size - 12 {
+            hasher.compress(&block[..block_size]);
+            block = [0u8; 128];
+        }
+        let bit_len = self.total_len.wrapping_mul(8).to_le_bytes();
+        block[block_size - 12..block_size].copy_from_slice(&bit_len[..12]);
+        hasher.compress(&block[..block_size]);
+
+        // Output transform: the last OUT bytes of T⊕(h) ^ h
+        let mut output = hasher.state;
+        Self::permute(&mut output, false);
+        let bytes: Vec<u8> = output
+            .iter()
+            .zip(hasher.state)
+            .flat_map(|(t, h)| (t ^ h).to_le_bytes())
+            .collect();
+        bytes[block_size - OUT..].try_into().unwrap()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; OUT] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Applies the permutation T⊕, or T+ when `additive` is set, to `state`.
+    fn permute(state: &mut [u64; COLUMNS], additive: bool) {
+        for round in 0..Self::ROUNDS {
+            // Round constants: T⊕ XORs them into row 0, T+ adds them to
+            // each whole column
+            for (j, column) in state.iter_mut().enumerate() {
+                if additive {
+                    let constant = ((((COLUMNS - 1 - j) << 4) ^ round) as u64) << 56;
+                    *column = column.wrapping_add(0x00f0_f0f0_f0f0_f0f3 ^ constant);
+                } else {
+                    *column ^= ((j << 4) ^ round) as u64;
+                }
+            }
+
+            // S-boxes, row shifts and MDS matrix through the tables
+            let input = *state;
+            for (j, column) in state.iter_mut().enumerate() {
+                *column = 0;
+                for (r, table) in TABLES.iter().enumerate() {
+                    let source = (j + COLUMNS - Self::SHIFTS[r]) % COLUMNS;
+                    let byte = (input[source] >> (8 * r)) as u8;
+                    *column ^= table[byte as usize];
+                }
+            }
+        }
+    }
+
+    /// Processes a single block: `h = T⊕(h ^ m) ^ T+(m) ^ h`.
+    fn compress(&mut self, block: &[u8]) {
+        let mut message = [0u64; COLUMNS];
+        for (column, chunk) in message.iter_mut().zip(block.chunks_exact(8)) {
+            *column = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut xored = [0u64; COLUMNS];
+        for j in 0..COLUMNS {
+            xored[j] = self.state[j] ^ message[j];
+        }
+        Self::permute(&mut xored, false);
+        Self::permute(&mut message, true);
+        for j in 0..COLUMNS {
+            self.state[j] ^= xored[j] ^ message[j];
+        }
+    }
+
+    /// Returns the output size in bytes (32 or 64).
+    pub fn digest_size() -> usize {
+        OUT
+    }
+
+    /// Returns the block size in bytes (64 or 128).
+    pub fn block_size() -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+impl<const COLUMNS: usize, const OUT: usize> Default for Kupyna<COLUMNS, OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const COLUMNS: usize, const OUT: usize> Digest for Kupyna<COLUMNS, OUT> {
+    type Output = [u8; OUT];
+
+    /// Never fails: the 96-bit length field covers any real message.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; OUT] {
+        Kupyna::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        OUT
+    }
+
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kupyna256_hex(data: &[u8]) -> String {
+        let mut hasher = Kupyna256::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    fn kupyna512_hex(data: &[u8]) -> String {
+        let mut hasher = Kupyna512::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    /// Bytes 0x00, 0x01, ... as in the DSTU 7564:2014 examples.
+    fn counting(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn test_kupyna256_vectors() {
+        // DSTU 7564:2014, appendix A: 512-, 1024- and 2048-bit messages
+        assert_eq!(
+            kupyna256_hex(&counting(64)),
+            "08f4ee6f1be6903b324c4e27990cb24ef69dd58dbe84813ee0a52f6631239875"
+        );
+        assert_eq!(
+            kupyna256_hex(&counting(128)),
+            "0a9474e645a7d25e255e9e89fff42ec7eb31349007059284f0b182e452bda882"
+        );
+        assert_eq!(
+            kupyna256_hex(&counting(256)),
+            "d305a32b963d149dc765f68594505d4077024f836c1bf03806e1624ce176c08f"
+        );
+        assert_eq!(
+            kupyna256_hex(b""),
+            "cd5101d1ccdf0d1d1f4ada56e888cd724ca1a0838a3521e7131d4fb78d0f5eb6"
+        );
+    }
+
+    #[test]
+    fn test_kupyna512_vectors() {
+        assert_eq!(
+            kupyna512_hex(&counting(64)),
+            "3813e2109118cdfb5a6d5e72f7208dccc80a2dfb3afdfb02f46992b5edbe536b\
+             3560dd1d7e29c6f53978af58b444e37ba685c0dd910533ba5d78efffc13de62a"
+        );
+        assert_eq!(
+            kupyna512_hex(&counting(128)),
+            "76ed1ac28b1d0143013ffa87213b4090b356441263c13e03fa060a8cada32b97\
+             9635657f256b15d5fca4a174de029f0b1b4387c878fcc1c00e8705d783fd7ffe"
+        );
+        assert_eq!(
+            kupyna512_hex(b""),
+            "656b2f4cd71462388b64a37043ea55dbe445d452aecd46c3298343314ef04019\
+             bcfa3f04265a9857f91be91fce197096187ceda78c9c1c021c294a0689198538"
+        );
+    }
+
+    #[test]
+    fn test_kupyna_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 51, 52, 53, 63, 64, 65, 115, 116, 128, 200] {
+            let mut k256 = Kupyna256::new();
+            let mut k512 = Kupyna512::new();
+            for chunk in data.chunks(step) {
+                k256.update(chunk);
+                k512.update(chunk);
+            }
+            assert_eq!(k256.hexdigest(), kupyna256_hex(&data), "step {}", step);
+            assert_eq!(k512.hexdigest(), kupyna512_hex(&data), "step {}", step);
+        }
+        let mut hasher = Kupyna512::new();
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Kupyna512::new().digest());
+    }
+}
@@ -0,0 +1,396 @@
+//! LSH hash function implementation.
+//!
+//! Pure Rust implementation of LSH, the Korean hash standard (KS X 3262,
+//! designed by NSR and published by KISA). Both members are wide-pipe: the
+//! chaining value holds 16 words, twice the largest digest. Each block is
+//! expanded into one 16-word message per step, and every step adds its
+//! message to the state, mixes the left and right halves with additions,
+//! rotations and a step constant, then permutes the words. The digest is
+//! the XOR of the two halves, truncated to the requested length.
+//!
+//! # Algorithm Details
+//!
+//! - **Word size**: 32 bits for [`Lsh256`], 64 bits for [`Lsh512`]
+//! - **Block size**: 32 words (128 or 256 bytes)
+//! - **Steps**: 26 (LSH-256) or 28 (LSH-512)
+//! - **Digest size**: 1 to 32 bytes (LSH-256) or 1 to 64 bytes (LSH-512);
+//!   the standard sizes are LSH-256-224/256 and LSH-512-224/256/384/512
+//! - **Byte order**: little-endian words
+//! - **Padding**: 0x80, then zeros up to the block size (no length field)
+//!
+//! The initial value depends on the digest size: it is the compression of
+//! an all-zero block into a chaining value holding the word size and the
+//! digest length in bits, so truncated digests are not prefixes of longer
+//! ones. Only byte-aligned digest sizes are supported.
+
+use super::{Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// Message expansion permutation: word `l` of a new message adds word
+/// `TAU[l]` of the message two steps back.
+const TAU: [usize; 16] = [3, 2, 0, 1, 7, 4, 5, 6, 11, 10, 8, 9, 15, 12, 13, 14];
+
+/// Word permutation closing each step: word `l` takes the value of word
+/// `SIGMA[l]`.
+const SIGMA: [usize; 16] = [6, 4, 5, 7, 12, 15, 14, 13, 2, 0, 1, 3, 8, 11, 10, 9];
+
+/// Step constants of the first LSH-256 step.
+const FIRST_CONSTANTS_256: [u64; 8] = [
+    0x917caf90, 0x6c1b10a2, 0x6f352943, 0xcf778243,
+    0x2ceb7472, 0x29e96ff2, 0x8a9ba428, 0x2eeb2642,
+];
+
+/// Step constants of the first LSH-512 step.
+const FIRST_CONSTANTS_512: [u64; 8] = [
+    0x97884283c938982a, 0xba1fca93533e2355, 0xc519a2e87aeb1c03, 0x9a0fc95462af17b1,
+    0xfc3dda8ab019a82b, 0x02825d079a895407, 0x79f2d0a7ee06a6f7, 0xd76d15eed9fdf5fe,
+];
+
+/// Derives the constants of every step: each constant is the previous one
+/// plus itself rotated left by 8 bits, modulo 2^`bits`.
+const fn step_constants<const STEPS: usize>(first: [u64; 8], bits: u32) -> [[u64; 8]; STEPS] {
+    let mask = u64::MAX >> (64 - bits);
+    let mut constants = [[0u64; 8]; STEPS];
+    constants[0] = first;
+    let mut j = 1;
+    while j < STEPS {
+        let mut l = 0;
+        while l < 8 {
+            let x = constants[j - 1][l];
+            let rotated = ((x << 8) | (x >> (bits - 8))) & mask;
+            constants[j][l] = x.wrapping_add(rotated) & mask;
+            l += 1;
+        }
+        j += 1;
+    }
+    constants
+}
+
+/// Step constants of LSH-256, one row of eight per step.
+static STEP_CONSTANTS_256: [[u64; 8]; 26] = step_constants(FIRST_CONSTANTS_256, 32);
+
+/// Step constants of LSH-512, one row of eight per step.
+static STEP_CONSTANTS_512: [[u64; 8]; 28] = step_constants(FIRST_CONSTANTS_512, 64);
+
+/// LSH hasher state, generic over the word size in bytes (4 or 8).
+///
+/// Use the [`Lsh256`] and [`Lsh512`] aliases. Words are kept in `u64`
+/// whatever the word size; LSH-256 arithmetic is reduced modulo 2^32.
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Lsh<const WORD_BYTES: usize> {
+    chain: [u64; 16],
+    buffer: [u8; 256],     // Pending block; only the first WORD_BYTES * 32 bytes are used
+    buffer_len: usize,     // Number of bytes currently in buffer
+    output_size: usize,    // Digest size in bytes
+}
+
+/// LSH-256: 32-bit words, 32-byte digest by default.
+pub type Lsh256 = Lsh<4>;
+
+/// LSH-512: 64-bit words, 64-byte digest by default.
+pub type Lsh512 = Lsh<8>;
+
+impl<const WORD_BYTES: usize> Lsh<WORD_BYTES> {
+    /// Block size in bytes (32 words).
+    const BLOCK_SIZE: usize = WORD_BYTES * 32;
+
+    /// Largest digest size in bytes (half the chaining value).
+    const MAX_OUTPUT_SIZE: usize = WORD_BYTES * 8;
+
+    /// Word size in bits.
+    const BITS: u32 = WORD_BYTES as u32 * 8;
+
+    /// Mask reducing a `u64` to a word.
+    const MASK: u64 = u64::MAX >> (64 - Self::BITS);
+
+    /// Creates a new hasher with the full digest size (LSH-256-256 or
+    /// LSH-512-512).
+    pub fn new() -> Self {
+        Self::with_output_size(Self::MAX_OUTPUT_SIZE).unwrap()
+    }
+
+    /// Creates a new hasher producing `output_size` bytes, e.g. 28 for
+    /// LSH-256-224 or 48 for LSH-512-384.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParameterError`] if `output_size` is 0 or larger than
+    /// 32 (LSH-256) or 64 (LSH-512).
+    pub fn with_output_size(output_size: usize) -> Result<Self, ParameterError> {
+        if output_size == 0 || output_size > Self::MAX_OUTPUT_SIZE {
+            return Err(ParameterError {
+                algorithm: Self::name(),
+                message: format!("digest_size must be between 1 and {}", Self::MAX_OUTPUT_SIZE),
+            });
+        }
+
+        // The initial value compresses an all-zero block into the
+        // parameters: word size in bits, then digest size in bits
+        let mut chain = [0u64; 16];
+        chain[0] = Self::BITS as u64;
+        chain[1] = output_size as u64 * 8;
+        Self::compress(&mut chain, &[0u8; 256][..Self::BLOCK_SIZE]);
+
+        Ok(Lsh {
+            chain,
+            buffer: [0u8; 256],
+            buffer_len: 0,
+            output_size,
+        })
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        let block_size = Self::BLOCK_SIZE;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(block_size - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == block_size {
+                let block = self.buffer;
+                Self::compress(&mut self.chain, &block[..block_size]);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete blocks directly from input
+        while offset + block_size <= data.len() {
+            Self::compress(&mut self.chain, &data[offset..offset + block_size]);
+            offset += block_size;
+        }
+
+        // Buffer any remaining bytes
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and chaining
+    /// value, so the hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut chain = self.chain;
+        let mut block = [0u8; 256];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        Self::compress(&mut chain, &block[..Self::BLOCK_SIZE]);
+
+        (0..8)
+            .flat_map(|l| (chain[l] ^ chain[l + 8]).to_le_bytes().into_iter().take(WORD_BYTES))
+            .take(self.output_size)
+            .collect()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so
+    /// far. The digest size is kept.
+    pub fn reset(&mut self) {
+        *self = Self::with_output_size(self.output_size).unwrap();
+    }
+
+    /// Processes a single block through the LSH compression function.
+    fn compress(chain: &mut [u64; 16], block: &[u8]) {
+        let (steps, constants, alpha, beta, gamma): (usize, &[[u64; 8]], _, _, [u32; 8]) =
+            match WORD_BYTES {
+                4 => (26, &STEP_CONSTANTS_256, [29, 5], [1, 17], [0, 8, 16, 24, 24, 16, 8, 0]),
+                8 => (28, &STEP_CONSTANTS_512, [23, 7], [59, 3], [0, 16, 32, 48, 8, 24, 40, 56]),
+                _ => unreachable!("LSH has 32-bit or 64-bit words"),
+            };
+        let add = |x: u64, y: u64| x.wrapping_add(y) & Self::MASK;
+        let rotate = |x: u64, r: u32| {
+            if r == 0 { x } else { ((x << r) | (x >> (Self::BITS - r))) & Self::MASK }
+        };
+
+        let mut words = [0u64; 32];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(WORD_BYTES)) {
+            let mut le = [0u8; 8];
+            le[..WORD_BYTES].copy_from_slice(bytes);
+            *word = u64::from_le_bytes(le);
+        }
+        // messages[j % 2] holds the message of step j
+        let mut messages: [[u64; 16]; 2] =
+            [words[..16].try_into().unwrap(), words[16..].try_into().unwrap()];
+
+        let mut state = *chain;
+        for j in 0..=steps {
+            if j >= 2 {
+                let (older, newer) = (messages[j % 2], messages[(j + 1) % 2]);
+                messages[j % 2] = std::array::from_fn(|l| add(newer[l], older[TAU[l]]));
+            }
+            for (word, message) in state.iter_mut().zip(messages[j % 2]) {
+                *word ^= message;
+            }
+            // The last message is added after the final step
+            if j == steps {
+                break;
+            }
+
+            // Mix the left half with the right half, word by word
+            for l in 0..8 {
+                let (mut x, mut y) = (state[l], state[l + 8]);
+                x = rotate(add(x, y), alpha[j % 2]) ^ constants[j][l];
+                y = rotate(add(x, y), beta[j % 2]);
+                x = add(x, y);
+                y = rotate(y, gamma[l]);
+                state[l] = x;
+                state[l + 8] = y;
+            }
+
+            let mixed = state;
+            for (word, &from) in state.iter_mut().zip(&SIGMA) {
+                *word = mixed[from];
+            }
+        }
+        *chain = state;
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size(&self) -> usize {
+        self.output_size
+    }
+
+    /// Returns the block size in bytes (128 for LSH-256, 256 for LSH-512).
+    pub fn block_size() -> usize {
+        Self::BLOCK_SIZE
+    }
+
+    /// Returns the algorithm name used in error messages.
+    fn name() -> &'static str {
+        match WORD_BYTES {
+            4 => "LSH-256",
+            _ => "LSH-512",
+        }
+    }
+}
+
+impl<const WORD_BYTES: usize> Default for Lsh<WORD_BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORD_BYTES: usize> Digest for Lsh<WORD_BYTES> {
+    type Output = Vec<u8>;
+
+    /// Never fails: LSH encodes no message length.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Lsh::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Lsh::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lsh_hex<const WORD_BYTES: usize>(output_size: usize, data: &[u8]) -> String {
+        let mut hasher = Lsh::<WORD_BYTES>::with_output_size(output_size).unwrap();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_lsh_initial_value() {
+        // LSH-256-256 IV, as tabulated in the specification
+        let hasher = Lsh256::new();
+        assert_eq!(&hasher.chain[..4], &[0x46a10f1f, 0xfddce486, 0xb41443a8, 0x198e6b9d]);
+    }
+
+    #[test]
+    fn test_lsh_known_answers() {
+        // KISA test vectors for "abc"
+        assert_eq!(
+            lsh_hex::<4>(28, b"abc"),
+            "f7c53ba4034e708e74fba42e55997ca5126bb7623688f85342f73732"
+        );
+        assert_eq!(
+            lsh_hex::<4>(32, b"abc"),
+            "5fbf365daea5446a7053c52b57404d77a07a5f48a1f7c1963a0898ba1b714741"
+        );
+        assert_eq!(
+            lsh_hex::<8>(28, b"abc"),
+            "d1683234513ec5698394571ead128a8cd5373e97661ba20dcf89e489"
+        );
+        assert_eq!(
+            lsh_hex::<8>(32, b"abc"),
+            "cd892310532602332b613f1ec11a6962fca61ea09ecffcd4bcf75858d802edec"
+        );
+        assert_eq!(
+            lsh_hex::<8>(48, b"abc"),
+            "5f344efaa0e43ccd2e5e194d6039794b4fb431f10fb4b65fd45e9da4ecde0f27\
+             b66e8dbdfa47252e0d0b741bfd91f9fe"
+        );
+        assert_eq!(
+            lsh_hex::<8>(64, b"abc"),
+            "a3d93cfe60dc1aacdd3bd4bef0a6985381a396c7d49d9fd177795697c3535208\
+             b5c57224bef21084d42083e95a4bd8eb33e869812b65031c428819a1e7ce596d"
+        );
+    }
+
+    #[test]
+    fn test_lsh_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 127, 128, 129, 255, 256, 257] {
+            let mut lsh256 = Lsh256::new();
+            let mut lsh512 = Lsh512::new();
+            for chunk in data.chunks(step) {
+                lsh256.update(chunk);
+                lsh512.update(chunk);
+            }
+            assert_eq!(lsh256.hexdigest(), lsh_hex::<4>(32, &data), "step {}", step);
+            assert_eq!(lsh512.hexdigest(), lsh_hex::<8>(64, &data), "step {}", step);
+        }
+        let mut hasher = Lsh512::with_output_size(48).unwrap();
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Lsh512::with_output_size(48).unwrap().digest());
+    }
+
+    #[test]
+    fn test_lsh_output_size_bounds() {
+        assert!(Lsh256::with_output_size(0).is_err());
+        assert!(Lsh256::with_output_size(33).is_err());
+        let err = Lsh512::with_output_size(65).err().unwrap();
+        assert_eq!(err.algorithm, "LSH-512");
+        // Truncated digests are not prefixes: the IV depends on the size
+        assert!(!lsh_hex::<8>(64, b"abc").starts_with(&lsh_hex::<8>(32, b"abc")));
+    }
+}
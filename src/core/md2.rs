@@ -0,0 +1,251 @@
+//! MD2 message digest implementation.
+//!
+//! Pure Rust implementation following RFC 1319, with the published erratum
+//! on the checksum (each checksum byte is XORed with the S-box output, not
+//! replaced by it). MD2 works on bytes: a 16-byte checksum of the message
+//! is appended to it after padding, and each 16-byte block is mixed into a
+//! 48-byte state by 18 passes of an S-box built from the digits of π.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 128 bits (16 bytes)
+//! - **Digest size**: 128 bits (16 bytes)
+//! - **Rounds**: 18 passes over a 48-byte state per block
+//! - **Padding**: `n` bytes of value `n` (1 to 16), then the checksum block
+//!
+//! # Security
+//!
+//! MD2 is broken (preimage and collision attacks) and very slow. It is
+//! provided only to recompute digests found in old certificates and PKCS#7
+//! structures.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Permutation of 0..=255 derived from the digits of π (RFC 1319, section 3.2).
+const PI_SUBST: [u8; 256] = [
+    0x29, 0x2e, 0x43, 0xc9, 0xa2, 0xd8, 0x7c, 0x01, 0x3d, 0x36, 0x54, 0xa1, 0xec, 0xf0, 0x06, 0x13,
+    0x62, 0xa7, 0x05, 0xf3, 0xc0, 0xc7, 0x73, 0x8c, 0x98, 0x93, 0x2b, 0xd9, 0xbc, 0x4c, 0x82, 0xca,
+    0x1e, 0x9b, 0x57, 0x3c, 0xfd, 0xd4, 0xe0, 0x16, 0x67, 0x42, 0x6f, 0x18, 0x8a, 0x17, 0xe5, 0x12,
+    0xbe, 0x4e, 0xc4, 0xd6, 0xda, 0x9e, 0xde, 0x49, 0xa0, 0xfb, 0xf5, 0x8e, 0xbb, 0x2f, 0xee, 0x7a,
+    0xa9, 0x68, 0x79, 0x91, 0x15, 0xb2, 0x07, 0x3f, 0x94, 0xc2, 0x10, 0x89, 0x0b, 0x22, 0x5f, 0x21,
+    0x80, 0x7f, 0x5d, 0x9a, 0x5a, 0x90, 0x32, 0x27, 0x35, 0x3e, 0xcc, 0xe7, 0xbf, 0xf7, 0x97, 0x03,
+    0xff, 0x19, 0x30, 0xb3, 0x48, 0xa5, 0xb5, 0xd1, 0xd7, 0x5e, 0x92, 0x2a, 0xac, 0x56, 0xaa, 0xc6,
+    0x4f, 0xb8, 0x38, 0xd2, 0x96, 0xa4, 0x7d, 0xb6, 0x76, 0xfc, 0x6b, 0xe2, 0x9c, 0x74, 0x04, 0xf1,
+    0x45, 0x9d, 0x70, 0x59, 0x64, 0x71, 0x87, 0x20, 0x86, 0x5b, 0xcf, 0x65, 0xe6, 0x2d, 0xa8, 0x02,
+    0x1b, 0x60, 0x25, 0xad, 0xae, 0xb0, 0xb9, 0xf6, 0x1c, 0x46, 0x61, 0x69, 0x34, 0x40, 0x7e, 0x0f,
+    0x55, 0x47, 0xa3, 0x23, 0xdd, 0x51, 0xaf, 0x3a, 0xc3, 0x5c, 0xf9, 0xce, 0xba, 0xc5, 0xea, 0x26,
+    0x2c, 0x53, 0x0d, 0x6e, 0x85, 0x28, 0x84, 0x09, 0xd3, 0xdf, 0xcd, 0xf4, 0x41, 0x81, 0x4d, 0x52,
+    0x6a, 0xdc, 0x37, 0xc8, 0x6c, 0xc1, 0xab, 0xfa, 0x24, 0xe1, 0x7b, 0x08, 0x0c, 0xbd, 0xb1, 0x4a,
+    0x78, 0x88, 0x95, 0x8b, 0xe3, 0x63, 0xe8, 0x6d, 0xe9, 0xcb, 0xd5, 0xfe, 0x3b, 0x00, 0x1d, 0x39,
+    0xf2, 0xef, 0xb7, 0x0e, 0x66, 0x58, 0xd0, 0xe4, 0xa6, 0x77, 0x72, 0xf8, 0xeb, 0x75, 0x4b, 0x0a,
+    0x31, 0x44, 0x50, 0xb4, 0x8f, 0xed, 0x1f, 0x1a, 0xdb, 0x99, 0x8d, 0x33, 0x9f, 0x11, 0x83, 0x14,
+];
+
+/// MD2 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Md2 {
+    state: [u8; 48],
+    checksum: [u8; 16],
+    buffer: [u8; 16],      // Fixed 16-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+}
+
+impl Md2 {
+    /// Creates a new MD2 hasher with an all-zero state and checksum.
+    pub fn new() -> Self {
+        Md2 {
+            state: [0u8; 48],
+            checksum: [0u8; 16],
+            buffer: [0u8; 16],
+            buffer_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 16-byte blocks immediately; only incomplete
+    /// blocks are buffered. MD2 has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(16 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 16 {
+                Self::process(&mut self.state, &mut self.checksum, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 16-byte blocks directly from input
+        while offset + 16 <= data.len() {
+            let block: [u8; 16] = data[offset..offset + 16].try_into().unwrap();
+            Self::process(&mut self.state, &mut self.checksum, &block);
+            offset += 16;
+        }
+
+        // Buffer any remaining bytes (< 16)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block, state and
+    /// checksum, so the hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 16] {
+        let mut state = self.state;
+        let mut checksum = self.checksum;
+
+        // Always pad, with 16 bytes of 16 when the message fills its blocks
+        let pad = (16 - self.buffer_len) as u8;
+        let mut block = [pad; 16];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        Self::process(&mut state, &mut checksum, &block);
+
+        // The checksum block only updates the state
+        Self::mix(&mut state, &checksum);
+
+        state[..16].try_into().unwrap()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 16] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a message block: updates the checksum, then mixes the
+    /// block into the state.
+    fn process(state: &mut [u8; 48], checksum: &mut [u8; 16], block: &[u8; 16]) {
+        let mut last = checksum[15];
+        for (sum, &byte) in checksum.iter_mut().zip(block) {
+            *sum ^= PI_SUBST[(byte ^ last) as usize];
+            last = *sum;
+        }
+        Self::mix(state, block);
+    }
+
+    /// Mixes a 16-byte block into the 48-byte state with 18 S-box passes.
+    fn mix(state: &mut [u8; 48], block: &[u8; 16]) {
+        for j in 0..16 {
+            state[16 + j] = block[j];
+            state[32 + j] = state[j] ^ block[j];
+        }
+        let mut t = 0u8;
+        for pass in 0..18u8 {
+            for byte in state.iter_mut() {
+                *byte ^= PI_SUBST[t as usize];
+                t = *byte;
+            }
+            t = t.wrapping_add(pass);
+        }
+    }
+
+    /// Returns the output size in bytes (16 for MD2).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (16 for MD2).
+    pub fn block_size() -> usize {
+        16
+    }
+}
+
+impl Default for Md2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md2 {
+    type Output = [u8; 16];
+
+    /// Never fails: MD2 has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Md2::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Md2::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Md2::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md2_hex(data: &[u8]) -> String {
+        let mut hasher = Md2::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_md2_rfc1319_suite() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "8350e5a3e24c153df2275c9f80692773"),
+            (b"a", "32ec01ec4a6dac72c0ab96fb34c0b5d1"),
+            (b"abc", "da853b0d3f88d99b30283a69e6ded6bb"),
+            (b"message digest", "ab4f496bfb2a530b219ff33031fe06b0"),
+            (b"abcdefghijklmnopqrstuvwxyz", "4e8ddff3650292ab5a4108c3aa47940b"),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "da33def2a42df13975352846c30338cd",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "d5976f79d83d3a0dc9806c3c66f3efd8",
+            ),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(md2_hex(data), expected);
+        }
+    }
+
+    #[test]
+    fn test_md2_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 15, 16, 17, 100] {
+            let mut hasher = Md2::new();
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.hexdigest(), md2_hex(&data), "step {}", step);
+        }
+    }
+}
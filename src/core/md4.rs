@@ -0,0 +1,265 @@
+//! MD4 message digest implementation.
+//!
+//! Pure Rust implementation following RFC 1320.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 128 bits (16 bytes)
+//! - **Rounds**: 48, in three rounds of 16 operations
+//! - **Byte order**: little-endian words and length
+//!
+//! # Security
+//!
+//! MD4 is completely broken: collisions can be found by hand. It is
+//! provided for legacy formats that still depend on it, such as NTLM
+//! password hashes, rsync block checksums and ed2k links.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// MD4 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,      // Number of bytes currently in buffer
+    total_len: u64,         // Total bytes processed, modulo 2^64
+}
+
+impl Md4 {
+    /// MD4 initial state (RFC 1320, section 3.3).
+    const IV: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+    /// Message word order of rounds 2 and 3 (round 1 takes the words in order).
+    const ORDER: [[usize; 16]; 2] = [
+        [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+        [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+    ];
+
+    /// Left rotation amounts of each round, cycling every four operations.
+    const R: [[u32; 4]; 3] = [[3, 7, 11, 19], [3, 5, 9, 13], [3, 9, 11, 15]];
+
+    /// Additive constants of each round: 0, `sqrt(2)` and `sqrt(3)` as 2.30
+    /// fixed-point numbers.
+    const K: [u32; 3] = [0x00000000, 0x5a827999, 0x6ed9eba1];
+
+    /// Creates a new MD4 hasher with initial state.
+    pub fn new() -> Self {
+        Md4 {
+            state: Self::IV,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        // MD4 encodes the length modulo 2^64, so there is no length limit
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 16] {
+        let bit_len = self.total_len.wrapping_mul(8);
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Copy pending bytes and add padding: 0x80 byte followed by zeros
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+
+        // If not enough space for length (need 8 bytes), process an extra block
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+
+        block[56..64].copy_from_slice(&bit_len.to_le_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 16];
+        for (i, &word) in state.iter().enumerate() {
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 16] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a single 512-bit block through the MD4 compression function.
+    fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+        let mut m = [0u32; 16];
+        for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let [mut a, mut b, mut c, mut d] = *state;
+        for i in 0..48 {
+            let round = i / 16;
+            let (f, g) = match round {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((b & c) | (b & d) | (c & d), Self::ORDER[0][i % 16]),
+                _ => (b ^ c ^ d, Self::ORDER[1][i % 16]),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(m[g])
+                .wrapping_add(Self::K[round])
+                .rotate_left(Self::R[round][i % 4]);
+            a = d;
+            d = c;
+            c = b;
+            b = rotated;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    /// Returns the output size in bytes (16 for MD4).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (64 for MD4).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md4 {
+    type Output = [u8; 16];
+
+    /// Never fails: MD4 has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Md4::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Md4::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Md4::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md4_hex(data: &[u8]) -> String {
+        let mut hasher = Md4::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_md4_rfc1320_suite() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+            (b"abcdefghijklmnopqrstuvwxyz", "d79e1c308aa5bbcdeea8ed63df412da9"),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(md4_hex(data), expected);
+        }
+    }
+
+    #[test]
+    fn test_md4_ntlm_hash() {
+        // The NT hash is MD4 over the UTF-16LE encoded password
+        let password: Vec<u8> = "password".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        assert_eq!(md4_hex(&password), "8846f7eaee8fb117ad06bdd830b7586c");
+    }
+
+    #[test]
+    fn test_md4_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Md4::new();
+        for chunk in data.chunks(37) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.hexdigest(), md4_hex(&data));
+    }
+}
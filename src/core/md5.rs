@@ -0,0 +1,265 @@
+//! MD5 message digest implementation.
+//!
+//! Pure Rust implementation following RFC 1321.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 128 bits (16 bytes)
+//! - **Rounds**: 64, in four rounds of 16 operations
+//! - **Byte order**: little-endian words and length
+//!
+//! # Security
+//!
+//! MD5 is completely broken for collision resistance and must not be used
+//! for security. It is provided for checksums and legacy formats such as
+//! S3 `Content-MD5` headers.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// MD5 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,      // Number of bytes currently in buffer
+    total_len: u64,         // Total bytes processed, modulo 2^64
+}
+
+impl Md5 {
+    /// MD5 initial state (RFC 1321, section 3.3).
+    const IV: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+    /// Per-operation additive constants, `floor(abs(sin(i + 1)) * 2^32)`.
+    const K: [u32; 64] = [
+        0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+        0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+        0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+        0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+        0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+        0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+        0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+        0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+    ];
+
+    /// Per-operation left rotation amounts.
+    const R: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+        5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+        4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+        6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+
+    /// Creates a new MD5 hasher with initial state.
+    pub fn new() -> Self {
+        Md5 {
+            state: Self::IV,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        // MD5 encodes the length modulo 2^64, so there is no length limit
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 16] {
+        let bit_len = self.total_len.wrapping_mul(8);
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Copy pending bytes and add padding: 0x80 byte followed by zeros
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+
+        // If not enough space for length (need 8 bytes), process an extra block
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+
+        block[56..64].copy_from_slice(&bit_len.to_le_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 16];
+        for (i, &word) in state.iter().enumerate() {
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 16] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a single 512-bit block through the MD5 compression function.
+    fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+        let mut m = [0u32; 16];
+        for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let [mut a, mut b, mut c, mut d] = *state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(Self::K[i])
+                .wrapping_add(m[g])
+                .rotate_left(Self::R[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    /// Returns the output size in bytes (16 for MD5).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (64 for MD5).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md5 {
+    type Output = [u8; 16];
+
+    /// Never fails: MD5 has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Md5::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Md5::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Md5::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md5_hex(data: &[u8]) -> String {
+        let mut hasher = Md5::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_md5_rfc1321_suite() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "d41d8cd98f00b204e9800998ecf8427e"),
+            (b"a", "0cc175b9c0f1b6a831c399e269772661"),
+            (b"abc", "900150983cd24fb0d6963f7d28e17f72"),
+            (b"message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (b"abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(md5_hex(data), expected);
+        }
+    }
+
+    #[test]
+    fn test_md5_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Md5::new();
+        for chunk in data.chunks(37) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.hexdigest(), md5_hex(&data));
+    }
+}
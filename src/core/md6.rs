@@ -0,0 +1,513 @@
+//! MD6 cryptographic hash function.
+//!
+//! Pure Rust implementation following the MD6 submission to the SHA-3
+//! competition (Rivest et al., 2008) and its reference code. MD6 hashes the
+//! message with a 4-ary Merkle tree of compression nodes: each 512-byte
+//! block of a level is compressed to 128 bytes, which form the next level,
+//! until a single node, the root, remains. The mode parameter `L` bounds the
+//! height of the tree; above it, the remaining data is hashed by a
+//! sequential chain of nodes. Nodes of a level are independent of each
+//! other, so large updates compress them on the rayon thread pool.
+//!
+//! # Algorithm Details
+//!
+//! - **Compression**: 89-word input (constant `Q`, key, node id, control
+//!   word and 64 data words), `r` rounds of 16 steps, 16-word output
+//! - **Rounds**: `40 + d / 4` for a `d`-bit digest (104 for MD6-256)
+//! - **Digest size**: any number of bits from 1 to 512, the last `d` bits of
+//!   the root's output
+//! - **Mode**: `L = 64` by default (a pure tree for any practical input);
+//!   `L = 0` is fully sequential, with 384 data bytes per node
+//! - **Byte order**: big-endian words
+
+use rayon::prelude::*;
+
+use super::{Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// Fractional part of the square root of 6, the first 15 input words of
+/// every compression.
+const Q: [u64; 15] = [
+    0x7311c2812425cfa0,
+    0x6432286434aac8e7,
+    0xb60450e9ef68b7c1,
+    0xe8fb23908d9f06f1,
+    0xdd2e76cba691e5bf,
+    0x0cd0d63b2c30bc41,
+    0x1f8ccf6823058f8a,
+    0x54e5ed5b88e3775d,
+    0x4ad12aae0a6d6031,
+    0x3e7f16bb88222e0d,
+    0x8af8671d3fb50c2c,
+    0x995ad1178bd25c31,
+    0xc878c1dd04c4b633,
+    0x3b72066c7a1552ac,
+    0x0d6f3522631effcb,
+];
+
+/// Number of input words of the compression function.
+const INPUT_WORDS: usize = 89;
+
+/// Tap positions `t0..t4` of the feedback function; the fifth input is the
+/// word `INPUT_WORDS` steps back.
+const TAPS: [usize; 5] = [17, 18, 21, 31, 67];
+
+/// Right shift applied in each step of a round.
+const RIGHT_SHIFTS: [u32; 16] = [10, 5, 13, 10, 11, 12, 2, 7, 14, 15, 7, 13, 11, 7, 6, 12];
+
+/// Left shift applied in each step of a round.
+const LEFT_SHIFTS: [u32; 16] = [11, 24, 9, 16, 15, 9, 27, 15, 6, 2, 29, 8, 15, 5, 31, 9];
+
+/// Initial round constant.
+const S0: u64 = 0x0123456789abcdef;
+
+/// Mask of the round constant update `S' = rotl(S, 1) ^ (S & S_STAR)`.
+const S_STAR: u64 = 0x7311c2812425cfa0;
+
+/// Size of a node's data block in bytes (64 words).
+const BLOCK_LEN: usize = 512;
+
+/// Size of a node's output in bytes (16 words).
+const CHAIN_LEN: usize = 128;
+
+/// Largest digest size in bits.
+const MAX_OUTPUT_BITS: usize = 512;
+
+/// Largest mode parameter `L`.
+const MAX_MODE: u32 = 64;
+
+/// Number of rounds for the largest digest size.
+const MAX_ROUNDS: usize = 40 + MAX_OUTPUT_BITS / 4;
+
+/// Minimum update size, in bytes, worth spreading across threads.
+const PARALLEL_THRESHOLD: usize = 16 * 1024;
+
+/// Parameters shared by every node of a tree.
+#[derive(Clone, Copy)]
+struct Params {
+    output_bits: usize,
+    mode: u32,
+    rounds: usize,
+}
+
+impl Params {
+    /// Compresses one node: `block` is its data (64 words, zero-padded with
+    /// `padding_bits` bits), `level` and `index` its position in the tree,
+    /// and `last` marks the root or the final sequential node.
+    fn compress(
+        self,
+        block: &[u8; BLOCK_LEN],
+        level: usize,
+        index: u64,
+        padding_bits: usize,
+        last: bool,
+    ) -> [u8; CHAIN_LEN] {
+        let mut a = [0u64; INPUT_WORDS + 16 * MAX_ROUNDS];
+        a[..15].copy_from_slice(&Q);
+        // Words 15 to 22 hold the key, which is not supported (all zero)
+        a[23] = (level as u64) << 56 | index;
+        a[24] = (self.rounds as u64) << 48
+            | u64::from(self.mode) << 40
+            | u64::from(last) << 36
+            | (padding_bits as u64) << 20
+            | self.output_bits as u64;
+        for (word, chunk) in a[25..INPUT_WORDS].iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut s = S0;
+        let mut i = INPUT_WORDS;
+        for _ in 0..self.rounds {
+            for step in 0..16 {
+                let mut x = s
+                    ^ a[i - INPUT_WORDS]
+                    ^ a[i - TAPS[0]]
+                    ^ (a[i - TAPS[1]] & a[i - TAPS[2]])
+                    ^ (a[i - TAPS[3]] & a[i - TAPS[4]]);
+                x ^= x >> RIGHT_SHIFTS[step];
+                a[i] = x ^ (x << LEFT_SHIFTS[step]);
+                i += 1;
+            }
+            s = s.rotate_left(1) ^ (s & S_STAR);
+        }
+
+        let mut out = [0u8; CHAIN_LEN];
+        for (chunk, word) in out.chunks_exact_mut(8).zip(&a[i - 16..i]) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+/// Pending data of one level of the tree.
+#[derive(Clone)]
+struct Level {
+    /// Node data; a sequential level keeps its chaining value in the first
+    /// [`CHAIN_LEN`] bytes. A full block stays buffered until more data
+    /// shows that it is not the last node of the level.
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,     // Number of bytes currently in buffer
+    index: u64,            // Number of nodes of the level compressed so far
+}
+
+impl Level {
+    fn new(sequential: bool) -> Self {
+        Level {
+            buffer: [0u8; BLOCK_LEN],
+            buffer_len: if sequential { CHAIN_LEN } else { 0 },
+            index: 0,
+        }
+    }
+}
+
+/// MD6 hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Md6 {
+    params: Params,
+    /// Pending data of each level, starting with the message level 1.
+    levels: Vec<Level>,
+    /// Whether large updates may compress nodes on the rayon thread pool.
+    parallel: bool,
+}
+
+impl Md6 {
+    /// Creates a new MD6-256 hasher with the default mode `L = 64`.
+    pub fn new() -> Self {
+        Self::with_params(256, MAX_MODE).unwrap()
+    }
+
+    /// Creates a new hasher producing an `output_bits`-bit digest with mode
+    /// parameter `mode` (`L`, the height above which nodes are chained
+    /// sequentially).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless `output_bits` is between 1 and 512 and
+    /// `mode` is between 0 and 64.
+    pub fn with_params(output_bits: usize, mode: u32) -> Result<Self, ParameterError> {
+        if output_bits == 0 || output_bits > MAX_OUTPUT_BITS {
+            return Err(ParameterError {
+                algorithm: "MD6",
+                message: format!("digest size must be between 1 and {} bits", MAX_OUTPUT_BITS),
+            });
+        }
+        if mode > MAX_MODE {
+            return Err(ParameterError {
+                algorithm: "MD6",
+                message: format!("mode must be between 0 and {}", MAX_MODE),
+            });
+        }
+        Ok(Md6 {
+            params: Params { output_bits, mode, rounds: 40 + output_bits / 4 },
+            levels: vec![Level::new(mode == 0)],
+            parallel: true,
+        })
+    }
+
+    /// Enables or disables compressing nodes on the rayon thread pool.
+    ///
+    /// Enabled by default. The digest is the same either way.
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Whether `level` (1-based) is hashed by the sequential chain.
+    fn is_sequential(&self, level: usize) -> bool {
+        level == self.params.mode as usize + 1
+    }
+
+    /// Compresses the full buffer of `level`, known not to be its last node.
+    fn flush(&mut self, level: usize) {
+        let params = self.params;
+        let sequential = self.is_sequential(level);
+        let node = &mut self.levels[level - 1];
+        debug_assert_eq!(node.buffer_len, BLOCK_LEN);
+        let out = params.compress(&node.buffer, level, node.index, 0, false);
+        node.index += 1;
+        if sequential {
+            node.buffer[..CHAIN_LEN].copy_from_slice(&out);
+            node.buffer_len = CHAIN_LEN;
+        } else {
+            node.buffer_len = 0;
+            self.append(level + 1, &out);
+        }
+    }
+
+    /// Compresses complete blocks of a tree level, each known to be
+    /// followed by more data, and passes their outputs to the next level.
+    fn compress_blocks(&mut self, level: usize, data: &[u8]) {
+        debug_assert!(data.len().is_multiple_of(BLOCK_LEN));
+        let params = self.params;
+        let first = self.levels[level - 1].index;
+        let node = |(i, block): (usize, &[u8])| {
+            params.compress(block.try_into().unwrap(), level, first + i as u64, 0, false)
+        };
+        let outputs: Vec<[u8; CHAIN_LEN]> = if self.parallel && data.len() >= PARALLEL_THRESHOLD {
+            data.par_chunks(BLOCK_LEN).enumerate().map(node).collect()
+        } else {
+            data.chunks_exact(BLOCK_LEN).enumerate().map(node).collect()
+        };
+        self.levels[level - 1].index += outputs.len() as u64;
+        self.append(level + 1, outputs.as_flattened());
+    }
+
+    /// Feeds data into `level` (1-based), creating it if needed.
+    fn append(&mut self, level: usize, mut data: &[u8]) {
+        if self.levels.len() < level {
+            self.levels.push(Level::new(self.is_sequential(level)));
+        }
+        while !data.is_empty() {
+            if self.levels[level - 1].buffer_len == BLOCK_LEN {
+                self.flush(level);
+            }
+
+            // Keep the last (possibly full) block buffered: only later
+            // input tells whether it is the final node of the level.
+            if self.levels[level - 1].buffer_len == 0 && data.len() > BLOCK_LEN {
+                let whole = (data.len() - 1) / BLOCK_LEN * BLOCK_LEN;
+                self.compress_blocks(level, &data[..whole]);
+                data = &data[whole..];
+            }
+
+            let node = &mut self.levels[level - 1];
+            let take = data.len().min(BLOCK_LEN - node.buffer_len);
+            node.buffer[node.buffer_len..node.buffer_len + take].copy_from_slice(&data[..take]);
+            node.buffer_len += take;
+            data = &data[take..];
+        }
+    }
+
+    /// Feeds data into the hasher. MD6 has no practical message length
+    /// limit.
+    ///
+    /// Complete blocks are compressed right away, in parallel when the
+    /// update is large. The result does not depend on how the message is
+    /// split across calls.
+    pub fn update(&mut self, data: &[u8]) {
+        self.append(1, data);
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// The pending nodes are compressed in a copy of the tree, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut tree = self.clone();
+        let mut level = 1;
+        let root = loop {
+            let node = &tree.levels[level - 1];
+            let mut block = [0u8; BLOCK_LEN];
+            block[..node.buffer_len].copy_from_slice(&node.buffer[..node.buffer_len]);
+            let padding_bits = (BLOCK_LEN - node.buffer_len) * 8;
+
+            // The first node of a tree level is the root when it is also the
+            // last; the sequential chain always ends the tree.
+            let last = node.index == 0 || tree.is_sequential(level);
+            let out = tree.params.compress(&block, level, node.index, padding_bits, last);
+            if last {
+                break out;
+            }
+            tree.append(level + 1, &out);
+            level += 1;
+        };
+
+        // The digest is the last `output_bits` bits of the root's output,
+        // left-aligned in whole bytes
+        let bits = self.params.output_bits;
+        let mut result = root[CHAIN_LEN - bits.div_ceil(8)..].to_vec();
+        let shift = (8 - bits % 8) % 8;
+        if shift > 0 {
+            for i in 0..result.len() {
+                let next = result.get(i + 1).copied().unwrap_or(0);
+                result[i] = result[i] << shift | next >> (8 - shift);
+            }
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so
+    /// far. The digest size, mode and parallelism are kept.
+    pub fn reset(&mut self) {
+        self.levels = vec![Level::new(self.params.mode == 0)];
+    }
+
+    /// Returns the digest size in bits.
+    pub fn output_bits(&self) -> usize {
+        self.params.output_bits
+    }
+
+    /// Returns the mode parameter `L`.
+    pub fn mode(&self) -> u32 {
+        self.params.mode
+    }
+
+    /// Returns the output size in bytes, rounded up for digest sizes that
+    /// are not a multiple of 8 bits.
+    pub fn digest_size(&self) -> usize {
+        self.params.output_bits.div_ceil(8)
+    }
+
+    /// Returns the block size in bytes (512 for MD6).
+    pub fn block_size() -> usize {
+        BLOCK_LEN
+    }
+}
+
+impl Default for Md6 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md6 {
+    type Output = Vec<u8>;
+
+    /// Never fails: MD6 node indices cover more than 2^64 bytes.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Md6::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Md6::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Md6::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md6_hex(data: &[u8], output_bits: usize, mode: u32) -> String {
+        let mut hasher = Md6::with_params(output_bits, mode).unwrap();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_md6_report_example() {
+        // MD6 report, appendix C: "abc" with 5 rounds
+        let mut hasher = Md6::new();
+        hasher.params.rounds = 5;
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "8854c14dc284f840ed71ad7ba542855ce189633e48c797a55121a746be48cec8"
+        );
+    }
+
+    #[test]
+    fn test_md6_reference_vectors() {
+        assert_eq!(
+            md6_hex(b"", 256, 64),
+            "bca38b24a804aa37d821d31af00f5598230122c5bbfc4c4ad5ed40e4258f04ca"
+        );
+        assert_eq!(
+            md6_hex(b"The quick brown fox jumps over the lazy dog", 256, 64),
+            "977592608c45c9923340338450fdcccc21a68888e1e6350e133c5186cd9736ee"
+        );
+        assert_eq!(
+            md6_hex(b"abc", 256, 64),
+            "230637d4e6845cf0d092b558e87625f03881dd53a7439da34cf3b94ed0d8b2c5"
+        );
+        assert_eq!(
+            md6_hex(b"abc", 224, 64),
+            "510c30e4202a5cdd8a4f2ae9beebb6f5988128897937615d52e6d228"
+        );
+        assert_eq!(
+            md6_hex(b"", 512, 64),
+            "6b7f33821a2c060ecdd81aefddea2fd3c4720270e18654f4cb08ece49ccb469f\
+             8beeee7c831206bd577f9f2630d9177979203a9489e47e04df4e6deaa0f8e0c0"
+        );
+    }
+
+    #[test]
+    fn test_md6_odd_digest_sizes() {
+        // The last bits of the root's output, left-aligned
+        assert_eq!(md6_hex(b"abc", 1, 64), "00");
+        assert_eq!(md6_hex(b"abc", 12, 64), "5d70");
+        assert_eq!(md6_hex(b"abc", 40, 64), "fe14af1090");
+        assert_eq!(Md6::with_params(12, 64).unwrap().digest_size(), 2);
+    }
+
+    #[test]
+    fn test_md6_tree_and_sequential_modes() {
+        let data = pattern(10_000);
+        assert_eq!(
+            md6_hex(&data, 256, 64),
+            "4c848995d37af9e91c3f5898d12c7e78cf528410c1ae1d83fcff6efc3f9767e8"
+        );
+        assert_eq!(
+            md6_hex(&data, 256, 0),
+            "1b4a144707a6ab006a6e22524c02c206ac83fa8bc1f8731205c65f654fc86b38"
+        );
+        assert_eq!(
+            md6_hex(&data, 256, 1),
+            "a92b5c1b7156f415e0b16b374b7dcb3089e409c958e74d5752f16403b4df8b3f"
+        );
+        assert_eq!(
+            md6_hex(&b"a".repeat(3000), 256, 0),
+            "e9f32ce924cd627c1ae4763faf25ff45076661ab2acdf1d14c332cacfc34cf8f"
+        );
+    }
+
+    #[test]
+    fn test_md6_streaming_matches_one_shot() {
+        let data = pattern(5000);
+        for mode in [0, 1, 64] {
+            let expected = md6_hex(&data, 256, mode);
+            for step in [1, 100, 128, 384, 511, 512, 513, 2048] {
+                let mut hasher = Md6::with_params(256, mode).unwrap();
+                for chunk in data.chunks(step) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.hexdigest(), expected, "mode {} step {}", mode, step);
+            }
+        }
+    }
+
+    #[test]
+    fn test_md6_parallel_and_serial_paths_match() {
+        let data = pattern(200_000);
+        let mut parallel = Md6::new();
+        let mut serial = Md6::new();
+        serial.set_parallel(false);
+        parallel.update(&data);
+        serial.update(&data);
+        assert_eq!(parallel.digest(), serial.digest());
+    }
+
+    #[test]
+    fn test_md6_invalid_params() {
+        assert!(Md6::with_params(0, 64).is_err());
+        assert!(Md6::with_params(513, 64).is_err());
+        let err = Md6::with_params(256, 65).err().unwrap();
+        assert_eq!(err.algorithm, "MD6");
+    }
+}
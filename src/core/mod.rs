@@ -1,20 +1,332 @@
 //! Core hash algorithm implementations.
 //!
-//! Pure Rust implementations of SHA-2 family algorithms following
-//! the FIPS 180-4 specification.
+//! Pure Rust implementations of the SHA-1 and SHA-2 family algorithms
+//! following the FIPS 180-4 specification, of the SHA-3 family built on the
+//! Keccak sponge (FIPS 202), of BLAKE2 (RFC 7693), of BLAKE3, of MD2
+//! (RFC 1319), MD4 (RFC 1320) and MD5 (RFC 1321), of the RIPEMD family
+//! and Whirlpool (ISO/IEC 10118-3), of the SHA-3 finalists Skein, Grøstl
+//! and JH, of Ascon-Hash256 and Ascon-XOF128 (SP 800-232), of SM3
+//! (GB/T 32905-2016), of the Korean standard LSH, of the Ukrainian standard
+//! Kupyna (DSTU 7564:2014), of the legacy Russian GOST R 34.11-94 and
+//! Korean HAS-160, of the tree-based MD6, and of Tiger, along with HMAC
+//! (RFC 2104), HKDF (RFC 5869), KBKDF (SP 800-108), ConcatKDF, the X9.63
+//! KDF and PBKDF2 (RFC 8018) over any of them and the scrypt (RFC 7914),
+//! Argon2 (RFC 9106), bcrypt and Balloon password hashes.
 //!
 //! # Algorithms
 //!
+//! - [`Md2`] - MD2 (128-bit output, legacy use only)
+//! - [`Md4`] - MD4 (128-bit output, legacy use only)
+//! - [`Md5`] - MD5 (128-bit output, legacy use only)
+//! - [`Sha1`] - SHA-1 (160-bit output, legacy use only)
+//! - [`Sha224`] - SHA-224 (224-bit output)
 //! - [`Sha256`] - SHA-256 (256-bit output)
 //! - [`Sha512`] - SHA-512 (512-bit output)
+//! - [`Sha512_224`] - SHA-512/224 (224-bit output)
+//! - [`Sha512T`] - SHA-512/t (any byte-aligned output below 512 bits)
+//! - [`Sha3_224`], [`Sha3_256`], [`Sha3_384`], [`Sha3_512`] - SHA-3
+//! - [`Shake128`], [`Shake256`] - SHAKE extendable-output functions
+//! - [`CShake128`], [`CShake256`] - cSHAKE customizable XOFs (SP 800-185)
+//! - [`TupleHash128`], [`TupleHash256`] - TupleHash (SP 800-185)
+//! - [`ParallelHash128`], [`ParallelHash256`] - ParallelHash (SP 800-185)
+//! - [`TurboShake128`], [`TurboShake256`] - TurboSHAKE 12-round XOFs (RFC 9861)
+//! - [`KangarooTwelve`] - KangarooTwelve tree hash (RFC 9861)
+//! - [`Keccak256`], [`Keccak512`] - original Keccak (legacy padding, Ethereum)
+//! - [`Blake2b`] - BLAKE2b (1 to 64-byte output, keyed, RFC 7693)
+//! - [`Blake2s`] - BLAKE2s (1 to 32-byte output, keyed, RFC 7693)
+//! - [`Blake2bp`], [`Blake2sp`] - BLAKE2bp and BLAKE2sp, 4- and 8-way parallel BLAKE2
+//! - [`Blake2Xb`], [`Blake2Xs`] - BLAKE2X extendable-output functions
+//! - [`Blake3`] - BLAKE3 (32-byte default output, extendable)
+//! - [`Ripemd128`], [`Ripemd160`], [`Ripemd256`], [`Ripemd320`] - RIPEMD
+//! - [`Sm3`] - SM3 (256-bit output, Chinese national standard)
+//! - [`Whirlpool`] - Whirlpool (512-bit output)
+//! - [`Tiger`], [`Tiger2`] - Tiger/192 with its two paddings
+//! - [`Skein256`], [`Skein512`], [`Skein1024`] - Skein (outputs up to 1 MiB)
+//! - [`Groestl256`], [`Groestl512`] - Grøstl (SHA-3 finalist, final-round version)
+//! - [`Jh256`], [`Jh512`] - JH (SHA-3 finalist, round-3 JH42)
+//! - [`AsconHash256`], [`AsconXof128`] - Ascon lightweight hash and XOF (SP 800-232)
+//! - [`Lsh256`], [`Lsh512`] - LSH (Korean standard KS X 3262, truncatable output)
+//! - [`Kupyna256`], [`Kupyna512`] - Kupyna (Ukrainian standard DSTU 7564:2014)
+//! - [`Gost94`] - GOST R 34.11-94 (256-bit output, legacy, test or CryptoPro S-boxes)
+//! - [`Has160`] - HAS-160 (160-bit output, legacy Korean standard)
+//! - [`Md6`] - MD6 (1 to 512-bit output, tree mode with sequential fallback)
+//! - [`Hmac`] - HMAC message authentication code over any [`Digest`]
+//! - [`HmacDrbg`] - HMAC-DRBG deterministic random bit generator (SP 800-90A)
+//! - [`Poly1305`], [`poly1305()`] - Poly1305 one-time authenticator (RFC 8439)
+//! - [`hotp`], [`hotp_verify`] - HMAC-based one-time passwords (RFC 4226) over any [`Digest`]
+//! - [`totp`], [`totp_verify`], [`totp_uri`] - time-based one-time passwords (RFC 6238)
+//! - [`hkdf`], [`hkdf_extract`], [`hkdf_expand`] - HKDF key derivation over any [`Digest`]
+//! - [`hkdf_expand_label`], [`tls13_derive_secret`] - TLS 1.3 key schedule framing of HKDF
+//! - [`concat_kdf`], [`x963_kdf`] - ConcatKDF (SP 800-56C one-step) and ANSI X9.63 KDF
+//! - [`kbkdf_counter`] - SP 800-108 KBKDF in counter mode with HMAC over any [`Digest`]
+//! - [`pbkdf2_hmac`] - PBKDF2-HMAC password-based key derivation
+//! - [`scrypt()`] - scrypt memory-hard key derivation (PBKDF2 and Salsa20/8)
+//! - [`argon2()`] - Argon2d, Argon2i and Argon2id password hashing (parallel lanes)
+//! - [`bcrypt()`] - bcrypt password hashing on the [`Blowfish`] key schedule
+//! - [`bcrypt_pbkdf()`] - OpenBSD bcrypt_pbkdf, the KDF of encrypted OpenSSH keys
+//! - [`sha_crypt()`] - SHA-crypt `$5$` and `$6$` password hashes (`/etc/shadow`)
+//! - [`password`] - one hash/verify/rehash interface over the password hash strings
+//! - [`balloon()`] - Balloon and Balloon-M memory-hard hashing over any [`Digest`]
 //!
 //! # Usage
 //!
 //! These are low-level implementations. For Python usage, see the
-//! top-level module documentation.
+//! top-level module documentation. Every fixed-size hasher implements
+//! [`Digest`] and every extendable-output function implements [`Xof`], so
+//! generic code can drive any of them. [`DynDigest`] does the same for
+//! hashers chosen at runtime.
 
+use std::fmt;
+
+pub mod argon2;
+pub mod ascon;
+pub mod balloon;
+pub mod bcrypt;
+pub mod bcrypt_pbkdf;
+pub mod blake2;
+pub mod blake2b;
+pub mod blake2p;
+pub mod blake2s;
+pub mod blake2x;
+pub mod blake3;
+pub mod blowfish;
+pub mod concatkdf;
+pub mod cshake;
+pub mod gost94;
+pub mod groestl;
+pub mod has160;
+pub mod hkdf;
+pub mod hmac;
+pub mod hmac_drbg;
+pub mod jh;
+pub mod kangarootwelve;
+pub mod kbkdf;
+pub mod keccak;
+pub mod kupyna;
+pub mod lsh;
+pub mod md2;
+pub mod md4;
+pub mod md5;
+pub mod md6;
+pub mod otp;
+pub mod parallelhash;
+pub mod password;
+pub mod pbkdf2;
+pub mod poly1305;
+pub mod ripemd;
+pub mod scrypt;
+pub mod sha1;
+pub mod sha224;
 pub mod sha256;
+pub mod sha3;
 pub mod sha512;
+pub mod sha512_224;
+pub mod sha512_t;
+pub mod sha_crypt;
+pub mod shake;
+pub mod skein;
+pub mod sm3;
+pub mod tiger;
+pub mod tuplehash;
+pub mod turboshake;
+pub mod whirlpool;
 
+pub use argon2::{Argon2Params, Argon2Variant, argon2};
+pub use ascon::{AsconHash256, AsconXof128};
+pub use balloon::balloon;
+pub use bcrypt::bcrypt;
+pub use bcrypt_pbkdf::bcrypt_pbkdf;
+pub use blake2::{Blake2Node, Blake2Params};
+pub use blake2b::Blake2b;
+pub use blake2p::{Blake2bp, Blake2sp};
+pub use blake2s::Blake2s;
+pub use blake2x::{Blake2Xb, Blake2Xs};
+pub use blake3::Blake3;
+pub use blowfish::Blowfish;
+pub use concatkdf::{concat_kdf, x963_kdf};
+pub use cshake::{CShake128, CShake256};
+pub use gost94::{Gost94, Gost94SBox};
+pub use groestl::{Groestl256, Groestl512};
+pub use has160::Has160;
+pub use hkdf::{hkdf, hkdf_expand, hkdf_expand_label, hkdf_extract, tls13_derive_secret};
+pub use hmac::Hmac;
+pub use hmac_drbg::HmacDrbg;
+pub use jh::{Jh256, Jh512};
+pub use kangarootwelve::KangarooTwelve;
+pub use kbkdf::kbkdf_counter;
+pub use keccak::{Keccak256, Keccak512};
+pub use kupyna::{Kupyna256, Kupyna512};
+pub use lsh::{Lsh256, Lsh512};
+pub use md2::Md2;
+pub use md4::Md4;
+pub use md5::Md5;
+pub use md6::Md6;
+pub use otp::{hotp, hotp_verify, totp, totp_time_step, totp_uri, totp_verify};
+pub use parallelhash::{ParallelHash128, ParallelHash256};
+pub use password::PasswordScheme;
+pub use pbkdf2::pbkdf2_hmac;
+pub use poly1305::{Poly1305, poly1305};
+pub use ripemd::{Ripemd128, Ripemd160, Ripemd256, Ripemd320};
+pub use scrypt::{ScryptParams, scrypt};
+pub use sha1::Sha1;
+pub use sha224::Sha224;
 pub use sha256::Sha256;
+pub use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
 pub use sha512::Sha512;
+pub use sha512_224::Sha512_224;
+pub use sha512_t::Sha512T;
+pub use sha_crypt::{ShaCryptVariant, sha_crypt};
+pub use shake::{Shake128, Shake256};
+pub use skein::{Skein1024, Skein256, Skein512};
+pub use sm3::Sm3;
+pub use tiger::{Tiger, Tiger2};
+pub use tuplehash::{TupleHash128, TupleHash256};
+pub use turboshake::{TurboShake128, TurboShake256};
+pub use whirlpool::Whirlpool;
+
+/// Streaming interface shared by all hashers.
+///
+/// Mirrors the inherent methods of each hasher; implementations simply
+/// forward to them.
+pub trait Digest: Clone + Send {
+    /// Digest type, e.g. `[u8; 32]` for SHA-256.
+    type Output: AsRef<[u8]> + Clone + Send;
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError>;
+
+    /// Returns the digest of the data fed so far, leaving the hasher usable.
+    fn digest(&self) -> Self::Output;
+
+    /// Returns the output size in bytes.
+    fn digest_size(&self) -> usize;
+
+    /// Returns the internal block size in bytes.
+    fn block_size(&self) -> usize;
+}
+
+/// Object-safe counterpart of [`Digest`], for hashers chosen at runtime.
+///
+/// Implemented by every [`Digest`]; `Box<dyn DynDigest>` implements
+/// [`Digest`] in turn, with the output as a `Vec<u8>`, so it can be used
+/// wherever a concrete hasher can, such as in [`Hmac`].
+pub trait DynDigest: Send {
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    fn dyn_try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError>;
+
+    /// Returns the digest of the data fed so far, leaving the hasher usable.
+    fn dyn_digest(&self) -> Vec<u8>;
+
+    /// Returns the output size in bytes.
+    fn dyn_digest_size(&self) -> usize;
+
+    /// Returns the internal block size in bytes.
+    fn dyn_block_size(&self) -> usize;
+
+    /// Returns a boxed copy of the hasher.
+    fn dyn_clone(&self) -> Box<dyn DynDigest>;
+}
+
+impl<D: Digest + 'static> DynDigest for D {
+    fn dyn_try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Digest::try_update(self, data)
+    }
+
+    fn dyn_digest(&self) -> Vec<u8> {
+        Digest::digest(self).as_ref().to_vec()
+    }
+
+    fn dyn_digest_size(&self) -> usize {
+        Digest::digest_size(self)
+    }
+
+    fn dyn_block_size(&self) -> usize {
+        Digest::block_size(self)
+    }
+
+    fn dyn_clone(&self) -> Box<dyn DynDigest> {
+        Box::new(self.clone())
+    }
+}
+
+// Calls go through `**self`: `Box<dyn DynDigest>` is itself a `DynDigest`,
+// so `self.dyn_clone()` would recurse instead of reaching the boxed hasher
+impl Clone for Box<dyn DynDigest> {
+    fn clone(&self) -> Self {
+        (**self).dyn_clone()
+    }
+}
+
+impl Digest for Box<dyn DynDigest> {
+    type Output = Vec<u8>;
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        (**self).dyn_try_update(data)
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        (**self).dyn_digest()
+    }
+
+    fn digest_size(&self) -> usize {
+        (**self).dyn_digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        (**self).dyn_block_size()
+    }
+}
+
+/// Common interface of the extendable-output functions (XOFs).
+///
+/// XOFs have no fixed digest size: callers choose how many output bytes to
+/// read. For SHAKE and cSHAKE shorter outputs are prefixes of longer ones;
+/// TupleHash hashes the requested length, so its outputs are not.
+pub trait Xof: Clone + Send {
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError>;
+
+    /// Fills `out` with `out.len()` output bytes for the data fed so far,
+    /// leaving the hasher usable.
+    fn squeeze(&self, out: &mut [u8]);
+
+    /// Returns the internal block size in bytes.
+    fn block_size(&self) -> usize;
+}
+
+/// Error returned when a message would exceed the maximum length an
+/// algorithm can encode in its padding.
+///
+/// FIPS 180-4 limits SHA-1, SHA-224 and SHA-256 messages to 2^64 - 1 bits
+/// and SHA-512 based messages to 2^128 - 1 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthOverflowError {
+    /// Name of the algorithm whose limit was exceeded.
+    pub algorithm: &'static str,
+}
+
+impl fmt::Display for LengthOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} message length limit exceeded", self.algorithm)
+    }
+}
+
+impl std::error::Error for LengthOverflowError {}
+
+/// Error returned when an algorithm is given an invalid parameter, such as
+/// an unsupported output size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterError {
+    /// Name of the algorithm that rejected the parameter.
+    pub algorithm: &'static str,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.algorithm, self.message)
+    }
+}
+
+impl std::error::Error for ParameterError {}
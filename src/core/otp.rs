@@ -0,0 +1,346 @@
+//! HMAC-based and time-based one-time passwords.
+//!
+//! Pure Rust implementation of HOTP (RFC 4226) and TOTP (RFC 6238) over
+//! any [`Digest`]. The counter is MACed as an 8-byte big-endian number and
+//! the MAC reduced to a short decimal code by dynamic truncation:
+//!
+//! ```text
+//! offset = MAC[len - 1] & 0x0f
+//! code   = (MAC[offset..offset + 4] as u32 & 0x7fffffff) mod 10^digits
+//! ```
+//!
+//! TOTP is HOTP with the number of time steps since `T0` as the counter,
+//! and [`totp_uri`] writes the `otpauth://` provisioning URI that
+//! authenticator apps read from QR codes.
+//!
+//! # Algorithm Details
+//!
+//! - **Counter**: 64 bits, big-endian
+//! - **Digits**: 6 to 8, zero-padded
+//! - **Digest size**: at least 20 bytes, so that any offset leaves four
+//!   bytes to read (SHA-1 as in RFC 4226, or SHA-256 and SHA-512)
+//! - **Time step**: `(time - T0) / step`, 30 seconds from the Unix epoch
+//!   by default
+
+use super::{Digest, Hmac, ParameterError};
+use crate::utils::{base32_encode, constant_time_eq};
+
+/// Smallest number of digits in a code.
+pub const MIN_DIGITS: u32 = 6;
+
+/// Largest number of digits in a code.
+pub const MAX_DIGITS: u32 = 8;
+
+/// Smallest digest size dynamic truncation works with, in bytes.
+pub const MIN_DIGEST_SIZE: usize = 20;
+
+/// Checks the number of digits and the digest size of `hasher`.
+fn check_params<D: Digest>(hasher: &D, digits: u32) -> Result<(), ParameterError> {
+    if !(MIN_DIGITS..=MAX_DIGITS).contains(&digits) {
+        return Err(ParameterError {
+            algorithm: "HOTP",
+            message: format!("digits must be between {} and {}", MIN_DIGITS, MAX_DIGITS),
+        });
+    }
+    if hasher.digest_size() < MIN_DIGEST_SIZE {
+        return Err(ParameterError {
+            algorithm: "HOTP",
+            message: format!("the digest must be at least {} bytes", MIN_DIGEST_SIZE),
+        });
+    }
+    Ok(())
+}
+
+/// Computes the code for `counter` with a MAC already keyed.
+fn counter_code<D: Digest>(mac: &Hmac<D>, counter: u64, digits: u32) -> String {
+    let tag = mac.mac(&counter.to_be_bytes());
+    let tag = tag.as_ref();
+    let offset = usize::from(tag[tag.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes(tag[offset..offset + 4].try_into().unwrap()) & 0x7fff_ffff;
+    format!("{:0width$}", binary % 10u32.pow(digits), width = digits as usize)
+}
+
+/// Returns the HOTP code of `counter` under `key`, `digits` digits long.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `digits` is not between 6 and 8 or the
+/// digest is shorter than 20 bytes.
+pub fn hotp<D: Digest>(
+    hasher: D,
+    key: &[u8],
+    counter: u64,
+    digits: u32,
+) -> Result<String, ParameterError> {
+    check_params(&hasher, digits)?;
+    Ok(counter_code(&Hmac::new(hasher, key), counter, digits))
+}
+
+/// Looks for `code` among the HOTP codes of `counter` to
+/// `counter + look_ahead`, returning the first matching counter.
+///
+/// Every counter in the window is tried and compared in constant time, so
+/// the running time does not tell which one matched. The window stops at
+/// `u64::MAX`.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `digits` is not between 6 and 8 or the
+/// digest is shorter than 20 bytes.
+pub fn hotp_verify<D: Digest>(
+    hasher: D,
+    key: &[u8],
+    counter: u64,
+    code: &str,
+    digits: u32,
+    look_ahead: u64,
+) -> Result<Option<u64>, ParameterError> {
+    check_params(&hasher, digits)?;
+    let mac = Hmac::new(hasher, key);
+    let mut matched = None;
+    for candidate in counter..=counter.saturating_add(look_ahead) {
+        let expected = counter_code(&mac, candidate, digits);
+        let found = constant_time_eq(expected.as_bytes(), code.as_bytes());
+        if found && matched.is_none() {
+            matched = Some(candidate);
+        }
+    }
+    Ok(matched)
+}
+
+/// Returns the TOTP time step of the Unix time `time`:
+/// `(time - t0) / step`.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `step` is 0 or `time` is before `t0`.
+pub fn totp_time_step(time: u64, step: u64, t0: u64) -> Result<u64, ParameterError> {
+    if step == 0 {
+        return Err(ParameterError {
+            algorithm: "TOTP",
+            message: "step must be at least 1 second".to_owned(),
+        });
+    }
+    let elapsed = time.checked_sub(t0).ok_or_else(|| ParameterError {
+        algorithm: "TOTP",
+        message: "time must not be before t0".to_owned(),
+    })?;
+    Ok(elapsed / step)
+}
+
+/// Returns the TOTP code of the Unix time `time` under `key`, `digits`
+/// digits long, with time steps of `step` seconds counted from `t0`.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] for the cases of [`totp_time_step`] and
+/// [`hotp`].
+pub fn totp<D: Digest>(
+    hasher: D,
+    key: &[u8],
+    time: u64,
+    step: u64,
+    t0: u64,
+    digits: u32,
+) -> Result<String, ParameterError> {
+    hotp(hasher, key, totp_time_step(time, step, t0)?, digits)
+}
+
+/// Looks for `code` among the TOTP codes of the time steps
+/// `time_step - window` to `time_step + window`, returning the first
+/// matching time step.
+///
+/// `time_step` comes from [`totp_time_step`]. Callers that reject replayed
+/// codes remember the returned step and refuse codes up to it.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] for the cases of [`hotp`].
+pub fn totp_verify<D: Digest>(
+    hasher: D,
+    key: &[u8],
+    time_step: u64,
+    code: &str,
+    digits: u32,
+    window: u64,
+) -> Result<Option<u64>, ParameterError> {
+    let first = time_step.saturating_sub(window);
+    let look_ahead = (time_step - first).saturating_add(window);
+    hotp_verify(hasher, key, first, code, digits, look_ahead)
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for &byte in text.as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Builds the `otpauth://totp/` provisioning URI of Google Authenticator's
+/// key URI format:
+///
+/// ```text
+/// otpauth://totp/ISSUER:ACCOUNT?secret=BASE32&issuer=ISSUER&algorithm=SHA1&digits=6&period=30
+/// ```
+///
+/// The secret is unpadded Base32 and the label and issuer are
+/// percent-encoded; an empty `issuer` is left out. `algorithm` is the name
+/// apps expect: `SHA1`, `SHA256` or `SHA512`.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `account` is empty, `issuer` or `account`
+/// contains a colon, `algorithm` is not one of the three above, `digits`
+/// is not between 6 and 8, or `period` is 0.
+pub fn totp_uri(
+    secret: &[u8],
+    issuer: &str,
+    account: &str,
+    algorithm: &str,
+    digits: u32,
+    period: u64,
+) -> Result<String, ParameterError> {
+    let error = |message: &str| ParameterError { algorithm: "TOTP", message: message.to_owned() };
+    if account.is_empty() {
+        return Err(error("account must not be empty"));
+    }
+    if issuer.contains(':') || account.contains(':') {
+        return Err(error("issuer and account must not contain ':'"));
+    }
+    if !["SHA1", "SHA256", "SHA512"].contains(&algorithm) {
+        return Err(error("provisioning URIs only support SHA1, SHA256 and SHA512"));
+    }
+    if !(MIN_DIGITS..=MAX_DIGITS).contains(&digits) {
+        return Err(error("digits must be between 6 and 8"));
+    }
+    if period == 0 {
+        return Err(error("step must be at least 1 second"));
+    }
+    let mut uri = String::from("otpauth://totp/");
+    if !issuer.is_empty() {
+        uri.push_str(&percent_encode(issuer));
+        uri.push(':');
+    }
+    uri.push_str(&percent_encode(account));
+    uri.push_str("?secret=");
+    uri.push_str(&base32_encode(secret));
+    if !issuer.is_empty() {
+        uri.push_str("&issuer=");
+        uri.push_str(&percent_encode(issuer));
+    }
+    uri.push_str(&format!("&algorithm={}&digits={}&period={}", algorithm, digits, period));
+    Ok(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Md5, Sha1, Sha256, Sha512};
+
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc4226_appendix_d() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(Sha1::new(), SECRET, counter as u64, 6).unwrap(), *code);
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        // The truncated value of counter 0 is 1284755224 (RFC 4226,
+        // appendix D); codes keep their leading zeros
+        assert_eq!(hotp(Sha1::new(), SECRET, 0, 7).unwrap(), "4755224");
+        assert_eq!(hotp(Sha1::new(), SECRET, 0, 8).unwrap(), "84755224");
+        assert_eq!(hotp(Sha1::new(), SECRET, 1, 8).unwrap(), "94287082");
+        assert!(hotp(Sha1::new(), SECRET, 0, 5).is_err());
+        assert!(hotp(Sha1::new(), SECRET, 0, 9).is_err());
+        assert!(hotp(Md5::new(), SECRET, 0, 6).is_err());
+    }
+
+    #[test]
+    fn test_other_digests() {
+        // RFC 6238, appendix B, at T = 1 (time 59), with the key sizes the
+        // RFC uses for each hash
+        let key32 = b"12345678901234567890123456789012";
+        let key64 = b"1234567890123456789012345678901234567890123456789012345678901234";
+        assert_eq!(hotp(Sha1::new(), SECRET, 1, 8).unwrap(), "94287082");
+        assert_eq!(hotp(Sha256::new(), key32, 1, 8).unwrap(), "46119246");
+        assert_eq!(hotp(Sha512::new(), key64, 1, 8).unwrap(), "90693936");
+    }
+
+    #[test]
+    fn test_verify_window() {
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, 0, "755224", 6, 3).unwrap(), Some(0));
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, 0, "969429", 6, 3).unwrap(), Some(3));
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, 0, "338314", 6, 3).unwrap(), None);
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, 4, "338314", 6, 0).unwrap(), Some(4));
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, 0, "75522", 6, 3).unwrap(), None);
+        assert_eq!(hotp_verify(Sha1::new(), SECRET, u64::MAX, "000000", 6, 5).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rfc6238_appendix_b() {
+        let key32 = b"12345678901234567890123456789012";
+        let key64 = b"1234567890123456789012345678901234567890123456789012345678901234";
+        let cases = [
+            (59, "94287082", "46119246", "90693936"),
+            (1111111109, "07081804", "68084774", "25091201"),
+            (1111111111, "14050471", "67062674", "99943326"),
+            (1234567890, "89005924", "91819424", "93441116"),
+            (2000000000, "69279037", "90698825", "38618901"),
+            (20000000000, "65353130", "77737706", "47863826"),
+        ];
+        for (time, sha1, sha256, sha512) in cases {
+            assert_eq!(totp(Sha1::new(), SECRET, time, 30, 0, 8).unwrap(), sha1);
+            assert_eq!(totp(Sha256::new(), key32, time, 30, 0, 8).unwrap(), sha256);
+            assert_eq!(totp(Sha512::new(), key64, time, 30, 0, 8).unwrap(), sha512);
+        }
+    }
+
+    #[test]
+    fn test_totp_time_step() {
+        assert_eq!(totp_time_step(59, 30, 0).unwrap(), 1);
+        assert_eq!(totp_time_step(60, 30, 0).unwrap(), 2);
+        assert_eq!(totp_time_step(100, 60, 40).unwrap(), 1);
+        assert!(totp_time_step(39, 30, 40).is_err());
+        assert!(totp_time_step(59, 0, 0).is_err());
+        // The window is clamped at time step 0
+        let code = totp(Sha1::new(), SECRET, 59, 30, 0, 6).unwrap();
+        assert_eq!(totp_verify(Sha1::new(), SECRET, 0, &code, 6, 1).unwrap(), Some(1));
+        assert_eq!(totp_verify(Sha1::new(), SECRET, 2, &code, 6, 1).unwrap(), Some(1));
+        assert_eq!(totp_verify(Sha1::new(), SECRET, 3, &code, 6, 1).unwrap(), None);
+        assert_eq!(totp_verify(Sha1::new(), SECRET, 3, &code, 6, 2).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_totp_uri() {
+        let uri = totp_uri(b"12345678901234567890", "ACME Co", "john@example.com", "SHA1", 6, 30);
+        assert_eq!(
+            uri.unwrap(),
+            "otpauth://totp/ACME%20Co:john%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &issuer=ACME%20Co&algorithm=SHA1&digits=6&period=30"
+        );
+        let uri = totp_uri(b"\xff", "", "alice", "SHA512", 8, 60).unwrap();
+        assert_eq!(uri, "otpauth://totp/alice?secret=74&algorithm=SHA512&digits=8&period=60");
+        assert!(totp_uri(b"k", "a:b", "alice", "SHA1", 6, 30).is_err());
+        assert!(totp_uri(b"k", "issuer", "", "SHA1", 6, 30).is_err());
+        assert!(totp_uri(b"k", "issuer", "alice", "MD5", 6, 30).is_err());
+        assert!(totp_uri(b"k", "issuer", "alice", "SHA1", 9, 30).is_err());
+        assert!(totp_uri(b"k", "issuer", "alice", "SHA1", 6, 0).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! ParallelHash functions for hashing long messages in parallel.
+//!
+//! Pure Rust implementation following NIST SP 800-185. The input is split
+//! into blocks of `B` bytes that are hashed independently, so complete
+//! blocks are processed concurrently on the rayon thread pool; the result
+//! does not depend on how many threads take part.
+//!
+//! # Algorithm Details
+//!
+//! - **Leaves**: each block is hashed with SHAKE (cSHAKE without strings)
+//!   to 32 bytes (ParallelHash128) or 64 bytes (ParallelHash256)
+//! - **Final node**: cSHAKE with `N = "ParallelHash"` over
+//!   `left_encode(B) || leaves || right_encode(n) || right_encode(L)`
+//! - **Output**: `L` bits; the requested length is hashed too, so outputs of
+//!   different lengths are unrelated rather than prefixes of each other
+
+use rayon::prelude::*;
+
+use super::cshake::{customized_sponge, left_encode, right_encode};
+use super::keccak::Sponge;
+use super::{LengthOverflowError, ParameterError, Xof};
+use crate::utils::to_hex;
+
+/// Domain separation byte of cSHAKE, used by the final node.
+const CSHAKE_DOMAIN: u8 = 0x04;
+
+/// Domain separation byte of SHAKE, used by the leaves.
+const SHAKE_DOMAIN: u8 = 0x1f;
+
+/// Minimum number of input bytes handed to one rayon task, so that small
+/// block sizes do not drown the leaves in scheduling overhead.
+const MIN_BYTES_PER_TASK: usize = 16 * 1024;
+
+/// ParallelHash hasher with a rate of `RATE` bytes.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct ParallelHash<const RATE: usize> {
+    /// Final-node sponge, holding the prefix and the leaves hashed so far.
+    sponge: Sponge,
+    /// Sponge right after `left_encode(B)`, restored by `reset()`.
+    initial: Sponge,
+    /// Bytes of the current, incomplete block.
+    buffer: Vec<u8>,
+    block_len: usize,
+    /// Number of leaves absorbed into `sponge`.
+    blocks: u64,
+    /// Whether complete blocks may be hashed on the rayon thread pool.
+    parallel: bool,
+}
+
+/// ParallelHash128 hasher.
+pub type ParallelHash128 = ParallelHash<168>;
+
+/// ParallelHash256 hasher.
+pub type ParallelHash256 = ParallelHash<136>;
+
+impl<const RATE: usize> ParallelHash<RATE> {
+    /// Leaf size in bytes: twice the security strength, i.e. the capacity.
+    const LEAF_LEN: usize = 200 - RATE;
+
+    /// Creates a new hasher with blocks of `block_len` bytes (`B`) and
+    /// customization string `customization` (`S`, may be empty).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if `block_len` is 0.
+    pub fn new(block_len: usize, customization: &[u8]) -> Result<Self, ParameterError> {
+        if block_len == 0 {
+            return Err(ParameterError {
+                algorithm: "ParallelHash",
+                message: "block size must be at least 1 byte".to_owned(),
+            });
+        }
+        let mut sponge = customized_sponge(RATE, b"ParallelHash", customization);
+        let (prefix, len) = left_encode(block_len as u64);
+        sponge.absorb(&prefix[..len]);
+        Ok(ParallelHash {
+            initial: sponge.clone(),
+            sponge,
+            buffer: Vec::new(),
+            block_len,
+            blocks: 0,
+            parallel: true,
+        })
+    }
+
+    /// Returns the block size `B` in bytes.
+    pub fn block_len(&self) -> usize {
+        self.block_len
+    }
+
+    /// Enables or disables hashing blocks on the rayon thread pool.
+    ///
+    /// Enabled by default. The output is the same either way.
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Hashes one block into a leaf.
+    fn leaf(block: &[u8]) -> [u8; 64] {
+        let mut sponge = Sponge::new(RATE, 24);
+        sponge.absorb(block);
+        let mut leaf = [0u8; 64];
+        sponge.finalize(SHAKE_DOMAIN).read(&mut leaf[..Self::LEAF_LEN]);
+        leaf
+    }
+
+    /// Hashes complete blocks and absorbs their leaves in order.
+    fn absorb_blocks(&mut self, data: &[u8]) {
+        debug_assert!(data.len().is_multiple_of(self.block_len));
+        let count = data.len() / self.block_len;
+        if self.parallel && count > 1 {
+            let min_blocks = (MIN_BYTES_PER_TASK / self.block_len).max(1);
+            let leaves: Vec<[u8; 64]> = data
+                .par_chunks(self.block_len)
+                .with_min_len(min_blocks)
+                .map(Self::leaf)
+                .collect();
+            for leaf in &leaves {
+                self.sponge.absorb(&leaf[..Self::LEAF_LEN]);
+            }
+        } else {
+            for block in data.chunks_exact(self.block_len) {
+                self.sponge.absorb(&Self::leaf(block)[..Self::LEAF_LEN]);
+            }
+        }
+        self.blocks += count as u64;
+    }
+
+    /// Feeds data into the hasher. ParallelHash has no message length limit.
+    ///
+    /// Complete blocks are hashed right away, in parallel when there are
+    /// several; a trailing partial block is buffered.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if !self.buffer.is_empty() {
+            let take = data.len().min(self.block_len - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < self.block_len {
+                return;
+            }
+            let block = std::mem::take(&mut self.buffer);
+            self.absorb_blocks(&block);
+        }
+
+        let whole = data.len() - data.len() % self.block_len;
+        self.absorb_blocks(&data[..whole]);
+        self.buffer.extend_from_slice(&data[whole..]);
+    }
+
+    /// Fills `out` with the `out.len()`-byte hash of the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        let mut sponge = self.sponge.clone();
+        let mut blocks = self.blocks;
+        if !self.buffer.is_empty() {
+            sponge.absorb(&Self::leaf(&self.buffer)[..Self::LEAF_LEN]);
+            blocks += 1;
+        }
+        let (suffix, len) = right_encode(blocks);
+        sponge.absorb(&suffix[..len]);
+        let (suffix, len) = right_encode(out.len() as u64 * 8);
+        sponge.absorb(&suffix[..len]);
+        sponge.finalize(CSHAKE_DOMAIN).read(out);
+    }
+
+    /// Returns the `length`-byte hash of the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        let mut out = vec![0u8; length];
+        self.finalize_into(&mut out);
+        out
+    }
+
+    /// Returns the `length`-byte hash as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, keeping `B` and `S`.
+    pub fn reset(&mut self) {
+        self.sponge = self.initial.clone();
+        self.buffer.clear();
+        self.blocks = 0;
+    }
+
+    /// Returns the rate of the underlying sponge in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize> Xof for ParallelHash<RATE> {
+    /// Never fails: ParallelHash has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sample input of SP 800-185: three 8-byte blocks.
+    fn sample_data() -> Vec<u8> {
+        [0x00u8, 0x10, 0x20].iter().flat_map(|&high| (0..8).map(move |i| high + i)).collect()
+    }
+
+    fn parallel_hex<const RATE: usize>(data: &[u8], custom: &[u8], length: usize) -> String {
+        let mut hasher = ParallelHash::<RATE>::new(8, custom).unwrap();
+        hasher.update(data);
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_parallelhash128_sp800_185_samples() {
+        assert_eq!(
+            parallel_hex::<168>(&sample_data(), b"", 32),
+            "ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5"
+        );
+        assert_eq!(
+            parallel_hex::<168>(&sample_data(), b"Parallel Data", 32),
+            "fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206"
+        );
+    }
+
+    #[test]
+    fn test_parallelhash256_sp800_185_samples() {
+        assert_eq!(
+            parallel_hex::<136>(&sample_data(), b"", 64),
+            "bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c45110553\
+             1b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429"
+        );
+        assert_eq!(
+            parallel_hex::<136>(&sample_data(), b"Parallel Data", 64),
+            "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb\
+             33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110"
+        );
+    }
+
+    #[test]
+    fn test_parallel_and_serial_paths_match() {
+        let mut seed = 0x2545f4914f6cdd1du64;
+        let data: Vec<u8> = (0..40_000)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed as u8
+            })
+            .collect();
+        for block_len in [1, 7, 4096] {
+            let mut parallel = ParallelHash128::new(block_len, b"S").unwrap();
+            let mut serial = ParallelHash128::new(block_len, b"S").unwrap();
+            serial.set_parallel(false);
+            for chunk in data.chunks(9_001) {
+                parallel.update(chunk);
+                serial.update(chunk);
+            }
+            assert_eq!(parallel.digest(32), serial.digest(32));
+        }
+    }
+
+    #[test]
+    fn test_update_split_matches_whole() {
+        let data = sample_data();
+        let expected = parallel_hex::<168>(&data, b"", 32);
+        for split in 0..=data.len() {
+            let mut hasher = ParallelHash128::new(8, b"").unwrap();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.hexdigest(32), expected);
+        }
+    }
+
+    #[test]
+    fn test_parallelhash_rejects_empty_blocks() {
+        let err = ParallelHash128::new(0, b"").err().expect("B = 0 should be rejected");
+        assert_eq!(err.algorithm, "ParallelHash");
+    }
+}
@@ -0,0 +1,243 @@
+//! Unified password hashing over the stored-string formats.
+//!
+//! One front door for the password hashes of this crate: [`hash`] writes
+//! the string of any [`PasswordScheme`], [`identify`] reads the scheme and
+//! costs back from the prefix of a stored string, [`verify`] dispatches to
+//! the matching algorithm, and [`PasswordScheme::is_weaker_than`] tells
+//! whether a stored hash should be upgraded to a policy.
+//!
+//! # Supported Strings
+//!
+//! - `$argon2id$`, `$argon2i$`, `$argon2d$` - Argon2 PHC strings
+//! - `$scrypt$` - scrypt PHC strings (`ln=`, `r=`, `p=`)
+//! - `$2a$`, `$2b$`, `$2y$` - bcrypt, written as `$2b$`
+//! - `$5$`, `$6$` - sha256-crypt and sha512-crypt
+
+use super::argon2::{self, Argon2Params};
+use super::scrypt::{self, ScryptParams};
+use super::sha_crypt::{self, ShaCryptVariant};
+use super::{ParameterError, bcrypt};
+use crate::utils::constant_time_eq;
+
+/// Length of the random salt, in bytes, for every scheme.
+pub const SALT_LEN: usize = 16;
+
+/// A password hashing scheme and its costs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PasswordScheme {
+    /// Argon2, with the length of its tag.
+    Argon2 { params: Argon2Params, hash_len: usize },
+    /// bcrypt, with its cost (log2 of the rounds).
+    Bcrypt { cost: u32 },
+    /// scrypt, with the length of its key.
+    Scrypt { params: ScryptParams, hash_len: usize },
+    /// sha256-crypt or sha512-crypt, with the number of rounds.
+    ShaCrypt { variant: ShaCryptVariant, rounds: u32 },
+}
+
+impl PasswordScheme {
+    /// Returns the scheme name, such as `"argon2id"`, `"bcrypt"`, `"scrypt"`
+    /// or `"sha512_crypt"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PasswordScheme::Argon2 { params, .. } => params.variant.name(),
+            PasswordScheme::Bcrypt { .. } => "bcrypt",
+            PasswordScheme::Scrypt { .. } => "scrypt",
+            PasswordScheme::ShaCrypt { variant: ShaCryptVariant::Sha256, .. } => "sha256_crypt",
+            PasswordScheme::ShaCrypt { variant: ShaCryptVariant::Sha512, .. } => "sha512_crypt",
+        }
+    }
+
+    /// Returns whether a hash with these settings falls short of `policy`:
+    /// another scheme or Argon2 variant, an older Argon2 version, or any
+    /// cost or hash length below the policy's.
+    ///
+    /// Argon2 `parallelism` only spreads the same work over more lanes, so
+    /// it does not count.
+    pub fn is_weaker_than(&self, policy: &PasswordScheme) -> bool {
+        use PasswordScheme::*;
+        match (self, policy) {
+            (Argon2 { params: s, hash_len: sl }, Argon2 { params: p, hash_len: pl }) => {
+                s.variant != p.variant
+                    || s.version < p.version
+                    || s.t_cost < p.t_cost
+                    || s.m_cost < p.m_cost
+                    || sl < pl
+            }
+            (Bcrypt { cost: s }, Bcrypt { cost: p }) => s < p,
+            (Scrypt { params: s, hash_len: sl }, Scrypt { params: p, hash_len: pl }) => {
+                s.n < p.n || s.r < p.r || s.p < p.p || sl < pl
+            }
+            (ShaCrypt { variant: sv, rounds: s }, ShaCrypt { variant: pv, rounds: p }) => {
+                sv != pv || s < p
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Hashes `password` with `scheme` and `salt`, returning the string to
+/// store.
+///
+/// sha-crypt salts are the 16 salt bytes mapped onto its Base64 alphabet,
+/// and its default 5000 rounds are left implicit as crypt(3) does.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the costs are out of range for the
+/// scheme.
+pub fn hash(
+    password: &[u8],
+    scheme: &PasswordScheme,
+    salt: &[u8; SALT_LEN],
+) -> Result<String, ParameterError> {
+    match scheme {
+        PasswordScheme::Argon2 { params, hash_len } => {
+            let mut tag = vec![0u8; *hash_len];
+            super::argon2(params, password, salt, &mut tag)?;
+            Ok(argon2::phc_encode(params, salt, &tag))
+        }
+        PasswordScheme::Bcrypt { cost } => {
+            let tag = super::bcrypt(password, salt, *cost)?;
+            Ok(bcrypt::encode("2b", *cost, salt, &tag))
+        }
+        PasswordScheme::Scrypt { params, hash_len } => {
+            let mut key = vec![0u8; *hash_len];
+            super::scrypt(password, salt, params.n, params.r, params.p, &mut key)?;
+            Ok(scrypt::phc_encode(params, salt, &key))
+        }
+        PasswordScheme::ShaCrypt { variant, rounds } => {
+            let salt: String =
+                salt.iter().map(|b| sha_crypt::ALPHABET[usize::from(b & 0x3f)] as char).collect();
+            let rounds = (*rounds != sha_crypt::DEFAULT_ROUNDS).then_some(*rounds);
+            sha_crypt::sha_crypt(*variant, password, &salt, rounds)
+        }
+    }
+}
+
+/// Reads the scheme and costs of a stored string.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is malformed or of none of
+/// the supported schemes.
+pub fn identify(stored: &str) -> Result<PasswordScheme, ParameterError> {
+    if stored.starts_with("$argon2") {
+        let (params, _, tag) = argon2::phc_decode(stored)?;
+        Ok(PasswordScheme::Argon2 { params, hash_len: tag.len() })
+    } else if stored.starts_with("$scrypt$") {
+        let (params, _, key) = scrypt::phc_decode(stored)?;
+        Ok(PasswordScheme::Scrypt { params, hash_len: key.len() })
+    } else if stored.starts_with("$2") {
+        let (_, cost, _, _) = bcrypt::decode(stored)?;
+        Ok(PasswordScheme::Bcrypt { cost })
+    } else if stored.starts_with("$5$") || stored.starts_with("$6$") {
+        let (variant, rounds, _) = sha_crypt::decode(stored)?;
+        let rounds = rounds.unwrap_or(sha_crypt::DEFAULT_ROUNDS);
+        Ok(PasswordScheme::ShaCrypt { variant, rounds })
+    } else {
+        Err(ParameterError {
+            algorithm: "password hash",
+            message: "unknown password hash scheme".to_owned(),
+        })
+    }
+}
+
+/// Checks `password` against a stored string of any supported scheme,
+/// comparing in constant time.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is malformed, of an unknown
+/// scheme, or has costs the algorithm rejects.
+pub fn verify(password: &[u8], stored: &str) -> Result<bool, ParameterError> {
+    if stored.starts_with("$argon2") {
+        let (params, salt, expected) = argon2::phc_decode(stored)?;
+        let mut tag = vec![0u8; expected.len()];
+        super::argon2(&params, password, &salt, &mut tag)?;
+        Ok(constant_time_eq(&tag, &expected))
+    } else if stored.starts_with("$scrypt$") {
+        let (params, salt, expected) = scrypt::phc_decode(stored)?;
+        let mut key = vec![0u8; expected.len()];
+        super::scrypt(password, &salt, params.n, params.r, params.p, &mut key)?;
+        Ok(constant_time_eq(&key, &expected))
+    } else if stored.starts_with("$2") {
+        let (version, cost, salt, _) = bcrypt::decode(stored)?;
+        let tag = super::bcrypt(password, &salt, cost)?;
+        let computed = bcrypt::encode(version, cost, &salt, &tag);
+        Ok(constant_time_eq(computed.as_bytes(), stored.as_bytes()))
+    } else if stored.starts_with("$5$") || stored.starts_with("$6$") {
+        sha_crypt::verify(password, stored)
+    } else {
+        identify(stored).map(|_| false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap settings for every scheme.
+    fn schemes() -> Vec<PasswordScheme> {
+        vec![
+            PasswordScheme::Argon2 {
+                params: Argon2Params {
+                    t_cost: 1,
+                    m_cost: 8,
+                    parallelism: 1,
+                    ..Argon2Params::new(argon2::Argon2Variant::Argon2id)
+                },
+                hash_len: 16,
+            },
+            PasswordScheme::Bcrypt { cost: 4 },
+            PasswordScheme::Scrypt { params: ScryptParams { n: 16, r: 1, p: 1 }, hash_len: 32 },
+            PasswordScheme::ShaCrypt { variant: ShaCryptVariant::Sha256, rounds: 5000 },
+            PasswordScheme::ShaCrypt { variant: ShaCryptVariant::Sha512, rounds: 1000 },
+        ]
+    }
+
+    #[test]
+    fn test_password_roundtrip() {
+        let salt = [7u8; SALT_LEN];
+        for scheme in schemes() {
+            let stored = hash(b"password", &scheme, &salt).unwrap();
+            assert_eq!(identify(&stored).unwrap(), scheme, "{}", stored);
+            assert!(verify(b"password", &stored).unwrap(), "{}", stored);
+            assert!(!verify(b"Password", &stored).unwrap(), "{}", stored);
+        }
+        let stored = hash(b"pw", &schemes()[3], &salt).unwrap();
+        assert!(stored.starts_with("$5$") && !stored.contains("rounds="));
+    }
+
+    #[test]
+    fn test_password_is_weaker_than() {
+        let all = schemes();
+        for (i, scheme) in all.iter().enumerate() {
+            assert!(!scheme.is_weaker_than(scheme));
+            for (j, other) in all.iter().enumerate() {
+                assert_eq!(scheme.is_weaker_than(other), i != j);
+            }
+        }
+        let bcrypt = |cost| PasswordScheme::Bcrypt { cost };
+        assert!(bcrypt(10).is_weaker_than(&bcrypt(12)));
+        assert!(!bcrypt(13).is_weaker_than(&bcrypt(12)));
+        let argon = |t_cost, parallelism| PasswordScheme::Argon2 {
+            params: Argon2Params {
+                t_cost,
+                parallelism,
+                ..Argon2Params::new(argon2::Argon2Variant::Argon2id)
+            },
+            hash_len: 32,
+        };
+        assert!(argon(2, 4).is_weaker_than(&argon(3, 4)));
+        assert!(!argon(3, 1).is_weaker_than(&argon(3, 4)));
+    }
+
+    #[test]
+    fn test_password_rejects_unknown() {
+        for bad in ["", "password", "$1$salt$hash", "$argon2id$", "$scrypt$ln=4", "$6$x"] {
+            assert!(identify(bad).is_err(), "{}", bad);
+            assert!(verify(b"password", bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! PBKDF2 password-based key derivation with HMAC.
+//!
+//! Pure Rust implementation following RFC 8018 (PKCS #5 v2.1), section
+//! 5.2, with HMAC over any [`Digest`] as the pseudorandom function:
+//!
+//! ```text
+//! U1 = HMAC(P, S || INT(i)),  Uj = HMAC(P, U(j-1))
+//! T(i) = U1 ^ U2 ^ ... ^ Uc,  DK = T(1) || T(2) || ...
+//! ```
+//!
+//! The password is keyed into HMAC once: every iteration starts from the
+//! precomputed inner and outer pad states.
+
+use super::{Digest, Hmac, ParameterError};
+
+/// Derives `out.len()` bytes from `password` and `salt` with
+/// PBKDF2-HMAC, running `iterations` rounds per output block.
+///
+/// `hasher` must be in its initial state.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `iterations` is 0, `out` is empty, or
+/// `out` is longer than (2^32 - 1) digests.
+pub fn pbkdf2_hmac<D: Digest>(
+    hasher: D,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    out: &mut [u8],
+) -> Result<(), ParameterError> {
+    let error = |message: String| ParameterError { algorithm: "PBKDF2", message };
+    if iterations == 0 {
+        return Err(error("iterations must be at least 1".to_owned()));
+    }
+    if out.is_empty() {
+        return Err(error("key length must be at least 1".to_owned()));
+    }
+    let hash_len = hasher.digest_size();
+    if out.len().div_ceil(hash_len) > u32::MAX as usize {
+        return Err(error(format!(
+            "key length must be at most (2^32 - 1) * {} bytes",
+            hash_len
+        )));
+    }
+
+    let keyed = Hmac::new(hasher, password);
+    for (index, chunk) in out.chunks_mut(hash_len).enumerate() {
+        let mut first = keyed.clone();
+        first.update(salt);
+        first.update(&(index as u32 + 1).to_be_bytes());
+        let mut u = first.digest();
+        let mut block = u.as_ref().to_vec();
+        for _ in 1..iterations {
+            u = keyed.mac(u.as_ref());
+            for (t, byte) in block.iter_mut().zip(u.as_ref()) {
+                *t ^= byte;
+            }
+        }
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Sha1, Sha256};
+    use crate::utils::to_hex;
+
+    fn derive<D: Digest>(hasher: D, password: &[u8], salt: &[u8], c: u32, len: usize) -> String {
+        let mut out = vec![0u8; len];
+        pbkdf2_hmac(hasher, password, salt, c, &mut out).unwrap();
+        to_hex(&out)
+    }
+
+    #[test]
+    fn test_pbkdf2_sha1_rfc6070() {
+        assert_eq!(
+            derive(Sha1::new(), b"password", b"salt", 1, 20),
+            "0c60c80f961f0e71f3a9b524af6012062fe037a6"
+        );
+        assert_eq!(
+            derive(Sha1::new(), b"password", b"salt", 4096, 20),
+            "4b007901b765489abead49d926f721d065a429c1"
+        );
+        assert_eq!(
+            derive(
+                Sha1::new(),
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                25
+            ),
+            "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"
+        );
+        assert_eq!(
+            derive(Sha1::new(), b"pass\0word", b"sa\0lt", 4096, 16),
+            "56fa6aa75548099dcc37d7f03425e0c3"
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_sha256_rfc6070_inputs() {
+        assert_eq!(
+            derive(Sha256::new(), b"password", b"salt", 2, 32),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+        // 40 bytes span two output blocks
+        assert_eq!(
+            derive(
+                Sha256::new(),
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                40
+            ),
+            "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_rejects_invalid_parameters() {
+        let mut out = [0u8; 32];
+        let err = pbkdf2_hmac(Sha256::new(), b"p", b"s", 0, &mut out).unwrap_err();
+        assert_eq!(err.algorithm, "PBKDF2");
+        assert!(pbkdf2_hmac(Sha256::new(), b"p", b"s", 1, &mut []).is_err());
+    }
+}
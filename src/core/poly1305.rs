@@ -0,0 +1,285 @@
+//! Poly1305 one-time message authenticator.
+//!
+//! Pure Rust implementation following RFC 8439, section 2.5. The 32-byte
+//! key is split into `r`, clamped, and `s`; each 16-byte block of the
+//! message, with a 1 byte appended, is added to an accumulator that is
+//! then multiplied by `r`:
+//!
+//! ```text
+//! h = (h + block) * r  mod 2^130 - 5
+//! tag = (h + s) mod 2^128
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **Key size**: 32 bytes, to be used for a single message
+//! - **Block size**: 16 bytes
+//! - **Tag size**: 16 bytes
+//! - **Arithmetic**: 130-bit numbers as three 64-bit limbs of 44, 44 and
+//!   42 bits, multiplied into 128-bit products
+
+use crate::utils::constant_time_eq;
+
+/// Key size in bytes.
+pub const KEY_SIZE: usize = 32;
+
+/// Block size in bytes.
+pub const BLOCK_SIZE: usize = 16;
+
+/// Tag size in bytes.
+pub const TAG_SIZE: usize = 16;
+
+/// Low 44 bits of a limb.
+const MASK44: u64 = (1 << 44) - 1;
+
+/// Low 42 bits of a limb.
+const MASK42: u64 = (1 << 42) - 1;
+
+/// Poly1305 state.
+///
+/// Deliberately not `Clone`: a key authenticates one message, so
+/// [`finalize`](Self::finalize) and [`verify`](Self::verify) consume the
+/// state.
+pub struct Poly1305 {
+    /// Clamped `r`, in 44/44/42-bit limbs.
+    r: [u64; 3],
+    /// `s`, added to the accumulator at the end.
+    s: [u64; 2],
+    /// Accumulator, in 44/44/42-bit limbs.
+    h: [u64; 3],
+    /// Partial block awaiting more data.
+    buffer: [u8; BLOCK_SIZE],
+    buffered: usize,
+}
+
+/// Splits 16 little-endian bytes into two 64-bit words.
+fn words(bytes: &[u8]) -> (u64, u64) {
+    let lo = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let hi = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+    (lo, hi)
+}
+
+impl Poly1305 {
+    /// Creates a Poly1305 state keyed with `key`, which must never be used
+    /// for another message.
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        let (t0, t1) = words(&key[..16]);
+        // Clamping clears the top 4 bits of every 32-bit word of r and the
+        // low 2 bits of the last three
+        let r = [
+            t0 & 0xffc_0fff_ffff,
+            ((t0 >> 44) | (t1 << 20)) & 0xfff_ffc0_ffff,
+            (t1 >> 24) & 0x00f_ffff_fc0f,
+        ];
+        let (s0, s1) = words(&key[16..]);
+        Poly1305 { r, s: [s0, s1], h: [0; 3], buffer: [0; BLOCK_SIZE], buffered: 0 }
+    }
+
+    /// Adds a 16-byte block, followed by `hibit` at bit 128, to the
+    /// accumulator and multiplies it by `r`.
+    fn block(&mut self, block: &[u8], hibit: u64) {
+        let (t0, t1) = words(block);
+        let [r0, r1, r2] = self.r.map(u128::from);
+        // 2^130 = 5 mod p, so the limbs that wrap around are multiplied by
+        // 5 and shifted by the 2-bit gap between 130 and 3 * 44
+        let (s1, s2) = (r1 * 20, r2 * 20);
+        let h0 = u128::from(self.h[0] + (t0 & MASK44));
+        let h1 = u128::from(self.h[1] + (((t0 >> 44) | (t1 << 20)) & MASK44));
+        let h2 = u128::from(self.h[2] + (((t1 >> 24) & MASK42) | (hibit << 40)));
+
+        let d0 = h0 * r0 + h1 * s2 + h2 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0;
+
+        d1 += d0 >> 44;
+        let mut h0 = d0 as u64 & MASK44;
+        d2 += d1 >> 44;
+        let mut h1 = d1 as u64 & MASK44;
+        let h2 = d2 as u64 & MASK42;
+        h0 += (d2 >> 42) as u64 * 5;
+        h1 += h0 >> 44;
+        h0 &= MASK44;
+        self.h = [h0, h1, h2];
+    }
+
+    /// Feeds message data into the authenticator.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.buffered > 0 {
+            let take = (BLOCK_SIZE - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < BLOCK_SIZE {
+                return;
+            }
+            let block = self.buffer;
+            self.block(&block, 1);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(BLOCK_SIZE);
+        for block in &mut blocks {
+            self.block(block, 1);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// Returns the tag of the message fed so far.
+    pub fn finalize(mut self) -> [u8; TAG_SIZE] {
+        if self.buffered > 0 {
+            // The last partial block gets its 1 byte appended in place of
+            // the bit at 128
+            let mut block = [0u8; BLOCK_SIZE];
+            block[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+            block[self.buffered] = 1;
+            self.block(&block, 0);
+        }
+
+        // Fully carries h, then computes h - p = h + 5 - 2^130 and keeps it
+        // when it does not borrow, without branching
+        let [mut h0, mut h1, mut h2] = self.h;
+        for _ in 0..2 {
+            h2 += h1 >> 44;
+            h1 &= MASK44;
+            h0 += (h2 >> 42) * 5;
+            h2 &= MASK42;
+            h1 += h0 >> 44;
+            h0 &= MASK44;
+        }
+        let mut g0 = h0 + 5;
+        let mut g1 = h1 + (g0 >> 44);
+        g0 &= MASK44;
+        let g2 = (h2 + (g1 >> 44)).wrapping_sub(1 << 42);
+        g1 &= MASK44;
+        let keep_g = (g2 >> 63).wrapping_sub(1);
+        h0 = (h0 & !keep_g) | (g0 & keep_g);
+        h1 = (h1 & !keep_g) | (g1 & keep_g);
+        h2 = (h2 & !keep_g) | (g2 & keep_g);
+
+        // tag = (h + s) mod 2^128
+        let [s0, s1] = self.s;
+        h0 += s0 & MASK44;
+        h1 += (((s0 >> 44) | (s1 << 20)) & MASK44) + (h0 >> 44);
+        h0 &= MASK44;
+        h2 += ((s1 >> 24) & MASK42) + (h1 >> 44);
+        h1 &= MASK44;
+        let lo = h0 | (h1 << 44);
+        let hi = (h1 >> 20) | (h2 << 24);
+
+        let mut tag = [0u8; TAG_SIZE];
+        tag[..8].copy_from_slice(&lo.to_le_bytes());
+        tag[8..].copy_from_slice(&hi.to_le_bytes());
+        tag
+    }
+
+    /// Checks `tag` against the tag of the message fed so far, in time
+    /// independent of where they differ.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        constant_time_eq(&self.finalize(), tag)
+    }
+}
+
+/// Computes the Poly1305 tag of `data` under the one-time `key`.
+pub fn poly1305(key: &[u8; KEY_SIZE], data: &[u8]) -> [u8; TAG_SIZE] {
+    let mut mac = Poly1305::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_hex;
+
+    fn unhex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn key(r: &str, s: &str) -> [u8; KEY_SIZE] {
+        unhex(&format!("{}{}", r, s)).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_rfc8439_example() {
+        // RFC 8439, section 2.5.2
+        let key = key("85d6be7857556d337f4452fe42d506a8", "0103808afb0db2fd4abff6af4149f51b");
+        let tag = poly1305(&key, b"Cryptographic Forum Research Group");
+        assert_eq!(to_hex(&tag), "a8061dc1305136c6c22b8baf0c0127a9");
+    }
+
+    #[test]
+    fn test_rfc8439_edge_cases() {
+        // RFC 8439, appendix A.3, test vectors 5 to 11: carries and
+        // reductions that naive implementations get wrong
+        let zero = "00000000000000000000000000000000";
+        let one = "01000000000000000000000000000000";
+        let two = "02000000000000000000000000000000";
+        let r10 = "01000000000000000400000000000000";
+        let data10 = "e33594d7505e43b900000000000000003394d7505e4379cd0100000000000000\
+                      0000000000000000000000000000000001000000000000000000000000000000";
+        let cases = [
+            (two, zero, "ffffffffffffffffffffffffffffffff", "03000000000000000000000000000000"),
+            (two, "ffffffffffffffffffffffffffffffff", two, "03000000000000000000000000000000"),
+            (
+                one,
+                zero,
+                "fffffffffffffffffffffffffffffffff0ffffffffffffffffffffffffffffff\
+                 11000000000000000000000000000000",
+                "05000000000000000000000000000000",
+            ),
+            (
+                one,
+                zero,
+                "fffffffffffffffffffffffffffffffffbfefefefefefefefefefefefefefefe\
+                 01010101010101010101010101010101",
+                zero,
+            ),
+            (two, zero, "fdffffffffffffffffffffffffffffff", "faffffffffffffffffffffffffffffff"),
+            (r10, zero, data10, "14000000000000005500000000000000"),
+            (r10, zero, &data10[..96], "13000000000000000000000000000000"),
+        ];
+        for (r, s, data, expected) in cases {
+            assert_eq!(to_hex(&poly1305(&key(r, s), &unhex(data))), expected, "r = {}", r);
+        }
+    }
+
+    #[test]
+    fn test_incremental() {
+        // RFC 8439, appendix A.3, test vector 4, fed in every split
+        let key = key("1c9240a5eb55d38af333888604f6b5f0", "473917c1402b80099dca5cbc207075c0");
+        let text = b"'Twas brillig, and the slithy toves\nDid gyre and gimble in the wabe:\n\
+                     All mimsy were the borogoves,\nAnd the mome raths outgrabe.";
+        let expected = "4541669a7eaaee61e708dc7cbcc5eb62";
+        assert_eq!(to_hex(&poly1305(&key, text)), expected);
+        for split in 0..=text.len() {
+            let mut mac = Poly1305::new(&key);
+            mac.update(&text[..split]);
+            mac.update(&text[split..]);
+            assert_eq!(to_hex(&mac.finalize()), expected, "split at {}", split);
+        }
+        let mut mac = Poly1305::new(&key);
+        text.iter().for_each(|byte| mac.update(&[*byte]));
+        assert!(mac.verify(&unhex(expected)));
+    }
+
+    #[test]
+    fn test_verify() {
+        let key = [7u8; KEY_SIZE];
+        let tag = poly1305(&key, b"message");
+        let mac = |data: &[u8]| {
+            let mut mac = Poly1305::new(&key);
+            mac.update(data);
+            mac
+        };
+        assert!(mac(b"message").verify(&tag));
+        assert!(!mac(b"messagE").verify(&tag));
+        assert!(!mac(b"message").verify(&tag[..15]));
+        let mut flipped = tag;
+        flipped[15] ^= 0x80;
+        assert!(!mac(b"message").verify(&flipped));
+    }
+}
@@ -0,0 +1,431 @@
+//! RIPEMD-128, RIPEMD-160, RIPEMD-256 and RIPEMD-320 hash functions.
+//!
+//! Pure Rust implementation following ISO/IEC 10118-3 and the reference
+//! description by Dobbertin, Bosselaers and Preneel. The four variants share
+//! one compression function: each block runs through two parallel lines of
+//! 16-step rounds with the same message word order, shifts and boolean
+//! functions, and only differ in the line width and in how the lines are
+//! combined.
+//!
+//! # Algorithm Details
+//!
+//! | Function   | Line width | Rounds | Lines combined      | Digest size |
+//! |------------|------------|--------|---------------------|-------------|
+//! | RIPEMD-128 | 4 words    | 4      | mixed into one      | 16 bytes    |
+//! | RIPEMD-160 | 5 words    | 5      | mixed into one      | 20 bytes    |
+//! | RIPEMD-256 | 4 words    | 4      | kept, one word swap | 32 bytes    |
+//! | RIPEMD-320 | 5 words    | 5      | kept, one word swap | 40 bytes    |
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Byte order**: little-endian words and length, as in MD5
+//!
+//! # Security
+//!
+//! RIPEMD-256 and RIPEMD-320 only widen the output of RIPEMD-128 and
+//! RIPEMD-160: the lines exchange a word after each round instead of being
+//! mixed, so they offer no more collision resistance than the narrower
+//! variants. RIPEMD-128 has a 64-bit collision bound and should only be used
+//! to check existing data.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Initial chaining values of the left line (the first four are MD5's).
+const IV_LEFT: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Initial chaining values of the right line in the double-width variants.
+const IV_RIGHT: [u32; 5] = [0x76543210, 0xfedcba98, 0x89abcdef, 0x01234567, 0x3c2d1e0f];
+
+/// Left line additive constants, one per round.
+const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// Right line additive constants of the 5-word variants, one per round.
+const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// Right line additive constants of the 4-word variants, one per round.
+const K_RIGHT_128: [u32; 4] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x00000000];
+
+/// Message word selected by each step of the left line.
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+/// Message word selected by each step of the right line.
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// Left rotation amount of each step of the left line.
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+/// Left rotation amount of each step of the right line.
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// Word exchanged between the lines after each round of RIPEMD-256
+/// (A, B, C then D).
+const EXCHANGE_4: [usize; 4] = [0, 1, 2, 3];
+
+/// Word exchanged between the lines after each round of RIPEMD-320
+/// (B, D, A, C then E).
+const EXCHANGE_5: [usize; 5] = [1, 3, 0, 2, 4];
+
+/// Boolean function `index` (f1 to f5 in the specification, from 0).
+fn boolean(index: usize, x: u32, y: u32, z: u32) -> u32 {
+    match index {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// Runs one step on a line of `LINE` words (A, B, C, D and, for the 5-word
+/// lines, E), using boolean function `f`, message word `x`, constant `k` and
+/// rotation `s`.
+fn step<const LINE: usize>(line: &mut [u32; LINE], f: usize, x: u32, k: u32, s: u32) {
+    let mut t = line[0]
+        .wrapping_add(boolean(f, line[1], line[2], line[3]))
+        .wrapping_add(x)
+        .wrapping_add(k)
+        .rotate_left(s);
+    if LINE == 5 {
+        t = t.wrapping_add(line[4]);
+    }
+    // A <- D (or E), B <- T, C <- B, D <- C (rotated by 10 with 5 words), E <- D
+    line.rotate_right(1);
+    line[1] = t;
+    if LINE == 5 {
+        line[3] = line[3].rotate_left(10);
+    }
+}
+
+/// RIPEMD hasher with lines of `LINE` words and an `OUT`-byte digest.
+///
+/// `OUT` is `4 * LINE` for the variants mixing their two lines into one
+/// chaining value, and `8 * LINE` for the double-width variants, which keep
+/// both. Instantiated as [`Ripemd128`], [`Ripemd160`], [`Ripemd256`] and
+/// [`Ripemd320`]. Cloning captures the full streaming state, so a clone can
+/// be finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Ripemd<const LINE: usize, const OUT: usize> {
+    /// Chaining values, `OUT / 4` of which are used.
+    state: [u32; 10],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u64,        // Total bytes processed, modulo 2^64
+}
+
+impl<const LINE: usize, const OUT: usize> Ripemd<LINE, OUT> {
+    /// Whether both lines are kept in the chaining value.
+    const DOUBLE: bool = OUT == 8 * LINE;
+
+    /// Creates a new hasher with the initial chaining values.
+    pub fn new() -> Self {
+        let mut state = [0u32; 10];
+        state[..LINE].copy_from_slice(&IV_LEFT[..LINE]);
+        if Self::DOUBLE {
+            state[LINE..2 * LINE].copy_from_slice(&IV_RIGHT[..LINE]);
+        }
+        Ripemd { state, buffer: [0u8; 64], buffer_len: 0, total_len: 0 }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        // RIPEMD encodes the length modulo 2^64, so there is no length limit
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        let mut offset = 0;
+
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; OUT] {
+        let bit_len = self.total_len.wrapping_mul(8);
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Same padding as MD5: 0x80, zeros, then the little-endian bit length
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+        block[56..64].copy_from_slice(&bit_len.to_le_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; OUT];
+        for (chunk, word) in result.chunks_exact_mut(4).zip(state) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; OUT] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a single 512-bit block through both lines.
+    ///
+    /// The lines run `LINE` rounds of 16 steps; the right line visits the
+    /// boolean functions in reverse order. The double-width variants exchange
+    /// one word between the lines after each round and add each line to its
+    /// own half of the state; the others mix both lines into one state.
+    fn compress(state: &mut [u32; 10], block: &[u8; 64]) {
+        let mut m = [0u32; 16];
+        for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut left: [u32; LINE] = state[..LINE].try_into().unwrap();
+        let right_start = if Self::DOUBLE { LINE } else { 0 };
+        let mut right: [u32; LINE] = state[right_start..right_start + LINE].try_into().unwrap();
+        let exchange: &[usize] = if LINE == 5 { &EXCHANGE_5 } else { &EXCHANGE_4 };
+
+        for round in 0..LINE {
+            let k_right = if LINE == 5 { K_RIGHT[round] } else { K_RIGHT_128[round] };
+            for j in 16 * round..16 * (round + 1) {
+                step(&mut left, round, m[R_LEFT[j]], K_LEFT[round], S_LEFT[j]);
+                step(&mut right, LINE - 1 - round, m[R_RIGHT[j]], k_right, S_RIGHT[j]);
+            }
+            if Self::DOUBLE {
+                let word = exchange[round];
+                std::mem::swap(&mut left[word], &mut right[word]);
+            }
+        }
+
+        if Self::DOUBLE {
+            for i in 0..LINE {
+                state[i] = state[i].wrapping_add(left[i]);
+                state[LINE + i] = state[LINE + i].wrapping_add(right[i]);
+            }
+        } else {
+            let previous = *state;
+            for i in 0..LINE {
+                state[i] = previous[(i + 1) % LINE]
+                    .wrapping_add(left[(i + 2) % LINE])
+                    .wrapping_add(right[(i + 3) % LINE]);
+            }
+        }
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size() -> usize {
+        OUT
+    }
+
+    /// Returns the block size in bytes (64 for every RIPEMD variant).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl<const LINE: usize, const OUT: usize> Default for Ripemd<LINE, OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const LINE: usize, const OUT: usize> Digest for Ripemd<LINE, OUT> {
+    type Output = [u8; OUT];
+
+    /// Never fails: RIPEMD has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; OUT] {
+        Ripemd::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        OUT
+    }
+
+    fn block_size(&self) -> usize {
+        64
+    }
+}
+
+/// RIPEMD-128 hasher.
+pub type Ripemd128 = Ripemd<4, 16>;
+
+/// RIPEMD-160 hasher.
+pub type Ripemd160 = Ripemd<5, 20>;
+
+/// RIPEMD-256 hasher.
+pub type Ripemd256 = Ripemd<4, 32>;
+
+/// RIPEMD-320 hasher.
+pub type Ripemd320 = Ripemd<5, 40>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs of the ISO/IEC 10118-3 test vectors; the last two are eight
+    /// repetitions of "1234567890" and one million "a", built by `input`.
+    const MESSAGES: [&[u8]; 7] = [
+        b"",
+        b"a",
+        b"abc",
+        b"message digest",
+        b"abcdefghijklmnopqrstuvwxyz",
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+    ];
+
+    fn input(index: usize) -> Vec<u8> {
+        match index {
+            7 => b"1234567890".repeat(8),
+            8 => vec![b'a'; 1_000_000],
+            _ => MESSAGES[index].to_vec(),
+        }
+    }
+
+    fn check<const LINE: usize, const OUT: usize>(expected: [&str; 9]) {
+        for (index, expected) in expected.into_iter().enumerate() {
+            let mut hasher = Ripemd::<LINE, OUT>::new();
+            hasher.update(&input(index));
+            assert_eq!(hasher.hexdigest(), expected, "message {}", index);
+        }
+    }
+
+    #[test]
+    fn test_ripemd128_iso_vectors() {
+        check::<4, 16>([
+            "cdf26213a150dc3ecb610f18f6b38b46",
+            "86be7afa339d0fc7cfc785e72f578d33",
+            "c14a12199c66e4ba84636b0f69144c77",
+            "9e327b3d6e523062afc1132d7df9d1b8",
+            "fd2aa607f71dc8f510714922b371834e",
+            "a1aa0689d0fafa2ddc22e88b49133a06",
+            "d1e959eb179c911faea4624c60c5c702",
+            "3f45ef194732c2dbb2c4a2c769795fa3",
+            "4a7f5723f954eba1216c9d8f6320431f",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd160_iso_vectors() {
+        check::<5, 20>([
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe",
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            "5d0689ef49d2fae572b881b123a85ffa21595f36",
+            "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            "b0e20b6e3116640286ed3a87a5713079b21f5189",
+            "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            "52783243c1697bdbe16d37f97f68f08325dc1528",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd256_iso_vectors() {
+        check::<4, 32>([
+            "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d",
+            "f9333e45d857f5d90a91bab70a1eba0cfb1be4b0783c9acfcd883a9134692925",
+            "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+            "87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e",
+            "649d3034751ea216776bf9a18acc81bc7896118a5197968782dd1fd97d8d5133",
+            "3843045583aac6c8c8d9128573e7a9809afb2a0f34ccc36ea9e72f16f6368e3f",
+            "5740a408ac16b720b84424ae931cbb1fe363d1d0bf4017f1a89f7ea6de77a0b8",
+            "06fdcc7a409548aaf91368c06a6275b553e3f099bf0ea4edfd6778df89a890dd",
+            "ac953744e10e31514c150d4d8d7b677342e33399788296e43ae4850ce4f97978",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd320_iso_vectors() {
+        check::<5, 40>([
+            "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8",
+            "ce78850638f92658a5a585097579926dda667a5716562cfcf6fbe77f63542f99b04705d6970dff5d",
+            "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+            "3a8e28502ed45d422f68844f9dd316e7b98533fa3f2a91d29f84d425c88d6b4eff727df66a7c0197",
+            "cabdb1810b92470a2093aa6bce05952c28348cf43ff60841975166bb40ed234004b8824463e6b009",
+            "d034a7950cf722021ba4b84df769a5de2060e259df4c9bb4a4268c0e935bbc7470a969c9d072a1ac",
+            "ed544940c86d67f250d232c30b7b3e5770e0c60c8cb9a4cafe3b11388af9920e1b99230b843c86a4",
+            "557888af5f6d8ed62ab66945c6d2a0a47ecd5341e915eb8fea1d0524955f825dc717e4a008ab2d42",
+            "bdee37f4371e20646b8b0d862dda16292ae36f40965e8c8509e63d1dbddecc503e2b63eb9245bb66",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut whole = Ripemd320::new();
+        whole.update(&data);
+        let mut split = Ripemd320::new();
+        for chunk in data.chunks(37) {
+            split.update(chunk);
+        }
+        assert_eq!(split.digest(), whole.digest());
+        assert_eq!(split.finalize_reset(), whole.digest());
+        assert_eq!(split.digest(), Ripemd320::new().digest());
+    }
+}
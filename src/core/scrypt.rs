@@ -0,0 +1,322 @@
+//! scrypt memory-hard password-based key derivation.
+//!
+//! Pure Rust implementation following RFC 7914. The password and salt are
+//! stretched with PBKDF2-HMAC-SHA-256 into `p` blocks of `128 * r` bytes,
+//! each block is mixed by ROMix, and a final PBKDF2 pass turns the mixed
+//! blocks into the key:
+//!
+//! ```text
+//! B = PBKDF2-HMAC-SHA256(P, S, 1, p * 128 * r)
+//! B[i] = ROMix(r, B[i], N)   for each of the p lanes
+//! DK = PBKDF2-HMAC-SHA256(P, B, 1, dkLen)
+//! ```
+//!
+//! ROMix fills a table of `N` blocks with BlockMix, the Salsa20/8 core
+//! applied in chaining mode, then reads it back in data-dependent order.
+//!
+//! # Algorithm Details
+//!
+//! - **N**: CPU/memory cost, a power of two greater than 1 and below 2^(16 * r)
+//! - **r**: block size factor, each block is `128 * r` bytes
+//! - **p**: parallelization factor, with `r * p < 2^30`
+//! - **Memory**: about `128 * r * (N + p)` bytes, see [`memory_required`]
+//!
+//! Lanes are mixed one after the other, so the table is allocated once
+//! whatever `p` is.
+
+use super::{ParameterError, Sha256, pbkdf2_hmac};
+use crate::utils::{b64_decode, b64_encode, parse_decimal};
+
+/// Number of Salsa20 double rounds in the scrypt core (Salsa20/8).
+const DOUBLE_ROUNDS: usize = 4;
+
+/// Bytes of working memory scrypt needs for the given parameters.
+///
+/// Counts the `p` lanes of `B` plus the ROMix table and its two scratch
+/// blocks, the same total OpenSSL compares against `maxmem`.
+pub fn memory_required(n: u64, r: u32, p: u32) -> u128 {
+    128 * r as u128 * (n as u128 + 2 + p as u128)
+}
+
+/// Derives `out.len()` bytes from `password` and `salt` with scrypt.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `n` is not a power of two greater than 1
+/// or is not below 2^(16 * `r`), if `r` or `p` is 0, if `r * p` is not
+/// below 2^30, if `out` is empty or too long for PBKDF2, or if the ROMix
+/// table cannot be allocated.
+pub fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    n: u64,
+    r: u32,
+    p: u32,
+    out: &mut [u8],
+) -> Result<(), ParameterError> {
+    let error = |message: &str| ParameterError { algorithm: "scrypt", message: message.to_owned() };
+    if n < 2 || !n.is_power_of_two() {
+        return Err(error("n must be a power of 2 greater than 1"));
+    }
+    if r == 0 || p == 0 {
+        return Err(error("r and p must be at least 1"));
+    }
+    if r as u64 * p as u64 >= 1 << 30 {
+        return Err(error("r * p must be less than 2^30"));
+    }
+    if 16 * (r as u64) < 64 && n >= 1 << (16 * r) {
+        return Err(error("n must be less than 2^(16 * r)"));
+    }
+
+    let block_words = 32 * r as usize;
+    let table_words = usize::try_from(n)
+        .ok()
+        .and_then(|n| n.checked_mul(block_words))
+        .ok_or_else(|| error("n * r is too large"))?;
+    let mut table = Vec::new();
+    table
+        .try_reserve_exact(table_words)
+        .map_err(|_| error("not enough memory for the ROMix table"))?;
+    table.resize(table_words, 0u32);
+
+    let mut b = vec![0u8; p as usize * 4 * block_words];
+    pbkdf2_hmac(Sha256::new(), password, salt, 1, &mut b)?;
+    let mut x = vec![0u32; block_words];
+    let mut scratch = vec![0u32; block_words];
+    for lane in b.chunks_exact_mut(4 * block_words) {
+        for (word, bytes) in x.iter_mut().zip(lane.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        ro_mix(&mut x, &mut table, &mut scratch, n);
+        for (bytes, word) in lane.chunks_exact_mut(4).zip(&x) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+    }
+    pbkdf2_hmac(Sha256::new(), password, &b, 1, out)
+}
+
+/// ROMix: mixes the block `x` in place using the `n`-entry `table`.
+fn ro_mix(x: &mut [u32], table: &mut [u32], scratch: &mut [u32], n: u64) {
+    let block_words = x.len();
+    for entry in table.chunks_exact_mut(block_words) {
+        entry.copy_from_slice(x);
+        block_mix(x, scratch);
+    }
+    for _ in 0..n {
+        let j = (integerify(x) & (n - 1)) as usize;
+        let entry = &table[j * block_words..(j + 1) * block_words];
+        for (word, t) in x.iter_mut().zip(entry) {
+            *word ^= t;
+        }
+        block_mix(x, scratch);
+    }
+}
+
+/// Interprets the first 64 bits of the last 64-byte chunk as an integer.
+fn integerify(x: &[u32]) -> u64 {
+    let last = x.len() - 16;
+    x[last] as u64 | (x[last + 1] as u64) << 32
+}
+
+/// BlockMix: chains Salsa20/8 over the 64-byte chunks of `b`, storing the
+/// even outputs in the first half and the odd outputs in the second half.
+fn block_mix(b: &mut [u32], y: &mut [u32]) {
+    let chunks = b.len() / 16;
+    let mut state: [u32; 16] = b[b.len() - 16..].try_into().unwrap();
+    for i in 0..chunks {
+        for (s, word) in state.iter_mut().zip(&b[i * 16..(i + 1) * 16]) {
+            *s ^= word;
+        }
+        salsa20_8(&mut state);
+        let dest = (i / 2 + (i % 2) * (chunks / 2)) * 16;
+        y[dest..dest + 16].copy_from_slice(&state);
+    }
+    b.copy_from_slice(y);
+}
+
+/// The Salsa20/8 core: eight rounds plus the feed-forward addition.
+fn salsa20_8(block: &mut [u32; 16]) {
+    let mut x = *block;
+    macro_rules! quarter {
+        ($a:expr, $b:expr, $c:expr, $d:expr) => {
+            x[$b] ^= x[$a].wrapping_add(x[$d]).rotate_left(7);
+            x[$c] ^= x[$b].wrapping_add(x[$a]).rotate_left(9);
+            x[$d] ^= x[$c].wrapping_add(x[$b]).rotate_left(13);
+            x[$a] ^= x[$d].wrapping_add(x[$c]).rotate_left(18);
+        };
+    }
+    for _ in 0..DOUBLE_ROUNDS {
+        // Columns
+        quarter!(0, 4, 8, 12);
+        quarter!(5, 9, 13, 1);
+        quarter!(10, 14, 2, 6);
+        quarter!(15, 3, 7, 11);
+        // Rows
+        quarter!(0, 1, 2, 3);
+        quarter!(5, 6, 7, 4);
+        quarter!(10, 11, 8, 9);
+        quarter!(15, 12, 13, 14);
+    }
+    for (word, mixed) in block.iter_mut().zip(x) {
+        *word = word.wrapping_add(mixed);
+    }
+}
+
+/// scrypt cost parameters, as stored in a PHC string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// CPU/memory cost, a power of two.
+    pub n: u64,
+    /// Block size factor.
+    pub r: u32,
+    /// Parallelization factor.
+    pub p: u32,
+}
+
+/// Formats a PHC string, `$scrypt$ln=<log2 n>,r=<r>,p=<p>$<salt>$<hash>`,
+/// with salt and hash in unpadded standard Base64, as the RustCrypto and
+/// passlib `$scrypt$` hashes.
+pub fn phc_encode(params: &ScryptParams, salt: &[u8], hash: &[u8]) -> String {
+    format!(
+        "$scrypt$ln={},r={},p={}${}${}",
+        params.n.trailing_zeros(),
+        params.r,
+        params.p,
+        b64_encode(salt),
+        b64_encode(hash)
+    )
+}
+
+/// Parses a `$scrypt$` PHC string into its parameters, salt and hash.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is malformed, `ln` is not
+/// between 1 and 63, `r` or `p` is 0, or the hash is empty.
+pub fn phc_decode(text: &str) -> Result<(ScryptParams, Vec<u8>, Vec<u8>), ParameterError> {
+    let invalid = || ParameterError {
+        algorithm: "scrypt",
+        message: "invalid scrypt PHC string".to_owned(),
+    };
+    let mut fields = text.strip_prefix("$scrypt$").ok_or_else(invalid)?.split('$');
+    let mut costs = fields.next().ok_or_else(invalid)?.split(',');
+    let mut values = [0u32; 3];
+    for (name, value) in ["ln=", "r=", "p="].into_iter().zip(&mut values) {
+        let cost = costs.next().and_then(|c| c.strip_prefix(name)).ok_or_else(invalid)?;
+        *value = parse_decimal(cost).filter(|&v| v > 0).ok_or_else(invalid)?;
+    }
+    let [log_n, r, p] = values;
+    let salt = fields.next().and_then(b64_decode).ok_or_else(invalid)?;
+    let hash = fields.next().and_then(b64_decode).ok_or_else(invalid)?;
+    if log_n > 63 || hash.is_empty() || costs.next().is_some() || fields.next().is_some() {
+        return Err(invalid());
+    }
+    Ok((ScryptParams { n: 1 << log_n, r, p }, salt, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_hex;
+
+    fn derive(password: &[u8], salt: &[u8], n: u64, r: u32, p: u32) -> String {
+        let mut out = [0u8; 64];
+        scrypt(password, salt, n, r, p, &mut out).unwrap();
+        to_hex(&out)
+    }
+
+    #[test]
+    fn test_salsa20_8_rfc7914() {
+        // RFC 7914, section 8
+        let input = "7e879a214f3ec9867ca940e641718f26baee555b8c61c1b50df846116dcd3b1d\
+                     ee24f319df9b3d8514121e4b5ac5aa3276021d2909c74829edebc68db8b8c25e";
+        let bytes: Vec<u8> = (0..64)
+            .map(|i| u8::from_str_radix(&input[2 * i..2 * i + 2], 16).unwrap())
+            .collect();
+        let mut block = [0u32; 16];
+        for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        salsa20_8(&mut block);
+        let output: Vec<u8> = block.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(
+            to_hex(&output),
+            "a41f859c6608cc993b81cacb020cef05044b2181a2fd337dfd7b1c6396682f29\
+             b4393168e3c9e6bcfe6bc5b7a06d96bae424cc102c91745c24ad673dc7618f81"
+        );
+    }
+
+    #[test]
+    fn test_scrypt_rfc7914() {
+        // RFC 7914, section 12, first three vectors
+        assert_eq!(
+            derive(b"", b"", 16, 1, 1),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+        assert_eq!(
+            derive(b"password", b"NaCl", 1024, 8, 16),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+        assert_eq!(
+            derive(b"pleaseletmein", b"SodiumChloride", 16384, 8, 1),
+            "7023bdcb3afd7348461c06cd81fd38ebfda8fbba904f8e3ea9b543f6545da1f2\
+             d5432955613f0fcf62d49705242a9af9e61e85dc0d651e40dfcf017b45575887"
+        );
+    }
+
+    #[test]
+    #[ignore = "needs 1 GiB, run with `cargo test --release -- --ignored`"]
+    fn test_scrypt_rfc7914_large() {
+        assert_eq!(
+            derive(b"pleaseletmein", b"SodiumChloride", 1048576, 8, 1),
+            "2101cb9b6a511aaeaddbbe09cf70f881ec568d574a2ffd4dabe5ee9820adaa47\
+             8e56fd8f4ba5d09ffa1c6d927c40f4c337304049e8a952fbcbf45c6fa77a41a4"
+        );
+    }
+
+    #[test]
+    fn test_scrypt_rejects_invalid_parameters() {
+        let mut out = [0u8; 32];
+        for (n, r, p) in [(0, 1, 1), (1, 1, 1), (24, 1, 1), (16, 0, 1), (16, 1, 0)] {
+            let err = scrypt(b"", b"", n, r, p, &mut out).unwrap_err();
+            assert_eq!(err.algorithm, "scrypt");
+        }
+        assert!(scrypt(b"", b"", 16, 1 << 15, 1 << 15, &mut out).is_err());
+        // r = 1 caps n below 2^16
+        assert!(scrypt(b"", b"", 1 << 16, 1, 1, &mut out).is_err());
+        assert!(scrypt(b"", b"", 16, 1, 1, &mut []).is_err());
+    }
+
+    #[test]
+    fn test_scrypt_phc() {
+        let params = ScryptParams { n: 16, r: 1, p: 1 };
+        let mut hash = [0u8; 32];
+        scrypt(b"password", b"saltsaltsaltsalt", 16, 1, 1, &mut hash).unwrap();
+        let text = phc_encode(&params, b"saltsaltsaltsalt", &hash);
+        assert!(text.starts_with("$scrypt$ln=4,r=1,p=1$c2FsdHNhbHRzYWx0c2FsdA$"));
+        let (decoded, salt, stored) = phc_decode(&text).unwrap();
+        assert_eq!(decoded, params);
+        assert_eq!(salt, b"saltsaltsaltsalt");
+        assert_eq!(stored, hash);
+        for bad in [
+            "$scrypt$ln=4,r=1,p=1$c2FsdA$",
+            "$scrypt$ln=0,r=1,p=1$c2FsdA$aGFzaA",
+            "$scrypt$ln=64,r=1,p=1$c2FsdA$aGFzaA",
+            "$scrypt$ln=4,r=1$c2FsdA$aGFzaA",
+            "$scrypt$ln=4,r=01,p=1$c2FsdA$aGFzaA",
+            "$scrypt$ln=4,r=1,p=1$c2FsdA==$aGFzaA",
+            "$scrypt$ln=4,r=1,p=1$c2FsdA$aGFzaA$",
+            "$argon2id$ln=4,r=1,p=1$c2FsdA$aGFzaA",
+        ] {
+            assert!(phc_decode(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_scrypt_memory_required() {
+        assert_eq!(memory_required(16384, 8, 1), 1024 * 16387);
+        assert!(memory_required(1 << 20, 8, 1) > 32 * 1024 * 1024);
+    }
+}
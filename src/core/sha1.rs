@@ -0,0 +1,272 @@
+//! SHA-1 cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following FIPS 180-4 specification.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 160 bits (20 bytes)
+//! - **Rounds**: 80
+//!
+//! # Security
+//!
+//! SHA-1 is broken for collision resistance (SHAttered, 2017) and must not
+//! be used for new signatures. It is provided for legacy protocols such as
+//! git object IDs, HMAC-SHA1 and TOTP.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// SHA-1 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,      // Number of bytes currently in buffer
+    total_len: u64,         // Total bytes processed (for final length)
+}
+
+impl Sha1 {
+    /// Maximum message length in bytes.
+    ///
+    /// The padding encodes the length in bits as a 64-bit integer, so
+    /// messages are limited to 2^61 - 1 bytes.
+    pub const MAX_MESSAGE_LEN: u64 = (1 << 61) - 1;
+
+    /// SHA-1 initial hash value.
+    const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    /// Creates a new SHA-1 hasher with initial state.
+    pub fn new() -> Self {
+        Sha1 {
+            state: Self::IV,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The length check happens before any data is absorbed, so the hasher
+    /// is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.total_len = self
+            .total_len
+            .checked_add(data.len() as u64)
+            .filter(|&len| len <= Self::MAX_MESSAGE_LEN)
+            .ok_or(LengthOverflowError { algorithm: "SHA-1" })?;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 20] {
+        let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Copy pending bytes and add padding: 0x80 byte followed by zeros
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+
+        // If not enough space for length (need 8 bytes), process an extra block
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+
+        block[56..64].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 20];
+        for (i, &word) in state.iter().enumerate() {
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_be_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 20] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Processes a single 512-bit block through the SHA-1 compression function.
+    fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+        let mut w = [0u32; 80];
+        for (i, chunk) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = *state;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+    }
+
+    /// Returns the output size in bytes (20 for SHA-1).
+    pub fn digest_size() -> usize {
+        20
+    }
+
+    /// Returns the block size in bytes (64 for SHA-1).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha1 {
+    type Output = [u8; 20];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha1::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 20] {
+        Sha1::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha1::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sha1::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha1_empty() {
+        assert_eq!(Sha1::new().hexdigest(), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    }
+
+    #[test]
+    fn test_sha1_abc() {
+        let mut hasher = Sha1::new();
+        hasher.update(b"abc");
+        assert_eq!(hasher.hexdigest(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn test_sha1_two_blocks() {
+        let mut hasher = Sha1::new();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(hasher.hexdigest(), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+    }
+
+    #[test]
+    fn test_sha1_million_a() {
+        let mut hasher = Sha1::new();
+        let chunk = [b'a'; 1000];
+        for _ in 0..1000 {
+            hasher.update(&chunk);
+        }
+        assert_eq!(hasher.hexdigest(), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn test_sha1_clone_mid_stream() {
+        let mut hasher = Sha1::new();
+        hasher.update(b"ab");
+        let mut clone = hasher.clone();
+        clone.update(b"c");
+        assert_eq!(clone.hexdigest(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+}
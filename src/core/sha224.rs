@@ -0,0 +1,166 @@
+//! SHA-224 cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following FIPS 180-4 specification.
+//! SHA-224 runs the SHA-256 engine from a different initial hash value
+//! and truncates the output.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 224 bits (28 bytes)
+//! - **Rounds**: 64
+
+use super::{Digest, LengthOverflowError, Sha256};
+use crate::utils::to_hex;
+
+/// SHA-224 hasher state.
+///
+/// Wraps a [`Sha256`] engine, so it shares its streaming behavior.
+#[derive(Clone)]
+pub struct Sha224 {
+    inner: Sha256,
+}
+
+impl Sha224 {
+    /// Maximum message length in bytes, same as SHA-256.
+    pub const MAX_MESSAGE_LEN: u64 = Sha256::MAX_MESSAGE_LEN;
+
+    /// SHA-224 initial hash value (second 32 bits of the fractional parts of
+    /// the square roots of the 9th through 16th primes).
+    const IV: [u32; 8] = [
+        0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939,
+        0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+    ];
+
+    /// Creates a new SHA-224 hasher with initial state.
+    pub fn new() -> Self {
+        Sha224 { inner: Sha256::with_iv(Self::IV) }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The hasher is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.inner
+            .try_update(data)
+            .map_err(|_| LengthOverflowError { algorithm: "SHA-224" })
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 28] {
+        let full = self.inner.digest();
+        let mut result = [0u8; 28];
+        result.copy_from_slice(&full[..28]);
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 28] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (28 for SHA-224).
+    pub fn digest_size() -> usize {
+        28
+    }
+
+    /// Returns the block size in bytes (64 for SHA-224).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Sha224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha224 {
+    type Output = [u8; 28];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha224::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 28] {
+        Sha224::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha224::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sha224::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha224_empty() {
+        assert_eq!(
+            Sha224::new().hexdigest(),
+            "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+        );
+    }
+
+    #[test]
+    fn test_sha224_abc() {
+        let mut hasher = Sha224::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+    }
+
+    #[test]
+    fn test_sha224_two_blocks() {
+        let mut hasher = Sha224::new();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            hasher.hexdigest(),
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+        );
+    }
+
+    #[test]
+    fn test_sha224_finalize_reset_reuse() {
+        let mut hasher = Sha224::new();
+        hasher.update(b"first message");
+        hasher.finalize_reset();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+    }
+}
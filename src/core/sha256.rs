@@ -13,10 +13,16 @@
 //! SHA-256 is considered cryptographically secure as of 2025.
 //! No practical collision attacks are known.
 
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
 /// SHA-256 hasher state.
 ///
 /// Maintains the internal state for incremental hashing.
 /// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
 pub struct Sha256 {
     state: [u32; 8],
     buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
@@ -25,6 +31,12 @@ pub struct Sha256 {
 }
 
 impl Sha256 {
+    /// Maximum message length in bytes.
+    ///
+    /// The padding encodes the length in bits as a 64-bit integer, so
+    /// messages are limited to 2^61 - 1 bytes.
+    pub const MAX_MESSAGE_LEN: u64 = (1 << 61) - 1;
+
     /// SHA-256 round constants (first 32 bits of fractional parts of cube roots of first 64 primes).
     const K: [u32; 64] = [
         0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
@@ -37,13 +49,22 @@ impl Sha256 {
         0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
     ];
 
+    /// SHA-256 initial hash value.
+    const IV: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+        0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
     /// Creates a new SHA-256 hasher with initial state.
     pub fn new() -> Self {
+        Self::with_iv(Self::IV)
+    }
+
+    /// Creates a hasher running the SHA-256 engine from a different initial
+    /// hash value, as used by SHA-224.
+    pub(crate) fn with_iv(iv: [u32; 8]) -> Self {
         Sha256 {
-            state: [
-                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
-                0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
-            ],
+            state: iv,
             buffer: [0u8; 64],
             buffer_len: 0,
             total_len: 0,
@@ -54,8 +75,25 @@ impl Sha256 {
     ///
     /// Processes complete 512-bit blocks immediately with zero-copy streaming.
     /// Only incomplete blocks (< 64 bytes) are buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
     pub fn update(&mut self, data: &[u8]) {
-        self.total_len += data.len() as u64;
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The length check happens before any data is absorbed, so the hasher
+    /// is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.total_len = self
+            .total_len
+            .checked_add(data.len() as u64)
+            .filter(|&len| len <= Self::MAX_MESSAGE_LEN)
+            .ok_or(LengthOverflowError { algorithm: "SHA-256" })?;
         let mut offset = 0;
         
         // If buffer has partial data, try to complete it first
@@ -71,8 +109,7 @@ impl Sha256 {
             
             // If buffer is now full, process it immediately
             if self.buffer_len == 64 {
-                let block_copy = self.buffer;
-                self.process_block(&block_copy);
+                Self::compress(&mut self.state, &self.buffer);
                 self.buffer_len = 0;
             }
         }
@@ -80,7 +117,7 @@ impl Sha256 {
         // Process complete 64-byte blocks directly from input (zero-copy!)
         while offset + 64 <= data.len() {
             let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
-            self.process_block(&block);
+            Self::compress(&mut self.state, &block);
             offset += 64;
         }
         
@@ -90,55 +127,76 @@ impl Sha256 {
             self.buffer[..remaining].copy_from_slice(&data[offset..]);
             self.buffer_len = remaining;
         }
+        
+        Ok(())
     }
 
-    /// Finalizes the hash and returns the digest as bytes.
+    /// Returns the digest of the data fed so far.
     ///
     /// Applies padding, processes remaining blocks, and outputs the final 256-bit digest.
-    pub fn finalize(&mut self) -> [u8; 32] {
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 32] {
         let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 64];
         
-        // Add padding: 0x80 byte followed by zeros
-        self.buffer[self.buffer_len] = 0x80;
-        self.buffer_len += 1;
+        // Copy pending bytes and add padding: 0x80 byte followed by zeros
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
         
-        // If not enough space for length (need 8 bytes), pad and process block
-        if self.buffer_len > 56 {
-            // Fill rest with zeros and process
-            self.buffer[self.buffer_len..].fill(0);
-            let block_copy = self.buffer;
-            self.process_block(&block_copy);
-            self.buffer.fill(0);
-            self.buffer_len = 0;
+        // If not enough space for length (need 8 bytes), process an extra block
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
         }
         
-        // Pad with zeros until 56 bytes
-        self.buffer[self.buffer_len..56].fill(0);
-        
-        // Append length as big-endian 64-bit integer
-        self.buffer[56..64].copy_from_slice(&bit_len.to_be_bytes());
-        
-        // Process final block
-        let block_copy = self.buffer;
-        self.process_block(&block_copy);
+        // Append length as big-endian 64-bit integer and process final block
+        block[56..64].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
         
         // Extract result from state
         let mut result = [0u8; 32];
-        for (i, &word) in self.state.iter().enumerate() {
+        for (i, &word) in state.iter().enumerate() {
             result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_be_bytes());
         }
         
         result
     }
 
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    ///
+    /// Allows reusing one hasher for several messages.
+    pub fn finalize_reset(&mut self) -> [u8; 32] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Finalizes the hash and returns the digest as bytes.
+    #[deprecated(since = "0.1.0", note = "use `digest()` or `finalize_reset()` instead")]
+    pub fn finalize(&mut self) -> [u8; 32] {
+        self.digest()
+    }
+
     /// Returns the digest as a hexadecimal string.
+    #[deprecated(since = "0.1.0", note = "use `hexdigest()` instead")]
     pub fn finalize_hex(&mut self) -> String {
-        let digest = self.finalize();
-        hex::encode(digest)
+        self.hexdigest()
     }
 
     /// Processes a single 512-bit block through the SHA-256 compression function.
-    fn process_block(&mut self, block: &[u8; 64]) {
+    fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
         let mut w = [0u32; 64];
         
         for i in 0..16 {
@@ -156,19 +214,19 @@ impl Sha256 {
             w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
         }
         
-        let mut a = self.state[0];
-        let mut b = self.state[1];
-        let mut c = self.state[2];
-        let mut d = self.state[3];
-        let mut e = self.state[4];
-        let mut f = self.state[5];
-        let mut g = self.state[6];
-        let mut h = self.state[7];
+        let mut a = state[0];
+        let mut b = state[1];
+        let mut c = state[2];
+        let mut d = state[3];
+        let mut e = state[4];
+        let mut f = state[5];
+        let mut g = state[6];
+        let mut h = state[7];
         
-        for i in 0..64 {
+        for (&k, &wi) in Self::K.iter().zip(w.iter()) {
             let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
             let ch = (e & f) ^ ((!e) & g);
-            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(Self::K[i]).wrapping_add(w[i]);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(k).wrapping_add(wi);
             let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
             let maj = (a & b) ^ (a & c) ^ (b & c);
             let temp2 = s0.wrapping_add(maj);
@@ -183,14 +241,14 @@ impl Sha256 {
             a = temp1.wrapping_add(temp2);
         }
         
-        self.state[0] = self.state[0].wrapping_add(a);
-        self.state[1] = self.state[1].wrapping_add(b);
-        self.state[2] = self.state[2].wrapping_add(c);
-        self.state[3] = self.state[3].wrapping_add(d);
-        self.state[4] = self.state[4].wrapping_add(e);
-        self.state[5] = self.state[5].wrapping_add(f);
-        self.state[6] = self.state[6].wrapping_add(g);
-        self.state[7] = self.state[7].wrapping_add(h);
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+        state[5] = state[5].wrapping_add(f);
+        state[6] = state[6].wrapping_add(g);
+        state[7] = state[7].wrapping_add(h);
     }
 
     /// Returns the output size in bytes (32 for SHA-256).
@@ -204,10 +262,29 @@ impl Sha256 {
     }
 }
 
-mod hex {
-    /// Encodes bytes as lowercase hexadecimal string.
-    pub fn encode(bytes: [u8; 32]) -> String {
-        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha256 {
+    type Output = [u8; 32];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha256::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        Sha256::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha256::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sha256::block_size()
     }
 }
 
@@ -219,7 +296,7 @@ mod tests {
     fn test_sha256_empty() {
         let mut hasher = Sha256::new();
         hasher.update(b"");
-        let result = hasher.finalize_hex();
+        let result = hasher.hexdigest();
         assert_eq!(
             result,
             "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
@@ -230,10 +307,95 @@ mod tests {
     fn test_sha256_abc() {
         let mut hasher = Sha256::new();
         hasher.update(b"abc");
-        let result = hasher.finalize_hex();
+        let result = hasher.hexdigest();
         assert_eq!(
             result,
             "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
         );
     }
+
+    #[test]
+    fn test_sha256_clone_mid_stream() {
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        let mut expected = Sha256::new();
+        expected.update(&data);
+        let expected = expected.digest();
+
+        for split in [0, 1, 63, 64, 65, 200, 300] {
+            let mut prefix = Sha256::new();
+            prefix.update(&data[..split]);
+            let mut branch = prefix.clone();
+            branch.update(&data[split..]);
+            assert_eq!(branch.digest(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_sha256_update_after_finalize() {
+        let data: Vec<u8> = (0..500u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Sha256::new();
+        let mut reference = Sha256::new();
+        let mut fed = 0;
+
+        for end in [0, 3, 55, 56, 64, 129, 500] {
+            hasher.update(&data[fed..end]);
+            fed = end;
+            let checkpoint = hasher.digest();
+
+            reference = Sha256::new();
+            reference.update(&data[..end]);
+            assert_eq!(checkpoint, reference.digest(), "checkpoint at {}", end);
+        }
+        assert_eq!(hasher.digest(), reference.digest());
+    }
+
+    #[test]
+    fn test_sha256_length_limit() {
+        let mut hasher = Sha256::new();
+        hasher.total_len = Sha256::MAX_MESSAGE_LEN - 3;
+        assert!(hasher.try_update(b"abc").is_ok());
+        assert_eq!(hasher.total_len, Sha256::MAX_MESSAGE_LEN);
+
+        // One more byte is rejected and leaves the state untouched
+        let before = hasher.clone();
+        let err = hasher.try_update(b"d").unwrap_err();
+        assert_eq!(err.algorithm, "SHA-256");
+        assert_eq!(hasher.total_len, before.total_len);
+        assert_eq!(hasher.buffer_len, before.buffer_len);
+        assert_eq!(hasher.digest(), before.digest());
+        assert!(hasher.try_update(b"").is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "message length limit exceeded")]
+    fn test_sha256_update_panics_past_limit() {
+        let mut hasher = Sha256::new();
+        hasher.total_len = Sha256::MAX_MESSAGE_LEN;
+        hasher.update(b"x");
+    }
+
+    #[test]
+    fn test_sha256_finalize_reset_reuse() {
+        let data: Vec<u8> = (0..400u32).map(|i| (i * 13) as u8).collect();
+        let mut hasher = Sha256::new();
+
+        for len in [0, 1, 63, 64, 65, 135, 400] {
+            hasher.update(&data[..len / 2]);
+            hasher.update(&data[len / 2..len]);
+            let mut fresh = Sha256::new();
+            fresh.update(&data[..len]);
+            assert_eq!(hasher.finalize_reset(), fresh.digest(), "length {}", len);
+        }
+        assert_eq!(hasher.digest(), Sha256::new().digest());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_sha256_deprecated_finalize() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"abc");
+        let expected = hasher.digest();
+        assert_eq!(hasher.finalize(), expected);
+        assert_eq!(hasher.finalize_hex(), hasher.hexdigest());
+    }
 }
@@ -0,0 +1,106 @@
+//! SHA-3 cryptographic hash functions.
+//!
+//! Pure Rust implementation following FIPS 202. All four fixed-length
+//! SHA-3 functions share the Keccak sponge from [`super::keccak`]; they only
+//! differ in rate and output length (the capacity is twice the output).
+//!
+//! # Algorithm Details
+//!
+//! | Function | Rate (block size) | Digest size |
+//! |----------|-------------------|-------------|
+//! | SHA3-224 | 144 bytes         | 28 bytes    |
+//! | SHA3-256 | 136 bytes         | 32 bytes    |
+//! | SHA3-384 | 104 bytes         | 48 bytes    |
+//! | SHA3-512 | 72 bytes          | 64 bytes    |
+
+use super::keccak::KeccakDigest;
+
+/// Domain separation byte of the SHA-3 functions (`01` suffix plus padding).
+const SHA3_DOMAIN: u8 = 0x06;
+
+/// SHA3-224 hasher.
+pub type Sha3_224 = KeccakDigest<144, 28, SHA3_DOMAIN>;
+
+/// SHA3-256 hasher.
+pub type Sha3_256 = KeccakDigest<136, 32, SHA3_DOMAIN>;
+
+/// SHA3-384 hasher.
+pub type Sha3_384 = KeccakDigest<104, 48, SHA3_DOMAIN>;
+
+/// SHA3-512 hasher.
+pub type Sha3_512 = KeccakDigest<72, 64, SHA3_DOMAIN>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha3_empty() {
+        assert_eq!(
+            Sha3_224::new().hexdigest(),
+            "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"
+        );
+        assert_eq!(
+            Sha3_256::new().hexdigest(),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            Sha3_384::new().hexdigest(),
+            "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2a\
+             c3713831264adb47fb6bd1e058d5f004"
+        );
+        assert_eq!(
+            Sha3_512::new().hexdigest(),
+            "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6\
+             15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"
+        );
+    }
+
+    #[test]
+    fn test_sha3_abc() {
+        let mut h224 = Sha3_224::new();
+        h224.update(b"abc");
+        assert_eq!(h224.hexdigest(), "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf");
+
+        let mut h256 = Sha3_256::new();
+        h256.update(b"abc");
+        assert_eq!(
+            h256.hexdigest(),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+
+        let mut h384 = Sha3_384::new();
+        h384.update(b"abc");
+        assert_eq!(
+            h384.hexdigest(),
+            "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2\
+             98d88cea927ac7f539f1edf228376d25"
+        );
+
+        let mut h512 = Sha3_512::new();
+        h512.update(b"abc");
+        assert_eq!(
+            h512.hexdigest(),
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e\
+             10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+        );
+    }
+
+    #[test]
+    fn test_sha3_256_two_blocks() {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            hasher.hexdigest(),
+            "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376"
+        );
+    }
+
+    #[test]
+    fn test_sha3_sizes() {
+        assert_eq!((Sha3_224::digest_size(), Sha3_224::block_size()), (28, 144));
+        assert_eq!((Sha3_256::digest_size(), Sha3_256::block_size()), (32, 136));
+        assert_eq!((Sha3_384::digest_size(), Sha3_384::block_size()), (48, 104));
+        assert_eq!((Sha3_512::digest_size(), Sha3_512::block_size()), (64, 72));
+    }
+}
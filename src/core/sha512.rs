@@ -13,10 +13,16 @@
 //! SHA-512 provides higher security margin than SHA-256 due to
 //! its larger internal state and digest size.
 
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
 /// SHA-512 hasher state.
 ///
 /// Maintains the internal state for incremental hashing.
 /// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
 pub struct Sha512 {
     state: [u64; 8],
     buffer: [u8; 128],     // Fixed 128-byte buffer (1 block)
@@ -25,6 +31,12 @@ pub struct Sha512 {
 }
 
 impl Sha512 {
+    /// Maximum message length in bytes.
+    ///
+    /// The padding encodes the length in bits as a 128-bit integer, so
+    /// messages are limited to 2^125 - 1 bytes.
+    pub const MAX_MESSAGE_LEN: u128 = (1 << 125) - 1;
+
     /// SHA-512 round constants (first 64 bits of fractional parts of cube roots of first 80 primes).
     const K: [u64; 80] = [
         0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
@@ -49,13 +61,22 @@ impl Sha512 {
         0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
     ];
 
+    /// SHA-512 initial hash value.
+    pub(crate) const IV: [u64; 8] = [
+        0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+    ];
+
     /// Creates a new SHA-512 hasher with initial state.
     pub fn new() -> Self {
+        Self::with_iv(Self::IV)
+    }
+
+    /// Creates a hasher running the SHA-512 engine from a different initial
+    /// hash value, as used by the truncated SHA-512/t variants.
+    pub(crate) fn with_iv(iv: [u64; 8]) -> Self {
         Sha512 {
-            state: [
-                0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
-                0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
-            ],
+            state: iv,
             buffer: [0u8; 128],
             buffer_len: 0,
             total_len: 0,
@@ -66,8 +87,25 @@ impl Sha512 {
     ///
     /// Processes complete 1024-bit blocks immediately with zero-copy streaming.
     /// Only incomplete blocks (< 128 bytes) are buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
     pub fn update(&mut self, data: &[u8]) {
-        self.total_len += data.len() as u128;
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The length check happens before any data is absorbed, so the hasher
+    /// is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.total_len = self
+            .total_len
+            .checked_add(data.len() as u128)
+            .filter(|&len| len <= Self::MAX_MESSAGE_LEN)
+            .ok_or(LengthOverflowError { algorithm: "SHA-512" })?;
         let mut offset = 0;
         
         // If buffer has partial data, try to complete it first
@@ -83,8 +121,7 @@ impl Sha512 {
             
             // If buffer is now full, process it immediately
             if self.buffer_len == 128 {
-                let block_copy = self.buffer;
-                self.process_block(&block_copy);
+                Self::compress(&mut self.state, &self.buffer);
                 self.buffer_len = 0;
             }
         }
@@ -92,7 +129,7 @@ impl Sha512 {
         // Process complete 128-byte blocks directly from input (zero-copy!)
         while offset + 128 <= data.len() {
             let block: [u8; 128] = data[offset..offset + 128].try_into().unwrap();
-            self.process_block(&block);
+            Self::compress(&mut self.state, &block);
             offset += 128;
         }
         
@@ -102,55 +139,76 @@ impl Sha512 {
             self.buffer[..remaining].copy_from_slice(&data[offset..]);
             self.buffer_len = remaining;
         }
+        
+        Ok(())
     }
 
-    /// Finalizes the hash and returns the digest as bytes.
+    /// Returns the digest of the data fed so far.
     ///
     /// Applies padding, processes remaining blocks, and outputs the final 512-bit digest.
-    pub fn finalize(&mut self) -> [u8; 64] {
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 64] {
         let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 128];
         
-        // Add padding: 0x80 byte followed by zeros
-        self.buffer[self.buffer_len] = 0x80;
-        self.buffer_len += 1;
+        // Copy pending bytes and add padding: 0x80 byte followed by zeros
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
         
-        // If not enough space for length (need 16 bytes), pad and process block
-        if self.buffer_len > 112 {
-            // Fill rest with zeros and process
-            self.buffer[self.buffer_len..].fill(0);
-            let block_copy = self.buffer;
-            self.process_block(&block_copy);
-            self.buffer.fill(0);
-            self.buffer_len = 0;
+        // If not enough space for length (need 16 bytes), process an extra block
+        if self.buffer_len >= 112 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 128];
         }
         
-        // Pad with zeros until 112 bytes
-        self.buffer[self.buffer_len..112].fill(0);
-        
-        // Append length as big-endian 128-bit integer
-        self.buffer[112..128].copy_from_slice(&bit_len.to_be_bytes());
-        
-        // Process final block
-        let block_copy = self.buffer;
-        self.process_block(&block_copy);
+        // Append length as big-endian 128-bit integer and process final block
+        block[112..128].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
         
         // Extract result from state
         let mut result = [0u8; 64];
-        for (i, &word) in self.state.iter().enumerate() {
+        for (i, &word) in state.iter().enumerate() {
             result[i * 8..(i + 1) * 8].copy_from_slice(&word.to_be_bytes());
         }
         
         result
     }
 
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    ///
+    /// Allows reusing one hasher for several messages.
+    pub fn finalize_reset(&mut self) -> [u8; 64] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Finalizes the hash and returns the digest as bytes.
+    #[deprecated(since = "0.1.0", note = "use `digest()` or `finalize_reset()` instead")]
+    pub fn finalize(&mut self) -> [u8; 64] {
+        self.digest()
+    }
+
     /// Returns the digest as a hexadecimal string.
+    #[deprecated(since = "0.1.0", note = "use `hexdigest()` instead")]
     pub fn finalize_hex(&mut self) -> String {
-        let digest = self.finalize();
-        hex::encode(digest)
+        self.hexdigest()
     }
 
     /// Processes a single 1024-bit block through the SHA-512 compression function.
-    fn process_block(&mut self, block: &[u8; 128]) {
+    fn compress(state: &mut [u64; 8], block: &[u8; 128]) {
         let mut w = [0u64; 80];
         
         for i in 0..16 {
@@ -172,19 +230,19 @@ impl Sha512 {
             w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
         }
         
-        let mut a = self.state[0];
-        let mut b = self.state[1];
-        let mut c = self.state[2];
-        let mut d = self.state[3];
-        let mut e = self.state[4];
-        let mut f = self.state[5];
-        let mut g = self.state[6];
-        let mut h = self.state[7];
+        let mut a = state[0];
+        let mut b = state[1];
+        let mut c = state[2];
+        let mut d = state[3];
+        let mut e = state[4];
+        let mut f = state[5];
+        let mut g = state[6];
+        let mut h = state[7];
         
-        for i in 0..80 {
+        for (&k, &wi) in Self::K.iter().zip(w.iter()) {
             let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
             let ch = (e & f) ^ ((!e) & g);
-            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(Self::K[i]).wrapping_add(w[i]);
+            let temp1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(k).wrapping_add(wi);
             let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
             let maj = (a & b) ^ (a & c) ^ (b & c);
             let temp2 = s0.wrapping_add(maj);
@@ -199,14 +257,14 @@ impl Sha512 {
             a = temp1.wrapping_add(temp2);
         }
         
-        self.state[0] = self.state[0].wrapping_add(a);
-        self.state[1] = self.state[1].wrapping_add(b);
-        self.state[2] = self.state[2].wrapping_add(c);
-        self.state[3] = self.state[3].wrapping_add(d);
-        self.state[4] = self.state[4].wrapping_add(e);
-        self.state[5] = self.state[5].wrapping_add(f);
-        self.state[6] = self.state[6].wrapping_add(g);
-        self.state[7] = self.state[7].wrapping_add(h);
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+        state[5] = state[5].wrapping_add(f);
+        state[6] = state[6].wrapping_add(g);
+        state[7] = state[7].wrapping_add(h);
     }
 
     /// Returns the output size in bytes (64 for SHA-512).
@@ -220,22 +278,64 @@ impl Sha512 {
     }
 }
 
-mod hex {
-    /// Encodes bytes as lowercase hexadecimal string.
-    pub fn encode(bytes: [u8; 64]) -> String {
-        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha512 {
+    type Output = [u8; 64];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha512::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 64] {
+        Sha512::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha512::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sha512::block_size()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// Counts heap allocations made by the current thread.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
 
     #[test]
     fn test_sha512_empty() {
         let mut hasher = Sha512::new();
         hasher.update(b"");
-        let result = hasher.finalize_hex();
+        let result = hasher.hexdigest();
         assert_eq!(
             result,
             "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
@@ -246,10 +346,130 @@ mod tests {
     fn test_sha512_abc() {
         let mut hasher = Sha512::new();
         hasher.update(b"abc");
-        let result = hasher.finalize_hex();
+        let result = hasher.hexdigest();
         assert_eq!(
             result,
             "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
         );
     }
+
+    #[test]
+    fn test_sha512_clone_mid_stream() {
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        let mut expected = Sha512::new();
+        expected.update(&data);
+        let expected = expected.digest();
+
+        for split in [0, 1, 127, 128, 129, 200, 300] {
+            let mut prefix = Sha512::new();
+            prefix.update(&data[..split]);
+            let mut branch = prefix.clone();
+            branch.update(&data[split..]);
+            assert_eq!(branch.digest(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_sha512_update_after_finalize() {
+        let data: Vec<u8> = (0..500u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Sha512::new();
+        let mut reference = Sha512::new();
+        let mut fed = 0;
+
+        for end in [0, 3, 119, 120, 128, 257, 500] {
+            hasher.update(&data[fed..end]);
+            fed = end;
+            let checkpoint = hasher.digest();
+
+            reference = Sha512::new();
+            reference.update(&data[..end]);
+            assert_eq!(checkpoint, reference.digest(), "checkpoint at {}", end);
+        }
+        assert_eq!(hasher.digest(), reference.digest());
+    }
+
+    #[test]
+    fn test_sha512_length_limit() {
+        let mut hasher = Sha512::new();
+        hasher.total_len = Sha512::MAX_MESSAGE_LEN - 3;
+        assert!(hasher.try_update(b"abc").is_ok());
+        assert_eq!(hasher.total_len, Sha512::MAX_MESSAGE_LEN);
+
+        // One more byte is rejected and leaves the state untouched
+        let before = hasher.clone();
+        let err = hasher.try_update(b"d").unwrap_err();
+        assert_eq!(err.algorithm, "SHA-512");
+        assert_eq!(hasher.total_len, before.total_len);
+        assert_eq!(hasher.buffer_len, before.buffer_len);
+        assert_eq!(hasher.digest(), before.digest());
+        assert!(hasher.try_update(b"").is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "message length limit exceeded")]
+    fn test_sha512_update_panics_past_limit() {
+        let mut hasher = Sha512::new();
+        hasher.total_len = Sha512::MAX_MESSAGE_LEN;
+        hasher.update(b"x");
+    }
+
+    #[test]
+    fn test_sha512_finalize_reset_reuse() {
+        let data: Vec<u8> = (0..400u32).map(|i| (i * 13) as u8).collect();
+        let mut hasher = Sha512::new();
+
+        for len in [0, 1, 127, 128, 129, 263, 400] {
+            hasher.update(&data[..len / 2]);
+            hasher.update(&data[len / 2..len]);
+            let mut fresh = Sha512::new();
+            fresh.update(&data[..len]);
+            assert_eq!(hasher.finalize_reset(), fresh.digest(), "length {}", len);
+        }
+        assert_eq!(hasher.digest(), Sha512::new().digest());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_sha512_deprecated_finalize() {
+        let mut hasher = Sha512::new();
+        hasher.update(b"abc");
+        let expected = hasher.digest();
+        assert_eq!(hasher.finalize(), expected);
+        assert_eq!(hasher.finalize_hex(), hasher.hexdigest());
+    }
+
+    #[test]
+    fn test_sha512_update_does_not_allocate() {
+        let data = vec![0x5au8; 1 << 20];
+        let mut hasher = Sha512::new();
+
+        let before = ALLOCATIONS.with(Cell::get);
+        for chunk in data.chunks(1000) {
+            hasher.update(chunk);
+        }
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(ALLOCATIONS.with(Cell::get), before);
+        assert_ne!(digest, [0u8; 64]);
+    }
+
+    #[test]
+    fn test_sha512_padding_boundaries() {
+        // Lengths around the 112-byte mark where the 16-byte length field
+        // no longer fits and padding spills into a second block
+        let cases = [
+            (0, "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"),
+            (111, "fa9121c7b32b9e01733d034cfc78cbf67f926c7ed83e82200ef86818196921760b4beff48404df811b953828274461673c68d04e297b0eb7b2b4d60fc6b566a2"),
+            (112, "c01d080efd492776a1c43bd23dd99d0a2e626d481e16782e75d54c2503b5dc32bd05f0f1ba33e568b88fd2d970929b719ecbb152f58f130a407c8830604b70ca"),
+            (113, "55ddd8ac210a6e18ba1ee055af84c966e0dbff091c43580ae1be703bdb85da31acf6948cf5bd90c55a20e5450f22fb89bd8d0085e39f85a86cc46abbca75e24d"),
+            (127, "828613968b501dc00a97e08c73b118aa8876c26b8aac93df128502ab360f91bab50a51e088769a5c1eff4782ace147dce3642554199876374291f5d921629502"),
+            (128, "b73d1929aa615934e61a871596b3f3b33359f42b8175602e89f7e06e5f658a243667807ed300314b95cacdd579f3e33abdfbe351909519a846d465c59582f321"),
+            (129, "4f681e0bd53cda4b5a2041cc8a06f2eabde44fb16c951fbd5b87702f07aeab611565b19c47fde30587177ebb852e3971bbd8d3fd30da18d71037dfbd98420429"),
+        ];
+        for (len, expected) in cases {
+            let mut hasher = Sha512::new();
+            hasher.update(&vec![b'a'; len]);
+            assert_eq!(hasher.hexdigest(), expected, "length {}", len);
+        }
+    }
 }
@@ -0,0 +1,157 @@
+//! SHA-512/224 cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following FIPS 180-4 specification.
+//! SHA-512/224 runs the SHA-512 engine from its own initial hash value
+//! and truncates the output, which makes it faster than SHA-224 on 64-bit
+//! platforms and immune to length extension.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 1024 bits (128 bytes)
+//! - **Digest size**: 224 bits (28 bytes)
+//! - **Rounds**: 80
+
+use super::{Digest, LengthOverflowError, Sha512};
+use crate::utils::to_hex;
+
+/// SHA-512/224 hasher state.
+///
+/// Wraps a [`Sha512`] engine, so it shares its streaming behavior.
+#[derive(Clone)]
+pub struct Sha512_224 {
+    inner: Sha512,
+}
+
+impl Sha512_224 {
+    /// Maximum message length in bytes, same as SHA-512.
+    pub const MAX_MESSAGE_LEN: u128 = Sha512::MAX_MESSAGE_LEN;
+
+    /// SHA-512/224 initial hash value (FIPS 180-4, section 5.3.6.1).
+    const IV: [u64; 8] = [
+        0x8c3d37c819544da2, 0x73e1996689dcd4d6, 0x1dfab7ae32ff9c82, 0x679dd514582f9fcf,
+        0x0f6d2b697bd44da8, 0x77e36f7304c48942, 0x3f9d85a86a1d36c8, 0x1112e6ad91d692a1,
+    ];
+
+    /// Creates a new SHA-512/224 hasher with initial state.
+    pub fn new() -> Self {
+        Sha512_224 { inner: Sha512::with_iv(Self::IV) }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The hasher is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.inner
+            .try_update(data)
+            .map_err(|_| LengthOverflowError { algorithm: "SHA-512/224" })
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 28] {
+        let full = self.inner.digest();
+        let mut result = [0u8; 28];
+        result.copy_from_slice(&full[..28]);
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 28] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (28 for SHA-512/224).
+    pub fn digest_size() -> usize {
+        28
+    }
+
+    /// Returns the block size in bytes (128 for SHA-512/224).
+    pub fn block_size() -> usize {
+        128
+    }
+}
+
+impl Default for Sha512_224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha512_224 {
+    type Output = [u8; 28];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha512_224::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 28] {
+        Sha512_224::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha512_224::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sha512_224::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha512_224_empty() {
+        assert_eq!(
+            Sha512_224::new().hexdigest(),
+            "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4"
+        );
+    }
+
+    #[test]
+    fn test_sha512_224_abc() {
+        let mut hasher = Sha512_224::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"
+        );
+    }
+
+    #[test]
+    fn test_sha512_224_two_blocks() {
+        let mut hasher = Sha512_224::new();
+        hasher.update(
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno\
+              ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        );
+        assert_eq!(
+            hasher.hexdigest(),
+            "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9"
+        );
+    }
+}
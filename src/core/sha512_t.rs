@@ -0,0 +1,208 @@
+//! SHA-512/t cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following FIPS 180-4 specification.
+//! SHA-512/t runs the SHA-512 engine from an initial hash value derived
+//! from `t` (section 5.3.6) and truncates the output to `t` bits.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 1024 bits (128 bytes)
+//! - **Digest size**: `t` bits, a multiple of 8 below 512 (except 384)
+//! - **Rounds**: 80
+
+use super::{Digest, LengthOverflowError, ParameterError, Sha512};
+use crate::utils::to_hex;
+
+/// SHA-512/t hasher state.
+///
+/// Wraps a [`Sha512`] engine, so it shares its streaming behavior.
+#[derive(Clone)]
+pub struct Sha512T {
+    inner: Sha512,
+    /// Output size in bits.
+    t: usize,
+}
+
+impl Sha512T {
+    /// Maximum message length in bytes, same as SHA-512.
+    pub const MAX_MESSAGE_LEN: u128 = Sha512::MAX_MESSAGE_LEN;
+
+    /// Creates a new SHA-512/t hasher with an output of `t` bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] unless `t` is a multiple of 8 between 8
+    /// and 504. `t = 384` is also rejected: FIPS 180-4 reserves that size
+    /// for SHA-384, which uses a different initial hash value.
+    pub fn new(t: usize) -> Result<Self, ParameterError> {
+        let message = if t == 0 || t >= 512 {
+            format!("t must be between 8 and 504, got {}", t)
+        } else if !t.is_multiple_of(8) {
+            format!("t must be a multiple of 8, got {}", t)
+        } else if t == 384 {
+            "t = 384 is not allowed, use SHA-384 instead".to_owned()
+        } else {
+            return Ok(Sha512T { inner: Sha512::with_iv(Self::derive_iv(t)), t });
+        };
+        Err(ParameterError { algorithm: "SHA-512/t", message })
+    }
+
+    /// Computes the initial hash value for SHA-512/t (FIPS 180-4, 5.3.6):
+    /// the SHA-512 digest of the string "SHA-512/t", computed from the
+    /// SHA-512 initial hash value XORed with `0xa5a5...a5`.
+    fn derive_iv(t: usize) -> [u64; 8] {
+        let mut iv = Sha512::IV;
+        for word in iv.iter_mut() {
+            *word ^= 0xa5a5a5a5a5a5a5a5;
+        }
+        let mut generator = Sha512::with_iv(iv);
+        generator.update(format!("SHA-512/{}", t).as_bytes());
+        let digest = generator.digest();
+
+        let mut derived = [0u64; 8];
+        for (word, bytes) in derived.iter_mut().zip(digest.chunks_exact(8)) {
+            *word = u64::from_be_bytes(bytes.try_into().unwrap());
+        }
+        derived
+    }
+
+    /// Returns the output size in bits.
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The hasher is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.inner
+            .try_update(data)
+            .map_err(|_| LengthOverflowError { algorithm: "SHA-512/t" })
+    }
+
+    /// Returns the digest of the data fed so far, `t / 8` bytes long.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        self.inner.digest()[..self.digest_size()].to_vec()
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        self.inner = Sha512::with_iv(Self::derive_iv(self.t));
+    }
+
+    /// Returns the output size in bytes (`t / 8`).
+    pub fn digest_size(&self) -> usize {
+        self.t / 8
+    }
+
+    /// Returns the block size in bytes (128 for SHA-512/t).
+    pub fn block_size() -> usize {
+        128
+    }
+}
+
+impl Digest for Sha512T {
+    type Output = Vec<u8>;
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sha512T::try_update(self, data)
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Sha512T::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sha512T::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Sha512T::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Sha512_224;
+
+    #[test]
+    fn test_sha512_t_224_matches_sha512_224() {
+        for data in [&b""[..], b"abc", &[0x61; 300]] {
+            let mut generic = Sha512T::new(224).unwrap();
+            generic.update(data);
+            let mut fixed = Sha512_224::new();
+            fixed.update(data);
+            assert_eq!(generic.digest(), fixed.digest());
+        }
+    }
+
+    #[test]
+    fn test_sha512_t_256_iv_and_vector() {
+        // Initial hash value published in FIPS 180-4, section 5.3.6.2
+        assert_eq!(
+            Sha512T::derive_iv(256),
+            [
+                0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 0x963877195940eabd,
+                0x96283ee2a88effe3, 0xbe5e1e2553863992, 0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+            ]
+        );
+        let mut hasher = Sha512T::new(256).unwrap();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(),
+            "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"
+        );
+    }
+
+    #[test]
+    fn test_sha512_t_digest_size() {
+        let hasher = Sha512T::new(128).unwrap();
+        assert_eq!(hasher.t(), 128);
+        assert_eq!(hasher.digest_size(), 16);
+        assert_eq!(hasher.digest().len(), 16);
+    }
+
+    #[test]
+    fn test_sha512_t_rejects_invalid_sizes() {
+        for t in [0, 100, 384, 512, 520] {
+            let err = Sha512T::new(t).err().expect("t should be rejected");
+            assert_eq!(err.algorithm, "SHA-512/t");
+        }
+    }
+
+    #[test]
+    fn test_sha512_t_reset() {
+        let mut hasher = Sha512T::new(192).unwrap();
+        hasher.update(b"first message");
+        hasher.finalize_reset();
+        hasher.update(b"abc");
+        let mut fresh = Sha512T::new(192).unwrap();
+        fresh.update(b"abc");
+        assert_eq!(hasher.digest(), fresh.digest());
+    }
+}
@@ -0,0 +1,362 @@
+//! SHA-crypt password hashing (`$5$` and `$6$`).
+//!
+//! Pure Rust implementation of Ulrich Drepper's "Unix crypt using SHA-256
+//! and SHA-512", the scheme of `/etc/shadow` on most Linux systems. The
+//! password `P` and salt `S` are first mixed into a digest `A`, then
+//! `rounds` digests alternate `C`, the password sequence `P'` and the salt
+//! sequence `S'`:
+//!
+//! ```text
+//! C(i+1) = H((i odd ? P' : C(i)) || (i % 3 ? S' : "") || (i % 7 ? P' : "")
+//!            || (i odd ? C(i) : P'))
+//! ```
+//!
+//! # Algorithm Details
+//!
+//! - **Salt**: up to 16 characters, longer salts are truncated
+//! - **Rounds**: 5000 by default, otherwise written as `rounds=N` and
+//!   clamped to 1000..=999999999
+//! - **Output**: `$5$[rounds=N$]salt$hash` with a 43-character SHA-256
+//!   hash, or `$6$...` with an 86-character SHA-512 hash, in crypt's
+//!   Base64 alphabet with the bytes of the digest permuted
+
+use super::{Digest, ParameterError, Sha256, Sha512};
+use crate::utils::constant_time_eq;
+
+/// Rounds used when the string does not specify any.
+pub const DEFAULT_ROUNDS: u32 = 5000;
+
+/// Lowest number of rounds; fewer are raised to this.
+pub const MIN_ROUNDS: u32 = 1000;
+
+/// Highest number of rounds; more are lowered to this.
+pub const MAX_ROUNDS: u32 = 999_999_999;
+
+/// Number of salt characters used.
+pub const MAX_SALT_LEN: usize = 16;
+
+/// crypt's Base64 alphabet.
+pub const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Digest bytes encoded by each group of four characters of a `$5$` hash;
+/// the last two bytes make a final group of three characters.
+const SHA256_ORDER: [[usize; 3]; 10] = [
+    [0, 10, 20], [21, 1, 11], [12, 22, 2], [3, 13, 23], [24, 4, 14],
+    [15, 25, 5], [6, 16, 26], [27, 7, 17], [18, 28, 8], [9, 19, 29],
+];
+
+/// Digest bytes encoded by each group of four characters of a `$6$` hash;
+/// the last byte makes a final group of two characters.
+const SHA512_ORDER: [[usize; 3]; 21] = [
+    [0, 21, 42], [22, 43, 1], [44, 2, 23], [3, 24, 45], [25, 46, 4],
+    [47, 5, 26], [6, 27, 48], [28, 49, 7], [50, 8, 29], [9, 30, 51],
+    [31, 52, 10], [53, 11, 32], [12, 33, 54], [34, 55, 13], [56, 14, 35],
+    [15, 36, 57], [37, 58, 16], [59, 17, 38], [18, 39, 60], [40, 61, 19],
+    [62, 20, 41],
+];
+
+/// The two SHA-crypt schemes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShaCryptVariant {
+    /// `$5$`, over SHA-256.
+    Sha256,
+    /// `$6$`, over SHA-512.
+    Sha512,
+}
+
+impl ShaCryptVariant {
+    /// Returns the identifier between the first two `$`, `"5"` or `"6"`.
+    pub fn id(self) -> &'static str {
+        match self {
+            ShaCryptVariant::Sha256 => "5",
+            ShaCryptVariant::Sha512 => "6",
+        }
+    }
+
+    /// Returns the length of the encoded hash.
+    fn encoded_len(self) -> usize {
+        match self {
+            ShaCryptVariant::Sha256 => 43,
+            ShaCryptVariant::Sha512 => 86,
+        }
+    }
+}
+
+/// Feeds `data` to `hasher`.
+fn feed<D: Digest>(hasher: &mut D, data: &[u8]) {
+    hasher.try_update(data).expect("inputs fit in memory, so below the length limit");
+}
+
+/// Returns the digest of the concatenation of `parts`.
+fn digest_of<D: Digest>(hasher: &D, parts: &[&[u8]]) -> Vec<u8> {
+    let mut h = hasher.clone();
+    for part in parts {
+        feed(&mut h, part);
+    }
+    h.digest().as_ref().to_vec()
+}
+
+/// Returns `len` bytes of `block` repeated.
+fn repeat(block: &[u8], len: usize) -> Vec<u8> {
+    block.iter().copied().cycle().take(len).collect()
+}
+
+/// Computes the raw SHA-crypt digest; `hasher` must be in its initial
+/// state and `rounds` already clamped.
+fn raw<D: Digest>(hasher: D, password: &[u8], salt: &[u8], rounds: u32) -> Vec<u8> {
+    let b = digest_of(&hasher, &[password, salt, password]);
+
+    let mut a = hasher.clone();
+    feed(&mut a, password);
+    feed(&mut a, salt);
+    feed(&mut a, &repeat(&b, password.len()));
+    let mut bits = password.len();
+    while bits > 0 {
+        feed(&mut a, if bits & 1 == 1 { &b } else { password });
+        bits >>= 1;
+    }
+    let a = a.digest().as_ref().to_vec();
+
+    let dp = digest_of(&hasher, &vec![password; password.len()]);
+    let p = repeat(&dp, password.len());
+    let ds = digest_of(&hasher, &vec![salt; 16 + a[0] as usize]);
+    let s = repeat(&ds, salt.len());
+
+    let mut c = a;
+    for i in 0..rounds {
+        let mut h = hasher.clone();
+        feed(&mut h, if i % 2 == 1 { &p } else { &c });
+        if i % 3 != 0 {
+            feed(&mut h, &s);
+        }
+        if i % 7 != 0 {
+            feed(&mut h, &p);
+        }
+        feed(&mut h, if i % 2 == 1 { &c } else { &p });
+        c = h.digest().as_ref().to_vec();
+    }
+    c
+}
+
+/// Appends the `chars` low-order characters of the 24-bit group
+/// `b2 b1 b0`, least significant first.
+fn push_group(out: &mut String, b2: u8, b1: u8, b0: u8, chars: usize) {
+    let mut w = u32::from(b2) << 16 | u32::from(b1) << 8 | u32::from(b0);
+    for _ in 0..chars {
+        out.push(ALPHABET[(w & 0x3f) as usize] as char);
+        w >>= 6;
+    }
+}
+
+/// Encodes a raw digest with the permutation of its variant.
+fn encode_hash(variant: ShaCryptVariant, hash: &[u8]) -> String {
+    let mut out = String::with_capacity(variant.encoded_len());
+    let order: &[[usize; 3]] = match variant {
+        ShaCryptVariant::Sha256 => &SHA256_ORDER,
+        ShaCryptVariant::Sha512 => &SHA512_ORDER,
+    };
+    for &[i, j, k] in order {
+        push_group(&mut out, hash[i], hash[j], hash[k], 4);
+    }
+    match variant {
+        ShaCryptVariant::Sha256 => push_group(&mut out, 0, hash[31], hash[30], 3),
+        ShaCryptVariant::Sha512 => push_group(&mut out, 0, 0, hash[63], 2),
+    }
+    out
+}
+
+/// Hashes `password` with `salt`, returning the full crypt string.
+///
+/// Only the first 16 characters of `salt` are used. Without `rounds`, the
+/// default 5000 rounds are used and not written in the string; explicit
+/// rounds are clamped to 1000..=999999999, as the specification requires.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `salt` has characters other than
+/// printable ASCII, or a `$` or `:`.
+pub fn sha_crypt(
+    variant: ShaCryptVariant,
+    password: &[u8],
+    salt: &str,
+    rounds: Option<u32>,
+) -> Result<String, ParameterError> {
+    if !salt.bytes().all(|b| b.is_ascii_graphic() && b != b'$' && b != b':') {
+        return Err(ParameterError {
+            algorithm: "SHA-crypt",
+            message: "salt must be printable ASCII without '$' or ':'".to_owned(),
+        });
+    }
+    let salt = &salt[..salt.len().min(MAX_SALT_LEN)];
+    let count = rounds.map_or(DEFAULT_ROUNDS, |r| r.clamp(MIN_ROUNDS, MAX_ROUNDS));
+    let hash = match variant {
+        ShaCryptVariant::Sha256 => raw(Sha256::new(), password, salt.as_bytes(), count),
+        ShaCryptVariant::Sha512 => raw(Sha512::new(), password, salt.as_bytes(), count),
+    };
+    let mut out = format!("${}$", variant.id());
+    if rounds.is_some() {
+        out.push_str(&format!("rounds={}$", count));
+    }
+    out.push_str(salt);
+    out.push('$');
+    out.push_str(&encode_hash(variant, &hash));
+    Ok(out)
+}
+
+/// A parsed SHA-crypt string: variant, explicit rounds and salt.
+pub type Decoded<'a> = (ShaCryptVariant, Option<u32>, &'a str);
+
+/// Parses a `$5$` or `$6$` crypt string into the settings that reproduce
+/// it.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if the string is not a well-formed SHA-crypt
+/// hash with rounds between 1000 and 999999999 and at most 16 salt
+/// characters.
+pub fn decode(text: &str) -> Result<Decoded<'_>, ParameterError> {
+    let invalid = || ParameterError {
+        algorithm: "SHA-crypt",
+        message: "invalid SHA-crypt hash".to_owned(),
+    };
+    let mut fields = text.strip_prefix('$').ok_or_else(invalid)?.split('$');
+    let variant = match fields.next() {
+        Some("5") => ShaCryptVariant::Sha256,
+        Some("6") => ShaCryptVariant::Sha512,
+        _ => return Err(invalid()),
+    };
+    let mut salt = fields.next().ok_or_else(invalid)?;
+    let mut rounds = None;
+    if let Some(count) = salt.strip_prefix("rounds=") {
+        if count.is_empty() || !count.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let count: u32 = count.parse().map_err(|_| invalid())?;
+        if !(MIN_ROUNDS..=MAX_ROUNDS).contains(&count) {
+            return Err(invalid());
+        }
+        rounds = Some(count);
+        salt = fields.next().ok_or_else(invalid)?;
+    }
+    let hash = fields.next().ok_or_else(invalid)?;
+    let valid_salt = salt.len() <= MAX_SALT_LEN
+        && salt.bytes().all(|b| b.is_ascii_graphic() && b != b':');
+    let valid_hash =
+        hash.len() == variant.encoded_len() && hash.bytes().all(|b| ALPHABET.contains(&b));
+    if !valid_salt || !valid_hash || fields.next().is_some() {
+        return Err(invalid());
+    }
+    Ok((variant, rounds, salt))
+}
+
+/// Checks `password` against a `$5$` or `$6$` crypt string, comparing the
+/// recomputed string in constant time.
+///
+/// # Errors
+///
+/// Returns [`ParameterError`] if `text` is not a valid SHA-crypt hash.
+pub fn verify(password: &[u8], text: &str) -> Result<bool, ParameterError> {
+    let (variant, rounds, salt) = decode(text)?;
+    let computed = sha_crypt(variant, password, salt, rounds)?;
+    Ok(constant_time_eq(computed.as_bytes(), text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors of the specification: salt setting, password, result.
+    const VECTORS: [(&str, &str, &str); 6] = [
+        (
+            "$5$saltstring",
+            "Hello world!",
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+        ),
+        (
+            "$5$rounds=10000$saltstringsaltstring",
+            "Hello world!",
+            "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA",
+        ),
+        (
+            "$5$rounds=10$roundstoolow",
+            "the minimum number is still observed",
+            "$5$rounds=1000$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC",
+        ),
+        (
+            "$6$saltstring",
+            "Hello world!",
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdc\
+             bYEdFCoEOfaS35inz1",
+        ),
+        (
+            "$6$rounds=5000$toolongsaltstring",
+            "This is just a test",
+            "$6$rounds=5000$toolongsaltstrin$lQ8jolhgVRVhY4b5pZKaysCLi0QBxGoNeKQzQ3glMhwllF7oGD\
+             ZxUhx1yxdYcz/e1JSbq3y6JMxxl8audkUEm0",
+        ),
+        (
+            "$6$rounds=10$roundstoolow",
+            "the minimum number is still observed",
+            "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt5\
+             8jaTfF4ZEQpyUNGc0dqbpBYYBaHHrsX.",
+        ),
+    ];
+
+    /// Splits a salt setting into variant, explicit rounds and salt.
+    fn setting(text: &str) -> (ShaCryptVariant, Option<u32>, &str) {
+        let variant = if text.starts_with("$5$") {
+            ShaCryptVariant::Sha256
+        } else {
+            ShaCryptVariant::Sha512
+        };
+        let rest = &text[3..];
+        match rest.strip_prefix("rounds=") {
+            Some(rest) => {
+                let (count, salt) = rest.split_once('$').unwrap();
+                (variant, Some(count.parse().unwrap()), salt)
+            }
+            None => (variant, None, rest),
+        }
+    }
+
+    #[test]
+    fn test_sha_crypt_vectors() {
+        for (salt_setting, password, expected) in VECTORS {
+            let (variant, rounds, salt) = setting(salt_setting);
+            let hash = sha_crypt(variant, password.as_bytes(), salt, rounds).unwrap();
+            assert_eq!(hash, expected);
+            assert!(verify(password.as_bytes(), expected).unwrap());
+            assert!(!verify(b"wrong", expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sha_crypt_rounds_and_salt() {
+        let hash = sha_crypt(ShaCryptVariant::Sha256, b"pw", "salt", Some(5000)).unwrap();
+        assert!(hash.starts_with("$5$rounds=5000$salt$"));
+        let implicit = sha_crypt(ShaCryptVariant::Sha256, b"pw", "salt", None).unwrap();
+        assert_eq!(implicit["$5$salt$".len()..], hash["$5$rounds=5000$salt$".len()..]);
+        assert!(sha_crypt(ShaCryptVariant::Sha512, b"pw", "a$b", None).is_err());
+        assert!(sha_crypt(ShaCryptVariant::Sha512, b"pw", "a:b", None).is_err());
+        assert!(sha_crypt(ShaCryptVariant::Sha512, b"pw", "sél", None).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        let good = VECTORS[1].2;
+        assert!(decode(good).is_ok());
+        for bad in [
+            "",
+            "$5$",
+            "$7$salt$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc",
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5$",
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWE_5",
+            "$5$rounds=10$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+            "$5$rounds=$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+            "$5$saltstringsaltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+        ] {
+            assert!(decode(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! SHAKE extendable-output functions.
+//!
+//! Pure Rust implementation following FIPS 202. SHAKE runs the Keccak
+//! sponge from [`super::keccak`] with the `1111` domain suffix and returns
+//! as many output bytes as requested.
+//!
+//! # Algorithm Details
+//!
+//! | Function | Rate (block size) | Security strength |
+//! |----------|-------------------|-------------------|
+//! | SHAKE128 | 168 bytes         | 128 bits          |
+//! | SHAKE256 | 136 bytes         | 256 bits          |
+
+use super::keccak::KeccakXof;
+
+/// Domain separation byte of the SHAKE functions (`1111` suffix plus padding).
+const SHAKE_DOMAIN: u8 = 0x1f;
+
+/// SHAKE128 extendable-output function.
+pub type Shake128 = KeccakXof<168, SHAKE_DOMAIN>;
+
+/// SHAKE256 extendable-output function.
+pub type Shake256 = KeccakXof<136, SHAKE_DOMAIN>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shake128_hex(data: &[u8], length: usize) -> String {
+        let mut hasher = Shake128::new();
+        hasher.update(data);
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_shake128_empty() {
+        assert_eq!(
+            shake128_hex(b"", 32),
+            "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+    }
+
+    #[test]
+    fn test_shake128_abc() {
+        assert_eq!(
+            shake128_hex(b"abc", 32),
+            "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"
+        );
+    }
+
+    #[test]
+    fn test_shake256_vectors() {
+        let mut hasher = Shake256::new();
+        assert_eq!(
+            hasher.hexdigest(64),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f\
+             d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+        );
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.hexdigest(32),
+            "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739"
+        );
+        assert_eq!(Shake256::block_size(), 136);
+    }
+
+    #[test]
+    fn test_shake128_lengths_are_prefixes() {
+        let long = Shake128::new().digest(1000);
+        for length in [0, 1, 167, 168, 169, 336, 999] {
+            assert_eq!(Shake128::new().digest(length), long[..length]);
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Skein hash function implementation.
+//!
+//! Pure Rust implementation of Skein 1.3, the SHA-3 finalist built on the
+//! Threefish tweakable block cipher. Each block is chained through Unique
+//! Block Iteration (UBI): it is encrypted under the previous chaining value
+//! with a tweak holding its position and type, then XORed with itself. A
+//! configuration block encoding the output length starts the chain, and an
+//! output transform hashes a counter block per output block, so any output
+//! length up to [`MAX_OUTPUT_SIZE`] can be requested.
+//!
+//! # Algorithm Details
+//!
+//! - **State and block sizes**: 256, 512 or 1024 bits ([`Skein256`],
+//!   [`Skein512`], [`Skein1024`])
+//! - **Digest size**: 1 to 2^20 bytes, the state size by default
+//! - **Rounds**: 72 Threefish rounds (80 for Skein-1024)
+//! - **Byte order**: little-endian words, tweaks and counters
+//!
+//! # Security
+//!
+//! Skein was one of the five SHA-3 finalists; no attacks on the full
+//! function are known. It is mostly found in archival and storage systems.
+
+use super::{Digest, LengthOverflowError, ParameterError};
+use crate::utils::to_hex;
+
+/// Largest digest size in bytes (1 MiB).
+///
+/// The specification allows up to 2^64 - 1 output bits, but digests are
+/// returned in memory; longer outputs are better served by an XOF.
+pub const MAX_OUTPUT_SIZE: usize = 1 << 20;
+
+/// Key schedule parity constant (Skein 1.3, section 3.3.2).
+const KEY_SCHEDULE_PARITY: u64 = 0x1bd1_1bda_a9fc_1a22;
+
+/// MIX rotation constants for Threefish-256, indexed by round modulo 8.
+const ROTATIONS_256: [u32; 16] = [14, 16, 52, 57, 23, 40, 5, 37, 25, 33, 46, 12, 58, 22, 32, 32];
+
+/// MIX rotation constants for Threefish-512, indexed by round modulo 8.
+const ROTATIONS_512: [u32; 32] = [
+    46, 36, 19, 37, 33, 27, 14, 42, 17, 49, 36, 39, 44, 9, 54, 56,
+    39, 30, 34, 24, 13, 50, 10, 17, 25, 29, 39, 43, 8, 35, 56, 22,
+];
+
+/// MIX rotation constants for Threefish-1024, indexed by round modulo 8.
+const ROTATIONS_1024: [u32; 64] = [
+    24, 13, 8, 47, 8, 17, 22, 37, 38, 19, 10, 55, 49, 18, 23, 52,
+    33, 4, 51, 13, 34, 41, 59, 17, 5, 20, 48, 41, 47, 28, 16, 25,
+    41, 9, 37, 31, 12, 47, 44, 30, 16, 34, 56, 51, 4, 53, 42, 41,
+    31, 44, 47, 46, 19, 42, 44, 25, 9, 48, 35, 52, 23, 31, 37, 20,
+];
+
+/// Word permutations applied after each round: word `i` takes the value of
+/// word `PERMUTATION[i]`.
+const PERMUTATION_256: [usize; 4] = [0, 3, 2, 1];
+const PERMUTATION_512: [usize; 8] = [2, 1, 4, 7, 6, 5, 0, 3];
+const PERMUTATION_1024: [usize; 16] = [0, 9, 2, 13, 6, 11, 4, 15, 10, 7, 12, 3, 14, 5, 8, 1];
+
+/// UBI block types, placed in bits 120 to 125 of the tweak.
+const TYPE_CONFIG: u64 = 4 << 56;
+const TYPE_MESSAGE: u64 = 48 << 56;
+const TYPE_OUTPUT: u64 = 63 << 56;
+
+/// Tweak flags marking the first and last block of a UBI call.
+const FLAG_FIRST: u64 = 1 << 62;
+const FLAG_FINAL: u64 = 1 << 63;
+
+/// Skein hasher state, generic over the number of 64-bit words in the
+/// state (4, 8 or 16).
+///
+/// Use the [`Skein256`], [`Skein512`] and [`Skein1024`] aliases. Cloning
+/// captures the full streaming state, so a clone can be finalized or
+/// extended independently of the original.
+#[derive(Clone)]
+pub struct Skein<const WORDS: usize> {
+    chain: [u64; WORDS],
+    buffer: [u8; 128],     // Pending block; only the first WORDS * 8 bytes are used
+    buffer_len: usize,     // Number of bytes currently in buffer
+    position: u128,        // Bytes already compressed (UBI tweak position)
+    output_size: usize,    // Digest size in bytes
+}
+
+/// Skein-256: 256-bit state, 32-byte digest by default.
+pub type Skein256 = Skein<4>;
+
+/// Skein-512: 512-bit state, 64-byte digest by default.
+pub type Skein512 = Skein<8>;
+
+/// Skein-1024: 1024-bit state, 128-byte digest by default.
+pub type Skein1024 = Skein<16>;
+
+impl<const WORDS: usize> Skein<WORDS> {
+    /// Block size in bytes, equal to the state size.
+    const BLOCK_SIZE: usize = WORDS * 8;
+
+    /// Creates a new hasher whose digest size equals the state size.
+    pub fn new() -> Self {
+        Self::with_output_size(Self::BLOCK_SIZE).unwrap()
+    }
+
+    /// Creates a new hasher producing `output_size` bytes.
+    ///
+    /// The output size is part of the configuration block, so digests of
+    /// different sizes are unrelated rather than prefixes of each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParameterError`] if `output_size` is 0 or larger than
+    /// [`MAX_OUTPUT_SIZE`].
+    pub fn with_output_size(output_size: usize) -> Result<Self, ParameterError> {
+        if !(1..=MAX_OUTPUT_SIZE).contains(&output_size) {
+            return Err(ParameterError {
+                algorithm: Self::name(),
+                message: format!("digest_size must be between 1 and {}", MAX_OUTPUT_SIZE),
+            });
+        }
+        let output_bits = output_size as u64 * 8;
+
+        // Configuration block: schema "SHA3", version 1, output length in
+        // bits, then zero tree parameters (sequential hashing)
+        let mut config = [0u8; 128];
+        config[..4].copy_from_slice(b"SHA3");
+        config[4..6].copy_from_slice(&1u16.to_le_bytes());
+        config[8..16].copy_from_slice(&output_bits.to_le_bytes());
+        let mut chain = [0u64; WORDS];
+        Self::ubi(&mut chain, &config, 32, TYPE_CONFIG | FLAG_FIRST | FLAG_FINAL);
+
+        Ok(Skein {
+            chain,
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            position: 0,
+            output_size,
+        })
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// The last block of a UBI call is flagged in its tweak, so a full
+    /// block stays buffered until more data arrives. All other blocks are
+    /// processed directly from the input.
+    pub fn update(&mut self, mut data: &[u8]) {
+        let block_size = Self::BLOCK_SIZE;
+
+        if self.buffer_len + data.len() > block_size {
+            // Complete and process the pending block; more data follows
+            if self.buffer_len > 0 {
+                let available = block_size - self.buffer_len;
+                self.buffer[self.buffer_len..block_size].copy_from_slice(&data[..available]);
+                data = &data[available..];
+                let block = self.buffer;
+                self.process_message_block(&block[..block_size]);
+                self.buffer_len = 0;
+            }
+
+            // Process complete blocks directly, keeping at least one byte
+            while data.len() > block_size {
+                self.process_message_block(&data[..block_size]);
+                data = &data[block_size..];
+            }
+        }
+
+        self.buffer[self.buffer_len..self.buffer_len + data.len()].copy_from_slice(data);
+        self.buffer_len += data.len();
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// The last block and the output transform are applied to a copy of
+    /// the chaining value, so the hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> Vec<u8> {
+        let block_size = Self::BLOCK_SIZE;
+        let mut chain = self.chain;
+        let mut block = [0u8; 128];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        let first = if self.position == 0 { FLAG_FIRST } else { 0 };
+        Self::ubi(
+            &mut chain,
+            &block[..block_size],
+            self.position + self.buffer_len as u128,
+            TYPE_MESSAGE | first | FLAG_FINAL,
+        );
+
+        // Output transform: one UBI call per output block, on a counter
+        let mut result = Vec::with_capacity(self.output_size);
+        let mut counter = 0u64;
+        while result.len() < self.output_size {
+            let mut block = [0u8; 128];
+            block[..8].copy_from_slice(&counter.to_le_bytes());
+            let mut output = chain;
+            Self::ubi(&mut output, &block[..block_size], 8, TYPE_OUTPUT | FLAG_FIRST | FLAG_FINAL);
+            let take = (self.output_size - result.len()).min(block_size);
+            result.extend(output.iter().flat_map(|word| word.to_le_bytes()).take(take));
+            counter += 1;
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so
+    /// far. The digest size is kept.
+    pub fn reset(&mut self) {
+        *self = Self::with_output_size(self.output_size).unwrap();
+    }
+
+    /// Processes a message block that is not the last one.
+    fn process_message_block(&mut self, block: &[u8]) {
+        let first = if self.position == 0 { FLAG_FIRST } else { 0 };
+        self.position += Self::BLOCK_SIZE as u128;
+        Self::ubi(&mut self.chain, block, self.position, TYPE_MESSAGE | first);
+    }
+
+    /// Chains one block through UBI: encrypts it under the chaining value
+    /// with the tweak built from `position` and `flags`, then XORs the
+    /// result with the block.
+    fn ubi(chain: &mut [u64; WORDS], block: &[u8], position: u128, flags: u64) {
+        let mut words = [0u64; WORDS];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        let tweak = [position as u64, (position >> 64) as u64 | flags];
+        let encrypted = Self::threefish(chain, tweak, &words);
+        for ((out, enc), word) in chain.iter_mut().zip(encrypted).zip(words) {
+            *out = enc ^ word;
+        }
+    }
+
+    /// Encrypts `block` with Threefish under `key` and `tweak`.
+    fn threefish(key: &[u64; WORDS], tweak: [u64; 2], block: &[u64; WORDS]) -> [u64; WORDS] {
+        let (rounds, rotations, permutation): (usize, &[u32], &[usize]) = match WORDS {
+            4 => (72, &ROTATIONS_256, &PERMUTATION_256),
+            8 => (72, &ROTATIONS_512, &PERMUTATION_512),
+            16 => (80, &ROTATIONS_1024, &PERMUTATION_1024),
+            _ => unreachable!("Threefish has 4, 8 or 16 words"),
+        };
+
+        // Extended key and tweak, each with a parity word
+        let mut keys = [0u64; 17];
+        keys[..WORDS].copy_from_slice(key);
+        keys[WORDS] = key.iter().fold(KEY_SCHEDULE_PARITY, |acc, &k| acc ^ k);
+        let tweaks = [tweak[0], tweak[1], tweak[0] ^ tweak[1]];
+        let add_subkey = |state: &mut [u64; WORDS], s: usize| {
+            for (i, word) in state.iter_mut().enumerate() {
+                *word = word.wrapping_add(keys[(s + i) % (WORDS + 1)]);
+            }
+            state[WORDS - 3] = state[WORDS - 3].wrapping_add(tweaks[s % 3]);
+            state[WORDS - 2] = state[WORDS - 2].wrapping_add(tweaks[(s + 1) % 3]);
+            state[WORDS - 1] = state[WORDS - 1].wrapping_add(s as u64);
+        };
+
+        let mut state = *block;
+        for round in 0..rounds {
+            if round % 4 == 0 {
+                add_subkey(&mut state, round / 4);
+            }
+            let rotation = &rotations[(round % 8) * WORDS / 2..];
+            for (pair, &r) in state.chunks_exact_mut(2).zip(rotation) {
+                pair[0] = pair[0].wrapping_add(pair[1]);
+                pair[1] = pair[1].rotate_left(r) ^ pair[0];
+            }
+            let mixed = state;
+            for (word, &from) in state.iter_mut().zip(permutation) {
+                *word = mixed[from];
+            }
+        }
+        add_subkey(&mut state, rounds / 4);
+        state
+    }
+
+    /// Returns the output size in bytes.
+    pub fn digest_size(&self) -> usize {
+        self.output_size
+    }
+
+    /// Returns the block size in bytes (the state size: 32, 64 or 128).
+    pub fn block_size() -> usize {
+        Self::BLOCK_SIZE
+    }
+
+    /// Returns the algorithm name used in error messages.
+    fn name() -> &'static str {
+        match WORDS {
+            4 => "Skein-256",
+            8 => "Skein-512",
+            _ => "Skein-1024",
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for Skein<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> Digest for Skein<WORDS> {
+    type Output = Vec<u8>;
+
+    /// Never fails: Skein positions are 96-bit, beyond any real message.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        Skein::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Skein::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skein_hex<const WORDS: usize>(output_size: usize, data: &[u8]) -> String {
+        let mut hasher = Skein::<WORDS>::with_output_size(output_size).unwrap();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    /// Bytes 0xff, 0xfe, ... as in the Skein 1.3 known-answer tests.
+    fn descending(len: usize) -> Vec<u8> {
+        (0..len).map(|i| 0xff - i as u8).collect()
+    }
+
+    #[test]
+    fn test_skein_empty_message() {
+        assert_eq!(
+            skein_hex::<4>(32, b""),
+            "c8877087da56e072870daa843f176e9453115929094c3a40c463a196c29bf7ba"
+        );
+        assert_eq!(
+            skein_hex::<8>(64, b""),
+            "bc5b4c50925519c290cc634277ae3d6257212395cba733bbad37a4af0fa06af4\
+             1fca7903d06564fea7a2d3730dbdb80c1f85562dfcc070334ea4d1d9e72cba7a"
+        );
+        assert_eq!(
+            skein_hex::<16>(128, b""),
+            "0fff9563bb3279289227ac77d319b6fff8d7e9f09da1247b72a0a265cd6d2a62\
+             645ad547ed8193db48cff847c06494a03f55666d3b47eb4c20456c9373c86297\
+             d630d5578ebd34cb40991578f9f52b18003efa35d3da6553ff35db91b81ab890\
+             bec1b189b7f52cb2a783ebb7d823d725b0b4a71f6824e88f68f982eefc6d19c6"
+        );
+    }
+
+    #[test]
+    fn test_skein_known_answers() {
+        // Skein 1.3 paper, appendix C: one byte, one block and two blocks
+        assert_eq!(
+            skein_hex::<4>(32, &[0xff]),
+            "0b98dcd198ea0e50a7a244c444e25c23da30c10fc9a1f270a6637f1f34e67ed2"
+        );
+        assert_eq!(
+            skein_hex::<4>(32, &descending(32)),
+            "8d0fa4ef777fd759dfd4044e6f6a5ac3c774aec943dcfc07927b723b5dbf408b"
+        );
+        assert_eq!(
+            skein_hex::<8>(64, &[0xff]),
+            "71b7bce6fe6452227b9ced6014249e5bf9a9754c3ad618ccc4e0aae16b316cc8\
+             ca698d864307ed3e80b6ef1570812ac5272dc409b5a012df2a579102f340617a"
+        );
+        assert_eq!(
+            skein_hex::<8>(64, &descending(64)),
+            "45863ba3be0c4dfc27e75d358496f4ac9a736a505d9313b42b2f5eada79fc17f\
+             63861e947afb1d056aa199575ad3f8c9a3cc1780b5e5fa4cae050e989876625b"
+        );
+        assert_eq!(
+            skein_hex::<16>(128, &descending(128)),
+            "1f3e02c46fb80a3fcd2dfbbc7c173800b40c60c2354af551189ebf433c3d85f9\
+             ff1803e6d920493179ed7ae7fce69c3581a5a2f82d3e0c7a295574d0cd7d217c\
+             484d2f6313d59a7718ead07d0729c24851d7e7d2491b902d489194e6b7d369db\
+             0ab7aa106f0ee0a39a42efc54f18d93776080985f907574f995ec6a37153a578"
+        );
+    }
+
+    #[test]
+    fn test_skein_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 31, 32, 33, 64, 128, 200] {
+            let mut hasher = Skein512::new();
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.hexdigest(), skein_hex::<8>(64, &data), "step {}", step);
+        }
+        let mut hasher = Skein1024::with_output_size(200).unwrap();
+        hasher.update(&data);
+        let digest = hasher.digest();
+        assert_eq!(digest.len(), 200);
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Skein1024::with_output_size(200).unwrap().digest());
+    }
+
+    #[test]
+    fn test_skein_output_size() {
+        // The output size is hashed into the configuration block
+        let short = skein_hex::<8>(32, b"abc");
+        assert_eq!(short.len(), 64);
+        assert!(!skein_hex::<8>(64, b"abc").starts_with(&short));
+        assert_eq!(Skein256::new().digest_size(), 32);
+        assert!(Skein512::with_output_size(0).is_err());
+        assert!(Skein512::with_output_size(MAX_OUTPUT_SIZE + 1).is_err());
+        assert!(Skein512::with_output_size(usize::MAX).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! SM3 cryptographic hash function implementation.
+//!
+//! Pure Rust implementation following GB/T 32905-2016 (also ISO/IEC
+//! 10118-3:2018). SM3 has the Merkle-Damgård layout of SHA-256, with its
+//! own message expansion, boolean functions and round constants.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 256 bits (32 bytes)
+//! - **Rounds**: 64, over 68 expanded words and their 64 XOR pairs
+//! - **Byte order**: big-endian words and length, as in SHA-256
+//!
+//! # Security
+//!
+//! SM3 is the Chinese national standard hash, required alongside SM2 and
+//! SM4 in regulated deployments. No practical collision attacks are known.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// SM3 hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Sm3 {
+    state: [u32; 8],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u64,        // Total bytes processed (for final length)
+}
+
+impl Sm3 {
+    /// Maximum message length in bytes.
+    ///
+    /// The padding encodes the length in bits as a 64-bit integer, so
+    /// messages are limited to 2^61 - 1 bytes.
+    pub const MAX_MESSAGE_LEN: u64 = (1 << 61) - 1;
+
+    /// SM3 initial value (GB/T 32905-2016, section 4.1).
+    const IV: [u32; 8] = [
+        0x7380166f, 0x4914b2b9, 0x172442d7, 0xda8a0600,
+        0xa96f30bc, 0x163138aa, 0xe38dee4d, 0xb0fb0e4e,
+    ];
+
+    /// Round constant of rounds 0 to 15.
+    const T_LOW: u32 = 0x79cc4519;
+
+    /// Round constant of rounds 16 to 63.
+    const T_HIGH: u32 = 0x7a879d8a;
+
+    /// Creates a new SM3 hasher with initial state.
+    pub fn new() -> Self {
+        Sm3 {
+            state: Self::IV,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total message length would exceed [`Self::MAX_MESSAGE_LEN`].
+    /// Use [`Self::try_update`] to handle that case instead.
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("message length limit exceeded");
+    }
+
+    /// Feeds data into the hasher, failing if the message becomes too long.
+    ///
+    /// The length check happens before any data is absorbed, so the hasher
+    /// is left unchanged when an error is returned.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.total_len = self
+            .total_len
+            .checked_add(data.len() as u64)
+            .filter(|&len| len <= Self::MAX_MESSAGE_LEN)
+            .ok_or(LengthOverflowError { algorithm: "SM3" })?;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 32] {
+        let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // Same padding as SHA-256: 0x80, zeros, then the big-endian bit length
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+        block[56..64].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 32];
+        for (i, &word) in state.iter().enumerate() {
+            result[i * 4..(i + 1) * 4].copy_from_slice(&word.to_be_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 32] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Permutation P0 of the compression function.
+    fn p0(x: u32) -> u32 {
+        x ^ x.rotate_left(9) ^ x.rotate_left(17)
+    }
+
+    /// Permutation P1 of the message expansion.
+    fn p1(x: u32) -> u32 {
+        x ^ x.rotate_left(15) ^ x.rotate_left(23)
+    }
+
+    /// Processes a single 512-bit block through the SM3 compression function.
+    fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+        let mut w = [0u32; 68];
+        for (word, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+        for j in 16..68 {
+            w[j] = Self::p1(w[j - 16] ^ w[j - 9] ^ w[j - 3].rotate_left(15))
+                ^ w[j - 13].rotate_left(7)
+                ^ w[j - 6];
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for j in 0..64 {
+            let (t, ff, gg) = if j < 16 {
+                (Self::T_LOW, a ^ b ^ c, e ^ f ^ g)
+            } else {
+                (Self::T_HIGH, (a & b) | (a & c) | (b & c), (e & f) | (!e & g))
+            };
+            let a12 = a.rotate_left(12);
+            let ss1 = a12
+                .wrapping_add(e)
+                .wrapping_add(t.rotate_left(j as u32 % 32))
+                .rotate_left(7);
+            let ss2 = ss1 ^ a12;
+            let tt1 = ff.wrapping_add(d).wrapping_add(ss2).wrapping_add(w[j] ^ w[j + 4]);
+            let tt2 = gg.wrapping_add(h).wrapping_add(ss1).wrapping_add(w[j]);
+
+            d = c;
+            c = b.rotate_left(9);
+            b = a;
+            a = tt1;
+            h = g;
+            g = f.rotate_left(19);
+            f = e;
+            e = Self::p0(tt2);
+        }
+
+        // The new chaining value is XORed with the old one, not added
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word ^= value;
+        }
+    }
+
+    /// Returns the output size in bytes (32 for SM3).
+    pub fn digest_size() -> usize {
+        32
+    }
+
+    /// Returns the block size in bytes (64 for SM3).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Sm3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sm3 {
+    type Output = [u8; 32];
+
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        Sm3::try_update(self, data)
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        Sm3::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Sm3::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Sm3::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sm3_hex(data: &[u8]) -> String {
+        let mut hasher = Sm3::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_sm3_standard_examples() {
+        // GB/T 32905-2016, appendix A: "abc" and 16 repetitions of "abcd"
+        assert_eq!(
+            sm3_hex(b"abc"),
+            "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"
+        );
+        assert_eq!(
+            sm3_hex(&b"abcd".repeat(16)),
+            "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"
+        );
+        assert_eq!(
+            sm3_hex(b""),
+            "1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b"
+        );
+    }
+
+    #[test]
+    fn test_sm3_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Sm3::new();
+        for chunk in data.chunks(37) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.hexdigest(), sm3_hex(&data));
+        let digest = hasher.digest();
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.digest(), Sm3::new().digest());
+    }
+
+    #[test]
+    fn test_sm3_length_limit() {
+        let mut hasher = Sm3::new();
+        hasher.total_len = Sm3::MAX_MESSAGE_LEN - 3;
+        assert!(hasher.try_update(b"abc").is_ok());
+        let err = hasher.try_update(b"d").unwrap_err();
+        assert_eq!(err.algorithm, "SM3");
+        assert_eq!(hasher.total_len, Sm3::MAX_MESSAGE_LEN);
+    }
+}
@@ -0,0 +1,164 @@
+//! TupleHash functions for hashing sequences of byte strings.
+//!
+//! Pure Rust implementation following NIST SP 800-185. Every element is
+//! framed with `encode_string`, so the tuple `("abc", "d")` and the tuple
+//! `("ab", "cd")` hash differently, unlike a plain concatenation.
+//!
+//! # Algorithm Details
+//!
+//! - **Construction**: cSHAKE with `N = "TupleHash"` over
+//!   `encode_string(X[0]) || ... || encode_string(X[n-1]) || right_encode(L)`
+//! - **Output**: `L` bits; the requested length is hashed too, so outputs of
+//!   different lengths are unrelated rather than prefixes of each other
+//! - **Rates**: 168 bytes (TupleHash128) and 136 bytes (TupleHash256)
+
+use super::cshake::{absorb_encoded_string, customized_sponge, right_encode};
+use super::keccak::Sponge;
+use super::{LengthOverflowError, Xof};
+use crate::utils::to_hex;
+
+/// Domain separation byte of cSHAKE, on which TupleHash is built.
+const CSHAKE_DOMAIN: u8 = 0x04;
+
+/// TupleHash hasher with a rate of `RATE` bytes.
+///
+/// Each call to [`TupleHash::update`] appends one element to the tuple.
+#[derive(Clone)]
+pub struct TupleHash<const RATE: usize> {
+    sponge: Sponge,
+    /// Sponge right after the customization prefix, restored by `reset()`.
+    initial: Sponge,
+}
+
+/// TupleHash128 hasher.
+pub type TupleHash128 = TupleHash<168>;
+
+/// TupleHash256 hasher.
+pub type TupleHash256 = TupleHash<136>;
+
+impl<const RATE: usize> TupleHash<RATE> {
+    /// Creates a new hasher for an empty tuple with customization string
+    /// `customization` (`S`, may be empty).
+    pub fn new(customization: &[u8]) -> Self {
+        let sponge = customized_sponge(RATE, b"TupleHash", customization);
+        TupleHash { initial: sponge.clone(), sponge }
+    }
+
+    /// Appends `item` as the next element of the tuple.
+    pub fn update(&mut self, item: &[u8]) {
+        absorb_encoded_string(&mut self.sponge, item);
+    }
+
+    /// Fills `out` with the `out.len()`-byte hash of the tuple so far.
+    ///
+    /// The hasher can keep absorbing elements afterwards.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        let mut sponge = self.sponge.clone();
+        let (suffix, len) = right_encode(out.len() as u64 * 8);
+        sponge.absorb(&suffix[..len]);
+        sponge.finalize(CSHAKE_DOMAIN).read(out);
+    }
+
+    /// Returns the `length`-byte hash of the tuple so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        let mut out = vec![0u8; length];
+        self.finalize_into(&mut out);
+        out
+    }
+
+    /// Returns the `length`-byte hash of the tuple as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to an empty tuple, keeping the customization.
+    pub fn reset(&mut self) {
+        self.sponge = self.initial.clone();
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize> Xof for TupleHash<RATE> {
+    /// Appends `data` as one element. Never fails.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: [&[u8]; 3] = [
+        &[0x00, 0x01, 0x02],
+        &[0x10, 0x11, 0x12, 0x13, 0x14, 0x15],
+        &[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28],
+    ];
+
+    fn tuple_hex<const RATE: usize>(items: &[&[u8]], custom: &[u8], length: usize) -> String {
+        let mut hasher = TupleHash::<RATE>::new(custom);
+        for item in items {
+            hasher.update(item);
+        }
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_tuplehash128_sp800_185_samples() {
+        assert_eq!(
+            tuple_hex::<168>(&ITEMS[..2], b"", 32),
+            "c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1"
+        );
+        assert_eq!(
+            tuple_hex::<168>(&ITEMS[..2], b"My Tuple App", 32),
+            "75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb"
+        );
+        assert_eq!(
+            tuple_hex::<168>(&ITEMS, b"My Tuple App", 32),
+            "e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84"
+        );
+    }
+
+    #[test]
+    fn test_tuplehash256_sp800_185_samples() {
+        assert_eq!(
+            tuple_hex::<136>(&ITEMS[..2], b"", 64),
+            "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073\
+             11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194"
+        );
+        assert_eq!(
+            tuple_hex::<136>(&ITEMS, b"My Tuple App", 64),
+            "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7\
+             d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce"
+        );
+    }
+
+    #[test]
+    fn test_tuplehash_framing_is_unambiguous() {
+        assert_ne!(
+            tuple_hex::<168>(&[b"abc", b"d"], b"", 32),
+            tuple_hex::<168>(&[b"ab", b"cd"], b"", 32)
+        );
+        assert_ne!(tuple_hex::<168>(&[], b"", 32), tuple_hex::<168>(&[b""], b"", 32));
+    }
+
+    #[test]
+    fn test_tuplehash_length_is_hashed() {
+        let short = tuple_hex::<168>(&ITEMS, b"", 16);
+        let long = tuple_hex::<168>(&ITEMS, b"", 32);
+        assert_ne!(short, long[..32]);
+    }
+}
@@ -0,0 +1,210 @@
+//! TurboSHAKE extendable-output functions.
+//!
+//! Pure Rust implementation following RFC 9861. TurboSHAKE is SHAKE with
+//! the Keccak permutation reduced to 12 rounds and a caller-chosen domain
+//! separation byte; it is the sponge underneath KangarooTwelve.
+//!
+//! # Algorithm Details
+//!
+//! - **Permutation**: Keccak-p[1600, 12], the last 12 rounds of Keccak-f
+//! - **Domain byte**: `D` in `0x01..=0x7F`, `0x1F` by default
+//! - **Rates**: 168 bytes (TurboSHAKE128) and 136 bytes (TurboSHAKE256)
+
+use super::keccak::{Sponge, XofReader};
+use super::{LengthOverflowError, ParameterError, Xof};
+use crate::utils::to_hex;
+
+/// Number of Keccak-p[1600] rounds.
+const ROUNDS: usize = 12;
+
+/// Default domain separation byte.
+const DEFAULT_DOMAIN: u8 = 0x1f;
+
+/// TurboSHAKE hasher with a rate of `RATE` bytes.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct TurboShake<const RATE: usize> {
+    sponge: Sponge,
+    domain: u8,
+}
+
+/// TurboSHAKE128 hasher.
+pub type TurboShake128 = TurboShake<168>;
+
+/// TurboSHAKE256 hasher.
+pub type TurboShake256 = TurboShake<136>;
+
+impl<const RATE: usize> TurboShake<RATE> {
+    /// Creates a new hasher with domain separation byte `domain` (`D`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError`] if `domain` is outside `0x01..=0x7F`.
+    pub fn new(domain: u8) -> Result<Self, ParameterError> {
+        if !(0x01..=0x7f).contains(&domain) {
+            return Err(ParameterError {
+                algorithm: "TurboSHAKE",
+                message: format!("domain byte must be in 0x01..=0x7f, got {:#04x}", domain),
+            });
+        }
+        Ok(TurboShake { sponge: Sponge::new(RATE, ROUNDS), domain })
+    }
+
+    /// Returns the domain separation byte.
+    pub fn domain(&self) -> u8 {
+        self.domain
+    }
+
+    /// Feeds data into the hasher. TurboSHAKE has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns a reader over the output stream for the data fed so far.
+    ///
+    /// The hasher can keep absorbing data afterwards.
+    pub fn finalize_xof(&self) -> XofReader {
+        self.sponge.finalize(self.domain)
+    }
+
+    /// Returns the first `length` output bytes for the data fed so far.
+    pub fn digest(&self, length: usize) -> Vec<u8> {
+        self.finalize_xof().read_vec(length)
+    }
+
+    /// Returns the first `length` output bytes as a hexadecimal string.
+    pub fn hexdigest(&self, length: usize) -> String {
+        to_hex(&self.digest(length))
+    }
+
+    /// Resets the hasher to its initial state, keeping the domain byte.
+    pub fn reset(&mut self) {
+        self.sponge = Sponge::new(RATE, ROUNDS);
+    }
+
+    /// Returns the block size (rate) in bytes.
+    pub fn block_size() -> usize {
+        RATE
+    }
+}
+
+impl<const RATE: usize> Default for TurboShake<RATE> {
+    fn default() -> Self {
+        TurboShake { sponge: Sponge::new(RATE, ROUNDS), domain: DEFAULT_DOMAIN }
+    }
+}
+
+impl<const RATE: usize> Xof for TurboShake<RATE> {
+    /// Never fails: TurboSHAKE has no message length limit.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn squeeze(&self, out: &mut [u8]) {
+        self.finalize_xof().read(out);
+    }
+
+    fn block_size(&self) -> usize {
+        RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `ptn(n)` of RFC 9861: the repeating pattern `00 01 .. FA`.
+    fn ptn(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn turbo_hex<const RATE: usize>(message: &[u8], domain: u8, length: usize) -> String {
+        let mut hasher = TurboShake::<RATE>::new(domain).unwrap();
+        hasher.update(message);
+        hasher.hexdigest(length)
+    }
+
+    #[test]
+    fn test_turboshake128_rfc9861() {
+        assert_eq!(
+            turbo_hex::<168>(b"", 0x1f, 64),
+            "1e415f1c5983aff2169217277d17bb538cd945a397ddec541f1ce41af2c1b74c\
+             3e8ccae2a4dae56c84a04c2385c03c15e8193bdf58737363321691c05462c8df"
+        );
+        let long = TurboShake128::default().digest(10032);
+        assert_eq!(
+            to_hex(&long[10000..]),
+            "a3b9b0385900ce761f22aed548e754da10a5242d62e8c658e3f3a923a7555607"
+        );
+        let patterns = [
+            "55cedd6f60af7bb29a4042ae832ef3f58db7299f893ebb9247247d856958daa9",
+            "9c97d036a3bac819db70ede0ca554ec6e4c2a1a4ffbfd9ec269ca6a111161233",
+            "96c77c279e0126f7fc07c9b07f5cdae1e0be60bdbe10620040e75d7223a624d2",
+            "d4976eb56bcf118520582b709f73e1d6853e001fdaf80e1b13e0d0599d5fb372",
+            "da67c7039e98bf530cf7a37830c6664e14cbab7f540f58403b1b82951318ee5c",
+        ];
+        for (i, expected) in patterns.iter().enumerate() {
+            assert_eq!(turbo_hex::<168>(&ptn(17usize.pow(i as u32)), 0x1f, 32), *expected);
+        }
+    }
+
+    #[test]
+    fn test_turboshake128_domains() {
+        let cases: [(&[u8], u8, &str); 6] = [
+            (&[0xff; 3], 0x01, "bf323f940494e88ee1c540fe660be8a0c93f43d15ec006998462fa994eed5dab"),
+            (&[0xff], 0x06, "8ec9c66465ed0d4a6c35d13506718d687a25cb05c74cca1e42501abd83874a67"),
+            (&[0xff; 3], 0x07, "b658576001cad9b1e5f399a9f77723bba05458042d68206f7252682dba3663ed"),
+            (&[0xff; 7], 0x0b, "8deeaa1aec47ccee569f659c21dfa8e112db3cee37b18178b2acd805b799cc37"),
+            (&[0xff], 0x30, "553122e2135e363c3292bed2c6421fa232bab03daa07c7d6636603286506325b"),
+            (&[0xff; 3], 0x7f, "16274cc656d44cefd422395d0f9053bda6d28e122aba15c765e5ad0e6eaf26f9"),
+        ];
+        for (message, domain, expected) in cases {
+            assert_eq!(turbo_hex::<168>(message, domain, 32), expected, "D = {:#04x}", domain);
+        }
+    }
+
+    #[test]
+    fn test_turboshake256_rfc9861() {
+        assert_eq!(
+            turbo_hex::<136>(b"", 0x1f, 64),
+            "367a329dafea871c7802ec67f905ae13c57695dc2c6663c61035f59a18f8e7db\
+             11edc0e12e91ea60eb6b32df06dd7f002fbafabb6e13ec1cc20d995547600db0"
+        );
+        let long = TurboShake256::default().digest(10032);
+        assert_eq!(
+            to_hex(&long[10000..]),
+            "abefa11630c661269249742685ec082f207265dccf2f43534e9c61ba0c9d1d75"
+        );
+        assert_eq!(
+            turbo_hex::<136>(&ptn(17), 0x1f, 64),
+            "b3bab0300e6a191fbe6137939835923578794ea54843f5011090fa2f3780a9e5\
+             cb22c59d78b40a0fbff9e672c0fbe0970bd2c845091c6044d687054da5d8e9c7"
+        );
+        assert_eq!(
+            turbo_hex::<136>(&[0xff; 7], 0x0b, 64),
+            "bb36764951ec97e9d85f7ee9a67a7718fc005cf42556be79ce12c0bde50e5736\
+             d6632b0d0dfb202d1bbb8ffe3dd74cb00834fa756cb03471bab13a1e2c16b3c0"
+        );
+    }
+
+    #[test]
+    fn test_turboshake_outputs_are_prefixes() {
+        let mut hasher = TurboShake256::new(0x1f).unwrap();
+        hasher.update(b"abc");
+        let long = hasher.digest(500);
+        for length in [0, 1, 31, 136, 137, 499] {
+            assert_eq!(hasher.digest(length), &long[..length]);
+        }
+    }
+
+    #[test]
+    fn test_turboshake_rejects_invalid_domains() {
+        for domain in [0x00, 0x80, 0xff] {
+            let err = TurboShake128::new(domain).err().expect("domain should be rejected");
+            assert_eq!(err.algorithm, "TurboSHAKE");
+        }
+    }
+}
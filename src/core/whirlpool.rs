@@ -0,0 +1,381 @@
+//! Whirlpool cryptographic hash function implementation.
+//!
+//! Pure Rust implementation of the final version of Whirlpool, as
+//! standardized in ISO/IEC 10118-3. The compression function is the
+//! Miyaguchi-Preneel construction over W, a 512-bit block cipher in the
+//! style of AES: each round substitutes the bytes of an 8x8 state through
+//! an S-box, shifts its columns, mixes its rows with an MDS matrix over
+//! GF(2^8) and adds the round key, itself derived by the same rounds.
+//!
+//! # Algorithm Details
+//!
+//! - **Block size**: 512 bits (64 bytes)
+//! - **Digest size**: 512 bits (64 bytes)
+//! - **Rounds**: 10
+//! - **Byte order**: big-endian, with a 256-bit message length
+//!
+//! The S-box, column shift and matrix product are merged into eight
+//! precomputed 256-entry tables of 64-bit rows, computed at compile time,
+//! so a round costs 64 lookups and XORs.
+
+use super::{Digest, LengthOverflowError};
+use crate::utils::to_hex;
+
+/// Whirlpool S-box (ISO/IEC 10118-3), as published.
+const SBOX: [u8; 256] = [
+    0x18, 0x23, 0xc6, 0xe8, 0x87, 0xb8, 0x01, 0x4f, 0x36, 0xa6, 0xd2, 0xf5, 0x79, 0x6f, 0x91, 0x52,
+    0x60, 0xbc, 0x9b, 0x8e, 0xa3, 0x0c, 0x7b, 0x35, 0x1d, 0xe0, 0xd7, 0xc2, 0x2e, 0x4b, 0xfe, 0x57,
+    0x15, 0x77, 0x37, 0xe5, 0x9f, 0xf0, 0x4a, 0xda, 0x58, 0xc9, 0x29, 0x0a, 0xb1, 0xa0, 0x6b, 0x85,
+    0xbd, 0x5d, 0x10, 0xf4, 0xcb, 0x3e, 0x05, 0x67, 0xe4, 0x27, 0x41, 0x8b, 0xa7, 0x7d, 0x95, 0xd8,
+    0xfb, 0xee, 0x7c, 0x66, 0xdd, 0x17, 0x47, 0x9e, 0xca, 0x2d, 0xbf, 0x07, 0xad, 0x5a, 0x83, 0x33,
+    0x63, 0x02, 0xaa, 0x71, 0xc8, 0x19, 0x49, 0xd9, 0xf2, 0xe3, 0x5b, 0x88, 0x9a, 0x26, 0x32, 0xb0,
+    0xe9, 0x0f, 0xd5, 0x80, 0xbe, 0xcd, 0x34, 0x48, 0xff, 0x7a, 0x90, 0x5f, 0x20, 0x68, 0x1a, 0xae,
+    0xb4, 0x54, 0x93, 0x22, 0x64, 0xf1, 0x73, 0x12, 0x40, 0x08, 0xc3, 0xec, 0xdb, 0xa1, 0x8d, 0x3d,
+    0x97, 0x00, 0xcf, 0x2b, 0x76, 0x82, 0xd6, 0x1b, 0xb5, 0xaf, 0x6a, 0x50, 0x45, 0xf3, 0x30, 0xef,
+    0x3f, 0x55, 0xa2, 0xea, 0x65, 0xba, 0x2f, 0xc0, 0xde, 0x1c, 0xfd, 0x4d, 0x92, 0x75, 0x06, 0x8a,
+    0xb2, 0xe6, 0x0e, 0x1f, 0x62, 0xd4, 0xa8, 0x96, 0xf9, 0xc5, 0x25, 0x59, 0x84, 0x72, 0x39, 0x4c,
+    0x5e, 0x78, 0x38, 0x8c, 0xd1, 0xa5, 0xe2, 0x61, 0xb3, 0x21, 0x9c, 0x1e, 0x43, 0xc7, 0xfc, 0x04,
+    0x51, 0x99, 0x6d, 0x0d, 0xfa, 0xdf, 0x7e, 0x24, 0x3b, 0xab, 0xce, 0x11, 0x8f, 0x4e, 0xb7, 0xeb,
+    0x3c, 0x81, 0x94, 0xf7, 0xb9, 0x13, 0x2c, 0xd3, 0xe7, 0x6e, 0xc4, 0x03, 0x56, 0x44, 0x7f, 0xa9,
+    0x2a, 0xbb, 0xc1, 0x53, 0xdc, 0x0b, 0x9d, 0x6c, 0x31, 0x74, 0xf6, 0x46, 0xac, 0x89, 0x14, 0xe1,
+    0x16, 0x3a, 0x69, 0x09, 0x70, 0xb6, 0xd0, 0xed, 0xcc, 0x42, 0x98, 0xa4, 0x28, 0x5c, 0xf8, 0x86,
+];
+
+/// First row of the circulant diffusion matrix; row `j` is this row
+/// rotated right by `j` positions.
+const MATRIX_ROW: [u8; 8] = [1, 1, 4, 1, 8, 5, 2, 9];
+
+/// Number of rounds of the W block cipher.
+const ROUNDS: usize = 10;
+
+/// Multiplies two elements of GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1d;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Builds the round tables: `TABLES[j][x]` is `SBOX[x]` times row `j` of the
+/// diffusion matrix, packed big-endian into a state row.
+const fn build_tables() -> [[u64; 256]; 8] {
+    let mut tables = [[0u64; 256]; 8];
+    let mut x = 0;
+    while x < 256 {
+        let mut row = 0u64;
+        let mut column = 0;
+        while column < 8 {
+            row = (row << 8) | gf_mul(SBOX[x], MATRIX_ROW[column]) as u64;
+            column += 1;
+        }
+        let mut j = 0;
+        while j < 8 {
+            tables[j][x] = row.rotate_right(8 * j as u32);
+            j += 1;
+        }
+        x += 1;
+    }
+    tables
+}
+
+/// Builds the first row of each round constant: eight consecutive S-box
+/// entries; the other rows are zero.
+const fn build_round_constants() -> [u64; ROUNDS] {
+    let mut constants = [0u64; ROUNDS];
+    let mut round = 0;
+    while round < ROUNDS {
+        let mut column = 0;
+        while column < 8 {
+            constants[round] = (constants[round] << 8) | SBOX[8 * round + column] as u64;
+            column += 1;
+        }
+        round += 1;
+    }
+    constants
+}
+
+/// Round tables merging the S-box, column shift and diffusion matrix.
+static TABLES: [[u64; 256]; 8] = build_tables();
+
+/// Round constants of the key schedule.
+const ROUND_CONSTANTS: [u64; ROUNDS] = build_round_constants();
+
+/// Whirlpool hasher state.
+///
+/// Maintains the internal state for incremental hashing.
+/// Uses a fixed-size buffer for optimal streaming performance.
+/// Cloning captures the full streaming state, so a clone can be
+/// finalized or extended independently of the original.
+#[derive(Clone)]
+pub struct Whirlpool {
+    /// Chaining value as eight big-endian rows.
+    state: [u64; 8],
+    buffer: [u8; 64],      // Fixed 64-byte buffer (1 block)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u128,       // Total bytes processed (for final length)
+}
+
+impl Whirlpool {
+    /// Creates a new Whirlpool hasher with an all-zero chaining value.
+    pub fn new() -> Self {
+        Whirlpool {
+            state: [0u64; 8],
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds data into the hasher.
+    ///
+    /// Processes complete 512-bit blocks immediately with zero-copy streaming.
+    /// Only incomplete blocks (< 64 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        // The padding holds a 256-bit length, out of reach of any message
+        self.total_len += data.len() as u128;
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 64 {
+                Self::compress(&mut self.state, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 64-byte blocks directly from input
+        while offset + 64 <= data.len() {
+            let block: [u8; 64] = data[offset..offset + 64].try_into().unwrap();
+            Self::compress(&mut self.state, &block);
+            offset += 64;
+        }
+
+        // Buffer any remaining bytes (< 64)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the digest of the data fed so far.
+    ///
+    /// Padding is applied to a copy of the pending block and state, so the
+    /// hasher can keep absorbing data afterwards.
+    pub fn digest(&self) -> [u8; 64] {
+        let bit_len = self.total_len * 8;
+        let mut state = self.state;
+        let mut block = [0u8; 64];
+
+        // 0x80 byte, zeros, then the length in bits on the last 32 bytes
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] = 0x80;
+        if self.buffer_len >= 32 {
+            Self::compress(&mut state, &block);
+            block = [0u8; 64];
+        }
+        block[48..64].copy_from_slice(&bit_len.to_be_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut result = [0u8; 64];
+        for (chunk, row) in result.chunks_exact_mut(8).zip(state) {
+            chunk.copy_from_slice(&row.to_be_bytes());
+        }
+        result
+    }
+
+    /// Returns the digest of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Returns the digest and resets the hasher to its initial state.
+    pub fn finalize_reset(&mut self) -> [u8; 64] {
+        let result = self.digest();
+        self.reset();
+        result
+    }
+
+    /// Resets the hasher to its initial state, discarding any data fed so far.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// One round of W: substitutes, shifts and mixes `state`, then adds
+    /// `key`.
+    fn round(state: &[u64; 8], key: &[u64; 8]) -> [u64; 8] {
+        let mut out = *key;
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, table) in TABLES.iter().enumerate() {
+                // Column j is shifted down by j rows
+                let byte = (state[(i + 8 - j) % 8] >> (56 - 8 * j)) as u8;
+                *row ^= table[byte as usize];
+            }
+        }
+        out
+    }
+
+    /// Processes a single 512-bit block: the chaining value keys W, which
+    /// encrypts the block, and both are XORed into the result.
+    fn compress(state: &mut [u64; 8], block: &[u8; 64]) {
+        let mut message = [0u64; 8];
+        for (row, chunk) in message.iter_mut().zip(block.chunks_exact(8)) {
+            *row = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut key = *state;
+        let mut cipher = [0u64; 8];
+        for i in 0..8 {
+            cipher[i] = message[i] ^ key[i];
+        }
+        for constant in ROUND_CONSTANTS {
+            let mut round_constant = [0u64; 8];
+            round_constant[0] = constant;
+            key = Self::round(&key, &round_constant);
+            cipher = Self::round(&cipher, &key);
+        }
+
+        for i in 0..8 {
+            state[i] ^= cipher[i] ^ message[i];
+        }
+    }
+
+    /// Returns the output size in bytes (64 for Whirlpool).
+    pub fn digest_size() -> usize {
+        64
+    }
+
+    /// Returns the block size in bytes (64 for Whirlpool).
+    pub fn block_size() -> usize {
+        64
+    }
+}
+
+impl Default for Whirlpool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Whirlpool {
+    type Output = [u8; 64];
+
+    /// Never fails: the 256-bit length field cannot overflow.
+    fn try_update(&mut self, data: &[u8]) -> Result<(), LengthOverflowError> {
+        self.update(data);
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 64] {
+        Whirlpool::digest(self)
+    }
+
+    fn digest_size(&self) -> usize {
+        Whirlpool::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Whirlpool::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whirlpool_hex(data: &[u8]) -> String {
+        let mut hasher = Whirlpool::new();
+        hasher.update(data);
+        hasher.hexdigest()
+    }
+
+    #[test]
+    fn test_whirlpool_iso_vectors() {
+        let vectors: [(&[u8], &str); 8] = [
+            (
+                b"",
+                "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7\
+                 3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+            ),
+            (
+                b"a",
+                "8aca2602792aec6f11a67206531fb7d7f0dff59413145e6973c45001d0087b42\
+                 d11bc645413aeff63a42391a39145a591a92200d560195e53b478584fdae231a",
+            ),
+            (
+                b"abc",
+                "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+                 7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+            ),
+            (
+                b"message digest",
+                "378c84a4126e2dc6e56dcc7458377aac838d00032230f53ce1f5700c0ffb4d3b\
+                 8421557659ef55c106b4b52ac5a4aaa692ed920052838f3362e86dbd37a8903e",
+            ),
+            (
+                b"abcdefghijklmnopqrstuvwxyz",
+                "f1d754662636ffe92c82ebb9212a484a8d38631ead4238f5442ee13b8054e41b\
+                 08bf2a9251c30b6a0b8aae86177ab4a6f68f673e7207865d5d9819a3dba4eb3b",
+            ),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "dc37e008cf9ee69bf11f00ed9aba26901dd7c28cdec066cc6af42e40f82f3a1e\
+                 08eba26629129d8fb7cb57211b9281a65517cc879d7b962142c65f5a7af01467",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "466ef18babb0154d25b9d38a6414f5c08784372bccb204d6549c4afadb601429\
+                 4d5bd8df2a6c44e538cd047b2681a51a2c60481e88c5a20b2c2a80cf3a9a083b",
+            ),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijk",
+                "2a987ea40f917061f5d6f0a0e4644f488a7a5a52deee656207c562f988e95c69\
+                 16bdc8031bc5be1b7b947639fe050b56939baaa0adff9ae6745b7b181c3be3fd",
+            ),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(whirlpool_hex(data), expected);
+        }
+    }
+
+    #[test]
+    fn test_whirlpool_quick_brown_fox() {
+        assert_eq!(
+            whirlpool_hex(b"The quick brown fox jumps over the lazy dog"),
+            "b97de512e91e3828b40d2b0fdce9ceb3c4a71f9bea8d88e75c4fa854df36725f\
+             d2b52eb6544edcacd6f8beddfea403cb55ae31f03ad62a5ef54e42ee82c3fb35"
+        );
+        assert_eq!(
+            whirlpool_hex(b"The quick brown fox jumps over the lazy eog"),
+            "c27ba124205f72e6847f3e19834f925cc666d0974167af915bb462420ed40cc5\
+             0900d85a1f923219d832357750492d5c143011a76988344c2635e69d06f2d38c"
+        );
+    }
+
+    #[test]
+    fn test_whirlpool_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for step in [1, 31, 32, 33, 64, 100] {
+            let mut hasher = Whirlpool::new();
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.hexdigest(), whirlpool_hex(&data), "step {}", step);
+        }
+    }
+}
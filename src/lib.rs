@@ -1,11 +1,27 @@
 //! Fast cryptographic hash functions implemented in Rust for Python.
 //!
-//! RsHash provides high-performance implementations of SHA-256 and SHA-512
-//! with a Python API compatible with the standard `hashlib` module.
+//! RsHash provides high-performance hash functions with a Python API
+//! compatible with the standard `hashlib` module. The algorithms fall into
+//! a few families:
+//!
+//! - Message digests: the SHA-1, SHA-2, SHA-3 and Keccak families, BLAKE2,
+//!   BLAKE3, MD2/MD4/MD5/MD6, RIPEMD, Whirlpool, Tiger, national standards
+//!   (SM3, GOST R 34.11-94, Kupyna, LSH, HAS-160) and the SHA-3 finalists
+//!   Skein, Grøstl and JH
+//! - Extendable-output functions: SHAKE, cSHAKE, TupleHash, ParallelHash,
+//!   TurboSHAKE, KangarooTwelve, BLAKE2X and Ascon-XOF128
+//! - MACs and key derivation: HMAC, Poly1305, HKDF, PBKDF2, KBKDF,
+//!   Concat KDF, HMAC-DRBG, HOTP/TOTP and the TLS 1.3 key schedule
+//! - Password hashing: scrypt, bcrypt, bcrypt-pbkdf, Argon2, Balloon and
+//!   SHA-crypt
+//! - Non-cryptographic checksums and hashes, in the [`noncrypto`] module
+//!
+//! The algorithms `new()` accepts by name are listed in the module's
+//! `algorithms_available` set, which is derived from a single registry.
 //!
 //! # Features
 //!
-//! - Pure Rust implementations following FIPS 180-4
+//! - Pure Rust implementations following the published specifications
 //! - hashlib-compatible API
 //! - Incremental hashing support
 //! - Zero-copy operations where possible
@@ -28,24 +44,160 @@
 //! # Factory function
 //! hasher = RsHash.new("sha512", b"data")
 //! print(hasher.digest())
+//!
+//! # One-shot helpers
+//! print(RsHash.sha256_hexdigest(b"hello world"))
 //! ```
 
 use pyo3::prelude::*;
+use pyo3::types::PySet;
 
-mod core;
+mod checksum;
+pub mod core;
+pub mod noncrypto;
 mod python;
 #[allow(dead_code)]
 mod utils;
 
 /// Python module initialization.
 ///
-/// Exposes SHA256, SHA512 classes and the `new()` factory function.
-#[pymodule]
+/// Exposes the hash classes, the `new()` factory function, the one-shot
+/// digest helpers, the `algorithms_available` set and the `noncrypto`
+/// submodule.
+///
+/// Hash objects lock their own state, so the module declares itself safe to
+/// use without the GIL on free-threaded CPython builds.
+#[pymodule(gil_used = false)]
 #[pyo3(name = "RsHash")]
 fn rshash(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<python::PyMD4>()?;
+    m.add_class::<python::PyMD5>()?;
+    m.add_class::<python::PySHA1>()?;
+    m.add_class::<python::PySHA224>()?;
     m.add_class::<python::PySHA256>()?;
     m.add_class::<python::PySHA512>()?;
+    m.add_class::<python::PySHA512_224>()?;
+    m.add_class::<python::PySHA512T>()?;
+    m.add_class::<python::PySHA3_224>()?;
+    m.add_class::<python::PySHA3_256>()?;
+    m.add_class::<python::PySHA3_384>()?;
+    m.add_class::<python::PySHA3_512>()?;
+    m.add_class::<python::PySHAKE128>()?;
+    m.add_class::<python::PySHAKE256>()?;
+    m.add_class::<python::PyKeccak256>()?;
+    m.add_class::<python::PyKeccak512>()?;
+    m.add_class::<python::PyRIPEMD128>()?;
+    m.add_class::<python::PyRIPEMD160>()?;
+    m.add_class::<python::PyRIPEMD256>()?;
+    m.add_class::<python::PyRIPEMD320>()?;
+    m.add_class::<python::PySM3>()?;
+    m.add_class::<python::PyWhirlpool>()?;
+    m.add_class::<python::PyTiger>()?;
+    m.add_class::<python::PyTiger2>()?;
+    m.add_class::<python::PySkein256>()?;
+    m.add_class::<python::PySkein512>()?;
+    m.add_class::<python::PySkein1024>()?;
+    m.add_class::<python::PyGroestl256>()?;
+    m.add_class::<python::PyGroestl512>()?;
+    m.add_class::<python::PyJH256>()?;
+    m.add_class::<python::PyJH512>()?;
+    m.add_class::<python::PyAsconHash256>()?;
+    m.add_class::<python::PyAsconXOF128>()?;
+    m.add_class::<python::PyLSH256>()?;
+    m.add_class::<python::PyLSH512>()?;
+    m.add_class::<python::PyKupyna256>()?;
+    m.add_class::<python::PyKupyna512>()?;
+    m.add_class::<python::PyGOST94>()?;
+    m.add_class::<python::PyGOST94CryptoPro>()?;
+    m.add_class::<python::PyHAS160>()?;
+    m.add_class::<python::PyMD6>()?;
+    m.add_class::<python::PyCSHAKE128>()?;
+    m.add_class::<python::PyCSHAKE256>()?;
+    m.add_class::<python::PyTupleHash128>()?;
+    m.add_class::<python::PyTupleHash256>()?;
+    m.add_class::<python::PyParallelHash128>()?;
+    m.add_class::<python::PyParallelHash256>()?;
+    m.add_class::<python::PyTurboSHAKE128>()?;
+    m.add_class::<python::PyTurboSHAKE256>()?;
+    m.add_class::<python::PyKangarooTwelve>()?;
+    m.add_class::<python::PyBLAKE2b>()?;
+    m.add_class::<python::PyBLAKE2s>()?;
+    m.add_class::<python::PyBLAKE2bp>()?;
+    m.add_class::<python::PyBLAKE2sp>()?;
+    m.add_class::<python::PyBLAKE2Xb>()?;
+    m.add_class::<python::PyBLAKE2Xs>()?;
+    m.add_class::<python::PyBLAKE3>()?;
+    m.add_class::<python::PyHMAC>()?;
+    m.add_class::<python::PyHmacDrbg>()?;
+    m.add_class::<python::PyPoly1305>()?;
+    m.add_class::<python::PyChecksumResult>()?;
     m.add_function(wrap_pyfunction!(python::new, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha512_t, m)?)?;
+    m.add_function(wrap_pyfunction!(python::cshake128, m)?)?;
+    m.add_function(wrap_pyfunction!(python::cshake256, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2b, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2s, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2bp, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2sp, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2xb, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake2xs, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake3, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake3_keyed, m)?)?;
+    m.add_function(wrap_pyfunction!(python::blake3_derive_key, m)?)?;
+    m.add_function(wrap_pyfunction!(python::skein256, m)?)?;
+    m.add_function(wrap_pyfunction!(python::skein512, m)?)?;
+    m.add_function(wrap_pyfunction!(python::skein1024, m)?)?;
+    m.add_function(wrap_pyfunction!(python::md6, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha256_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha256_hexdigest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha512_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha512_hexdigest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hash_file, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hash_file_range, m)?)?;
+    m.add_function(wrap_pyfunction!(python::verify_file, m)?)?;
+    m.add_function(wrap_pyfunction!(python::verify_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(python::write_checksum_file, m)?)?;
+    m.add_function(wrap_pyfunction!(python::check_checksum_file, m)?)?;
+    m.add_function(wrap_pyfunction!(python::file_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hmac_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hmac_hexdigest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hmac_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::compare_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(python::poly1305, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hotp, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hotp_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::totp, m)?)?;
+    m.add_function(wrap_pyfunction!(python::totp_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::totp_uri, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hkdf, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hkdf_extract, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hkdf_expand, m)?)?;
+    m.add_function(wrap_pyfunction!(python::hkdf_expand_label, m)?)?;
+    m.add_function(wrap_pyfunction!(python::tls13_derive_secret, m)?)?;
+    m.add_function(wrap_pyfunction!(python::kbkdf_counter, m)?)?;
+    m.add_function(wrap_pyfunction!(python::concat_kdf, m)?)?;
+    m.add_function(wrap_pyfunction!(python::x963_kdf, m)?)?;
+    m.add_function(wrap_pyfunction!(python::jose_other_info, m)?)?;
+    m.add_function(wrap_pyfunction!(python::pbkdf2_hmac, m)?)?;
+    m.add_function(wrap_pyfunction!(python::scrypt, m)?)?;
+    m.add_function(wrap_pyfunction!(python::argon2_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(python::argon2_phc, m)?)?;
+    m.add_function(wrap_pyfunction!(python::argon2_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::bcrypt_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(python::bcrypt_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::bcrypt_pbkdf, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha256_crypt, m)?)?;
+    m.add_function(wrap_pyfunction!(python::sha512_crypt, m)?)?;
+    m.add_function(wrap_pyfunction!(python::crypt_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::password_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(python::password_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(python::password_needs_rehash, m)?)?;
+    m.add("InvalidHashError", m.py().get_type::<python::InvalidHashError>())?;
+    m.add_function(wrap_pyfunction!(python::balloon, m)?)?;
+    m.add_function(wrap_pyfunction!(python::set_fips_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(python::get_fips_mode, m)?)?;
+    m.add("algorithms_available", PySet::new(m.py(), python::registry::names())?)?;
+    python::noncrypto::register(m)?;
     
     Ok(())
 }
@@ -0,0 +1,175 @@
+//! Adler-32 checksum (RFC 1950).
+//!
+//! The checksum of zlib streams: two 16-bit sums modulo 65521, the first
+//! of the bytes (plus one) and the second of the successive first sums.
+//! Weaker than CRC-32 on short inputs, but cheaper to compute.
+//!
+//! # Algorithm Details
+//!
+//! - **Modulus**: 65521, the largest prime below 2^16
+//! - **Speed**: the modulo is deferred over runs of 5552 bytes, the most
+//!   that cannot overflow 32-bit sums
+//! - **Output**: 32 bits, the second sum in the high half, written
+//!   big-endian by `digest()` as in zlib streams
+//! - **Chaining**: the `initial` value is the checksum of preceding data,
+//!   1 for none; [`Adler32::combine`] merges checksums of consecutive
+//!   segments
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+/// Modulus of both sums.
+const MOD: u32 = 65521;
+
+/// Largest run of bytes whose sums fit in 32 bits before reduction.
+const NMAX: usize = 5552;
+
+/// Adler-32 checksum.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Adler32 {
+    initial: u32,
+    a: u32, // Sum of the bytes, plus one
+    b: u32, // Sum of the successive values of `a`
+}
+
+impl Adler32 {
+    /// Creates a new checksum continuing from `initial`, the checksum of
+    /// any preceding data (1 for none).
+    pub fn new(initial: u32) -> Self {
+        Adler32 { initial, a: (initial & 0xffff) % MOD, b: (initial >> 16) % MOD }
+    }
+
+    /// Feeds data into the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        for run in data.chunks(NMAX) {
+            for &byte in run {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+
+    /// Returns the checksum of the data fed so far as an integer, equal to
+    /// `zlib.adler32()`.
+    pub fn intdigest(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    /// Returns the checksum of the data fed so far as 4 big-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial value.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.initial);
+    }
+
+    /// Returns the checksum of the concatenation of two segments from their
+    /// checksums `first` and `second` and the length of the second segment,
+    /// as zlib's `adler32_combine()`.
+    pub fn combine(first: u32, second: u32, second_len: u64) -> u32 {
+        let rem = (second_len % u64::from(MOD)) as u32;
+        let a1 = first & 0xffff;
+        let b1 = first >> 16;
+        // Each byte of the second segment adds `a1` once more to `b`; the
+        // second segment's own `a` and `b` started from 1 instead of `a1`
+        let a = (a1 + (second & 0xffff) + MOD - 1) % MOD;
+        let b = ((rem * a1) % MOD + b1 + (second >> 16) + MOD - rem) % MOD;
+        (b << 16) | a
+    }
+
+    /// Returns the output size in bytes (4 for Adler-32).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the block size in bytes (1: Adler-32 processes single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Checksum for Adler32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        Adler32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        Adler32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        Adler32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Adler32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Adler32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Adler32::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adler32(data: &[u8]) -> u32 {
+        let mut adler = Adler32::default();
+        adler.update(data);
+        adler.intdigest()
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        assert_eq!(adler32(b"123456789"), 0x091e01de);
+        // All-0xff input, long enough to need the deferred reductions
+        assert_eq!(adler32(&[0xff; 100_000]), 0x149a302c);
+    }
+
+    #[test]
+    fn test_resume_and_combine() {
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 89 % 256) as u8).collect();
+        let whole = adler32(&data);
+        for split in [0, 1, 5552, 5553, 12_345, 20_000] {
+            let (head, tail) = data.split_at(split);
+            let mut adler = Adler32::new(adler32(head));
+            adler.update(tail);
+            assert_eq!(adler.intdigest(), whole, "split {}", split);
+            let combined = Adler32::combine(adler32(head), adler32(tail), tail.len() as u64);
+            assert_eq!(combined, whole, "split {}", split);
+        }
+        // Lengths beyond the modulus wrap
+        let zeros = vec![0u8; 70_000];
+        let mut adler = Adler32::new(adler32(b"abc"));
+        adler.update(&zeros);
+        assert_eq!(Adler32::combine(adler32(b"abc"), adler32(&zeros), 70_000), adler.intdigest());
+    }
+}
@@ -0,0 +1,255 @@
+//! CRC-16 checksums.
+//!
+//! A table-driven engine for any 16-bit CRC in the Rocksoft model
+//! (polynomial, initial value, input and output reflection, final XOR),
+//! with presets for the variants found in serial protocols and firmware.
+//!
+//! # Algorithm Details
+//!
+//! - **Presets**: CRC-16/CCITT-FALSE, CRC-16/MODBUS, CRC-16/XMODEM and
+//!   CRC-16/USB, named as in the CRC catalogue
+//! - **Speed**: one 256-entry table lookup per byte, built per parameter set
+//! - **Output**: 16 bits, written big-endian by `digest()` (MODBUS frames
+//!   transmit the low byte first)
+
+use std::str::FromStr;
+
+use super::Checksum;
+use crate::core::ParameterError;
+use crate::utils::to_hex;
+
+/// Parameters of a 16-bit CRC in the Rocksoft model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc16Params {
+    /// Generator polynomial, MSB-first without the x^16 term.
+    pub poly: u16,
+    /// Register value before the first byte.
+    pub init: u16,
+    /// Whether input bytes are processed least significant bit first.
+    pub refin: bool,
+    /// Whether the final register is bit-reversed.
+    pub refout: bool,
+    /// Value XORed into the result.
+    pub xorout: u16,
+}
+
+impl Crc16Params {
+    /// CRC-16/CCITT-FALSE (CRC-16/IBM-3740). Check value 0x29B1.
+    pub const CCITT_FALSE: Crc16Params =
+        Crc16Params { poly: 0x1021, init: 0xffff, refin: false, refout: false, xorout: 0 };
+
+    /// CRC-16/MODBUS. Check value 0x4B37.
+    pub const MODBUS: Crc16Params =
+        Crc16Params { poly: 0x8005, init: 0xffff, refin: true, refout: true, xorout: 0 };
+
+    /// CRC-16/XMODEM. Check value 0x31C3.
+    pub const XMODEM: Crc16Params =
+        Crc16Params { poly: 0x1021, init: 0, refin: false, refout: false, xorout: 0 };
+
+    /// CRC-16/USB. Check value 0xB4C8.
+    pub const USB: Crc16Params =
+        Crc16Params { poly: 0x8005, init: 0xffff, refin: true, refout: true, xorout: 0xffff };
+}
+
+impl FromStr for Crc16Params {
+    type Err = ParameterError;
+
+    /// Looks up a preset by name, ignoring case and accepting `_` for `-`:
+    /// "ccitt-false" (or "ibm-3740"), "modbus", "xmodem" or "usb".
+    fn from_str(name: &str) -> Result<Self, ParameterError> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "ccitt-false" | "ibm-3740" => Ok(Crc16Params::CCITT_FALSE),
+            "modbus" => Ok(Crc16Params::MODBUS),
+            "xmodem" => Ok(Crc16Params::XMODEM),
+            "usb" => Ok(Crc16Params::USB),
+            _ => Err(ParameterError {
+                algorithm: "CRC-16",
+                message: format!(
+                    "unknown variant {:?} (expected \"ccitt-false\", \"modbus\", \"xmodem\" \
+                     or \"usb\")",
+                    name
+                ),
+            }),
+        }
+    }
+}
+
+/// CRC-16 checksum.
+///
+/// Cloning captures the full state, so a clone can be finalized or extended
+/// independently of the original.
+#[derive(Clone)]
+pub struct Crc16 {
+    params: Crc16Params,
+    table: [u16; 256],
+    crc: u16, // Register, bit-reversed when `refin` is set
+}
+
+impl Crc16 {
+    /// Creates a new checksum with the given parameters.
+    pub fn new(params: Crc16Params) -> Self {
+        let mut table = [0u16; 256];
+        let reflected_poly = params.poly.reverse_bits();
+        for (x, entry) in table.iter_mut().enumerate() {
+            let mut crc = if params.refin { x as u16 } else { (x as u16) << 8 };
+            for _ in 0..8 {
+                crc = match (params.refin, crc & 1 != 0, crc & 0x8000 != 0) {
+                    (true, true, _) => (crc >> 1) ^ reflected_poly,
+                    (true, false, _) => crc >> 1,
+                    (false, _, true) => (crc << 1) ^ params.poly,
+                    (false, _, false) => crc << 1,
+                };
+            }
+            *entry = crc;
+        }
+        let mut crc16 = Crc16 { params, table, crc: 0 };
+        crc16.reset();
+        crc16
+    }
+
+    /// Returns the parameters the checksum was created with.
+    pub fn params(&self) -> Crc16Params {
+        self.params
+    }
+
+    /// Feeds data into the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut crc = self.crc;
+        if self.params.refin {
+            for &byte in data {
+                crc = (crc >> 8) ^ self.table[usize::from((crc as u8) ^ byte)];
+            }
+        } else {
+            for &byte in data {
+                crc = (crc << 8) ^ self.table[usize::from(((crc >> 8) as u8) ^ byte)];
+            }
+        }
+        self.crc = crc;
+    }
+
+    /// Returns the checksum of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u16 {
+        let crc = if self.params.refin != self.params.refout {
+            self.crc.reverse_bits()
+        } else {
+            self.crc
+        };
+        crc ^ self.params.xorout
+    }
+
+    /// Returns the checksum of the data fed so far as 2 big-endian bytes.
+    pub fn digest(&self) -> [u8; 2] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial value.
+    pub fn reset(&mut self) {
+        let init = self.params.init;
+        self.crc = if self.params.refin { init.reverse_bits() } else { init };
+    }
+
+    /// Returns the output size in bytes (2 for CRC-16).
+    pub fn digest_size() -> usize {
+        2
+    }
+
+    /// Returns the block size in bytes (1: CRCs process single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Checksum for Crc16 {
+    type Value = u16;
+    type Output = [u8; 2];
+
+    fn update(&mut self, data: &[u8]) {
+        Crc16::update(self, data);
+    }
+
+    fn intdigest(&self) -> u16 {
+        Crc16::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 2] {
+        Crc16::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Crc16::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Crc16::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Crc16::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crc16(params: Crc16Params, data: &[u8]) -> u16 {
+        let mut crc = Crc16::new(params);
+        crc.update(data);
+        crc.intdigest()
+    }
+
+    #[test]
+    fn test_preset_check_values() {
+        // "check" values of the CRC catalogue
+        assert_eq!(crc16(Crc16Params::CCITT_FALSE, b"123456789"), 0x29b1);
+        assert_eq!(crc16(Crc16Params::MODBUS, b"123456789"), 0x4b37);
+        assert_eq!(crc16(Crc16Params::XMODEM, b"123456789"), 0x31c3);
+        assert_eq!(crc16(Crc16Params::USB, b"123456789"), 0xb4c8);
+    }
+
+    #[test]
+    fn test_custom_params() {
+        // Other catalogue entries (CRC-16/KERMIT, CRC-16/GENIBUS, CRC-16/ARC)
+        let kermit = Crc16Params { poly: 0x1021, init: 0, refin: true, refout: true, xorout: 0 };
+        let genibus =
+            Crc16Params { poly: 0x1021, init: 0xffff, refin: false, refout: false, xorout: 0xffff };
+        let arc = Crc16Params { init: 0, ..Crc16Params::MODBUS };
+        assert_eq!(crc16(kermit, b"123456789"), 0x2189);
+        assert_eq!(crc16(genibus, b"123456789"), 0xd64e);
+        assert_eq!(crc16(arc, b"123456789"), 0xbb3d);
+    }
+
+    #[test]
+    fn test_modbus_frame() {
+        // Read Holding Registers request; the CRC is sent low byte first
+        let frame = [0x01, 0x03, 0x00, 0x00, 0x00, 0x0a];
+        assert_eq!(crc16(Crc16Params::MODBUS, &frame).to_le_bytes(), [0xc5, 0xcd]);
+    }
+
+    #[test]
+    fn test_streaming_and_reset() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 13 % 256) as u8).collect();
+        for params in [Crc16Params::CCITT_FALSE, Crc16Params::USB] {
+            let mut crc = Crc16::new(params);
+            for chunk in data.chunks(7) {
+                crc.update(chunk);
+            }
+            assert_eq!(crc.intdigest(), crc16(params, &data));
+            crc.reset();
+            assert_eq!(crc.intdigest(), crc16(params, b""));
+        }
+    }
+
+    #[test]
+    fn test_preset_names() {
+        assert_eq!("MODBUS".parse::<Crc16Params>(), Ok(Crc16Params::MODBUS));
+        assert_eq!("ccitt_false".parse::<Crc16Params>(), Ok(Crc16Params::CCITT_FALSE));
+        assert_eq!("ibm-3740".parse::<Crc16Params>(), Ok(Crc16Params::CCITT_FALSE));
+        assert!("ccitt".parse::<Crc16Params>().is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! CRC-64 checksums.
+//!
+//! Both supported variants use the ECMA-182 polynomial: CRC-64/XZ is the
+//! reflected form with all-ones initial value and final XOR, stored in
+//! `.xz` files and by many artifact stores; CRC-64/ECMA-182 is the plain
+//! MSB-first form with neither.
+//!
+//! # Algorithm Details
+//!
+//! - **Polynomial**: 0x42F0E1EBA9EA3693
+//! - **Speed**: slice-by-8 tables, 8 input bytes per step
+//! - **Output**: 64 bits, written big-endian by `digest()`
+//! - **Chaining**: the `initial` value is the checksum of preceding data, so
+//!   an interrupted computation can be resumed
+
+use std::str::FromStr;
+
+use super::Checksum;
+use crate::core::ParameterError;
+use crate::utils::to_hex;
+
+/// ECMA-182 polynomial.
+const POLY: u64 = 0x42f0e1eba9ea3693;
+
+/// Builds the slice-by-8 tables of the reflected (LSB-first) CRC.
+const fn build_reflected_tables() -> [[u64; 256]; 8] {
+    let poly = POLY.reverse_bits();
+    let mut tables = [[0u64; 256]; 8];
+    let mut x = 0;
+    while x < 256 {
+        let mut crc = x as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            bit += 1;
+        }
+        tables[0][x] = crc;
+        x += 1;
+    }
+    let mut k = 1;
+    while k < 8 {
+        let mut x = 0;
+        while x < 256 {
+            let prev = tables[k - 1][x];
+            tables[k][x] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            x += 1;
+        }
+        k += 1;
+    }
+    tables
+}
+
+/// Builds the slice-by-8 tables of the MSB-first CRC.
+const fn build_tables() -> [[u64; 256]; 8] {
+    let mut tables = [[0u64; 256]; 8];
+    let mut x = 0;
+    while x < 256 {
+        let mut crc = (x as u64) << 56;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc >> 63 != 0 { (crc << 1) ^ POLY } else { crc << 1 };
+            bit += 1;
+        }
+        tables[0][x] = crc;
+        x += 1;
+    }
+    let mut k = 1;
+    while k < 8 {
+        let mut x = 0;
+        while x < 256 {
+            let prev = tables[k - 1][x];
+            tables[k][x] = (prev << 8) ^ tables[0][(prev >> 56) as usize];
+            x += 1;
+        }
+        k += 1;
+    }
+    tables
+}
+
+static REFLECTED_TABLES: [[u64; 256]; 8] = build_reflected_tables();
+static TABLES: [[u64; 256]; 8] = build_tables();
+
+/// Updates a reflected CRC register.
+fn update_reflected(mut crc: u64, data: &[u8]) -> u64 {
+    let t = &REFLECTED_TABLES;
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let v = u64::from_le_bytes(chunk.try_into().unwrap()) ^ crc;
+        crc = t[7][(v & 0xff) as usize]
+            ^ t[6][((v >> 8) & 0xff) as usize]
+            ^ t[5][((v >> 16) & 0xff) as usize]
+            ^ t[4][((v >> 24) & 0xff) as usize]
+            ^ t[3][((v >> 32) & 0xff) as usize]
+            ^ t[2][((v >> 40) & 0xff) as usize]
+            ^ t[1][((v >> 48) & 0xff) as usize]
+            ^ t[0][(v >> 56) as usize];
+    }
+    for &byte in chunks.remainder() {
+        crc = (crc >> 8) ^ t[0][((crc ^ u64::from(byte)) & 0xff) as usize];
+    }
+    crc
+}
+
+/// Updates an MSB-first CRC register.
+fn update_msb_first(mut crc: u64, data: &[u8]) -> u64 {
+    let t = &TABLES;
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let v = u64::from_be_bytes(chunk.try_into().unwrap()) ^ crc;
+        crc = t[7][(v >> 56) as usize]
+            ^ t[6][((v >> 48) & 0xff) as usize]
+            ^ t[5][((v >> 40) & 0xff) as usize]
+            ^ t[4][((v >> 32) & 0xff) as usize]
+            ^ t[3][((v >> 24) & 0xff) as usize]
+            ^ t[2][((v >> 16) & 0xff) as usize]
+            ^ t[1][((v >> 8) & 0xff) as usize]
+            ^ t[0][(v & 0xff) as usize];
+    }
+    for &byte in chunks.remainder() {
+        crc = (crc << 8) ^ t[0][((crc >> 56) ^ u64::from(byte)) as usize];
+    }
+    crc
+}
+
+/// CRC-64 variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crc64Variant {
+    /// CRC-64/XZ (also CRC-64/GO-ECMA): reflected, all-ones initial value
+    /// and final XOR. Check value 0x995DC9BBDF1939FA.
+    Xz,
+    /// CRC-64/ECMA-182: MSB-first, zero initial value and final XOR.
+    /// Check value 0x6C40DF5F0B497347.
+    Ecma182,
+}
+
+impl Crc64Variant {
+    /// Returns the lowercase name accepted by [`FromStr`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Crc64Variant::Xz => "xz",
+            Crc64Variant::Ecma182 => "ecma-182",
+        }
+    }
+
+    /// Value XORed into the register at the start and end.
+    fn xorout(self) -> u64 {
+        match self {
+            Crc64Variant::Xz => u64::MAX,
+            Crc64Variant::Ecma182 => 0,
+        }
+    }
+}
+
+impl FromStr for Crc64Variant {
+    type Err = ParameterError;
+
+    /// Parses a variant name, ignoring case: "xz" (or "go-ecma"), or
+    /// "ecma-182" (or "ecma182", "ecma").
+    fn from_str(name: &str) -> Result<Self, ParameterError> {
+        match name.to_ascii_lowercase().as_str() {
+            "xz" | "go-ecma" => Ok(Crc64Variant::Xz),
+            "ecma-182" | "ecma182" | "ecma" => Ok(Crc64Variant::Ecma182),
+            _ => Err(ParameterError {
+                algorithm: "CRC-64",
+                message: format!("unknown variant {:?} (expected \"xz\" or \"ecma-182\")", name),
+            }),
+        }
+    }
+}
+
+/// CRC-64 checksum.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Crc64 {
+    variant: Crc64Variant,
+    initial: u64,
+    crc: u64, // Register, i.e. the checksum so far with the final XOR undone
+}
+
+impl Crc64 {
+    /// Creates a new checksum continuing from `initial`, the checksum of
+    /// any preceding data (0 for none).
+    pub fn new(variant: Crc64Variant, initial: u64) -> Self {
+        Crc64 { variant, initial, crc: initial ^ variant.xorout() }
+    }
+
+    /// Returns the variant the checksum was created with.
+    pub fn variant(&self) -> Crc64Variant {
+        self.variant
+    }
+
+    /// Feeds data into the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        self.crc = match self.variant {
+            Crc64Variant::Xz => update_reflected(self.crc, data),
+            Crc64Variant::Ecma182 => update_msb_first(self.crc, data),
+        };
+    }
+
+    /// Returns the checksum of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        self.crc ^ self.variant.xorout()
+    }
+
+    /// Returns the checksum of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string,
+    /// as shown by `xz --list -vv`.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial value.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.variant, self.initial);
+    }
+
+    /// Returns the output size in bytes (8 for CRC-64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (1: CRCs process single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Crc64 {
+    fn default() -> Self {
+        Self::new(Crc64Variant::Xz, 0)
+    }
+}
+
+impl Checksum for Crc64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Crc64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Crc64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Crc64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Crc64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Crc64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Crc64::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crc64(variant: Crc64Variant, data: &[u8]) -> u64 {
+        let mut crc = Crc64::new(variant, 0);
+        crc.update(data);
+        crc.intdigest()
+    }
+
+    /// Bit-at-a-time reference of the MSB-first variant.
+    fn ecma_bitwise(data: &[u8]) -> u64 {
+        let mut crc = 0u64;
+        for &byte in data {
+            crc ^= u64::from(byte) << 56;
+            for _ in 0..8 {
+                crc = if crc >> 63 != 0 { (crc << 1) ^ POLY } else { crc << 1 };
+            }
+        }
+        crc
+    }
+
+    #[test]
+    fn test_check_values() {
+        // "check" values of the CRC catalogue
+        assert_eq!(crc64(Crc64Variant::Xz, b"123456789"), 0x995dc9bbdf1939fa);
+        assert_eq!(crc64(Crc64Variant::Ecma182, b"123456789"), 0x6c40df5f0b497347);
+        assert_eq!(crc64(Crc64Variant::Xz, b""), 0);
+        assert_eq!(crc64(Crc64Variant::Ecma182, b""), 0);
+    }
+
+    #[test]
+    fn test_slice_by_8_matches_bitwise() {
+        let data: Vec<u8> = (0..500u32).map(|i| (i * 37 % 256) as u8).collect();
+        for len in [1, 7, 8, 9, 16, 100, 500] {
+            assert_eq!(crc64(Crc64Variant::Ecma182, &data[..len]), ecma_bitwise(&data[..len]));
+        }
+    }
+
+    #[test]
+    fn test_resume_from_initial() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 256) as u8).collect();
+        let (head, tail) = data.split_at(401);
+        for variant in [Crc64Variant::Xz, Crc64Variant::Ecma182] {
+            let mut crc = Crc64::new(variant, crc64(variant, head));
+            crc.update(tail);
+            assert_eq!(crc.intdigest(), crc64(variant, &data), "{:?}", variant);
+            crc.reset();
+            assert_eq!(crc.intdigest(), crc64(variant, head));
+        }
+    }
+
+    #[test]
+    fn test_variant_names() {
+        assert_eq!("XZ".parse::<Crc64Variant>(), Ok(Crc64Variant::Xz));
+        assert_eq!("ecma182".parse::<Crc64Variant>(), Ok(Crc64Variant::Ecma182));
+        for variant in [Crc64Variant::Xz, Crc64Variant::Ecma182] {
+            assert_eq!(variant.name().parse::<Crc64Variant>(), Ok(variant));
+        }
+        assert!("iso".parse::<Crc64Variant>().is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! CityHash64 and FarmHash Fingerprint64.
+//!
+//! Google's CityHash (v1.1) and its successor FarmHash. `Fingerprint64` is
+//! the FarmHash function with a frozen, platform-independent definition:
+//! BigQuery's `FARM_FINGERPRINT` and many stored fingerprints use it.
+//! CityHash64 remains in older data.
+//!
+//! # Algorithm Details
+//!
+//! - **Inputs up to 64 bytes**: one of three fixed-size mixing routines
+//!   (0-16, 17-32, 33-64 bytes); the 33-64 routine differs between the two
+//! - **Longer inputs**: a 56-byte state absorbs 64-byte chunks; FarmHash
+//!   then mixes the last 64 bytes (overlapping the final chunk) with a
+//!   state-dependent multiplier, where CityHash starts from them
+//! - **Output**: 64 bits; there is no streaming form, since both functions
+//!   read the end of the input first
+//!
+//! `farmhash::Hash64()` may select other variants depending on the CPU
+//! (for example for inputs over 256 bytes); only `Fingerprint64`, which is
+//! FarmHash's `na` variant, is stable and provided here.
+
+const K0: u64 = 0xc3a5c85c97cb3127;
+const K1: u64 = 0xb492b66fbe98f273;
+const K2: u64 = 0x9ae16a3b2f90404f;
+const K_MUL: u64 = 0x9ddfea08eb382d69;
+
+fn fetch64(s: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(s[at..at + 8].try_into().unwrap())
+}
+
+fn fetch32(s: &[u8], at: usize) -> u64 {
+    u64::from(u32::from_le_bytes(s[at..at + 4].try_into().unwrap()))
+}
+
+fn shift_mix(val: u64) -> u64 {
+    val ^ (val >> 47)
+}
+
+/// Mixes two words into one with the given multiplier.
+fn hash_len16_mul(u: u64, v: u64, mul: u64) -> u64 {
+    let a = shift_mix((u ^ v).wrapping_mul(mul));
+    let b = shift_mix((v ^ a).wrapping_mul(mul));
+    b.wrapping_mul(mul)
+}
+
+fn hash_len16(u: u64, v: u64) -> u64 {
+    hash_len16_mul(u, v, K_MUL)
+}
+
+fn hash_len0_to16(s: &[u8]) -> u64 {
+    let len = s.len();
+    if len >= 8 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch64(s, 0).wrapping_add(K2);
+        let b = fetch64(s, len - 8);
+        let c = b.rotate_right(37).wrapping_mul(mul).wrapping_add(a);
+        let d = a.rotate_right(25).wrapping_add(b).wrapping_mul(mul);
+        hash_len16_mul(c, d, mul)
+    } else if len >= 4 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch32(s, 0);
+        hash_len16_mul(len as u64 + (a << 3), fetch32(s, len - 4), mul)
+    } else if len > 0 {
+        let y = u32::from(s[0]) + (u32::from(s[len >> 1]) << 8);
+        let z = len as u32 + (u32::from(s[len - 1]) << 2);
+        shift_mix(u64::from(y).wrapping_mul(K2) ^ u64::from(z).wrapping_mul(K0)).wrapping_mul(K2)
+    } else {
+        K2
+    }
+}
+
+fn hash_len17_to32(s: &[u8]) -> u64 {
+    let len = s.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(s, 0).wrapping_mul(K1);
+    let b = fetch64(s, 8);
+    let c = fetch64(s, len - 8).wrapping_mul(mul);
+    let d = fetch64(s, len - 16).wrapping_mul(K2);
+    hash_len16_mul(
+        a.wrapping_add(b).rotate_right(43).wrapping_add(c.rotate_right(30)).wrapping_add(d),
+        a.wrapping_add(b.wrapping_add(K2).rotate_right(18)).wrapping_add(c),
+        mul,
+    )
+}
+
+/// Returns two words mixed from four input words and two seeds.
+fn weak_hash_len32_with_seeds(s: &[u8], at: usize, mut a: u64, mut b: u64) -> (u64, u64) {
+    let (w, x) = (fetch64(s, at), fetch64(s, at + 8));
+    let (y, z) = (fetch64(s, at + 16), fetch64(s, at + 24));
+    a = a.wrapping_add(w);
+    b = b.wrapping_add(a).wrapping_add(z).rotate_right(21);
+    let c = a;
+    a = a.wrapping_add(x).wrapping_add(y);
+    b = b.wrapping_add(a.rotate_right(44));
+    (a.wrapping_add(z), b.wrapping_add(c))
+}
+
+/// Internal state of the long-input loops.
+struct LongState {
+    x: u64,
+    y: u64,
+    z: u64,
+    v: (u64, u64),
+    w: (u64, u64),
+}
+
+impl LongState {
+    /// Absorbs the 64-byte chunk at `s[at..]`, shared by both hashes.
+    fn round(&mut self, s: &[u8], at: usize) {
+        let LongState { x, y, z, v, w } = self;
+        *x = x.wrapping_add(*y).wrapping_add(v.0).wrapping_add(fetch64(s, at + 8));
+        *x = x.rotate_right(37).wrapping_mul(K1);
+        *y = y.wrapping_add(v.1).wrapping_add(fetch64(s, at + 48));
+        *y = y.rotate_right(42).wrapping_mul(K1);
+        *x ^= w.1;
+        *y = y.wrapping_add(v.0).wrapping_add(fetch64(s, at + 40));
+        *z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+        *v = weak_hash_len32_with_seeds(s, at, v.1.wrapping_mul(K1), x.wrapping_add(w.0));
+        let seed = y.wrapping_add(fetch64(s, at + 16));
+        *w = weak_hash_len32_with_seeds(s, at + 32, z.wrapping_add(w.1), seed);
+        std::mem::swap(z, x);
+    }
+}
+
+fn city_hash_len33_to64(s: &[u8]) -> u64 {
+    let len = s.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(s, 0).wrapping_mul(K2);
+    let b = fetch64(s, 8);
+    let c = fetch64(s, len - 24);
+    let d = fetch64(s, len - 32);
+    let e = fetch64(s, 16).wrapping_mul(K2);
+    let f = fetch64(s, 24).wrapping_mul(9);
+    let g = fetch64(s, len - 8);
+    let h = fetch64(s, len - 16).wrapping_mul(mul);
+    let u = a
+        .wrapping_add(g)
+        .rotate_right(43)
+        .wrapping_add(b.rotate_right(30).wrapping_add(c).wrapping_mul(9));
+    let v = (a.wrapping_add(g) ^ d).wrapping_add(f).wrapping_add(1);
+    let w = u.wrapping_add(v).wrapping_mul(mul).swap_bytes().wrapping_add(h);
+    let x = e.wrapping_add(f).rotate_right(42).wrapping_add(c);
+    let y = v.wrapping_add(w).wrapping_mul(mul).swap_bytes().wrapping_add(g).wrapping_mul(mul);
+    let z = e.wrapping_add(f).wrapping_add(c);
+    let a = x.wrapping_add(z).wrapping_mul(mul).wrapping_add(y).swap_bytes().wrapping_add(b);
+    let b = shift_mix(z.wrapping_add(a).wrapping_mul(mul).wrapping_add(d).wrapping_add(h))
+        .wrapping_mul(mul);
+    b.wrapping_add(x)
+}
+
+/// Returns the CityHash64 (v1.1) of `s`.
+pub fn cityhash64(s: &[u8]) -> u64 {
+    let len = s.len();
+    if len <= 16 {
+        return hash_len0_to16(s);
+    } else if len <= 32 {
+        return hash_len17_to32(s);
+    } else if len <= 64 {
+        return city_hash_len33_to64(s);
+    }
+    // Starts from the last 64 bytes, then absorbs the chunks from the front
+    let x = fetch64(s, len - 40);
+    let y = fetch64(s, len - 16).wrapping_add(fetch64(s, len - 56));
+    let z = hash_len16(fetch64(s, len - 48).wrapping_add(len as u64), fetch64(s, len - 24));
+    let mut state = LongState {
+        x: x.wrapping_mul(K1).wrapping_add(fetch64(s, 0)),
+        y,
+        z,
+        v: weak_hash_len32_with_seeds(s, len - 64, len as u64, z),
+        w: weak_hash_len32_with_seeds(s, len - 32, y.wrapping_add(K1), x),
+    };
+    for at in (0..(len - 1) & !63).step_by(64) {
+        state.round(s, at);
+    }
+    let LongState { x, y, z, v, w } = state;
+    hash_len16(
+        hash_len16(v.0, w.0).wrapping_add(shift_mix(y).wrapping_mul(K1)).wrapping_add(z),
+        hash_len16(v.1, w.1).wrapping_add(x),
+    )
+}
+
+/// Returns the CityHash64WithSeed (v1.1) of `s`.
+pub fn cityhash64_with_seed(s: &[u8], seed: u64) -> u64 {
+    cityhash64_with_seeds(s, K2, seed)
+}
+
+/// Returns the CityHash64WithSeeds (v1.1) of `s`.
+pub fn cityhash64_with_seeds(s: &[u8], seed0: u64, seed1: u64) -> u64 {
+    hash_len16(cityhash64(s).wrapping_sub(seed0), seed1)
+}
+
+fn farm_hash_len33_to64(s: &[u8]) -> u64 {
+    let len = s.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(s, 0).wrapping_mul(K2);
+    let b = fetch64(s, 8);
+    let c = fetch64(s, len - 8).wrapping_mul(mul);
+    let d = fetch64(s, len - 16).wrapping_mul(K2);
+    let y = a.wrapping_add(b).rotate_right(43).wrapping_add(c.rotate_right(30)).wrapping_add(d);
+    let z = a.wrapping_add(b.wrapping_add(K2).rotate_right(18)).wrapping_add(c);
+    let z = hash_len16_mul(y, z, mul);
+    let e = fetch64(s, 16).wrapping_mul(mul);
+    let f = fetch64(s, 24);
+    let g = y.wrapping_add(fetch64(s, len - 32)).wrapping_mul(mul);
+    let h = z.wrapping_add(fetch64(s, len - 24)).wrapping_mul(mul);
+    hash_len16_mul(
+        e.wrapping_add(f).rotate_right(43).wrapping_add(g.rotate_right(30)).wrapping_add(h),
+        e.wrapping_add(f.wrapping_add(a).rotate_right(18)).wrapping_add(g),
+        mul,
+    )
+}
+
+/// Returns the FarmHash Fingerprint64 of `s`, equal to BigQuery's
+/// `FARM_FINGERPRINT` taken as unsigned.
+pub fn fingerprint64(s: &[u8]) -> u64 {
+    const SEED: u64 = 81;
+    let len = s.len();
+    if len <= 16 {
+        return hash_len0_to16(s);
+    } else if len <= 32 {
+        return hash_len17_to32(s);
+    } else if len <= 64 {
+        return farm_hash_len33_to64(s);
+    }
+    let y = SEED.wrapping_mul(K1).wrapping_add(113);
+    let mut state = LongState {
+        x: SEED.wrapping_mul(K2).wrapping_add(fetch64(s, 0)),
+        y,
+        z: shift_mix(y.wrapping_mul(K2).wrapping_add(113)).wrapping_mul(K2),
+        v: (0, 0),
+        w: (0, 0),
+    };
+    // Whole chunks, leaving 1 to 64 bytes: a length multiple of 64 keeps
+    // its last chunk for the final round
+    for at in (0..(len - 1) / 64 * 64).step_by(64) {
+        state.round(s, at);
+    }
+    let LongState { mut x, mut y, mut z, mut v, mut w } = state;
+    // The last 64 bytes, overlapping the final chunk, with a
+    // state-dependent multiplier
+    let mul = K1.wrapping_add((z & 0xff) << 1);
+    let at = len - 64;
+    w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+    v.0 = v.0.wrapping_add(w.0);
+    w.0 = w.0.wrapping_add(v.0);
+    x = x.wrapping_add(y).wrapping_add(v.0).wrapping_add(fetch64(s, at + 8));
+    x = x.rotate_right(37).wrapping_mul(mul);
+    y = y.wrapping_add(v.1).wrapping_add(fetch64(s, at + 48)).rotate_right(42).wrapping_mul(mul);
+    x ^= w.1.wrapping_mul(9);
+    y = y.wrapping_add(v.0.wrapping_mul(9)).wrapping_add(fetch64(s, at + 40));
+    z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+    v = weak_hash_len32_with_seeds(s, at, v.1.wrapping_mul(mul), x.wrapping_add(w.0));
+    let seed = y.wrapping_add(fetch64(s, at + 16));
+    w = weak_hash_len32_with_seeds(s, at + 32, z.wrapping_add(w.1), seed);
+    std::mem::swap(&mut z, &mut x);
+    hash_len16_mul(
+        hash_len16_mul(v.0, w.0, mul).wrapping_add(shift_mix(y).wrapping_mul(K0)).wrapping_add(z),
+        hash_len16_mul(v.1, w.1, mul).wrapping_add(x),
+        mul,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test input of CityHash's `city-test.cc`.
+    fn test_data(size: usize) -> Vec<u8> {
+        let (mut a, mut b) = (9u64, 777u64);
+        (0..size as u64)
+            .map(|i| {
+                a = a.wrapping_add(b);
+                b = b.wrapping_add(a);
+                a = (a ^ (a >> 41)).wrapping_mul(K0);
+                b = (b ^ (b >> 41)).wrapping_mul(K0).wrapping_add(i);
+                (b >> 37) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cityhash64_reference() {
+        // Rows of `city-test.cc` (v1.1): input `data[i * i..][..i]`, then
+        // CityHash64, CityHash64WithSeed(1234567) and
+        // CityHash64WithSeeds(1234567, k0)
+        let rows: [(usize, [u64; 3]); 11] = [
+            (0, [0x9ae16a3b2f90404f, 0x75106db890237a4a, 0x3feac5f636039766]),
+            (5, [0x831f448bdc5600b3, 0x62a24be3120a6919, 0x1b44098a41e010da]),
+            (16, [0x03ead5f21d344056, 0xfb6420393cfb05c3, 0x407932394cbbd303]),
+            (17, [0x6abbfde37ee03b5b, 0x83febf188d2cc113, 0xcda7b62d94d5b8ee]),
+            (40, [0x4ec0b54cf1566aff, 0x30d2c7269b206bf4, 0x77c22e82295e1061]),
+            (64, [0xe88419922b87176f, 0xbcf32f41a7ddbf6f, 0xd6ebefd8085c1a0f]),
+            (65, [0x105191e0ec8f7f60, 0x5918dbfcca971e79, 0x6b285c8a944767b9]),
+            (100, [0x6369163565814de6, 0x8feb86fb38d08c2f, 0x4976933485cc9a20]),
+            (128, [0xb2e23e8116c2ba9f, 0x7e4d9c0060101151, 0x3310da5e5028f367]),
+            (200, [0x07fc98006e25cac9, 0x77fee0484cda86a7, 0x376ec3d447060456]),
+            (298, [0x74c0b8a6821faafe, 0xabac39d7491370e7, 0xfaf0b2a48a4e6aed]),
+        ];
+        let data = test_data(298 * 298 + 298);
+        for (i, [plain, seeded, two_seeds]) in rows {
+            let s = &data[i * i..i * i + i];
+            assert_eq!(cityhash64(s), plain, "length {}", i);
+            assert_eq!(cityhash64_with_seed(s, 1234567), seeded, "length {}", i);
+            assert_eq!(cityhash64_with_seeds(s, 1234567, K0), two_seeds, "length {}", i);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint64_reference() {
+        // Computed with the reference farmhash.cc on `data[len * len % 4096..][..len]`
+        let expected: [(usize, u64); 16] = [
+            (0, 0x9ae16a3b2f90404f),
+            (3, 0xef923a7a1af78eab),
+            (7, 0x1b5a063fb4c7f9f1),
+            (16, 0x03ead5f21d344056),
+            (17, 0x6abbfde37ee03b5b),
+            (32, 0x0782fa1b08b475e7),
+            (33, 0x09deb3da5e16b5b8),
+            (64, 0x6a3157c8c0bad83b),
+            (65, 0x77104e3d3c43128f),
+            (127, 0x336ea1574bd21b01),
+            (128, 0x2dcab320149cd602),
+            (129, 0x38eaef06eca0ce55),
+            (256, 0xad3a9ea5a6d95b31),
+            (257, 0xc3a730176e34fea9),
+            (1000, 0xa26396f9567ba544),
+            (2048, 0x74bca38c7f08405a),
+        ];
+        let data = test_data(8192);
+        for (len, value) in expected {
+            let start = len * len % 4096;
+            assert_eq!(fingerprint64(&data[start..start + len]), value, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_fingerprint64_bigquery() {
+        // FARM_FINGERPRINT examples of the BigQuery documentation
+        assert_eq!(fingerprint64(b"1footrue") as i64, -1541654101129638711);
+        assert_eq!(fingerprint64(b"2applefalse") as i64, 2794438866806483259);
+        assert_eq!(fingerprint64(b"3true") as i64, -4880158226897771312);
+    }
+}
+
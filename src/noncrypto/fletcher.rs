@@ -0,0 +1,595 @@
+//! Fletcher checksums.
+//!
+//! Fletcher-16, -32 and -64 keep two running sums of the input words
+//! modulo 2^n - 1 (255, 65535 and 2^32 - 1), the second summing the
+//! successive values of the first. They catch more errors than a plain sum
+//! at similar cost, and are common in sensor frames and embedded protocols.
+//!
+//! # Algorithm Details
+//!
+//! - **Words**: 1, 2 or 4 bytes, little-endian; a trailing partial word is
+//!   zero-padded
+//! - **Reduction**: deferred over runs of 1024 words in 64-bit sums, far
+//!   from overflowing, so streams of any length are exact
+//! - **Output**: the second sum in the high half, written big-endian by
+//!   `digest()`
+//!
+//! [`Fletcher4`] is the ZFS variant: four running sums of 32-bit words,
+//! each summing the successive values of the previous one, kept unreduced
+//! in 64 bits (wrapping on overflow). ZFS checksums every data block with
+//! it by default; its 256-bit result is written as the four sums in order,
+//! each big-endian, matching the `a:b:c:d` form printed by `zdb`.
+
+use super::{Checksum, U256};
+use crate::utils::to_hex;
+
+/// Words summed between two reductions.
+const RUN_WORDS: usize = 1024;
+
+/// Running sums shared by the three widths; `W` is the word size in bytes.
+#[derive(Clone, Copy)]
+struct Sums<const W: usize> {
+    s1: u64,
+    s2: u64,
+    pending: [u8; 4], // Incomplete word
+    pending_len: usize,
+}
+
+impl<const W: usize> Sums<W> {
+    const MODULUS: u64 = (1 << (8 * W)) - 1;
+
+    fn new() -> Self {
+        Sums { s1: 0, s2: 0, pending: [0u8; 4], pending_len: 0 }
+    }
+
+    /// Adds whole words to the sums.
+    fn add_words(&mut self, data: &[u8]) {
+        for run in data.chunks(RUN_WORDS * W) {
+            for word in run.chunks_exact(W) {
+                let mut bytes = [0u8; 8];
+                bytes[..W].copy_from_slice(word);
+                self.s1 += u64::from_le_bytes(bytes);
+                self.s2 += self.s1;
+            }
+            self.s1 %= Self::MODULUS;
+            self.s2 %= Self::MODULUS;
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        if self.pending_len > 0 {
+            let take = data.len().min(W - self.pending_len);
+            let end = self.pending_len + take;
+            self.pending[self.pending_len..end].copy_from_slice(&data[..take]);
+            self.pending_len = end;
+            data = &data[take..];
+            if self.pending_len < W {
+                return;
+            }
+            let word = self.pending;
+            self.add_words(&word[..W]);
+            self.pending_len = 0;
+        }
+        let whole = data.len() / W * W;
+        self.add_words(&data[..whole]);
+        self.pending[..data.len() - whole].copy_from_slice(&data[whole..]);
+        self.pending_len = data.len() - whole;
+    }
+
+    /// Returns `s2 << 8W | s1`, with a partial word zero-padded.
+    fn value(&self) -> u64 {
+        let mut sums = *self;
+        if sums.pending_len > 0 {
+            sums.pending[sums.pending_len..].fill(0);
+            let word = sums.pending;
+            sums.add_words(&word[..W]);
+        }
+        (sums.s2 << (8 * W)) | sums.s1
+    }
+}
+
+/// Fletcher-16 checksum: bytes summed modulo 255.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fletcher16 {
+    sums: Sums<1>,
+}
+
+impl Fletcher16 {
+    /// Creates a new checksum.
+    pub fn new() -> Self {
+        Fletcher16 { sums: Sums::new() }
+    }
+
+    /// Feeds data into the checksum. Words may straddle calls.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sums.update(data);
+    }
+
+    /// Returns the checksum of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u16 {
+        self.sums.value() as u16
+    }
+
+    /// Returns the checksum of the data fed so far as 2 big-endian bytes.
+    pub fn digest(&self) -> [u8; 2] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (2 for Fletcher-16).
+    pub fn digest_size() -> usize {
+        2
+    }
+
+    /// Returns the block size in bytes (1: the word size of Fletcher-16).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Fletcher16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Fletcher16 {
+    type Value = u16;
+    type Output = [u8; 2];
+
+    fn update(&mut self, data: &[u8]) {
+        Fletcher16::update(self, data);
+    }
+
+    fn intdigest(&self) -> u16 {
+        Fletcher16::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 2] {
+        Fletcher16::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fletcher16::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fletcher16::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fletcher16::block_size()
+    }
+}
+
+/// Fletcher-32 checksum: 16-bit little-endian words summed modulo 65535.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fletcher32 {
+    sums: Sums<2>,
+}
+
+impl Fletcher32 {
+    /// Creates a new checksum.
+    pub fn new() -> Self {
+        Fletcher32 { sums: Sums::new() }
+    }
+
+    /// Feeds data into the checksum. Words may straddle calls.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sums.update(data);
+    }
+
+    /// Returns the checksum of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u32 {
+        self.sums.value() as u32
+    }
+
+    /// Returns the checksum of the data fed so far as 4 big-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (4 for Fletcher-32).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the block size in bytes (2: the word size of Fletcher-32).
+    pub fn block_size() -> usize {
+        2
+    }
+}
+
+impl Default for Fletcher32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Fletcher32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        Fletcher32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        Fletcher32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        Fletcher32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fletcher32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fletcher32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fletcher32::block_size()
+    }
+}
+
+/// Fletcher-64 checksum: 32-bit little-endian words summed modulo 2^32 - 1.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fletcher64 {
+    sums: Sums<4>,
+}
+
+impl Fletcher64 {
+    /// Creates a new checksum.
+    pub fn new() -> Self {
+        Fletcher64 { sums: Sums::new() }
+    }
+
+    /// Feeds data into the checksum. Words may straddle calls.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sums.update(data);
+    }
+
+    /// Returns the checksum of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        self.sums.value()
+    }
+
+    /// Returns the checksum of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (8 for Fletcher-64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (4: the word size of Fletcher-64).
+    pub fn block_size() -> usize {
+        4
+    }
+}
+
+impl Default for Fletcher64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Fletcher64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Fletcher64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Fletcher64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Fletcher64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fletcher64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fletcher64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fletcher64::block_size()
+    }
+}
+
+/// ZFS fletcher4 checksum: four wrapping 64-bit sums of 32-bit
+/// little-endian words.
+///
+/// Matches ZFS's `fletcher_4_native` on little-endian hosts. ZFS only sums
+/// whole words; a trailing partial word is zero-padded, as for the other
+/// widths. Copying captures the full state, so a copy can be finalized or
+/// extended independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fletcher4 {
+    sums: [u64; 4],
+    pending: [u8; 4], // Incomplete word
+    pending_len: usize,
+}
+
+impl Fletcher4 {
+    /// Creates a new checksum.
+    pub fn new() -> Self {
+        Fletcher4 { sums: [0; 4], pending: [0u8; 4], pending_len: 0 }
+    }
+
+    /// Adds whole words to the sums.
+    fn add_words(&mut self, data: &[u8]) {
+        let [mut a, mut b, mut c, mut d] = self.sums;
+        for word in data.chunks_exact(4) {
+            a = a.wrapping_add(u32::from_le_bytes(word.try_into().unwrap()) as u64);
+            b = b.wrapping_add(a);
+            c = c.wrapping_add(b);
+            d = d.wrapping_add(c);
+        }
+        self.sums = [a, b, c, d];
+    }
+
+    /// Feeds data into the checksum. Words may straddle calls.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.pending_len > 0 {
+            let take = data.len().min(4 - self.pending_len);
+            let end = self.pending_len + take;
+            self.pending[self.pending_len..end].copy_from_slice(&data[..take]);
+            self.pending_len = end;
+            data = &data[take..];
+            if self.pending_len < 4 {
+                return;
+            }
+            let word = self.pending;
+            self.add_words(&word);
+            self.pending_len = 0;
+        }
+        let whole = data.len() / 4 * 4;
+        self.add_words(&data[..whole]);
+        self.pending[..data.len() - whole].copy_from_slice(&data[whole..]);
+        self.pending_len = data.len() - whole;
+    }
+
+    /// Returns the four sums `[a, b, c, d]`, with a partial word
+    /// zero-padded.
+    pub fn sums(&self) -> [u64; 4] {
+        let mut checksum = *self;
+        if checksum.pending_len > 0 {
+            checksum.pending[checksum.pending_len..].fill(0);
+            let word = checksum.pending;
+            checksum.add_words(&word);
+        }
+        checksum.sums
+    }
+
+    /// Returns the checksum of the data fed so far as an integer, with `a`
+    /// in the most significant word.
+    pub fn intdigest(&self) -> U256 {
+        let [a, b, c, d] = self.sums();
+        U256([d, c, b, a])
+    }
+
+    /// Returns the checksum of the data fed so far as the four sums in
+    /// order, each as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, sum) in bytes.chunks_exact_mut(8).zip(self.sums()) {
+            chunk.copy_from_slice(&sum.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the checksum of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the output size in bytes (32 for fletcher4).
+    pub fn digest_size() -> usize {
+        32
+    }
+
+    /// Returns the block size in bytes (4: the word size of fletcher4).
+    pub fn block_size() -> usize {
+        4
+    }
+}
+
+impl Default for Fletcher4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Fletcher4 {
+    type Value = U256;
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: &[u8]) {
+        Fletcher4::update(self, data);
+    }
+
+    fn intdigest(&self) -> U256 {
+        Fletcher4::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        Fletcher4::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fletcher4::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fletcher4::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fletcher4::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_shot<C: Checksum + Default>(data: &[u8]) -> C::Value {
+        let mut checksum = C::default();
+        checksum.update(data);
+        checksum.intdigest()
+    }
+
+    #[test]
+    fn test_known_vectors() {
+        // Examples of the Wikipedia article, with zero-padded final words
+        assert_eq!(one_shot::<Fletcher16>(b"abcde"), 0xc8f0);
+        assert_eq!(one_shot::<Fletcher16>(b"abcdef"), 0x2057);
+        assert_eq!(one_shot::<Fletcher16>(b"abcdefgh"), 0x0627);
+        assert_eq!(one_shot::<Fletcher32>(b"abcde"), 0xf04fc729);
+        assert_eq!(one_shot::<Fletcher32>(b"abcdef"), 0x56502d2a);
+        assert_eq!(one_shot::<Fletcher32>(b"abcdefgh"), 0xebe19591);
+        assert_eq!(one_shot::<Fletcher64>(b"abcde"), 0xc8c6c527646362c6);
+        assert_eq!(one_shot::<Fletcher64>(b"abcdef"), 0xc8c72b276463c8c6);
+        assert_eq!(one_shot::<Fletcher64>(b"abcdefgh"), 0x312e2b28cccac8c6);
+    }
+
+    #[test]
+    fn test_long_input_reduction() {
+        // All-ones words are congruent to 0, so the sums stay 0 ...
+        assert_eq!(one_shot::<Fletcher32>(&[0xff; 1 << 20]), 0);
+        // ... and one more byte makes the first sum 1 and the second
+        // sum 1 too (the earlier words contributed multiples of the modulus)
+        let mut data = vec![0xffu8; 1 << 20];
+        data.push(1);
+        assert_eq!(one_shot::<Fletcher16>(&data), 0x0101);
+    }
+
+    #[test]
+    fn test_streaming_across_words() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i * 151 % 256) as u8).collect();
+        for step in [1, 2, 3, 5, 4097] {
+            let mut f32 = Fletcher32::new();
+            let mut f64 = Fletcher64::new();
+            for chunk in data.chunks(step) {
+                f32.update(chunk);
+                f64.update(chunk);
+            }
+            assert_eq!(f32.intdigest(), one_shot::<Fletcher32>(&data), "step {}", step);
+            assert_eq!(f64.intdigest(), one_shot::<Fletcher64>(&data), "step {}", step);
+        }
+        let mut f64 = Fletcher64::new();
+        f64.update(b"abc");
+        f64.reset();
+        assert_eq!(f64.intdigest(), 0);
+        assert_eq!(Fletcher16::new().digest(), [0, 0]);
+    }
+
+    fn fletcher4(data: &[u8]) -> [u64; 4] {
+        let mut checksum = Fletcher4::new();
+        checksum.update(data);
+        checksum.sums()
+    }
+
+    #[test]
+    fn test_fletcher4_known_vectors() {
+        // Sums of the fletcher_4_native loop of ZFS
+        assert_eq!(fletcher4(b""), [0; 4]);
+        assert_eq!(fletcher4(b"abcd"), [0x64636261; 4]);
+        assert_eq!(fletcher4(b"abcdefgh"), [0xcccac8c6, 0x1312e2b27, 0x195918d88, 0x1f9f4efe9]);
+        assert_eq!(fletcher4(b"abcde"), [0x646362c6, 0xc8c6c527, 0x12d2a2788, 0x1918d89e9]);
+        let mut checksum = Fletcher4::new();
+        checksum.update(b"abcdefgh");
+        assert_eq!(
+            checksum.hexdigest(),
+            "00000000cccac8c600000001312e2b270000000195918d8800000001f9f4efe9"
+        );
+        assert_eq!(checksum.intdigest(), U256([0x1f9f4efe9, 0x195918d88, 0x1312e2b27, 0xcccac8c6]));
+    }
+
+    #[test]
+    fn test_fletcher4_wrapping_sums() {
+        // n copies of a word w give a = n w, b = w n(n+1)/2,
+        // c = w n(n+1)(n+2)/6 and d = w n(n+1)(n+2)(n+3)/24 modulo 2^64;
+        // a 128 KiB record of 0xFF bytes overflows c and d
+        assert_eq!(
+            fletcher4(&[0xff; 128 * 1024]),
+            [0x7fffffff8000, 0x20003fffdfffc000, 0x75557aaa8aaa8000, 0xc7556d5537ffe000]
+        );
+        let ones: Vec<u8> = [1, 0, 0, 0].repeat(1 << 20);
+        assert_eq!(
+            fletcher4(&ones),
+            [0x100000, 0x8000080000, 0x2aaab2aaab00000, 0xaeaaab2000040000]
+        );
+    }
+
+    #[test]
+    fn test_fletcher4_streaming() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i * 151 % 256) as u8).collect();
+        for step in [1, 2, 3, 5, 4097] {
+            let mut checksum = Fletcher4::new();
+            for chunk in data.chunks(step) {
+                checksum.update(chunk);
+            }
+            assert_eq!(checksum.sums(), fletcher4(&data), "step {}", step);
+        }
+        let mut checksum = Fletcher4::new();
+        checksum.update(b"abc");
+        checksum.reset();
+        assert_eq!(checksum.digest(), [0; 32]);
+    }
+}
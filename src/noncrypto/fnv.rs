@@ -0,0 +1,428 @@
+//! FNV-1 and FNV-1a hashes.
+//!
+//! The Fowler-Noll-Vo hashes multiply by a prime and XOR in one byte at a
+//! time: tiny, fast on short keys, and common for routing and hash-table
+//! keys. FNV-1a (XOR, then multiply) mixes the last byte better than FNV-1
+//! (multiply, then XOR) and is usually preferred.
+//!
+//! # Algorithm Details
+//!
+//! - **Parameters**: the standard offset bases and FNV primes of the
+//!   IETF draft (draft-eastlake-fnv) for 32, 64 and 128 bits
+//! - **Speed**: one multiplication per byte; 128-bit products use `u128`
+//! - **Output**: the final state, written big-endian by `digest()`
+
+use std::str::FromStr;
+
+use super::Checksum;
+use crate::core::ParameterError;
+use crate::utils::to_hex;
+
+/// FNV variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FnvVariant {
+    /// FNV-1: multiply by the prime, then XOR the byte.
+    Fnv1,
+    /// FNV-1a: XOR the byte, then multiply by the prime.
+    Fnv1a,
+}
+
+impl FnvVariant {
+    /// Returns the lowercase name accepted by [`FromStr`].
+    pub fn name(self) -> &'static str {
+        match self {
+            FnvVariant::Fnv1 => "fnv1",
+            FnvVariant::Fnv1a => "fnv1a",
+        }
+    }
+}
+
+impl FromStr for FnvVariant {
+    type Err = ParameterError;
+
+    /// Parses a variant name, ignoring case and dashes: "fnv1" or "fnv1a".
+    fn from_str(name: &str) -> Result<Self, ParameterError> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "fnv1" => Ok(FnvVariant::Fnv1),
+            "fnv1a" => Ok(FnvVariant::Fnv1a),
+            _ => Err(ParameterError {
+                algorithm: "FNV",
+                message: format!("unknown variant {:?} (expected \"fnv1\" or \"fnv1a\")", name),
+            }),
+        }
+    }
+}
+
+const OFFSET_32: u32 = 0x811c9dc5;
+const PRIME_32: u32 = 0x01000193;
+
+/// 32-bit FNV hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fnv32 {
+    variant: FnvVariant,
+    hash: u32,
+}
+
+impl Fnv32 {
+    /// Creates a new hasher of the given variant.
+    pub fn new(variant: FnvVariant) -> Self {
+        Fnv32 { variant, hash: OFFSET_32 }
+    }
+
+    /// Returns the variant the hasher was created with.
+    pub fn variant(&self) -> FnvVariant {
+        self.variant
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut hash = self.hash;
+        match self.variant {
+            FnvVariant::Fnv1 => {
+                for &byte in data {
+                    hash = hash.wrapping_mul(PRIME_32) ^ u32::from(byte);
+                }
+            }
+            FnvVariant::Fnv1a => {
+                for &byte in data {
+                    hash = (hash ^ u32::from(byte)).wrapping_mul(PRIME_32);
+                }
+            }
+        }
+        self.hash = hash;
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u32 {
+        self.hash
+    }
+
+    /// Returns the hash of the data fed so far as 4 big-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.hash.to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to the offset basis.
+    pub fn reset(&mut self) {
+        self.hash = OFFSET_32;
+    }
+
+    /// Returns the output size in bytes (4 for 32-bit FNV).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the block size in bytes (1: FNV processes single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Fnv32 {
+    fn default() -> Self {
+        Self::new(FnvVariant::Fnv1a)
+    }
+}
+
+impl Checksum for Fnv32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        Fnv32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        Fnv32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        Fnv32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fnv32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fnv32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fnv32::block_size()
+    }
+}
+
+const OFFSET_64: u64 = 0xcbf29ce484222325;
+const PRIME_64: u64 = 0x00000100000001b3;
+
+/// 64-bit FNV hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fnv64 {
+    variant: FnvVariant,
+    hash: u64,
+}
+
+impl Fnv64 {
+    /// Creates a new hasher of the given variant.
+    pub fn new(variant: FnvVariant) -> Self {
+        Fnv64 { variant, hash: OFFSET_64 }
+    }
+
+    /// Returns the variant the hasher was created with.
+    pub fn variant(&self) -> FnvVariant {
+        self.variant
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut hash = self.hash;
+        match self.variant {
+            FnvVariant::Fnv1 => {
+                for &byte in data {
+                    hash = hash.wrapping_mul(PRIME_64) ^ u64::from(byte);
+                }
+            }
+            FnvVariant::Fnv1a => {
+                for &byte in data {
+                    hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME_64);
+                }
+            }
+        }
+        self.hash = hash;
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the hash of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.hash.to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to the offset basis.
+    pub fn reset(&mut self) {
+        self.hash = OFFSET_64;
+    }
+
+    /// Returns the output size in bytes (8 for 64-bit FNV).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (1: FNV processes single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Fnv64 {
+    fn default() -> Self {
+        Self::new(FnvVariant::Fnv1a)
+    }
+}
+
+impl Checksum for Fnv64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Fnv64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Fnv64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Fnv64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fnv64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fnv64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fnv64::block_size()
+    }
+}
+
+const OFFSET_128: u128 = 0x6c62272e07bb014262b821756295c58d;
+const PRIME_128: u128 = 0x0000000001000000000000000000013b;
+
+/// 128-bit FNV hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Fnv128 {
+    variant: FnvVariant,
+    hash: u128,
+}
+
+impl Fnv128 {
+    /// Creates a new hasher of the given variant.
+    pub fn new(variant: FnvVariant) -> Self {
+        Fnv128 { variant, hash: OFFSET_128 }
+    }
+
+    /// Returns the variant the hasher was created with.
+    pub fn variant(&self) -> FnvVariant {
+        self.variant
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut hash = self.hash;
+        match self.variant {
+            FnvVariant::Fnv1 => {
+                for &byte in data {
+                    hash = hash.wrapping_mul(PRIME_128) ^ u128::from(byte);
+                }
+            }
+            FnvVariant::Fnv1a => {
+                for &byte in data {
+                    hash = (hash ^ u128::from(byte)).wrapping_mul(PRIME_128);
+                }
+            }
+        }
+        self.hash = hash;
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u128 {
+        self.hash
+    }
+
+    /// Returns the hash of the data fed so far as 16 big-endian bytes.
+    pub fn digest(&self) -> [u8; 16] {
+        self.hash.to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to the offset basis.
+    pub fn reset(&mut self) {
+        self.hash = OFFSET_128;
+    }
+
+    /// Returns the output size in bytes (16 for 128-bit FNV).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (1: FNV processes single bytes).
+    pub fn block_size() -> usize {
+        1
+    }
+}
+
+impl Default for Fnv128 {
+    fn default() -> Self {
+        Self::new(FnvVariant::Fnv1a)
+    }
+}
+
+impl Checksum for Fnv128 {
+    type Value = u128;
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        Fnv128::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        Fnv128::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Fnv128::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Fnv128::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Fnv128::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Fnv128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fnv<C: Checksum>(mut hasher: C, data: &[u8]) -> C::Value {
+        hasher.update(data);
+        hasher.intdigest()
+    }
+
+    #[test]
+    fn test_fnv1_vectors() {
+        let v = FnvVariant::Fnv1;
+        assert_eq!(fnv(Fnv32::new(v), b""), 0x811c9dc5);
+        assert_eq!(fnv(Fnv32::new(v), b"a"), 0x050c5d7e);
+        assert_eq!(fnv(Fnv32::new(v), b"foobar"), 0x31f0b262);
+        assert_eq!(fnv(Fnv64::new(v), b""), 0xcbf29ce484222325);
+        assert_eq!(fnv(Fnv64::new(v), b"a"), 0xaf63bd4c8601b7be);
+        assert_eq!(fnv(Fnv64::new(v), b"foobar"), 0x340d8765a4dda9c2);
+        assert_eq!(fnv(Fnv128::new(v), b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_eq!(fnv(Fnv128::new(v), b"a"), 0xd228cb69101a8caf78912b704e4a141e);
+        assert_eq!(fnv(Fnv128::new(v), b"foobar"), 0x7896bfea9c3c64bf6dc58353d2c293aa);
+    }
+
+    #[test]
+    fn test_fnv1a_vectors() {
+        let v = FnvVariant::Fnv1a;
+        assert_eq!(fnv(Fnv32::new(v), b""), 0x811c9dc5);
+        assert_eq!(fnv(Fnv32::new(v), b"a"), 0xe40c292c);
+        assert_eq!(fnv(Fnv32::new(v), b"foobar"), 0xbf9cf968);
+        assert_eq!(fnv(Fnv64::new(v), b""), 0xcbf29ce484222325);
+        assert_eq!(fnv(Fnv64::new(v), b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv(Fnv64::new(v), b"foobar"), 0x85944171f73967e8);
+        assert_eq!(fnv(Fnv128::new(v), b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_eq!(fnv(Fnv128::new(v), b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+        assert_eq!(fnv(Fnv128::new(v), b"foobar"), 0x343e1662793c64bf6f0d3597ba446f18);
+    }
+
+    #[test]
+    fn test_streaming_and_reset() {
+        let mut hasher = Fnv64::new(FnvVariant::Fnv1a);
+        hasher.update(b"foo");
+        hasher.update(b"bar");
+        assert_eq!(hasher.intdigest(), 0x85944171f73967e8);
+        hasher.reset();
+        assert_eq!(hasher.intdigest(), OFFSET_64);
+        assert_eq!("FNV-1A".parse::<FnvVariant>(), Ok(FnvVariant::Fnv1a));
+        assert!("fnv0".parse::<FnvVariant>().is_err());
+    }
+}
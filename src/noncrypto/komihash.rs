@@ -0,0 +1,357 @@
+//! komihash.
+//!
+//! Aleksey Vaneev's komihash (version 5), a fast 64-bit hash built on
+//! 64x64 to 128-bit multiplications, used by caches and hash tables. Values
+//! match the reference `komihash()` and `komihash_stream_*()` functions,
+//! which agree with each other for any split of the input.
+//!
+//! # Algorithm Details
+//!
+//! - **Seed**: 64 bits, split into its odd and even bits to initialize two
+//!   state words
+//! - **Short inputs**: below 16 bytes, the message goes straight to the
+//!   final rounds; from 16 to 31 bytes, one 16-byte round comes first
+//! - **Bulk**: 64-byte blocks over eight state words, folded back into two
+//!   once the last full block is mixed; the remaining 32- and 16-byte
+//!   pieces each take their own rounds
+//! - **Padding**: the final bytes are read with a 1 bit right after them,
+//!   so that trailing zeros change the value
+//! - **Output**: 64 bits, written big-endian by `digest()`
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+/// Block size of the bulk loop.
+const BLOCK: usize = 64;
+
+/// Constants XORed into the state words that only the bulk loop uses.
+const LANES: [u64; 6] = [
+    0x13198a2e03707344,
+    0xa4093822299f31d0,
+    0x082efa98ec4e6c89,
+    0xbe5466cf34e90c6c,
+    0xc0ac29b7c97c50dd,
+    0x3f84d5b5b5470917,
+];
+
+fn mum(a: u64, b: u64) -> (u64, u64) {
+    let r = u128::from(a) * u128::from(b);
+    (r as u64, (r >> 64) as u64)
+}
+
+fn read64(s: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(s[at..at + 8].try_into().unwrap())
+}
+
+/// Reads up to 7 bytes as a little-endian integer, with a 1 bit above them.
+fn read_padded(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word) | (1 << (8 * bytes.len()))
+}
+
+/// Mixes two input words into the seeds `s1` and `s5`.
+fn round(s1: &mut u64, s5: &mut u64, a: u64, b: u64) {
+    let (low, high) = mum(a ^ *s1, b ^ *s5);
+    *s5 = s5.wrapping_add(high);
+    *s1 = *s5 ^ low;
+}
+
+/// Returns the seeds `s1` and `s5` derived from `seed`.
+fn init(seed: u64) -> (u64, u64) {
+    let mut s1 = 0x243f6a8885a308d3 ^ (seed & 0x5555555555555555);
+    let mut s5 = 0x452821e638d01377 ^ (seed & 0xaaaaaaaaaaaaaaaa);
+    round(&mut s1, &mut s5, 0, 0);
+    (s1, s5)
+}
+
+/// Returns the eight state words of the bulk loop, `s1` to `s8`.
+fn lanes(s1: u64, s5: u64) -> [u64; 8] {
+    [
+        s1,
+        LANES[0] ^ s1,
+        LANES[1] ^ s1,
+        LANES[2] ^ s1,
+        s5,
+        LANES[3] ^ s5,
+        LANES[4] ^ s5,
+        LANES[5] ^ s5,
+    ]
+}
+
+/// Mixes a 64-byte block into the eight state words.
+fn mix_block(s: &mut [u64; 8], block: &[u8]) {
+    let mut low = [0u64; 4];
+    for i in 0..4 {
+        let (l, h) = mum(read64(block, 8 * i) ^ s[i], read64(block, 8 * i + 32) ^ s[4 + i]);
+        s[4 + i] = s[4 + i].wrapping_add(h);
+        low[i] = l;
+    }
+    // Each low half goes to the next lane, so the lanes depend on each other
+    for i in 0..4 {
+        s[(i + 1) % 4] = s[4 + i] ^ low[(i + 1) % 4];
+    }
+}
+
+/// Folds the eight state words back into `s1` and `s5`.
+fn fold(s: &[u64; 8]) -> (u64, u64) {
+    (s[0] ^ s[1] ^ s[2] ^ s[3], s[4] ^ s[5] ^ s[6] ^ s[7])
+}
+
+/// Mixes the last bytes (fewer than 64) of a non-empty message and returns
+/// the hash.
+fn finish(mut s1: u64, mut s5: u64, mut tail: &[u8]) -> u64 {
+    if tail.len() >= 32 {
+        round(&mut s1, &mut s5, read64(tail, 0), read64(tail, 8));
+        round(&mut s1, &mut s5, read64(tail, 16), read64(tail, 24));
+        tail = &tail[32..];
+    }
+    if tail.len() >= 16 {
+        round(&mut s1, &mut s5, read64(tail, 0), read64(tail, 8));
+        tail = &tail[16..];
+    }
+    let (a, b) = if tail.len() >= 8 {
+        (read64(tail, 0), read_padded(&tail[8..]))
+    } else {
+        (read_padded(tail), 0)
+    };
+    round(&mut s1, &mut s5, a, b);
+    round(&mut s1, &mut s5, 0, 0);
+    s1
+}
+
+/// Returns the hash of an empty message.
+fn finish_empty(mut s1: u64, mut s5: u64) -> u64 {
+    round(&mut s1, &mut s5, 0, 0);
+    round(&mut s1, &mut s5, 0, 0);
+    s1
+}
+
+/// Returns the komihash of `data`, as the reference `komihash()`.
+pub fn komihash(data: &[u8], seed: u64) -> u64 {
+    let (mut s1, mut s5) = init(seed);
+    if data.is_empty() {
+        return finish_empty(s1, s5);
+    }
+    let mut tail = data;
+    if data.len() >= BLOCK {
+        let mut s = lanes(s1, s5);
+        let mut blocks = data.chunks_exact(BLOCK);
+        for block in &mut blocks {
+            mix_block(&mut s, block);
+        }
+        (s1, s5) = fold(&s);
+        tail = blocks.remainder();
+    }
+    finish(s1, s5, tail)
+}
+
+/// Streaming komihash hasher, as the reference `komihash_stream_*()`.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Komihash {
+    seed: u64,
+    s: [u64; 8],
+    buffer: [u8; BLOCK],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl Komihash {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u64) -> Self {
+        let (s1, s5) = init(seed);
+        Komihash { seed, s: lanes(s1, s5), buffer: [0u8; BLOCK], buffer_len: 0, total_len: 0 }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.buffer_len > 0 {
+            let take = data.len().min(BLOCK - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < BLOCK {
+                return;
+            }
+            let block = self.buffer;
+            mix_block(&mut self.s, &block);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(BLOCK);
+        for block in &mut blocks {
+            mix_block(&mut self.s, block);
+        }
+        let tail = blocks.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        if self.total_len == 0 {
+            return finish_empty(self.s[0], self.s[4]);
+        }
+        let (s1, s5) =
+            if self.total_len >= BLOCK as u64 { fold(&self.s) } else { (self.s[0], self.s[4]) };
+        finish(s1, s5, &self.buffer[..self.buffer_len])
+    }
+
+    /// Returns the hash of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (8 for komihash).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (64 for komihash).
+    pub fn block_size() -> usize {
+        BLOCK
+    }
+}
+
+impl Default for Komihash {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Komihash {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Komihash::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Komihash::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Komihash::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Komihash::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Komihash::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Komihash::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRINGS: [&[u8]; 5] = [
+        b"This is a 32-byte testing string",
+        b"The cat is out of the bag",
+        b"A 16-byte string",
+        b"The new string",
+        b"7 chars",
+    ];
+
+    const BULK_LENGTHS: [usize; 17] =
+        [3, 6, 8, 12, 20, 31, 32, 40, 47, 48, 56, 64, 72, 80, 112, 132, 256];
+
+    /// Test vectors of the reference README: the strings above, then the
+    /// bytes 0, 1, ..., n - 1 for each of `BULK_LENGTHS`.
+    const REFERENCE: [(u64, [u64; 22]); 3] = [
+        (
+            0,
+            [
+                0x05ad960802903a9d, 0xd15723521d3c37b1, 0x467caa28ea3da7a6, 0xf18e67bc90c43233,
+                0x2c514f6e5dcb11cb, 0x7a9717e9eea4be8b, 0xa56469564c2ea0ff, 0x00b4313a24431306,
+                0x64c2ad96013f70fe, 0x7a3888bc95545364, 0xc77e02ed4b201b9a, 0x256d74350303a1ba,
+                0x59609c71697bb9df, 0x36eb9e6a4c2c5e4b, 0x8dd56c332850baa6, 0xcbb722192b353999,
+                0x90b07e2158f88cc0, 0x24c9621701603741, 0x1d4c1d97ca684334, 0xd1a425d530652287,
+                0x72623be342c20ab5, 0x94c3dbdca59ddf57,
+            ],
+        ),
+        (
+            0x0123456789abcdef,
+            [
+                0x6ce66a2e8d4979a5, 0x5b1da0b43545d196, 0x26af914213d0c915, 0x62d9ca1b73250cb5,
+                0x90ab7c9f831cd940, 0x84ae4eb65b96617e, 0xaceebc32a3c0d9e4, 0xdaa1a90ecb95f6f8,
+                0xec8eb3ef4af380b4, 0x07045bd31abba34c, 0xd5f619fb2e62c4ae, 0x5a336fd2c4c39abe,
+                0x0e870b4623eea8ec, 0xe552edd6bf419d1d, 0x37d170ddcb1223e6, 0x1cd89e708e5098b6,
+                0x765490569ccd77f2, 0x19e9d77b86d01ee8, 0x25f83ee520c1d241, 0xd6007417091cd4c0,
+                0x3e49c2d3727b9cc9, 0xb2b3405ee5d65f4c,
+            ],
+        ),
+        (
+            0x100,
+            [
+                0x5f197b30bcec1e45, 0xa761280322bb7698, 0x11c31ccabaa524f1, 0x3a43b7f58281c229,
+                0xcff90b0466b7e3a2, 0x8ab53f45cc9315e3, 0xea606e43d1976ccf, 0x889b2f2ceecbec73,
+                0xacbec1886cd23275, 0x57c3affd1b71fcdb, 0x7ef6ba49a3b068c3, 0x49dbca62ed5a1ddf,
+                0x192848484481e8c0, 0x420b43a5edba1bd7, 0xd6e8400a9de24ce3, 0xbea291b225ff384d,
+                0x0ec94062b2f06960, 0xfa613272ecd49985, 0x76f0bb380bc207be, 0x4afb4e08ca77c020,
+                0x410f9c129ad88aea, 0x066c7b25f4f569ae,
+            ],
+        ),
+    ];
+
+    fn stream(data: &[u8], seed: u64, step: usize) -> u64 {
+        let mut hasher = Komihash::new(seed);
+        for chunk in data.chunks(step.max(1)) {
+            hasher.update(chunk);
+        }
+        hasher.intdigest()
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        let bulk: Vec<u8> = (0..=255).collect();
+        for (seed, expected) in REFERENCE {
+            let inputs = STRINGS.iter().copied().chain(BULK_LENGTHS.iter().map(|&n| &bulk[..n]));
+            for (data, expected) in inputs.zip(expected) {
+                assert_eq!(komihash(data, seed), expected, "seed {:#x} len {}", seed, data.len());
+                assert_eq!(stream(data, seed, data.len()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 73 % 256) as u8).collect();
+        for len in [0, 1, 7, 8, 15, 16, 31, 32, 63, 64, 65, 127, 128, 1000] {
+            let whole = komihash(&data[..len], 9);
+            for step in [1, 3, 16, 63, 64, 65] {
+                assert_eq!(stream(&data[..len], 9, step), whole, "len {} step {}", len, step);
+            }
+        }
+        let mut hasher = Komihash::new(9);
+        hasher.update(b"abc");
+        hasher.reset();
+        assert_eq!(hasher.intdigest(), komihash(b"", 9));
+        assert_eq!(hasher.digest(), komihash(b"", 9).to_be_bytes());
+    }
+}
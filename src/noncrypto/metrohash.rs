@@ -0,0 +1,426 @@
+//! MetroHash64 and MetroHash128.
+//!
+//! J. Andrew Rogers' fast non-cryptographic hashes, found in columnar
+//! databases and storage engines. Values match the streaming `MetroHash64`
+//! and `MetroHash128` classes of the reference implementation (v1.1), which
+//! also back its one-shot `Hash()` functions.
+//!
+//! # Algorithm Details
+//!
+//! - **Seed**: 64 bits, as in the reference classes
+//! - **Blocks**: 32 bytes, four little-endian 64-bit lanes; the trailing
+//!   bytes are mixed in 16, 8, 4, 2 and 1-byte steps at finalization
+//! - **Output**: 64 or 128 bits, written by `digest()` in the byte order of
+//!   the reference output buffer (the state words, little-endian)
+//!
+//! The older `metrohash64_1`/`metrohash64_2` and `metrohash128_1`/
+//! `metrohash128_2` functions (as in SMHasher) mix the length into the seed
+//! and use other rotations, so their values differ; they are not provided.
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+/// Reads a little-endian integer of up to 8 bytes.
+fn read_le(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+/// Input buffering and bulk loop shared by both widths.
+///
+/// The bulk loop is the same for MetroHash64 and MetroHash128; only the
+/// multipliers `k` differ.
+#[derive(Clone, Copy)]
+struct Bulk {
+    v: [u64; 4],
+    buffer: [u8; 32],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl Bulk {
+    fn new(v: [u64; 4]) -> Self {
+        Bulk { v, buffer: [0u8; 32], buffer_len: 0, total_len: 0 }
+    }
+
+    fn mix(&mut self, k: &[u64; 4], block: &[u8]) {
+        let v = &mut self.v;
+        for i in 0..4 {
+            let lane = read_le(&block[8 * i..8 * i + 8]);
+            v[i] = v[i].wrapping_add(lane.wrapping_mul(k[i]));
+            v[i] = v[i].rotate_right(29).wrapping_add(v[(i + 2) % 4]);
+        }
+    }
+
+    fn update(&mut self, k: &[u64; 4], mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.buffer_len > 0 {
+            let take = data.len().min(32 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 32 {
+                return;
+            }
+            let block = self.buffer;
+            self.mix(k, &block);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(32);
+        for block in &mut blocks {
+            self.mix(k, block);
+        }
+        let tail = blocks.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the bytes not yet mixed, fewer than 32.
+    fn tail(&self) -> &[u8] {
+        &self.buffer[..self.buffer_len]
+    }
+}
+
+const K64: [u64; 4] = [0xd6d018f5, 0xa2aa033b, 0x62992fc1, 0x30bc5b29];
+const K128: [u64; 4] = [0xc83a91e1, 0x8648dbdb, 0x7bdec03b, 0x2f5870a5];
+
+/// MetroHash64 hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct MetroHash64 {
+    seed: u64,
+    bulk: Bulk,
+}
+
+impl MetroHash64 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u64) -> Self {
+        let vseed = Self::vseed(seed);
+        MetroHash64 { seed, bulk: Bulk::new([vseed; 4]) }
+    }
+
+    fn vseed(seed: u64) -> u64 {
+        seed.wrapping_add(K64[2]).wrapping_mul(K64[0])
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.bulk.update(&K64, data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer, the
+    /// little-endian value of `digest()`.
+    pub fn intdigest(&self) -> u64 {
+        let [k0, k1, k2, k3] = K64;
+        let mut h = Self::vseed(self.seed);
+        if self.bulk.total_len >= 32 {
+            let mut v = self.bulk.v;
+            let fold = |a: u64, b: u64, c: u64, ka: u64| {
+                a.wrapping_add(b).wrapping_mul(ka).wrapping_add(c).rotate_right(37)
+            };
+            v[2] ^= fold(v[0], v[3], v[1], k0).wrapping_mul(k1);
+            v[3] ^= fold(v[1], v[2], v[0], k1).wrapping_mul(k0);
+            v[0] ^= fold(v[0], v[2], v[3], k0).wrapping_mul(k1);
+            v[1] ^= fold(v[1], v[3], v[2], k1).wrapping_mul(k0);
+            h = h.wrapping_add(v[0] ^ v[1]);
+        }
+        let mut tail = self.bulk.tail();
+        if tail.len() >= 16 {
+            let mut v0 = h.wrapping_add(read_le(&tail[..8]).wrapping_mul(k2));
+            let mut v1 = h.wrapping_add(read_le(&tail[8..16]).wrapping_mul(k2));
+            v0 = v0.rotate_right(29).wrapping_mul(k3);
+            v1 = v1.rotate_right(29).wrapping_mul(k3);
+            v0 ^= v0.wrapping_mul(k0).rotate_right(21).wrapping_add(v1);
+            v1 ^= v1.wrapping_mul(k3).rotate_right(21).wrapping_add(v0);
+            h = h.wrapping_add(v1);
+            tail = &tail[16..];
+        }
+        for (size, rotation) in [(8, 55), (4, 26), (2, 48), (1, 37)] {
+            if tail.len() >= size {
+                h = h.wrapping_add(read_le(&tail[..size]).wrapping_mul(k3));
+                h ^= h.rotate_right(rotation).wrapping_mul(k1);
+                tail = &tail[size..];
+            }
+        }
+        h ^= h.rotate_right(28);
+        h = h.wrapping_mul(k0);
+        h ^ h.rotate_right(29)
+    }
+
+    /// Returns the hash of the data fed so far as 8 bytes, in the byte
+    /// order of the reference output (little-endian).
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (8 for MetroHash64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (32 for MetroHash64).
+    pub fn block_size() -> usize {
+        32
+    }
+}
+
+impl Default for MetroHash64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for MetroHash64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        MetroHash64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        MetroHash64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        MetroHash64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        MetroHash64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        MetroHash64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        MetroHash64::block_size()
+    }
+}
+
+/// MetroHash128 hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct MetroHash128 {
+    seed: u64,
+    bulk: Bulk,
+}
+
+impl MetroHash128 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u64) -> Self {
+        let [k0, k1, k2, k3] = K128;
+        let v = [
+            seed.wrapping_sub(k0).wrapping_mul(k3),
+            seed.wrapping_add(k1).wrapping_mul(k2),
+            seed.wrapping_add(k0).wrapping_mul(k2),
+            seed.wrapping_sub(k1).wrapping_mul(k3),
+        ];
+        MetroHash128 { seed, bulk: Bulk::new(v) }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.bulk.update(&K128, data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer, the
+    /// little-endian value of `digest()`.
+    pub fn intdigest(&self) -> u128 {
+        let [k0, k1, k2, k3] = K128;
+        let mut v = self.bulk.v;
+        if self.bulk.total_len >= 32 {
+            let fold = |a: u64, b: u64, c: u64, ka: u64| {
+                a.wrapping_add(b).wrapping_mul(ka).wrapping_add(c).rotate_right(21)
+            };
+            v[2] ^= fold(v[0], v[3], v[1], k0).wrapping_mul(k1);
+            v[3] ^= fold(v[1], v[2], v[0], k1).wrapping_mul(k0);
+            v[0] ^= fold(v[0], v[2], v[3], k0).wrapping_mul(k1);
+            v[1] ^= fold(v[1], v[3], v[2], k1).wrapping_mul(k0);
+        }
+        // Each step mixes into v0 or v1 alternately, the other one feeding it
+        let step = |v: &mut [u64; 4], i: usize, bytes: &[u8], rotation: u32| {
+            let (kv, kx) = if i == 0 { (k2, k1) } else { (k3, k0) };
+            let x = v[i].wrapping_add(read_le(bytes).wrapping_mul(k2));
+            v[i] = x.rotate_right(33).wrapping_mul(k3);
+            let mixed = v[i].wrapping_mul(kv).wrapping_add(v[1 - i]).rotate_right(rotation);
+            v[i] ^= mixed.wrapping_mul(kx);
+        };
+        let mut tail = self.bulk.tail();
+        if tail.len() >= 16 {
+            for i in 0..2 {
+                let x = v[i].wrapping_add(read_le(&tail[8 * i..8 * i + 8]).wrapping_mul(k2));
+                v[i] = x.rotate_right(33).wrapping_mul(k3);
+            }
+            v[0] ^= v[0].wrapping_mul(k2).wrapping_add(v[1]).rotate_right(45).wrapping_mul(k1);
+            v[1] ^= v[1].wrapping_mul(k3).wrapping_add(v[0]).rotate_right(45).wrapping_mul(k0);
+            tail = &tail[16..];
+        }
+        for (i, size, rotation) in [(0, 8, 27), (1, 4, 46), (0, 2, 22), (1, 1, 58)] {
+            if tail.len() >= size {
+                step(&mut v, i, &tail[..size], rotation);
+                tail = &tail[size..];
+            }
+        }
+        v[0] = v[0].wrapping_add(v[0].wrapping_mul(k0).wrapping_add(v[1]).rotate_right(13));
+        v[1] = v[1].wrapping_add(v[1].wrapping_mul(k1).wrapping_add(v[0]).rotate_right(37));
+        v[0] = v[0].wrapping_add(v[0].wrapping_mul(k2).wrapping_add(v[1]).rotate_right(13));
+        v[1] = v[1].wrapping_add(v[1].wrapping_mul(k3).wrapping_add(v[0]).rotate_right(37));
+        (u128::from(v[1]) << 64) | u128::from(v[0])
+    }
+
+    /// Returns the hash of the data fed so far as 16 bytes, in the byte
+    /// order of the reference output (two little-endian words).
+    pub fn digest(&self) -> [u8; 16] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (16 for MetroHash128).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (32 for MetroHash128).
+    pub fn block_size() -> usize {
+        32
+    }
+}
+
+impl Default for MetroHash128 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for MetroHash128 {
+    type Value = u128;
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        MetroHash128::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        MetroHash128::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        MetroHash128::digest(self)
+    }
+
+    fn reset(&mut self) {
+        MetroHash128::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        MetroHash128::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        MetroHash128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test string of the reference implementation.
+    const TEST_STRING: &[u8] = b"012345678901234567890123456789012345678901234567890123456789012";
+
+    fn one_shot<C: Checksum>(mut hasher: C, data: &[u8]) -> C::Output {
+        hasher.update(data);
+        hasher.digest()
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        let h = |seed| one_shot(MetroHash64::new(seed), TEST_STRING);
+        assert_eq!(h(0), [0x6b, 0x75, 0x3d, 0xae, 0x06, 0x70, 0x4b, 0xad]);
+        assert_eq!(h(1), [0x3b, 0x0d, 0x48, 0x1c, 0xf4, 0xb9, 0xb8, 0xdf]);
+        let h = |seed| one_shot(MetroHash128::new(seed), TEST_STRING);
+        assert_eq!(
+            h(0),
+            [
+                0xc7, 0x7c, 0xe2, 0xbf, 0xa4, 0xed, 0x9f, 0x9b, 0x05, 0x48, 0xb2, 0xac, 0x50,
+                0x74, 0xa2, 0x97
+            ]
+        );
+        assert_eq!(
+            h(1),
+            [
+                0x45, 0xa3, 0xcd, 0xb8, 0x38, 0x19, 0x9d, 0x7f, 0xbd, 0xd6, 0x8d, 0x86, 0x7a,
+                0x14, 0xec, 0xef
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intdigest_is_little_endian_digest() {
+        let mut h64 = MetroHash64::new(0);
+        h64.update(TEST_STRING);
+        assert_eq!(h64.intdigest(), 0xad4b7006ae3d756b);
+        let mut h128 = MetroHash128::new(0);
+        h128.update(TEST_STRING);
+        assert_eq!(h128.intdigest(), u128::from_le_bytes(h128.digest()));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 73 % 256) as u8).collect();
+        for len in [0, 1, 15, 31, 32, 33, 63, 64, 1000] {
+            let whole64 = one_shot(MetroHash64::new(5), &data[..len]);
+            let whole128 = one_shot(MetroHash128::new(5), &data[..len]);
+            for step in [1, 3, 31, 32, 33] {
+                let mut h64 = MetroHash64::new(5);
+                let mut h128 = MetroHash128::new(5);
+                for chunk in data[..len].chunks(step) {
+                    h64.update(chunk);
+                    h128.update(chunk);
+                }
+                assert_eq!(h64.digest(), whole64, "len {} step {}", len, step);
+                assert_eq!(h128.digest(), whole128, "len {} step {}", len, step);
+            }
+        }
+        let mut hasher = MetroHash128::new(5);
+        hasher.update(b"abc");
+        hasher.reset();
+        assert_eq!(hasher.digest(), MetroHash128::new(5).digest());
+    }
+}
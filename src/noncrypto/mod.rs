@@ -0,0 +1,121 @@
+//! Non-cryptographic hash functions and checksums.
+//!
+//! Fast hashes for cache keys, hash tables and data integrity checks, such
+//! as xxHash and CRCs. They detect accidental corruption but offer no resistance to
+//! deliberate collisions, so they must not be used where an attacker
+//! controls the input.
+//!
+//! # Algorithms
+//!
+//! - [`Adler32`] - Adler-32 (zlib)
+//! - [`Crc16`] - CRC-16 with any parameters, presets in [`Crc16Params`]
+//! - [`Crc32`] - CRC-32 (zlib polynomial)
+//! - [`Crc32c`] - CRC-32C (Castagnoli polynomial)
+//! - [`Crc64`] - CRC-64/XZ and CRC-64/ECMA-182
+//! - [`cityhash64`], [`fingerprint64`] - CityHash64 (v1.1) and FarmHash
+//!   Fingerprint64, one-shot functions
+//! - [`Fletcher16`], [`Fletcher32`], [`Fletcher64`] - Fletcher checksums,
+//!   and [`Fletcher4`], the 256-bit variant of ZFS
+//! - [`Fnv32`], [`Fnv64`], [`Fnv128`] - FNV-1 and FNV-1a
+//! - [`HighwayHash64`], [`HighwayHash128`], [`HighwayHash256`] - HighwayHash
+//!   (keyed, AVX2 when available)
+//! - [`Komihash`], [`komihash`] - komihash (v5, seeded), streaming and
+//!   one-shot
+//! - [`MetroHash64`], [`MetroHash128`] - MetroHash (seeded)
+//! - [`Murmur3_32`], [`Murmur3_128`] - MurmurHash3 x86_32 and x64_128 (seeded)
+//! - [`SipHash24`], [`SipHash13`] - SipHash (keyed, 64/128-bit output)
+//! - [`Xxh32`] - xxHash32 (32-bit output, seeded)
+//! - [`Xxh64`] - xxHash64 (64-bit output, seeded)
+//! - [`SpookyHash32`], [`SpookyHash64`], [`SpookyHash128`] - SpookyHash V2
+//!   (seeded)
+//! - [`wyhash`], [`rapidhash`] - wyhash (final 4, seed or custom secret)
+//!   and rapidhash (v3), one-shot functions
+//! - [`Xxh3_64`], [`Xxh3_128`] - XXH3 (64/128-bit output, seed or custom
+//!   secret)
+//!
+//! # Usage
+//!
+//! Every checksum implements [`Checksum`], which mirrors the inherent
+//! methods of each type. Unlike the cryptographic [`Digest`] trait, the
+//! value is also available as an integer, and no algorithm here has a
+//! message length limit.
+//!
+//! [`Digest`]: crate::core::Digest
+
+pub mod adler32;
+pub mod crc16;
+pub mod crc32;
+pub mod crc64;
+pub mod farmhash;
+pub mod fletcher;
+pub mod fnv;
+pub mod highwayhash;
+pub mod komihash;
+pub mod metrohash;
+pub mod murmur3;
+pub mod siphash;
+pub mod spooky;
+pub mod wyhash;
+pub mod xxh3;
+pub mod xxhash;
+
+pub use adler32::Adler32;
+pub use crc16::{Crc16, Crc16Params};
+pub use crc32::{Crc32, Crc32c};
+pub use crc64::{Crc64, Crc64Variant};
+pub use farmhash::{cityhash64, cityhash64_with_seed, cityhash64_with_seeds, fingerprint64};
+pub use fletcher::{Fletcher4, Fletcher16, Fletcher32, Fletcher64};
+pub use fnv::{Fnv128, Fnv32, Fnv64, FnvVariant};
+pub use highwayhash::{HighwayHash128, HighwayHash256, HighwayHash64};
+pub use komihash::{Komihash, komihash};
+pub use metrohash::{MetroHash64, MetroHash128};
+pub use murmur3::{Murmur3_128, Murmur3_32};
+pub use siphash::{SipHash, SipHash13, SipHash24};
+pub use spooky::{SpookyHash128, SpookyHash32, SpookyHash64};
+pub use wyhash::{WYHASH_SECRET, rapidhash, wyhash, wyhash_secret, wyhash_with_secret};
+pub use xxh3::{Xxh3_128, Xxh3_64};
+pub use xxhash::{Xxh32, Xxh64};
+
+/// Streaming interface shared by all non-cryptographic hashes.
+pub trait Checksum: Clone + Send {
+    /// Integer value of the checksum, e.g. `u64` for xxHash64.
+    type Value: Copy + Send;
+
+    /// Byte representation of the checksum, e.g. `[u8; 8]` for xxHash64.
+    type Output: AsRef<[u8]> + Clone + Send;
+
+    /// Feeds data into the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of the data fed so far as an integer.
+    fn intdigest(&self) -> Self::Value;
+
+    /// Returns the checksum of the data fed so far in the algorithm's
+    /// canonical byte order.
+    fn digest(&self) -> Self::Output;
+
+    /// Resets the checksum to its initial state, keeping its parameters
+    /// (such as the seed).
+    fn reset(&mut self);
+
+    /// Returns the output size in bytes.
+    fn digest_size(&self) -> usize;
+
+    /// Returns the internal block size in bytes.
+    fn block_size(&self) -> usize;
+}
+
+/// A 256-bit value as four words, least significant first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    /// Returns the value as 32 little-endian bytes.
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.0) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
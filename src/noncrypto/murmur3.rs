@@ -0,0 +1,416 @@
+//! MurmurHash3 (x86_32 and x64_128).
+//!
+//! Austin Appleby's multiply-rotate hash, used for bucketing and sampling
+//! by Spark, Kafka, Cassandra, Guava and many Bloom filters. Values match
+//! the reference `MurmurHash3_x86_32` and `MurmurHash3_x64_128` and the
+//! `mmh3` Python package.
+//!
+//! # Algorithm Details
+//!
+//! - **Seed**: 32 bits for both variants, as in the reference
+//! - **Blocks**: 4 bytes (x86_32) or 16 bytes (x64_128), little-endian
+//! - **Output**: 32 or 128 bits, written little-endian by `digest()` as in
+//!   the reference output buffer and `mmh3.hash_bytes()`
+//! - **Signedness**: JVM implementations return signed values (Guava's
+//!   `asInt()`, `mmh3.hash()` by default); `sintdigest()` gives the same
+//!   bits as a signed integer
+//!
+//! Spark SQL's `hash()` uses a legacy variant that mixes each trailing byte
+//! as a full block, so it differs from MurmurHash3_x86_32 for inputs whose
+//! length is not a multiple of 4.
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+const C1_32: u32 = 0xcc9e2d51;
+const C2_32: u32 = 0x1b873593;
+const C1_64: u64 = 0x87c37b91114253d5;
+const C2_64: u64 = 0x4cf5ad432745937f;
+
+/// Final avalanche of the 32-bit variant.
+fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^ (h >> 16)
+}
+
+/// Final avalanche of the 128-bit variant.
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^ (k >> 33)
+}
+
+/// Scrambles a 32-bit block before it is mixed into the state.
+fn scramble32(k: u32) -> u32 {
+    k.wrapping_mul(C1_32).rotate_left(15).wrapping_mul(C2_32)
+}
+
+/// Scrambles the first 64-bit lane of a 128-bit block.
+fn scramble64_1(k: u64) -> u64 {
+    k.wrapping_mul(C1_64).rotate_left(31).wrapping_mul(C2_64)
+}
+
+/// Scrambles the second 64-bit lane of a 128-bit block.
+fn scramble64_2(k: u64) -> u64 {
+    k.wrapping_mul(C2_64).rotate_left(33).wrapping_mul(C1_64)
+}
+
+/// Reads up to 8 bytes as a little-endian integer.
+fn read_partial(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+/// MurmurHash3_x86_32 hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Murmur3_32 {
+    seed: u32,
+    h: u32,
+    buffer: [u8; 4],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl Murmur3_32 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u32) -> Self {
+        Murmur3_32 { seed, h: seed, buffer: [0u8; 4], buffer_len: 0, total_len: 0 }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    fn mix(&mut self, block: &[u8]) {
+        let k = u32::from_le_bytes(block.try_into().unwrap());
+        self.h ^= scramble32(k);
+        self.h = self.h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.buffer_len > 0 {
+            let take = data.len().min(4 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 4 {
+                return;
+            }
+            let block = self.buffer;
+            self.mix(&block);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(4);
+        for block in &mut blocks {
+            self.mix(block);
+        }
+        let tail = blocks.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the hash of the data fed so far as an unsigned integer
+    /// (`mmh3.hash(data, seed, signed=False)`).
+    pub fn intdigest(&self) -> u32 {
+        let mut h = self.h;
+        if self.buffer_len > 0 {
+            h ^= scramble32(read_partial(&self.buffer[..self.buffer_len]) as u32);
+        }
+        // The reference takes the length as a 32-bit int
+        fmix32(h ^ self.total_len as u32)
+    }
+
+    /// Returns the hash of the data fed so far as a signed integer, as on
+    /// the JVM (`mmh3.hash(data, seed)`).
+    pub fn sintdigest(&self) -> i32 {
+        self.intdigest() as i32
+    }
+
+    /// Returns the hash of the data fed so far as 4 little-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (4 for MurmurHash3_x86_32).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the block size in bytes (4 for MurmurHash3_x86_32).
+    pub fn block_size() -> usize {
+        4
+    }
+}
+
+impl Default for Murmur3_32 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Murmur3_32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        Murmur3_32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        Murmur3_32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        Murmur3_32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Murmur3_32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Murmur3_32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Murmur3_32::block_size()
+    }
+}
+
+/// MurmurHash3_x64_128 hasher.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct Murmur3_128 {
+    seed: u32,
+    h1: u64,
+    h2: u64,
+    buffer: [u8; 16],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl Murmur3_128 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u32) -> Self {
+        let h = u64::from(seed);
+        Murmur3_128 { seed, h1: h, h2: h, buffer: [0u8; 16], buffer_len: 0, total_len: 0 }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    fn mix(&mut self, block: &[u8]) {
+        let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+        let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+        self.h1 ^= scramble64_1(k1);
+        self.h1 = self.h1.rotate_left(27).wrapping_add(self.h2);
+        self.h1 = self.h1.wrapping_mul(5).wrapping_add(0x52dce729);
+        self.h2 ^= scramble64_2(k2);
+        self.h2 = self.h2.rotate_left(31).wrapping_add(self.h1);
+        self.h2 = self.h2.wrapping_mul(5).wrapping_add(0x38495ab5);
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.buffer_len > 0 {
+            let take = data.len().min(16 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 16 {
+                return;
+            }
+            let block = self.buffer;
+            self.mix(&block);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(16);
+        for block in &mut blocks {
+            self.mix(block);
+        }
+        let tail = blocks.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the hash of the data fed so far as an unsigned integer, the
+    /// little-endian value of `digest()` (`mmh3.hash128(data, seed)`).
+    pub fn intdigest(&self) -> u128 {
+        let (mut h1, mut h2) = (self.h1, self.h2);
+        let tail = &self.buffer[..self.buffer_len];
+        if tail.len() > 8 {
+            h2 ^= scramble64_2(read_partial(&tail[8..]));
+        }
+        if !tail.is_empty() {
+            h1 ^= scramble64_1(read_partial(&tail[..tail.len().min(8)]));
+        }
+        h1 ^= self.total_len;
+        h2 ^= self.total_len;
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        h1 = fmix64(h1);
+        h2 = fmix64(h2);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        (u128::from(h2) << 64) | u128::from(h1)
+    }
+
+    /// Returns the hash of the data fed so far as a signed integer
+    /// (`mmh3.hash128(data, seed, signed=True)`); its low 64 bits are
+    /// Guava's `asLong()`.
+    pub fn sintdigest(&self) -> i128 {
+        self.intdigest() as i128
+    }
+
+    /// Returns the hash of the data fed so far as 16 little-endian bytes.
+    pub fn digest(&self) -> [u8; 16] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (16 for MurmurHash3_x64_128).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (16 for MurmurHash3_x64_128).
+    pub fn block_size() -> usize {
+        16
+    }
+}
+
+impl Default for Murmur3_128 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Murmur3_128 {
+    type Value = u128;
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        Murmur3_128::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        Murmur3_128::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Murmur3_128::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Murmur3_128::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Murmur3_128::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Murmur3_128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SMHasher's `VerificationTest`: hashes the prefixes of 0, 1, ..., 255
+    /// with seeds 256 - length, then hashes the concatenated digests.
+    fn verification<C: Checksum>(new: fn(u32) -> C) -> u32 {
+        let key: Vec<u8> = (0..=255).collect();
+        let mut hashes = Vec::new();
+        for len in 0..256 {
+            let mut hasher = new(256 - len as u32);
+            hasher.update(&key[..len]);
+            hashes.extend_from_slice(hasher.digest().as_ref());
+        }
+        let mut hasher = new(0);
+        hasher.update(&hashes);
+        u32::from_le_bytes(hasher.digest().as_ref()[..4].try_into().unwrap())
+    }
+
+    fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+        let mut hasher = Murmur3_32::new(seed);
+        hasher.update(data);
+        hasher.intdigest()
+    }
+
+    #[test]
+    fn test_smhasher_verification() {
+        assert_eq!(verification(Murmur3_32::new), 0xb0f57ee3);
+        assert_eq!(verification(Murmur3_128::new), 0x6384ba69);
+    }
+
+    #[test]
+    fn test_known_values_32() {
+        assert_eq!(murmur3_32(b"", 0), 0);
+        assert_eq!(murmur3_32(b"", 1), 0x514e28b7);
+        assert_eq!(murmur3_32(b"", 0xffffffff), 0x81f16f39);
+        assert_eq!(murmur3_32(b"test", 0), 0xba6bd213);
+        assert_eq!(murmur3_32(b"Hello, world!", 0x9747b28c), 0x24884cba);
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(murmur3_32(fox, 0x9747b28c), 0x2fa826cd);
+        let mut hasher = Murmur3_32::new(0);
+        hasher.update(b"test");
+        assert_eq!(hasher.sintdigest(), 0xba6bd213u32 as i32);
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 73 % 256) as u8).collect();
+        let mut whole = Murmur3_128::new(7);
+        whole.update(&data);
+        for step in [1, 3, 15, 16, 17, 100] {
+            let mut h32 = Murmur3_32::new(7);
+            let mut h128 = Murmur3_128::new(7);
+            for chunk in data.chunks(step) {
+                h32.update(chunk);
+                h128.update(chunk);
+            }
+            assert_eq!(h32.intdigest(), murmur3_32(&data, 7), "step {}", step);
+            assert_eq!(h128.intdigest(), whole.intdigest(), "step {}", step);
+        }
+        whole.reset();
+        assert_eq!(whole.intdigest(), Murmur3_128::new(7).intdigest());
+    }
+}
@@ -0,0 +1,311 @@
+//! SipHash keyed hashes.
+//!
+//! SipHash is a keyed pseudorandom function designed against hash-flooding:
+//! without the 128-bit key, an attacker cannot craft colliding inputs. It
+//! is the string hash of Python (SipHash-1-3 since 3.11), Rust's standard
+//! library and many hash tables.
+//!
+//! # Algorithm Details
+//!
+//! - **Key**: exactly 16 bytes, read as two little-endian 64-bit words
+//! - **Rounds**: SipHash-2-4 (2 compression, 4 finalization rounds) is the
+//!   conservative original; SipHash-1-3 trades margin for speed
+//! - **Output**: 8 bytes, or 16 with the 128-bit variant, written
+//!   little-endian by `digest()` as in the reference implementation
+//! - **Speed**: one 8-byte word per compression
+//!
+//! The key must be secret and random for the flooding resistance to hold;
+//! with a fixed key, SipHash is merely a stable, well-mixed checksum.
+
+use super::Checksum;
+use crate::core::ParameterError;
+use crate::utils::to_hex;
+
+/// SipHash with `C` compression and `D` finalization rounds.
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct SipHash<const C: usize, const D: usize> {
+    k0: u64,
+    k1: u64,
+    wide: bool, // 128-bit output
+    v: [u64; 4],
+    buffer: [u8; 8],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+/// SipHash-2-4, the original parameters.
+pub type SipHash24 = SipHash<2, 4>;
+
+/// SipHash-1-3, the faster variant used by Python and Rust.
+pub type SipHash13 = SipHash<1, 3>;
+
+impl<const C: usize, const D: usize> SipHash<C, D> {
+    /// Creates a new hash with a 16-byte `key` and an output of 8 or 16
+    /// bytes.
+    pub fn new(key: &[u8], output_size: usize) -> Result<Self, ParameterError> {
+        let key: [u8; 16] = key.try_into().map_err(|_| ParameterError {
+            algorithm: "SipHash",
+            message: format!("key must be 16 bytes, got {}", key.len()),
+        })?;
+        if output_size != 8 && output_size != 16 {
+            return Err(ParameterError {
+                algorithm: "SipHash",
+                message: format!("output size must be 8 or 16 bytes, got {}", output_size),
+            });
+        }
+        let k0 = u64::from_le_bytes(key[..8].try_into().unwrap());
+        let k1 = u64::from_le_bytes(key[8..].try_into().unwrap());
+        Ok(Self::with_keys(k0, k1, output_size == 16))
+    }
+
+    /// Creates a new hash from the two key words; `wide` selects the
+    /// 128-bit output.
+    pub fn with_keys(k0: u64, k1: u64, wide: bool) -> Self {
+        let mut v = [
+            k0 ^ 0x736f6d6570736575,
+            k1 ^ 0x646f72616e646f6d,
+            k0 ^ 0x6c7967656e657261,
+            k1 ^ 0x7465646279746573,
+        ];
+        if wide {
+            v[1] ^= 0xee;
+        }
+        SipHash { k0, k1, wide, v, buffer: [0u8; 8], buffer_len: 0, total_len: 0 }
+    }
+
+    /// Feeds data into the hash.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.buffer_len > 0 {
+            let take = data.len().min(8 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 8 {
+                return;
+            }
+            compress::<C>(&mut self.v, u64::from_le_bytes(self.buffer));
+            self.buffer_len = 0;
+        }
+        let mut words = data.chunks_exact(8);
+        for word in &mut words {
+            compress::<C>(&mut self.v, u64::from_le_bytes(word.try_into().unwrap()));
+        }
+        let tail = words.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the hash of the data fed so far as an integer: the
+    /// little-endian value of `digest()`, below 2^64 unless the output is
+    /// 128 bits.
+    pub fn intdigest(&self) -> u128 {
+        let mut v = self.v;
+        let mut last = [0u8; 8];
+        last[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        let last = u64::from_le_bytes(last) | (self.total_len << 56);
+        compress::<C>(&mut v, last);
+        v[2] ^= if self.wide { 0xee } else { 0xff };
+        for _ in 0..D {
+            sip_round(&mut v);
+        }
+        let low = v[0] ^ v[1] ^ v[2] ^ v[3];
+        if !self.wide {
+            return u128::from(low);
+        }
+        v[1] ^= 0xdd;
+        for _ in 0..D {
+            sip_round(&mut v);
+        }
+        let high = v[0] ^ v[1] ^ v[2] ^ v[3];
+        (u128::from(high) << 64) | u128::from(low)
+    }
+
+    /// Returns the hash of the data fed so far as 8 or 16 little-endian
+    /// bytes.
+    pub fn digest(&self) -> Vec<u8> {
+        self.intdigest().to_le_bytes()[..self.digest_size()].to_vec()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hash to its initial state, keeping the key.
+    pub fn reset(&mut self) {
+        *self = Self::with_keys(self.k0, self.k1, self.wide);
+    }
+
+    /// Returns the output size in bytes (8 or 16).
+    pub fn digest_size(&self) -> usize {
+        if self.wide { 16 } else { 8 }
+    }
+
+    /// Returns the block size in bytes (8 for SipHash).
+    pub fn block_size() -> usize {
+        8
+    }
+}
+
+/// Absorbs one message word with `C` rounds.
+fn compress<const C: usize>(v: &mut [u64; 4], m: u64) {
+    v[3] ^= m;
+    for _ in 0..C {
+        sip_round(v);
+    }
+    v[0] ^= m;
+}
+
+/// The SipRound add-rotate-xor permutation.
+#[inline(always)]
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+impl<const C: usize, const D: usize> Checksum for SipHash<C, D> {
+    type Value = u128;
+    type Output = Vec<u8>;
+
+    fn update(&mut self, data: &[u8]) {
+        SipHash::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        SipHash::intdigest(self)
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        SipHash::digest(self)
+    }
+
+    fn reset(&mut self) {
+        SipHash::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        SipHash::digest_size(self)
+    }
+
+    fn block_size(&self) -> usize {
+        Self::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Key of the reference test vectors: bytes 0 to 15.
+    const KEY: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    /// `vectors_sip64` of the reference implementation: SipHash-2-4 of the
+    /// messages `[]`, `[0]`, `[0, 1]`, ... `[0, ..., 62]`, as integers.
+    const VECTORS_SIP64: [u64; 64] = [
+        0x726fdb47dd0e0e31, 0x74f839c593dc67fd, 0x0d6c8009d9a94f5a,
+        0x85676696d7fb7e2d, 0xcf2794e0277187b7, 0x18765564cd99a68d,
+        0xcbc9466e58fee3ce, 0xab0200f58b01d137, 0x93f5f5799a932462,
+        0x9e0082df0ba9e4b0, 0x7a5dbbc594ddb9f3, 0xf4b32f46226bada7,
+        0x751e8fbc860ee5fb, 0x14ea5627c0843d90, 0xf723ca908e7af2ee,
+        0xa129ca6149be45e5, 0x3f2acc7f57c29bdb, 0x699ae9f52cbe4794,
+        0x4bc1b3f0968dd39c, 0xbb6dc91da77961bd, 0xbed65cf21aa2ee98,
+        0xd0f2cbb02e3b67c7, 0x93536795e3a33e88, 0xa80c038ccd5ccec8,
+        0xb8ad50c6f649af94, 0xbce192de8a85b8ea, 0x17d835b85bbb15f3,
+        0x2f2e6163076bcfad, 0xde4daaaca71dc9a5, 0xa6a2506687956571,
+        0xad87a3535c49ef28, 0x32d892fad841c342, 0x7127512f72f27cce,
+        0xa7f32346f95978e3, 0x12e0b01abb051238, 0x15e034d40fa197ae,
+        0x314dffbe0815a3b4, 0x027990f029623981, 0xcadcd4e59ef40c4d,
+        0x9abfd8766a33735c, 0x0e3ea96b5304a7d0, 0xad0c42d6fc585992,
+        0x187306c89bc215a9, 0xd4a60abcf3792b95, 0xf935451de4f21df2,
+        0xa9538f0419755787, 0xdb9acddff56ca510, 0xd06c98cd5c0975eb,
+        0xe612a3cb9ecba951, 0xc766e62cfcadaf96, 0xee64435a9752fe72,
+        0xa192d576b245165a, 0x0a8787bf8ecb74b2, 0x81b3e73d20b49b6f,
+        0x7fa8220ba3b2ecea, 0x245731c13ca42499, 0xb78dbfaf3a8d83bd,
+        0xea1ad565322a1a0b, 0x60e61c23a3795013, 0x6606d7e446282b93,
+        0x6ca4ecb15c5f91e1, 0x9f626da15c9625f3, 0xe51b38608ef25f57,
+        0x958a324ceb064572,
+    ];
+
+    fn message(len: usize) -> Vec<u8> {
+        (0..len as u8).collect()
+    }
+
+    #[test]
+    fn test_reference_vectors_64() {
+        for (len, &expected) in VECTORS_SIP64.iter().enumerate() {
+            let mut hash = SipHash24::new(&KEY, 8).unwrap();
+            hash.update(&message(len));
+            assert_eq!(hash.intdigest(), u128::from(expected), "length {}", len);
+            assert_eq!(hash.digest(), expected.to_le_bytes(), "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_reference_vectors_128() {
+        // First entries of `vectors_sip128`, as little-endian bytes
+        let expected: [[u8; 16]; 2] = [
+            [
+                0xa3, 0x81, 0x7f, 0x04, 0xba, 0x25, 0xa8, 0xe6, 0x6d, 0xf6, 0x72, 0x14, 0xc7,
+                0x55, 0x02, 0x93,
+            ],
+            [
+                0xda, 0x87, 0xc1, 0xd8, 0x6b, 0x99, 0xaf, 0x44, 0x34, 0x76, 0x59, 0x11, 0x9b,
+                0x22, 0xfc, 0x45,
+            ],
+        ];
+        for (len, expected) in expected.iter().enumerate() {
+            let mut hash = SipHash24::new(&KEY, 16).unwrap();
+            hash.update(&message(len));
+            assert_eq!(hash.digest(), expected, "length {}", len);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_matches_std() {
+        use std::hash::{Hasher, SipHasher};
+        let data = message(200);
+        for len in [0, 7, 8, 9, 63, 64, 200] {
+            let mut std = SipHasher::new_with_keys(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+            std.write(&data[..len]);
+            let mut hash = SipHash24::new(&KEY, 8).unwrap();
+            for chunk in data[..len].chunks(3) {
+                hash.update(chunk);
+            }
+            assert_eq!(hash.intdigest(), u128::from(std.finish()), "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_siphash13_matches_python() {
+        // hash(b"abc") in Python 3.11 with PYTHONHASHSEED=0 (all-zero key)
+        let mut hash = SipHash13::new(&[0u8; 16], 8).unwrap();
+        hash.update(b"abc");
+        assert_eq!(hash.intdigest() as u64 as i64, -4594863902769663758);
+    }
+
+    #[test]
+    fn test_reset_and_invalid_parameters() {
+        let mut hash = SipHash13::new(&KEY, 16).unwrap();
+        let empty = hash.intdigest();
+        hash.update(b"abcdefghijk");
+        hash.reset();
+        assert_eq!(hash.intdigest(), empty);
+        assert_eq!(hash.digest_size(), 16);
+        assert!(SipHash24::new(&KEY[..15], 8).is_err());
+        assert!(SipHash24::new(&[0u8; 17], 8).is_err());
+        assert!(SipHash13::new(&KEY, 4).is_err());
+    }
+}
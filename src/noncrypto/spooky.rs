@@ -0,0 +1,567 @@
+//! SpookyHash V2.
+//!
+//! Bob Jenkins' 128-bit hash, built from additions, rotations and XORs
+//! over twelve 64-bit words. Values match the reference `SpookyHash` class
+//! (version 2, August 2012): `Hash128()`, `Hash64()` and `Hash32()`, and
+//! `Init()`/`Update()`/`Final()` for input given in pieces.
+//!
+//! # Algorithm Details
+//!
+//! - **Seeds**: two 64-bit words for the 128-bit hash; the 64-bit and
+//!   32-bit hashes use their single seed for both
+//! - **Short messages** (under 192 bytes): a four-word state, mixed every
+//!   32 bytes, with the length added to the last word
+//! - **Long messages**: 96-byte blocks over twelve words; the last partial
+//!   block is zero-padded, ends with its length, and is added to the state
+//!   before the final rounds
+//! - **Output**: the 128-bit hash is the two result words; the 64-bit and
+//!   32-bit hashes are the low bits of the first one; `digest()` writes the
+//!   words little-endian, as in the reference output array
+//!
+//! Version 1 (2011) gives different values for every input: its short hash
+//! drops part of the last word, and its long hash mixes the last block
+//! before the final rounds instead of adding it. It is not provided.
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+/// Number of 64-bit words in the long state.
+const WORDS: usize = 12;
+/// Block size of the long hash, in bytes.
+const BLOCK: usize = 8 * WORDS;
+/// Messages shorter than this use the short hash.
+const SHORT_LIMIT: usize = 2 * BLOCK;
+/// Arbitrary constant of the reference (`sc_const`).
+const CONST: u64 = 0xdeadbeefdeadbeef;
+
+const MIX_ROTATIONS: [u32; WORDS] = [11, 32, 43, 31, 17, 28, 39, 57, 55, 54, 22, 46];
+const END_ROTATIONS: [u32; WORDS] = [44, 15, 34, 21, 38, 33, 10, 13, 38, 53, 42, 54];
+const SHORT_MIX_ROTATIONS: [u32; 12] = [50, 52, 30, 41, 54, 48, 38, 37, 62, 34, 5, 36];
+const SHORT_END_ROTATIONS: [u32; 11] = [15, 52, 26, 51, 28, 9, 47, 54, 32, 25, 63];
+
+fn read64(s: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(s[at..at + 8].try_into().unwrap())
+}
+
+/// Reads up to 8 bytes as a little-endian integer.
+fn read_partial(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+/// Mixes a 96-byte block into the long state (the reference `Mix()`).
+fn mix(s: &mut [u64; WORDS], block: &[u8]) {
+    for i in 0..WORDS {
+        s[i] = s[i].wrapping_add(read64(block, 8 * i));
+        s[(i + 2) % WORDS] ^= s[(i + 10) % WORDS];
+        s[(i + 11) % WORDS] ^= s[i];
+        s[i] = s[i].rotate_left(MIX_ROTATIONS[i]);
+        s[(i + 11) % WORDS] = s[(i + 11) % WORDS].wrapping_add(s[(i + 1) % WORDS]);
+    }
+}
+
+/// Adds the padded last block to the long state and runs the final rounds
+/// (the reference `End()`).
+fn end(s: &mut [u64; WORDS], block: &[u8; BLOCK]) {
+    for (i, word) in s.iter_mut().enumerate() {
+        *word = word.wrapping_add(read64(block, 8 * i));
+    }
+    for _ in 0..3 {
+        for (i, &rotation) in END_ROTATIONS.iter().enumerate() {
+            let (x, y) = ((i + 1) % WORDS, (i + 11) % WORDS);
+            s[y] = s[y].wrapping_add(s[x]);
+            s[(i + 2) % WORDS] ^= s[y];
+            s[x] = s[x].rotate_left(rotation);
+        }
+    }
+}
+
+/// Mixes the four-word short state (the reference `ShortMix()`).
+fn short_mix(h: &mut [u64; 4]) {
+    for (i, &rotation) in SHORT_MIX_ROTATIONS.iter().enumerate() {
+        let x = (i + 2) % 4;
+        h[x] = h[x].rotate_left(rotation).wrapping_add(h[(x + 1) % 4]);
+        h[(x + 2) % 4] ^= h[x];
+    }
+}
+
+/// Final rounds of the short hash (the reference `ShortEnd()`).
+fn short_end(h: &mut [u64; 4]) {
+    for (i, &rotation) in SHORT_END_ROTATIONS.iter().enumerate() {
+        let (x, y) = ((i + 2) % 4, (i + 3) % 4);
+        h[y] ^= h[x];
+        h[x] = h[x].rotate_left(rotation);
+        h[y] = h[y].wrapping_add(h[x]);
+    }
+}
+
+/// Hashes a message of fewer than 192 bytes (the reference `Short()`).
+fn short(data: &[u8], seed1: u64, seed2: u64) -> [u64; 2] {
+    let mut h = [seed1, seed2, CONST, CONST];
+    let mut rest = data;
+    if data.len() >= 16 {
+        let mut chunks = data.chunks_exact(32);
+        for chunk in &mut chunks {
+            h[2] = h[2].wrapping_add(read64(chunk, 0));
+            h[3] = h[3].wrapping_add(read64(chunk, 8));
+            short_mix(&mut h);
+            h[0] = h[0].wrapping_add(read64(chunk, 16));
+            h[1] = h[1].wrapping_add(read64(chunk, 24));
+        }
+        rest = chunks.remainder();
+        if rest.len() >= 16 {
+            h[2] = h[2].wrapping_add(read64(rest, 0));
+            h[3] = h[3].wrapping_add(read64(rest, 8));
+            short_mix(&mut h);
+            rest = &rest[16..];
+        }
+    }
+    h[3] = h[3].wrapping_add((data.len() as u64) << 56);
+    if rest.len() >= 8 {
+        h[2] = h[2].wrapping_add(read64(rest, 0));
+        h[3] = h[3].wrapping_add(read_partial(&rest[8..]));
+    } else if !rest.is_empty() {
+        h[2] = h[2].wrapping_add(read_partial(rest));
+    } else {
+        h[2] = h[2].wrapping_add(CONST);
+        h[3] = h[3].wrapping_add(CONST);
+    }
+    short_end(&mut h);
+    [h[0], h[1]]
+}
+
+/// Input buffering and state shared by the three output sizes.
+///
+/// Messages stay in the buffer until they reach 192 bytes, since shorter
+/// ones are hashed by `short()` at finalization.
+#[derive(Clone, Copy)]
+struct Core {
+    seeds: [u64; 2],
+    state: [u64; WORDS],
+    buffer: [u8; SHORT_LIMIT],
+    buffer_len: usize,
+    total_len: u64,
+}
+
+impl Core {
+    fn new(seed1: u64, seed2: u64) -> Self {
+        let mut state = [CONST; WORDS];
+        for i in (0..WORDS).step_by(3) {
+            state[i] = seed1;
+            state[i + 1] = seed2;
+        }
+        Core {
+            seeds: [seed1, seed2],
+            state,
+            buffer: [0u8; SHORT_LIMIT],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if self.total_len < SHORT_LIMIT as u64 {
+            self.buffer[self.buffer_len..self.buffer_len + data.len()].copy_from_slice(data);
+            self.buffer_len += data.len();
+            return;
+        }
+        if self.buffer_len > 0 {
+            let take = data.len().min(SHORT_LIMIT - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            let whole = self.buffer_len / BLOCK * BLOCK;
+            let buffer = self.buffer;
+            for block in buffer[..whole].chunks_exact(BLOCK) {
+                mix(&mut self.state, block);
+            }
+            // Any bytes left over mean the data was used up
+            self.buffer.copy_within(whole..self.buffer_len, 0);
+            self.buffer_len -= whole;
+            if self.buffer_len > 0 {
+                return;
+            }
+        }
+        let mut blocks = data.chunks_exact(BLOCK);
+        for block in &mut blocks {
+            mix(&mut self.state, block);
+        }
+        let tail = blocks.remainder();
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.buffer_len = tail.len();
+    }
+
+    /// Returns the two result words for the data fed so far.
+    fn finish(&self) -> [u64; 2] {
+        let [seed1, seed2] = self.seeds;
+        if self.total_len < SHORT_LIMIT as u64 {
+            return short(&self.buffer[..self.buffer_len], seed1, seed2);
+        }
+        let mut block = [0u8; BLOCK];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[BLOCK - 1] = self.buffer_len as u8;
+        let mut state = self.state;
+        end(&mut state, &block);
+        [state[0], state[1]]
+    }
+}
+
+/// SpookyHash V2 with 32-bit output (the reference `Hash32()`).
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct SpookyHash32 {
+    core: Core,
+}
+
+impl SpookyHash32 {
+    /// Creates a new hasher with the given seed, used for both seed words.
+    pub fn new(seed: u32) -> Self {
+        SpookyHash32 { core: Core::new(u64::from(seed), u64::from(seed)) }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u32 {
+        self.core.seeds[0] as u32
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.core.update(data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer: the low 32
+    /// bits of the first result word.
+    pub fn intdigest(&self) -> u32 {
+        self.core.finish()[0] as u32
+    }
+
+    /// Returns the hash of the data fed so far as 4 little-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed());
+    }
+
+    /// Returns the output size in bytes (4 for SpookyHash32).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the block size in bytes (96 for SpookyHash).
+    pub fn block_size() -> usize {
+        BLOCK
+    }
+}
+
+impl Default for SpookyHash32 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for SpookyHash32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        SpookyHash32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        SpookyHash32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        SpookyHash32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        SpookyHash32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        SpookyHash32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        SpookyHash32::block_size()
+    }
+}
+
+/// SpookyHash V2 with 64-bit output (the reference `Hash64()`).
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct SpookyHash64 {
+    core: Core,
+}
+
+impl SpookyHash64 {
+    /// Creates a new hasher with the given seed, used for both seed words.
+    pub fn new(seed: u64) -> Self {
+        SpookyHash64 { core: Core::new(seed, seed) }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u64 {
+        self.core.seeds[0]
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.core.update(data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer: the first
+    /// result word.
+    pub fn intdigest(&self) -> u64 {
+        self.core.finish()[0]
+    }
+
+    /// Returns the hash of the data fed so far as 8 little-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed());
+    }
+
+    /// Returns the output size in bytes (8 for SpookyHash64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the block size in bytes (96 for SpookyHash).
+    pub fn block_size() -> usize {
+        BLOCK
+    }
+}
+
+impl Default for SpookyHash64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for SpookyHash64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        SpookyHash64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        SpookyHash64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        SpookyHash64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        SpookyHash64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        SpookyHash64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        SpookyHash64::block_size()
+    }
+}
+
+/// SpookyHash V2 with 128-bit output (the reference `Hash128()`).
+///
+/// Copying captures the full state, so a copy can be finalized or extended
+/// independently of the original.
+#[derive(Clone, Copy)]
+pub struct SpookyHash128 {
+    core: Core,
+}
+
+impl SpookyHash128 {
+    /// Creates a new hasher with the two seed words, as the reference
+    /// `Init(seed1, seed2)`.
+    pub fn new(seed1: u64, seed2: u64) -> Self {
+        SpookyHash128 { core: Core::new(seed1, seed2) }
+    }
+
+    /// Returns the two seed words the hasher was created with.
+    pub fn seeds(&self) -> (u64, u64) {
+        (self.core.seeds[0], self.core.seeds[1])
+    }
+
+    /// Feeds data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.core.update(data);
+    }
+
+    /// Returns the two result words of the data fed so far, as the
+    /// reference `Final(&hash1, &hash2)`.
+    pub fn words(&self) -> (u64, u64) {
+        let [hash1, hash2] = self.core.finish();
+        (hash1, hash2)
+    }
+
+    /// Returns the hash of the data fed so far as an integer, the
+    /// little-endian value of `digest()` (`hash2 << 64 | hash1`).
+    pub fn intdigest(&self) -> u128 {
+        let (hash1, hash2) = self.words();
+        (u128::from(hash2) << 64) | u128::from(hash1)
+    }
+
+    /// Returns the hash of the data fed so far as 16 bytes: the two result
+    /// words, little-endian.
+    pub fn digest(&self) -> [u8; 16] {
+        self.intdigest().to_le_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping the seeds.
+    pub fn reset(&mut self) {
+        let (seed1, seed2) = self.seeds();
+        *self = Self::new(seed1, seed2);
+    }
+
+    /// Returns the output size in bytes (16 for SpookyHash128).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the block size in bytes (96 for SpookyHash).
+    pub fn block_size() -> usize {
+        BLOCK
+    }
+}
+
+impl Default for SpookyHash128 {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl Checksum for SpookyHash128 {
+    type Value = u128;
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        SpookyHash128::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        SpookyHash128::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        SpookyHash128::digest(self)
+    }
+
+    fn reset(&mut self) {
+        SpookyHash128::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        SpookyHash128::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        SpookyHash128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_shot<C: Checksum>(mut hasher: C, data: &[u8]) -> C::Value {
+        hasher.update(data);
+        hasher.intdigest()
+    }
+
+    /// `Hash32()` of the bytes 128, 129, ..., of lengths 0 to 15 with seed
+    /// 0, the first values checked by the reference `TestResults()`.
+    #[test]
+    fn test_reference_results() {
+        const EXPECTED: [u32; 16] = [
+            0x6bf50919, 0x70de1d26, 0xa2b37298, 0x35bc5fbf, 0x8223b279, 0x5bcb315e, 0x53fe88a1,
+            0xf9f1a233, 0xee193982, 0x54f86f29, 0xc8772d36, 0x9ed60886, 0x5f23d1da, 0x1ed9f474,
+            0xf2ef0c89, 0x83ec01f9,
+        ];
+        let buf: Vec<u8> = (0..512u32).map(|i| (i + 128) as u8).collect();
+        for (len, expected) in EXPECTED.into_iter().enumerate() {
+            assert_eq!(one_shot(SpookyHash32::new(0), &buf[..len]), expected, "len {}", len);
+        }
+    }
+
+    /// `Hash128()` with seeds 1 and 2, from the reference implementation,
+    /// over the short and long paths.
+    #[test]
+    fn test_hash128() {
+        const EXPECTED: [(usize, u64, u64); 10] = [
+            (0, 0xeec07ed910bce07a, 0xfce8383cffab081d),
+            (1, 0xdac0cd68f3e4881b, 0xbff5568c000257b4),
+            (15, 0x9c691efe4be0d4ae, 0x8b4e12dab95d33b8),
+            (16, 0xad70f5ca5dd7c0b2, 0x678b45c7eb0424ab),
+            (31, 0x34b54096130ca3cc, 0x340fce40c01b1367),
+            (191, 0x3a4edaea67b74cb6, 0x57461930eb9c5a1b),
+            (192, 0x8e881626389a6e08, 0xe09878f0be849fd1),
+            (287, 0x54bf201fc8685a6d, 0xe38ee68eeabbf089),
+            (288, 0x8a0ea8fa1ab9fa4e, 0x179d0dc0c043090d),
+            (1000, 0xa524941a45826695, 0xf3e6c50d7af3486f),
+        ];
+        let buf: Vec<u8> = (0..1000u32).map(|i| (i * 7 + 3) as u8).collect();
+        for (len, hash1, hash2) in EXPECTED {
+            let mut hasher = SpookyHash128::new(1, 2);
+            hasher.update(&buf[..len]);
+            assert_eq!(hasher.words(), (hash1, hash2), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 73 % 256) as u8).collect();
+        for len in [0, 1, 95, 96, 97, 191, 192, 193, 287, 288, 1000] {
+            let whole = one_shot(SpookyHash128::new(3, 4), &data[..len]);
+            for step in [1, 7, 95, 96, 97, 191, 192] {
+                let mut hasher = SpookyHash128::new(3, 4);
+                for chunk in data[..len].chunks(step) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.intdigest(), whole, "len {} step {}", len, step);
+            }
+        }
+        let full = one_shot(SpookyHash128::new(5, 5), &data);
+        assert_eq!(one_shot(SpookyHash64::new(5), &data), full as u64);
+        assert_eq!(one_shot(SpookyHash32::new(5), &data), full as u32);
+        let mut hasher = SpookyHash64::new(5);
+        hasher.update(b"abc");
+        hasher.reset();
+        assert_eq!(hasher.intdigest(), SpookyHash64::new(5).intdigest());
+    }
+}
@@ -0,0 +1,237 @@
+//! wyhash and rapidhash.
+//!
+//! Wang Yi's wyhash (final version 4) and its successor rapidhash by
+//! Nicolas De Carli (version 3), both built on the "mum" mix: a 64x64 to
+//! 128-bit multiplication whose halves are XORed. Among the fastest
+//! portable hashes for hash tables and record fingerprints.
+//!
+//! # Algorithm Details
+//!
+//! - **Short inputs** (up to 16 bytes): two overlapping reads of the head
+//!   and tail, mixed once
+//! - **Bulk**: wyhash runs three independent lanes over 48-byte blocks,
+//!   rapidhash seven over 112-byte blocks; the lanes are XORed together,
+//!   then the rest is mixed 16 bytes at a time
+//! - **Tail**: the last 16 bytes of the input, read even if they overlap
+//!   data already mixed, go through a final mum and mix
+//! - **Secret**: wyhash takes four 64-bit secret words, the default ones
+//!   being [`WYHASH_SECRET`]; rapidhash uses its fixed default secrets
+//! - **Output**: 64 bits; there is no streaming form, since both functions
+//!   read the end of the input first
+
+use crate::core::ParameterError;
+
+/// Default secret of wyhash (`_wyp`); its first words also start the
+/// rapidhash secrets.
+pub const WYHASH_SECRET: [u64; 4] =
+    [0x2d358dccaa6c78a5, 0x8bb84b93962eacc9, 0x4b33a62ed433d4a3, 0x4d5a2da51de1aa47];
+
+/// Reads a wyhash secret from 32 bytes, as four little-endian words.
+pub fn wyhash_secret(bytes: &[u8]) -> Result<[u64; 4], ParameterError> {
+    if bytes.len() != 32 {
+        return Err(ParameterError {
+            algorithm: "wyhash",
+            message: format!("secret must be 32 bytes, got {}", bytes.len()),
+        });
+    }
+    Ok(std::array::from_fn(|i| read64(bytes, 8 * i)))
+}
+
+/// Default secrets of rapidhash.
+const RAPID_SECRET: [u64; 7] = [
+    0x2d358dccaa6c78a5,
+    0x8bb84b93962eacc9,
+    0x4b33a62ed433d4a3,
+    0x4d5a2da51de1aa47,
+    0xa0761d6478bd642f,
+    0xe7037ed1a0b428db,
+    0x90ed1765281c388c,
+];
+
+/// Multiplies `a` and `b`, returning the low and high halves.
+fn mum(a: u64, b: u64) -> (u64, u64) {
+    let r = u128::from(a) * u128::from(b);
+    (r as u64, (r >> 64) as u64)
+}
+
+/// Multiplies `a` and `b` and XORs the halves of the product.
+fn mix(a: u64, b: u64) -> u64 {
+    let (low, high) = mum(a, b);
+    low ^ high
+}
+
+fn read64(s: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(s[at..at + 8].try_into().unwrap())
+}
+
+fn read32(s: &[u8], at: usize) -> u64 {
+    u64::from(u32::from_le_bytes(s[at..at + 4].try_into().unwrap()))
+}
+
+/// Returns the wyhash (final version 4) of `data` with the default secret.
+pub fn wyhash(data: &[u8], seed: u64) -> u64 {
+    wyhash_with_secret(data, seed, &WYHASH_SECRET)
+}
+
+/// Returns the wyhash (final version 4) of `data` with a custom secret, as
+/// the reference `wyhash(key, len, seed, secret)`.
+pub fn wyhash_with_secret(data: &[u8], seed: u64, secret: &[u64; 4]) -> u64 {
+    let len = data.len();
+    let mut seed = seed ^ mix(seed ^ secret[0], secret[1]);
+    let (a, b) = if len <= 16 {
+        if len >= 4 {
+            // Two overlapping pairs of 32-bit reads cover 4 to 16 bytes
+            let middle = (len >> 3) << 2;
+            let a = (read32(data, 0) << 32) | read32(data, middle);
+            let b = (read32(data, len - 4) << 32) | read32(data, len - 4 - middle);
+            (a, b)
+        } else if len > 0 {
+            let a = (u64::from(data[0]) << 16)
+                | (u64::from(data[len >> 1]) << 8)
+                | u64::from(data[len - 1]);
+            (a, 0)
+        } else {
+            (0, 0)
+        }
+    } else {
+        let mut rest = data;
+        if rest.len() > 48 {
+            let (mut see1, mut see2) = (seed, seed);
+            while rest.len() > 48 {
+                seed = mix(read64(rest, 0) ^ secret[1], read64(rest, 8) ^ seed);
+                see1 = mix(read64(rest, 16) ^ secret[2], read64(rest, 24) ^ see1);
+                see2 = mix(read64(rest, 32) ^ secret[3], read64(rest, 40) ^ see2);
+                rest = &rest[48..];
+            }
+            seed ^= see1 ^ see2;
+        }
+        while rest.len() > 16 {
+            seed = mix(read64(rest, 0) ^ secret[1], read64(rest, 8) ^ seed);
+            rest = &rest[16..];
+        }
+        (read64(data, len - 16), read64(data, len - 8))
+    };
+    let (a, b) = mum(a ^ secret[1], b ^ seed);
+    mix(a ^ secret[0] ^ len as u64, b ^ secret[1])
+}
+
+/// Returns the rapidhash (version 3) of `data`, as the reference
+/// `rapidhash_withSeed()`.
+pub fn rapidhash(data: &[u8], seed: u64) -> u64 {
+    let secret = &RAPID_SECRET;
+    let len = data.len();
+    let mut seed = seed ^ mix(seed ^ secret[2], secret[1]);
+    let (a, b, remainder) = if len <= 16 {
+        let (a, b) = if len >= 8 {
+            seed ^= len as u64;
+            (read64(data, 0), read64(data, len - 8))
+        } else if len >= 4 {
+            seed ^= len as u64;
+            (read32(data, 0), read32(data, len - 4))
+        } else if len > 0 {
+            let a = (u64::from(data[0]) << 45) | u64::from(data[len - 1]);
+            (a, u64::from(data[len >> 1]))
+        } else {
+            (0, 0)
+        };
+        (a, b, len)
+    } else {
+        let mut rest = data;
+        if rest.len() > 112 {
+            let mut see = [seed; 7];
+            while rest.len() > 112 {
+                for (i, lane) in see.iter_mut().enumerate() {
+                    *lane = mix(read64(rest, 16 * i) ^ secret[i], read64(rest, 16 * i + 8) ^ *lane);
+                }
+                rest = &rest[112..];
+            }
+            seed = see.iter().fold(0, |acc, lane| acc ^ lane);
+        }
+        // Up to six more 16-byte pieces, with a fixed pattern of secrets
+        for (i, piece) in rest.chunks_exact(16).enumerate() {
+            if 16 * i + 16 >= rest.len() {
+                break;
+            }
+            let key = secret[[2, 2, 1, 1, 2, 1][i]];
+            seed = mix(read64(piece, 0) ^ key, read64(piece, 8) ^ seed);
+        }
+        let a = read64(data, len - 16) ^ rest.len() as u64;
+        (a, read64(data, len - 8), rest.len())
+    };
+    let (a, b) = mum(a ^ secret[1], b ^ seed);
+    mix(a ^ 0xaaaaaaaaaaaaaaaa, b ^ secret[1] ^ remainder as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors of the wyhash reference (`test_vector.cpp`), with the
+    /// seed equal to the index.
+    const WYHASH_VECTORS: [(&[u8], u64); 7] = [
+        (b"", 0x93228a4de0eec5a2),
+        (b"a", 0xc5bac3db178713c4),
+        (b"abc", 0xa97f2f7b1d9b3314),
+        (b"message digest", 0x786d1f1df3801df4),
+        (b"abcdefghijklmnopqrstuvwxyz", 0xdca5a8138ad37c87),
+        (b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", 0xb9e734f117cfaf70),
+        (
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            0x6cc5eab49a92d617,
+        ),
+    ];
+
+    /// rapidhash of the bytes 0, 1, ..., n - 1 with seeds 0 and 42, from
+    /// the reference implementation.
+    const RAPIDHASH_VECTORS: [(usize, u64, u64); 18] = [
+        (0, 0x0338dc4be2cecdae, 0x9293ba21a570895d),
+        (1, 0x4f23c791b16eba02, 0x4e48c929c3a377eb),
+        (3, 0xdbd091bcf57ae814, 0x8135fe7d667af25f),
+        (4, 0x46fef26db4943adf, 0xc2be7fccaec9cc73),
+        (8, 0xda56413ff396af3e, 0x362abbdf6a5dee7a),
+        (16, 0xd6bfc1bcf7e9ca19, 0x52687b5adc4b3bd9),
+        (17, 0x7508c9e74d5b5366, 0x63ccce2b9f0b725a),
+        (32, 0xc0186990f026b180, 0x3f809231d53bac00),
+        (33, 0xeb4ff8393398a779, 0x9069eb246b08ad2c),
+        (48, 0xecd5ed3e946f9c91, 0x1b88997be585cd08),
+        (49, 0x635a714c24c02d64, 0x22f8150aa2357275),
+        (64, 0xd1a6cc5fe6cf87f4, 0x4725e095422183b1),
+        (80, 0xe7e477a0dffeae1f, 0x19a2bfeb53e50d48),
+        (96, 0x1e9a8d81b63de536, 0xa169a9b0a245f180),
+        (97, 0x07784269b17cbbfe, 0x0f802e8fcf55f7b2),
+        (112, 0x667174637fd34ae7, 0x9b25547c2920b181),
+        (113, 0xabaf0e2bdacf7e23, 0x2daeac18f7d39098),
+        (200, 0x9d1612cdf44b1c42, 0x4d519343c0caf32a),
+    ];
+
+    #[test]
+    fn test_wyhash_reference_vectors() {
+        for (seed, (data, expected)) in WYHASH_VECTORS.iter().enumerate() {
+            assert_eq!(wyhash(data, seed as u64), *expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_wyhash_secret() {
+        let data = b"abcdefghijklmnopqrstuvwxyz";
+        assert_eq!(wyhash_with_secret(data, 4, &WYHASH_SECRET), wyhash(data, 4));
+        let mut secret = WYHASH_SECRET;
+        secret[3] ^= 2;
+        // The fourth word only enters the 48-byte loop
+        assert_eq!(wyhash_with_secret(data, 4, &secret), wyhash(data, 4));
+        let long = [7u8; 100];
+        assert_ne!(wyhash_with_secret(&long, 4, &secret), wyhash(&long, 4));
+        let bytes: Vec<u8> = WYHASH_SECRET.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(wyhash_secret(&bytes).unwrap(), WYHASH_SECRET);
+        assert!(wyhash_secret(&bytes[..31]).is_err());
+    }
+
+    #[test]
+    fn test_rapidhash_reference_vectors() {
+        let data: Vec<u8> = (0..=255).collect();
+        for (len, seed0, seed42) in RAPIDHASH_VECTORS {
+            assert_eq!(rapidhash(&data[..len], 0), seed0, "length {}", len);
+            assert_eq!(rapidhash(&data[..len], 42), seed42, "length {}", len);
+        }
+    }
+}
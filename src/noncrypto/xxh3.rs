@@ -0,0 +1,827 @@
+//! XXH3 non-cryptographic hash functions.
+//!
+//! Pure Rust implementation following the xxHash specification
+//! (`doc/xxhash_spec.md` in the reference repository). XXH3 keys every
+//! input with a secret: inputs up to 240 bytes are hashed by dedicated
+//! short routines (0-16, 17-128 and 129-240 bytes), longer ones by eight
+//! 64-bit accumulators over 64-byte stripes, scrambled after each block.
+//!
+//! # Algorithm Details
+//!
+//! - **Stripe size**: 64 bytes (eight 64-bit lanes); a block is
+//!   `(secret_len - 64) / 8` stripes, 16 with the default secret
+//! - **Output**: 64 or 128 bits, written big-endian by `digest()` as in the
+//!   reference canonical representation
+//! - **Seed**: any 64-bit value, mixed into the short routines and used to
+//!   derive the secret for long inputs
+//! - **Secret**: the 192-byte default, or a custom one of at least 136
+//!   bytes (used with seed 0)
+
+use super::xxhash::{avalanche64, PRIME32_1, PRIME32_2, PRIME64_1, PRIME64_2, PRIME64_4};
+use super::Checksum;
+use crate::core::ParameterError;
+use crate::utils::to_hex;
+
+/// Minimum size of a custom secret in bytes.
+pub const SECRET_SIZE_MIN: usize = 136;
+
+const STRIPE_LEN: usize = 64;
+const SECRET_CONSUME_RATE: usize = 8;
+const SECRET_MERGEACCS_START: usize = 11;
+const SECRET_LASTACC_START: usize = 7;
+const MID_SIZE_MAX: usize = 240;
+const BUFFER_LEN: usize = 256; // 4 stripes
+
+const DEFAULT_SECRET: [u8; 192] = [
+    0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+    0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+    0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+    0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+    0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+    0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+    0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+    0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+    0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+    0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+    0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+    0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+];
+
+const INITIAL_ACC: [u64; 8] = [
+    0xc2b2ae3d, // PRIME32_3
+    PRIME64_1,
+    PRIME64_2,
+    0x165667b19e3779f9, // PRIME64_3
+    PRIME64_4,
+    PRIME32_2 as u64,
+    0x27d4eb2f165667c5, // PRIME64_5
+    PRIME32_1 as u64,
+];
+
+/// Reads the little-endian 32-bit word at `offset`.
+fn read32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Reads the little-endian 64-bit word at `offset`.
+fn read64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Full 64x64 -> 128-bit product as `(low, high)`.
+fn mul128(a: u64, b: u64) -> (u64, u64) {
+    let product = u128::from(a) * u128::from(b);
+    (product as u64, (product >> 64) as u64)
+}
+
+/// 128-bit product folded to 64 bits by XORing its halves.
+fn mul128_fold64(a: u64, b: u64) -> u64 {
+    let (low, high) = mul128(a, b);
+    low ^ high
+}
+
+/// XXH3 avalanche, lighter than XXH64's.
+fn avalanche(mut hash: u64) -> u64 {
+    hash ^= hash >> 37;
+    hash = hash.wrapping_mul(0x165667919e3779f9);
+    hash ^ (hash >> 32)
+}
+
+/// Stronger avalanche (`rrmxmx`) for 4- to 8-byte inputs.
+fn rrmxmx(mut hash: u64, len: u64) -> u64 {
+    hash ^= hash.rotate_left(49) ^ hash.rotate_left(24);
+    hash = hash.wrapping_mul(0x9fb21c651e98df25);
+    hash ^= (hash >> 35).wrapping_add(len);
+    hash = hash.wrapping_mul(0x9fb21c651e98df25);
+    hash ^ (hash >> 28)
+}
+
+/// Mixes 16 input bytes with 16 secret bytes.
+fn mix16(input: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let lo = read64(input, 0) ^ read64(secret, 0).wrapping_add(seed);
+    let hi = read64(input, 8) ^ read64(secret, 8).wrapping_sub(seed);
+    mul128_fold64(lo, hi)
+}
+
+/// Mixes two 16-byte chunks into the 128-bit accumulator pair.
+fn mix32(acc: (u64, u64), a: &[u8], b: &[u8], secret: &[u8], seed: u64) -> (u64, u64) {
+    let lo = acc.0.wrapping_add(mix16(a, secret, seed))
+        ^ read64(b, 0).wrapping_add(read64(b, 8));
+    let hi = acc.1.wrapping_add(mix16(b, &secret[16..], seed))
+        ^ read64(a, 0).wrapping_add(read64(a, 8));
+    (lo, hi)
+}
+
+/// Packs a 1- to 3-byte input and its length into a 32-bit word.
+fn combine_1to3(input: &[u8]) -> u32 {
+    let len = input.len();
+    (u32::from(input[0]) << 16)
+        | (u32::from(input[len >> 1]) << 24)
+        | u32::from(input[len - 1])
+        | ((len as u32) << 8)
+}
+
+/// 64-bit hash of an input of at most 240 bytes.
+fn hash64_short(input: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let len = input.len();
+    match len {
+        0 => avalanche64(seed ^ read64(secret, 56) ^ read64(secret, 64)),
+        1..=3 => {
+            let flip = u64::from(read32(secret, 0) ^ read32(secret, 4)).wrapping_add(seed);
+            avalanche64(u64::from(combine_1to3(input)) ^ flip)
+        }
+        4..=8 => {
+            let seed = seed ^ (u64::from((seed as u32).swap_bytes()) << 32);
+            let flip = (read64(secret, 8) ^ read64(secret, 16)).wrapping_sub(seed);
+            let word =
+                u64::from(read32(input, len - 4)).wrapping_add(u64::from(read32(input, 0)) << 32);
+            rrmxmx(word ^ flip, len as u64)
+        }
+        9..=16 => {
+            let lo = read64(input, 0) ^ (read64(secret, 24) ^ read64(secret, 32)).wrapping_add(seed);
+            let hi = read64(input, len - 8)
+                ^ (read64(secret, 40) ^ read64(secret, 48)).wrapping_sub(seed);
+            avalanche(
+                (len as u64)
+                    .wrapping_add(lo.swap_bytes())
+                    .wrapping_add(hi)
+                    .wrapping_add(mul128_fold64(lo, hi)),
+            )
+        }
+        17..=128 => {
+            // Pairs of 16-byte chunks from both ends, one pair per 32 bytes
+            let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+            for i in 0..=(len - 1) / 32 {
+                acc = acc
+                    .wrapping_add(mix16(&input[16 * i..], &secret[32 * i..], seed))
+                    .wrapping_add(mix16(&input[len - 16 * (i + 1)..], &secret[32 * i + 16..], seed));
+            }
+            avalanche(acc)
+        }
+        _ => {
+            let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+            for i in 0..8 {
+                acc = acc.wrapping_add(mix16(&input[16 * i..], &secret[16 * i..], seed));
+            }
+            acc = avalanche(acc);
+            for i in 8..len / 16 {
+                acc = acc.wrapping_add(mix16(&input[16 * i..], &secret[16 * (i - 8) + 3..], seed));
+            }
+            let last = &secret[SECRET_SIZE_MIN - 17..];
+            avalanche(acc.wrapping_add(mix16(&input[len - 16..], last, seed)))
+        }
+    }
+}
+
+/// Final step of the 17- to 240-byte 128-bit routines.
+fn finish128(acc: (u64, u64), len: usize, seed: u64) -> u128 {
+    let lo = acc.0.wrapping_add(acc.1);
+    let hi = acc
+        .0
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(acc.1.wrapping_mul(PRIME64_4))
+        .wrapping_add((len as u64).wrapping_sub(seed).wrapping_mul(PRIME64_2));
+    u128::from(avalanche(lo)) | (u128::from(avalanche(hi).wrapping_neg()) << 64)
+}
+
+/// 128-bit hash of an input of at most 240 bytes.
+fn hash128_short(input: &[u8], secret: &[u8], seed: u64) -> u128 {
+    let len = input.len();
+    let (lo, hi) = match len {
+        0 => (
+            avalanche64(seed ^ read64(secret, 64) ^ read64(secret, 72)),
+            avalanche64(seed ^ read64(secret, 80) ^ read64(secret, 88)),
+        ),
+        1..=3 => {
+            let lo = combine_1to3(input);
+            let hi = lo.swap_bytes().rotate_left(13);
+            let flip_lo = u64::from(read32(secret, 0) ^ read32(secret, 4)).wrapping_add(seed);
+            let flip_hi = u64::from(read32(secret, 8) ^ read32(secret, 12)).wrapping_sub(seed);
+            (avalanche64(u64::from(lo) ^ flip_lo), avalanche64(u64::from(hi) ^ flip_hi))
+        }
+        4..=8 => {
+            let seed = seed ^ (u64::from((seed as u32).swap_bytes()) << 32);
+            let flip = (read64(secret, 16) ^ read64(secret, 24)).wrapping_add(seed);
+            let word =
+                u64::from(read32(input, 0)).wrapping_add(u64::from(read32(input, len - 4)) << 32);
+            let (mut lo, mut hi) =
+                mul128(word ^ flip, PRIME64_1.wrapping_add((len as u64) << 2));
+            hi = hi.wrapping_add(lo << 1);
+            lo ^= hi >> 3;
+            lo ^= lo >> 35;
+            lo = lo.wrapping_mul(0x9fb21c651e98df25);
+            (lo ^ (lo >> 28), avalanche(hi))
+        }
+        9..=16 => {
+            let flip_lo = (read64(secret, 32) ^ read64(secret, 40)).wrapping_sub(seed);
+            let flip_hi = (read64(secret, 48) ^ read64(secret, 56)).wrapping_add(seed);
+            let input_lo = read64(input, 0);
+            let input_hi = read64(input, len - 8) ^ flip_hi;
+            let (mut mul_lo, mut mul_hi) =
+                mul128(input_lo ^ read64(input, len - 8) ^ flip_lo, PRIME64_1);
+            mul_lo = mul_lo.wrapping_add(((len - 1) as u64) << 54);
+            mul_hi = mul_hi.wrapping_add(input_hi).wrapping_add(
+                u64::from(input_hi as u32).wrapping_mul(u64::from(PRIME32_2 - 1)),
+            );
+            mul_lo ^= mul_hi.swap_bytes();
+            let (lo, hi) = mul128(mul_lo, PRIME64_2);
+            (avalanche(lo), avalanche(hi.wrapping_add(mul_hi.wrapping_mul(PRIME64_2))))
+        }
+        17..=128 => {
+            let mut acc = ((len as u64).wrapping_mul(PRIME64_1), 0);
+            for i in (0..=(len - 1) / 32).rev() {
+                let (a, b) = (&input[16 * i..], &input[len - 16 * (i + 1)..]);
+                acc = mix32(acc, a, b, &secret[32 * i..], seed);
+            }
+            return finish128(acc, len, seed);
+        }
+        _ => {
+            let mut acc = ((len as u64).wrapping_mul(PRIME64_1), 0);
+            for i in 0..4 {
+                acc = mix32(acc, &input[32 * i..], &input[32 * i + 16..], &secret[32 * i..], seed);
+            }
+            acc = (avalanche(acc.0), avalanche(acc.1));
+            for i in 4..len / 32 {
+                let secret = &secret[32 * (i - 4) + 3..];
+                acc = mix32(acc, &input[32 * i..], &input[32 * i + 16..], secret, seed);
+            }
+            let last = &secret[SECRET_SIZE_MIN - 33..];
+            acc = mix32(acc, &input[len - 16..], &input[len - 32..], last, seed.wrapping_neg());
+            return finish128(acc, len, seed);
+        }
+    };
+    u128::from(lo) | (u128::from(hi) << 64)
+}
+
+/// Mixes one 64-byte stripe into the accumulators.
+fn accumulate(acc: &mut [u64; 8], stripe: &[u8], secret: &[u8]) {
+    for i in 0..8 {
+        let value = read64(stripe, 8 * i);
+        let keyed = value ^ read64(secret, 8 * i);
+        acc[i ^ 1] = acc[i ^ 1].wrapping_add(value);
+        acc[i] = acc[i].wrapping_add((keyed & 0xffffffff).wrapping_mul(keyed >> 32));
+    }
+}
+
+/// Scrambles the accumulators at the end of a block.
+fn scramble(acc: &mut [u64; 8], secret: &[u8]) {
+    for (i, lane) in acc.iter_mut().enumerate() {
+        *lane = (*lane ^ (*lane >> 47) ^ read64(secret, 8 * i)).wrapping_mul(PRIME32_1.into());
+    }
+}
+
+/// Folds the accumulators into a 64-bit value.
+fn merge(acc: &[u64; 8], secret: &[u8], start: u64) -> u64 {
+    let hash = (0..4).fold(start, |hash, i| {
+        hash.wrapping_add(mul128_fold64(
+            acc[2 * i] ^ read64(secret, 16 * i),
+            acc[2 * i + 1] ^ read64(secret, 16 * i + 8),
+        ))
+    });
+    avalanche(hash)
+}
+
+/// Mixes whole stripes into the accumulators, scrambling after each block.
+///
+/// `stripes_done` counts the stripes of the current block and selects the
+/// secret offset, so a block may span several calls.
+fn consume_stripes(acc: &mut [u64; 8], stripes_done: &mut usize, secret: &[u8], data: &[u8]) {
+    let per_block = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
+    for stripe in data.chunks_exact(STRIPE_LEN) {
+        accumulate(acc, stripe, &secret[*stripes_done * SECRET_CONSUME_RATE..]);
+        *stripes_done += 1;
+        if *stripes_done == per_block {
+            scramble(acc, &secret[secret.len() - STRIPE_LEN..]);
+            *stripes_done = 0;
+        }
+    }
+}
+
+/// Streaming state shared by XXH3-64 and XXH3-128.
+///
+/// Inputs up to 240 bytes stay in the buffer and go through the short
+/// routines at the end. Beyond that, stripes are consumed as data arrives,
+/// always keeping at least one byte buffered so that the last stripe, which
+/// is keyed differently, is only mixed in when finalizing.
+#[derive(Clone)]
+struct State {
+    seed: u64,
+    secret: Vec<u8>,        // Secret for long inputs (derived from the seed)
+    acc: [u64; 8],
+    buffer: [u8; BUFFER_LEN],
+    buffer_len: usize,      // Number of bytes currently in buffer
+    stripes_done: usize,    // Stripes consumed in the current block
+    total_len: u64,         // Total bytes processed, modulo 2^64
+}
+
+impl State {
+    fn with_seed(seed: u64) -> Self {
+        let mut secret = DEFAULT_SECRET.to_vec();
+        if seed != 0 {
+            for pair in secret.chunks_exact_mut(16) {
+                let lo = read64(pair, 0).wrapping_add(seed);
+                let hi = read64(pair, 8).wrapping_sub(seed);
+                pair[..8].copy_from_slice(&lo.to_le_bytes());
+                pair[8..].copy_from_slice(&hi.to_le_bytes());
+            }
+        }
+        Self::with_long_secret(seed, secret)
+    }
+
+    fn with_secret(secret: &[u8]) -> Result<Self, ParameterError> {
+        if secret.len() < SECRET_SIZE_MIN {
+            return Err(ParameterError {
+                algorithm: "XXH3",
+                message: format!("secret must be at least {} bytes", SECRET_SIZE_MIN),
+            });
+        }
+        Ok(Self::with_long_secret(0, secret.to_vec()))
+    }
+
+    fn with_long_secret(seed: u64, secret: Vec<u8>) -> Self {
+        State {
+            seed,
+            secret,
+            acc: INITIAL_ACC,
+            buffer: [0u8; BUFFER_LEN],
+            buffer_len: 0,
+            stripes_done: 0,
+            total_len: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.acc = INITIAL_ACC;
+        self.buffer_len = 0;
+        self.stripes_done = 0;
+        self.total_len = 0;
+    }
+
+    /// Secret for the short routines: seeded hashes mix the seed into the
+    /// default secret instead of using the derived one.
+    fn short_secret(&self) -> &[u8] {
+        if self.seed != 0 { &DEFAULT_SECRET } else { &self.secret }
+    }
+
+    /// Whole input if it fits the short routines.
+    fn short_input(&self) -> Option<&[u8]> {
+        (self.total_len <= MID_SIZE_MAX as u64).then(|| &self.buffer[..self.buffer_len])
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        if data.len() <= BUFFER_LEN - self.buffer_len {
+            self.buffer[self.buffer_len..self.buffer_len + data.len()].copy_from_slice(data);
+            self.buffer_len += data.len();
+            return;
+        }
+
+        // Complete and consume the buffer; more data follows, so its last
+        // stripe is not the final one
+        let mut input = data;
+        if self.buffer_len > 0 {
+            let fill = BUFFER_LEN - self.buffer_len;
+            self.buffer[self.buffer_len..].copy_from_slice(&input[..fill]);
+            input = &input[fill..];
+            consume_stripes(&mut self.acc, &mut self.stripes_done, &self.secret, &self.buffer);
+            self.buffer_len = 0;
+        }
+
+        // Consume stripes directly from the input, keeping the previous
+        // stripe at the end of the buffer for a short final stripe
+        if input.len() > BUFFER_LEN {
+            let (stripes, rest) = input.split_at((input.len() - 1) / STRIPE_LEN * STRIPE_LEN);
+            consume_stripes(&mut self.acc, &mut self.stripes_done, &self.secret, stripes);
+            self.buffer[BUFFER_LEN - STRIPE_LEN..]
+                .copy_from_slice(&stripes[stripes.len() - STRIPE_LEN..]);
+            input = rest;
+        }
+
+        self.buffer[..input.len()].copy_from_slice(input);
+        self.buffer_len = input.len();
+    }
+
+    /// Accumulators of a long input, including its last stripe.
+    fn finish_long(&self) -> [u64; 8] {
+        let mut acc = self.acc;
+        let mut last = [0u8; STRIPE_LEN];
+        if self.buffer_len >= STRIPE_LEN {
+            let whole = (self.buffer_len - 1) / STRIPE_LEN * STRIPE_LEN;
+            let mut stripes_done = self.stripes_done;
+            consume_stripes(&mut acc, &mut stripes_done, &self.secret, &self.buffer[..whole]);
+            last.copy_from_slice(&self.buffer[self.buffer_len - STRIPE_LEN..self.buffer_len]);
+        } else {
+            // The last stripe overlaps the end of the previous one
+            let previous = STRIPE_LEN - self.buffer_len;
+            last[..previous].copy_from_slice(&self.buffer[BUFFER_LEN - previous..]);
+            last[previous..].copy_from_slice(&self.buffer[..self.buffer_len]);
+        }
+        let key = self.secret.len() - STRIPE_LEN - SECRET_LASTACC_START;
+        accumulate(&mut acc, &last, &self.secret[key..]);
+        acc
+    }
+
+    fn hash64(&self) -> u64 {
+        if let Some(input) = self.short_input() {
+            return hash64_short(input, self.short_secret(), self.seed);
+        }
+        let start = self.total_len.wrapping_mul(PRIME64_1);
+        merge(&self.finish_long(), &self.secret[SECRET_MERGEACCS_START..], start)
+    }
+
+    fn hash128(&self) -> u128 {
+        if let Some(input) = self.short_input() {
+            return hash128_short(input, self.short_secret(), self.seed);
+        }
+        let acc = self.finish_long();
+        let lo = merge(&acc, &self.secret[SECRET_MERGEACCS_START..], self.total_len.wrapping_mul(PRIME64_1));
+        let hi_key = self.secret.len() - STRIPE_LEN - SECRET_MERGEACCS_START;
+        let hi = merge(&acc, &self.secret[hi_key..], !self.total_len.wrapping_mul(PRIME64_2));
+        u128::from(lo) | (u128::from(hi) << 64)
+    }
+}
+
+/// XXH3 64-bit hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Xxh3_64 {
+    state: State,
+}
+
+impl Xxh3_64 {
+    /// Creates a new hasher with the given seed and the default secret.
+    pub fn new(seed: u64) -> Self {
+        Xxh3_64 { state: State::with_seed(seed) }
+    }
+
+    /// Creates a new hasher with a custom secret of at least
+    /// [`SECRET_SIZE_MIN`] bytes, such as high-entropy random bytes.
+    pub fn with_secret(secret: &[u8]) -> Result<Self, ParameterError> {
+        Ok(Xxh3_64 { state: State::with_secret(secret)? })
+    }
+
+    /// Returns the seed the hasher was created with (0 with a custom
+    /// secret).
+    pub fn seed(&self) -> u64 {
+        self.state.seed
+    }
+
+    /// Feeds data into the hasher. XXH3 has no message length limit.
+    ///
+    /// Updates of any size give the same result as a single call.
+    pub fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        self.state.hash64()
+    }
+
+    /// Returns the hash of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping its seed or secret.
+    pub fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Returns the output size in bytes (8 for XXH3-64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the stripe size in bytes (64 for XXH3).
+    pub fn block_size() -> usize {
+        STRIPE_LEN
+    }
+}
+
+impl Default for Xxh3_64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Xxh3_64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Xxh3_64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Xxh3_64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Xxh3_64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Xxh3_64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Xxh3_64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Xxh3_64::block_size()
+    }
+}
+
+/// XXH3 128-bit hasher.
+///
+/// The integer value packs the high 64-bit half above the low one, so the
+/// big-endian `digest()` matches the reference canonical representation.
+#[derive(Clone)]
+pub struct Xxh3_128 {
+    state: State,
+}
+
+impl Xxh3_128 {
+    /// Creates a new hasher with the given seed and the default secret.
+    pub fn new(seed: u64) -> Self {
+        Xxh3_128 { state: State::with_seed(seed) }
+    }
+
+    /// Creates a new hasher with a custom secret of at least
+    /// [`SECRET_SIZE_MIN`] bytes.
+    pub fn with_secret(secret: &[u8]) -> Result<Self, ParameterError> {
+        Ok(Xxh3_128 { state: State::with_secret(secret)? })
+    }
+
+    /// Returns the seed the hasher was created with (0 with a custom
+    /// secret).
+    pub fn seed(&self) -> u64 {
+        self.state.seed
+    }
+
+    /// Feeds data into the hasher. XXH3 has no message length limit.
+    pub fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u128 {
+        self.state.hash128()
+    }
+
+    /// Returns the hash of the data fed so far as 16 big-endian bytes.
+    pub fn digest(&self) -> [u8; 16] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping its seed or secret.
+    pub fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Returns the output size in bytes (16 for XXH3-128).
+    pub fn digest_size() -> usize {
+        16
+    }
+
+    /// Returns the stripe size in bytes (64 for XXH3).
+    pub fn block_size() -> usize {
+        STRIPE_LEN
+    }
+}
+
+impl Default for Xxh3_128 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Xxh3_128 {
+    type Value = u128;
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        Xxh3_128::update(self, data);
+    }
+
+    fn intdigest(&self) -> u128 {
+        Xxh3_128::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        Xxh3_128::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Xxh3_128::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Xxh3_128::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Xxh3_128::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `PRIME64` in the reference sanity checks, used as a seed.
+    const SANITY_SEED: u64 = 11400714785074694797;
+
+    /// The reference sanity-check buffer (see the xxhash tests).
+    fn sanity_buffer(len: usize) -> Vec<u8> {
+        let mut generator: u64 = 2654435761;
+        (0..len)
+            .map(|_| {
+                let byte = (generator >> 56) as u8;
+                generator = generator.wrapping_mul(11400714785074694797);
+                byte
+            })
+            .collect()
+    }
+
+    /// Lengths of the reference sanity checks, covering every code path.
+    const LENGTHS: [usize; 13] = [0, 1, 6, 12, 24, 48, 80, 195, 403, 512, 2048, 2240, 2367];
+
+    /// The sanity checks' custom secret: 147 bytes of the buffer.
+    fn sanity_secret(buffer: &[u8]) -> &[u8] {
+        &buffer[7..7 + SECRET_SIZE_MIN + 11]
+    }
+
+    #[test]
+    fn test_xxh3_64_sanity_vectors() {
+        // xxhsum sanity checks (tests/sanity_test_vectors.h), by length:
+        // seed 0, seed PRIME64 and custom secret
+        let expected: [[u64; 3]; 13] = [
+            [0x2d06800538d394c2, 0xa8a6b918b2f0364a, 0x3559d64878c5c66c],
+            [0xc44bdff4074eecdb, 0x032be332dd766ef8, 0x8a52451418b2da4d],
+            [0x27b56a84cd2d7325, 0x84589c116ab59ab9, 0x82c90ab0519369ad],
+            [0xa713daf0dfbb77e7, 0xe7303e1b2336de0e, 0x14631e773b78ec57],
+            [0xa3fe70bf9d3510eb, 0x850e80fc35bdd690, 0xcdd5542e4a9d9fe8],
+            [0x397da259ecba1f11, 0xadc2cbaa44acc616, 0x33abd54d094b2534],
+            [0xbcdefbbb2c47c90a, 0xc6dd0cb699532e73, 0xe687ba1684965297],
+            [0xcd94217ee362ec3a, 0xba68003d370cb3d9, 0xa057273f5eecfb20],
+            [0xcdeb804d65c6dea4, 0x6259f6ecfd6443fd, 0x14546019124d43b8],
+            [0x617e49599013cb6b, 0x3ce457de14c27708, 0x7564693dd526e28d],
+            [0xdd59e2c3a5f038e0, 0x66f81670669ababc, 0xd32e975821d6519f],
+            [0x6e73a90539cf2948, 0x757ba8487d1b5247, 0xb26c938c7af3a71f],
+            [0xcb37aeb9e5d361ed, 0xd2db3415b942b42a, 0x293fa8e5173bb5e7],
+        ];
+        let buffer = sanity_buffer(2367);
+        for (len, [seed0, seeded, secret]) in LENGTHS.into_iter().zip(expected) {
+            let data = &buffer[..len];
+            let mut hashers = [
+                Xxh3_64::new(0),
+                Xxh3_64::new(SANITY_SEED),
+                Xxh3_64::with_secret(sanity_secret(&buffer)).unwrap(),
+            ];
+            for (hasher, expected) in hashers.iter_mut().zip([seed0, seeded, secret]) {
+                hasher.update(data);
+                assert_eq!(hasher.intdigest(), expected, "len {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_xxh3_128_sanity_vectors() {
+        let expected: [[u128; 3]; 13] = [
+            [
+                0x99aa06d3014798d86001c324468d497f,
+                0x00feaa732a3ce25ea986dfc5d7605bfe,
+                0x5f70f4ea232f1d38005923cceecbe8ae,
+            ],
+            [
+                0xa6cd5e9392000f6ac44bdff4074eecdb,
+                0x20e49abcc53b3842032be332dd766ef8,
+                0x3a66af5a9819198e8a52451418b2da4d,
+            ],
+            [
+                0x082afe0b8162d12a3e7039bdda43cfc6,
+                0x014bd95a51ca5ddbc5b54d56038e4e40,
+                0x376bd91b6432f36d0b61c8aca7d4778f,
+            ],
+            [
+                0x6e3efd8fc7802b18061a192713f69ad9,
+                0xff0d60acd02ed4015d92b5d7190b12d1,
+                0x90a3c2d839f57d0faf82f6eba263d7d8,
+            ],
+            [
+                0x0ce966e4678d37611e7044d28b1b901d,
+                0xd7895ded1f62559dc6cbf92a70680b19,
+                0x3476c01ab8b8e821d9ed8351e0bb5526,
+            ],
+            [
+                0xa002ac4e5478227ef942219aed80f67b,
+                0xbc689f4c0152fb443a94d91333ed395a,
+                0x2c599633a4d78138d3488d14a0fc9147,
+            ],
+            [
+                0xfdf2cefde9eaac8a454ae6bf7a8a532d,
+                0x19bf02d69bc56833a5eac764d1ff1166,
+                0x5de1c8eb7bd056b812568d8d732f8544,
+            ],
+            [
+                0x7729543a26b207ee3fb593c086a66075,
+                0x0326104c4d4849e7cf9d9ec2c8c9913f,
+                0x18783075f43015be002cdb4476b9a36f,
+            ],
+            [
+                0x1b6de21e332dd73dcdeb804d65c6dea4,
+                0xbed311971e0be8f26259f6ecfd6443fd,
+                0xe14eedf084a487f314546019124d43b8,
+            ],
+            [
+                0x18d2d110dcc9bca1617e49599013cb6b,
+                0x925d06b8ec5b80403ce457de14c27708,
+                0x918c0f2c7656ab6d7564693dd526e28d,
+            ],
+            [
+                0xf736557fd47073a5dd59e2c3a5f038e0,
+                0x23cc3a2e75ebaaea66f81670669ababc,
+                0xe862d841c07049afd32e975821d6519f,
+            ],
+            [
+                0xccb134fbfa7ce49d6e73a90539cf2948,
+                0xe40842f585875ba9757ba8487d1b5247,
+                0x1e89ee710a768055b26c938c7af3a71f,
+            ],
+            [
+                0xe89c0f6ff369b427cb37aeb9e5d361ed,
+                0xccb7a94cca1a6496d2db3415b942b42a,
+                0x343654a35acf0dae293fa8e5173bb5e7,
+            ],
+        ];
+        let buffer = sanity_buffer(2367);
+        for (len, [seed0, seeded, secret]) in LENGTHS.into_iter().zip(expected) {
+            let data = &buffer[..len];
+            let mut hashers = [
+                Xxh3_128::new(0),
+                Xxh3_128::new(SANITY_SEED),
+                Xxh3_128::with_secret(sanity_secret(&buffer)).unwrap(),
+            ];
+            for (hasher, expected) in hashers.iter_mut().zip([seed0, seeded, secret]) {
+                hasher.update(data);
+                assert_eq!(hasher.intdigest(), expected, "len {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_xxh3_streaming_matches_one_shot() {
+        // Crosses the 256-byte buffer and the 1024-byte default block, and
+        // leaves final stripes both shorter and longer than 64 bytes
+        let data = sanity_buffer(3000);
+        for len in [241, 256, 257, 320, 1024, 1025, 1088, 2367, 3000] {
+            let data = &data[..len];
+            let mut one_shot = Xxh3_128::new(SANITY_SEED);
+            one_shot.update(data);
+            for step in [1, 7, 63, 64, 65, 255, 256, 257, 1000] {
+                let mut hasher = Xxh3_128::new(SANITY_SEED);
+                for chunk in data.chunks(step) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.intdigest(), one_shot.intdigest(), "len {} step {}", len, step);
+            }
+        }
+    }
+
+    #[test]
+    fn test_xxh3_digest_and_reset() {
+        let mut hasher = Xxh3_64::default();
+        hasher.update(b"a");
+        assert_eq!(hasher.hexdigest(), "e6c632b61e964e1f");
+        hasher.update(&[0u8; 500]);
+        hasher.reset();
+        assert_eq!(hasher.intdigest(), 0x2d06800538d394c2);
+
+        let mut hasher = Xxh3_128::default();
+        hasher.update(b"a");
+        assert_eq!(hasher.hexdigest(), "a96faf705af16834e6c632b61e964e1f");
+    }
+
+    #[test]
+    fn test_xxh3_rejects_short_secret() {
+        let secret = [0u8; SECRET_SIZE_MIN];
+        assert!(Xxh3_64::with_secret(&secret).is_ok());
+        let err = Xxh3_128::with_secret(&secret[1..]).err().unwrap();
+        assert_eq!(err.to_string(), "XXH3: secret must be at least 136 bytes");
+    }
+}
@@ -0,0 +1,541 @@
+//! xxHash non-cryptographic hash functions.
+//!
+//! Pure Rust implementation following the xxHash specification
+//! (`doc/xxhash_spec.md` in the reference repository). XXH32 and XXH64
+//! run four independent accumulators over 16- and 32-byte stripes, then
+//! merge them and mix in the remaining bytes and the input length.
+//!
+//! # Algorithm Details
+//!
+//! - **Stripe size**: 16 bytes (four 32-bit lanes) for XXH32, 32 bytes
+//!   (four 64-bit lanes) for XXH64
+//! - **Output**: 32 or 64 bits, written big-endian by `digest()` as in the
+//!   reference canonical representation
+//! - **Seed**: any 32- or 64-bit value; seed 0 is the common default
+
+use super::Checksum;
+use crate::utils::to_hex;
+
+pub(super) const PRIME32_1: u32 = 0x9e3779b1;
+pub(super) const PRIME32_2: u32 = 0x85ebca77;
+pub(super) const PRIME32_3: u32 = 0xc2b2ae3d;
+pub(super) const PRIME32_4: u32 = 0x27d4eb2f;
+pub(super) const PRIME32_5: u32 = 0x165667b1;
+
+pub(super) const PRIME64_1: u64 = 0x9e3779b185ebca87;
+pub(super) const PRIME64_2: u64 = 0xc2b2ae3d27d4eb4f;
+pub(super) const PRIME64_3: u64 = 0x165667b19e3779f9;
+pub(super) const PRIME64_4: u64 = 0x85ebca77c2b2ae63;
+pub(super) const PRIME64_5: u64 = 0x27d4eb2f165667c5;
+
+/// Mixes one 4-byte lane into an XXH32 accumulator.
+fn round32(acc: u32, lane: u32) -> u32 {
+    acc.wrapping_add(lane.wrapping_mul(PRIME32_2))
+        .rotate_left(13)
+        .wrapping_mul(PRIME32_1)
+}
+
+/// Reads the little-endian 32-bit lane at `offset`.
+fn read32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Mixes one 8-byte lane into an XXH64 accumulator.
+fn round64(acc: u64, lane: u64) -> u64 {
+    acc.wrapping_add(lane.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+/// Folds an accumulator into the hash after the stripes are done.
+fn merge64(hash: u64, acc: u64) -> u64 {
+    (hash ^ round64(0, acc)).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4)
+}
+
+/// Final XXH64 avalanche, also used by XXH3 for its shortest inputs.
+pub(super) fn avalanche64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME64_3);
+    hash ^ (hash >> 32)
+}
+
+/// Reads the little-endian 64-bit lane at `offset`.
+fn read64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// xxHash32 hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Xxh32 {
+    seed: u32,
+    acc: [u32; 4],
+    buffer: [u8; 16],      // Fixed 16-byte buffer (1 stripe)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u64,        // Total bytes processed, modulo 2^64
+}
+
+impl Xxh32 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u32) -> Self {
+        Xxh32 {
+            seed,
+            acc: [
+                seed.wrapping_add(PRIME32_1).wrapping_add(PRIME32_2),
+                seed.wrapping_add(PRIME32_2),
+                seed,
+                seed.wrapping_sub(PRIME32_1),
+            ],
+            buffer: [0u8; 16],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Mixes one 16-byte stripe into the accumulators.
+    fn stripe(acc: &mut [u32; 4], stripe: &[u8]) {
+        for (i, lane) in acc.iter_mut().enumerate() {
+            *lane = round32(*lane, read32(stripe, i * 4));
+        }
+    }
+
+    /// Feeds data into the hasher. XXH32 has no message length limit: the
+    /// length is mixed in modulo 2^32.
+    ///
+    /// Processes complete stripes directly from the input; only incomplete
+    /// stripes (< 16 bytes) are buffered, so updates of any size, including
+    /// many smaller than a stripe, give the same result as a single call.
+    pub fn update(&mut self, data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(16 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 16 {
+                Self::stripe(&mut self.acc, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 16-byte stripes directly from input
+        while offset + 16 <= data.len() {
+            Self::stripe(&mut self.acc, &data[offset..offset + 16]);
+            offset += 16;
+        }
+
+        // Buffer any remaining bytes (< 16)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u32 {
+        // The accumulators are only used once a full stripe was seen
+        let mut hash = if self.total_len >= 16 {
+            let [a, b, c, d] = self.acc;
+            a.rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18))
+        } else {
+            self.seed.wrapping_add(PRIME32_5)
+        };
+        hash = hash.wrapping_add(self.total_len as u32);
+
+        // Remaining bytes: 4-byte lanes, then single bytes
+        let tail = &self.buffer[..self.buffer_len];
+        let mut lanes = tail.chunks_exact(4);
+        for lane in &mut lanes {
+            hash = hash.wrapping_add(read32(lane, 0).wrapping_mul(PRIME32_3));
+            hash = hash.rotate_left(17).wrapping_mul(PRIME32_4);
+        }
+        for &byte in lanes.remainder() {
+            hash = hash.wrapping_add(u32::from(byte).wrapping_mul(PRIME32_5));
+            hash = hash.rotate_left(11).wrapping_mul(PRIME32_1);
+        }
+
+        // Final avalanche
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(PRIME32_2);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(PRIME32_3);
+        hash ^ (hash >> 16)
+    }
+
+    /// Returns the hash of the data fed so far as 4 big-endian bytes.
+    pub fn digest(&self) -> [u8; 4] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping its seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (4 for XXH32).
+    pub fn digest_size() -> usize {
+        4
+    }
+
+    /// Returns the stripe size in bytes (16 for XXH32).
+    pub fn block_size() -> usize {
+        16
+    }
+}
+
+impl Default for Xxh32 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Xxh32 {
+    type Value = u32;
+    type Output = [u8; 4];
+
+    fn update(&mut self, data: &[u8]) {
+        Xxh32::update(self, data);
+    }
+
+    fn intdigest(&self) -> u32 {
+        Xxh32::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 4] {
+        Xxh32::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Xxh32::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Xxh32::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Xxh32::block_size()
+    }
+}
+
+/// xxHash64 hasher.
+///
+/// Cloning captures the full streaming state, so a clone can be finalized
+/// or extended independently of the original.
+#[derive(Clone)]
+pub struct Xxh64 {
+    seed: u64,
+    acc: [u64; 4],
+    buffer: [u8; 32],      // Fixed 32-byte buffer (1 stripe)
+    buffer_len: usize,     // Number of bytes currently in buffer
+    total_len: u64,        // Total bytes processed, modulo 2^64
+}
+
+impl Xxh64 {
+    /// Creates a new hasher with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Xxh64 {
+            seed,
+            acc: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            buffer: [0u8; 32],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Returns the seed the hasher was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Mixes one 32-byte stripe into the accumulators.
+    fn stripe(acc: &mut [u64; 4], stripe: &[u8]) {
+        for (i, lane) in acc.iter_mut().enumerate() {
+            *lane = round64(*lane, read64(stripe, i * 8));
+        }
+    }
+
+    /// Feeds data into the hasher. XXH64 has no message length limit: the
+    /// length is mixed in modulo 2^64.
+    ///
+    /// Processes complete stripes directly from the input; only incomplete
+    /// stripes (< 32 bytes) are buffered.
+    pub fn update(&mut self, data: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+        let mut offset = 0;
+
+        // If buffer has partial data, try to complete it first
+        if self.buffer_len > 0 {
+            let available = data.len().min(32 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            offset += available;
+
+            if self.buffer_len == 32 {
+                Self::stripe(&mut self.acc, &self.buffer);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process complete 32-byte stripes directly from input
+        while offset + 32 <= data.len() {
+            Self::stripe(&mut self.acc, &data[offset..offset + 32]);
+            offset += 32;
+        }
+
+        // Buffer any remaining bytes (< 32)
+        let remaining = data.len() - offset;
+        if remaining > 0 {
+            self.buffer[..remaining].copy_from_slice(&data[offset..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Returns the hash of the data fed so far as an integer.
+    pub fn intdigest(&self) -> u64 {
+        let mut hash = if self.total_len >= 32 {
+            let [a, b, c, d] = self.acc;
+            let hash = a
+                .rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18));
+            self.acc.iter().fold(hash, |hash, &acc| merge64(hash, acc))
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        hash = hash.wrapping_add(self.total_len);
+
+        // Remaining bytes: 8-byte lanes, then a 4-byte lane, then single bytes
+        let tail = &self.buffer[..self.buffer_len];
+        let mut lanes = tail.chunks_exact(8);
+        for lane in &mut lanes {
+            hash ^= round64(0, read64(lane, 0));
+            hash = hash.rotate_left(27).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4);
+        }
+        let mut rest = lanes.remainder();
+        if rest.len() >= 4 {
+            let word = u32::from_le_bytes(rest[..4].try_into().unwrap());
+            hash ^= u64::from(word).wrapping_mul(PRIME64_1);
+            hash = hash.rotate_left(23).wrapping_mul(PRIME64_2).wrapping_add(PRIME64_3);
+            rest = &rest[4..];
+        }
+        for &byte in rest {
+            hash ^= u64::from(byte).wrapping_mul(PRIME64_5);
+            hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+
+        avalanche64(hash)
+    }
+
+    /// Returns the hash of the data fed so far as 8 big-endian bytes.
+    pub fn digest(&self) -> [u8; 8] {
+        self.intdigest().to_be_bytes()
+    }
+
+    /// Returns the hash of the data fed so far as a hexadecimal string.
+    pub fn hexdigest(&self) -> String {
+        to_hex(&self.digest())
+    }
+
+    /// Resets the hasher to its initial state, keeping its seed.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.seed);
+    }
+
+    /// Returns the output size in bytes (8 for XXH64).
+    pub fn digest_size() -> usize {
+        8
+    }
+
+    /// Returns the stripe size in bytes (32 for XXH64).
+    pub fn block_size() -> usize {
+        32
+    }
+}
+
+impl Default for Xxh64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Checksum for Xxh64 {
+    type Value = u64;
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        Xxh64::update(self, data);
+    }
+
+    fn intdigest(&self) -> u64 {
+        Xxh64::intdigest(self)
+    }
+
+    fn digest(&self) -> [u8; 8] {
+        Xxh64::digest(self)
+    }
+
+    fn reset(&mut self) {
+        Xxh64::reset(self);
+    }
+
+    fn digest_size(&self) -> usize {
+        Xxh64::digest_size()
+    }
+
+    fn block_size(&self) -> usize {
+        Xxh64::block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `PRIME32` in the reference sanity checks, used as a seed.
+    const SANITY_SEED: u64 = 2654435761;
+
+    /// The reference sanity-check buffer: the top byte of successive powers
+    /// of `PRIME64` (starting from `PRIME32`).
+    fn sanity_buffer(len: usize) -> Vec<u8> {
+        let mut generator: u64 = 2654435761;
+        (0..len)
+            .map(|_| {
+                let byte = (generator >> 56) as u8;
+                generator = generator.wrapping_mul(11400714785074694797);
+                byte
+            })
+            .collect()
+    }
+
+    fn xxh32(data: &[u8], seed: u32) -> u32 {
+        let mut hasher = Xxh32::new(seed);
+        hasher.update(data);
+        hasher.intdigest()
+    }
+
+    fn xxh64(data: &[u8], seed: u64) -> u64 {
+        let mut hasher = Xxh64::new(seed);
+        hasher.update(data);
+        hasher.intdigest()
+    }
+
+    #[test]
+    fn test_xxh32_sanity_vectors() {
+        // xxhsum sanity checks (tests/sanity_test_vectors.h)
+        let buffer = sanity_buffer(222);
+        let seed = SANITY_SEED as u32;
+        let cases: [(usize, u32, u32); 8] = [
+            (0, 0, 0x02cc5d05),
+            (0, seed, 0x36b78ae7),
+            (1, 0, 0xcf65b03e),
+            (1, seed, 0xb4545aa4),
+            (14, 0, 0x1208e7e2),
+            (14, seed, 0x6af1d1fe),
+            (222, 0, 0x5bd11dbd),
+            (222, seed, 0x58803c5f),
+        ];
+        for (len, seed, expected) in cases {
+            assert_eq!(xxh32(&buffer[..len], seed), expected, "len {} seed {}", len, seed);
+        }
+    }
+
+    #[test]
+    fn test_xxh32_stripe_boundaries() {
+        // Around the 16-byte stripe, with a seed that wraps the accumulators
+        let buffer = sanity_buffer(17);
+        assert_eq!(xxh32(&buffer[..3], u32::MAX), 0x37919432);
+        assert_eq!(xxh32(&buffer[..15], u32::MAX), 0x83c33fab);
+        assert_eq!(xxh32(&buffer[..16], u32::MAX), 0x6d7a6de2);
+        assert_eq!(xxh32(&buffer[..17], 0x7fffffff), 0x32bcd374);
+
+        // Updates smaller than a stripe are buffered across calls
+        let data = sanity_buffer(300);
+        for step in [1, 3, 5, 15, 16, 17, 100] {
+            let mut hasher = Xxh32::new(u32::MAX);
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.intdigest(), xxh32(&data, u32::MAX), "step {}", step);
+        }
+    }
+
+    #[test]
+    fn test_xxh64_sanity_vectors() {
+        // xxhsum sanity checks (tests/sanity_test_vectors.h)
+        let buffer = sanity_buffer(222);
+        let cases: [(usize, u64, u64); 9] = [
+            (0, 0, 0xef46db3751d8e999),
+            (0, SANITY_SEED, 0xac75fda2929b17ef),
+            (1, 0, 0xe934a84adb052768),
+            (1, SANITY_SEED, 0x5014607643a9b4c3),
+            (4, 0, 0x9136a0dca57457ee),
+            (14, 0, 0x8282dcc4994e35c8),
+            (14, SANITY_SEED, 0xc3bd6bf63deb6df0),
+            (222, 0, 0xb641ae8cb691c174),
+            (222, SANITY_SEED, 0x20cb8ab7ae10c14a),
+        ];
+        for (len, seed, expected) in cases {
+            assert_eq!(xxh64(&buffer[..len], seed), expected, "len {} seed {}", len, seed);
+        }
+    }
+
+    #[test]
+    fn test_xxh64_digest_is_big_endian() {
+        let mut hasher = Xxh64::default();
+        hasher.update(b"a");
+        assert_eq!(hasher.intdigest(), 0xd24ec4f1a98c6e5b);
+        assert_eq!(hasher.hexdigest(), "d24ec4f1a98c6e5b");
+    }
+
+    #[test]
+    fn test_xxh64_streaming_matches_one_shot() {
+        let data = sanity_buffer(300);
+        for step in [1, 3, 7, 8, 31, 32, 33, 100] {
+            let mut hasher = Xxh64::new(SANITY_SEED);
+            for chunk in data.chunks(step) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.intdigest(), xxh64(&data, SANITY_SEED), "step {}", step);
+        }
+    }
+
+    #[test]
+    fn test_xxh64_reset_keeps_seed() {
+        let mut hasher = Xxh64::new(42);
+        hasher.update(b"some data");
+        hasher.reset();
+        assert_eq!(hasher.seed(), 42);
+        assert_eq!(hasher.intdigest(), xxh64(b"", 42));
+    }
+}
@@ -4,192 +4,4188 @@
 //!
 //! # Classes
 //!
+//! - [`PyMD2`] - MD2 hash object (only reachable through `new("md2")`)
+//! - [`PyMD4`] - MD4 hash object
+//! - [`PyMD5`] - MD5 hash object
+//! - [`PySHA1`] - SHA-1 hash object
+//! - [`PySHA224`] - SHA-224 hash object
 //! - [`PySHA256`] - SHA-256 hash object
 //! - [`PySHA512`] - SHA-512 hash object
+//! - [`PySHA512_224`] - SHA-512/224 hash object
+//! - [`PySHA512T`] - SHA-512/t hash object
+//! - [`PySHA3_224`], [`PySHA3_256`], [`PySHA3_384`], [`PySHA3_512`] - SHA-3 hash objects
+//! - [`PySHAKE128`], [`PySHAKE256`] - SHAKE extendable-output objects
+//! - [`PyKeccak256`], [`PyKeccak512`] - original Keccak hash objects
+//! - [`PyRIPEMD128`], [`PyRIPEMD160`], [`PyRIPEMD256`], [`PyRIPEMD320`] - RIPEMD hash objects
+//! - [`PySM3`] - SM3 hash object
+//! - [`PyWhirlpool`] - Whirlpool hash object
+//! - [`PyTiger`], [`PyTiger2`] - Tiger/192 hash objects
+//! - [`PySkein256`], [`PySkein512`], [`PySkein1024`] - Skein hash objects
+//! - [`PyGroestl256`], [`PyGroestl512`] - Grøstl hash objects
+//! - [`PyJH256`], [`PyJH512`] - JH hash objects
+//! - [`PyAsconHash256`], [`PyAsconXOF128`] - Ascon hash and extendable-output objects
+//! - [`PyLSH256`], [`PyLSH512`] - LSH hash objects
+//! - [`PyKupyna256`], [`PyKupyna512`] - Kupyna hash objects
+//! - [`PyGOST94`], [`PyGOST94CryptoPro`] - GOST R 34.11-94 hash objects
+//! - [`PyHAS160`] - HAS-160 hash object
+//! - [`PyMD6`] - MD6 hash object
+//! - [`PyCSHAKE128`], [`PyCSHAKE256`] - cSHAKE extendable-output objects
+//! - [`PyTupleHash128`], [`PyTupleHash256`] - TupleHash objects
+//! - [`PyParallelHash128`], [`PyParallelHash256`] - ParallelHash objects
+//! - [`PyTurboSHAKE128`], [`PyTurboSHAKE256`] - TurboSHAKE extendable-output objects
+//! - [`PyKangarooTwelve`] - KangarooTwelve extendable-output object
+//! - [`PyBLAKE2b`], [`PyBLAKE2s`] - BLAKE2 hash objects
+//! - [`PyBLAKE2bp`], [`PyBLAKE2sp`] - parallel BLAKE2 hash objects
+//! - [`PyBLAKE2Xb`], [`PyBLAKE2Xs`] - BLAKE2X extendable-output functions
+//! - [`PyBLAKE3`] - BLAKE3 hash object
+//! - [`PyHMAC`] - HMAC object, as in the hmac module
+//! - [`PyHmacDrbg`] - HMAC-DRBG deterministic random bit generator (SP 800-90A)
+//! - [`PyPoly1305`] - Poly1305 one-time authenticator (RFC 8439)
+//! - [`PyChecksumResult`] - Outcome of checking a checksum file
 //!
 //! # Functions
 //!
 //! - [`new`] - Factory function to create hash objects by name
+//! - [`sha512_t`] - SHA-512/t hash object for a given output size
+//! - [`cshake128`], [`cshake256`] - cSHAKE hash objects
+//! - [`blake2b`], [`blake2s`] - BLAKE2 hash objects, as in hashlib
+//! - [`blake2bp`], [`blake2sp`] - parallel BLAKE2 hash objects
+//! - [`blake2xb`], [`blake2xs`] - BLAKE2X extendable-output functions
+//! - [`blake3`] - BLAKE3 hash object
+//! - [`blake3_keyed`], [`blake3_derive_key`] - one-shot BLAKE3 MAC and key derivation
+//! - [`skein256`], [`skein512`], [`skein1024`] - Skein hash objects of any digest size
+//! - [`md6`] - MD6 hash object of any digest size
+//! - [`sha256_digest`], [`sha256_hexdigest`] - One-shot SHA-256
+//! - [`sha512_digest`], [`sha512_hexdigest`] - One-shot SHA-512
+//! - [`hash_file`] - Hashes a file read in Rust, with the GIL released
+//! - [`hash_file_range`] - Hashes a byte range of a file, with the GIL released
+//! - [`verify_file`], [`verify_bytes`] - Checks a file or buffer against an expected digest
+//! - [`write_checksum_file`], [`check_checksum_file`] - `sha256sum` checksum files
+//! - [`file_digest`] - Hashes a binary file object, as `hashlib.file_digest()`
+//! - [`hmac_digest`], [`hmac_hexdigest`] - One-shot HMAC, as `hmac.digest()`
+//! - [`hmac_verify`] - One-shot HMAC check with a constant-time comparison
+//! - [`compare_digest`] - Constant-time comparison, as `hmac.compare_digest`
+//! - [`poly1305`] - One-shot Poly1305 tag
+//! - [`hotp`], [`hotp_verify`] - HMAC-based one-time passwords (RFC 4226)
+//! - [`totp`], [`totp_verify`], [`totp_uri`] - time-based one-time passwords (RFC 6238)
+//! - [`hkdf`], [`hkdf_extract`], [`hkdf_expand`] - HKDF key derivation (RFC 5869)
+//! - [`hkdf_expand_label`], [`tls13_derive_secret`] - TLS 1.3 key schedule (RFC 8446)
+//! - [`kbkdf_counter`] - SP 800-108 KBKDF in counter mode with HMAC
+//! - [`concat_kdf`], [`x963_kdf`], [`jose_other_info`] - ConcatKDF and ANSI X9.63 KDF
+//! - [`pbkdf2_hmac`] - PBKDF2-HMAC, as in hashlib
+//! - [`scrypt`] - scrypt memory-hard key derivation (RFC 7914), as in hashlib
+//! - [`argon2_hash`], [`argon2_phc`], [`argon2_verify`] - Argon2 password hashing (RFC 9106)
+//! - [`bcrypt_hash`], [`bcrypt_verify`] - bcrypt password hashing (`$2b$` strings)
+//! - [`bcrypt_pbkdf`] - OpenBSD bcrypt_pbkdf, as used for OpenSSH private keys
+//! - [`sha256_crypt`], [`sha512_crypt`] - SHA-crypt password hashing (`$5$`/`$6$` strings)
+//! - [`crypt_verify`] - checks SHA-crypt and bcrypt strings, as in `/etc/shadow`
+//! - [`password_hash`], [`password_verify`], [`password_needs_rehash`] - any password scheme
+//!   behind one interface, with [`InvalidHashError`] for unusable stored hashes
+//! - [`balloon`] - Balloon and Balloon-M memory-hard password hashing
+//! - [`set_fips_mode`], [`get_fips_mode`] - Module-wide strict mode toggle
+//!
+//! Algorithm names accepted by [`new`] are listed in [`registry`]. The
+//! non-cryptographic hashes live in the [`noncrypto`] submodule.
+//!
+//! # Thread safety
+//!
+//! Hash objects can be shared between threads, including on free-threaded
+//! CPython builds. Each object guards its state with a lock, so concurrent
+//! calls are applied one at a time in some order rather than interleaved.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{
+    PyBlockingIOError, PyBufferError, PyOSError, PyOverflowError, PyRuntimeError, PyTypeError,
+    PyValueError,
+};
+use pyo3::intern;
+use pyo3::types::{
+    PyByteArray, PyBytes, PyDict, PyList, PyMemoryView, PySlice, PyString, PyTuple, PyType,
+};
+use pyo3::pyclass::boolean_struct::True;
+use pyo3::pyclass_init::PyClassInitializer;
+use pyo3::{PyClass, PyTypeInfo};
+use crate::core::{
+    Argon2Params, Argon2Variant, AsconHash256, AsconXof128, Blake2Params, Blake2Xb, Blake2Xs,
+    Blake2b, Blake2bp, Blake2s, Blake2sp, Blake3, CShake128, CShake256, Digest, DynDigest, Gost94,
+    Gost94SBox, Groestl256, Groestl512, Has160, Hmac, HmacDrbg, Jh256, Jh512, KangarooTwelve,
+    Keccak256, Keccak512, Kupyna256, Kupyna512, LengthOverflowError, Lsh256, Lsh512, Md2, Md4, Md5,
+    Md6, ParallelHash128, ParallelHash256, ParameterError, PasswordScheme, Poly1305, Ripemd128,
+    Ripemd160, Ripemd256, Ripemd320, Sha1, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha224, Sha256,
+    Sha512, Sha512_224, Sha512T, Shake128, Shake256, Skein256, Skein512, Skein1024, Sm3, Tiger,
+    Tiger2, TupleHash128, TupleHash256, TurboShake128, TurboShake256, Whirlpool, Xof,
+};
+use crate::core::blake3::{KEY_LEN as BLAKE3_KEY_LEN, OUT_LEN as BLAKE3_OUT_LEN};
+use crate::core::hmac_drbg::{MAX_REQUEST_LEN, RESEED_INTERVAL};
+use crate::core::otp::totp_time_step;
+use crate::core::poly1305::{
+    BLOCK_SIZE as POLY1305_BLOCK_SIZE, KEY_SIZE as POLY1305_KEY_SIZE, TAG_SIZE as POLY1305_TAG_SIZE,
+};
+use crate::checksum;
+use crate::utils::{constant_time_eq, from_hex, hex_into, to_hex};
+
+mod buffer;
+pub mod noncrypto;
+pub mod registry;
+
+use buffer::{ByteView, BytesArg};
+
+impl From<ParameterError> for PyErr {
+    fn from(err: ParameterError) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+impl From<LengthOverflowError> for PyErr {
+    fn from(err: LengthOverflowError) -> PyErr {
+        PyOverflowError::new_err(err.to_string())
+    }
+}
+
+/// Module-wide FIPS-style strict mode.
+///
+/// When enabled, algorithms that are not approved for security use can only
+/// be constructed with `usedforsecurity=False`. SHA-1 and the SHA-2 family
+/// (FIPS 180-4), the SHA-3 and SHAKE functions (FIPS 202) and the Ascon
+/// hash functions (SP 800-232) stay usable; MD5 and the original Keccak
+/// padding are not.
+static FIPS_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables FIPS-style strict mode for the whole module.
+#[pyfunction]
+pub fn set_fips_mode(enabled: bool) {
+    FIPS_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether FIPS-style strict mode is enabled.
+#[pyfunction]
+pub fn get_fips_mode() -> bool {
+    FIPS_MODE.load(Ordering::Relaxed)
+}
+
+/// Fails if strict mode forbids using an unapproved algorithm for security.
+///
+/// Mirrors hashlib on FIPS-enabled OpenSSL builds, where such algorithms are
+/// only available with `usedforsecurity=False`.
+fn check_approved(algorithm: &str, approved: bool, usedforsecurity: bool) -> PyResult<()> {
+    if approved || !usedforsecurity || !FIPS_MODE.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    Err(PyValueError::new_err(format!(
+        "{} is not approved in FIPS mode; pass usedforsecurity=False for non-security uses",
+        algorithm
+    )))
+}
+
+/// Inputs at least this large are hashed with the GIL released.
+///
+/// Below this size the cost of releasing and reacquiring the GIL outweighs
+/// the hashing work itself.
+const GIL_RELEASE_THRESHOLD: usize = 4096;
+
+/// Runs `f` over `data`, releasing the GIL when the input is large enough.
+fn with_gil_released<T, F>(py: Python, data: &[u8], f: F) -> T
+where
+    T: Send,
+    F: FnOnce(&[u8]) -> T + Send,
+{
+    if data.len() >= GIL_RELEASE_THRESHOLD {
+        py.allow_threads(|| f(data))
+    } else {
+        f(data)
+    }
+}
+
+/// Feeds the bytes of `obj` to `update`.
+///
+/// C-contiguous buffers are hashed in place, with the GIL released for large
+/// inputs. Strided buffers (e.g. `memoryview(b)[::2]` or a sliced NumPy
+/// array) are rejected with `BufferError` unless `copy` is set, in which case
+/// they are gathered in logical order chunk by chunk.
+fn feed_buffer<F>(py: Python, obj: &Bound<'_, PyAny>, copy: bool, update: F) -> PyResult<()>
+where
+    F: FnMut(&[u8]) -> Result<(), LengthOverflowError> + Send,
+{
+    feed_view(py, &ByteView::get(obj)?, copy, update)
+}
+
+/// Feeds the bytes of an already exported buffer to `update` (see
+/// [`feed_buffer`]).
+///
+/// Exporting a buffer can run Python code, such as a `__buffer__` method, so
+/// objects that lock their state take the view first and feed it under the
+/// lock.
+fn feed_view<F>(py: Python, view: &ByteView<'_>, copy: bool, mut update: F) -> PyResult<()>
+where
+    F: FnMut(&[u8]) -> Result<(), LengthOverflowError> + Send,
+{
+    if let Some(bytes) = view.as_slice() {
+        with_gil_released(py, bytes, update)?;
+    } else if copy {
+        view.for_each_chunk(&mut update)?;
+    } else {
+        return Err(PyBufferError::new_err(
+            "buffer is not C-contiguous; pass copy=True to hash it in logical order",
+        ));
+    }
+    Ok(())
+}
+
+/// Feeds the bytes of `obj` to `update` in a single call.
+///
+/// Like [`feed_buffer`], but strided buffers (with `copy` set) are gathered
+/// into one temporary copy, for hashers that treat each call as a separate
+/// input element.
+fn feed_item<F>(py: Python, obj: &Bound<'_, PyAny>, copy: bool, update: F) -> PyResult<()>
+where
+    F: FnOnce(&[u8]) -> Result<(), LengthOverflowError> + Send,
+{
+    feed_item_view(py, &ByteView::get(obj)?, copy, update)
+}
+
+/// Feeds the bytes of an already exported buffer to `update` in a single
+/// call (see [`feed_item`] and [`feed_view`]).
+fn feed_item_view<F>(py: Python, view: &ByteView<'_>, copy: bool, update: F) -> PyResult<()>
+where
+    F: FnOnce(&[u8]) -> Result<(), LengthOverflowError> + Send,
+{
+    if let Some(bytes) = view.as_slice() {
+        with_gil_released(py, bytes, update)?;
+    } else if copy {
+        let mut item = Vec::new();
+        view.for_each_chunk(|chunk| {
+            item.extend_from_slice(chunk);
+            Ok::<_, PyErr>(())
+        })?;
+        with_gil_released(py, &item, update)?;
+    } else {
+        return Err(PyBufferError::new_err(
+            "buffer is not C-contiguous; pass copy=True to hash it in logical order",
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects constructor arguments the hash classes do not understand.
+///
+/// Mirrors `object.__new__`: extra arguments are an error for the classes
+/// themselves, but are ignored when constructing a Python subclass, whose
+/// own `__init__` is expected to consume them.
+fn check_extra_args<T: PyTypeInfo>(
+    cls: &Bound<'_, PyType>,
+    args: &Bound<'_, PyTuple>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<()> {
+    if !cls.is(&T::type_object(cls.py())) {
+        return Ok(());
+    }
+    if !args.is_empty() {
+        return Err(PyTypeError::new_err(format!(
+            "{}() takes at most 1 positional argument ({} given)",
+            T::NAME,
+            args.len() + 1
+        )));
+    }
+    if let Some((key, _)) = kwargs.and_then(|kwargs| kwargs.iter().next()) {
+        return Err(PyTypeError::new_err(format!(
+            "{}() got an unexpected keyword argument '{}'",
+            T::NAME,
+            key
+        )));
+    }
+    Ok(())
+}
+
+/// Mutable state of a Python hash object, guarded by the object's lock.
+#[derive(Clone)]
+struct HashState<H: Digest> {
+    hasher: H,
+    /// Digest of the current state, cleared by `update()`.
+    cached_digest: Option<H::Output>,
+    /// Whether the caller declared this hash as security-relevant.
+    usedforsecurity: bool,
+}
+
+impl<H: Digest> HashState<H> {
+    fn new(hasher: H, usedforsecurity: bool) -> Self {
+        HashState { hasher, cached_digest: None, usedforsecurity }
+    }
+
+    /// Feeds the bytes of `view` to the hasher (see [`feed_view`]).
+    fn update(&mut self, py: Python, view: &ByteView<'_>, copy: bool) -> PyResult<()> {
+        let hasher = &mut self.hasher;
+        feed_view(py, view, copy, |bytes| hasher.try_update(bytes))?;
+        self.cached_digest = None;
+        Ok(())
+    }
+
+    /// Returns the digest of the current state, computing it at most once
+    /// between updates.
+    fn digest(&mut self) -> H::Output {
+        self.cached_digest.get_or_insert_with(|| self.hasher.digest()).clone()
+    }
+}
+
+/// Mutable state of a Python XOF object, guarded by the object's lock.
+///
+/// The output depends on the requested length, so unlike [`HashState`]
+/// nothing is cached.
+#[derive(Clone)]
+struct XofState<H: Xof> {
+    hasher: H,
+    /// Whether the caller declared this hash as security-relevant.
+    usedforsecurity: bool,
+}
+
+impl<H: Xof> XofState<H> {
+    fn new(hasher: H, usedforsecurity: bool) -> Self {
+        XofState { hasher, usedforsecurity }
+    }
+
+    /// Feeds the bytes of `view` to the hasher (see [`feed_view`]).
+    fn update(&mut self, py: Python, view: &ByteView<'_>, copy: bool) -> PyResult<()> {
+        let hasher = &mut self.hasher;
+        feed_view(py, view, copy, |bytes| hasher.try_update(bytes))
+    }
+}
+
+/// Mutable state of a Python object whose `update()` calls each feed one
+/// input element, such as a TupleHash tuple item.
+///
+/// Same as [`XofState`], except that every buffer reaches the hasher in a
+/// single call: strided buffers are gathered into a temporary copy instead
+/// of being split into chunks.
+#[derive(Clone)]
+struct ItemState<H: Xof> {
+    hasher: H,
+    /// Whether the caller declared this hash as security-relevant.
+    usedforsecurity: bool,
+}
+
+impl<H: Xof> ItemState<H> {
+    fn new(hasher: H, usedforsecurity: bool) -> Self {
+        ItemState { hasher, usedforsecurity }
+    }
+
+    /// Feeds the bytes of `view` to the hasher as one element (see
+    /// [`feed_item_view`]).
+    fn update(&mut self, py: Python, view: &ByteView<'_>, copy: bool) -> PyResult<()> {
+        let hasher = &mut self.hasher;
+        feed_item_view(py, view, copy, |bytes| hasher.try_update(bytes))
+    }
+}
+
+/// Streaming states that `update_from()` can feed from a file object.
+trait StreamState: Send {
+    /// Whether every update is one input element, so that a file must reach
+    /// the hasher in a single call rather than chunk by chunk.
+    const WHOLE_ITEMS: bool = false;
+
+    /// Feeds `bytes` to the hasher as one update.
+    fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), LengthOverflowError>;
+}
+
+impl<H: Digest> StreamState for HashState<H> {
+    fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), LengthOverflowError> {
+        self.cached_digest = None;
+        self.hasher.try_update(bytes)
+    }
+}
+
+impl<H: Xof> StreamState for XofState<H> {
+    fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), LengthOverflowError> {
+        self.hasher.try_update(bytes)
+    }
+}
+
+impl<H: Xof> StreamState for ItemState<H> {
+    const WHOLE_ITEMS: bool = true;
+
+    fn update_bytes(&mut self, bytes: &[u8]) -> Result<(), LengthOverflowError> {
+        self.hasher.try_update(bytes)
+    }
+}
+
+/// Reads `fileobj` until end of file, or until `max_bytes` bytes, and feeds
+/// what it reads to the state behind `mutex`, returning the number of
+/// bytes consumed.
+///
+/// Uses `readinto()` into one reusable `chunk_size`-byte buffer when the
+/// object has it, `read(n)` otherwise. The lock is only held while a chunk
+/// is hashed, with the GIL released for large chunks, never across the
+/// Python calls, which may use the hash object themselves.
+fn update_from_file<S: StreamState>(
+    py: Python,
+    mutex: &Mutex<S>,
+    fileobj: &Bound<'_, PyAny>,
+    chunk_size: usize,
+    max_bytes: Option<u64>,
+) -> PyResult<u64> {
+    check_chunk_size(chunk_size)?;
+    let buf = if fileobj.hasattr(intern!(py, "readinto"))? {
+        Some(PyByteArray::new_with(py, chunk_size, |_| Ok(()))?)
+    } else {
+        None
+    };
+    let mut consumed = 0u64;
+    let mut gathered = Vec::new();
+    loop {
+        let remaining = max_bytes.map_or(u64::MAX, |max| max - consumed);
+        let want = remaining.min(chunk_size as u64) as usize;
+        if want == 0 {
+            break;
+        }
+        let chunk = match &buf {
+            Some(buf) => {
+                let target = if want < chunk_size {
+                    PyMemoryView::from(buf)?.get_item(PySlice::new(py, 0, want as isize, 1))?
+                } else {
+                    buf.clone().into_any()
+                };
+                let size = fileobj.call_method1(intern!(py, "readinto"), (target,))?;
+                if size.is_none() {
+                    return Err(PyBlockingIOError::new_err("I/O operation would block."));
+                }
+                let size: usize = size.extract()?;
+                buf.clone().into_any().get_item(PySlice::new(py, 0, size.min(want) as isize, 1))?
+            }
+            None => {
+                let data = fileobj.call_method1(intern!(py, "read"), (want,))?;
+                if data.is_none() {
+                    return Err(PyBlockingIOError::new_err("I/O operation would block."));
+                }
+                data
+            }
+        };
+        let view = ByteView::get(&chunk)?;
+        let gathered_chunk;
+        let bytes = match view.as_slice() {
+            Some(bytes) => bytes,
+            None => {
+                // read() may return any buffer, such as a strided memoryview
+                let mut copy = Vec::new();
+                view.for_each_chunk(|part| {
+                    copy.extend_from_slice(part);
+                    Ok::<_, PyErr>(())
+                })?;
+                gathered_chunk = copy;
+                &gathered_chunk
+            }
+        };
+        let bytes = &bytes[..bytes.len().min(want)];
+        if bytes.is_empty() {
+            break;
+        }
+        if S::WHOLE_ITEMS {
+            gathered.extend_from_slice(bytes);
+        } else {
+            let state = &mut *lock(py, mutex);
+            with_gil_released(py, bytes, |bytes| state.update_bytes(bytes))?;
+        }
+        consumed += bytes.len() as u64;
+    }
+    if S::WHOLE_ITEMS {
+        let state = &mut *lock(py, mutex);
+        with_gil_released(py, &gathered, |bytes| state.update_bytes(bytes))?;
+    }
+    Ok(consumed)
+}
+
+/// Returns the first `length` output bytes of `hasher` as a Python bytes
+/// object.
+///
+/// The output is written straight into the bytes object, so huge lengths
+/// raise `MemoryError` instead of aborting. Large outputs are squeezed with
+/// the GIL released.
+fn squeeze_bytes<'py, H: Xof>(
+    py: Python<'py>,
+    hasher: H,
+    length: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    PyBytes::new_with(py, length, |out| {
+        if length >= GIL_RELEASE_THRESHOLD {
+            py.allow_threads(move || hasher.squeeze(out));
+        } else {
+            hasher.squeeze(out);
+        }
+        Ok(())
+    })
+}
+
+/// Locks `mutex`, waiting with the GIL released if another thread holds it.
+///
+/// Hash objects stay locked while large inputs are hashed without the GIL.
+/// Blocking on the lock while holding the GIL would deadlock as soon as that
+/// thread needs the GIL back, so contended waits happen outside of it.
+/// A poisoned lock is recovered: updates leave the state consistent.
+fn lock<'a, T: Send>(py: Python, mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+    loop {
+        match mutex.try_lock() {
+            Ok(guard) => return guard,
+            Err(TryLockError::Poisoned(err)) => return err.into_inner(),
+            Err(TryLockError::WouldBlock) => py.allow_threads(|| {
+                drop(mutex.lock());
+            }),
+        }
+    }
+}
+
+/// Python hash objects whose state can be copied into another instance.
+trait LockedState: PyClass<Frozen = True> + Sync + Into<PyClassInitializer<Self>> {
+    /// Returns a new object holding a copy of the current state.
+    fn snapshot(&self, py: Python) -> Self;
+
+    /// Replaces the state of `self` with the state of `other`.
+    fn replace_with(&self, py: Python, other: Self);
+}
+
+/// Copies `slf` into a new object of the same class.
+///
+/// Copies of Python subclasses keep their type and a copy of their instance
+/// `__dict__` (deep-copied when `memo` is given). The object is created
+/// through the base class's `__new__`, so subclass constructors are not run.
+fn copy_object<'py, T: LockedState>(
+    slf: &Bound<'py, T>,
+    memo: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, T>> {
+    let py = slf.py();
+    let snapshot = slf.get().snapshot(py);
+    let base = T::type_object(py);
+    let cls = slf.as_any().get_type();
+    if cls.is(&base) {
+        return Bound::new(py, snapshot);
+    }
+
+    let clone = base
+        .call_method1(intern!(py, "__new__"), (&cls,))?
+        .downcast_into::<T>()?;
+    clone.get().replace_with(py, snapshot);
+    if let Ok(dict) = slf.as_any().getattr(intern!(py, "__dict__")) {
+        let dict = match memo {
+            Some(memo) => py.import("copy")?.call_method1("deepcopy", (dict, memo))?,
+            None => dict,
+        };
+        clone.as_any().getattr(intern!(py, "__dict__"))?.call_method1("update", (dict,))?;
+    }
+    Ok(clone)
+}
+
+/// Hex-encodes a digest into a Python string, on the stack for usual sizes.
+fn hex_string<'py>(py: Python<'py>, digest: &[u8]) -> Bound<'py, PyString> {
+    let mut hex = [0u8; 256];
+    match hex.get_mut(..digest.len() * 2) {
+        Some(out) => PyString::new(py, hex_into(digest, out)),
+        None => PyString::new(py, &to_hex(digest)),
+    }
+}
+
+/// Defines a hashlib-compatible Python class around a core hasher.
+///
+/// All classes share the same interface: the constructor and `update()`
+/// accept any buffer, `digest()`/`hexdigest()` are cached and leave the
+/// hasher usable, and `copy()` preserves Python subclasses. Methods may be
+/// called from several threads at once; they serialize on an internal lock.
+///
+/// Parameterized algorithms list their constructor arguments, which come
+/// before `data`, then after a `;` their keyword-only arguments with default
+/// values, and compute their name from the hasher.
+///
+/// The `@class` rule generates everything but the output methods, which are
+/// passed in as a block; [`xof_class!`] reuses it for extendable-output
+/// functions.
+macro_rules! hash_class {
+    (@approved) => { true };
+    (@approved $approved:expr) => { $approved };
+    (
+        $(#[$doc:meta])* $class:ident, $pyname:literal, $name:literal, $hasher:ty
+        $(, approved: $approved:expr)?
+    ) => {
+        hash_class!(
+            $(#[$doc])* $class, $pyname, $hasher,
+            new() => Ok::<_, PyErr>(<$hasher>::new()),
+            name(_hasher) => $name.to_owned()
+            $(, approved: $approved)?
+        );
+    };
+    (
+        $(#[$doc:meta])* $class:ident, $pyname:literal, $hasher:ty,
+        new($($arg:ident: $ty:ty),* $(; $($kw:ident: $kty:ty = $kdefault:expr),*)?) => $ctor:expr,
+        name($h:ident) => $name:expr
+        $(, approved: $approved:expr)?
+    ) => {
+        hash_class!(
+            @class $(#[$doc])* $class, $pyname, HashState<$hasher>,
+            new($($arg: $ty),*; $($($kw: $kty = $kdefault),*)?) => $ctor,
+            name($h) => $name,
+            approved: hash_class!(@approved $($approved)?),
+            output {
+                /// Returns the digest as bytes.
+                ///
+                /// Does not modify the hasher, so it can be called repeatedly.
+                /// The result is cached until the next `update()`.
+                fn digest(&self, py: Python) -> PyResult<PyObject> {
+                    let result = self.lock(py).digest();
+                    Ok(PyBytes::new(py, result.as_ref()).into())
+                }
+
+                /// Returns the digest as a hexadecimal string.
+                ///
+                /// Does not modify the hasher, so it can be called repeatedly.
+                /// The result is cached until the next `update()`.
+                fn hexdigest<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+                    let result = self.lock(py).digest();
+                    hex_string(py, result.as_ref())
+                }
+
+                #[getter]
+                fn digest_size(&self, py: Python) -> usize {
+                    self.lock(py).hasher.digest_size()
+                }
+            }
+        );
+    };
+    (
+        @class $(#[$doc:meta])* $class:ident, $pyname:literal, $state:ident<$hasher:ty>,
+        new($($arg:ident: $ty:ty),*; $($kw:ident: $kty:ty = $kdefault:expr),*) => $ctor:expr,
+        name($h:ident) => $name:expr,
+        approved: $approved:expr,
+        output { $($output:tt)* }
+    ) => {
+        $(#[$doc])*
+        #[pyclass(name = $pyname, subclass, frozen)]
+        pub struct $class {
+            state: Mutex<$state<$hasher>>,
+        }
+
+        impl $class {
+            /// Whether the algorithm may be used for security in strict mode.
+            const APPROVED: bool = $approved;
+
+            /// Locks the streaming state (see [`lock`]).
+            fn lock(&self, py: Python) -> MutexGuard<'_, $state<$hasher>> {
+                lock(py, &self.state)
+            }
+        }
+
+        impl LockedState for $class {
+            fn snapshot(&self, py: Python) -> Self {
+                $class { state: Mutex::new(self.lock(py).clone()) }
+            }
+
+            fn replace_with(&self, py: Python, other: Self) {
+                *self.lock(py) = other.state.into_inner().unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+
+        #[pymethods]
+        impl $class {
+            /// Creates a new hasher, optionally with initial data.
+            ///
+            /// `None` is treated as no initial data. Set `copy` to accept
+            /// non-contiguous buffers (see `update()`). `usedforsecurity` is
+            /// accepted for hashlib compatibility and exposed as a read-only
+            /// property. Python subclasses may pass additional arguments for
+            /// their `__init__`.
+            #[new]
+            #[classmethod]
+            #[pyo3(signature = (
+                $($arg,)* data=None, *args, $($kw=$kdefault,)* usedforsecurity=true, copy=false, **kwargs
+            ))]
+            #[allow(clippy::too_many_arguments)]
+            fn new(
+                cls: &Bound<'_, PyType>,
+                py: Python,
+                $($arg: $ty,)*
+                data: Option<&Bound<'_, PyAny>>,
+                args: &Bound<'_, PyTuple>,
+                $($kw: $kty,)*
+                usedforsecurity: bool,
+                copy: bool,
+                kwargs: Option<&Bound<'_, PyDict>>,
+            ) -> PyResult<Self> {
+                check_extra_args::<Self>(cls, args, kwargs)?;
+                check_approved($pyname, Self::APPROVED, usedforsecurity)?;
+                let hasher: $hasher = $ctor?;
+                let mut state = $state::new(hasher, usedforsecurity);
+                if let Some(obj) = data {
+                    state.update(py, &ByteView::get(obj)?, copy)?;
+                }
+                Ok($class { state: Mutex::new(state) })
+            }
+
+            /// Does nothing; the hasher is fully set up by the constructor.
+            ///
+            /// Defined so that Python subclasses can call `super().__init__()`
+            /// with the same arguments they received.
+            #[pyo3(signature = (*_args, **_kwargs))]
+            fn __init__(&self, _args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>) {}
+
+            /// Updates the hash with additional data.
+            ///
+            /// Accepts any object supporting the buffer protocol; contiguous
+            /// bytes are hashed in place, with the GIL released for large
+            /// inputs. Non-contiguous buffers raise `BufferError` unless
+            /// `copy=True`, which hashes them in logical order through a
+            /// bounded scratch buffer. Concurrent calls are applied one after
+            /// the other, never interleaved.
+            #[pyo3(signature = (data, *, copy=false))]
+            fn update(&self, py: Python, data: &Bound<'_, PyAny>, copy: bool) -> PyResult<()> {
+                let view = ByteView::get(data)?;
+                self.lock(py).update(py, &view, copy)
+            }
+
+            /// Reads `fileobj` until end of file, or until `max_bytes` bytes,
+            /// and hashes what it reads, returning the number of bytes
+            /// consumed.
+            ///
+            /// `fileobj` is any object with `readinto()`, which is preferred,
+            /// or `read()`, such as a binary file or `socket.makefile("rb")`.
+            /// Reads of up to `chunk_size` bytes go through Python, and each
+            /// chunk is hashed with the GIL released. The result is the same
+            /// as `update(fileobj.read(max_bytes))`. Exceptions raised by the
+            /// reads propagate unchanged.
+            #[pyo3(signature = (fileobj, chunk_size=1 << 20, max_bytes=None))]
+            fn update_from(
+                &self,
+                py: Python,
+                fileobj: &Bound<'_, PyAny>,
+                chunk_size: usize,
+                max_bytes: Option<u64>,
+            ) -> PyResult<u64> {
+                update_from_file(py, &self.state, fileobj, chunk_size, max_bytes)
+            }
+
+            $($output)*
+
+            /// Creates a copy of the current hasher state.
+            ///
+            /// The copy has the same class as the original, including for
+            /// Python subclasses, whose instance attributes are copied as well.
+            fn copy<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, None)
+            }
+
+            /// Supports `copy.copy()` by cloning the streaming state.
+            fn __copy__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, None)
+            }
+
+            /// Supports `copy.deepcopy()`; subclass attributes are deep-copied.
+            fn __deepcopy__<'py>(
+                slf: &Bound<'py, Self>,
+                memo: &Bound<'py, PyAny>,
+            ) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, Some(memo))
+            }
+
+            #[getter]
+            fn block_size(&self, py: Python) -> usize {
+                self.lock(py).hasher.block_size()
+            }
+
+            #[getter]
+            fn name(&self, py: Python) -> String {
+                let $h = &self.lock(py).hasher;
+                $name
+            }
+
+            /// Whether the hash was created with `usedforsecurity=True`.
+            #[getter]
+            fn usedforsecurity(&self, py: Python) -> bool {
+                self.lock(py).usedforsecurity
+            }
+        }
+    };
+}
+
+/// Defines a hashlib-compatible Python class around an extendable-output
+/// function.
+///
+/// Same interface as [`hash_class!`], except that `digest()` and
+/// `hexdigest()` take the output length in bytes as a required argument and
+/// `digest_size` is 0, as for hashlib's SHAKE objects. Outputs are not
+/// cached.
+///
+/// With a leading `@items`, each `update()` call is one input element
+/// (see [`ItemState`]). A trailing `methods { ... }` block adds methods
+/// specific to the class.
+macro_rules! xof_class {
+    (
+        $(#[$doc:meta])* $class:ident, $pyname:literal, $name:literal, $hasher:ty
+        $(, approved: $approved:expr)?
+    ) => {
+        xof_class!(
+            $(#[$doc])* $class, $pyname, $hasher,
+            new() => Ok::<_, PyErr>(<$hasher>::new()),
+            name(_hasher) => $name.to_owned()
+            $(, approved: $approved)?
+        );
+    };
+    (@items $($rest:tt)*) => {
+        xof_class!(@state ItemState, $($rest)*);
+    };
+    (
+        $(#[$doc:meta])* $class:ident, $pyname:literal, $hasher:ty,
+        new($($arg:ident: $ty:ty),* $(; $($kw:ident: $kty:ty = $kdefault:expr),*)?) => $ctor:expr,
+        name($h:ident) => $name:expr
+        $(, approved: $approved:expr)?
+        $(, methods { $($methods:tt)* })?
+    ) => {
+        xof_class!(
+            @state XofState, $(#[$doc])* $class, $pyname, $hasher,
+            new($($arg: $ty),* $(; $($kw: $kty = $kdefault),*)?) => $ctor,
+            name($h) => $name
+            $(, approved: $approved)?
+            $(, methods { $($methods)* })?
+        );
+    };
+    (
+        @state $state:ident, $(#[$doc:meta])* $class:ident, $pyname:literal, $hasher:ty,
+        new($($arg:ident: $ty:ty),* $(; $($kw:ident: $kty:ty = $kdefault:expr),*)?) => $ctor:expr,
+        name($h:ident) => $name:expr
+        $(, approved: $approved:expr)?
+        $(, methods { $($methods:tt)* })?
+    ) => {
+        hash_class!(
+            @class $(#[$doc])* $class, $pyname, $state<$hasher>,
+            new($($arg: $ty),*; $($($kw: $kty = $kdefault),*)?) => $ctor,
+            name($h) => $name,
+            approved: hash_class!(@approved $($approved)?),
+            output {
+                /// Returns `length` output bytes.
+                ///
+                /// Does not modify the hasher, so it can be called repeatedly.
+                fn digest<'py>(
+                    &self,
+                    py: Python<'py>,
+                    length: usize,
+                ) -> PyResult<Bound<'py, PyBytes>> {
+                    let hasher = self.lock(py).hasher.clone();
+                    squeeze_bytes(py, hasher, length)
+                }
+
+                /// Returns `length` output bytes as a hexadecimal string.
+                fn hexdigest<'py>(
+                    &self,
+                    py: Python<'py>,
+                    length: usize,
+                ) -> PyResult<Bound<'py, PyString>> {
+                    let hasher = self.lock(py).hasher.clone();
+                    let result = squeeze_bytes(py, hasher, length)?;
+                    Ok(hex_string(py, result.as_bytes()))
+                }
+
+                /// Always 0: the output length is chosen at each `digest()` call.
+                #[getter]
+                fn digest_size(&self) -> usize {
+                    0
+                }
+
+                $($($methods)*)?
+            }
+        );
+    };
+}
+
+hash_class!(
+    /// Python wrapper for MD2 message digest (RFC 1319).
+    ///
+    /// Kept for old certificates and PKCS#7 structures, so it is not added
+    /// to the module: it is only created by `new("md2")`. Not approved for
+    /// security use: in FIPS mode it requires `usedforsecurity=False`.
+    PyMD2, "MD2", "md2", Md2,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for MD4 message digest (RFC 1320).
+    ///
+    /// For NTLM hashes and other legacy formats; modern OpenSSL builds no
+    /// longer provide it to hashlib. Not approved for security use: in FIPS
+    /// mode it requires `usedforsecurity=False`.
+    PyMD4, "MD4", "md4", Md4,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for MD5 message digest.
+    ///
+    /// Compatible with `hashlib.md5()` API. Not approved for security use:
+    /// in FIPS mode it requires `usedforsecurity=False`.
+    PyMD5, "MD5", "md5", Md5,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for SHA-1 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha1()` API. Provided for legacy protocols;
+    /// SHA-1 is not collision resistant.
+    PySHA1, "SHA1", "sha1", Sha1
+);
+
+hash_class!(
+    /// Python wrapper for SHA-224 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha224()` API.
+    PySHA224, "SHA224", "sha224", Sha224
+);
+
+hash_class!(
+    /// Python wrapper for SHA-256 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha256()` API.
+    PySHA256, "SHA256", "sha256", Sha256
+);
+
+hash_class!(
+    /// Python wrapper for SHA-512 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha512()` API.
+    PySHA512, "SHA512", "sha512", Sha512
+);
+
+hash_class!(
+    /// Python wrapper for SHA-512/224 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha512_224()` API.
+    PySHA512_224, "SHA512_224", "sha512_224", Sha512_224
+);
+
+hash_class!(
+    /// Python wrapper for SHA-512/t, SHA-512 truncated to `t` bits.
+    ///
+    /// Takes `t` as first constructor argument; `name` follows hashlib's
+    /// spelling of the standard variants, e.g. "sha512_256".
+    PySHA512T, "SHA512_t", Sha512T,
+    new(t: usize) => Sha512T::new(t),
+    name(hasher) => format!("sha512_{}", hasher.t())
+);
+
+hash_class!(
+    /// Python wrapper for SHA3-224 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha3_224()` API.
+    PySHA3_224, "SHA3_224", "sha3_224", Sha3_224
+);
+
+hash_class!(
+    /// Python wrapper for SHA3-256 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha3_256()` API.
+    PySHA3_256, "SHA3_256", "sha3_256", Sha3_256
+);
+
+hash_class!(
+    /// Python wrapper for SHA3-384 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha3_384()` API.
+    PySHA3_384, "SHA3_384", "sha3_384", Sha3_384
+);
+
+hash_class!(
+    /// Python wrapper for SHA3-512 hash algorithm.
+    ///
+    /// Compatible with `hashlib.sha3_512()` API.
+    PySHA3_512, "SHA3_512", "sha3_512", Sha3_512
+);
+
+xof_class!(
+    /// Python wrapper for the SHAKE128 extendable-output function.
+    ///
+    /// Compatible with `hashlib.shake_128()` API.
+    PySHAKE128, "SHAKE128", "shake_128", Shake128
+);
+
+xof_class!(
+    /// Python wrapper for the SHAKE256 extendable-output function.
+    ///
+    /// Compatible with `hashlib.shake_256()` API.
+    PySHAKE256, "SHAKE256", "shake_256", Shake256
+);
+
+hash_class!(
+    /// Python wrapper for Keccak-256 with the original padding.
+    ///
+    /// The hash used by Ethereum, which hashlib does not provide. Not a
+    /// FIPS 202 function: in FIPS mode it requires `usedforsecurity=False`.
+    PyKeccak256, "Keccak256", "keccak_256", Keccak256,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Keccak-512 with the original padding.
+    ///
+    /// Not a FIPS 202 function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyKeccak512, "Keccak512", "keccak_512", Keccak512,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for RIPEMD-128.
+    ///
+    /// Provided to verify existing RIPEMD-128 checksums; its 64-bit collision
+    /// bound is too weak for new designs. Not a FIPS function: in FIPS mode
+    /// it requires `usedforsecurity=False`.
+    PyRIPEMD128, "RIPEMD128", "ripemd128", Ripemd128,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for RIPEMD-160.
+    ///
+    /// Compatible with `hashlib.new("ripemd160")`, which depends on the
+    /// OpenSSL build. Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyRIPEMD160, "RIPEMD160", "ripemd160", Ripemd160,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for RIPEMD-256.
+    ///
+    /// A wider output for RIPEMD-128, with the same collision resistance.
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyRIPEMD256, "RIPEMD256", "ripemd256", Ripemd256,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for RIPEMD-320.
+    ///
+    /// A wider output for RIPEMD-160, with the same collision resistance.
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyRIPEMD320, "RIPEMD320", "ripemd320", Ripemd320,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for the SM3 hash function (GB/T 32905-2016).
+    ///
+    /// Compatible with `hashlib.new("sm3")`, which depends on the OpenSSL
+    /// build. Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PySM3, "SM3", "sm3", Sm3,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for the Whirlpool hash function (ISO/IEC 10118-3).
+    ///
+    /// Produces a 64-byte digest. Not a FIPS function: in FIPS mode it
+    /// requires `usedforsecurity=False`.
+    PyWhirlpool, "Whirlpool", "whirlpool", Whirlpool,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Tiger/192 with its original 0x01 padding.
+    ///
+    /// The digest holds the three state words in little-endian byte order,
+    /// matching the reference test vectors (`3293ac63...` for b""). Not a
+    /// FIPS function: in FIPS mode it requires `usedforsecurity=False`.
+    PyTiger, "Tiger", "tiger", Tiger,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Tiger2/192, Tiger with MD5-style 0x80 padding.
+    ///
+    /// Same byte order as [`PyTiger`]. Not a FIPS function: in FIPS mode it
+    /// requires `usedforsecurity=False`.
+    PyTiger2, "Tiger2", "tiger2", Tiger2,
+    approved: false
+);
+
+/// Defines the Python class of a Skein variant, taking the digest size in
+/// bytes as the keyword-only `digest_size`.
+macro_rules! skein_class {
+    ($(#[$doc:meta])* $class:ident, $pyname:literal, $name:literal, $hasher:ident, $size:literal) => {
+        hash_class!(
+            $(#[$doc])*
+            $class, $pyname, $hasher,
+            new(; digest_size: usize = $size) => $hasher::with_output_size(digest_size),
+            name(_hasher) => $name.to_owned(),
+            approved: false
+        );
+    };
+}
+
+skein_class!(
+    /// Python wrapper for Skein-256 (Skein 1.3, 256-bit state).
+    ///
+    /// `digest_size` (32 bytes by default) may be 1 to 2**20 bytes; it is
+    /// hashed into the configuration block, so shorter digests are not
+    /// prefixes of longer ones. Not a FIPS function: in FIPS mode it
+    /// requires `usedforsecurity=False`.
+    PySkein256, "Skein256", "skein256", Skein256, 32
+);
+
+skein_class!(
+    /// Python wrapper for Skein-512 (Skein 1.3, 512-bit state).
+    ///
+    /// Like [`PySkein256`], with a 64-byte default digest. Not a FIPS
+    /// function.
+    PySkein512, "Skein512", "skein512", Skein512, 64
+);
+
+skein_class!(
+    /// Python wrapper for Skein-1024 (Skein 1.3, 1024-bit state).
+    ///
+    /// Like [`PySkein256`], with a 128-byte default digest. Not a FIPS
+    /// function.
+    PySkein1024, "Skein1024", "skein1024", Skein1024, 128
+);
+
+hash_class!(
+    /// Python wrapper for Grøstl-256 (final-round SHA-3 submission).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyGroestl256, "Groestl256", "groestl256", Groestl256,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Grøstl-512 (final-round SHA-3 submission).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyGroestl512, "Groestl512", "groestl512", Groestl512,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for JH-256 (round-3 SHA-3 submission, JH42).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyJH256, "JH256", "jh256", Jh256,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for JH-512 (round-3 SHA-3 submission, JH42).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyJH512, "JH512", "jh512", Jh512,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Ascon-Hash256 (NIST SP 800-232).
+    ///
+    /// Approved by NIST for lightweight use, so it stays usable in FIPS
+    /// mode.
+    PyAsconHash256, "AsconHash256", "ascon_hash256", AsconHash256
+);
+
+xof_class!(
+    /// Python wrapper for the Ascon-XOF128 extendable-output function
+    /// (NIST SP 800-232).
+    ///
+    /// Shorter outputs are prefixes of longer ones. Approved by NIST for
+    /// lightweight use, so it stays usable in FIPS mode.
+    PyAsconXOF128, "AsconXOF128", "ascon_xof128", AsconXof128
+);
+
+hash_class!(
+    /// Python wrapper for LSH-256 (KS X 3262, 32-bit words).
+    ///
+    /// `digest_size` is 32 bytes by default (LSH-256-256); 28 gives
+    /// LSH-256-224. Any size from 1 to 32 is accepted and selects its own
+    /// initial value, so shorter digests are not prefixes of longer ones.
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyLSH256, "LSH256", Lsh256,
+    new(; digest_size: usize = 32) => Lsh256::with_output_size(digest_size),
+    name(_hasher) => "lsh256".to_owned(),
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for LSH-512 (KS X 3262, 64-bit words).
+    ///
+    /// Like [`PyLSH256`], with a digest of 1 to 64 bytes, 64 by default
+    /// (LSH-512-512); 28, 32 and 48 give the other standard sizes. Not a
+    /// FIPS function.
+    PyLSH512, "LSH512", Lsh512,
+    new(; digest_size: usize = 64) => Lsh512::with_output_size(digest_size),
+    name(_hasher) => "lsh512".to_owned(),
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Kupyna-256 (DSTU 7564:2014).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyKupyna256, "Kupyna256", "kupyna256", Kupyna256,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for Kupyna-512 (DSTU 7564:2014).
+    ///
+    /// Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyKupyna512, "Kupyna512", "kupyna512", Kupyna512,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for GOST R 34.11-94 with the "test" S-box parameters
+    /// (the RFC 5831 examples).
+    ///
+    /// Legacy only. Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyGOST94, "GOST94", "gost94", Gost94,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for GOST R 34.11-94 with the CryptoPro S-box
+    /// parameters (RFC 4357), the set used by most signed archives.
+    ///
+    /// Legacy only. Not a FIPS function.
+    PyGOST94CryptoPro, "GOST94_CryptoPro", Gost94,
+    new() => Ok::<_, PyErr>(Gost94::with_sbox(Gost94SBox::CryptoPro)),
+    name(_hasher) => "gost94_cryptopro".to_owned(),
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for HAS-160 (TTAS.KO-12.0011/R2), the hash of the
+    /// Korean KCDSA signature scheme.
+    ///
+    /// Legacy only. Not a FIPS function: in FIPS mode it requires
+    /// `usedforsecurity=False`.
+    PyHAS160, "HAS160", "has160", Has160,
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for MD6 (SHA-3 submission, tree mode).
+    ///
+    /// `digest_size` is 32 bytes by default (MD6-256) and may be anything
+    /// from 1 to 64; each size has its own round count, so shorter digests
+    /// are not prefixes of longer ones. `mode` is the mode parameter `L`,
+    /// 64 by default; 0 hashes the message sequentially. `name` embeds the
+    /// digest size in bits, e.g. "md6_256", which `new()` accepts. Not a
+    /// FIPS function: in FIPS mode it requires `usedforsecurity=False`.
+    PyMD6, "MD6", Md6,
+    new(; digest_size: usize = 32, mode: u32 = 64) => {
+        Md6::with_params(digest_size.saturating_mul(8), mode)
+    },
+    name(hasher) => format!("md6_{}", hasher.output_bits()),
+    approved: false
+);
+
+xof_class!(
+    /// Python wrapper for the cSHAKE128 customizable extendable-output
+    /// function (NIST SP 800-185).
+    ///
+    /// Takes keyword-only `function_name` (N) and `custom` (S) byte strings,
+    /// empty by default; with both empty it is SHAKE128.
+    PyCSHAKE128, "cSHAKE128", CShake128,
+    new(; function_name: Option<BytesArg> = None, custom: Option<BytesArg> = None) => {
+        Ok::<_, PyErr>(CShake128::new(
+            &function_name.unwrap_or_default().0,
+            &custom.unwrap_or_default().0,
+        ))
+    },
+    name(_hasher) => "cshake_128".to_owned()
+);
+
+xof_class!(
+    /// Python wrapper for the cSHAKE256 customizable extendable-output
+    /// function (NIST SP 800-185).
+    ///
+    /// Takes keyword-only `function_name` (N) and `custom` (S) byte strings,
+    /// empty by default; with both empty it is SHAKE256.
+    PyCSHAKE256, "cSHAKE256", CShake256,
+    new(; function_name: Option<BytesArg> = None, custom: Option<BytesArg> = None) => {
+        Ok::<_, PyErr>(CShake256::new(
+            &function_name.unwrap_or_default().0,
+            &custom.unwrap_or_default().0,
+        ))
+    },
+    name(_hasher) => "cshake_256".to_owned()
+);
+
+xof_class!(
+    @items
+    /// Python wrapper for TupleHash128 (NIST SP 800-185).
+    ///
+    /// Each `update()` call, and the constructor's `data`, appends one
+    /// element to the tuple being hashed. Takes a keyword-only `custom`
+    /// byte string. The output length is part of the hash, so `digest(16)`
+    /// is not a prefix of `digest(32)`.
+    PyTupleHash128, "TupleHash128", TupleHash128,
+    new(; custom: Option<BytesArg> = None) => {
+        Ok::<_, PyErr>(TupleHash128::new(&custom.unwrap_or_default().0))
+    },
+    name(_hasher) => "tuplehash_128".to_owned()
+);
+
+xof_class!(
+    @items
+    /// Python wrapper for TupleHash256 (NIST SP 800-185).
+    ///
+    /// Each `update()` call, and the constructor's `data`, appends one
+    /// element to the tuple being hashed. Takes a keyword-only `custom`
+    /// byte string. The output length is part of the hash, so `digest(32)`
+    /// is not a prefix of `digest(64)`.
+    PyTupleHash256, "TupleHash256", TupleHash256,
+    new(; custom: Option<BytesArg> = None) => {
+        Ok::<_, PyErr>(TupleHash256::new(&custom.unwrap_or_default().0))
+    },
+    name(_hasher) => "tuplehash_256".to_owned()
+);
+
+xof_class!(
+    /// Python wrapper for ParallelHash128 (NIST SP 800-185).
+    ///
+    /// Takes keyword-only `block_size` (the block size `B` in bytes, 8192 by
+    /// default) and `custom` arguments. Complete blocks are hashed on
+    /// several threads with the GIL released.
+    ///
+    /// `B` is exposed as the `chunk_size` attribute. The `block_size`
+    /// attribute is the sponge rate (168 bytes), as for the other
+    /// Keccak-based objects, not the constructor's `block_size`.
+    PyParallelHash128, "ParallelHash128", ParallelHash128,
+    new(; block_size: usize = 8192, custom: Option<BytesArg> = None) => {
+        ParallelHash128::new(block_size, &custom.unwrap_or_default().0)
+    },
+    name(_hasher) => "parallelhash_128".to_owned(),
+    methods {
+        /// The block size `B` in bytes given to the constructor.
+        #[getter]
+        fn chunk_size(&self, py: Python) -> usize {
+            self.lock(py).hasher.block_len()
+        }
+    }
+);
+
+xof_class!(
+    /// Python wrapper for ParallelHash256 (NIST SP 800-185).
+    ///
+    /// Takes keyword-only `block_size` (the block size `B` in bytes, 8192 by
+    /// default) and `custom` arguments. Complete blocks are hashed on
+    /// several threads with the GIL released.
+    ///
+    /// `B` is exposed as the `chunk_size` attribute. The `block_size`
+    /// attribute is the sponge rate (136 bytes), as for the other
+    /// Keccak-based objects, not the constructor's `block_size`.
+    PyParallelHash256, "ParallelHash256", ParallelHash256,
+    new(; block_size: usize = 8192, custom: Option<BytesArg> = None) => {
+        ParallelHash256::new(block_size, &custom.unwrap_or_default().0)
+    },
+    name(_hasher) => "parallelhash_256".to_owned(),
+    methods {
+        /// The block size `B` in bytes given to the constructor.
+        #[getter]
+        fn chunk_size(&self, py: Python) -> usize {
+            self.lock(py).hasher.block_len()
+        }
+    }
+);
+
+xof_class!(
+    /// Python wrapper for TurboSHAKE128 (RFC 9861).
+    ///
+    /// Takes a keyword-only `domain` separation byte in `0x01..=0x7F`
+    /// (`0x1F` by default); any other value raises `ValueError`.
+    PyTurboSHAKE128, "TurboSHAKE128", TurboShake128,
+    new(; domain: u8 = 0x1f) => TurboShake128::new(domain),
+    name(_hasher) => "turboshake_128".to_owned()
+);
+
+xof_class!(
+    /// Python wrapper for TurboSHAKE256 (RFC 9861).
+    ///
+    /// Takes a keyword-only `domain` separation byte in `0x01..=0x7F`
+    /// (`0x1F` by default); any other value raises `ValueError`.
+    PyTurboSHAKE256, "TurboSHAKE256", TurboShake256,
+    new(; domain: u8 = 0x1f) => TurboShake256::new(domain),
+    name(_hasher) => "turboshake_256".to_owned()
+);
+
+xof_class!(
+    /// Python wrapper for KangarooTwelve (RFC 9861).
+    ///
+    /// Takes a keyword-only `custom` byte string. Chunks of 8 KiB are
+    /// hashed on several threads with the GIL released, and shorter outputs
+    /// are prefixes of longer ones.
+    PyKangarooTwelve, "KangarooTwelve", KangarooTwelve,
+    new(; custom: Option<BytesArg> = None) => {
+        Ok::<_, PyErr>(KangarooTwelve::new(&custom.unwrap_or_default().0))
+    },
+    name(_hasher) => "k12".to_owned()
+);
+
+/// Converts a one-byte BLAKE2 tree parameter, raising `ValueError` with
+/// hashlib's message when it does not fit.
+fn blake2_byte(name: &str, value: u64, min: u8) -> PyResult<u8> {
+    u8::try_from(value)
+        .ok()
+        .filter(|&byte| byte >= min)
+        .ok_or_else(|| PyValueError::new_err(format!("{} must be between {} and 255", name, min)))
+}
+
+/// Builds BLAKE2 parameters from hashlib's keyword arguments.
+///
+/// Limits that depend on the variant are checked by the core constructors.
+#[allow(clippy::too_many_arguments)]
+fn blake2_params(
+    digest_size: usize,
+    key: Option<BytesArg>,
+    salt: Option<BytesArg>,
+    person: Option<BytesArg>,
+    fanout: u64,
+    depth: u64,
+    leaf_size: u32,
+    node_offset: u64,
+    node_depth: u64,
+    inner_size: u64,
+    last_node: bool,
+) -> PyResult<Blake2Params> {
+    Ok(Blake2Params {
+        key: key.unwrap_or_default().0,
+        salt: salt.unwrap_or_default().0,
+        person: person.unwrap_or_default().0,
+        fanout: blake2_byte("fanout", fanout, 0)?,
+        depth: blake2_byte("depth", depth, 1)?,
+        leaf_size,
+        node_offset,
+        node_depth: blake2_byte("node_depth", node_depth, 0)?,
+        inner_size: blake2_byte("inner_size", inner_size, 0)?,
+        last_node,
+        ..Blake2Params::new(digest_size)
+    })
+}
+
+/// Defines the Python class of a BLAKE2 variant, taking hashlib's keyword
+/// arguments with their defaults.
+macro_rules! blake2_class {
+    ($(#[$doc:meta])* $class:ident, $pyname:literal, $name:literal, $hasher:ident, $size:literal) => {
+        hash_class!(
+            $(#[$doc])*
+            $class, $pyname, $hasher,
+            new(
+                ;
+                digest_size: usize = $size,
+                key: Option<BytesArg> = None,
+                salt: Option<BytesArg> = None,
+                person: Option<BytesArg> = None,
+                fanout: u64 = 1,
+                depth: u64 = 1,
+                leaf_size: u32 = 0,
+                node_offset: u64 = 0,
+                node_depth: u64 = 0,
+                inner_size: u64 = 0,
+                last_node: bool = false
+            ) => $hasher::with_params(&blake2_params(
+                digest_size, key, salt, person,
+                fanout, depth, leaf_size, node_offset, node_depth, inner_size, last_node,
+            )?),
+            name(_hasher) => $name.to_owned(),
+            approved: false
+        );
+    };
+}
+
+blake2_class!(
+    /// Python wrapper for BLAKE2b (RFC 7693).
+    ///
+    /// Compatible with `hashlib.blake2b()`, including its keyword-only
+    /// `digest_size`, `key`, `salt`, `person` and tree arguments and the
+    /// `ValueError` raised for out-of-range values. Not a FIPS function: in
+    /// FIPS mode it requires `usedforsecurity=False`.
+    PyBLAKE2b, "BLAKE2b", "blake2b", Blake2b, 64
+);
+
+blake2_class!(
+    /// Python wrapper for BLAKE2s (RFC 7693).
+    ///
+    /// Compatible with `hashlib.blake2s()`, with the same arguments as
+    /// [`PyBLAKE2b`] and BLAKE2s's smaller limits. Not a FIPS function: in
+    /// FIPS mode it requires `usedforsecurity=False`.
+    PyBLAKE2s, "BLAKE2s", "blake2s", Blake2s, 32
+);
+
+hash_class!(
+    /// Python wrapper for BLAKE2bp, 4-way parallel BLAKE2b.
+    ///
+    /// Takes `digest_size` (1 to 64, default 64) and `key` (up to 64 bytes)
+    /// like `hashlib.blake2b()`; the tree arguments are fixed by the mode.
+    /// Large updates are hashed on several threads. Not a FIPS function.
+    PyBLAKE2bp, "BLAKE2bp", Blake2bp,
+    new(; digest_size: usize = 64, key: Option<BytesArg> = None)
+        => Blake2bp::with_key(digest_size, &key.unwrap_or_default().0),
+    name(_hasher) => "blake2bp".to_owned(),
+    approved: false
+);
+
+hash_class!(
+    /// Python wrapper for BLAKE2sp, 8-way parallel BLAKE2s.
+    ///
+    /// Takes `digest_size` (1 to 32, default 32) and `key` (up to 32 bytes)
+    /// like `hashlib.blake2s()`. Not a FIPS function.
+    PyBLAKE2sp, "BLAKE2sp", Blake2sp,
+    new(; digest_size: usize = 32, key: Option<BytesArg> = None)
+        => Blake2sp::with_key(digest_size, &key.unwrap_or_default().0),
+    name(_hasher) => "blake2sp".to_owned(),
+    approved: false
+);
+
+/// Resolves the `length` argument of a BLAKE2X `digest()` call against the
+/// output length declared at construction.
+fn blake2x_length(declared: Option<usize>, length: Option<usize>) -> PyResult<usize> {
+    match (declared, length) {
+        (Some(declared), None) => Ok(declared),
+        (Some(declared), Some(length)) if length > declared => Err(PyValueError::new_err(
+            format!("length must not exceed digest_size ({})", declared),
+        )),
+        (_, Some(length)) => Ok(length),
+        (None, None) => Err(PyTypeError::new_err("length is required when digest_size is None")),
+    }
+}
+
+/// Defines the Python class of a BLAKE2X variant.
+///
+/// The constructor takes the declared output length as `digest_size`
+/// (`None` for an unknown length) and a `key`. `digest()` and `hexdigest()`
+/// return `digest_size` bytes by default and take a shorter `length`; with
+/// an unknown length, `length` is required and unbounded.
+macro_rules! blake2x_class {
+    ($(#[$doc:meta])* $class:ident, $pyname:literal, $name:literal, $hasher:ident) => {
+        hash_class!(
+            @class $(#[$doc])* $class, $pyname, XofState<$hasher>,
+            new(; digest_size: Option<usize> = None, key: Option<BytesArg> = None)
+                => $hasher::new(digest_size, &key.unwrap_or_default().0),
+            name(_hasher) => $name.to_owned(),
+            approved: false,
+            output {
+                /// Returns `length` output bytes, `digest_size` by default.
+                ///
+                /// Does not modify the hasher, so it can be called repeatedly.
+                #[pyo3(signature = (length=None))]
+                fn digest<'py>(
+                    &self,
+                    py: Python<'py>,
+                    length: Option<usize>,
+                ) -> PyResult<Bound<'py, PyBytes>> {
+                    let hasher = self.lock(py).hasher.clone();
+                    let length = blake2x_length(hasher.output_length(), length)?;
+                    squeeze_bytes(py, hasher, length)
+                }
+
+                /// Returns `length` output bytes as a hexadecimal string.
+                #[pyo3(signature = (length=None))]
+                fn hexdigest<'py>(
+                    &self,
+                    py: Python<'py>,
+                    length: Option<usize>,
+                ) -> PyResult<Bound<'py, PyString>> {
+                    let hasher = self.lock(py).hasher.clone();
+                    let length = blake2x_length(hasher.output_length(), length)?;
+                    let result = squeeze_bytes(py, hasher, length)?;
+                    Ok(hex_string(py, result.as_bytes()))
+                }
+
+                /// The declared output length, 0 when it is unknown.
+                #[getter]
+                fn digest_size(&self, py: Python) -> usize {
+                    self.lock(py).hasher.output_length().unwrap_or(0)
+                }
+            }
+        );
+    };
+}
+
+blake2x_class!(
+    /// Python wrapper for the BLAKE2Xb extendable-output function.
+    ///
+    /// `digest_size` declares the output length (1 to 2**32 - 2 bytes),
+    /// which changes the whole output; leave it `None` to read any length.
+    /// `key` takes up to 64 bytes. Not a FIPS function.
+    PyBLAKE2Xb, "BLAKE2Xb", "blake2xb", Blake2Xb
+);
+
+blake2x_class!(
+    /// Python wrapper for the BLAKE2Xs extendable-output function.
+    ///
+    /// Like [`PyBLAKE2Xb`], with at most 65534 declared output bytes and a
+    /// key of up to 32 bytes. Not a FIPS function.
+    PyBLAKE2Xs, "BLAKE2Xs", "blake2xs", Blake2Xs
+);
+
+/// Creates a BLAKE3 hasher in the mode selected by the `key` and
+/// `derive_key_context` keyword arguments, using at most `max_threads`
+/// threads for large updates.
+fn blake3_hasher(
+    key: Option<BytesArg>,
+    derive_key_context: Option<String>,
+    max_threads: Option<usize>,
+) -> PyResult<Blake3> {
+    let mut hasher = match (key, derive_key_context) {
+        (Some(_), Some(_)) => {
+            return Err(PyValueError::new_err(
+                "key and derive_key_context are mutually exclusive",
+            ));
+        }
+        (Some(BytesArg(key)), None) => {
+            let key: &[u8; BLAKE3_KEY_LEN] = key.as_slice().try_into().map_err(|_| {
+                PyValueError::new_err(format!(
+                    "key must be {} bytes, got {}",
+                    BLAKE3_KEY_LEN,
+                    key.len()
+                ))
+            })?;
+            Blake3::new_keyed(key)
+        }
+        (None, Some(context)) => Blake3::new_derive_key(&context),
+        (None, None) => Blake3::new(),
+    };
+    hasher.set_max_threads(max_threads)?;
+    Ok(hasher)
+}
+
+hash_class!(
+    @class
+    /// Python wrapper for BLAKE3.
+    ///
+    /// Same interface as the `blake3` package: `digest()` and `hexdigest()`
+    /// return 32 bytes by default and take any other `length`, shorter
+    /// outputs being prefixes of longer ones. The keyword-only `key` (32
+    /// bytes) selects the keyed mode and `derive_key_context` (a string) the
+    /// key derivation mode, in which the data is key material; at most one
+    /// of them may be given. Updates of 128 KiB or more are hashed on
+    /// several threads with the GIL released; `max_threads` caps the thread
+    /// count (1 hashes on the calling thread, values above the CPU count are
+    /// clamped to it) without changing the output. Not a FIPS function.
+    PyBLAKE3, "BLAKE3", XofState<Blake3>,
+    new(
+        ;
+        key: Option<BytesArg> = None,
+        derive_key_context: Option<String> = None,
+        max_threads: Option<usize> = None
+    ) => blake3_hasher(key, derive_key_context, max_threads),
+    name(_hasher) => "blake3".to_owned(),
+    approved: false,
+    output {
+        /// Returns `length` output bytes (32 by default).
+        ///
+        /// Does not modify the hasher, so it can be called repeatedly.
+        #[pyo3(signature = (length=BLAKE3_OUT_LEN))]
+        fn digest<'py>(&self, py: Python<'py>, length: usize) -> PyResult<Bound<'py, PyBytes>> {
+            let hasher = self.lock(py).hasher.clone();
+            squeeze_bytes(py, hasher, length)
+        }
+
+        /// Returns `length` output bytes (32 by default) as a hexadecimal
+        /// string.
+        #[pyo3(signature = (length=BLAKE3_OUT_LEN))]
+        fn hexdigest<'py>(
+            &self,
+            py: Python<'py>,
+            length: usize,
+        ) -> PyResult<Bound<'py, PyString>> {
+            let hasher = self.lock(py).hasher.clone();
+            let result = squeeze_bytes(py, hasher, length)?;
+            Ok(hex_string(py, result.as_bytes()))
+        }
+
+        /// The default output size, 32 bytes.
+        #[getter]
+        fn digest_size(&self) -> usize {
+            BLAKE3_OUT_LEN
+        }
+    }
+);
+
+/// Resolves the `digestmod` argument of [`PyHMAC`] to a registered
+/// algorithm: either a name accepted by `new()` or one of the hash classes.
+fn hmac_algorithm(digestmod: &Bound<'_, PyAny>) -> PyResult<&'static registry::Algorithm> {
+    let py = digestmod.py();
+    let algo = if let Ok(name) = digestmod.downcast::<PyString>() {
+        registry::lookup(name.to_str()?)
+    } else if let Ok(cls) = digestmod.downcast::<PyType>() {
+        registry::ALGORITHMS.iter().find(|algo| cls.is(&(algo.class)(py)))
+    } else {
+        return Err(PyTypeError::new_err(
+            "digestmod must be an algorithm name or an RsHash hash class",
+        ));
+    };
+    algo.ok_or_else(|| PyValueError::new_err(format!("unsupported digestmod {}", digestmod)))
+}
+
+/// Returns a fresh core hasher for the HMAC-based functions, selected by
+/// `digestmod` (SHA-256 when `None`), with its canonical name.
+///
+/// # Errors
+/// Returns `TypeError` if `digestmod` is neither a string nor a class, and
+/// `ValueError` if the hash is unknown, has no fixed digest size, or is
+/// refused in strict mode.
+fn hmac_hasher(
+    digestmod: Option<&Bound<'_, PyAny>>,
+) -> PyResult<(Box<dyn DynDigest>, &'static str)> {
+    let algo = match digestmod {
+        Some(digestmod) => hmac_algorithm(digestmod)?,
+        None => registry::lookup("sha256").expect("sha256 is registered"),
+    };
+    let hasher = algo.hasher.ok_or_else(|| {
+        PyValueError::new_err(format!("unsupported digestmod {} for HMAC", algo.name))
+    })?;
+    if !algo.approved && FIPS_MODE.load(Ordering::Relaxed) {
+        return Err(PyValueError::new_err(format!(
+            "HMAC-{} is not approved in FIPS mode",
+            algo.name
+        )));
+    }
+    Ok((hasher(), algo.name))
+}
+
+/// Creates an HMAC keyed with `key` over the hash selected by `digestmod`
+/// (see [`hmac_hasher`]), returning it with the hash's canonical name.
+fn hmac_new(
+    key: &[u8],
+    digestmod: Option<&Bound<'_, PyAny>>,
+) -> PyResult<(Hmac<Box<dyn DynDigest>>, &'static str)> {
+    let (hasher, name) = hmac_hasher(digestmod)?;
+    Ok((Hmac::new(hasher, key), name))
+}
+
+/// Python HMAC object, compatible with `hmac.HMAC`.
+///
+/// Created as `HMAC(key, msg=None, digestmod="sha256")`, where `digestmod`
+/// is an algorithm name accepted by `new()` or one of the hash classes,
+/// such as `RsHash.SHA512`. Every fixed-size hash of the [`registry`]
+/// works, with its default parameters; its block size and digest size are
+/// picked up from the core hasher. Keys longer than the block size are
+/// hashed first, shorter ones zero-padded. Like the hash objects, `digest()` and `hexdigest()` leave
+/// the MAC usable and methods may be called from several threads at once.
+/// HMAC is a security use, so unapproved hashes are refused in strict mode.
+#[pyclass(name = "HMAC", frozen)]
+pub struct PyHMAC {
+    state: Mutex<HashState<Hmac<Box<dyn DynDigest>>>>,
+    /// "hmac-" followed by the name of the underlying hash.
+    name: String,
+}
+
+impl PyHMAC {
+    /// Locks the streaming state (see [`lock`]).
+    fn lock(&self, py: Python) -> MutexGuard<'_, HashState<Hmac<Box<dyn DynDigest>>>> {
+        lock(py, &self.state)
+    }
+
+    /// Returns a new object holding a copy of the current state.
+    fn snapshot(&self, py: Python) -> Self {
+        PyHMAC { state: Mutex::new(self.lock(py).clone()), name: self.name.clone() }
+    }
+}
+
+#[pymethods]
+impl PyHMAC {
+    /// Creates a new HMAC keyed with `key`, optionally with a first message
+    /// part.
+    ///
+    /// # Errors
+    /// Returns `TypeError` if `key` or `msg` is not a buffer or `digestmod`
+    /// is neither a string nor a class, and `ValueError` if the digest
+    /// algorithm is unknown or not supported by HMAC.
+    #[new]
+    #[pyo3(signature = (key, msg=None, digestmod=None))]
+    fn new(
+        py: Python,
+        key: BytesArg,
+        msg: Option<&Bound<'_, PyAny>>,
+        digestmod: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let (mac, name) = hmac_new(&key.0, digestmod)?;
+        let mut state = HashState::new(mac, true);
+        if let Some(obj) = msg {
+            state.update(py, &ByteView::get(obj)?, false)?;
+        }
+        Ok(PyHMAC { state: Mutex::new(state), name: format!("hmac-{}", name) })
+    }
+
+    /// Feeds `msg` into the MAC.
+    ///
+    /// Accepts any C-contiguous buffer, with the GIL released for large
+    /// inputs.
+    fn update(&self, py: Python, msg: &Bound<'_, PyAny>) -> PyResult<()> {
+        let view = ByteView::get(msg)?;
+        self.lock(py).update(py, &view, false)
+    }
+
+    /// Returns the MAC as bytes.
+    ///
+    /// Does not modify the state, so more data can be fed afterwards.
+    fn digest(&self, py: Python) -> PyResult<PyObject> {
+        let result = self.lock(py).digest();
+        Ok(PyBytes::new(py, &result).into())
+    }
+
+    /// Returns the MAC as a hexadecimal string.
+    fn hexdigest<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        let result = self.lock(py).digest();
+        hex_string(py, &result)
+    }
+
+    /// Creates a copy of the current MAC state.
+    fn copy(&self, py: Python) -> Self {
+        self.snapshot(py)
+    }
+
+    /// Supports `copy.copy()` by cloning the streaming state.
+    fn __copy__(&self, py: Python) -> Self {
+        self.snapshot(py)
+    }
+
+    /// Supports `copy.deepcopy()`; the state holds no Python objects.
+    fn __deepcopy__(&self, py: Python, _memo: &Bound<'_, PyAny>) -> Self {
+        self.snapshot(py)
+    }
+
+    #[getter]
+    fn digest_size(&self, py: Python) -> usize {
+        self.lock(py).hasher.digest_size()
+    }
+
+    #[getter]
+    fn block_size(&self, py: Python) -> usize {
+        self.lock(py).hasher.block_size()
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// State of a [`PyHmacDrbg`]: the hash to instantiate with and the
+/// generator, once instantiated.
+struct DrbgState {
+    hasher: Box<dyn DynDigest>,
+    drbg: Option<HmacDrbg<Box<dyn DynDigest>>>,
+    reseed_interval: u64,
+}
+
+/// Python HMAC-DRBG object (NIST SP 800-90A).
+///
+/// Created as `HmacDrbg(hash="sha256")`, where `hash` is an algorithm name
+/// or one of the hash classes, then seeded with `instantiate()`. The caller
+/// provides all the entropy, so the output is deterministic: known-answer
+/// tests and RFC 6979 nonces are the intended uses. Requests are limited to
+/// 65536 bytes, and `reseed_interval` requests (2^48 by default) are
+/// allowed between reseeds.
+#[pyclass(name = "HmacDrbg", frozen)]
+pub struct PyHmacDrbg {
+    state: Mutex<DrbgState>,
+    /// "hmac-drbg-" followed by the name of the underlying hash.
+    name: String,
+}
+
+impl PyHmacDrbg {
+    /// Locks the generator state (see [`lock`]).
+    fn lock(&self, py: Python) -> MutexGuard<'_, DrbgState> {
+        lock(py, &self.state)
+    }
+}
+
+/// Returns the instantiated generator of `state`.
+///
+/// # Errors
+/// Returns `RuntimeError` if `instantiate()` has not been called.
+fn drbg_instance(state: &mut DrbgState) -> PyResult<&mut HmacDrbg<Box<dyn DynDigest>>> {
+    state
+        .drbg
+        .as_mut()
+        .ok_or_else(|| PyRuntimeError::new_err("HmacDrbg must be instantiated first"))
+}
+
+#[pymethods]
+impl PyHmacDrbg {
+    /// Creates an uninstantiated generator over `hash`.
+    ///
+    /// # Errors
+    /// Returns `ValueError` if the hash is unknown, an extendable-output
+    /// function or refused in strict mode, or if `reseed_interval` is not
+    /// between 1 and 2^48.
+    #[new]
+    #[pyo3(signature = (hash=None, *, reseed_interval=RESEED_INTERVAL))]
+    fn new(hash: Option<&Bound<'_, PyAny>>, reseed_interval: u64) -> PyResult<Self> {
+        let (hasher, name) = hmac_hasher(hash)?;
+        if !(1..=RESEED_INTERVAL).contains(&reseed_interval) {
+            return Err(PyValueError::new_err("reseed_interval must be between 1 and 2**48"));
+        }
+        let state = DrbgState { hasher, drbg: None, reseed_interval };
+        Ok(PyHmacDrbg { state: Mutex::new(state), name: format!("hmac-drbg-{}", name) })
+    }
+
+    /// Seeds the generator from `entropy`, `nonce` and an optional
+    /// personalization string, replacing any previous state.
+    #[pyo3(signature = (entropy, nonce, personalization=BytesArg::default()))]
+    fn instantiate(
+        &self,
+        py: Python,
+        entropy: BytesArg,
+        nonce: BytesArg,
+        personalization: BytesArg,
+    ) -> PyResult<()> {
+        let mut state = self.lock(py);
+        let mut drbg =
+            HmacDrbg::new(state.hasher.clone(), &entropy.0, &nonce.0, &personalization.0)?;
+        drbg.set_reseed_interval(state.reseed_interval);
+        state.drbg = Some(drbg);
+        Ok(())
+    }
+
+    /// Mixes fresh `entropy` and optional additional input into the state,
+    /// resetting the reseed counter.
+    ///
+    /// # Errors
+    /// Returns `RuntimeError` before `instantiate()`.
+    #[pyo3(signature = (entropy, additional=BytesArg::default()))]
+    fn reseed(&self, py: Python, entropy: BytesArg, additional: BytesArg) -> PyResult<()> {
+        let mut state = self.lock(py);
+        drbg_instance(&mut state)?.reseed(&entropy.0, &additional.0)?;
+        Ok(())
+    }
+
+    /// Returns `num_bytes` pseudorandom bytes, with optional additional
+    /// input.
+    ///
+    /// # Errors
+    /// Returns `ValueError` if `num_bytes` is above 65536, and
+    /// `RuntimeError` before `instantiate()` or when a reseed is required.
+    #[pyo3(signature = (num_bytes, additional=BytesArg::default()))]
+    fn generate<'py>(
+        &self,
+        py: Python<'py>,
+        num_bytes: usize,
+        additional: BytesArg,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        // Checked before allocating the output, which may be huge
+        if num_bytes > MAX_REQUEST_LEN {
+            return Err(PyValueError::new_err(format!(
+                "at most {} bytes can be requested at once, got {}",
+                MAX_REQUEST_LEN, num_bytes
+            )));
+        }
+        let mut state = self.lock(py);
+        let drbg = drbg_instance(&mut state)?;
+        if drbg.needs_reseed() {
+            return Err(PyRuntimeError::new_err("reseed required"));
+        }
+        let mut out = vec![0u8; num_bytes];
+        drbg.generate(&mut out, &additional.0)?;
+        Ok(PyBytes::new(py, &out))
+    }
+
+    /// Number of the next request, which restarts at 1 after a reseed;
+    /// `None` before `instantiate()`.
+    #[getter]
+    fn reseed_counter(&self, py: Python) -> Option<u64> {
+        self.lock(py).drbg.as_ref().map(|drbg| drbg.reseed_counter())
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Computes the HMAC of `msg` in one call, releasing the GIL for large
+/// messages (see [`hmac_new`] for `digest`).
+fn hmac_oneshot(
+    py: Python,
+    key: &[u8],
+    msg: &Bound<'_, PyAny>,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Vec<u8>> {
+    let (mut mac, _) = hmac_new(key, digest)?;
+    feed_buffer(py, msg, false, |bytes| mac.try_update(bytes))?;
+    Ok(mac.digest())
+}
+
+/// One-shot HMAC returning the MAC as bytes.
+///
+/// Equivalent to `hmac.digest(key, msg, digest)`, with SHA-256 by default;
+/// `digest` is a name accepted by `new()` or one of the hash classes. No
+/// hash object is created and the GIL is released for large messages.
+#[pyfunction]
+#[pyo3(signature = (key, msg, digest=None))]
+pub fn hmac_digest<'py>(
+    py: Python<'py>,
+    key: BytesArg,
+    msg: &Bound<'_, PyAny>,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    Ok(PyBytes::new(py, &hmac_oneshot(py, &key.0, msg, digest)?))
+}
+
+/// One-shot HMAC returning the MAC as a hexadecimal string.
+///
+/// Same as [`hmac_digest`], hex-encoded.
+#[pyfunction]
+#[pyo3(signature = (key, msg, digest=None))]
+pub fn hmac_hexdigest<'py>(
+    py: Python<'py>,
+    key: BytesArg,
+    msg: &Bound<'_, PyAny>,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyString>> {
+    Ok(hex_string(py, &hmac_oneshot(py, &key.0, msg, digest)?))
+}
+
+/// Checks `tag` against the HMAC of `msg`.
+///
+/// Equivalent to `hmac.compare_digest(hmac_digest(key, msg, digest), tag)`:
+/// the comparison takes the same time wherever the first mismatching byte
+/// is, and a tag of the wrong length is rejected without looking at its
+/// contents.
+#[pyfunction]
+#[pyo3(signature = (key, msg, tag, digest=None))]
+pub fn hmac_verify(
+    py: Python,
+    key: BytesArg,
+    msg: &Bound<'_, PyAny>,
+    tag: BytesArg,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<bool> {
+    let expected = hmac_oneshot(py, &key.0, msg, digest)?;
+    Ok(constant_time_eq(&expected, &tag.0))
+}
+
+/// Compares two digests in constant time, as `hmac.compare_digest()`.
+///
+/// `a` and `b` are both bytes-like objects or both ASCII `str`. Every
+/// byte is compared whatever the position of the first difference, and
+/// operands of different lengths take the time of comparing `a` with
+/// itself, so only the length of `a` can be observed.
+///
+/// # Errors
+/// Returns `TypeError` when mixing `str` with bytes-like operands, for a
+/// `str` with non-ASCII characters or an object without buffer support,
+/// and `BufferError` for non-contiguous buffers.
+#[pyfunction]
+pub fn compare_digest(a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>) -> PyResult<bool> {
+    match (a.downcast::<PyString>(), b.downcast::<PyString>()) {
+        (Ok(a), Ok(b)) => {
+            let (a, b) = (a.to_str()?, b.to_str()?);
+            if !a.is_ascii() || !b.is_ascii() {
+                return Err(PyTypeError::new_err(
+                    "comparing strings with non-ASCII characters is not supported",
+                ));
+            }
+            Ok(constant_time_eq(a.as_bytes(), b.as_bytes()))
+        }
+        (Err(_), Err(_)) => {
+            let (a, b) = (ByteView::get(a)?, ByteView::get(b)?);
+            match (a.as_slice(), b.as_slice()) {
+                (Some(a), Some(b)) => Ok(constant_time_eq(a, b)),
+                _ => Err(PyBufferError::new_err("compare_digest needs C-contiguous buffers")),
+            }
+        }
+        _ => Err(PyTypeError::new_err(format!(
+            "unsupported operand types(s) or combination of types: '{}' and '{}'",
+            a.get_type().name()?,
+            b.get_type().name()?
+        ))),
+    }
+}
+
+/// Creates a Poly1305 state from a one-time `key`.
+///
+/// # Errors
+/// Returns `ValueError` if `key` is not 32 bytes long or in strict mode,
+/// Poly1305 not being a FIPS algorithm.
+fn poly1305_new(key: &[u8]) -> PyResult<Poly1305> {
+    check_approved("Poly1305", false, true)?;
+    let key: &[u8; POLY1305_KEY_SIZE] = key.try_into().map_err(|_| {
+        PyValueError::new_err(format!(
+            "key must be {} bytes, got {}",
+            POLY1305_KEY_SIZE,
+            key.len()
+        ))
+    })?;
+    Ok(Poly1305::new(key))
+}
+
+/// Python Poly1305 object (RFC 8439).
+///
+/// Created as `Poly1305(key, data=None)` with a 32-byte key that must
+/// authenticate a single message. The object can be finalized once: after
+/// `digest()`, `hexdigest()` or `verify()`, every method raises
+/// `RuntimeError`, and it cannot be copied.
+#[pyclass(name = "Poly1305", frozen)]
+pub struct PyPoly1305 {
+    /// `None` once finalized.
+    state: Mutex<Option<Poly1305>>,
+}
+
+impl PyPoly1305 {
+    /// Takes the state out of the object, leaving it finalized.
+    ///
+    /// # Errors
+    /// Returns `RuntimeError` if the object was already finalized.
+    fn finalize(&self, py: Python) -> PyResult<Poly1305> {
+        lock(py, &self.state).take().ok_or_else(finalized_error)
+    }
+}
+
+/// Error raised when a finalized [`PyPoly1305`] is used again.
+fn finalized_error() -> PyErr {
+    PyRuntimeError::new_err("Poly1305 object already finalized")
+}
+
+#[pymethods]
+impl PyPoly1305 {
+    /// Creates a Poly1305 object keyed with `key`, optionally with a first
+    /// message part.
+    ///
+    /// # Errors
+    /// Returns `ValueError` if `key` is not 32 bytes long or in strict mode.
+    #[new]
+    #[pyo3(signature = (key, data=None))]
+    fn new(py: Python, key: BytesArg, data: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut mac = poly1305_new(&key.0)?;
+        if let Some(obj) = data {
+            feed_buffer(py, obj, false, |bytes| {
+                mac.update(bytes);
+                Ok(())
+            })?;
+        }
+        Ok(PyPoly1305 { state: Mutex::new(Some(mac)) })
+    }
+
+    /// Feeds `data` into the MAC, with the GIL released for large inputs.
+    ///
+    /// # Errors
+    /// Returns `RuntimeError` if the object was already finalized.
+    fn update(&self, py: Python, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        let view = ByteView::get(data)?;
+        let mut state = lock(py, &self.state);
+        let mac = state.as_mut().ok_or_else(finalized_error)?;
+        feed_view(py, &view, false, |bytes| {
+            mac.update(bytes);
+            Ok(())
+        })
+    }
+
+    /// Returns the 16-byte tag and finalizes the object.
+    fn digest<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.finalize(py)?.finalize()))
+    }
+
+    /// Returns the tag as a hexadecimal string and finalizes the object.
+    fn hexdigest<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyString>> {
+        Ok(hex_string(py, &self.finalize(py)?.finalize()))
+    }
+
+    /// Checks `tag` against the tag of the message in constant time, and
+    /// finalizes the object.
+    fn verify(&self, py: Python, tag: BytesArg) -> PyResult<bool> {
+        Ok(self.finalize(py)?.verify(&tag.0))
+    }
+
+    #[getter]
+    fn digest_size(&self) -> usize {
+        POLY1305_TAG_SIZE
+    }
+
+    #[getter]
+    fn block_size(&self) -> usize {
+        POLY1305_BLOCK_SIZE
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        "poly1305"
+    }
+}
+
+/// One-shot Poly1305 returning the 16-byte tag of `data` under the
+/// one-time 32-byte `key`, with the GIL released for large inputs.
+///
+/// # Errors
+/// Returns `ValueError` if `key` is not 32 bytes long or in strict mode.
+#[pyfunction]
+pub fn poly1305<'py>(
+    py: Python<'py>,
+    key: BytesArg,
+    data: &Bound<'_, PyAny>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let mut mac = poly1305_new(&key.0)?;
+    feed_buffer(py, data, false, |bytes| {
+        mac.update(bytes);
+        Ok(())
+    })?;
+    Ok(PyBytes::new(py, &mac.finalize()))
+}
+
+/// Returns the hash for the one-time password functions, selected like
+/// `digestmod` in [`PyHMAC`] but SHA-1 by default, as in RFC 4226.
+fn otp_hasher(py: Python, digest: Option<&Bound<'_, PyAny>>) -> PyResult<Box<dyn DynDigest>> {
+    let default = PyString::new(py, "sha1");
+    Ok(hmac_hasher(Some(digest.unwrap_or(default.as_any())))?.0)
+}
+
+/// Returns the HOTP code (RFC 4226) of `counter` under `key`, as a string
+/// of `digits` decimal digits keeping its leading zeros.
+///
+/// `digest` is a name accepted by `new()` or one of the hash classes,
+/// SHA-1 by default.
+///
+/// # Errors
+/// Returns `ValueError` if `digits` is not between 6 and 8 or the digest
+/// is shorter than 20 bytes, and `OverflowError` if `counter` is negative
+/// or above 2**64 - 1.
+#[pyfunction]
+#[pyo3(signature = (key, counter, *, digits=6, digest=None))]
+pub fn hotp(
+    py: Python,
+    key: BytesArg,
+    counter: u64,
+    digits: u32,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<String> {
+    Ok(crate::core::hotp(otp_hasher(py, digest)?, &key.0, counter, digits)?)
+}
+
+/// Checks an HOTP `code` against the counters `counter` to
+/// `counter + look_ahead`, returning the matching counter or `None`.
+///
+/// Every counter of the window is tried and compared in constant time.
+/// After a match, the next expected counter is the returned one plus 1.
+///
+/// # Errors
+/// Same as [`hotp`].
+#[pyfunction]
+#[pyo3(signature = (key, counter, code, *, look_ahead=3, digits=6, digest=None))]
+pub fn hotp_verify(
+    py: Python,
+    key: BytesArg,
+    counter: u64,
+    code: &str,
+    look_ahead: u64,
+    digits: u32,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<u64>> {
+    let hasher = otp_hasher(py, digest)?;
+    Ok(crate::core::hotp_verify(hasher, &key.0, counter, code, digits, look_ahead)?)
+}
+
+/// Returns `time` in whole seconds, or the current Unix time when `None`.
+///
+/// # Errors
+/// Returns `ValueError` if `time` is negative or not finite.
+fn unix_time(time: Option<f64>) -> PyResult<u64> {
+    match time {
+        None => Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| PyValueError::new_err("system clock is before 1970"))?
+            .as_secs()),
+        Some(time) if time.is_finite() && time >= 0.0 => Ok(time as u64),
+        Some(_) => Err(PyValueError::new_err("time must be a non-negative number of seconds")),
+    }
+}
+
+/// Returns the TOTP code (RFC 6238) of `time`, the current time by
+/// default, under `key`.
+///
+/// `time` is a Unix time in seconds, as from `time.time()`, and is cut
+/// into `step`-second steps counted from `t0`; `digits` and `digest` are
+/// as in [`hotp`].
+///
+/// # Errors
+/// Returns `ValueError` if `step` is 0, `time` is before `t0` or negative,
+/// or for the cases of [`hotp`].
+#[pyfunction]
+#[pyo3(signature = (key, *, time=None, step=30, t0=0, digits=6, digest=None))]
+pub fn totp(
+    py: Python,
+    key: BytesArg,
+    time: Option<f64>,
+    step: u64,
+    t0: u64,
+    digits: u32,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<String> {
+    let hasher = otp_hasher(py, digest)?;
+    Ok(crate::core::totp(hasher, &key.0, unix_time(time)?, step, t0, digits)?)
+}
+
+/// Checks a TOTP `code` against the time steps within `window` steps of
+/// `time`, the current time by default, returning the matching time step
+/// or `None`.
+///
+/// Remembering the returned step and refusing codes up to it on the next
+/// call keeps a code from being used twice.
+///
+/// # Errors
+/// Same as [`totp`].
+#[pyfunction]
+#[pyo3(signature = (key, code, *, window=1, time=None, step=30, t0=0, digits=6, digest=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn totp_verify(
+    py: Python,
+    key: BytesArg,
+    code: &str,
+    window: u64,
+    time: Option<f64>,
+    step: u64,
+    t0: u64,
+    digits: u32,
+    digest: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<u64>> {
+    let hasher = otp_hasher(py, digest)?;
+    let time_step = totp_time_step(unix_time(time)?, step, t0)?;
+    Ok(crate::core::totp_verify(hasher, &key.0, time_step, code, digits, window)?)
+}
+
+/// Returns the `otpauth://totp/` URI that provisions `key` in an
+/// authenticator app, usually shown as a QR code.
+///
+/// The secret is written in unpadded Base32; an empty `issuer` is left
+/// out. `digest` is SHA-1, SHA-256 or SHA-512, the only hashes of the key
+/// URI format.
+///
+/// # Errors
+/// Returns `ValueError` if `account` is empty, `issuer` or `account`
+/// contains a colon, `digest` is another hash, `digits` is not between 6
+/// and 8, or `step` is 0.
+#[pyfunction]
+#[pyo3(signature = (key, issuer, account, *, digits=6, digest=None, step=30))]
+pub fn totp_uri(
+    py: Python,
+    key: BytesArg,
+    issuer: &str,
+    account: &str,
+    digits: u32,
+    digest: Option<&Bound<'_, PyAny>>,
+    step: u64,
+) -> PyResult<String> {
+    let default = PyString::new(py, "sha1");
+    let (_, name) = hmac_hasher(Some(digest.unwrap_or(default.as_any())))?;
+    let algorithm = name.to_ascii_uppercase();
+    Ok(crate::core::totp_uri(&key.0, issuer, account, &algorithm, digits, step)?)
+}
+
+/// Derives `length` bytes from `ikm` with HKDF (RFC 5869).
+///
+/// Runs HKDF-Extract with `salt` (an empty salt stands for `HashLen` zero
+/// bytes), then HKDF-Expand with `info`. `hash` selects the hash like
+/// `digestmod` in [`PyHMAC`], SHA-256 by default.
+///
+/// # Errors
+/// Returns `ValueError` if `length` exceeds 255 times the digest size.
+#[pyfunction]
+#[pyo3(signature = (ikm, *, salt=BytesArg::default(), info=BytesArg::default(), length, hash=None))]
+pub fn hkdf<'py>(
+    py: Python<'py>,
+    ikm: BytesArg,
+    salt: BytesArg,
+    info: BytesArg,
+    length: usize,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let okm = crate::core::hkdf(hasher, &salt.0, &ikm.0, &info.0, length)?;
+    Ok(PyBytes::new(py, &okm))
+}
+
+/// HKDF-Extract (RFC 5869): returns the pseudorandom key derived from
+/// `ikm` and `salt`, one digest long.
+#[pyfunction]
+#[pyo3(signature = (salt, ikm, hash=None))]
+pub fn hkdf_extract<'py>(
+    py: Python<'py>,
+    salt: BytesArg,
+    ikm: BytesArg,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    Ok(PyBytes::new(py, &crate::core::hkdf_extract(hasher, &salt.0, &ikm.0)))
+}
+
+/// HKDF-Expand (RFC 5869): derives `length` bytes from the pseudorandom
+/// key `prk` and the context `info`.
+///
+/// # Errors
+/// Returns `ValueError` if `length` exceeds 255 times the digest size.
+#[pyfunction]
+#[pyo3(signature = (prk, info, length, hash=None))]
+pub fn hkdf_expand<'py>(
+    py: Python<'py>,
+    prk: BytesArg,
+    info: BytesArg,
+    length: usize,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    Ok(PyBytes::new(py, &crate::core::hkdf_expand(hasher, &prk.0, &info.0, length)?))
+}
+
+/// HKDF-Expand-Label from TLS 1.3 (RFC 8446): HKDF-Expand of `secret`
+/// with the `"tls13 "`-prefixed `label` and `context` framed as info.
+///
+/// # Errors
+/// Returns `ValueError` if `label` is longer than 249 bytes, `context`
+/// longer than 255 bytes, or `length` above 65535 or 255 times the digest
+/// size.
+#[pyfunction]
+#[pyo3(signature = (secret, label, context, length, hash=None))]
+pub fn hkdf_expand_label<'py>(
+    py: Python<'py>,
+    secret: BytesArg,
+    label: BytesArg,
+    context: BytesArg,
+    length: usize,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let okm = crate::core::hkdf_expand_label(hasher, &secret.0, &label.0, &context.0, length)?;
+    Ok(PyBytes::new(py, &okm))
+}
+
+/// Derive-Secret from TLS 1.3 (RFC 8446): HKDF-Expand-Label of `secret`
+/// with `transcript_hash` as context, one digest long.
+///
+/// `transcript_hash` is the hash of the handshake messages so far, taken
+/// with the same `hash`; the empty transcript is the hash of `b""`.
+///
+/// # Errors
+/// Returns `ValueError` if `label` is longer than 249 bytes or
+/// `transcript_hash` longer than 255 bytes.
+#[pyfunction]
+#[pyo3(signature = (secret, label, transcript_hash, hash=None))]
+pub fn tls13_derive_secret<'py>(
+    py: Python<'py>,
+    secret: BytesArg,
+    label: BytesArg,
+    transcript_hash: BytesArg,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let secret = crate::core::tls13_derive_secret(hasher, &secret.0, &label.0, &transcript_hash.0)?;
+    Ok(PyBytes::new(py, &secret))
+}
+
+/// SP 800-108 KBKDF in counter mode: derives `length` bytes from `key`,
+/// `label` and `context` with HMAC as the PRF.
+///
+/// Each block is `HMAC(key, [i] || label || 0x00 || context || [L])`,
+/// where the counter `i` is on `rlen` bits and the output length `L` in
+/// bits on `llen` bits (left out when `llen` is 0), both in `byteorder`.
+/// `prf` is `"hmac-<hash>"`, or a hash name or class.
+///
+/// # Errors
+/// Returns `ValueError` for an unknown hash or byte order, a field size
+/// other than 8, 16, 24 or 32 bits, or a `length` that does not fit in
+/// the counter or length fields.
+#[pyfunction]
+#[pyo3(signature = (key, *, label, context, length, prf=None, rlen=32, llen=32, byteorder="big"))]
+#[allow(clippy::too_many_arguments)]
+pub fn kbkdf_counter<'py>(
+    py: Python<'py>,
+    key: BytesArg,
+    label: BytesArg,
+    context: BytesArg,
+    length: usize,
+    prf: Option<&Bound<'_, PyAny>>,
+    rlen: u32,
+    llen: u32,
+    byteorder: &str,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let hash = match prf {
+        Some(prf) => match prf.downcast::<PyString>() {
+            Ok(name) => {
+                let name = name.to_str()?.to_ascii_lowercase();
+                let hash = name.strip_prefix("hmac-").unwrap_or(&name);
+                hmac_hasher(Some(PyString::new(py, hash).as_any()))?.0
+            }
+            Err(_) => hmac_hasher(Some(prf))?.0,
+        },
+        None => hmac_hasher(None)?.0,
+    };
+    let order = crate::core::kbkdf::ByteOrder::from_name(byteorder)
+        .ok_or_else(|| PyValueError::new_err("byteorder must be either 'little' or 'big'"))?;
+    let fixed = crate::core::kbkdf::fixed_input(&label.0, &context.0, length, llen, order)?;
+    let okm = crate::core::kbkdf_counter(hash, &key.0, &fixed, length, rlen, order)?;
+    Ok(PyBytes::new(py, &okm))
+}
+
+/// ConcatKDF, the hash-based one-step KDF of SP 800-56C: derives `length`
+/// bytes as `H(counter || shared_secret || other_info)` blocks.
+///
+/// `hash` is an algorithm name or one of the hash classes, SHA-256 by
+/// default. For JOSE ECDH-ES, build `other_info` with [`jose_other_info`].
+///
+/// # Errors
+/// Returns `ValueError` if `length` exceeds (2^32 - 1) times the digest
+/// size or `hash` is an extendable-output function.
+#[pyfunction]
+#[pyo3(signature = (shared_secret, *, length, other_info, hash=None))]
+pub fn concat_kdf<'py>(
+    py: Python<'py>,
+    shared_secret: BytesArg,
+    length: usize,
+    other_info: BytesArg,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let key = crate::core::concat_kdf(hasher, &shared_secret.0, &other_info.0, length)?;
+    Ok(PyBytes::new(py, &key))
+}
+
+/// ANSI X9.63 KDF: derives `length` bytes as
+/// `H(shared_secret || counter || shared_info)` blocks.
+///
+/// # Errors
+/// Returns `ValueError` if `length` exceeds (2^32 - 1) times the digest
+/// size or `hash` is an extendable-output function.
+#[pyfunction]
+#[pyo3(signature = (shared_secret, *, length, shared_info=BytesArg::default(), hash=None))]
+pub fn x963_kdf<'py>(
+    py: Python<'py>,
+    shared_secret: BytesArg,
+    length: usize,
+    shared_info: BytesArg,
+    hash: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let key = crate::core::x963_kdf(hasher, &shared_secret.0, &shared_info.0, length)?;
+    Ok(PyBytes::new(py, &key))
+}
+
+/// Builds the ConcatKDF `other_info` of JOSE ECDH-ES (RFC 7518, section
+/// 4.6.2) from the `alg` or `enc` value, the decoded `apu` and `apv`
+/// headers and the key size in bits.
+///
+/// # Errors
+/// Returns `OverflowError` if `keydatalen` does not fit in 32 bits.
+#[pyfunction]
+#[pyo3(signature = (algorithm_id, *, keydatalen, apu=BytesArg::default(), apv=BytesArg::default()))]
+pub fn jose_other_info<'py>(
+    py: Python<'py>,
+    algorithm_id: &str,
+    keydatalen: u32,
+    apu: BytesArg,
+    apv: BytesArg,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let other_info = crate::core::concatkdf::jose_other_info(
+        algorithm_id.as_bytes(),
+        &apu.0,
+        &apv.0,
+        keydatalen,
+    )?;
+    Ok(PyBytes::new(py, &other_info))
+}
+
+/// Derives a key from a password with PBKDF2-HMAC.
+///
+/// Same semantics as `hashlib.pbkdf2_hmac()`: `dklen` defaults to the
+/// digest size and may span several hash outputs. `hash_name` may also be
+/// one of the hash classes. The password is keyed into HMAC once, and the
+/// whole derivation runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if `iterations` or `dklen` is below 1, and
+/// `OverflowError` if `iterations` does not fit in 32 bits or `dklen` is
+/// above 2^31 - 1, as hashlib does.
+#[pyfunction]
+#[pyo3(signature = (hash_name, password, salt, iterations, dklen=None))]
+pub fn pbkdf2_hmac<'py>(
+    py: Python<'py>,
+    hash_name: &Bound<'_, PyAny>,
+    password: BytesArg,
+    salt: BytesArg,
+    iterations: i64,
+    dklen: Option<i64>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(Some(hash_name))?;
+    if iterations < 1 {
+        return Err(PyValueError::new_err("iteration value must be greater than 0."));
+    }
+    let iterations = u32::try_from(iterations)
+        .map_err(|_| PyOverflowError::new_err("iteration value is too great."))?;
+    let dklen = dklen.unwrap_or(hasher.dyn_digest_size() as i64);
+    if dklen < 1 {
+        return Err(PyValueError::new_err("key length must be greater than 0."));
+    }
+    if dklen > i32::MAX as i64 {
+        return Err(PyOverflowError::new_err("key length is too great."));
+    }
+    let mut key = vec![0u8; dklen as usize];
+    py.allow_threads(|| {
+        crate::core::pbkdf2_hmac(hasher, &password.0, &salt.0, iterations, &mut key)
+    })?;
+    Ok(PyBytes::new(py, &key))
+}
+
+/// Default scrypt memory limit when `maxmem` is 0, the same as OpenSSL.
+const SCRYPT_DEFAULT_MAXMEM: i64 = 32 * 1024 * 1024;
+
+/// Derives a key from a password with scrypt (RFC 7914).
+///
+/// Same signature and limits as `hashlib.scrypt()`: `n` must be a power
+/// of two, and the memory the parameters need must fit in `maxmem` bytes
+/// (32 MiB when 0). The derivation runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` for a non-power-of-two `n`, an out-of-range
+/// `maxmem` or `dklen`, an invalid `r` and `p` combination, or parameters
+/// that need more than `maxmem` bytes.
+#[pyfunction]
+#[pyo3(signature = (password, *, salt, n, r, p, maxmem=0, dklen=64))]
+#[allow(clippy::too_many_arguments)]
+pub fn scrypt<'py>(
+    py: Python<'py>,
+    password: BytesArg,
+    salt: BytesArg,
+    n: u64,
+    r: u32,
+    p: u32,
+    maxmem: i64,
+    dklen: i64,
+) -> PyResult<Bound<'py, PyBytes>> {
+    if n < 2 || !n.is_power_of_two() {
+        return Err(PyValueError::new_err("n must be a power of 2."));
+    }
+    if !(0..=i32::MAX as i64).contains(&maxmem) {
+        return Err(PyValueError::new_err(
+            "maxmem must be positive and smaller than 2147483647",
+        ));
+    }
+    if !(1..=i32::MAX as i64).contains(&dklen) {
+        return Err(PyValueError::new_err(
+            "dklen must be greater than 0 and smaller than 2147483647",
+        ));
+    }
+    let maxmem = if maxmem == 0 { SCRYPT_DEFAULT_MAXMEM } else { maxmem };
+    if crate::core::scrypt::memory_required(n, r, p) > maxmem as u128 {
+        return Err(PyValueError::new_err("memory limit exceeded"));
+    }
+    let mut key = vec![0u8; dklen as usize];
+    py.allow_threads(|| crate::core::scrypt(&password.0, &salt.0, n, r, p, &mut key))?;
+    Ok(PyBytes::new(py, &key))
+}
+
+/// Size of the salts generated for password hashes, in bytes.
+const PASSWORD_SALT_LEN: usize = 16;
+
+/// Returns `len` random bytes from `os.urandom()`.
+fn random_bytes(py: Python, len: usize) -> PyResult<Vec<u8>> {
+    py.import(intern!(py, "os"))?.call_method1(intern!(py, "urandom"), (len,))?.extract()
+}
+
+/// Builds Argon2 parameters from the Python keyword arguments.
+///
+/// `type` is `"id"`, `"i"` or `"d"`, or the full `"argon2id"` style name.
+fn argon2_params(
+    time_cost: u32,
+    memory_cost: u32,
+    parallelism: u32,
+    variant: &str,
+) -> PyResult<Argon2Params> {
+    let variant = Argon2Variant::from_name(variant)
+        .or_else(|| Argon2Variant::from_name(&format!("argon2{}", variant)))
+        .ok_or_else(|| PyValueError::new_err("type must be 'id', 'i' or 'd'"))?;
+    Ok(Argon2Params {
+        t_cost: time_cost,
+        m_cost: memory_cost,
+        parallelism,
+        ..Argon2Params::new(variant)
+    })
+}
+
+/// Runs Argon2 with the GIL released, returning a `hash_len`-byte tag.
+///
+/// The tag is written straight into the bytes object, so a length that
+/// cannot be allocated raises `MemoryError`.
+fn argon2_run<'py>(
+    py: Python<'py>,
+    params: &Argon2Params,
+    password: &[u8],
+    salt: &[u8],
+    hash_len: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    // RFC 9106 caps the tag length at 2^32 - 1 bytes
+    if hash_len > u32::MAX as usize {
+        return Err(PyValueError::new_err("hash_len must be at most 2^32 - 1 bytes"));
+    }
+    PyBytes::new_with(py, hash_len, |hash| {
+        py.allow_threads(|| crate::core::argon2(params, password, salt, hash))?;
+        Ok(())
+    })
+}
+
+/// Hashes a password with Argon2 (RFC 9106), returning the raw tag.
+///
+/// `memory_cost` is in KiB and `type` is `"id"` (the default), `"i"` or
+/// `"d"`. The lanes are filled on parallel threads with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if a cost is out of range, the salt is shorter
+/// than 8 bytes or `hash_len` is not between 4 and 2^32 - 1, and
+/// `MemoryError` if the tag cannot be allocated.
+#[pyfunction]
+#[pyo3(signature = (
+    password, salt, *, time_cost=3, memory_cost=65536, parallelism=4, hash_len=32, r#type="id"
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn argon2_hash<'py>(
+    py: Python<'py>,
+    password: BytesArg,
+    salt: BytesArg,
+    time_cost: u32,
+    memory_cost: u32,
+    parallelism: u32,
+    hash_len: usize,
+    r#type: &str,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let params = argon2_params(time_cost, memory_cost, parallelism, r#type)?;
+    argon2_run(py, &params, &password.0, &salt.0, hash_len)
+}
+
+/// Hashes a password with Argon2 and returns the PHC string, such as
+/// `$argon2id$v=19$m=65536,t=3,p=4$<salt>$<hash>`.
+///
+/// Takes the same arguments as [`argon2_hash`], except that a random
+/// 16-byte salt is generated when `salt` is omitted.
+///
+/// # Errors
+/// Returns `ValueError` like [`argon2_hash`].
+#[pyfunction]
+#[pyo3(signature = (
+    password, salt=None, *, time_cost=3, memory_cost=65536, parallelism=4, hash_len=32,
+    r#type="id"
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn argon2_phc(
+    py: Python,
+    password: BytesArg,
+    salt: Option<BytesArg>,
+    time_cost: u32,
+    memory_cost: u32,
+    parallelism: u32,
+    hash_len: usize,
+    r#type: &str,
+) -> PyResult<String> {
+    let params = argon2_params(time_cost, memory_cost, parallelism, r#type)?;
+    let salt = match salt {
+        Some(salt) => salt.0,
+        None => random_bytes(py, PASSWORD_SALT_LEN)?,
+    };
+    let hash = argon2_run(py, &params, &password.0, &salt, hash_len)?;
+    Ok(crate::core::argon2::phc_encode(&params, &salt, hash.as_bytes()))
+}
+
+/// Checks a password against an Argon2 PHC string.
+///
+/// The variant, version, costs, salt and hash length all come from the
+/// string, and the hashes are compared in constant time.
+///
+/// # Errors
+/// Returns `ValueError` if `phc_string` is not a valid Argon2 PHC string.
+#[pyfunction]
+pub fn argon2_verify(py: Python, phc_string: &str, password: BytesArg) -> PyResult<bool> {
+    let (params, salt, expected) = crate::core::argon2::phc_decode(phc_string)?;
+    let hash = argon2_run(py, &params, &password.0, &salt, expected.len())?;
+    Ok(constant_time_eq(hash.as_bytes(), &expected))
+}
+
+/// Hashes a password with bcrypt, returning a `$2b$` string.
+///
+/// `salt` is 16 raw bytes, random when omitted. Only the first 72 bytes
+/// of the password count. The key schedule runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if `rounds` is not between 4 and 31 or `salt` is
+/// not 16 bytes long.
+#[pyfunction]
+#[pyo3(signature = (password, *, rounds=12, salt=None))]
+pub fn bcrypt_hash(
+    py: Python,
+    password: BytesArg,
+    rounds: u32,
+    salt: Option<BytesArg>,
+) -> PyResult<String> {
+    let salt = match salt {
+        Some(salt) => salt.0,
+        None => random_bytes(py, crate::core::bcrypt::SALT_LEN)?,
+    };
+    let salt: [u8; crate::core::bcrypt::SALT_LEN] = salt
+        .try_into()
+        .map_err(|_| PyValueError::new_err("salt must be 16 bytes long"))?;
+    let hash = py.allow_threads(|| crate::core::bcrypt(&password.0, &salt, rounds))?;
+    Ok(crate::core::bcrypt::encode("2b", rounds, &salt, &hash))
+}
+
+/// Checks a password against a `$2a$`, `$2b$` or `$2y$` bcrypt hash,
+/// given as `str` or `bytes`.
+///
+/// The hash is recomputed with the stored version, cost and salt, and the
+/// strings are compared in constant time.
+///
+/// # Errors
+/// Returns `ValueError` if `hashed` is not a valid bcrypt hash.
+#[pyfunction]
+pub fn bcrypt_verify(py: Python, password: BytesArg, hashed: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let hashed = hash_string(hashed)?;
+    let (version, cost, salt, _) = crate::core::bcrypt::decode(&hashed)?;
+    let hash = py.allow_threads(|| crate::core::bcrypt(&password.0, &salt, cost))?;
+    let computed = crate::core::bcrypt::encode(version, cost, &salt, &hash);
+    Ok(constant_time_eq(computed.as_bytes(), hashed.as_bytes()))
+}
+
+/// Derives `key_len` bytes with OpenBSD's bcrypt_pbkdf, the KDF of
+/// encrypted OpenSSH private keys; same arguments as `bcrypt.kdf()`.
+///
+/// OpenSSH keys store the salt and `rounds` (16 by default) and derive the
+/// cipher key and IV together. Runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if `password` or `salt` is empty, `key_len` is not
+/// between 1 and 1024, or `rounds` is 0.
+#[pyfunction]
+pub fn bcrypt_pbkdf<'py>(
+    py: Python<'py>,
+    password: BytesArg,
+    salt: BytesArg,
+    key_len: usize,
+    rounds: u32,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let key = py.allow_threads(|| {
+        crate::core::bcrypt_pbkdf(&password.0, &salt.0, key_len, rounds)
+    })?;
+    Ok(PyBytes::new(py, &key))
+}
 
-use pyo3::prelude::*;
-use pyo3::types::PyBytes;
-use crate::core::{Sha256, Sha512};
+/// Hashes a password with SHA-crypt over `variant`, drawing a random
+/// 16-character salt when `salt` is omitted.
+fn sha_crypt_run(
+    py: Python,
+    variant: crate::core::ShaCryptVariant,
+    password: &[u8],
+    salt: Option<&str>,
+    rounds: u32,
+) -> PyResult<String> {
+    let salt = match salt {
+        Some(salt) => salt.to_owned(),
+        None => random_bytes(py, crate::core::sha_crypt::MAX_SALT_LEN)?
+            .iter()
+            .map(|b| crate::core::sha_crypt::ALPHABET[usize::from(b & 0x3f)] as char)
+            .collect(),
+    };
+    // The default count is implied, as in the strings written by crypt(3)
+    let rounds = (rounds != crate::core::sha_crypt::DEFAULT_ROUNDS).then_some(rounds);
+    Ok(py.allow_threads(|| crate::core::sha_crypt(variant, password, &salt, rounds))?)
+}
 
-/// Python wrapper for SHA-256 hash algorithm.
+/// Hashes a password with sha256-crypt, returning a `$5$` string as in
+/// `/etc/shadow`.
+///
+/// `salt` keeps its first 16 characters and is random when omitted.
+/// `rounds` is clamped to 1000..=999999999 and written in the string
+/// unless it is the default 5000. Runs with the GIL released.
 ///
-/// Compatible with `hashlib.sha256()` API.
-#[pyclass(name = "SHA256")]
-pub struct PySHA256 {
-    hasher: Sha256,
+/// # Errors
+/// Returns `ValueError` if `salt` has characters other than printable
+/// ASCII, or a `$` or `:`.
+#[pyfunction]
+#[pyo3(signature = (password, salt=None, rounds=5000))]
+pub fn sha256_crypt(
+    py: Python,
+    password: BytesArg,
+    salt: Option<&str>,
+    rounds: u32,
+) -> PyResult<String> {
+    sha_crypt_run(py, crate::core::ShaCryptVariant::Sha256, &password.0, salt, rounds)
 }
 
-#[pymethods]
-impl PySHA256 {
-    /// Creates a new SHA-256 hasher, optionally with initial data.
-    #[new]
-    #[pyo3(signature = (data=None))]
-    fn new(data: Option<&[u8]>) -> Self {
-        let mut hasher = Sha256::new();
-        if let Some(bytes) = data {
-            hasher.update(bytes);
+/// Hashes a password with sha512-crypt, returning a `$6$` string as in
+/// `/etc/shadow`; same arguments as [`sha256_crypt`].
+///
+/// # Errors
+/// Returns `ValueError` if `salt` has characters other than printable
+/// ASCII, or a `$` or `:`.
+#[pyfunction]
+#[pyo3(signature = (password, salt=None, rounds=5000))]
+pub fn sha512_crypt(
+    py: Python,
+    password: BytesArg,
+    salt: Option<&str>,
+    rounds: u32,
+) -> PyResult<String> {
+    sha_crypt_run(py, crate::core::ShaCryptVariant::Sha512, &password.0, salt, rounds)
+}
+
+/// Checks a password against a crypt string, given as `str` or `bytes`:
+/// `$5$` and `$6$` SHA-crypt, or `$2a$`, `$2b$` and `$2y$` bcrypt.
+///
+/// The hash is recomputed with the stored settings and compared in
+/// constant time.
+///
+/// # Errors
+/// Returns `ValueError` if `crypt_string` is malformed or of another
+/// scheme.
+#[pyfunction]
+pub fn crypt_verify(
+    py: Python,
+    password: BytesArg,
+    crypt_string: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    let text = hash_string(crypt_string)?;
+    if text.starts_with("$5$") || text.starts_with("$6$") {
+        Ok(py.allow_threads(|| crate::core::sha_crypt::verify(&password.0, &text))?)
+    } else if ["$2a$", "$2b$", "$2y$"].iter().any(|prefix| text.starts_with(prefix)) {
+        bcrypt_verify(py, password, crypt_string)
+    } else {
+        Err(PyValueError::new_err("unsupported crypt scheme, expected $5$, $6$ or $2b$"))
+    }
+}
+
+pyo3::create_exception!(
+    RsHash,
+    InvalidHashError,
+    PyValueError,
+    "Stored password hash that is malformed or of an unknown scheme."
+);
+
+/// Wraps the error of a stored password hash that cannot be used.
+fn invalid_hash(err: ParameterError) -> PyErr {
+    InvalidHashError::new_err(err.to_string())
+}
+
+/// Builds a [`PasswordScheme`] from its name and keyword costs, starting
+/// from the defaults of the matching single-scheme functions.
+///
+/// # Errors
+/// Returns `ValueError` for an unknown scheme and `TypeError` for a
+/// keyword the scheme does not take.
+fn password_scheme(scheme: &str, params: Option<&Bound<'_, PyDict>>) -> PyResult<PasswordScheme> {
+    let mut scheme = match scheme {
+        "argon2id" | "argon2i" | "argon2d" => PasswordScheme::Argon2 {
+            params: Argon2Params::new(Argon2Variant::from_name(scheme).unwrap()),
+            hash_len: 32,
+        },
+        "bcrypt" => PasswordScheme::Bcrypt { cost: 12 },
+        "scrypt" => PasswordScheme::Scrypt {
+            params: crate::core::ScryptParams { n: 1 << 16, r: 8, p: 1 },
+            hash_len: 32,
+        },
+        "sha256_crypt" | "sha512_crypt" => PasswordScheme::ShaCrypt {
+            variant: if scheme == "sha256_crypt" {
+                crate::core::ShaCryptVariant::Sha256
+            } else {
+                crate::core::ShaCryptVariant::Sha512
+            },
+            rounds: crate::core::sha_crypt::DEFAULT_ROUNDS,
+        },
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "unsupported password scheme '{}', expected argon2id, argon2i, argon2d, bcrypt, \
+                 scrypt, sha256_crypt or sha512_crypt",
+                scheme
+            )));
+        }
+    };
+    let name = scheme.name();
+    for (key, value) in params.into_iter().flatten() {
+        let key: String = key.extract()?;
+        match (&mut scheme, key.as_str()) {
+            (PasswordScheme::Argon2 { params, .. }, "time_cost") => {
+                params.t_cost = value.extract()?
+            }
+            (PasswordScheme::Argon2 { params, .. }, "memory_cost") => {
+                params.m_cost = value.extract()?
+            }
+            (PasswordScheme::Argon2 { params, .. }, "parallelism") => {
+                params.parallelism = value.extract()?
+            }
+            (
+                PasswordScheme::Argon2 { hash_len, .. } | PasswordScheme::Scrypt { hash_len, .. },
+                "hash_len",
+            ) => {
+                let len: usize = value.extract()?;
+                if !(4..=1024).contains(&len) {
+                    return Err(PyValueError::new_err("hash_len must be between 4 and 1024 bytes"));
+                }
+                *hash_len = len;
+            }
+            (PasswordScheme::Scrypt { params, .. }, "n") => params.n = value.extract()?,
+            (PasswordScheme::Scrypt { params, .. }, "r") => params.r = value.extract()?,
+            (PasswordScheme::Scrypt { params, .. }, "p") => params.p = value.extract()?,
+            (
+                PasswordScheme::Bcrypt { cost: rounds } | PasswordScheme::ShaCrypt { rounds, .. },
+                "rounds",
+            ) => *rounds = value.extract()?,
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "unexpected parameter '{}' for {}",
+                    key, name
+                )));
+            }
         }
-        PySHA256 { hasher }
     }
+    Ok(scheme)
+}
+
+/// Hashes a password with any supported scheme and a random 16-byte salt,
+/// returning the string to store.
+///
+/// `scheme` is `"argon2id"` (the default), `"argon2i"`, `"argon2d"`,
+/// `"bcrypt"`, `"scrypt"`, `"sha256_crypt"` or `"sha512_crypt"`. The
+/// keyword costs are those of the single-scheme functions: `time_cost`,
+/// `memory_cost`, `parallelism` and `hash_len` for Argon2, `rounds` for
+/// bcrypt and SHA-crypt, and `n`, `r`, `p` and `hash_len` for scrypt,
+/// which writes a `$scrypt$ln=...,r=...,p=...` PHC string (n = 2^16 and
+/// r = 8 by default). Runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` for an unknown scheme, out-of-range costs or a
+/// `hash_len` outside 4 to 1024 bytes, and `TypeError` for a keyword the
+/// scheme does not take.
+#[pyfunction]
+#[pyo3(signature = (password, scheme="argon2id", **params))]
+pub fn password_hash(
+    py: Python,
+    password: BytesArg,
+    scheme: &str,
+    params: Option<&Bound<'_, PyDict>>,
+) -> PyResult<String> {
+    let scheme = password_scheme(scheme, params)?;
+    let salt: [u8; crate::core::password::SALT_LEN] = random_bytes(
+        py,
+        crate::core::password::SALT_LEN,
+    )?
+    .try_into()
+    .expect("random_bytes returns the requested length");
+    Ok(py.allow_threads(|| crate::core::password::hash(&password.0, &scheme, &salt))?)
+}
+
+/// Checks a password against a stored hash of any supported scheme, given
+/// as `str` or `bytes`, recognised from its prefix: `$argon2id$`,
+/// `$argon2i$`, `$argon2d$`, `$scrypt$`, `$2a$`/`$2b$`/`$2y$`, `$5$` or
+/// `$6$`.
+///
+/// The hash is recomputed with the stored settings and compared in
+/// constant time. Runs with the GIL released.
+///
+/// # Errors
+/// Returns `InvalidHashError`, a `ValueError`, if `stored` is malformed,
+/// of an unknown scheme or has costs its algorithm rejects.
+#[pyfunction]
+pub fn password_verify(
+    py: Python,
+    password: BytesArg,
+    stored: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    let stored = hash_string(stored)?;
+    py.allow_threads(|| crate::core::password::verify(&password.0, &stored)).map_err(invalid_hash)
+}
 
-    /// Updates the hash with additional data.
-    fn update(&mut self, data: &[u8]) {
-        self.hasher.update(data);
+/// Returns whether a stored hash should be recomputed to meet `policy`, a
+/// dict with a `"scheme"` key (`"argon2id"` when missing) and the keyword
+/// costs of [`password_hash`], such as
+/// `{"scheme": "bcrypt", "rounds": 12}`.
+///
+/// True when the stored string uses another scheme or Argon2 variant, an
+/// older Argon2 version, or any cost or hash length below the policy's.
+/// Argon2 `parallelism` does not count, as it only spreads the same work.
+/// Costs the policy omits take the [`password_hash`] defaults.
+///
+/// # Errors
+/// Returns `InvalidHashError` if `stored` is malformed or of an unknown
+/// scheme, and `ValueError` or `TypeError` for an invalid policy.
+#[pyfunction]
+pub fn password_needs_rehash(
+    stored: &Bound<'_, PyAny>,
+    policy: &Bound<'_, PyDict>,
+) -> PyResult<bool> {
+    let stored = crate::core::password::identify(&hash_string(stored)?).map_err(invalid_hash)?;
+    let params = policy.copy()?;
+    let scheme = match params.get_item("scheme")? {
+        Some(scheme) => {
+            params.del_item("scheme")?;
+            scheme.extract::<String>()?
+        }
+        None => "argon2id".to_owned(),
+    };
+    Ok(stored.is_weaker_than(&password_scheme(&scheme, Some(&params))?))
+}
+
+/// Hashes a password with Balloon, or Balloon-M when `parallelism` is
+/// above 1, returning the first `length` bytes of the output.
+///
+/// `space_cost` is the buffer size in digest-sized blocks and `hash` an
+/// algorithm name or one of the hash classes, SHA-256 by default.
+/// Balloon-M instances run on parallel threads, and the whole computation
+/// runs with the GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if a cost or `parallelism` is 0, `hash` is an
+/// extendable-output function or `length` is not between 1 and the digest
+/// size.
+#[pyfunction]
+#[pyo3(signature = (password, salt, *, space_cost, time_cost, parallelism=1, hash=None, length=32))]
+#[allow(clippy::too_many_arguments)]
+pub fn balloon<'py>(
+    py: Python<'py>,
+    password: BytesArg,
+    salt: BytesArg,
+    space_cost: u64,
+    time_cost: u64,
+    parallelism: u32,
+    hash: Option<&Bound<'_, PyAny>>,
+    length: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let (hasher, _) = hmac_hasher(hash)?;
+    let digest_size = hasher.dyn_digest_size();
+    if !(1..=digest_size).contains(&length) {
+        return Err(PyValueError::new_err(format!(
+            "length must be between 1 and {}",
+            digest_size
+        )));
     }
+    let output = py.allow_threads(|| {
+        crate::core::balloon(hasher, &password.0, &salt.0, space_cost, time_cost, parallelism)
+    })?;
+    Ok(PyBytes::new(py, &output[..length]))
+}
 
-    /// Returns the digest as bytes.
-    fn digest(&mut self, py: Python) -> PyResult<PyObject> {
-        let result = self.hasher.finalize();
-        Ok(PyBytes::new_bound(py, &result).into())
+/// Reads a stored password hash given as `str` or as ASCII `bytes`.
+fn hash_string(hashed: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(text) = hashed.downcast::<PyString>() {
+        return Ok(text.to_str()?.to_owned());
     }
+    let bytes = hashed.extract::<BytesArg>()?.0;
+    String::from_utf8(bytes).map_err(|_| PyValueError::new_err("hash must be ASCII"))
+}
+
+/// Hashes `data` with a fresh SHA-256 hasher, releasing the GIL for large inputs.
+fn sha256_oneshot(py: Python, data: &Bound<'_, PyAny>) -> PyResult<Sha256> {
+    let mut hasher = Sha256::new();
+    feed_buffer(py, data, false, |bytes| hasher.try_update(bytes))?;
+    Ok(hasher)
+}
 
-    /// Returns the digest as a hexadecimal string.
-    fn hexdigest(&mut self) -> String {
-        self.hasher.finalize_hex()
+/// Hashes `data` with a fresh SHA-512 hasher, releasing the GIL for large inputs.
+fn sha512_oneshot(py: Python, data: &Bound<'_, PyAny>) -> PyResult<Sha512> {
+    let mut hasher = Sha512::new();
+    feed_buffer(py, data, false, |bytes| hasher.try_update(bytes))?;
+    Ok(hasher)
+}
+
+/// One-shot SHA-256 returning the digest as bytes.
+///
+/// Equivalent to `SHA256(data).digest()` without creating a hash object.
+#[pyfunction]
+pub fn sha256_digest(py: Python, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let result = sha256_oneshot(py, data)?.digest();
+    Ok(PyBytes::new(py, &result).into())
+}
+
+/// One-shot SHA-256 returning the digest as a hexadecimal string.
+///
+/// Equivalent to `SHA256(data).hexdigest()` without creating a hash object.
+#[pyfunction]
+pub fn sha256_hexdigest<'py>(py: Python<'py>, data: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PyString>> {
+    let mut hex = [0u8; 64];
+    Ok(PyString::new(py, hex_into(&sha256_oneshot(py, data)?.digest(), &mut hex)))
+}
+
+/// One-shot SHA-512 returning the digest as bytes.
+///
+/// Equivalent to `SHA512(data).digest()` without creating a hash object.
+#[pyfunction]
+pub fn sha512_digest(py: Python, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let result = sha512_oneshot(py, data)?.digest();
+    Ok(PyBytes::new(py, &result).into())
+}
+
+/// One-shot SHA-512 returning the digest as a hexadecimal string.
+///
+/// Equivalent to `SHA512(data).hexdigest()` without creating a hash object.
+#[pyfunction]
+pub fn sha512_hexdigest<'py>(py: Python<'py>, data: &Bound<'_, PyAny>) -> PyResult<Bound<'py, PyString>> {
+    let mut hex = [0u8; 128];
+    Ok(PyString::new(py, hex_into(&sha512_oneshot(py, data)?.digest(), &mut hex)))
+}
+
+/// Largest read size accepted by the file hashing functions.
+const MAX_CHUNK_SIZE: usize = 1 << 30;
+
+/// Failure while streaming a file through a hasher.
+enum FileError {
+    /// The file could not be opened or read.
+    Io(std::io::Error),
+    /// The input went past the hash's length limit.
+    Overflow(LengthOverflowError),
+    /// The file ends at byte `size`, before the requested byte `end`.
+    Truncated { end: u64, size: u64 },
+}
+
+impl From<std::io::Error> for FileError {
+    fn from(err: std::io::Error) -> Self {
+        FileError::Io(err)
     }
+}
 
-    /// Creates a copy of the current hasher state.
-    fn copy(&self) -> Self {
-        PySHA256 {
-            hasher: Sha256::new(),
-        }
+impl From<LengthOverflowError> for FileError {
+    fn from(err: LengthOverflowError) -> Self {
+        FileError::Overflow(err)
     }
+}
 
-    #[getter]
-    fn digest_size(&self) -> usize {
-        Sha256::digest_size()
+impl FileError {
+    /// Converts the failure into a Python exception naming `path`.
+    ///
+    /// OS errors become `OSError(errno, strerror, path)`, which Python
+    /// turns into the matching subclass such as `FileNotFoundError`,
+    /// `PermissionError` or `IsADirectoryError`, exactly as `open()` does.
+    fn into_pyerr(self, path: &Bound<'_, PyAny>) -> PyErr {
+        let err = match self {
+            FileError::Io(err) => err,
+            FileError::Overflow(err) => return err.into(),
+            FileError::Truncated { end, size } => {
+                return PyValueError::new_err(format!(
+                    "file is shorter than offset + length: ends at byte {}, not {}",
+                    size, end
+                ));
+            }
+        };
+        let Some(code) = err.raw_os_error() else {
+            return err.into();
+        };
+        let strerror = path
+            .py()
+            .import("os")
+            .and_then(|os| os.call_method1("strerror", (code,)))
+            .and_then(|text| text.extract::<String>())
+            .unwrap_or_else(|_| err.to_string());
+        PyOSError::new_err((code, strerror, path.clone().unbind()))
     }
+}
 
-    #[getter]
-    fn block_size(&self) -> usize {
-        Sha256::block_size()
+/// Converts a `str`, `bytes` or `os.PathLike` argument into a path, with
+/// `os.fsdecode()` so undecodable bytes survive the round trip.
+fn fs_path(path: &Bound<'_, PyAny>) -> PyResult<std::path::PathBuf> {
+    path.py().import("os")?.call_method1("fsdecode", (path,))?.extract()
+}
+
+/// Returns a fresh core hasher for the algorithm named `algorithm`.
+///
+/// # Errors
+/// Returns `ValueError` if the algorithm is unknown, has no fixed digest
+/// size, or is refused in strict mode.
+fn named_hasher(algorithm: &str, usedforsecurity: bool) -> PyResult<Box<dyn DynDigest>> {
+    let algo = registry::lookup(algorithm)
+        .ok_or_else(|| PyValueError::new_err(format!("unsupported hash type {}", algorithm)))?;
+    let hasher = algo.hasher.ok_or_else(|| {
+        PyValueError::new_err(format!("{} has no fixed digest size", algo.name))
+    })?;
+    check_approved(algo.name, algo.approved, usedforsecurity)?;
+    Ok(hasher())
+}
+
+/// Checks a `chunk_size` argument.
+fn check_chunk_size(chunk_size: usize) -> PyResult<()> {
+    if !(1..=MAX_CHUNK_SIZE).contains(&chunk_size) {
+        return Err(PyValueError::new_err("chunk_size must be between 1 and 2^30"));
     }
+    Ok(())
+}
 
-    #[getter]
-    fn name(&self) -> &str {
-        "sha256"
+/// Feeds `reader` to `hasher` in reads of up to `chunk_size` bytes until
+/// the end of the input, retrying interrupted reads, and returns the number
+/// of bytes hashed.
+fn hash_reader(
+    hasher: &mut dyn DynDigest,
+    mut reader: impl std::io::Read,
+    chunk_size: usize,
+) -> Result<u64, FileError> {
+    let mut buf = vec![0u8; chunk_size];
+    let mut total = 0u64;
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(total),
+            Ok(n) => {
+                hasher.dyn_try_update(&buf[..n])?;
+                total += n as u64;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
     }
 }
 
-/// Python wrapper for SHA-512 hash algorithm.
+/// Hashes the file at `path`, returning the hex digest, or the raw bytes
+/// with `digest=True`.
 ///
-/// Compatible with `hashlib.sha512()` API.
-#[pyclass(name = "SHA512")]
-pub struct PySHA512 {
-    hasher: Sha512,
+/// The file is opened and read in Rust, `chunk_size` bytes at a time, with
+/// the GIL released for the whole loop. `path` is a `str`, `bytes` or
+/// `os.PathLike`, and `algorithm` any fixed-size name accepted by
+/// [`new`].
+///
+/// # Errors
+/// Returns the `OSError` subclass `open()` would raise, such as
+/// `FileNotFoundError`, `PermissionError` or `IsADirectoryError`, and
+/// `ValueError` for an unknown or extendable-output algorithm, one refused
+/// in strict mode, or a `chunk_size` outside 1..=2^30.
+#[pyfunction]
+#[pyo3(signature = (
+    path, algorithm="sha256", chunk_size=1 << 20, *, digest=false, usedforsecurity=true
+))]
+pub fn hash_file(
+    py: Python,
+    path: &Bound<'_, PyAny>,
+    algorithm: &str,
+    chunk_size: usize,
+    digest: bool,
+    usedforsecurity: bool,
+) -> PyResult<PyObject> {
+    let mut hasher = named_hasher(algorithm, usedforsecurity)?;
+    check_chunk_size(chunk_size)?;
+    let file_path = fs_path(path)?;
+    let result = py
+        .allow_threads(|| {
+            let file = std::fs::File::open(&file_path)?;
+            hash_reader(hasher.as_mut(), file, chunk_size)?;
+            Ok(hasher.dyn_digest())
+        })
+        .map_err(|err: FileError| err.into_pyerr(path))?;
+    Ok(if digest {
+        PyBytes::new(py, &result).into_any().unbind()
+    } else {
+        hex_string(py, &result).into_any().unbind()
+    })
 }
 
-#[pymethods]
-impl PySHA512 {
-    /// Creates a new SHA-512 hasher, optionally with initial data.
-    #[new]
-    #[pyo3(signature = (data=None))]
-    fn new(data: Option<&[u8]>) -> Self {
-        let mut hasher = Sha512::new();
-        if let Some(bytes) = data {
-            hasher.update(bytes);
+/// Hashes bytes `[offset, offset + length)` of the file at `path`, or from
+/// `offset` to the end of the file when `length` is `None`, returning the
+/// hex digest, or the raw bytes with `digest=True`.
+///
+/// The file is positioned at `offset` with a seek, so the bytes before it
+/// are never read, then streamed as by [`hash_file`], with the GIL
+/// released.
+///
+/// # Errors
+/// Returns `ValueError` if the file ends before `offset + length` (or
+/// before `offset` when `length` is `None`), and otherwise the same errors
+/// as [`hash_file`].
+#[pyfunction]
+#[pyo3(signature = (
+    path, offset, length, algorithm="sha256", chunk_size=1 << 20, *, digest=false,
+    usedforsecurity=true
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn hash_file_range(
+    py: Python,
+    path: &Bound<'_, PyAny>,
+    offset: u64,
+    length: Option<u64>,
+    algorithm: &str,
+    chunk_size: usize,
+    digest: bool,
+    usedforsecurity: bool,
+) -> PyResult<PyObject> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut hasher = named_hasher(algorithm, usedforsecurity)?;
+    check_chunk_size(chunk_size)?;
+    let file_path = fs_path(path)?;
+    let result = py
+        .allow_threads(|| {
+            let mut file = std::fs::File::open(&file_path)?;
+            let metadata = file.metadata()?;
+            let end = offset.saturating_add(length.unwrap_or(0));
+            if metadata.is_file() && metadata.len() < end {
+                return Err(FileError::Truncated { end, size: metadata.len() });
+            }
+            file.seek(SeekFrom::Start(offset))?;
+            match length {
+                Some(length) => {
+                    let read = hash_reader(hasher.as_mut(), file.take(length), chunk_size)?;
+                    if read < length {
+                        return Err(FileError::Truncated { end, size: offset + read });
+                    }
+                }
+                None => {
+                    hash_reader(hasher.as_mut(), file, chunk_size)?;
+                }
+            }
+            Ok(hasher.dyn_digest())
+        })
+        .map_err(|err: FileError| err.into_pyerr(path))?;
+    Ok(if digest {
+        PyBytes::new(py, &result).into_any().unbind()
+    } else {
+        hex_string(py, &result).into_any().unbind()
+    })
+}
+
+/// Reads the `expected` argument of [`verify_file`] and [`verify_bytes`],
+/// returning a fresh hasher and the expected digest.
+///
+/// `expected` is raw digest bytes, a hex string of either case, or a hex
+/// string prefixed with its algorithm, as in `"sha256:9f86..."`. The
+/// algorithm comes from the prefix or from `algorithm`, which must agree
+/// when both are given.
+fn expected_digest(
+    expected: &Bound<'_, PyAny>,
+    algorithm: Option<&str>,
+    usedforsecurity: bool,
+) -> PyResult<(Box<dyn DynDigest>, Vec<u8>)> {
+    let (name, digest) = match expected.downcast::<PyString>() {
+        Ok(text) => {
+            let text = text.to_str()?.trim();
+            let (prefix, hex) = match text.split_once(':') {
+                Some((prefix, hex)) => (Some(prefix), hex),
+                None => (None, text),
+            };
+            if let (Some(prefix), Some(algorithm)) = (prefix, algorithm) {
+                let same = match (registry::lookup(prefix), registry::lookup(algorithm)) {
+                    (Some(a), Some(b)) => a.name == b.name,
+                    _ => prefix.eq_ignore_ascii_case(algorithm),
+                };
+                if !same {
+                    return Err(PyValueError::new_err(format!(
+                        "expected digest is for {}, not {}",
+                        prefix, algorithm
+                    )));
+                }
+            }
+            let digest = from_hex(hex).ok_or_else(|| {
+                PyValueError::new_err("expected digest is not a hexadecimal string")
+            })?;
+            (prefix.or(algorithm), digest)
         }
-        PySHA512 { hasher }
+        Err(_) => (algorithm, expected.extract::<BytesArg>()?.0),
+    };
+    let name = name.ok_or_else(|| {
+        PyValueError::new_err(
+            "cannot determine the hash algorithm: pass algorithm= or an expected digest \
+             such as 'sha256:<hex>'",
+        )
+    })?;
+    let hasher = named_hasher(name, usedforsecurity)?;
+    if digest.len() != hasher.dyn_digest_size() {
+        return Err(PyValueError::new_err(format!(
+            "expected digest has {} bytes, but {} digests have {}",
+            digest.len(),
+            name,
+            hasher.dyn_digest_size()
+        )));
     }
+    Ok((hasher, digest))
+}
 
-    /// Updates the hash with additional data.
-    fn update(&mut self, data: &[u8]) {
-        self.hasher.update(data);
-    }
+/// Returns whether the file at `path` hashes to `expected`, comparing in
+/// constant time.
+///
+/// `expected` is raw digest bytes, a hex string of either case, or
+/// `"<algorithm>:<hex>"`, from which the algorithm is taken when
+/// `algorithm` is `None`. The file is read as by [`hash_file`], with the
+/// GIL released.
+///
+/// # Errors
+/// Returns `ValueError` if the algorithm cannot be determined, the prefix
+/// and `algorithm` disagree, or `expected` is not valid hex or not a digest
+/// of that algorithm's size, and otherwise the same errors as
+/// [`hash_file`].
+#[pyfunction]
+#[pyo3(signature = (
+    path, expected, algorithm=None, *, chunk_size=1 << 20, usedforsecurity=true
+))]
+pub fn verify_file(
+    py: Python,
+    path: &Bound<'_, PyAny>,
+    expected: &Bound<'_, PyAny>,
+    algorithm: Option<&str>,
+    chunk_size: usize,
+    usedforsecurity: bool,
+) -> PyResult<bool> {
+    let (mut hasher, expected) = expected_digest(expected, algorithm, usedforsecurity)?;
+    check_chunk_size(chunk_size)?;
+    let file_path = fs_path(path)?;
+    let result = py
+        .allow_threads(|| {
+            let file = std::fs::File::open(&file_path)?;
+            hash_reader(hasher.as_mut(), file, chunk_size)?;
+            Ok(hasher.dyn_digest())
+        })
+        .map_err(|err: FileError| err.into_pyerr(path))?;
+    Ok(constant_time_eq(&result, &expected))
+}
 
-    /// Returns the digest as bytes.
-    fn digest(&mut self, py: Python) -> PyResult<PyObject> {
-        let result = self.hasher.finalize();
-        Ok(PyBytes::new_bound(py, &result).into())
-    }
+/// Returns whether `data` hashes to `expected`, comparing in constant time.
+///
+/// Accepts the same `expected` values as [`verify_file`]; `data` is any
+/// C-contiguous buffer, hashed with the GIL released when large.
+///
+/// # Errors
+/// Same as [`verify_file`], with `BufferError` for a non-contiguous buffer
+/// instead of the file errors.
+#[pyfunction]
+#[pyo3(signature = (data, expected, algorithm=None, *, usedforsecurity=true))]
+pub fn verify_bytes(
+    py: Python,
+    data: &Bound<'_, PyAny>,
+    expected: &Bound<'_, PyAny>,
+    algorithm: Option<&str>,
+    usedforsecurity: bool,
+) -> PyResult<bool> {
+    let (mut hasher, expected) = expected_digest(expected, algorithm, usedforsecurity)?;
+    feed_buffer(py, data, false, |bytes| hasher.dyn_try_update(bytes))?;
+    Ok(constant_time_eq(&hasher.dyn_digest(), &expected))
+}
+
+/// Algorithms of the coreutils `*sum` tools by digest size, used to read a
+/// checksum line when no algorithm is given.
+const SUM_ALGORITHMS: [(usize, &str); 5] =
+    [(16, "md5"), (20, "sha1"), (28, "sha224"), (32, "sha256"), (64, "sha512")];
 
-    /// Returns the digest as a hexadecimal string.
-    fn hexdigest(&mut self) -> String {
-        self.hasher.finalize_hex()
+/// Tags of the BSD checksum lines written by `sha256sum --tag` and
+/// `shasum --tag`; other algorithms are tagged with their uppercase name.
+const BSD_TAGS: [(&str, &str); 8] = [
+    ("md5", "MD5"),
+    ("sha1", "SHA1"),
+    ("sha224", "SHA224"),
+    ("sha256", "SHA256"),
+    ("sha512", "SHA512"),
+    ("sha512_224", "SHA512/224"),
+    ("blake2b", "BLAKE2b"),
+    ("sm3", "SM3"),
+];
+
+/// Returns the canonical name and a fresh hasher for the algorithm tag of
+/// a BSD checksum line, such as `SHA256`, `SHA2-256`, `SHA512/256` or
+/// `BLAKE2b-256`, or `None` if the tag names no fixed-size algorithm of
+/// this module.
+///
+/// # Errors
+/// Returns `ValueError` if the algorithm is refused in strict mode.
+fn tag_hasher(
+    tag: &str,
+    usedforsecurity: bool,
+) -> PyResult<Option<(String, Box<dyn DynDigest>)>> {
+    if let Some(algo) = registry::lookup(tag) {
+        return match algo.hasher {
+            Some(hasher) => {
+                check_approved(algo.name, algo.approved, usedforsecurity)?;
+                Ok(Some((algo.name.to_owned(), hasher())))
+            }
+            None => Ok(None),
+        };
+    }
+    let lower = tag.to_ascii_lowercase();
+    let blake2b = lower
+        .strip_prefix("blake2b-")
+        .and_then(|bits| bits.parse::<usize>().ok())
+        .filter(|bits| bits % 8 == 0)
+        .and_then(|bits| Blake2b::with_params(&Blake2Params::new(bits / 8)).ok());
+    if let Some(hasher) = blake2b {
+        check_approved("BLAKE2b", PyBLAKE2b::APPROVED, usedforsecurity)?;
+        return Ok(Some((lower, Box::new(hasher))));
+    }
+    let sha512_t = registry::lookup_family(&lower)
+        .filter(|(family, _)| family.pattern == "sha512/<t>")
+        .and_then(|(_, t)| Sha512T::new(t).ok());
+    if let Some(hasher) = sha512_t {
+        check_approved("SHA512_t", PySHA512T::APPROVED, usedforsecurity)?;
+        return Ok(Some((format!("sha512_{}", hasher.t()), Box::new(hasher))));
     }
+    Ok(None)
+}
 
-    /// Creates a copy of the current hasher state.
-    fn copy(&self) -> Self {
-        PySHA512 {
-            hasher: Sha512::new(),
+/// Writes a checksum file in the format of `sha256sum`, one
+/// `<hex digest> *<name>` line per entry (`<hex digest>  <name>` with
+/// `binary=False`), or with `format="bsd"` one `<TAG> (<name>) = <hex
+/// digest>` line, as `sha256sum --tag` and `shasum --tag` write them.
+///
+/// Each entry is a path, hashed with `algorithm` as by [`hash_file`] and
+/// written as given, or a `(name, digest)` pair whose digest is bytes or a
+/// hex string. Names holding a backslash, newline or carriage return are
+/// escaped as coreutils does. `path_or_file` is a path, which is replaced,
+/// or an object with `write()`, given `bytes` unless it is an
+/// `io.TextIOBase`.
+///
+/// # Errors
+/// Returns `ValueError` for an unknown or extendable-output algorithm, one
+/// refused in strict mode, a `format` other than `"gnu"` and `"bsd"`,
+/// `binary=False` with the BSD format, which has no text mode, or a pair
+/// whose digest is not a hex string or bytes of the digest size, and the
+/// `OSError` subclass `open()` would raise when an entry cannot be read or
+/// the file cannot be written.
+#[pyfunction]
+#[pyo3(signature = (
+    path_or_file, entries, algorithm="sha256", binary=true, *, format="gnu",
+    usedforsecurity=true
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn write_checksum_file(
+    py: Python,
+    path_or_file: &Bound<'_, PyAny>,
+    entries: &Bound<'_, PyAny>,
+    algorithm: &str,
+    binary: bool,
+    format: &str,
+    usedforsecurity: bool,
+) -> PyResult<()> {
+    let digest_size = named_hasher(algorithm, usedforsecurity)?.dyn_digest_size();
+    let tag = match format {
+        "gnu" => None,
+        "bsd" if !binary => {
+            return Err(PyValueError::new_err("the BSD format has no text mode"));
+        }
+        "bsd" => {
+            let name = registry::lookup(algorithm).map_or(algorithm, |algo| algo.name);
+            Some(match BSD_TAGS.iter().find(|(known, _)| *known == name) {
+                Some((_, tag)) => (*tag).to_owned(),
+                None => name.to_ascii_uppercase().replace('_', "-"),
+            })
         }
+        _ => return Err(PyValueError::new_err("format must be 'gnu' or 'bsd'")),
+    };
+    let os = py.import("os")?;
+    let mut out = Vec::new();
+    for entry in entries.try_iter()? {
+        let entry = entry?;
+        let (name, digest) = match entry.downcast::<PyTuple>() {
+            Ok(pair) => {
+                let (name, digest): (Bound<'_, PyAny>, Bound<'_, PyAny>) = pair.extract()?;
+                let digest = match digest.downcast::<PyString>() {
+                    Ok(hex) => from_hex(hex.to_str()?).ok_or_else(|| {
+                        PyValueError::new_err("digest is not a hexadecimal string")
+                    })?,
+                    Err(_) => digest.extract::<BytesArg>()?.0,
+                };
+                if digest.len() != digest_size {
+                    return Err(PyValueError::new_err(format!(
+                        "digest has {} bytes, but {} digests have {}",
+                        digest.len(),
+                        algorithm,
+                        digest_size
+                    )));
+                }
+                (name, digest)
+            }
+            Err(_) => {
+                let mut hasher = named_hasher(algorithm, usedforsecurity)?;
+                let file_path = fs_path(&entry)?;
+                let digest = py
+                    .allow_threads(|| {
+                        let file = std::fs::File::open(&file_path)?;
+                        hash_reader(hasher.as_mut(), file, 1 << 20)?;
+                        Ok(hasher.dyn_digest())
+                    })
+                    .map_err(|err: FileError| err.into_pyerr(&entry))?;
+                (entry, digest)
+            }
+        };
+        let name: Vec<u8> = os.call_method1("fsencode", (name,))?.extract()?;
+        out.extend(match &tag {
+            Some(tag) => checksum::format_tagged_line(tag, &digest, &name),
+            None => checksum::format_line(&digest, &name, binary),
+        });
     }
+    if path_or_file.hasattr(intern!(py, "write"))? {
+        let text_io = py.import("io")?.getattr("TextIOBase")?;
+        let data = if path_or_file.is_instance(&text_io)? {
+            os.call_method1("fsdecode", (PyBytes::new(py, &out),))?
+        } else {
+            PyBytes::new(py, &out).into_any()
+        };
+        path_or_file.call_method1(intern!(py, "write"), (data,))?;
+    } else {
+        let file_path = fs_path(path_or_file)?;
+        py.allow_threads(|| std::fs::write(&file_path, &out))
+            .map_err(|err| FileError::from(err).into_pyerr(path_or_file))?;
+    }
+    Ok(())
+}
+
+/// Outcome of [`check_checksum_file`], as `sha256sum -c` reports it.
+///
+/// True when every listed file was read and matched; improperly formatted
+/// lines only count in `malformed`, as they only cause a warning there.
+#[pyclass(name = "ChecksumResult", frozen)]
+pub struct PyChecksumResult {
+    ok: Vec<PyObject>,
+    failed: Vec<PyObject>,
+    missing: Vec<PyObject>,
+    malformed: Vec<usize>,
+}
 
+#[pymethods]
+impl PyChecksumResult {
+    /// Names of the files whose digest matched.
     #[getter]
-    fn digest_size(&self) -> usize {
-        Sha512::digest_size()
+    fn ok<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, &self.ok)
     }
 
+    /// Names of the files whose digest did not match.
     #[getter]
-    fn block_size(&self) -> usize {
-        Sha512::block_size()
+    fn failed<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, &self.failed)
     }
 
+    /// Names of the files that could not be opened or read.
     #[getter]
-    fn name(&self) -> &str {
-        "sha512"
+    fn missing<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, &self.missing)
+    }
+
+    /// Numbers, counted from 1, of the improperly formatted lines.
+    #[getter]
+    fn malformed<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(py, &self.malformed)
+    }
+
+    fn __bool__(&self) -> bool {
+        self.failed.is_empty() && self.missing.is_empty()
+    }
+
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        Ok(format!(
+            "ChecksumResult(ok={}, failed={}, missing={}, malformed={})",
+            self.ok(py)?.repr()?,
+            self.failed(py)?.repr()?,
+            self.missing(py)?.repr()?,
+            self.malformed(py)?.repr()?
+        ))
     }
 }
 
-/// Direct SHA-256 hashing function.
+/// Checks the files listed in a checksum file, as `sha256sum -c`.
 ///
-/// Convenience function similar to `hashlib.sha256()`.
+/// Reads both formats of [`write_checksum_file`], detected line by line,
+/// and the `<TAG>(<name>)= <hex digest>` lines of `openssl dgst`: escaped
+/// names, comment lines starting with `#`, CRLF line endings and the
+/// reversed `<hex digest> <name>` form are handled as coreutils does.
+/// Names are resolved against `base_dir` when given, the current directory
+/// otherwise.
+///
+/// The tag of a BSD line names its algorithm, which must then match
+/// `algorithm` when that is given. Other lines use `algorithm`, or without
+/// it the algorithm picked from their digest size among MD5, SHA-1,
+/// SHA-224, SHA-256 and SHA-512. Lines whose tag is unknown or disagrees,
+/// or whose digest has the wrong size, count as improperly formatted.
+///
+/// # Errors
+/// Returns `ValueError` if the file has no properly formatted line, or for
+/// an unknown or extendable-output algorithm or one refused in strict mode,
+/// and the `OSError` subclass `open()` would raise if the checksum file
+/// itself cannot be read.
 #[pyfunction]
-#[pyo3(name = "SHA256", signature = (data=None))]
-pub fn sha256_direct(data: Option<&[u8]>) -> String {
-    let mut hasher = Sha256::new();
-    if let Some(bytes) = data {
-        hasher.update(bytes);
+#[pyo3(signature = (path, base_dir=None, algorithm=None, *, usedforsecurity=true))]
+pub fn check_checksum_file(
+    py: Python,
+    path: &Bound<'_, PyAny>,
+    base_dir: Option<&Bound<'_, PyAny>>,
+    algorithm: Option<&str>,
+    usedforsecurity: bool,
+) -> PyResult<PyChecksumResult> {
+    // Canonical name, so that it can be compared with the tags
+    let algorithm = match algorithm {
+        Some(name) => {
+            named_hasher(name, usedforsecurity)?;
+            registry::lookup(name).map(|algo| algo.name)
+        }
+        None => None,
+    };
+    let file_path = fs_path(path)?;
+    let text = py
+        .allow_threads(|| std::fs::read(&file_path))
+        .map_err(|err| FileError::from(err).into_pyerr(path))?;
+    let base_dir = base_dir.map(fs_path).transpose()?;
+    let os = py.import("os")?;
+    let mut result = PyChecksumResult {
+        ok: Vec::new(),
+        failed: Vec::new(),
+        missing: Vec::new(),
+        malformed: Vec::new(),
+    };
+    for (number, entry) in checksum::parse(&text) {
+        let Some(entry) = entry else {
+            result.malformed.push(number);
+            continue;
+        };
+        let hasher = match &entry.tag {
+            Some(tag) => match tag_hasher(tag, usedforsecurity)? {
+                Some((name, hasher)) if algorithm.is_none_or(|expected| expected == name) => {
+                    Some(hasher)
+                }
+                _ => None,
+            },
+            None => {
+                let name = algorithm.or_else(|| {
+                    let size = entry.digest.len();
+                    SUM_ALGORITHMS.iter().find(|(s, _)| *s == size).map(|(_, name)| *name)
+                });
+                name.map(|name| named_hasher(name, usedforsecurity)).transpose()?
+            }
+        };
+        let hasher = hasher.filter(|hasher| hasher.dyn_digest_size() == entry.digest.len());
+        let Some(mut hasher) = hasher else {
+            result.malformed.push(number);
+            continue;
+        };
+        let file_name = os.call_method1("fsdecode", (PyBytes::new(py, &entry.name),))?;
+        let mut target = fs_path(&file_name)?;
+        if let Some(dir) = &base_dir {
+            target = dir.join(target);
+        }
+        let digest = py.allow_threads(|| {
+            let file = std::fs::File::open(&target)?;
+            hash_reader(hasher.as_mut(), file, 1 << 20)?;
+            Ok(hasher.dyn_digest())
+        });
+        match digest {
+            Ok(digest) if constant_time_eq(&digest, &entry.digest) => {
+                result.ok.push(file_name.unbind())
+            }
+            Ok(_) => result.failed.push(file_name.unbind()),
+            Err(FileError::Io(_)) => result.missing.push(file_name.unbind()),
+            Err(err) => return Err(err.into_pyerr(&file_name)),
+        }
+    }
+    if result.ok.is_empty() && result.failed.is_empty() && result.missing.is_empty() {
+        return Err(PyValueError::new_err(format!(
+            "{}: no properly formatted checksum lines found",
+            file_path.display()
+        )));
     }
-    hasher.finalize_hex()
+    Ok(result)
 }
 
-/// Direct SHA-512 hashing function.
+/// Hashes the contents of a binary file object, returning the hash object,
+/// as `hashlib.file_digest()`.
 ///
-/// Convenience function similar to `hashlib.sha512()`.
+/// `digest` is an algorithm name accepted by [`new`] or a callable, such as
+/// `RsHash.SHA256`, returning a fresh hash object. `io.BytesIO` objects are
+/// hashed in place through `getbuffer()`; other objects must be readable
+/// binary files, such as those of `open(..., "rb")` or
+/// `socket.makefile("rb")`, and are read with `readinto()` into one
+/// reusable `_bufsize`-byte buffer.
+///
+/// # Errors
+/// Returns `ValueError` if `fileobj` is not a file object in binary reading
+/// mode or `_bufsize` is 0, `BlockingIOError` if a non-blocking file has
+/// no data ready, and propagates errors from `digest` and the reads.
 #[pyfunction]
-#[pyo3(name = "SHA512", signature = (data=None))]
-pub fn sha512_direct(data: Option<&[u8]>) -> String {
-    let mut hasher = Sha512::new();
-    if let Some(bytes) = data {
-        hasher.update(bytes);
+#[pyo3(signature = (fileobj, digest, /, *, _bufsize=1 << 18))]
+pub fn file_digest<'py>(
+    py: Python<'py>,
+    fileobj: &Bound<'py, PyAny>,
+    digest: &Bound<'py, PyAny>,
+    _bufsize: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    let hasher = match digest.downcast::<PyString>() {
+        Ok(name) => registry::create(py, name.to_str()?, None, None)?,
+        Err(_) => digest.call0()?,
+    };
+    let update = intern!(py, "update");
+    if fileobj.hasattr(intern!(py, "getbuffer"))? {
+        hasher.call_method1(update, (fileobj.call_method0(intern!(py, "getbuffer"))?,))?;
+        return Ok(hasher);
+    }
+    let binary_readable = fileobj.hasattr(intern!(py, "readinto"))?
+        && fileobj.hasattr(intern!(py, "readable"))?
+        && fileobj.call_method0(intern!(py, "readable"))?.is_truthy()?;
+    if !binary_readable {
+        return Err(PyValueError::new_err(format!(
+            "'{}' is not a file-like object in binary reading mode.",
+            fileobj.repr()?
+        )));
+    }
+    if _bufsize == 0 {
+        return Err(PyValueError::new_err("_bufsize must be positive"));
+    }
+    let buf = PyByteArray::new_with(py, _bufsize, |_| Ok(()))?;
+    let view = PyMemoryView::from(&buf)?;
+    loop {
+        let size = fileobj.call_method1(intern!(py, "readinto"), (&buf,))?;
+        if size.is_none() {
+            return Err(PyBlockingIOError::new_err("I/O operation would block."));
+        }
+        let size: usize = size.extract()?;
+        if size == 0 {
+            return Ok(hasher);
+        }
+        let chunk = view.get_item(PySlice::new(py, 0, size as isize, 1))?;
+        hasher.call_method1(update, (chunk,))?;
     }
-    hasher.finalize_hex()
 }
 
 /// Creates a hash object by algorithm name.
 ///
-/// Compatible with `hashlib.new()`. Supports "sha256" and "sha512".
+/// Compatible with `hashlib.new()`. Accepts every algorithm in
+/// [`registry::ALGORITHMS`], by canonical name or by an alias such as
+/// "SHA-256" or "SHA2-512", and sized names from [`registry::FAMILIES`]
+/// such as "sha512/192" or "md6_128".
 ///
 /// # Arguments
-/// * `name` - Algorithm name (case-insensitive).
+/// * `name` - Algorithm name or alias (case-insensitive).
 /// * `data` - Optional initial data to hash.
+/// * `kwargs` - Extra keyword arguments forwarded to the algorithm's constructor.
 ///
 /// # Returns
-/// A hash object (SHA256 or SHA512).
+/// A hash object of the requested algorithm.
 ///
 /// # Errors
-/// Returns `ValueError` if the algorithm is unsupported.
+/// Returns `ValueError` if the algorithm is unsupported (listing the supported
+/// ones), and `TypeError` if the constructor does not accept one of the
+/// keyword arguments.
 #[pyfunction]
-#[pyo3(signature = (name, data=None))]
-pub fn new(name: &str, data: Option<&[u8]>) -> PyResult<PyObject> {
-    Python::with_gil(|py| {
-        match name.to_lowercase().as_str() {
-            "sha256" => {
-                let hasher = PySHA256::new(data);
-                Ok(Py::new(py, hasher)?.into_py(py))
-            }
-            "sha512" => {
-                let hasher = PySHA512::new(data);
-                Ok(Py::new(py, hasher)?.into_py(py))
-            }
-            _ => Err(pyo3::exceptions::PyValueError::new_err(
-                format!("Unsupported hash algorithm: {}", name)
-            )),
-        }
-    })
+#[pyo3(signature = (name, data=None, **kwargs))]
+pub fn new(
+    py: Python,
+    name: &str,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(registry::create(py, name, data, kwargs)?.unbind())
+}
+
+/// Creates a SHA-512/t hash object with a `t`-bit output.
+///
+/// Equivalent to `new("sha512/<t>", data)`.
+///
+/// # Errors
+/// Returns `ValueError` unless `t` is a multiple of 8 below 512 other than
+/// 384 (use SHA-384 for that size).
+#[pyfunction]
+#[pyo3(signature = (t, data=None, **kwargs))]
+pub fn sha512_t(
+    py: Python,
+    t: usize,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PySHA512T>().call((t, data), kwargs)?.unbind())
+}
+
+/// Creates a cSHAKE128 hash object.
+///
+/// Equivalent to `cSHAKE128(data, function_name=..., custom=...)`; the
+/// output length is given to `digest()`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn cshake128(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyCSHAKE128>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a cSHAKE256 hash object.
+///
+/// Equivalent to `cSHAKE256(data, function_name=..., custom=...)`; the
+/// output length is given to `digest()`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn cshake256(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyCSHAKE256>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2b hash object.
+///
+/// Equivalent to `hashlib.blake2b(data, digest_size=..., key=..., salt=...,
+/// person=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2b(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2b>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2s hash object.
+///
+/// Equivalent to `hashlib.blake2s(data, digest_size=..., key=..., salt=...,
+/// person=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2s(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2s>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2bp hash object.
+///
+/// Equivalent to `BLAKE2bp(data, digest_size=..., key=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2bp(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2bp>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2sp hash object.
+///
+/// Equivalent to `BLAKE2sp(data, digest_size=..., key=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2sp(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2sp>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2Xb hash object.
+///
+/// Equivalent to `BLAKE2Xb(data, digest_size=..., key=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2xb(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2Xb>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE2Xs hash object.
+///
+/// Equivalent to `BLAKE2Xs(data, digest_size=..., key=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake2xs(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE2Xs>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a BLAKE3 hash object.
+///
+/// Equivalent to `BLAKE3(data, key=..., derive_key_context=...)`; the
+/// output length is given to `digest()` and defaults to 32 bytes.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn blake3(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyBLAKE3>().call((data,), kwargs)?.unbind())
+}
+
+/// One-shot keyed BLAKE3 returning the 32-byte MAC as bytes.
+///
+/// Equivalent to `BLAKE3(data, key=key).digest()`, with the GIL released
+/// for large inputs. As a security use, it is refused in strict mode.
+#[pyfunction]
+pub fn blake3_keyed<'py>(
+    py: Python<'py>,
+    key: BytesArg,
+    data: &Bound<'_, PyAny>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    check_approved("BLAKE3", PyBLAKE3::APPROVED, true)?;
+    let mut hasher = blake3_hasher(Some(key), None, None)?;
+    feed_buffer(py, data, false, |bytes| hasher.try_update(bytes))?;
+    squeeze_bytes(py, hasher, BLAKE3_OUT_LEN)
+}
+
+/// One-shot BLAKE3 key derivation returning `length` bytes.
+///
+/// Derives a key for `context` from `key_material`; equivalent to
+/// `BLAKE3(key_material, derive_key_context=context).digest(length)`. As a
+/// security use, it is refused in strict mode.
+#[pyfunction]
+#[pyo3(signature = (context, key_material, length=BLAKE3_OUT_LEN))]
+pub fn blake3_derive_key<'py>(
+    py: Python<'py>,
+    context: String,
+    key_material: &Bound<'_, PyAny>,
+    length: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    check_approved("BLAKE3", PyBLAKE3::APPROVED, true)?;
+    let mut hasher = Blake3::new_derive_key(&context);
+    feed_buffer(py, key_material, false, |bytes| hasher.try_update(bytes))?;
+    squeeze_bytes(py, hasher, length)
+}
+
+/// Creates a Skein-256 hash object.
+///
+/// Equivalent to `Skein256(data, digest_size=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn skein256(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PySkein256>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a Skein-512 hash object.
+///
+/// Equivalent to `Skein512(data, digest_size=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn skein512(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PySkein512>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates a Skein-1024 hash object.
+///
+/// Equivalent to `Skein1024(data, digest_size=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn skein1024(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PySkein1024>().call((data,), kwargs)?.unbind())
+}
+
+/// Creates an MD6 hash object.
+///
+/// Equivalent to `MD6(data, digest_size=..., mode=...)`.
+#[pyfunction]
+#[pyo3(signature = (data=None, **kwargs))]
+pub fn md6(
+    py: Python,
+    data: Option<&Bound<'_, PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    Ok(py.get_type::<PyMD6>().call((data,), kwargs)?.unbind())
 }
@@ -0,0 +1,162 @@
+//! Zero-copy access to objects implementing the buffer protocol.
+//!
+//! Mirrors how hashlib reads its input: `str` is rejected and anything else
+//! must export a buffer. C-contiguous buffers are hashed in place without
+//! an intermediate copy; strided ones can be walked in logical order.
+
+use std::os::raw::c_char;
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::ffi;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+/// Size of the scratch buffer used to gather strided data.
+const GATHER_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Read-only byte view of a Python object.
+///
+/// The buffer stays exported for as long as the view is alive, so objects
+/// such as `bytearray` cannot be resized underneath it.
+pub struct ByteView<'py> {
+    // Boxed so the address handed to the exporter stays stable
+    view: Box<ffi::Py_buffer>,
+    _py: Python<'py>,
+}
+
+impl<'py> ByteView<'py> {
+    /// Acquires a buffer view of `obj`, including its shape and strides.
+    ///
+    /// # Errors
+    /// Returns `TypeError` for `str` and objects without buffer support, and
+    /// propagates the exporter's error if the buffer cannot be exported.
+    pub fn get(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if obj.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err("Strings must be encoded before hashing"));
+        }
+        // SAFETY: `obj` is a valid object and the GIL is held
+        if unsafe { ffi::PyObject_CheckBuffer(obj.as_ptr()) } == 0 {
+            return Err(PyTypeError::new_err("object supporting the buffer API required"));
+        }
+
+        // SAFETY: an all-zero Py_buffer is a valid "empty" value to be filled in
+        let mut view: Box<ffi::Py_buffer> = Box::new(unsafe { std::mem::zeroed() });
+        // SAFETY: `view` points to writable storage that outlives the export
+        let rc = unsafe { ffi::PyObject_GetBuffer(obj.as_ptr(), &mut *view, ffi::PyBUF_FULL_RO) };
+        if rc == -1 {
+            return Err(PyErr::fetch(obj.py()));
+        }
+        Ok(ByteView { view, _py: obj.py() })
+    }
+
+    /// Returns true if the bytes are laid out contiguously in C order.
+    pub fn is_contiguous(&self) -> bool {
+        // SAFETY: the view was filled in by a successful export
+        unsafe { ffi::PyBuffer_IsContiguous(&*self.view, b'C' as c_char) != 0 }
+    }
+
+    /// Returns the exported bytes, or `None` if the buffer is not C-contiguous.
+    pub fn as_slice(&self) -> Option<&[u8]> {
+        if !self.is_contiguous() {
+            return None;
+        }
+        if self.view.buf.is_null() || self.view.len <= 0 {
+            return Some(&[]);
+        }
+        // SAFETY: a C-contiguous export describes `len` readable bytes at
+        // `buf`, valid until the buffer is released in `drop`
+        Some(unsafe { std::slice::from_raw_parts(self.view.buf as *const u8, self.view.len as usize) })
+    }
+
+    /// Feeds the buffer's bytes to `f` in logical (C) order.
+    ///
+    /// Contiguous buffers are passed in a single call. Strided buffers are
+    /// gathered item by item into a bounded scratch buffer, so memory use
+    /// stays constant regardless of the buffer size.
+    pub fn for_each_chunk<E>(&self, mut f: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if let Some(bytes) = self.as_slice() {
+            return f(bytes);
+        }
+
+        let ndim = self.view.ndim as usize;
+        // SAFETY: a non-contiguous PyBUF_FULL_RO export always provides
+        // `ndim` entries of shape and strides, and optionally suboffsets
+        let (shape, strides, suboffsets) = unsafe {
+            (
+                std::slice::from_raw_parts(self.view.shape, ndim),
+                std::slice::from_raw_parts(self.view.strides, ndim),
+                (!self.view.suboffsets.is_null())
+                    .then(|| std::slice::from_raw_parts(self.view.suboffsets, ndim)),
+            )
+        };
+        if shape.contains(&0) {
+            return Ok(());
+        }
+
+        let itemsize = self.view.itemsize as usize;
+        let mut scratch = Vec::with_capacity(GATHER_CHUNK_SIZE + itemsize);
+        let mut index = vec![0isize; ndim];
+        loop {
+            // Resolve the address of the current item, following suboffsets
+            // for PIL-style indirect arrays
+            let mut ptr = self.view.buf as *const u8;
+            for dim in 0..ndim {
+                // SAFETY: the index is within `shape`, so the offset stays
+                // inside the exported memory
+                unsafe {
+                    ptr = ptr.offset(index[dim] * strides[dim]);
+                    if let Some(sub) = suboffsets.filter(|sub| sub[dim] >= 0) {
+                        ptr = (*(ptr as *const *const u8)).offset(sub[dim]);
+                    }
+                }
+            }
+            // SAFETY: each item spans `itemsize` readable bytes
+            scratch.extend_from_slice(unsafe { std::slice::from_raw_parts(ptr, itemsize) });
+            if scratch.len() >= GATHER_CHUNK_SIZE {
+                f(&scratch)?;
+                scratch.clear();
+            }
+
+            // Advance the index, last dimension fastest
+            let mut dim = ndim;
+            loop {
+                if dim == 0 {
+                    return if scratch.is_empty() { Ok(()) } else { f(&scratch) };
+                }
+                dim -= 1;
+                index[dim] += 1;
+                if index[dim] < shape[dim] {
+                    break;
+                }
+                index[dim] = 0;
+            }
+        }
+    }
+}
+
+impl Drop for ByteView<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the buffer was acquired in `get`, and the `'py` lifetime
+        // guarantees the GIL is still held
+        unsafe { ffi::PyBuffer_Release(&mut *self.view) }
+    }
+}
+
+/// Owned copy of a byte-string argument, such as a customization string.
+///
+/// Extracted like hashed data: any object supporting the buffer protocol
+/// is accepted, in logical order, and `str` is rejected.
+#[derive(Default)]
+pub struct BytesArg(pub Vec<u8>);
+
+impl<'py> FromPyObject<'py> for BytesArg {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let view = ByteView::get(obj)?;
+        let mut bytes = Vec::new();
+        view.for_each_chunk(|chunk| {
+            bytes.extend_from_slice(chunk);
+            Ok::<_, PyErr>(())
+        })?;
+        Ok(BytesArg(bytes))
+    }
+}
@@ -0,0 +1,964 @@
+//! Python bindings for the non-cryptographic hashes, exposed as the
+//! `RsHash.noncrypto` submodule.
+//!
+//! The objects follow the interface of the `xxhash` package: `update()`,
+//! `digest()`, `hexdigest()`, `intdigest()`, `copy()` and `reset()`, with
+//! algorithm parameters such as the seed accepted positionally after the
+//! data. Checksums are never used for security, so they take no
+//! `usedforsecurity` argument and stay available in FIPS mode.
+//!
+//! # Classes
+//!
+//! - [`PyXxh32`], [`PyXxh64`] - xxHash objects
+//! - [`PyXxh3_64`], [`PyXxh3_128`] - XXH3 objects
+//! - [`PyAdler32`] - Adler-32 objects
+//! - [`PyCrc16`] - CRC-16 objects (CCITT-FALSE, MODBUS, XMODEM and USB)
+//! - [`PyCrc32`], [`PyCrc32c`] - CRC-32 objects
+//! - [`PyCrc64`] - CRC-64 objects (XZ and ECMA-182 variants)
+//! - [`PyFletcher16`], [`PyFletcher32`], [`PyFletcher64`], [`PyFletcher4`] -
+//!   Fletcher objects, the last one the 256-bit ZFS variant
+//! - [`PyFnv32_1`], [`PyFnv32_1a`] and the 64- and 128-bit equivalents -
+//!   FNV objects
+//! - [`PyHighwayHash64`], [`PyHighwayHash128`], [`PyHighwayHash256`] -
+//!   HighwayHash objects (keyed)
+//! - [`PyKomihash`] - komihash streaming objects
+//! - [`PyMetroHash64`], [`PyMetroHash128`] - MetroHash objects
+//! - [`PyMurmur3_32`], [`PyMurmur3_128`] - MurmurHash3 objects
+//! - [`PySipHash24`], [`PySipHash13`] - SipHash objects (keyed)
+//! - [`PySpookyHash32`], [`PySpookyHash64`], [`PySpookyHash128`] -
+//!   SpookyHash V2 objects
+//!
+//! # Functions
+//!
+//! - `crc32_digest()`, `crc32_hexdigest()`, `crc32_intdigest()` and the
+//!   same for `adler32`, `crc16`, `crc32c`, `crc64`, `fletcher16`,
+//!   `fletcher32`, `fletcher64`, `fletcher4`, `fnv1_32`, `fnv1a_32` (and 64/128),
+//!   `metrohash64`, `metrohash128`, `murmur3_32`, `murmur3_128`,
+//!   `spookyhash32`, `spookyhash64` and `spookyhash128` - one-shot
+//!   checksums
+//! - `farmhash_fingerprint64()`, `cityhash64()` - one-shot 64-bit hashes,
+//!   unsigned or signed
+//! - `komihash()`, `wyhash()`, `rapidhash()` - one-shot 64-bit hashes,
+//!   seeded
+//! - `siphash24_digest()`, `siphash24_hexdigest()`, `siphash24_intdigest()`
+//!   and the same for `siphash13`, `highwayhash64`, `highwayhash128` and
+//!   `highwayhash256` - one-shot keyed hashes, taking the key first
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyInt, PyString, PyTuple, PyType};
+
+use super::buffer::{ByteView, BytesArg};
+use super::{
+    LockedState, check_extra_args, copy_object, feed_buffer, feed_item, feed_view, hex_string, lock,
+};
+use crate::core::ParameterError;
+use crate::noncrypto::{
+    Adler32, Checksum, Crc16, Crc16Params, Crc32, Crc32c, Crc64, Crc64Variant, Fletcher4,
+    Fletcher16, Fletcher32, Fletcher64, Fnv32, Fnv64, Fnv128, FnvVariant, HighwayHash64,
+    HighwayHash128, HighwayHash256, Komihash, MetroHash64, MetroHash128, Murmur3_32, Murmur3_128,
+    SipHash13, SipHash24, SpookyHash32, SpookyHash64, SpookyHash128, U256, WYHASH_SECRET, Xxh3_64,
+    Xxh3_128, Xxh32, Xxh64, cityhash64, cityhash64_with_seed, fingerprint64, komihash, rapidhash,
+    wyhash_secret, wyhash_with_secret,
+};
+
+/// Defines a Python class around a [`Checksum`].
+///
+/// Constructor parameters after `data` are listed with their default
+/// values and may be passed positionally, as in the `xxhash` package.
+/// Required parameters, such as a key, are listed in brackets after `new`
+/// and come before `data`. An optional `methods { ... }` block adds
+/// algorithm-specific methods.
+/// Unlike [`hash_class!`], nothing is cached: computing a checksum value is
+/// cheaper than the bookkeeping.
+macro_rules! checksum_class {
+    (
+        $(#[$doc:meta])* $class:ident, $pyname:literal, $hasher:ty,
+        new $([$($arg:ident: $aty:ty),*])? ($($kw:ident: $kty:ty = $kdefault:expr),*) => $ctor:expr
+        $(, methods { $($methods:tt)* })?
+    ) => {
+        $(#[$doc])*
+        #[pyclass(name = $pyname, module = "RsHash.noncrypto", subclass, frozen)]
+        pub struct $class {
+            state: Mutex<$hasher>,
+        }
+
+        impl $class {
+            /// Locks the streaming state (see [`lock`]).
+            fn lock(&self, py: Python) -> MutexGuard<'_, $hasher> {
+                lock(py, &self.state)
+            }
+        }
+
+        impl LockedState for $class {
+            fn snapshot(&self, py: Python) -> Self {
+                $class { state: Mutex::new(self.lock(py).clone()) }
+            }
+
+            fn replace_with(&self, py: Python, other: Self) {
+                *self.lock(py) = other.state.into_inner().unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+
+        #[pymethods]
+        impl $class {
+            /// Creates a new checksum object, optionally with initial data.
+            ///
+            /// `None` is treated as no initial data. Set `copy` to accept
+            /// non-contiguous buffers (see `update()`). Python subclasses may
+            /// pass additional arguments for their `__init__`.
+            #[new]
+            #[classmethod]
+            #[pyo3(signature = (
+                $($($arg,)*)? data=None, $($kw=$kdefault,)* *args, copy=false, **kwargs
+            ))]
+            #[allow(clippy::too_many_arguments)]
+            fn new(
+                cls: &Bound<'_, PyType>,
+                py: Python,
+                $($($arg: $aty,)*)?
+                data: Option<&Bound<'_, PyAny>>,
+                $($kw: $kty,)*
+                args: &Bound<'_, PyTuple>,
+                copy: bool,
+                kwargs: Option<&Bound<'_, PyDict>>,
+            ) -> PyResult<Self> {
+                check_extra_args::<Self>(cls, args, kwargs)?;
+                let mut hasher: $hasher = $ctor?;
+                if let Some(obj) = data {
+                    feed_buffer(py, obj, copy, |bytes| {
+                        hasher.update(bytes);
+                        Ok(())
+                    })?;
+                }
+                Ok($class { state: Mutex::new(hasher) })
+            }
+
+            /// Does nothing; the object is fully set up by the constructor.
+            #[pyo3(signature = (*_args, **_kwargs))]
+            fn __init__(&self, _args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>) {}
+
+            /// Updates the checksum with additional data.
+            ///
+            /// Accepts any object supporting the buffer protocol, with the
+            /// GIL released for large inputs. Non-contiguous buffers raise
+            /// `BufferError` unless `copy=True`.
+            #[pyo3(signature = (data, *, copy=false))]
+            fn update(&self, py: Python, data: &Bound<'_, PyAny>, copy: bool) -> PyResult<()> {
+                let view = ByteView::get(data)?;
+                let mut state = self.lock(py);
+                let hasher = &mut *state;
+                feed_view(py, &view, copy, |bytes| {
+                    hasher.update(bytes);
+                    Ok(())
+                })
+            }
+
+            /// Returns the checksum as bytes, in the algorithm's canonical
+            /// byte order.
+            fn digest<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+                let result = self.lock(py).digest();
+                PyBytes::new(py, result.as_ref())
+            }
+
+            /// Returns the checksum as a hexadecimal string.
+            fn hexdigest<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+                let result = self.lock(py).digest();
+                hex_string(py, result.as_ref())
+            }
+
+            /// Returns the checksum as an unsigned integer.
+            fn intdigest(&self, py: Python) -> <$hasher as Checksum>::Value {
+                self.lock(py).intdigest()
+            }
+
+            /// Resets the object to its initial state, keeping its
+            /// parameters.
+            fn reset(&self, py: Python) {
+                self.lock(py).reset();
+            }
+
+            /// Creates a copy of the current state.
+            fn copy<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, None)
+            }
+
+            /// Supports `copy.copy()` by cloning the streaming state.
+            fn __copy__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, None)
+            }
+
+            /// Supports `copy.deepcopy()`; subclass attributes are deep-copied.
+            fn __deepcopy__<'py>(
+                slf: &Bound<'py, Self>,
+                memo: &Bound<'py, PyAny>,
+            ) -> PyResult<Bound<'py, Self>> {
+                copy_object(slf, Some(memo))
+            }
+
+            #[getter]
+            fn digest_size(&self, py: Python) -> usize {
+                self.lock(py).digest_size()
+            }
+
+            #[getter]
+            fn block_size(&self, py: Python) -> usize {
+                self.lock(py).block_size()
+            }
+
+            #[getter]
+            fn name(&self) -> &'static str {
+                $pyname
+            }
+
+            $($($methods)*)?
+        }
+    };
+}
+
+/// Defines the one-shot functions of a checksum, named as in the `xxhash`
+/// package (`<name>_digest()`, `<name>_hexdigest()`, `<name>_intdigest()`)
+/// and taking the same parameters as the class.
+macro_rules! checksum_functions {
+    (
+        $hasher:ty, $digest:ident, $hexdigest:ident, $intdigest:ident,
+        $([$($arg:ident: $aty:ty),*])? ($($kw:ident: $kty:ty = $kdefault:expr),*) => $ctor:expr
+    ) => {
+        /// One-shot checksum returning the value as bytes.
+        #[pyfunction]
+        #[pyo3(signature = ($($($arg,)*)? data, $($kw=$kdefault),*))]
+        pub fn $digest<'py>(
+            py: Python<'py>,
+            $($($arg: $aty,)*)?
+            data: &Bound<'_, PyAny>,
+            $($kw: $kty),*
+        ) -> PyResult<Bound<'py, PyBytes>> {
+            let hasher: $hasher = oneshot(py, data, $ctor?)?;
+            Ok(PyBytes::new(py, hasher.digest().as_ref()))
+        }
+
+        /// One-shot checksum returning the value as a hexadecimal string.
+        #[pyfunction]
+        #[pyo3(signature = ($($($arg,)*)? data, $($kw=$kdefault),*))]
+        pub fn $hexdigest<'py>(
+            py: Python<'py>,
+            $($($arg: $aty,)*)?
+            data: &Bound<'_, PyAny>,
+            $($kw: $kty),*
+        ) -> PyResult<Bound<'py, PyString>> {
+            let hasher: $hasher = oneshot(py, data, $ctor?)?;
+            Ok(hex_string(py, hasher.digest().as_ref()))
+        }
+
+        /// One-shot checksum returning the value as an unsigned integer.
+        #[pyfunction]
+        #[pyo3(signature = ($($($arg,)*)? data, $($kw=$kdefault),*))]
+        pub fn $intdigest(
+            py: Python,
+            $($($arg: $aty,)*)?
+            data: &Bound<'_, PyAny>,
+            $($kw: $kty),*
+        ) -> PyResult<<$hasher as Checksum>::Value> {
+            let hasher: $hasher = oneshot(py, data, $ctor?)?;
+            Ok(hasher.intdigest())
+        }
+    };
+}
+
+/// Feeds `data` to a fresh checksum, releasing the GIL for large inputs.
+fn oneshot<H: Checksum>(py: Python, data: &Bound<'_, PyAny>, mut hasher: H) -> PyResult<H> {
+    feed_buffer(py, data, false, |bytes| {
+        hasher.update(bytes);
+        Ok(())
+    })?;
+    Ok(hasher)
+}
+
+checksum_class!(
+    /// Python wrapper for xxHash32.
+    ///
+    /// Compatible with `xxhash.xxh32()`, and with the content checksums of
+    /// LZ4 frames: `digest()` is big-endian and `seed` is any 32-bit value.
+    PyXxh32, "xxh32", Xxh32,
+    new(seed: u32 = 0) => Ok::<_, PyErr>(Xxh32::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for xxHash64.
+    ///
+    /// Compatible with `xxhash.xxh64()`: `digest()` is big-endian, as in the
+    /// reference canonical representation, and `seed` is any 64-bit value.
+    PyXxh64, "xxh64", Xxh64,
+    new(seed: u64 = 0) => Ok::<_, PyErr>(Xxh64::new(seed))
+);
+
+/// Builds an XXH3 hasher from a seed or a custom secret, which are
+/// mutually exclusive as in the reference `_withSeed` and `_withSecret`
+/// variants.
+fn xxh3<H>(
+    seed: u64,
+    secret: Option<BytesArg>,
+    new: fn(u64) -> H,
+    with_secret: fn(&[u8]) -> Result<H, ParameterError>,
+) -> PyResult<H> {
+    match secret {
+        None => Ok(new(seed)),
+        Some(_) if seed != 0 => Err(PyValueError::new_err("seed and secret cannot be combined")),
+        Some(secret) => Ok(with_secret(&secret.0)?),
+    }
+}
+
+checksum_class!(
+    /// Python wrapper for XXH3-64.
+    ///
+    /// Compatible with `xxhash.xxh3_64()`. `secret` replaces the default
+    /// secret with at least 136 bytes of high-entropy data.
+    PyXxh3_64, "xxh3_64", Xxh3_64,
+    new(seed: u64 = 0, secret: Option<BytesArg> = None) =>
+        xxh3(seed, secret, Xxh3_64::new, Xxh3_64::with_secret)
+);
+
+checksum_class!(
+    /// Python wrapper for XXH3-128.
+    ///
+    /// Compatible with `xxhash.xxh3_128()`: `intdigest()` is the full
+    /// 128-bit value and `digest()` its 16 big-endian bytes.
+    PyXxh3_128, "xxh3_128", Xxh3_128,
+    new(seed: u64 = 0, secret: Option<BytesArg> = None) =>
+        xxh3(seed, secret, Xxh3_128::new, Xxh3_128::with_secret)
+);
+
+checksum_class!(
+    /// Python wrapper for Adler-32.
+    ///
+    /// `intdigest()` equals `zlib.adler32()`, and `initial` plays the role
+    /// of its `value` argument: the checksum of preceding data, 1 for none.
+    PyAdler32, "adler32", Adler32,
+    new(initial: u32 = 1) => Ok::<_, PyErr>(Adler32::new(initial)),
+    methods {
+        /// Returns the checksum of two consecutive segments from their
+        /// checksums and the length of the second one, without rehashing
+        /// (zlib's `adler32_combine()`).
+        #[classmethod]
+        fn combine(_cls: &Bound<'_, PyType>, first: u32, other: u32, length: u64) -> u32 {
+            Adler32::combine(first, other, length)
+        }
+    }
+);
+
+checksum_functions!(
+    Adler32, adler32_digest, adler32_hexdigest, adler32_intdigest,
+    (initial: u32 = 1) => Ok::<_, PyErr>(Adler32::new(initial))
+);
+
+checksum_class!(
+    /// Python wrapper for CRC-16.
+    ///
+    /// `variant` names a preset: "ccitt-false", "modbus", "xmodem" or
+    /// "usb". `digest()` is big-endian even for MODBUS, whose frames carry
+    /// the low byte first.
+    PyCrc16, "crc16", Crc16,
+    new(variant: &str = "modbus") => variant.parse::<Crc16Params>().map(Crc16::new)
+);
+
+checksum_functions!(
+    Crc16, crc16_digest, crc16_hexdigest, crc16_intdigest,
+    (variant: &str = "modbus") => variant.parse::<Crc16Params>().map(Crc16::new)
+);
+
+checksum_class!(
+    /// Python wrapper for CRC-32.
+    ///
+    /// `intdigest()` equals `zlib.crc32()` and `binascii.crc32()`, and
+    /// `initial` plays the role of their `value` argument: the checksum of
+    /// preceding data.
+    PyCrc32, "crc32", Crc32,
+    new(initial: u32 = 0) => Ok::<_, PyErr>(Crc32::new(initial))
+);
+
+checksum_functions!(
+    Crc32, crc32_digest, crc32_hexdigest, crc32_intdigest,
+    (initial: u32 = 0) => Ok::<_, PyErr>(Crc32::new(initial))
+);
+
+checksum_class!(
+    /// Python wrapper for CRC-32C.
+    ///
+    /// `digest()` is big-endian, as in `google_crc32c` and the base64
+    /// checksums of cloud storage objects.
+    PyCrc32c, "crc32c", Crc32c,
+    new(initial: u32 = 0) => Ok::<_, PyErr>(Crc32c::new(initial))
+);
+
+checksum_functions!(
+    Crc32c, crc32c_digest, crc32c_hexdigest, crc32c_intdigest,
+    (initial: u32 = 0) => Ok::<_, PyErr>(Crc32c::new(initial))
+);
+
+checksum_class!(
+    /// Python wrapper for CRC-64.
+    ///
+    /// `variant` is "xz" (the check stored in `.xz` files, shown by
+    /// `xz --list -vv`) or "ecma-182"; `initial` resumes from the checksum
+    /// of preceding data.
+    PyCrc64, "crc64", Crc64,
+    new(variant: &str = "xz", initial: u64 = 0) =>
+        variant.parse::<Crc64Variant>().map(|variant| Crc64::new(variant, initial))
+);
+
+checksum_functions!(
+    Crc64, crc64_digest, crc64_hexdigest, crc64_intdigest,
+    (variant: &str = "xz", initial: u64 = 0) =>
+        variant.parse::<Crc64Variant>().map(|variant| Crc64::new(variant, initial))
+);
+
+checksum_class!(
+    /// Python wrapper for Fletcher-16 (bytes, modulo 255).
+    PyFletcher16, "fletcher16", Fletcher16,
+    new() => Ok::<_, PyErr>(Fletcher16::new())
+);
+
+checksum_functions!(
+    Fletcher16, fletcher16_digest, fletcher16_hexdigest, fletcher16_intdigest,
+    () => Ok::<_, PyErr>(Fletcher16::new())
+);
+
+checksum_class!(
+    /// Python wrapper for Fletcher-32 (16-bit little-endian words, modulo 65535).
+    PyFletcher32, "fletcher32", Fletcher32,
+    new() => Ok::<_, PyErr>(Fletcher32::new())
+);
+
+checksum_functions!(
+    Fletcher32, fletcher32_digest, fletcher32_hexdigest, fletcher32_intdigest,
+    () => Ok::<_, PyErr>(Fletcher32::new())
+);
+
+checksum_class!(
+    /// Python wrapper for Fletcher-64 (32-bit little-endian words, modulo 2^32 - 1).
+    PyFletcher64, "fletcher64", Fletcher64,
+    new() => Ok::<_, PyErr>(Fletcher64::new())
+);
+
+checksum_functions!(
+    Fletcher64, fletcher64_digest, fletcher64_hexdigest, fletcher64_intdigest,
+    () => Ok::<_, PyErr>(Fletcher64::new())
+);
+
+checksum_class!(
+    /// Python wrapper for ZFS fletcher4 (four wrapping 64-bit sums of 32-bit
+    /// little-endian words, 32 bytes).
+    PyFletcher4, "fletcher4", Fletcher4,
+    new() => Ok::<_, PyErr>(Fletcher4::new())
+);
+
+checksum_functions!(
+    Fletcher4, fletcher4_digest, fletcher4_hexdigest, fletcher4_intdigest,
+    () => Ok::<_, PyErr>(Fletcher4::new())
+);
+
+checksum_class!(
+    /// Python wrapper for 32-bit FNV-1.
+    PyFnv32_1, "fnv1_32", Fnv32,
+    new() => Ok::<_, PyErr>(Fnv32::new(FnvVariant::Fnv1))
+);
+
+checksum_functions!(
+    Fnv32, fnv1_32_digest, fnv1_32_hexdigest, fnv1_32_intdigest,
+    () => Ok::<_, PyErr>(Fnv32::new(FnvVariant::Fnv1))
+);
+
+checksum_class!(
+    /// Python wrapper for 32-bit FNV-1a.
+    PyFnv32_1a, "fnv1a_32", Fnv32,
+    new() => Ok::<_, PyErr>(Fnv32::new(FnvVariant::Fnv1a))
+);
+
+checksum_functions!(
+    Fnv32, fnv1a_32_digest, fnv1a_32_hexdigest, fnv1a_32_intdigest,
+    () => Ok::<_, PyErr>(Fnv32::new(FnvVariant::Fnv1a))
+);
+
+checksum_class!(
+    /// Python wrapper for 64-bit FNV-1.
+    PyFnv64_1, "fnv1_64", Fnv64,
+    new() => Ok::<_, PyErr>(Fnv64::new(FnvVariant::Fnv1))
+);
+
+checksum_functions!(
+    Fnv64, fnv1_64_digest, fnv1_64_hexdigest, fnv1_64_intdigest,
+    () => Ok::<_, PyErr>(Fnv64::new(FnvVariant::Fnv1))
+);
+
+checksum_class!(
+    /// Python wrapper for 64-bit FNV-1a.
+    PyFnv64_1a, "fnv1a_64", Fnv64,
+    new() => Ok::<_, PyErr>(Fnv64::new(FnvVariant::Fnv1a))
+);
+
+checksum_functions!(
+    Fnv64, fnv1a_64_digest, fnv1a_64_hexdigest, fnv1a_64_intdigest,
+    () => Ok::<_, PyErr>(Fnv64::new(FnvVariant::Fnv1a))
+);
+
+checksum_class!(
+    /// Python wrapper for 128-bit FNV-1.
+    PyFnv128_1, "fnv1_128", Fnv128,
+    new() => Ok::<_, PyErr>(Fnv128::new(FnvVariant::Fnv1))
+);
+
+checksum_functions!(
+    Fnv128, fnv1_128_digest, fnv1_128_hexdigest, fnv1_128_intdigest,
+    () => Ok::<_, PyErr>(Fnv128::new(FnvVariant::Fnv1))
+);
+
+checksum_class!(
+    /// Python wrapper for 128-bit FNV-1a.
+    PyFnv128_1a, "fnv1a_128", Fnv128,
+    new() => Ok::<_, PyErr>(Fnv128::new(FnvVariant::Fnv1a))
+);
+
+checksum_functions!(
+    Fnv128, fnv1a_128_digest, fnv1a_128_hexdigest, fnv1a_128_intdigest,
+    () => Ok::<_, PyErr>(Fnv128::new(FnvVariant::Fnv1a))
+);
+
+checksum_class!(
+    /// Python wrapper for streaming komihash.
+    ///
+    /// Matches the reference `komihash_stream_*()` functions, and so the
+    /// one-shot `komihash()` for the same bytes and seed. `digest()` is
+    /// big-endian.
+    PyKomihash, "komihash_stream", Komihash,
+    new(seed: u64 = 0) => Ok::<_, PyErr>(Komihash::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for MetroHash64.
+    ///
+    /// Matches the `MetroHash64` class of the reference implementation
+    /// (v1.1), with a 64-bit seed. `digest()` is the reference output,
+    /// little-endian, and `intdigest()` its little-endian value.
+    PyMetroHash64, "metrohash64", MetroHash64,
+    new(seed: u64 = 0) => Ok::<_, PyErr>(MetroHash64::new(seed))
+);
+
+checksum_functions!(
+    MetroHash64, metrohash64_digest, metrohash64_hexdigest, metrohash64_intdigest,
+    (seed: u64 = 0) => Ok::<_, PyErr>(MetroHash64::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for MetroHash128.
+    ///
+    /// Same interface as `metrohash64`, with 16-byte output.
+    PyMetroHash128, "metrohash128", MetroHash128,
+    new(seed: u64 = 0) => Ok::<_, PyErr>(MetroHash128::new(seed))
+);
+
+checksum_functions!(
+    MetroHash128, metrohash128_digest, metrohash128_hexdigest, metrohash128_intdigest,
+    (seed: u64 = 0) => Ok::<_, PyErr>(MetroHash128::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for MurmurHash3_x86_32.
+    ///
+    /// Compatible with `mmh3`: `intdigest()` is `mmh3.hash(data, seed,
+    /// signed=False)` and `sintdigest()` the signed value returned by
+    /// default and on the JVM. `digest()` is little-endian, as in the
+    /// reference implementation.
+    PyMurmur3_32, "murmur3_32", Murmur3_32,
+    new(seed: u32 = 0) => Ok::<_, PyErr>(Murmur3_32::new(seed)),
+    methods {
+        /// Returns the hash as a signed 32-bit integer.
+        fn sintdigest(&self, py: Python) -> i32 {
+            self.lock(py).sintdigest()
+        }
+    }
+);
+
+checksum_functions!(
+    Murmur3_32, murmur3_32_digest, murmur3_32_hexdigest, murmur3_32_intdigest,
+    (seed: u32 = 0) => Ok::<_, PyErr>(Murmur3_32::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for MurmurHash3_x64_128.
+    ///
+    /// Compatible with `mmh3`: `intdigest()` is `mmh3.hash128(data, seed)`,
+    /// `sintdigest()` is the same with `signed=True`, and `digest()` is
+    /// `mmh3.hash_bytes(data, seed)`.
+    PyMurmur3_128, "murmur3_128", Murmur3_128,
+    new(seed: u32 = 0) => Ok::<_, PyErr>(Murmur3_128::new(seed)),
+    methods {
+        /// Returns the hash as a signed 128-bit integer.
+        fn sintdigest(&self, py: Python) -> i128 {
+            self.lock(py).sintdigest()
+        }
+    }
+);
+
+checksum_functions!(
+    Murmur3_128, murmur3_128_digest, murmur3_128_hexdigest, murmur3_128_intdigest,
+    (seed: u32 = 0) => Ok::<_, PyErr>(Murmur3_128::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for SipHash-2-4.
+    ///
+    /// `key` must be exactly 16 bytes and comes before `data`.
+    /// `output_size=16` selects the 128-bit variant. `digest()` is
+    /// little-endian as in the reference implementation, and `intdigest()`
+    /// its little-endian value.
+    PySipHash24, "siphash24", SipHash24,
+    new[key: BytesArg](output_size: usize = 8) => SipHash24::new(&key.0, output_size)
+);
+
+checksum_functions!(
+    SipHash24, siphash24_digest, siphash24_hexdigest, siphash24_intdigest,
+    [key: BytesArg](output_size: usize = 8) => SipHash24::new(&key.0, output_size)
+);
+
+checksum_class!(
+    /// Python wrapper for SipHash-1-3.
+    ///
+    /// Same interface as `siphash24`. With an all-zero key, `intdigest()`
+    /// is `hash()` of the bytes in Python 3.11+ started with
+    /// `PYTHONHASHSEED=0`, taken as unsigned.
+    PySipHash13, "siphash13", SipHash13,
+    new[key: BytesArg](output_size: usize = 8) => SipHash13::new(&key.0, output_size)
+);
+
+checksum_functions!(
+    SipHash13, siphash13_digest, siphash13_hexdigest, siphash13_intdigest,
+    [key: BytesArg](output_size: usize = 8) => SipHash13::new(&key.0, output_size)
+);
+
+checksum_class!(
+    /// Python wrapper for SpookyHash V2 with 32-bit output.
+    ///
+    /// Matches the reference `SpookyHash::Hash32()`: the low 32 bits of the
+    /// 128-bit hash with both seed words set to `seed`. `digest()` is
+    /// little-endian.
+    PySpookyHash32, "spookyhash32", SpookyHash32,
+    new(seed: u32 = 0) => Ok::<_, PyErr>(SpookyHash32::new(seed))
+);
+
+checksum_functions!(
+    SpookyHash32, spookyhash32_digest, spookyhash32_hexdigest, spookyhash32_intdigest,
+    (seed: u32 = 0) => Ok::<_, PyErr>(SpookyHash32::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for SpookyHash V2 with 64-bit output.
+    ///
+    /// Matches the reference `SpookyHash::Hash64()`, with a 64-bit seed.
+    PySpookyHash64, "spookyhash64", SpookyHash64,
+    new(seed: u64 = 0) => Ok::<_, PyErr>(SpookyHash64::new(seed))
+);
+
+checksum_functions!(
+    SpookyHash64, spookyhash64_digest, spookyhash64_hexdigest, spookyhash64_intdigest,
+    (seed: u64 = 0) => Ok::<_, PyErr>(SpookyHash64::new(seed))
+);
+
+checksum_class!(
+    /// Python wrapper for SpookyHash V2 with 128-bit output.
+    ///
+    /// Matches the reference `SpookyHash::Hash128()` and `Init(seed1,
+    /// seed2)`. `digest()` is the two result words, little-endian, and
+    /// `intdigest()` its little-endian value (`hash2 << 64 | hash1`).
+    PySpookyHash128, "spookyhash128", SpookyHash128,
+    new(seed1: u64 = 0, seed2: u64 = 0) => Ok::<_, PyErr>(SpookyHash128::new(seed1, seed2))
+);
+
+checksum_functions!(
+    SpookyHash128, spookyhash128_digest, spookyhash128_hexdigest, spookyhash128_intdigest,
+    (seed1: u64 = 0, seed2: u64 = 0) => Ok::<_, PyErr>(SpookyHash128::new(seed1, seed2))
+);
+
+checksum_class!(
+    /// Python wrapper for HighwayHash with 64-bit output.
+    ///
+    /// `key` must be exactly 32 bytes (the reference `HHKey` words,
+    /// little-endian) and comes before `data`. `intdigest()` is the
+    /// reference `HHResult64` and `digest()` its little-endian bytes.
+    PyHighwayHash64, "highwayhash64", HighwayHash64,
+    new[key: BytesArg]() => HighwayHash64::new(&key.0)
+);
+
+checksum_functions!(
+    HighwayHash64, highwayhash64_digest, highwayhash64_hexdigest, highwayhash64_intdigest,
+    [key: BytesArg]() => HighwayHash64::new(&key.0)
+);
+
+checksum_class!(
+    /// Python wrapper for HighwayHash with 128-bit output.
+    ///
+    /// Same interface as `highwayhash64`. `digest()` is the two words of
+    /// the reference `HHResult128`, little-endian, and `intdigest()` its
+    /// little-endian value.
+    PyHighwayHash128, "highwayhash128", HighwayHash128,
+    new[key: BytesArg]() => HighwayHash128::new(&key.0)
+);
+
+checksum_functions!(
+    HighwayHash128, highwayhash128_digest, highwayhash128_hexdigest, highwayhash128_intdigest,
+    [key: BytesArg]() => HighwayHash128::new(&key.0)
+);
+
+checksum_class!(
+    /// Python wrapper for HighwayHash with 256-bit output.
+    ///
+    /// Same interface as `highwayhash64`. `digest()` is the four words of
+    /// the reference `HHResult256`, little-endian, and `intdigest()` its
+    /// little-endian value.
+    PyHighwayHash256, "highwayhash256", HighwayHash256,
+    new[key: BytesArg]() => HighwayHash256::new(&key.0)
+);
+
+checksum_functions!(
+    HighwayHash256, highwayhash256_digest, highwayhash256_hexdigest, highwayhash256_intdigest,
+    [key: BytesArg]() => HighwayHash256::new(&key.0)
+);
+
+/// Converts a 256-bit value to a Python `int`, from its little-endian
+/// bytes.
+impl<'py> IntoPyObject<'py> for U256 {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyInt>> {
+        let bytes = PyBytes::new(py, &self.to_le_bytes());
+        let value = py.get_type::<PyInt>().call_method1("from_bytes", (bytes, "little"))?;
+        Ok(value.downcast_into::<PyInt>()?)
+    }
+}
+
+/// Applies a one-shot 64-bit hash to `data`, returning the value as a
+/// signed integer if `signed` is set.
+fn oneshot64<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    signed: bool,
+    hash: impl FnOnce(&[u8]) -> u64 + Send,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mut value = 0;
+    let out = &mut value;
+    feed_item(py, data, false, move |bytes| {
+        *out = hash(bytes);
+        Ok(())
+    })?;
+    if signed {
+        Ok((value as i64).into_pyobject(py)?.into_any())
+    } else {
+        Ok(value.into_pyobject(py)?.into_any())
+    }
+}
+
+/// FarmHash Fingerprint64 of `data`.
+///
+/// With `signed=True`, the value is BigQuery's `FARM_FINGERPRINT()` (an
+/// INT64); otherwise the same bits as an unsigned integer.
+#[pyfunction]
+#[pyo3(signature = (data, *, signed=false))]
+pub fn farmhash_fingerprint64<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    signed: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    oneshot64(py, data, signed, fingerprint64)
+}
+
+/// CityHash64 (v1.1) of `data`, or CityHash64WithSeed if `seed` is given.
+///
+/// `signed=True` returns the value as a signed 64-bit integer.
+#[pyfunction]
+#[pyo3(name = "cityhash64", signature = (data, seed=None, *, signed=false))]
+pub fn py_cityhash64<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    seed: Option<u64>,
+    signed: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    match seed {
+        None => oneshot64(py, data, signed, cityhash64),
+        Some(seed) => oneshot64(py, data, signed, |bytes| cityhash64_with_seed(bytes, seed)),
+    }
+}
+
+/// komihash (version 5) of `data`.
+#[pyfunction]
+#[pyo3(name = "komihash", signature = (data, seed=0))]
+pub fn py_komihash<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    seed: u64,
+) -> PyResult<Bound<'py, PyAny>> {
+    oneshot64(py, data, false, |bytes| komihash(bytes, seed))
+}
+
+/// wyhash (final version 4) of `data`.
+///
+/// `secret` replaces the default secret with 32 bytes, read as four
+/// little-endian 64-bit words as in the reference `make_secret()` output.
+#[pyfunction]
+#[pyo3(name = "wyhash", signature = (data, seed=0, secret=None))]
+pub fn py_wyhash<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    seed: u64,
+    secret: Option<BytesArg>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let secret = match secret {
+        None => WYHASH_SECRET,
+        Some(secret) => wyhash_secret(&secret.0)?,
+    };
+    oneshot64(py, data, false, |bytes| wyhash_with_secret(bytes, seed, &secret))
+}
+
+/// rapidhash (version 3) of `data`.
+#[pyfunction]
+#[pyo3(name = "rapidhash", signature = (data, seed=0))]
+pub fn py_rapidhash<'py>(
+    py: Python<'py>,
+    data: &Bound<'_, PyAny>,
+    seed: u64,
+) -> PyResult<Bound<'py, PyAny>> {
+    oneshot64(py, data, false, |bytes| rapidhash(bytes, seed))
+}
+
+/// Creates the `noncrypto` submodule and adds it to `parent`.
+///
+/// The submodule is also registered in `sys.modules`, so that
+/// `import RsHash.noncrypto` and `from RsHash.noncrypto import ...` work.
+pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = parent.py();
+    let m = PyModule::new(py, "noncrypto")?;
+    m.gil_used(false)?;
+    m.add_class::<PyXxh32>()?;
+    m.add_class::<PyXxh64>()?;
+    m.add_class::<PyXxh3_64>()?;
+    m.add_class::<PyXxh3_128>()?;
+    m.add_class::<PyAdler32>()?;
+    m.add_function(wrap_pyfunction!(adler32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(adler32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(adler32_intdigest, &m)?)?;
+    m.add_class::<PyCrc16>()?;
+    m.add_function(wrap_pyfunction!(crc16_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc16_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc16_intdigest, &m)?)?;
+    m.add_class::<PyCrc32>()?;
+    m.add_function(wrap_pyfunction!(crc32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc32_intdigest, &m)?)?;
+    m.add_class::<PyCrc32c>()?;
+    m.add_function(wrap_pyfunction!(crc32c_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc32c_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc32c_intdigest, &m)?)?;
+    m.add_class::<PyCrc64>()?;
+    m.add_function(wrap_pyfunction!(crc64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(crc64_intdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(farmhash_fingerprint64, &m)?)?;
+    m.add_function(wrap_pyfunction!(py_cityhash64, &m)?)?;
+    m.add_class::<PyFletcher16>()?;
+    m.add_function(wrap_pyfunction!(fletcher16_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher16_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher16_intdigest, &m)?)?;
+    m.add_class::<PyFletcher32>()?;
+    m.add_function(wrap_pyfunction!(fletcher32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher32_intdigest, &m)?)?;
+    m.add_class::<PyFletcher64>()?;
+    m.add_function(wrap_pyfunction!(fletcher64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher64_intdigest, &m)?)?;
+    m.add_class::<PyFletcher4>()?;
+    m.add_function(wrap_pyfunction!(fletcher4_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher4_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fletcher4_intdigest, &m)?)?;
+    m.add_class::<PyFnv32_1>()?;
+    m.add_function(wrap_pyfunction!(fnv1_32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_32_intdigest, &m)?)?;
+    m.add_class::<PyFnv32_1a>()?;
+    m.add_function(wrap_pyfunction!(fnv1a_32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_32_intdigest, &m)?)?;
+    m.add_class::<PyFnv64_1>()?;
+    m.add_function(wrap_pyfunction!(fnv1_64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_64_intdigest, &m)?)?;
+    m.add_class::<PyFnv64_1a>()?;
+    m.add_function(wrap_pyfunction!(fnv1a_64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_64_intdigest, &m)?)?;
+    m.add_class::<PyFnv128_1>()?;
+    m.add_function(wrap_pyfunction!(fnv1_128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1_128_intdigest, &m)?)?;
+    m.add_class::<PyFnv128_1a>()?;
+    m.add_function(wrap_pyfunction!(fnv1a_128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(fnv1a_128_intdigest, &m)?)?;
+    m.add_class::<PyHighwayHash64>()?;
+    m.add_function(wrap_pyfunction!(highwayhash64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash64_intdigest, &m)?)?;
+    m.add_class::<PyHighwayHash128>()?;
+    m.add_function(wrap_pyfunction!(highwayhash128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash128_intdigest, &m)?)?;
+    m.add_class::<PyHighwayHash256>()?;
+    m.add_function(wrap_pyfunction!(highwayhash256_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash256_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(highwayhash256_intdigest, &m)?)?;
+    m.add_class::<PyKomihash>()?;
+    m.add_function(wrap_pyfunction!(py_komihash, &m)?)?;
+    m.add_class::<PyMetroHash64>()?;
+    m.add_function(wrap_pyfunction!(metrohash64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(metrohash64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(metrohash64_intdigest, &m)?)?;
+    m.add_class::<PyMetroHash128>()?;
+    m.add_function(wrap_pyfunction!(metrohash128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(metrohash128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(metrohash128_intdigest, &m)?)?;
+    m.add_class::<PyMurmur3_32>()?;
+    m.add_function(wrap_pyfunction!(murmur3_32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(murmur3_32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(murmur3_32_intdigest, &m)?)?;
+    m.add_class::<PyMurmur3_128>()?;
+    m.add_function(wrap_pyfunction!(murmur3_128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(murmur3_128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(murmur3_128_intdigest, &m)?)?;
+    m.add_class::<PySipHash24>()?;
+    m.add_function(wrap_pyfunction!(siphash24_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(siphash24_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(siphash24_intdigest, &m)?)?;
+    m.add_class::<PySipHash13>()?;
+    m.add_function(wrap_pyfunction!(siphash13_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(siphash13_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(siphash13_intdigest, &m)?)?;
+    m.add_class::<PySpookyHash32>()?;
+    m.add_function(wrap_pyfunction!(spookyhash32_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash32_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash32_intdigest, &m)?)?;
+    m.add_class::<PySpookyHash64>()?;
+    m.add_function(wrap_pyfunction!(spookyhash64_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash64_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash64_intdigest, &m)?)?;
+    m.add_class::<PySpookyHash128>()?;
+    m.add_function(wrap_pyfunction!(spookyhash128_digest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash128_hexdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(spookyhash128_intdigest, &m)?)?;
+    m.add_function(wrap_pyfunction!(py_wyhash, &m)?)?;
+    m.add_function(wrap_pyfunction!(py_rapidhash, &m)?)?;
+    parent.add_submodule(&m)?;
+    py.import("sys")?.getattr("modules")?.set_item("RsHash.noncrypto", &m)?;
+    Ok(())
+}
@@ -0,0 +1,570 @@
+//! Registry of the hash algorithms exposed to Python.
+//!
+//! Single source of truth for algorithm names: `new()`, its error message
+//! and the `algorithms_available` module attribute are all derived from
+//! [`ALGORITHMS`], so registering an algorithm here makes it available
+//! everywhere at once. Parameterized algorithms whose names embed a size,
+//! such as "sha512/192" or "md6_128", are registered in [`FAMILIES`]. HMAC looks up its
+//! digest algorithm here as well, and works with every entry that has a
+//! core hasher.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
+
+use crate::core::{
+    AsconHash256, Blake2b, Blake2bp, Blake2s, Blake2sp, DynDigest, Gost94, Gost94SBox,
+    Groestl256, Groestl512, Has160, Jh256, Jh512, Keccak256, Keccak512, Kupyna256, Kupyna512,
+    Lsh256, Lsh512, Md2, Md4, Md5, Md6, Ripemd128, Ripemd160, Ripemd256, Ripemd320, Sha1, Sha224,
+    Sha256, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha512, Sha512_224, Skein1024, Skein256,
+    Skein512, Sm3, Tiger, Tiger2, Whirlpool,
+};
+
+use super::{
+    PyAsconHash256, PyAsconXOF128, PyBLAKE2Xb, PyBLAKE2Xs, PyBLAKE3, PyBLAKE2b, PyBLAKE2bp,
+    PyBLAKE2s, PyBLAKE2sp, PyCSHAKE128, PyCSHAKE256, PyGOST94, PyGOST94CryptoPro, PyGroestl256,
+    PyGroestl512, PyHAS160, PyJH256, PyJH512, PyKangarooTwelve, PyKeccak256, PyKeccak512,
+    PyKupyna256, PyKupyna512, PyLSH256, PyLSH512, PyMD2, PyMD4, PyMD5, PyMD6, PyParallelHash128,
+    PyParallelHash256, PyRIPEMD128, PyRIPEMD160, PyRIPEMD256, PyRIPEMD320, PySHA1, PySHA224,
+    PySHA256, PySHA3_224, PySHA3_256, PySHA3_384, PySHA3_512, PySHA512, PySHA512T, PySHA512_224,
+    PySHAKE128, PySHAKE256, PySM3, PySkein1024, PySkein256, PySkein512, PyTiger, PyTiger2,
+    PyTupleHash128, PyTupleHash256, PyTurboSHAKE128, PyTurboSHAKE256, PyWhirlpool,
+};
+
+/// A hash algorithm that can be created by name.
+pub struct Algorithm {
+    /// Canonical hashlib spelling, returned by the `name` attribute.
+    pub name: &'static str,
+    /// Other accepted spellings, in lowercase (lookups are case-insensitive).
+    pub aliases: &'static [&'static str],
+    /// Returns the Python class implementing the algorithm.
+    pub class: for<'py> fn(Python<'py>) -> Bound<'py, PyType>,
+    /// Whether the algorithm may be used for security in strict mode.
+    pub approved: bool,
+    /// Returns a core hasher with the class's default parameters, or `None`
+    /// for extendable-output functions, which have no fixed digest and so
+    /// cannot be used with HMAC.
+    pub hasher: Option<fn() -> Box<dyn DynDigest>>,
+}
+
+/// Every algorithm `new()` can create.
+pub static ALGORITHMS: &[Algorithm] = &[
+    Algorithm {
+        name: "md2",
+        aliases: &["md-2"],
+        class: |py| py.get_type::<PyMD2>(),
+        approved: PyMD2::APPROVED,
+        hasher: Some(|| Box::new(Md2::new())),
+    },
+    Algorithm {
+        name: "md4",
+        aliases: &["md-4"],
+        class: |py| py.get_type::<PyMD4>(),
+        approved: PyMD4::APPROVED,
+        hasher: Some(|| Box::new(Md4::new())),
+    },
+    Algorithm {
+        name: "md5",
+        aliases: &["md-5"],
+        class: |py| py.get_type::<PyMD5>(),
+        approved: PyMD5::APPROVED,
+        hasher: Some(|| Box::new(Md5::new())),
+    },
+    Algorithm {
+        name: "sha1",
+        aliases: &["sha-1", "sha_1"],
+        class: |py| py.get_type::<PySHA1>(),
+        approved: PySHA1::APPROVED,
+        hasher: Some(|| Box::new(Sha1::new())),
+    },
+    Algorithm {
+        name: "sha224",
+        aliases: &["sha-224", "sha_224", "sha2-224", "sha2_224"],
+        class: |py| py.get_type::<PySHA224>(),
+        approved: PySHA224::APPROVED,
+        hasher: Some(|| Box::new(Sha224::new())),
+    },
+    Algorithm {
+        name: "sha256",
+        aliases: &["sha-256", "sha_256", "sha2-256", "sha2_256"],
+        class: |py| py.get_type::<PySHA256>(),
+        approved: PySHA256::APPROVED,
+        hasher: Some(|| Box::new(Sha256::new())),
+    },
+    Algorithm {
+        name: "sha512",
+        aliases: &["sha-512", "sha_512", "sha2-512", "sha2_512"],
+        class: |py| py.get_type::<PySHA512>(),
+        approved: PySHA512::APPROVED,
+        hasher: Some(|| Box::new(Sha512::new())),
+    },
+    Algorithm {
+        name: "sha512_224",
+        aliases: &["sha512/224", "sha-512/224", "sha2-512/224", "sha512-224"],
+        class: |py| py.get_type::<PySHA512_224>(),
+        approved: PySHA512_224::APPROVED,
+        hasher: Some(|| Box::new(Sha512_224::new())),
+    },
+    Algorithm {
+        name: "sha3_224",
+        aliases: &["sha3-224"],
+        class: |py| py.get_type::<PySHA3_224>(),
+        approved: PySHA3_224::APPROVED,
+        hasher: Some(|| Box::new(Sha3_224::new())),
+    },
+    Algorithm {
+        name: "sha3_256",
+        aliases: &["sha3-256"],
+        class: |py| py.get_type::<PySHA3_256>(),
+        approved: PySHA3_256::APPROVED,
+        hasher: Some(|| Box::new(Sha3_256::new())),
+    },
+    Algorithm {
+        name: "sha3_384",
+        aliases: &["sha3-384"],
+        class: |py| py.get_type::<PySHA3_384>(),
+        approved: PySHA3_384::APPROVED,
+        hasher: Some(|| Box::new(Sha3_384::new())),
+    },
+    Algorithm {
+        name: "sha3_512",
+        aliases: &["sha3-512"],
+        class: |py| py.get_type::<PySHA3_512>(),
+        approved: PySHA3_512::APPROVED,
+        hasher: Some(|| Box::new(Sha3_512::new())),
+    },
+    Algorithm {
+        name: "shake_128",
+        aliases: &["shake128", "shake-128"],
+        class: |py| py.get_type::<PySHAKE128>(),
+        approved: PySHAKE128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "shake_256",
+        aliases: &["shake256", "shake-256"],
+        class: |py| py.get_type::<PySHAKE256>(),
+        approved: PySHAKE256::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "cshake_128",
+        aliases: &["cshake128", "cshake-128"],
+        class: |py| py.get_type::<PyCSHAKE128>(),
+        approved: PyCSHAKE128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "cshake_256",
+        aliases: &["cshake256", "cshake-256"],
+        class: |py| py.get_type::<PyCSHAKE256>(),
+        approved: PyCSHAKE256::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "tuplehash_128",
+        aliases: &["tuplehash128", "tuplehash-128"],
+        class: |py| py.get_type::<PyTupleHash128>(),
+        approved: PyTupleHash128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "tuplehash_256",
+        aliases: &["tuplehash256", "tuplehash-256"],
+        class: |py| py.get_type::<PyTupleHash256>(),
+        approved: PyTupleHash256::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "parallelhash_128",
+        aliases: &["parallelhash128", "parallelhash-128"],
+        class: |py| py.get_type::<PyParallelHash128>(),
+        approved: PyParallelHash128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "parallelhash_256",
+        aliases: &["parallelhash256", "parallelhash-256"],
+        class: |py| py.get_type::<PyParallelHash256>(),
+        approved: PyParallelHash256::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "turboshake_128",
+        aliases: &["turboshake128", "turboshake-128"],
+        class: |py| py.get_type::<PyTurboSHAKE128>(),
+        approved: PyTurboSHAKE128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "turboshake_256",
+        aliases: &["turboshake256", "turboshake-256"],
+        class: |py| py.get_type::<PyTurboSHAKE256>(),
+        approved: PyTurboSHAKE256::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "k12",
+        aliases: &["kangarootwelve", "kangaroo12"],
+        class: |py| py.get_type::<PyKangarooTwelve>(),
+        approved: PyKangarooTwelve::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "blake2b",
+        aliases: &["blake2b512", "blake2b-512"],
+        class: |py| py.get_type::<PyBLAKE2b>(),
+        approved: PyBLAKE2b::APPROVED,
+        hasher: Some(|| Box::new(Blake2b::new())),
+    },
+    Algorithm {
+        name: "blake2s",
+        aliases: &["blake2s256", "blake2s-256"],
+        class: |py| py.get_type::<PyBLAKE2s>(),
+        approved: PyBLAKE2s::APPROVED,
+        hasher: Some(|| Box::new(Blake2s::new())),
+    },
+    Algorithm {
+        name: "blake2bp",
+        aliases: &["blake2bp512", "blake2bp-512"],
+        class: |py| py.get_type::<PyBLAKE2bp>(),
+        approved: PyBLAKE2bp::APPROVED,
+        hasher: Some(|| Box::new(Blake2bp::new())),
+    },
+    Algorithm {
+        name: "blake2sp",
+        aliases: &["blake2sp256", "blake2sp-256"],
+        class: |py| py.get_type::<PyBLAKE2sp>(),
+        approved: PyBLAKE2sp::APPROVED,
+        hasher: Some(|| Box::new(Blake2sp::new())),
+    },
+    Algorithm {
+        name: "blake2xb",
+        aliases: &["blake2x-b"],
+        class: |py| py.get_type::<PyBLAKE2Xb>(),
+        approved: PyBLAKE2Xb::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "blake2xs",
+        aliases: &["blake2x-s"],
+        class: |py| py.get_type::<PyBLAKE2Xs>(),
+        approved: PyBLAKE2Xs::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "blake3",
+        aliases: &["blake3-256", "blake3_256"],
+        class: |py| py.get_type::<PyBLAKE3>(),
+        approved: PyBLAKE3::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "keccak_256",
+        aliases: &["keccak256", "keccak-256"],
+        class: |py| py.get_type::<PyKeccak256>(),
+        approved: PyKeccak256::APPROVED,
+        hasher: Some(|| Box::new(Keccak256::new())),
+    },
+    Algorithm {
+        name: "keccak_512",
+        aliases: &["keccak512", "keccak-512"],
+        class: |py| py.get_type::<PyKeccak512>(),
+        approved: PyKeccak512::APPROVED,
+        hasher: Some(|| Box::new(Keccak512::new())),
+    },
+    Algorithm {
+        name: "ripemd128",
+        aliases: &["ripemd-128", "ripemd_128", "rmd128"],
+        class: |py| py.get_type::<PyRIPEMD128>(),
+        approved: PyRIPEMD128::APPROVED,
+        hasher: Some(|| Box::new(Ripemd128::new())),
+    },
+    Algorithm {
+        name: "ripemd160",
+        aliases: &["ripemd-160", "ripemd_160", "rmd160"],
+        class: |py| py.get_type::<PyRIPEMD160>(),
+        approved: PyRIPEMD160::APPROVED,
+        hasher: Some(|| Box::new(Ripemd160::new())),
+    },
+    Algorithm {
+        name: "ripemd256",
+        aliases: &["ripemd-256", "ripemd_256", "rmd256"],
+        class: |py| py.get_type::<PyRIPEMD256>(),
+        approved: PyRIPEMD256::APPROVED,
+        hasher: Some(|| Box::new(Ripemd256::new())),
+    },
+    Algorithm {
+        name: "ripemd320",
+        aliases: &["ripemd-320", "ripemd_320", "rmd320"],
+        class: |py| py.get_type::<PyRIPEMD320>(),
+        approved: PyRIPEMD320::APPROVED,
+        hasher: Some(|| Box::new(Ripemd320::new())),
+    },
+    Algorithm {
+        name: "sm3",
+        aliases: &["sm-3"],
+        class: |py| py.get_type::<PySM3>(),
+        approved: PySM3::APPROVED,
+        hasher: Some(|| Box::new(Sm3::new())),
+    },
+    Algorithm {
+        name: "whirlpool",
+        aliases: &["whirlpool-512", "whirlpool512"],
+        class: |py| py.get_type::<PyWhirlpool>(),
+        approved: PyWhirlpool::APPROVED,
+        hasher: Some(|| Box::new(Whirlpool::new())),
+    },
+    Algorithm {
+        name: "tiger",
+        aliases: &["tiger192", "tiger-192", "tiger/192", "tiger192,3"],
+        class: |py| py.get_type::<PyTiger>(),
+        approved: PyTiger::APPROVED,
+        hasher: Some(|| Box::new(Tiger::new())),
+    },
+    Algorithm {
+        name: "tiger2",
+        aliases: &["tiger2-192", "tiger2/192"],
+        class: |py| py.get_type::<PyTiger2>(),
+        approved: PyTiger2::APPROVED,
+        hasher: Some(|| Box::new(Tiger2::new())),
+    },
+    Algorithm {
+        name: "skein256",
+        aliases: &["skein-256", "skein_256"],
+        class: |py| py.get_type::<PySkein256>(),
+        approved: PySkein256::APPROVED,
+        hasher: Some(|| Box::new(Skein256::new())),
+    },
+    Algorithm {
+        name: "skein512",
+        aliases: &["skein-512", "skein_512"],
+        class: |py| py.get_type::<PySkein512>(),
+        approved: PySkein512::APPROVED,
+        hasher: Some(|| Box::new(Skein512::new())),
+    },
+    Algorithm {
+        name: "skein1024",
+        aliases: &["skein-1024", "skein_1024"],
+        class: |py| py.get_type::<PySkein1024>(),
+        approved: PySkein1024::APPROVED,
+        hasher: Some(|| Box::new(Skein1024::new())),
+    },
+    Algorithm {
+        name: "groestl256",
+        aliases: &["groestl-256", "groestl_256", "grøstl256", "grøstl-256"],
+        class: |py| py.get_type::<PyGroestl256>(),
+        approved: PyGroestl256::APPROVED,
+        hasher: Some(|| Box::new(Groestl256::new())),
+    },
+    Algorithm {
+        name: "groestl512",
+        aliases: &["groestl-512", "groestl_512", "grøstl512", "grøstl-512"],
+        class: |py| py.get_type::<PyGroestl512>(),
+        approved: PyGroestl512::APPROVED,
+        hasher: Some(|| Box::new(Groestl512::new())),
+    },
+    Algorithm {
+        name: "jh256",
+        aliases: &["jh-256", "jh_256"],
+        class: |py| py.get_type::<PyJH256>(),
+        approved: PyJH256::APPROVED,
+        hasher: Some(|| Box::new(Jh256::new())),
+    },
+    Algorithm {
+        name: "jh512",
+        aliases: &["jh-512", "jh_512"],
+        class: |py| py.get_type::<PyJH512>(),
+        approved: PyJH512::APPROVED,
+        hasher: Some(|| Box::new(Jh512::new())),
+    },
+    Algorithm {
+        name: "ascon_hash256",
+        aliases: &["ascon-hash256", "asconhash256", "ascon-hash-256"],
+        class: |py| py.get_type::<PyAsconHash256>(),
+        approved: PyAsconHash256::APPROVED,
+        hasher: Some(|| Box::new(AsconHash256::new())),
+    },
+    Algorithm {
+        name: "ascon_xof128",
+        aliases: &["ascon-xof128", "asconxof128", "ascon-xof-128"],
+        class: |py| py.get_type::<PyAsconXOF128>(),
+        approved: PyAsconXOF128::APPROVED,
+        hasher: None,
+    },
+    Algorithm {
+        name: "lsh256",
+        aliases: &["lsh-256", "lsh_256", "lsh-256-256"],
+        class: |py| py.get_type::<PyLSH256>(),
+        approved: PyLSH256::APPROVED,
+        hasher: Some(|| Box::new(Lsh256::new())),
+    },
+    Algorithm {
+        name: "lsh512",
+        aliases: &["lsh-512", "lsh_512", "lsh-512-512"],
+        class: |py| py.get_type::<PyLSH512>(),
+        approved: PyLSH512::APPROVED,
+        hasher: Some(|| Box::new(Lsh512::new())),
+    },
+    Algorithm {
+        name: "kupyna256",
+        aliases: &["kupyna-256", "kupyna_256", "dstu7564-256"],
+        class: |py| py.get_type::<PyKupyna256>(),
+        approved: PyKupyna256::APPROVED,
+        hasher: Some(|| Box::new(Kupyna256::new())),
+    },
+    Algorithm {
+        name: "kupyna512",
+        aliases: &["kupyna-512", "kupyna_512", "dstu7564-512"],
+        class: |py| py.get_type::<PyKupyna512>(),
+        approved: PyKupyna512::APPROVED,
+        hasher: Some(|| Box::new(Kupyna512::new())),
+    },
+    Algorithm {
+        name: "gost94",
+        aliases: &["gost", "gost-94", "gostr341194", "gost94_test"],
+        class: |py| py.get_type::<PyGOST94>(),
+        approved: PyGOST94::APPROVED,
+        hasher: Some(|| Box::new(Gost94::new())),
+    },
+    Algorithm {
+        name: "gost94_cryptopro",
+        aliases: &["gost94-cryptopro", "gost-94-cryptopro", "gost94cryptopro"],
+        class: |py| py.get_type::<PyGOST94CryptoPro>(),
+        approved: PyGOST94CryptoPro::APPROVED,
+        hasher: Some(|| Box::new(Gost94::with_sbox(Gost94SBox::CryptoPro))),
+    },
+    Algorithm {
+        name: "has160",
+        aliases: &["has-160", "has_160"],
+        class: |py| py.get_type::<PyHAS160>(),
+        approved: PyHAS160::APPROVED,
+        hasher: Some(|| Box::new(Has160::new())),
+    },
+    Algorithm {
+        name: "md6_256",
+        aliases: &["md6", "md6-256"],
+        class: |py| py.get_type::<PyMD6>(),
+        approved: PyMD6::APPROVED,
+        hasher: Some(|| Box::new(Md6::new())),
+    },
+];
+
+/// A family of algorithms selected by a size embedded in the name.
+pub struct Family {
+    /// How the family is shown in error messages, e.g. "sha512/<t>".
+    pub pattern: &'static str,
+    /// Name prefixes, in lowercase, that are directly followed by the size.
+    pub prefixes: &'static [&'static str],
+    /// Creates a hash object of the given size, forwarding `data` and
+    /// `kwargs` to the class.
+    pub create: FamilyConstructor,
+}
+
+/// Constructor of a [`Family`] member: takes the size embedded in the name,
+/// then `data` and `kwargs` as given to `new()`.
+pub type FamilyConstructor = for<'py> fn(
+    Python<'py>,
+    usize,
+    Option<&Bound<'py, PyAny>>,
+    Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>>;
+
+/// Every algorithm family `new()` can create.
+pub static FAMILIES: &[Family] = &[
+    Family {
+        pattern: "sha512/<t>",
+        prefixes: &["sha512/", "sha-512/", "sha2-512/", "sha512_", "sha512-"],
+        create: |py, t, data, kwargs| py.get_type::<PySHA512T>().call((t, data), kwargs),
+    },
+    Family {
+        pattern: "md6_<bits>",
+        prefixes: &["md6_", "md6-"],
+        create: create_md6,
+    },
+];
+
+/// Creates an MD6 hash object with a `bits`-bit digest, the size that
+/// `MD6.name` embeds.
+///
+/// # Errors
+/// Returns `ValueError` unless `bits` is a multiple of 8, and `TypeError`
+/// if `kwargs` also sets `digest_size`.
+fn create_md6<'py>(
+    py: Python<'py>,
+    bits: usize,
+    data: Option<&Bound<'py, PyAny>>,
+    kwargs: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if !bits.is_multiple_of(8) {
+        return Err(PyValueError::new_err(format!(
+            "md6_<bits> takes a whole number of bytes, got {} bits",
+            bits
+        )));
+    }
+    let kwargs = match kwargs {
+        Some(kwargs) => kwargs.copy()?,
+        None => PyDict::new(py),
+    };
+    if kwargs.contains("digest_size")? {
+        return Err(PyTypeError::new_err("digest_size is already given by the name"));
+    }
+    kwargs.set_item("digest_size", bits / 8)?;
+    py.get_type::<PyMD6>().call((data,), Some(&kwargs))
+}
+
+/// Finds an algorithm by canonical name or alias, ignoring case.
+pub fn lookup(name: &str) -> Option<&'static Algorithm> {
+    let lower = name.to_ascii_lowercase();
+    ALGORITHMS
+        .iter()
+        .find(|algo| algo.name == lower || algo.aliases.contains(&lower.as_str()))
+}
+
+/// Canonical names of all registered algorithms, sorted.
+pub fn names() -> Vec<&'static str> {
+    let mut names: Vec<&str> = ALGORITHMS.iter().map(|algo| algo.name).collect();
+    names.sort_unstable();
+    names
+}
+
+/// Finds an algorithm family by name, returning it with the embedded size.
+pub fn lookup_family(name: &str) -> Option<(&'static Family, usize)> {
+    let lower = name.to_ascii_lowercase();
+    FAMILIES.iter().find_map(|family| {
+        family.prefixes.iter().find_map(|prefix| {
+            let size = lower.strip_prefix(prefix)?;
+            if !size.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((family, size.parse().ok()?))
+        })
+    })
+}
+
+/// Creates a hash object by name, forwarding `data` and `kwargs` to the
+/// algorithm's class.
+///
+/// # Errors
+/// Returns `ValueError: unsupported hash type <name>`, followed by the list
+/// of supported algorithms, and propagates errors from the constructor.
+pub fn create<'py>(
+    py: Python<'py>,
+    name: &str,
+    data: Option<&Bound<'py, PyAny>>,
+    kwargs: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    // Going through the class keeps keyword validation in one place
+    if let Some(algo) = lookup(name) {
+        return (algo.class)(py).call((data,), kwargs);
+    }
+    if let Some((family, size)) = lookup_family(name) {
+        return (family.create)(py, size, data, kwargs);
+    }
+    let patterns = FAMILIES.iter().map(|family| family.pattern);
+    Err(PyValueError::new_err(format!(
+        "unsupported hash type {} (supported: {})",
+        name,
+        names().into_iter().chain(patterns).collect::<Vec<_>>().join(", ")
+    )))
+}
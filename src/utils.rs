@@ -23,6 +23,150 @@ pub fn u64_to_bytes_be(value: u64) -> [u8; 8] {
     value.to_be_bytes()
 }
 
+/// Lowercase hex digit pairs for every byte value.
+static HEX_TABLE: [[u8; 2]; 256] = {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut table = [[0u8; 2]; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = [DIGITS[i >> 4], DIGITS[i & 0x0f]];
+        i += 1;
+    }
+    table
+};
+
+/// Encodes bytes as lowercase hexadecimal into `out`, returning it as `&str`.
+///
+/// # Panics
+///
+/// Panics if `out` is not exactly twice as long as `bytes`.
+pub fn hex_into<'a>(bytes: &[u8], out: &'a mut [u8]) -> &'a str {
+    assert_eq!(out.len(), bytes.len() * 2, "hex output buffer has the wrong size");
+    for (pair, &byte) in out.chunks_exact_mut(2).zip(bytes) {
+        pair.copy_from_slice(&HEX_TABLE[byte as usize]);
+    }
+    // Only ASCII hex digits were written
+    std::str::from_utf8(out).expect("hex digits are valid UTF-8")
+}
+
+/// Encodes bytes as a lowercase hexadecimal string.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut out = vec![0u8; bytes.len() * 2];
+    hex_into(bytes, &mut out);
+    String::from_utf8(out).expect("hex digits are valid UTF-8")
+}
+
+/// Decodes hexadecimal digits of either case, returning `None` for an odd
+/// length or any other character.
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    text.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+/// Compares two byte strings in time independent of their contents.
+///
+/// Every byte is visited whatever the position of the first difference.
+/// When the lengths differ, `a` is compared with itself so the running time
+/// still only depends on `a.len()`, and the result is `false`.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let same_len = a.len() == b.len();
+    let other = if same_len { b } else { a };
+    let diff = a.iter().zip(other).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    // Keeps the compiler from turning the fold into an early-exit loop
+    std::hint::black_box(diff) == 0 && same_len
+}
+
+/// Parses a decimal number without sign or leading zeros, as in the cost
+/// fields of PHC strings.
+pub fn parse_decimal(text: &str) -> Option<u32> {
+    if text.is_empty() || (text.len() > 1 && text.starts_with('0')) {
+        return None;
+    }
+    if !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Standard Base64 alphabet, as used by PHC password hash strings.
+pub const B64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as standard Base64 without `=` padding (PHC "B64").
+pub fn b64_encode(bytes: &[u8]) -> String {
+    b64_encode_with(bytes, B64_ALPHABET)
+}
+
+/// Decodes unpadded standard Base64 (PHC "B64"), see [`b64_decode_with`].
+pub fn b64_decode(text: &str) -> Option<Vec<u8>> {
+    b64_decode_with(text, B64_ALPHABET)
+}
+
+/// Encodes bytes as unpadded Base64 over `alphabet`, most significant
+/// bits first.
+pub fn b64_encode_with(bytes: &[u8], alphabet: &[u8; 64]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().fold(0u32, |acc, &b| acc << 8 | b as u32);
+        let group = group << (8 * (3 - chunk.len()));
+        for i in 0..=chunk.len() {
+            out.push(alphabet[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decodes unpadded Base64 over `alphabet`, most significant bits first.
+///
+/// Returns `None` on padding, characters outside the alphabet, an
+/// impossible length, or unused trailing bits that are not zero, so every
+/// byte string has exactly one accepted encoding.
+pub fn b64_decode_with(text: &str, alphabet: &[u8; 64]) -> Option<Vec<u8>> {
+    if text.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for &c in text.as_bytes() {
+        let value = alphabet.iter().position(|&a| a == c)? as u32;
+        acc = (acc << 6 | value) & 0xffff;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    (acc & ((1 << bits) - 1) == 0).then_some(out)
+}
+
+/// RFC 4648 Base32 alphabet.
+pub const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes bytes as Base32 (RFC 4648) without `=` padding, as one-time
+/// password secrets are written in `otpauth://` URIs.
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut acc, mut bits) = (0u16, 0u32);
+    for &byte in bytes {
+        acc = acc << 8 | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[(acc >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[(acc << (5 - bits) & 0x1f) as usize] as char);
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,6 +179,101 @@ mod tests {
         assert_eq!(bytes_to_u32_be(&bytes), value);
     }
 
+    #[test]
+    fn test_to_hex() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0x00, 0x0f, 0xa0, 0xff]), "000fa0ff");
+
+        let all: Vec<u8> = (0..=255).collect();
+        let expected: String = all.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(to_hex(&all), expected);
+    }
+
+    #[test]
+    fn test_hex_into() {
+        let mut out = [0u8; 6];
+        assert_eq!(hex_into(&[0xde, 0xad, 0x01], &mut out), "dead01");
+    }
+
+    #[test]
+    fn test_from_hex() {
+        assert_eq!(from_hex("00ff7F").unwrap(), [0x00, 0xff, 0x7f]);
+        assert!(from_hex("").unwrap().is_empty());
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes);
+        assert_eq!(from_hex(&to_hex(&bytes).to_uppercase()).unwrap(), bytes);
+        for bad in ["0", "abc", "0g", "+1", " 01", "é0"] {
+            assert!(from_hex(bad).is_none(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        let tag = [0x5au8; 32];
+        assert!(constant_time_eq(&tag, &tag));
+        assert!(constant_time_eq(&[], &[]));
+        let mut first = tag;
+        first[0] ^= 1;
+        let mut last = tag;
+        last[31] ^= 0x80;
+        assert!(!constant_time_eq(&tag, &first));
+        assert!(!constant_time_eq(&tag, &last));
+        assert!(!constant_time_eq(&tag, &tag[..31]));
+        assert!(!constant_time_eq(&tag[..0], &tag));
+    }
+
+    #[test]
+    fn test_b64() {
+        // RFC 4648, section 10, without padding
+        let cases = ["", "Zg", "Zm8", "Zm9v", "Zm9vYg", "Zm9vYmE", "Zm9vYmFy"];
+        for (len, encoded) in cases.iter().enumerate() {
+            assert_eq!(b64_encode(&b"foobar"[..len]), *encoded);
+            assert_eq!(b64_decode(encoded).unwrap(), b"foobar"[..len]);
+        }
+        let all: Vec<u8> = (0..=255).collect();
+        assert_eq!(b64_decode(&b64_encode(&all)).unwrap(), all);
+        for bad in ["Zg==", "Z", "Zh", "Zm9v!", "Zm9vYmF"] {
+            assert_eq!(b64_decode(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_base32() {
+        // RFC 4648, section 10, without padding
+        let cases = ["", "MY", "MZXQ", "MZXW6", "MZXW6YQ", "MZXW6YTB", "MZXW6YTBOI"];
+        for (len, encoded) in cases.iter().enumerate() {
+            assert_eq!(base32_encode(&b"foobar"[..len]), *encoded);
+        }
+        assert_eq!(base32_encode(b"12345678901234567890"), "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+    }
+
+    #[test]
+    #[ignore = "micro-benchmark, run with `cargo test --release -- --ignored`"]
+    fn bench_to_hex() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let digest = [0xa5u8; 64];
+        let rounds = 200_000;
+
+        let start = Instant::now();
+        for _ in 0..rounds {
+            let hex: String = black_box(&digest).iter().map(|b| format!("{:02x}", b)).collect();
+            black_box(hex);
+        }
+        let format_time = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..rounds {
+            black_box(to_hex(black_box(&digest)));
+        }
+        let table_time = start.elapsed();
+
+        let speedup = format_time.as_secs_f64() / table_time.as_secs_f64();
+        println!("format!: {:?}, table: {:?}, speedup: {:.1}x", format_time, table_time, speedup);
+        assert!(speedup >= 5.0, "expected at least a 5x speedup, got {:.1}x", speedup);
+    }
+
     #[test]
     fn test_u64_conversion() {
         let value: u64 = 0x123456789ABCDEF0;